@@ -99,6 +99,15 @@ impl Default for BrowserConfig {
     }
 }
 
+impl BrowserConfig {
+    /// Launch the internal browser with a persistent profile directory, or a
+    /// throwaway temp profile when `dir` is `None`.
+    pub fn use_profile_dir(&mut self, dir: Option<PathBuf>) {
+        self.persist_profile = dir.is_some();
+        self.user_data_dir = dir;
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ViewportConfig {
     pub width: u32,
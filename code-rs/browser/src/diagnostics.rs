@@ -0,0 +1,293 @@
+//! Console problems and failed network requests observed on a page.
+//!
+//! The page records every console entry already; this module adds a bounded
+//! log of requests that failed (transport errors, blocked requests and HTTP
+//! 4xx/5xx responses) and the helpers that pick out what went wrong after a
+//! given moment, so an action can report the errors it caused.
+
+use chromiumoxide::cdp::js_protocol::runtime::RemoteObject;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+const MAX_PENDING_REQUESTS: usize = 1000;
+const MAX_FAILURES: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NetworkFailure {
+    pub ts_unix_ms: i128,
+    pub method: String,
+    pub url: String,
+    pub resource_type: Option<String>,
+    /// HTTP status for error responses; `None` when the request never got one.
+    pub status: Option<i64>,
+    pub error: String,
+    /// `WWW-Authenticate` header of a 401 response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_challenge: Option<String>,
+}
+
+impl NetworkFailure {
+    /// One-line description, e.g. `GET https://x.test/api (Fetch) → 500 Internal Server Error`.
+    pub fn summary(&self) -> String {
+        let kind = self
+            .resource_type
+            .as_deref()
+            .map(|kind| format!(" ({kind})"))
+            .unwrap_or_default();
+        let outcome = match self.status {
+            Some(status) if self.error.is_empty() => status.to_string(),
+            Some(status) => format!("{status} {}", self.error),
+            None => self.error.clone(),
+        };
+        format!("{} {}{kind} → {outcome}", self.method, self.url)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConsoleProblem {
+    pub level: String,
+    pub message: String,
+}
+
+/// Errors and warnings a page produced after some point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PageDiagnostics {
+    pub console: Vec<ConsoleProblem>,
+    pub network: Vec<NetworkFailure>,
+}
+
+impl PageDiagnostics {
+    pub fn is_empty(&self) -> bool {
+        self.console.is_empty() && self.network.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NetworkLog {
+    pending: HashMap<String, (String, String, Option<String>)>,
+    failures: VecDeque<NetworkFailure>,
+}
+
+impl NetworkLog {
+    pub fn request_started(
+        &mut self,
+        request_id: &str,
+        method: &str,
+        url: &str,
+        resource_type: Option<String>,
+    ) {
+        if url.starts_with("data:") || url.starts_with("blob:") {
+            return;
+        }
+        if self.pending.len() >= MAX_PENDING_REQUESTS {
+            // Requests that never finish (long polls, aborted pages) would
+            // otherwise accumulate forever.
+            self.pending.clear();
+        }
+        self.pending.insert(
+            request_id.to_string(),
+            (method.to_string(), url.to_string(), resource_type),
+        );
+    }
+
+    pub fn response(
+        &mut self,
+        request_id: &str,
+        status: i64,
+        status_text: &str,
+        auth_challenge: Option<String>,
+        now_ms: i128,
+    ) {
+        let Some((method, url, resource_type)) = self.pending.remove(request_id) else {
+            return;
+        };
+        if status >= 400 {
+            self.push(NetworkFailure {
+                ts_unix_ms: now_ms,
+                method,
+                url,
+                resource_type,
+                status: Some(status),
+                error: status_text.to_string(),
+                auth_challenge: auth_challenge.filter(|_| status == 401),
+            });
+        }
+    }
+
+    pub fn failed(
+        &mut self,
+        request_id: &str,
+        error_text: &str,
+        canceled: bool,
+        blocked_reason: Option<String>,
+        now_ms: i128,
+    ) {
+        let Some((method, url, resource_type)) = self.pending.remove(request_id) else {
+            return;
+        };
+        // Navigations and aborted fetches cancel in-flight requests all the
+        // time; those are not errors worth reporting.
+        if canceled {
+            return;
+        }
+        let error = match blocked_reason {
+            Some(reason) => format!("blocked ({reason})"),
+            None => error_text.to_string(),
+        };
+        self.push(NetworkFailure {
+            ts_unix_ms: now_ms,
+            method,
+            url,
+            resource_type,
+            status: None,
+            error,
+            auth_challenge: None,
+        });
+    }
+
+    fn push(&mut self, failure: NetworkFailure) {
+        self.failures.push_back(failure);
+        if self.failures.len() > MAX_FAILURES {
+            self.failures.pop_front();
+        }
+    }
+
+    /// The most recent `limit` failures, oldest first; all of them when `limit` is 0.
+    pub fn tail(&self, limit: usize) -> Vec<NetworkFailure> {
+        let skip = if limit == 0 {
+            0
+        } else {
+            self.failures.len().saturating_sub(limit)
+        };
+        self.failures.iter().skip(skip).cloned().collect()
+    }
+
+    /// Realm of the most recent HTTP Basic challenge for a document on
+    /// `origin` (empty when the server named none).
+    pub fn basic_auth_realm(&self, origin: &str) -> Option<String> {
+        self.failures.iter().rev().find_map(|failure| {
+            let challenge = failure.auth_challenge.as_deref()?;
+            if failure.resource_type.as_deref() != Some("Document")
+                || !challenge.trim_start().to_ascii_lowercase().starts_with("basic")
+                || crate::login::origin_of(&failure.url).as_deref() != Some(origin)
+            {
+                return None;
+            }
+            let realm = challenge
+                .split_once("realm=")
+                .map(|(_, rest)| rest.trim_start_matches('"'))
+                .and_then(|rest| rest.split('"').next())
+                .unwrap_or_default();
+            Some(realm.to_string())
+        })
+    }
+
+    pub fn since(&self, since_ms: i128) -> Vec<NetworkFailure> {
+        self.failures
+            .iter()
+            .filter(|failure| failure.ts_unix_ms >= since_ms)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Console entries at error or warning level recorded at or after `since_ms`.
+pub fn console_problems_since(entries: &[serde_json::Value], since_ms: i128) -> Vec<ConsoleProblem> {
+    entries
+        .iter()
+        .filter(|entry| {
+            entry
+                .get("ts_unix_ms")
+                .and_then(serde_json::Value::as_i64)
+                .is_some_and(|ts| i128::from(ts) >= since_ms)
+        })
+        .filter_map(|entry| {
+            let level = entry.get("level").and_then(serde_json::Value::as_str)?;
+            let level = match level.to_ascii_lowercase().as_str() {
+                "error" | "assert" => "error",
+                "warning" | "warn" => "warning",
+                _ => return None,
+            };
+            let message = entry
+                .get("message")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            Some(ConsoleProblem {
+                level: level.to_string(),
+                message: message.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Render console call arguments the way DevTools prints them: strings as-is,
+/// other values as JSON, objects by their description.
+pub fn console_args_text(args: &[RemoteObject]) -> String {
+    args.iter()
+        .map(|arg| match (&arg.value, &arg.description) {
+            (Some(serde_json::Value::String(text)), _) => text.clone(),
+            (Some(value), _) => value.to_string(),
+            (None, Some(description)) => description.clone(),
+            (None, None) => format!("{:?}", arg.r#type).to_ascii_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn now_unix_ms() -> i128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i128)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn records_failures_and_basic_challenges_but_not_cancellations() {
+        let mut log = NetworkLog::default();
+        log.request_started("1", "GET", "https://app.test/", Some("Document".to_string()));
+        log.request_started("2", "POST", "https://app.test/api", Some("Fetch".to_string()));
+        log.request_started("3", "GET", "https://cdn.test/a.js", Some("Script".to_string()));
+        log.request_started("4", "GET", "https://app.test/poll", None);
+        log.request_started("5", "GET", "data:image/png;base64,AAAA", None);
+        log.response("1", 200, "OK", None, 10);
+        log.response("2", 500, "Internal Server Error", None, 20);
+        log.failed("3", "net::ERR_NAME_NOT_RESOLVED", false, None, 30);
+        log.failed("4", "net::ERR_ABORTED", true, None, 40);
+
+        let failures = log.tail(0);
+        assert_eq!(
+            failures.iter().map(NetworkFailure::summary).collect::<Vec<_>>(),
+            vec![
+                "POST https://app.test/api (Fetch) → 500 Internal Server Error".to_string(),
+                "GET https://cdn.test/a.js (Script) → net::ERR_NAME_NOT_RESOLVED".to_string(),
+            ]
+        );
+        assert_eq!(log.since(25).len(), 1);
+        assert_eq!(log.tail(1)[0].url, "https://cdn.test/a.js");
+
+        log.request_started("6", "GET", "https://docs.test/private/", Some("Document".to_string()));
+        log.response("6", 401, "Unauthorized", Some("Basic realm=\"Team docs\"".to_string()), 50);
+        assert_eq!(log.basic_auth_realm("https://docs.test").as_deref(), Some("Team docs"));
+        assert_eq!(log.basic_auth_realm("https://app.test"), None);
+
+        let console = vec![
+            json!({"ts_unix_ms": 5, "level": "Error", "message": "too early"}),
+            json!({"ts_unix_ms": 15, "level": "Log", "message": "hello"}),
+            json!({"ts_unix_ms": 15, "level": "Warning", "message": "deprecated"}),
+            json!({"ts_unix_ms": 16, "level": "Error", "message": "boom"}),
+        ];
+        assert_eq!(
+            console_problems_since(&console, 10),
+            vec![
+                ConsoleProblem { level: "warning".to_string(), message: "deprecated".to_string() },
+                ConsoleProblem { level: "error".to_string(), message: "boom".to_string() },
+            ]
+        );
+    }
+}
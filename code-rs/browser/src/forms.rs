@@ -0,0 +1,146 @@
+//! Form interaction: fill inputs, pick `<select>` options and submit forms.
+//!
+//! Fields are addressed by CSS selector or by the text a person would use to
+//! describe them (their `<label>`, `aria-label`, placeholder or name), so the
+//! model does not have to work out coordinates for every field of a form.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::BrowserError;
+use crate::Result;
+
+const FORM_ACTION_JS: &str = include_str!("js/form_action.js");
+
+/// How to locate the element a form action applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormTarget {
+    Selector(String),
+    Label(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormAction {
+    /// Set a text field, textarea or contenteditable; for checkboxes and radio
+    /// buttons the value is read as on/off.
+    Fill(String),
+    /// Choose a `<select>` option by value or visible text.
+    Select(String),
+    /// Click a submit button, or submit the form containing the target.
+    Submit,
+}
+
+impl FormAction {
+    fn name(&self) -> &'static str {
+        match self {
+            FormAction::Fill(_) => "fill",
+            FormAction::Select(_) => "select",
+            FormAction::Submit => "submit",
+        }
+    }
+
+    fn value(&self) -> Option<&str> {
+        match self {
+            FormAction::Fill(value) | FormAction::Select(value) => Some(value),
+            FormAction::Submit => None,
+        }
+    }
+}
+
+/// What a form action did, as reported by the page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormOutcome {
+    pub action: &'static str,
+    /// Short description of the element acted on, e.g. `input#title`.
+    pub target: String,
+    pub value: Option<String>,
+    /// The field was a password input; its value must not be echoed.
+    pub secret: bool,
+}
+
+impl FormOutcome {
+    /// One-line description of the action, suitable for history and the model.
+    pub fn summary(&self) -> String {
+        match (self.action, self.value.as_deref()) {
+            ("submit", _) => format!("Submitted {}", self.target),
+            ("fill", _) if self.secret => format!("Filled {} with a hidden value", self.target),
+            ("fill", Some(value)) => format!("Filled {} with \"{value}\"", self.target),
+            ("select", Some(value)) => format!("Selected \"{value}\" in {}", self.target),
+            (action, _) => format!("{action} {}", self.target),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawOutcome {
+    ok: bool,
+    #[serde(default)]
+    target: String,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    secret: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Script that performs `action` on `target` and evaluates to a result object
+/// for [`parse_outcome`].
+pub fn form_script(target: &FormTarget, action: &FormAction) -> String {
+    let (selector, label) = match target {
+        FormTarget::Selector(selector) => (Some(selector.as_str()), None),
+        FormTarget::Label(label) => (None, Some(label.as_str())),
+    };
+    let request = json!({
+        "selector": selector,
+        "label": label,
+        "action": action.name(),
+        "value": action.value(),
+    });
+    format!("{}({request})", FORM_ACTION_JS.trim_end())
+}
+
+/// Interpret the value returned by [`form_script`].
+pub fn parse_outcome(action: &FormAction, value: serde_json::Value) -> Result<FormOutcome> {
+    let raw: RawOutcome = serde_json::from_value(value)
+        .map_err(|err| BrowserError::FormError(format!("unexpected page response: {err}")))?;
+    if !raw.ok {
+        return Err(BrowserError::FormError(
+            raw.error.unwrap_or_else(|| "form action failed".to_string()),
+        ));
+    }
+    Ok(FormOutcome {
+        action: action.name(),
+        target: raw.target,
+        value: raw.value,
+        secret: raw.secret,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn script_embeds_request_as_json_and_outcomes_hide_passwords() {
+        let script = form_script(
+            &FormTarget::Label("Title \"quoted\"".to_string()),
+            &FormAction::Fill("</script>".to_string()),
+        );
+        assert!(script.starts_with("// Form interaction helper"));
+        assert!(script.contains(r#""label":"Title \"quoted\"""#));
+        assert!(script.contains(r#""value":"</script>""#));
+
+        let action = FormAction::Fill("hunter2".to_string());
+        let outcome = parse_outcome(
+            &action,
+            json!({"ok": true, "target": "input#password", "value": "hunter2", "secret": true}),
+        )
+        .expect("outcome");
+        assert_eq!(outcome.summary(), "Filled input#password with a hidden value");
+
+        let err = parse_outcome(&FormAction::Submit, json!({"ok": false, "error": "no form"}))
+            .expect_err("failure");
+        assert_eq!(err.to_string(), "Form interaction failed: no form");
+    }
+}
@@ -1,7 +1,11 @@
 use crate::config::BrowserConfig;
+use crate::login::Credentials;
 use crate::manager::BrowserManager;
 use once_cell::sync::Lazy;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use tokio::sync::RwLock;
 
 /// Global browser manager instance shared between TUI and Session
@@ -12,6 +16,51 @@ static GLOBAL_BROWSER_MANAGER: Lazy<Arc<RwLock<Option<Arc<BrowserManager>>>>> =
 static LAST_CONNECTION: Lazy<Arc<RwLock<(Option<u16>, Option<String>)>>> =
     Lazy::new(|| Arc::new(RwLock::new((None, None))));
 
+/// Persistent profile directory for the internal browser (None = temp profile)
+static ACTIVE_PROFILE_DIR: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+/// Headless preference for internal launches (None = each entry point's default)
+static HEADLESS_PREFERENCE: Lazy<Mutex<Option<bool>>> = Lazy::new(|| Mutex::new(None));
+
+/// External Chrome configured to be attached to instead of launching one
+static CDP_TARGET: Lazy<Mutex<Option<CdpTarget>>> = Lazy::new(|| Mutex::new(None));
+
+/// Proxy passed to internally launched Chrome
+static CHROME_PROXY: Lazy<Mutex<Option<ChromeProxy>>> = Lazy::new(|| Mutex::new(None));
+
+/// `--proxy-server` / `--proxy-bypass-list` for Chrome. Chrome cannot take
+/// credentials on the command line, so `server` carries none; `credentials`
+/// answer the proxy's auth challenges instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChromeProxy {
+    pub server: String,
+    pub bypass_list: String,
+    pub credentials: Option<Credentials>,
+}
+
+/// DevTools endpoint of an already-running Chrome.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CdpTarget {
+    pub ws: Option<String>,
+    pub port: Option<u16>,
+    pub host: Option<String>,
+}
+
+impl CdpTarget {
+    /// Point `config` at this Chrome; a WebSocket URL wins over a port.
+    pub fn apply(&self, config: &mut BrowserConfig) {
+        config.headless = false;
+        config.connect_host = self.host.clone();
+        if let Some(ws) = self.ws.clone() {
+            config.connect_ws = Some(ws);
+            config.connect_port = None;
+        } else {
+            config.connect_ws = None;
+            config.connect_port = self.port;
+        }
+    }
+}
+
 /// Get or create the global browser manager
 pub async fn get_or_create_browser_manager() -> Arc<BrowserManager> {
     // Fast path: try read lock to avoid contending on writer when already initialized
@@ -24,7 +73,14 @@ pub async fn get_or_create_browser_manager() -> Arc<BrowserManager> {
     if let Some(existing) = w.as_ref() {
         return existing.clone();
     }
-    let config = BrowserConfig::default();
+    let mut config = BrowserConfig::default();
+    config.use_profile_dir(active_profile_dir());
+    if let Some(headless) = headless_preference() {
+        config.headless = headless;
+    }
+    if let Some(target) = cdp_target() {
+        target.apply(&mut config);
+    }
     let manager = Arc::new(BrowserManager::new(config));
     *w = Some(manager.clone());
     manager
@@ -47,6 +103,83 @@ pub async fn set_global_browser_manager(manager: Arc<BrowserManager>) {
     tracing::info!("Global browser manager set");
 }
 
+/// Profile directory the internal browser should launch with
+pub fn active_profile_dir() -> Option<PathBuf> {
+    ACTIVE_PROFILE_DIR
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Select the profile directory for internal browser launches. The running
+/// browser keeps its profile until it is restarted.
+pub async fn set_active_profile_dir(dir: Option<PathBuf>) {
+    *ACTIVE_PROFILE_DIR
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = dir.clone();
+    if let Some(manager) = get_browser_manager().await {
+        manager.config.write().await.use_profile_dir(dir);
+    }
+}
+
+/// Headless preference for internal launches, if one was chosen
+pub fn headless_preference() -> Option<bool> {
+    *HEADLESS_PREFERENCE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Choose whether internal launches are headless. The running browser keeps
+/// its mode until it is restarted.
+pub async fn set_headless_preference(headless: bool) {
+    *HEADLESS_PREFERENCE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(headless);
+    if let Some(manager) = get_browser_manager().await {
+        let mut config = manager.config.write().await;
+        if config.connect_port.is_none() && config.connect_ws.is_none() {
+            config.headless = headless;
+        }
+    }
+}
+
+/// External Chrome to attach to, if one was configured
+pub fn cdp_target() -> Option<CdpTarget> {
+    CDP_TARGET
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Configure the external Chrome to attach to. An idle manager is pointed at
+/// it right away; a running browser is left alone.
+pub async fn set_cdp_target(target: Option<CdpTarget>) {
+    *CDP_TARGET
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = target.clone();
+    let (Some(target), Some(manager)) = (target, get_browser_manager().await) else {
+        return;
+    };
+    if !manager.get_status().await.browser_active {
+        target.apply(&mut *manager.config.write().await);
+    }
+}
+
+/// Proxy for internal Chrome launches, if one was configured
+pub fn chrome_proxy() -> Option<ChromeProxy> {
+    CHROME_PROXY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Route internally launched Chrome through `proxy`. Applies from the next launch.
+pub fn set_chrome_proxy(proxy: Option<ChromeProxy>) {
+    *CHROME_PROXY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = proxy;
+}
+
 /// Get the last known external Chrome connection (port, ws)
 pub async fn get_last_connection() -> (Option<u16>, Option<String>) {
     let (port, ws) = LAST_CONNECTION.read().await.clone();
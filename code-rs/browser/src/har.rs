@@ -0,0 +1,539 @@
+//! HTTP Archive (HAR 1.2) recording of the page's network activity.
+//!
+//! Every request the page makes is tracked from `Network.requestWillBeSent`
+//! to `loadingFinished`/`loadingFailed`, with the response headers and CDP
+//! resource timing, so `/browser har <path>` can write a file that DevTools,
+//! Charles or any HAR viewer opens. Response bodies are not recorded, and
+//! credentials are not either: authorization and cookie headers are replaced
+//! with a placeholder, as are the bodies of form posts and of requests that
+//! carry a password or token.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+
+use chromiumoxide::cdp::browser_protocol::network as cdp_network;
+use serde_json::Value;
+use serde_json::json;
+
+const MAX_PENDING_REQUESTS: usize = 1000;
+const MAX_ENTRIES: usize = 5000;
+/// Stands in for header values and request bodies that hold credentials.
+const REDACTED: &str = "[redacted]";
+const CREDENTIAL_HEADERS: &[&str] =
+    &["authorization", "proxy-authorization", "cookie", "set-cookie"];
+/// Body fragments that mark a non-form request as a login or token exchange.
+const CREDENTIAL_BODY_HINTS: &[&str] =
+    &["password", "passwd", "secret", "token", "credential", "otp"];
+
+/// Response fields kept for the archive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HarResponse {
+    pub status: i64,
+    pub status_text: String,
+    pub protocol: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub mime_type: String,
+    pub remote_ip: Option<String>,
+    pub timing: Option<HarTiming>,
+}
+
+/// CDP `ResourceTiming`: `request_time` in seconds, the rest in milliseconds
+/// relative to it, -1 when a phase did not happen (e.g. reused connections).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HarTiming {
+    pub request_time: f64,
+    pub dns_start: f64,
+    pub dns_end: f64,
+    pub connect_start: f64,
+    pub connect_end: f64,
+    pub ssl_start: f64,
+    pub ssl_end: f64,
+    pub send_start: f64,
+    pub send_end: f64,
+    pub receive_headers_end: f64,
+}
+
+impl From<&cdp_network::Response> for HarResponse {
+    fn from(response: &cdp_network::Response) -> Self {
+        Self {
+            status: response.status,
+            status_text: response.status_text.clone(),
+            protocol: response.protocol.clone(),
+            headers: redact_headers(header_pairs(response.headers.inner())),
+            mime_type: response.mime_type.clone(),
+            remote_ip: response.remote_ip_address.clone(),
+            timing: response.timing.as_ref().map(|timing| HarTiming {
+                request_time: timing.request_time,
+                dns_start: timing.dns_start,
+                dns_end: timing.dns_end,
+                connect_start: timing.connect_start,
+                connect_end: timing.connect_end,
+                ssl_start: timing.ssl_start,
+                ssl_end: timing.ssl_end,
+                send_start: timing.send_start,
+                send_end: timing.send_end,
+                receive_headers_end: timing.receive_headers_end,
+            }),
+        }
+    }
+}
+
+/// CDP headers are a JSON object; HAR wants name/value pairs.
+pub fn header_pairs(headers: &Value) -> Vec<(String, String)> {
+    let Some(map) = headers.as_object() else {
+        return Vec::new();
+    };
+    map.iter()
+        .flat_map(|(name, value)| {
+            // Chrome joins repeated headers (Set-Cookie) with newlines.
+            let text = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+            text.split('\n')
+                .map(|part| (name.clone(), part.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    started_wall_s: f64,
+    started_mono_s: f64,
+    method: String,
+    url: String,
+    request_headers: Vec<(String, String)>,
+    post_data: Option<String>,
+    /// Length of the body as sent, which a redacted `post_data` no longer has.
+    post_data_size: usize,
+    resource_type: Option<String>,
+    response: Option<HarResponse>,
+    redirect_url: Option<String>,
+    finished_mono_s: Option<f64>,
+    encoded_length: Option<f64>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct HarLog {
+    pending: HashMap<String, Entry>,
+    entries: VecDeque<Entry>,
+}
+
+/// A request as seen in `Network.requestWillBeSent`.
+pub struct HarRequest<'a> {
+    pub request_id: &'a str,
+    pub wall_time_s: f64,
+    pub monotonic_s: f64,
+    pub method: &'a str,
+    pub url: &'a str,
+    pub headers: &'a Value,
+    pub post_data: Option<String>,
+    pub resource_type: Option<String>,
+}
+
+impl HarLog {
+    pub fn request_will_be_sent(&mut self, request: HarRequest<'_>) {
+        if request.url.starts_with("data:") || request.url.starts_with("blob:") {
+            return;
+        }
+        if self.pending.len() >= MAX_PENDING_REQUESTS {
+            // Requests that never finish (long polls, aborted pages) would
+            // otherwise accumulate forever.
+            self.pending.clear();
+        }
+        let request_headers = header_pairs(request.headers);
+        let post_data_size = request.post_data.as_ref().map_or(0, String::len);
+        let post_data = request.post_data.map(|body| {
+            let content_type = header_value(&request_headers, "content-type").unwrap_or_default();
+            if holds_credentials(content_type, &body) {
+                REDACTED.to_string()
+            } else {
+                body
+            }
+        });
+        self.pending.insert(
+            request.request_id.to_string(),
+            Entry {
+                started_wall_s: request.wall_time_s,
+                started_mono_s: request.monotonic_s,
+                method: request.method.to_string(),
+                url: request.url.to_string(),
+                request_headers: redact_headers(request_headers),
+                post_data,
+                post_data_size,
+                resource_type: request.resource_type,
+                response: None,
+                redirect_url: None,
+                finished_mono_s: None,
+                encoded_length: None,
+                error: None,
+            },
+        );
+    }
+
+    /// A redirect reuses the request id: close the hop that was redirected
+    /// before [`Self::request_will_be_sent`] starts the next one.
+    pub fn redirected(
+        &mut self,
+        request_id: &str,
+        response: HarResponse,
+        location: &str,
+        monotonic_s: f64,
+    ) {
+        if let Some(mut entry) = self.pending.remove(request_id) {
+            entry.response = Some(response);
+            entry.redirect_url = Some(location.to_string());
+            entry.finished_mono_s = Some(monotonic_s);
+            self.push(entry);
+        }
+    }
+
+    pub fn response_received(&mut self, request_id: &str, response: HarResponse) {
+        if let Some(entry) = self.pending.get_mut(request_id) {
+            entry.response = Some(response);
+        }
+    }
+
+    pub fn finished(&mut self, request_id: &str, monotonic_s: f64, encoded_length: f64) {
+        if let Some(mut entry) = self.pending.remove(request_id) {
+            entry.finished_mono_s = Some(monotonic_s);
+            entry.encoded_length = Some(encoded_length);
+            self.push(entry);
+        }
+    }
+
+    pub fn failed(&mut self, request_id: &str, monotonic_s: f64, error: &str) {
+        if let Some(mut entry) = self.pending.remove(request_id) {
+            entry.finished_mono_s = Some(monotonic_s);
+            entry.error = Some(error.to_string());
+            self.push(entry);
+        }
+    }
+
+    fn push(&mut self, entry: Entry) {
+        self.entries.push_back(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len() + self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The archive as a HAR 1.2 document; requests still in flight are
+    /// included without a response.
+    pub fn to_har(&self) -> Value {
+        let mut entries: Vec<&Entry> = self.entries.iter().chain(self.pending.values()).collect();
+        entries.sort_by(|a, b| a.started_mono_s.total_cmp(&b.started_mono_s));
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "Code", "version": env!("CARGO_PKG_VERSION") },
+                "pages": [],
+                "entries": entries.into_iter().map(entry_json).collect::<Vec<_>>(),
+            }
+        })
+    }
+}
+
+fn pairs_json(pairs: &[(String, String)]) -> Vec<Value> {
+    pairs
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn redact_headers(pairs: Vec<(String, String)>) -> Vec<(String, String)> {
+    pairs
+        .into_iter()
+        .map(|(name, value)| {
+            if CREDENTIAL_HEADERS.iter().any(|header| name.eq_ignore_ascii_case(header)) {
+                (name, REDACTED.to_string())
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
+
+/// Form posts (login forms, `browser_fill` submissions) and bodies naming a
+/// password or token are not archived.
+fn holds_credentials(content_type: &str, body: &str) -> bool {
+    let content_type = content_type.to_ascii_lowercase();
+    if content_type.starts_with("application/x-www-form-urlencoded")
+        || content_type.starts_with("multipart/form-data")
+    {
+        return true;
+    }
+    let body = body.to_ascii_lowercase();
+    CREDENTIAL_BODY_HINTS.iter().any(|hint| body.contains(hint))
+}
+
+fn header_value<'a>(pairs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    pairs
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn http_version(protocol: Option<&str>) -> String {
+    match protocol {
+        Some("h2") => "HTTP/2.0".to_string(),
+        Some("h3") => "HTTP/3.0".to_string(),
+        Some(other) if !other.is_empty() => other.to_ascii_uppercase(),
+        _ => "HTTP/1.1".to_string(),
+    }
+}
+
+/// HAR phase durations in ms (`-1` = not applicable) and their total.
+fn timings(entry: &Entry) -> (Value, f64) {
+    let elapsed_ms = entry
+        .finished_mono_s
+        .map(|end| ((end - entry.started_mono_s) * 1000.0).max(0.0))
+        .unwrap_or(0.0);
+    let Some(timing) = entry.response.as_ref().and_then(|response| response.timing) else {
+        return (
+            json!({ "blocked": -1, "dns": -1, "connect": -1, "ssl": -1, "send": 0, "wait": elapsed_ms, "receive": 0 }),
+            elapsed_ms,
+        );
+    };
+    let span = |start: f64, end: f64| if start >= 0.0 && end >= start { end - start } else { -1.0 };
+    let blocked = [timing.dns_start, timing.connect_start, timing.send_start]
+        .into_iter()
+        .find(|start| *start >= 0.0)
+        .unwrap_or(0.0);
+    let dns = span(timing.dns_start, timing.dns_end);
+    // HAR counts TLS inside `connect` and reports it separately as `ssl`.
+    let connect = span(timing.connect_start, timing.connect_end);
+    let ssl = span(timing.ssl_start, timing.ssl_end);
+    let send = span(timing.send_start, timing.send_end).max(0.0);
+    let wait = (timing.receive_headers_end - timing.send_end).max(0.0);
+    let receive = entry
+        .finished_mono_s
+        .map(|end| ((end - timing.request_time) * 1000.0 - timing.receive_headers_end).max(0.0))
+        .unwrap_or(0.0);
+    let total = [blocked, dns, connect, send, wait, receive]
+        .into_iter()
+        .filter(|phase| *phase > 0.0)
+        .sum::<f64>();
+    (
+        json!({
+            "blocked": blocked,
+            "dns": dns,
+            "connect": connect,
+            "ssl": ssl,
+            "send": send,
+            "wait": wait,
+            "receive": receive,
+        }),
+        total,
+    )
+}
+
+fn entry_json(entry: &Entry) -> Value {
+    let started = chrono::DateTime::from_timestamp_millis((entry.started_wall_s * 1000.0) as i64)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let query_string = url::Url::parse(&entry.url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let protocol = entry.response.as_ref().and_then(|r| r.protocol.as_deref());
+    let mut request = json!({
+        "method": entry.method,
+        "url": entry.url,
+        "httpVersion": http_version(protocol),
+        "cookies": [],
+        "headers": pairs_json(&entry.request_headers),
+        "queryString": query_string,
+        "headersSize": -1,
+        "bodySize": entry.post_data_size,
+    });
+    if let Some(post_data) = &entry.post_data {
+        request["postData"] = json!({
+            "mimeType": header_value(&entry.request_headers, "content-type").unwrap_or_default(),
+            "text": post_data,
+        });
+    }
+    let response = match &entry.response {
+        Some(response) => json!({
+            "status": response.status,
+            "statusText": response.status_text,
+            "httpVersion": http_version(response.protocol.as_deref()),
+            "cookies": [],
+            "headers": pairs_json(&response.headers),
+            "content": {
+                "size": entry.encoded_length.map_or(-1, |length| length as i64),
+                "mimeType": response.mime_type,
+            },
+            "redirectURL": entry.redirect_url.clone().unwrap_or_default(),
+            "headersSize": -1,
+            "bodySize": entry.encoded_length.map_or(-1, |length| length as i64),
+        }),
+        None => json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "x-unknown" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+        }),
+    };
+    let (timings, time) = timings(entry);
+    let mut value = json!({
+        "startedDateTime": started,
+        "time": time,
+        "request": request,
+        "response": response,
+        "cache": {},
+        "timings": timings,
+    });
+    if let Some(ip) = entry.response.as_ref().and_then(|r| r.remote_ip.as_deref()) {
+        value["serverIPAddress"] = json!(ip.trim_start_matches('[').trim_end_matches(']'));
+    }
+    if let Some(kind) = &entry.resource_type {
+        value["_resourceType"] = json!(kind.to_ascii_lowercase());
+    }
+    if let Some(error) = &entry.error {
+        value["_error"] = json!(error);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: i64, timing: Option<HarTiming>) -> HarResponse {
+        HarResponse {
+            status,
+            status_text: "OK".to_string(),
+            protocol: Some("h2".to_string()),
+            headers: header_pairs(&json!({"Content-Type": "text/html", "Set-Cookie": "a=1\nb=2"})),
+            mime_type: "text/html".to_string(),
+            remote_ip: Some("[::1]".to_string()),
+            timing,
+        }
+    }
+
+    #[test]
+    fn archives_requests_redirects_and_failures() {
+        let mut log = HarLog::default();
+        let headers = json!({"Accept": "text/html"});
+        let request = |id, url, monotonic_s| HarRequest {
+            request_id: id,
+            wall_time_s: 1_700_000_000.0 + monotonic_s,
+            monotonic_s,
+            method: "GET",
+            url,
+            headers: &headers,
+            post_data: None,
+            resource_type: Some("Document".to_string()),
+        };
+        log.request_will_be_sent(request("1", "http://app.test/old?x=1", 10.0));
+        log.redirected("1", response(301, None), "http://app.test/new", 10.05);
+        log.request_will_be_sent(request("1", "http://app.test/new", 10.05));
+        let timing = HarTiming {
+            request_time: 10.05,
+            dns_start: 1.0,
+            dns_end: 3.0,
+            connect_start: 3.0,
+            connect_end: 10.0,
+            ssl_start: 5.0,
+            ssl_end: 10.0,
+            send_start: 10.0,
+            send_end: 11.0,
+            receive_headers_end: 40.0,
+        };
+        log.response_received("1", response(200, Some(timing)));
+        log.finished("1", 10.1, 1234.0);
+        log.request_will_be_sent(request("2", "http://cdn.test/a.js", 10.2));
+        log.failed("2", 10.3, "net::ERR_NAME_NOT_RESOLVED");
+        log.request_will_be_sent(request("3", "http://app.test/poll", 10.4));
+        log.request_will_be_sent(request("4", "data:text/plain,hi", 10.5));
+        assert_eq!(log.len(), 4);
+
+        let har = log.to_har();
+        let entries = har["log"]["entries"].as_array().expect("entries");
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(entries.len(), 4);
+
+        assert_eq!(entries[0]["response"]["status"], 301);
+        assert_eq!(entries[0]["response"]["redirectURL"], "http://app.test/new");
+        assert_eq!(entries[0]["request"]["queryString"], json!([{"name": "x", "value": "1"}]));
+        assert_eq!(entries[0]["startedDateTime"], "2023-11-14T22:13:30.000Z");
+
+        let page = &entries[1];
+        assert_eq!(page["response"]["httpVersion"], "HTTP/2.0");
+        assert_eq!(page["response"]["content"]["size"], 1234);
+        assert_eq!(page["serverIPAddress"], "::1");
+        assert_eq!(page["_resourceType"], "document");
+        assert_eq!(
+            page["response"]["headers"],
+            json!([
+                {"name": "Content-Type", "value": "text/html"},
+                {"name": "Set-Cookie", "value": "[redacted]"},
+                {"name": "Set-Cookie", "value": "[redacted]"},
+            ])
+        );
+        let timings = &page["timings"];
+        assert_eq!(
+            (timings["blocked"].as_f64(), timings["dns"].as_f64(), timings["ssl"].as_f64()),
+            (Some(1.0), Some(2.0), Some(5.0))
+        );
+        assert_eq!((timings["wait"].as_f64(), timings["receive"].as_f64().map(f64::round)), (Some(29.0), Some(10.0)));
+
+        assert_eq!(entries[2]["_error"], "net::ERR_NAME_NOT_RESOLVED");
+        assert_eq!(entries[3]["response"]["status"], 0);
+    }
+
+    #[test]
+    fn redacts_credential_headers_and_form_bodies() {
+        let mut log = HarLog::default();
+        let form = json!({
+            "Authorization": "Bearer abc",
+            "Content-Type": "application/x-www-form-urlencoded",
+            "Cookie": "sid=1",
+        });
+        let api = json!({"Content-Type": "application/json"});
+        let post = |id, monotonic_s, headers, body: &str| HarRequest {
+            request_id: id,
+            wall_time_s: 1_700_000_000.0 + monotonic_s,
+            monotonic_s,
+            method: "POST",
+            url: "https://app.test/login",
+            headers,
+            post_data: Some(body.to_string()),
+            resource_type: Some("XHR".to_string()),
+        };
+        log.request_will_be_sent(post("1", 1.0, &form, "user=a&pass=hunter2"));
+        log.request_will_be_sent(post("2", 2.0, &api, r#"{"password":"hunter2"}"#));
+        log.request_will_be_sent(post("3", 3.0, &api, r#"{"title":"hi"}"#));
+
+        let har = log.to_har();
+        let entries = har["log"]["entries"].as_array().expect("entries");
+        let request = &entries[0]["request"];
+        assert_eq!(
+            request["headers"],
+            json!([
+                {"name": "Authorization", "value": "[redacted]"},
+                {"name": "Content-Type", "value": "application/x-www-form-urlencoded"},
+                {"name": "Cookie", "value": "[redacted]"},
+            ])
+        );
+        assert_eq!(request["postData"]["text"], "[redacted]");
+        assert_eq!(request["bodySize"], 19);
+        assert_eq!(entries[1]["request"]["postData"]["text"], "[redacted]");
+        assert_eq!(entries[2]["request"]["postData"]["text"], r#"{"title":"hi"}"#);
+    }
+}
@@ -0,0 +1,133 @@
+// Form interaction helper used by the browser fill/select/submit actions.
+// Invoked as `(FORM_ACTION)(request)` where request is
+// { selector?, label?, action: 'fill' | 'select' | 'submit', value? }.
+(function (request) {
+  const normalize = (text) => (text || '').replace(/\s+/g, ' ').trim().toLowerCase();
+  const isVisible = (el) => {
+    const style = window.getComputedStyle(el);
+    if (style.visibility === 'hidden' || style.display === 'none') return false;
+    const rect = el.getBoundingClientRect();
+    return rect.width > 0 || rect.height > 0;
+  };
+  const controlFor = (label) => {
+    if (label.control) return label.control;
+    if (label.htmlFor) return document.getElementById(label.htmlFor);
+    return label.querySelector('input, select, textarea');
+  };
+
+  // Candidates that a human would call "the <label> field": <label> text first,
+  // then aria-label, placeholder and name; for submit also button text.
+  const findByLabel = (wanted) => {
+    const want = normalize(wanted);
+    const scored = [];
+    const consider = (el, text) => {
+      if (!el || !isVisible(el)) return;
+      const have = normalize(text);
+      if (!have) return;
+      if (have === want) scored.push([0, el]);
+      else if (have.includes(want)) scored.push([1, el]);
+    };
+    document.querySelectorAll('label').forEach((label) => consider(controlFor(label), label.textContent));
+    document.querySelectorAll('input, select, textarea, [contenteditable=""], [contenteditable="true"]')
+      .forEach((el) => {
+        consider(el, el.getAttribute('aria-label'));
+        consider(el, el.getAttribute('placeholder'));
+        consider(el, el.getAttribute('name'));
+      });
+    if (request.action === 'submit') {
+      document.querySelectorAll('button, input[type=submit], input[type=button]')
+        .forEach((el) => consider(el, el.textContent || el.value));
+    }
+    scored.sort((a, b) => a[0] - b[0]);
+    return scored.length ? scored[0][1] : null;
+  };
+
+  const describe = (el) => {
+    let text = el.tagName.toLowerCase();
+    if (el.id) text += '#' + el.id;
+    else if (el.getAttribute('name')) text += '[name="' + el.getAttribute('name') + '"]';
+    return text;
+  };
+  const fail = (error) => ({ ok: false, error });
+  const setValue = (el, value) => {
+    const proto = Object.getPrototypeOf(el);
+    const setter = Object.getOwnPropertyDescriptor(proto, 'value');
+    // Framework-controlled inputs (React, Vue) only notice the native setter.
+    if (setter && setter.set) setter.set.call(el, value);
+    else el.value = value;
+  };
+  const notify = (el) => {
+    el.dispatchEvent(new Event('input', { bubbles: true }));
+    el.dispatchEvent(new Event('change', { bubbles: true }));
+  };
+
+  let el;
+  try {
+    el = request.selector ? document.querySelector(request.selector) : findByLabel(request.label);
+  } catch (e) {
+    return fail('Invalid selector: ' + e.message);
+  }
+  if (!el) {
+    return fail(request.selector
+      ? 'No element matches selector ' + request.selector
+      : 'No field labelled "' + request.label + '"');
+  }
+  el.scrollIntoView({ block: 'center', inline: 'nearest' });
+  const target = describe(el);
+  const tag = el.tagName;
+  const value = request.value == null ? '' : String(request.value);
+
+  if (request.action === 'fill') {
+    if (tag === 'INPUT' && (el.type === 'checkbox' || el.type === 'radio')) {
+      const checked = !/^(|0|false|no|off|unchecked)$/i.test(value.trim());
+      if (el.checked !== checked) el.click();
+      return { ok: true, target, value: checked ? 'checked' : 'unchecked' };
+    }
+    if (el.isContentEditable) {
+      el.focus();
+      el.textContent = value;
+      notify(el);
+      return { ok: true, target, value };
+    }
+    if (tag !== 'INPUT' && tag !== 'TEXTAREA') {
+      return fail(target + ' is not a text field');
+    }
+    if (el.disabled || el.readOnly) return fail(target + ' is not editable');
+    el.focus();
+    setValue(el, value);
+    notify(el);
+    return { ok: true, target, value, secret: el.type === 'password' };
+  }
+
+  if (request.action === 'select') {
+    if (tag !== 'SELECT') return fail(target + ' is not a <select>');
+    const want = normalize(value);
+    const options = Array.from(el.options);
+    const option = options.find((o) => o.value === value)
+      || options.find((o) => normalize(o.textContent) === want)
+      || options.find((o) => normalize(o.textContent).includes(want));
+    if (!option) {
+      const available = options.map((o) => o.textContent.trim()).filter(Boolean).slice(0, 20);
+      return fail('No option "' + value + '" in ' + target + '; available: ' + available.join(', '));
+    }
+    setValue(el, option.value);
+    option.selected = true;
+    notify(el);
+    return { ok: true, target, value: option.textContent.trim() };
+  }
+
+  if (request.action === 'submit') {
+    const isButton = tag === 'BUTTON' || (tag === 'INPUT' && /^(submit|image|button)$/i.test(el.type));
+    if (isButton) {
+      el.click();
+      return { ok: true, target };
+    }
+    const form = tag === 'FORM' ? el : (el.form || el.closest('form'));
+    if (!form) return fail(target + ' is not inside a form');
+    if (typeof form.requestSubmit === 'function') form.requestSubmit();
+    else form.submit();
+    return { ok: true, target: describe(form) };
+  }
+
+  return fail('Unknown form action ' + request.action);
+})
@@ -0,0 +1,65 @@
+// Login form helper used by the browser login action.
+// Invoked as `(LOGIN_FORM)(request)` where request is
+// { mode: 'detect' } or { mode: 'fill', username?, password, submit }.
+// Filled values are never returned to the caller.
+(function (request) {
+  const isVisible = (el) => {
+    const style = window.getComputedStyle(el);
+    if (style.visibility === 'hidden' || style.display === 'none') return false;
+    const rect = el.getBoundingClientRect();
+    return rect.width > 0 && rect.height > 0;
+  };
+  const textTypes = ['', 'text', 'email', 'tel'];
+
+  // A sign-in form has exactly one visible password field; two or more
+  // usually means sign-up or change-password, which we leave alone.
+  const passwords = Array.from(document.querySelectorAll('input[type=password]'))
+    .filter((el) => isVisible(el) && !el.disabled && !el.readOnly);
+  if (passwords.length !== 1) return { found: false };
+  const password = passwords[0];
+  const scope = password.form || document;
+
+  const fields = Array.from(scope.querySelectorAll('input'))
+    .filter((el) => el !== password && isVisible(el) && !el.disabled
+      && textTypes.includes((el.getAttribute('type') || '').toLowerCase()));
+  const byAutocomplete = fields.find((el) => /\b(username|email)\b/i.test(el.autocomplete || ''));
+  // Otherwise the last text field before the password in document order.
+  const before = fields.filter((el) =>
+    el.compareDocumentPosition(password) & Node.DOCUMENT_POSITION_FOLLOWING);
+  const username = byAutocomplete || before[before.length - 1] || null;
+
+  if (request.mode === 'detect') {
+    return { found: true, has_username: !!username, in_form: !!password.form };
+  }
+
+  const setValue = (el, value) => {
+    const proto = Object.getPrototypeOf(el);
+    const setter = Object.getOwnPropertyDescriptor(proto, 'value');
+    // Framework-controlled inputs (React, Vue) only notice the native setter.
+    if (setter && setter.set) setter.set.call(el, value);
+    else el.value = value;
+    el.dispatchEvent(new Event('input', { bubbles: true }));
+    el.dispatchEvent(new Event('change', { bubbles: true }));
+  };
+  if (username && typeof request.username === 'string') {
+    username.focus();
+    setValue(username, request.username);
+  }
+  password.focus();
+  setValue(password, request.password || '');
+
+  if (!request.submit) return { found: true, submitted: false };
+  const form = password.form;
+  if (form) {
+    const button = form.querySelector('button[type=submit], input[type=submit], button:not([type])');
+    if (typeof form.requestSubmit === 'function') form.requestSubmit(button || undefined);
+    else if (button) button.click();
+    else form.submit();
+    return { found: true, submitted: true };
+  }
+  // Script-driven forms without a <form>: press Enter in the password field.
+  for (const type of ['keydown', 'keypress', 'keyup']) {
+    password.dispatchEvent(new KeyboardEvent(type, { key: 'Enter', code: 'Enter', keyCode: 13, bubbles: true }));
+  }
+  return { found: true, submitted: true };
+})
@@ -0,0 +1,36 @@
+// DOM query helper used by the browser query action.
+// Invoked as `(QUERY)(request)` where request is
+// { selector, limit, html, attributes: string[] | null, maxText, maxHtml }.
+(function (request) {
+  const clip = (text, max) => (text.length > max ? text.slice(0, max) + '…' : text);
+  let nodes;
+  try {
+    nodes = Array.from(document.querySelectorAll(request.selector));
+  } catch (e) {
+    return { ok: false, error: 'Invalid selector: ' + e.message };
+  }
+  const wanted = (name) => !request.attributes || request.attributes.includes(name);
+  const matches = nodes.slice(0, request.limit).map((el) => {
+    const attributes = {};
+    for (const attr of Array.from(el.attributes)) {
+      if (!wanted(attr.name) || (attr.name === 'value' && el.type === 'password')) continue;
+      // Inline styles and handlers are noise for data extraction.
+      if (!request.attributes && (attr.name === 'style' || attr.name.startsWith('on'))) continue;
+      attributes[attr.name] = clip(attr.value, request.maxText);
+    }
+    // Resolved URLs and live form values instead of the raw markup.
+    if (el.tagName === 'A' && el.href && wanted('href')) attributes.href = el.href;
+    if (el.tagName === 'IMG' && el.currentSrc && wanted('src')) attributes.src = el.currentSrc;
+    if ('value' in el && typeof el.value === 'string' && el.type !== 'password' && wanted('value')) {
+      attributes.value = clip(el.value, request.maxText);
+    }
+    const text = (el.innerText || el.textContent || '').replace(/\s+/g, ' ').trim();
+    return {
+      tag: el.tagName.toLowerCase(),
+      text: clip(text, request.maxText),
+      attributes,
+      html: request.html ? clip(el.outerHTML, request.maxHtml) : null,
+    };
+  });
+  return { ok: true, total: nodes.length, matches };
+})
@@ -1,9 +1,15 @@
 pub mod assets;
 pub mod config;
+pub mod diagnostics;
+pub mod forms;
 pub mod global;
+pub mod har;
 pub mod hooks;
+pub mod login;
 pub mod manager;
 pub mod page;
+pub mod profiles;
+pub mod query;
 pub mod tools;
 
 pub use config::BrowserConfig;
@@ -38,6 +44,12 @@ pub enum BrowserError {
 
     #[error("Asset storage error: {0}")]
     AssetError(String),
+
+    #[error("Form interaction failed: {0}")]
+    FormError(String),
+
+    #[error("Query failed: {0}")]
+    QueryError(String),
 }
 
 impl From<chromiumoxide::error::CdpError> for BrowserError {
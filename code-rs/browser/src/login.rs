@@ -0,0 +1,131 @@
+//! Sign-in support: spotting a login form or an HTTP Basic challenge and
+//! supplying credentials the user entered.
+//!
+//! Credentials only ever travel from the caller into the page (a script that
+//! fills the form, or a CDP auth reply); nothing here returns them, so the
+//! model never sees what the user typed.
+
+use serde::Deserialize;
+use serde_json::json;
+use url::Url;
+
+use crate::BrowserError;
+use crate::Result;
+
+const LOGIN_FORM_JS: &str = include_str!("js/login_form.js");
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// A sign-in form found on the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct LoginForm {
+    /// A username/email field was found next to the password field.
+    #[serde(default)]
+    pub has_username: bool,
+    /// The fields belong to a `<form>` that can be submitted directly.
+    #[serde(default)]
+    pub in_form: bool,
+}
+
+#[derive(Deserialize)]
+struct RawDetect {
+    found: bool,
+    #[serde(flatten)]
+    form: LoginForm,
+}
+
+#[derive(Deserialize)]
+struct RawFill {
+    found: bool,
+    #[serde(default)]
+    submitted: bool,
+}
+
+pub fn detect_script() -> String {
+    format!("{}({})", LOGIN_FORM_JS.trim_end(), json!({ "mode": "detect" }))
+}
+
+pub fn parse_detect(value: serde_json::Value) -> Result<Option<LoginForm>> {
+    let raw: RawDetect = serde_json::from_value(value)
+        .map_err(|err| BrowserError::FormError(format!("unexpected page response: {err}")))?;
+    Ok(raw.found.then_some(raw.form))
+}
+
+pub fn fill_script(credentials: &Credentials, submit: bool) -> String {
+    let request = json!({
+        "mode": "fill",
+        "username": credentials.username,
+        "password": credentials.password,
+        "submit": submit,
+    });
+    format!("{}({request})", LOGIN_FORM_JS.trim_end())
+}
+
+/// Interpret the value returned by [`fill_script`]; `Ok(true)` when the form was submitted.
+pub fn parse_fill(value: serde_json::Value) -> Result<bool> {
+    let raw: RawFill = serde_json::from_value(value)
+        .map_err(|err| BrowserError::FormError(format!("unexpected page response: {err}")))?;
+    if !raw.found {
+        return Err(BrowserError::FormError(
+            "the login form is no longer on the page".to_string(),
+        ));
+    }
+    Ok(raw.submitted)
+}
+
+/// `scheme://host[:port]` of an http(s) URL, the key credentials are stored under.
+pub fn origin_of(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    Some(parsed.origin().ascii_serialization())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_carry_credentials_only_one_way() {
+        let credentials = Credentials {
+            username: "ada".to_string(),
+            password: "p\"w</script>".to_string(),
+        };
+        assert_eq!(
+            format!("{credentials:?}"),
+            "Credentials { username: \"ada\", password: \"<redacted>\" }"
+        );
+        let script = fill_script(&credentials, true);
+        assert!(script.starts_with("// Login form helper"));
+        assert!(script.contains(r#""password":"p\"w</script>""#));
+        assert_eq!(parse_fill(json!({"found": true, "submitted": true})).ok(), Some(true));
+        assert!(parse_fill(json!({"found": false})).is_err());
+
+        assert_eq!(
+            parse_detect(json!({"found": true, "has_username": true, "in_form": false})).ok(),
+            Some(Some(LoginForm { has_username: true, in_form: false }))
+        );
+        assert_eq!(parse_detect(json!({"found": false})).ok(), Some(None));
+
+        assert_eq!(
+            origin_of("https://Docs.Example.com:8443/a?b").as_deref(),
+            Some("https://docs.example.com:8443")
+        );
+        assert_eq!(origin_of("http://example.com/").as_deref(), Some("http://example.com"));
+        assert_eq!(origin_of("about:blank"), None);
+    }
+}
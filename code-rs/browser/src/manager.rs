@@ -2,7 +2,20 @@ use crate::BrowserError;
 use crate::Result;
 use crate::config::BrowserConfig;
 use crate::config::WaitStrategy;
+use crate::diagnostics::NetworkFailure;
+use crate::diagnostics::PageDiagnostics;
+use crate::forms::FormAction;
+use crate::forms::FormOutcome;
+use crate::forms::FormTarget;
+use crate::login::Credentials;
+use crate::login::LoginForm;
+use crate::query::QueryOptions;
+use crate::query::QueryResult;
 use crate::page::Page;
+use crate::page::ScrollPosition;
+use crate::page::ScrollTarget;
+use crate::page::SetViewportParams;
+use crate::page::ViewportResult;
 use chromiumoxide::Browser;
 use chromiumoxide::BrowserConfig as CdpConfig;
 use chromiumoxide::browser::HeadlessMode;
@@ -910,6 +923,12 @@ impl BrowserManager {
                     // Set a longer timeout for CDP requests (60 seconds instead of default 30)
                     .request_timeout(Duration::from_secs(60));
 
+                if let Some(proxy) = crate::global::chrome_proxy() {
+                    builder = builder
+                        .arg(format!("--proxy-server={}", proxy.server))
+                        .arg(format!("--proxy-bypass-list={}", proxy.bypass_list));
+                }
+
                 if let Some(ref log_file) = log_file {
                     builder = builder
                         .arg("--enable-logging")
@@ -1237,6 +1256,16 @@ impl BrowserManager {
         let page = Arc::new(Page::new(cdp_page, config.clone()));
         *page_guard = Some(Arc::clone(&page));
 
+        // Only a Chrome we launched was pointed at the configured proxy.
+        let launched = config.connect_port.is_none() && config.connect_ws.is_none();
+        if launched
+            && let Some(credentials) =
+                crate::global::chrome_proxy().and_then(|proxy| proxy.credentials)
+            && let Err(e) = page.provide_proxy_auth(credentials).await
+        {
+            warn!("Failed to set up proxy authentication: {}", e);
+        }
+
         // Inject the virtual cursor when page is created
         debug!("Injecting virtual cursor for new page");
         if let Err(e) = page.inject_virtual_cursor().await {
@@ -2067,12 +2096,60 @@ impl BrowserManager {
         page.execute_javascript(code).await
     }
 
+    /// Fill, select or submit a form element on the current page
+    pub async fn form_action(
+        &self,
+        target: &FormTarget,
+        action: &FormAction,
+    ) -> Result<FormOutcome> {
+        let page = self.get_or_create_page().await?;
+        page.form_action(target, action).await
+    }
+
+    /// Run a CSS selector query against the current page
+    pub async fn query(&self, options: &QueryOptions) -> Result<QueryResult> {
+        let page = self.get_or_create_page().await?;
+        page.query(options).await
+    }
+
     /// Scroll the page by the given delta in pixels
     pub async fn scroll_by(&self, dx: f64, dy: f64) -> Result<()> {
         let page = self.get_or_create_page().await?;
         page.scroll_by(dx, dy).await
     }
 
+    /// Scroll by an offset, by viewport pages, to either end, or to an element
+    pub async fn scroll_page(&self, target: &ScrollTarget) -> Result<ScrollPosition> {
+        let page = self.get_or_create_page().await?;
+        page.scroll_page(target).await
+    }
+
+    /// Move the cursor to the center of the element matching `selector` and click it
+    pub async fn click_selector(&self, selector: &str) -> Result<(f64, f64)> {
+        let page = self.get_or_create_page().await?;
+        let (x, y) = page.element_center(selector).await?;
+        page.move_mouse(x, y).await?;
+        page.click_at_current().await
+    }
+
+    /// Resize the viewport; later screenshots use the new size
+    pub async fn set_viewport_size(&self, width: u32, height: u32) -> Result<ViewportResult> {
+        let page = self.get_or_create_page().await?;
+        let (device_scale_factor, mobile) = {
+            let mut config = self.config.write().await;
+            config.viewport.width = width;
+            config.viewport.height = height;
+            (config.viewport.device_scale_factor, config.viewport.mobile)
+        };
+        page.set_viewport(SetViewportParams {
+            width,
+            height,
+            device_scale_factor: Some(device_scale_factor),
+            mobile: Some(mobile),
+        })
+        .await
+    }
+
     /// Navigate browser history backward one entry
     pub async fn history_back(&self) -> Result<()> {
         let page = self.get_or_create_page().await?;
@@ -2154,6 +2231,49 @@ impl BrowserManager {
         page.inject_js(&script).await
     }
 
+    /// Look for a sign-in form on the current page.
+    pub async fn detect_login_form(&self) -> Result<Option<LoginForm>> {
+        let page = self.get_or_create_page().await?;
+        page.detect_login_form().await
+    }
+
+    /// Fill the current page's sign-in form; returns whether it was submitted.
+    pub async fn fill_login_form(&self, credentials: &Credentials, submit: bool) -> Result<bool> {
+        let page = self.get_or_create_page().await?;
+        page.fill_login_form(credentials, submit).await
+    }
+
+    /// Realm of the latest HTTP Basic challenge from `origin`, if any.
+    pub async fn basic_auth_realm(&self, origin: &str) -> Result<Option<String>> {
+        let page = self.get_or_create_page().await?;
+        Ok(page.basic_auth_realm(origin).await)
+    }
+
+    /// Answer HTTP authentication challenges from `origin` with `credentials`.
+    pub async fn provide_basic_auth(&self, origin: &str, credentials: Credentials) -> Result<()> {
+        let page = self.get_or_create_page().await?;
+        page.provide_basic_auth(origin, credentials).await
+    }
+
+    /// Failed requests and HTTP error responses on the current page, newest last.
+    pub async fn get_network_failures(&self, limit: usize) -> Result<Vec<NetworkFailure>> {
+        let page = self.get_or_create_page().await?;
+        Ok(page.get_network_failures_tail(limit).await)
+    }
+
+    /// Problems the current page reported at or after `since_ms`; `None` when no page is open.
+    pub async fn diagnostics_since(&self, since_ms: i128) -> Option<PageDiagnostics> {
+        let page = self.page.lock().await.clone()?;
+        Some(page.diagnostics_since(since_ms).await)
+    }
+
+    /// The current page's network activity as a HAR document and its request
+    /// count; `None` when no page is open.
+    pub async fn har(&self) -> Option<(Value, usize)> {
+        let page = self.page.lock().await.clone()?;
+        Some(page.har().await)
+    }
+
     /// Execute an arbitrary CDP command against the active page session
     pub async fn execute_cdp(
         &self,
@@ -4,6 +4,13 @@ use crate::config::BrowserConfig;
 use crate::config::ImageFormat;
 use crate::config::ViewportConfig;
 use crate::config::WaitStrategy;
+use crate::forms;
+use crate::forms::FormAction;
+use crate::forms::FormOutcome;
+use crate::forms::FormTarget;
+use crate::query;
+use crate::query::QueryOptions;
+use crate::query::QueryResult;
 use chromiumoxide::cdp::browser_protocol::input::DispatchKeyEventParams;
 use chromiumoxide::cdp::browser_protocol::input::DispatchKeyEventType;
 use chromiumoxide::cdp::browser_protocol::input::DispatchMouseEventParams;
@@ -19,8 +26,23 @@ use chromiumoxide::cdp::browser_protocol::page::CaptureScreenshotParams;
 use chromiumoxide::page::Page as CdpPage;
 use chromiumoxide::cdp::js_protocol::runtime as cdp_runtime;
 use chromiumoxide::cdp::browser_protocol::log as cdp_log;
+use chromiumoxide::cdp::browser_protocol::network as cdp_network;
+use crate::diagnostics;
+use crate::diagnostics::NetworkFailure;
+use crate::diagnostics::NetworkLog;
+use crate::diagnostics::PageDiagnostics;
+use crate::har::HarLog;
+use crate::har::HarRequest;
+use crate::har::HarResponse;
+use crate::login;
+use crate::login::Credentials;
+use crate::login::LoginForm;
+use chromiumoxide::cdp::browser_protocol::fetch as cdp_fetch;
+use std::collections::HashMap;
 use futures::StreamExt;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 // Use Mutex for cursor state (New)
@@ -51,6 +73,15 @@ pub struct Page {
     cursor_state: Arc<Mutex<CursorState>>,
     // Buffer for CDP-captured console logs
     console_logs: Arc<Mutex<Vec<serde_json::Value>>>,
+    // Failed requests and HTTP error responses seen via the Network domain
+    network_log: Arc<Mutex<NetworkLog>>,
+    har_log: Arc<Mutex<HarLog>>,
+    // HTTP Basic credentials by origin, answered through the Fetch domain
+    basic_auth: Arc<Mutex<HashMap<String, Credentials>>>,
+    // Credentials for the proxy Chrome was launched with
+    proxy_auth: Arc<Mutex<Option<Credentials>>>,
+    // Whether the Fetch auth responder is running
+    answering_auth: AtomicBool,
     // Screenshot path preflight cache:
     // - We strongly prefer compositor captures via from_surface(false) to avoid visible flashes in the
     //   user's real Chrome window. However, that path can be flaky or unavailable when the window is not
@@ -126,6 +157,11 @@ impl Page {
             cursor_state: Arc::new(Mutex::new(initial_cursor)),
             preflight_cache: Arc::new(Mutex::new(None)),
             console_logs: Arc::new(Mutex::new(Vec::new())),
+            network_log: Arc::new(Mutex::new(NetworkLog::default())),
+            har_log: Arc::new(Mutex::new(HarLog::default())),
+            basic_auth: Arc::new(Mutex::new(HashMap::new())),
+            proxy_auth: Arc::new(Mutex::new(None)),
+            answering_auth: AtomicBool::new(false),
         };
 
         // Register a unified bootstrap (runs on every new document):
@@ -156,15 +192,8 @@ impl Page {
                 .await
             {
                 while let Some(evt) = stream.next().await {
-                    let ts = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|d| d.as_millis() as i128)
-                        .unwrap_or(0);
-                    // Join args into a readable string; also keep raw values
-                    let text = match serde_json::to_string(&evt.args) {
-                        Ok(s) => s,
-                        Err(_) => String::new(),
-                    };
+                    let ts = diagnostics::now_unix_ms();
+                    let text = diagnostics::console_args_text(&evt.args);
                     let item = serde_json::json!({
                         "ts_unix_ms": ts,
                         "level": format!("{:?}", evt.r#type),
@@ -176,17 +205,19 @@ impl Page {
                     if buf.len() > 2000 { buf.remove(0); }
                 }
             }
+        });
 
-            // Also listen for Log.entryAdded (browser-side logs)
+        // Log.entryAdded (browser-side logs) and uncaught exceptions each need
+        // their own task; the console stream above never ends.
+        let cdp_page_events = page.cdp_page.clone();
+        let logs_buf = page.console_logs.clone();
+        tokio::spawn(async move {
             if let Ok(mut stream) = cdp_page_events
                 .event_listener::<cdp_log::EventEntryAdded>()
                 .await
             {
                 while let Some(evt) = stream.next().await {
-                    let ts = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .map(|d| d.as_millis() as i128)
-                        .unwrap_or(0);
+                    let ts = diagnostics::now_unix_ms();
                     let entry = &evt.entry;
                     let item = serde_json::json!({
                         "ts_unix_ms": ts,
@@ -203,6 +234,42 @@ impl Page {
             }
         });
 
+        let cdp_page_events = page.cdp_page.clone();
+        let logs_buf = page.console_logs.clone();
+        tokio::spawn(async move {
+            if let Ok(mut stream) = cdp_page_events
+                .event_listener::<cdp_runtime::EventExceptionThrown>()
+                .await
+            {
+                while let Some(evt) = stream.next().await {
+                    let details = &evt.exception_details;
+                    let message = details
+                        .exception
+                        .as_ref()
+                        .and_then(|exception| exception.description.clone())
+                        .unwrap_or_else(|| details.text.clone());
+                    let item = serde_json::json!({
+                        "ts_unix_ms": diagnostics::now_unix_ms(),
+                        "level": "Error",
+                        "message": message,
+                        "source": "cdp:exception",
+                        "url": details.url,
+                        "line": details.line_number
+                    });
+                    let mut buf = logs_buf.lock().await;
+                    buf.push(item);
+                    if buf.len() > 2000 { buf.remove(0); }
+                }
+            }
+        });
+
+        let cdp_page_events = page.cdp_page.clone();
+        let network_log = page.network_log.clone();
+        let har_log = page.har_log.clone();
+        tokio::spawn(async move {
+            Self::record_network_activity(&cdp_page_events, network_log, har_log).await;
+        });
+
         page
     }
 
@@ -1534,12 +1601,281 @@ impl Page {
         Ok(result.value().cloned().unwrap_or(serde_json::Value::Null))
     }
 
+    /// Fill, select or submit a form element located by selector or label.
+    pub async fn form_action(
+        &self,
+        target: &FormTarget,
+        action: &FormAction,
+    ) -> Result<FormOutcome> {
+        let value = self.inject_js(&forms::form_script(target, action)).await?;
+        forms::parse_outcome(action, value)
+    }
+
+    /// Run a CSS selector query and collect the matched elements.
+    pub async fn query(&self, options: &QueryOptions) -> Result<QueryResult> {
+        let value = self.inject_js(&query::query_script(options)).await?;
+        query::parse_result(value)
+    }
+
     pub async fn close(&self) -> Result<()> {
         // Note: chromiumoxide's close() takes ownership, so we can't call it on Arc<Page>
         // The page will be closed when the Arc is dropped
         Ok(())
     }
 
+    /// Feed Network domain events into the failure log and the HAR recorder.
+    async fn record_network_activity(
+        cdp_page: &CdpPage,
+        network_log: Arc<Mutex<NetworkLog>>,
+        har_log: Arc<Mutex<HarLog>>,
+    ) {
+        enum NetworkEvent {
+            Request(Arc<cdp_network::EventRequestWillBeSent>),
+            Response(Arc<cdp_network::EventResponseReceived>),
+            Finished(Arc<cdp_network::EventLoadingFinished>),
+            Failed(Arc<cdp_network::EventLoadingFailed>),
+        }
+
+        let _ = cdp_page.execute(cdp_network::EnableParams::default()).await;
+        let (Ok(requests), Ok(responses), Ok(finished), Ok(failures)) = (
+            cdp_page.event_listener::<cdp_network::EventRequestWillBeSent>().await,
+            cdp_page.event_listener::<cdp_network::EventResponseReceived>().await,
+            cdp_page.event_listener::<cdp_network::EventLoadingFinished>().await,
+            cdp_page.event_listener::<cdp_network::EventLoadingFailed>().await,
+        ) else {
+            warn!("Failed to subscribe to network events; network activity will not be recorded");
+            return;
+        };
+        let mut events = futures::stream::select_all([
+            requests.map(NetworkEvent::Request).boxed(),
+            responses.map(NetworkEvent::Response).boxed(),
+            finished.map(NetworkEvent::Finished).boxed(),
+            failures.map(NetworkEvent::Failed).boxed(),
+        ]);
+        while let Some(event) = events.next().await {
+            let mut log = network_log.lock().await;
+            let mut har = har_log.lock().await;
+            match event {
+                NetworkEvent::Request(evt) => {
+                    let request_id = evt.request_id.inner();
+                    let resource_type = evt.r#type.as_ref().map(|kind| format!("{kind:?}"));
+                    if let Some(redirect) = evt.redirect_response.as_ref() {
+                        har.redirected(
+                            request_id,
+                            HarResponse::from(redirect),
+                            &evt.request.url,
+                            *evt.timestamp.inner(),
+                        );
+                    }
+                    har.request_will_be_sent(HarRequest {
+                        request_id,
+                        wall_time_s: *evt.wall_time.inner(),
+                        monotonic_s: *evt.timestamp.inner(),
+                        method: &evt.request.method,
+                        url: &evt.request.url,
+                        headers: evt.request.headers.inner(),
+                        post_data: evt.request.post_data.clone(),
+                        resource_type: resource_type.clone(),
+                    });
+                    log.request_started(
+                        request_id,
+                        &evt.request.method,
+                        &evt.request.url,
+                        resource_type,
+                    )
+                }
+                NetworkEvent::Response(evt) => {
+                    har.response_received(evt.request_id.inner(), HarResponse::from(&evt.response));
+                    let auth_challenge = evt.response.headers.inner().as_object().and_then(|headers| {
+                        headers
+                            .iter()
+                            .find(|(name, _)| name.eq_ignore_ascii_case("www-authenticate"))
+                            .and_then(|(_, value)| value.as_str().map(str::to_string))
+                    });
+                    log.response(
+                        evt.request_id.inner(),
+                        evt.response.status,
+                        &evt.response.status_text,
+                        auth_challenge,
+                        diagnostics::now_unix_ms(),
+                    )
+                }
+                NetworkEvent::Finished(evt) => har.finished(
+                    evt.request_id.inner(),
+                    *evt.timestamp.inner(),
+                    evt.encoded_data_length,
+                ),
+                NetworkEvent::Failed(evt) => {
+                    har.failed(evt.request_id.inner(), *evt.timestamp.inner(), &evt.error_text);
+                    log.failed(
+                        evt.request_id.inner(),
+                        &evt.error_text,
+                        evt.canceled.unwrap_or(false),
+                        evt.blocked_reason.as_ref().map(|reason| format!("{reason:?}")),
+                        diagnostics::now_unix_ms(),
+                    )
+                }
+            }
+        }
+    }
+
+    /// Look for a sign-in form (a single visible password field) on the page.
+    pub async fn detect_login_form(&self) -> Result<Option<LoginForm>> {
+        let value = self.inject_js(&login::detect_script()).await?;
+        login::parse_detect(value)
+    }
+
+    /// Fill the sign-in form with `credentials`; returns whether it was submitted.
+    pub async fn fill_login_form(&self, credentials: &Credentials, submit: bool) -> Result<bool> {
+        let value = self.inject_js(&login::fill_script(credentials, submit)).await?;
+        login::parse_fill(value)
+    }
+
+    /// Realm of the latest HTTP Basic challenge a document on `origin` answered with.
+    pub async fn basic_auth_realm(&self, origin: &str) -> Option<String> {
+        self.network_log.lock().await.basic_auth_realm(origin)
+    }
+
+    /// Answer HTTP Basic/Digest challenges from `origin` with `credentials`
+    /// from now on. Only requests to origins with credentials are intercepted.
+    pub async fn provide_basic_auth(&self, origin: &str, credentials: Credentials) -> Result<()> {
+        self.basic_auth.lock().await.insert(origin.to_string(), credentials);
+        self.intercept_auth_challenges().await
+    }
+
+    /// Answer challenges from the proxy Chrome was launched with using
+    /// `credentials`. Any request may be challenged, so all are intercepted.
+    pub async fn provide_proxy_auth(&self, credentials: Credentials) -> Result<()> {
+        *self.proxy_auth.lock().await = Some(credentials);
+        self.intercept_auth_challenges().await
+    }
+
+    async fn intercept_auth_challenges(&self) -> Result<()> {
+        let patterns: Vec<cdp_fetch::RequestPattern> = if self.proxy_auth.lock().await.is_some() {
+            vec![cdp_fetch::RequestPattern::builder().url_pattern("*").build()]
+        } else {
+            self.basic_auth
+                .lock()
+                .await
+                .keys()
+                .map(|origin| {
+                    cdp_fetch::RequestPattern::builder()
+                        .url_pattern(format!("{origin}/*"))
+                        .build()
+                })
+                .collect()
+        };
+        self.cdp_page
+            .execute(
+                cdp_fetch::EnableParams::builder()
+                    .patterns(patterns)
+                    .handle_auth_requests(true)
+                    .build(),
+            )
+            .await?;
+        if !self.answering_auth.swap(true, Ordering::AcqRel) {
+            let cdp_page = self.cdp_page.clone();
+            let basic_auth = self.basic_auth.clone();
+            let proxy_auth = self.proxy_auth.clone();
+            tokio::spawn(async move {
+                Self::answer_auth_challenges(&cdp_page, basic_auth, proxy_auth).await;
+            });
+        }
+        Ok(())
+    }
+
+    async fn answer_auth_challenges(
+        cdp_page: &CdpPage,
+        basic_auth: Arc<Mutex<HashMap<String, Credentials>>>,
+        proxy_auth: Arc<Mutex<Option<Credentials>>>,
+    ) {
+        enum FetchEvent {
+            Paused(Arc<cdp_fetch::EventRequestPaused>),
+            Auth(Arc<cdp_fetch::EventAuthRequired>),
+        }
+
+        let (Ok(paused), Ok(auth)) = (
+            cdp_page.event_listener::<cdp_fetch::EventRequestPaused>().await,
+            cdp_page.event_listener::<cdp_fetch::EventAuthRequired>().await,
+        ) else {
+            warn!("Failed to subscribe to fetch events; HTTP authentication will not be answered");
+            return;
+        };
+        let mut events = futures::stream::select_all([
+            paused.map(FetchEvent::Paused).boxed(),
+            auth.map(FetchEvent::Auth).boxed(),
+        ]);
+        while let Some(event) = events.next().await {
+            match event {
+                FetchEvent::Paused(evt) => {
+                    // Intercepted only so challenges reach us; let it through untouched.
+                    let params = cdp_fetch::ContinueRequestParams::new(evt.request_id.clone());
+                    if let Err(e) = cdp_page.execute(params).await {
+                        debug!("Failed to continue intercepted request: {}", e);
+                    }
+                }
+                FetchEvent::Auth(evt) => {
+                    let challenge = &evt.auth_challenge;
+                    let from_proxy = matches!(
+                        challenge.source,
+                        Some(cdp_fetch::AuthChallengeSource::Proxy)
+                    );
+                    let known = if from_proxy {
+                        proxy_auth.lock().await.clone()
+                    } else {
+                        let origin = login::origin_of(&challenge.origin)
+                            .unwrap_or_else(|| challenge.origin.clone());
+                        basic_auth.lock().await.get(&origin).cloned()
+                    };
+                    let response = match known {
+                        Some(credentials) => cdp_fetch::AuthChallengeResponse::builder()
+                            .response(cdp_fetch::AuthChallengeResponseResponse::ProvideCredentials)
+                            .username(credentials.username)
+                            .password(credentials.password)
+                            .build(),
+                        None => cdp_fetch::AuthChallengeResponse::builder()
+                            .response(cdp_fetch::AuthChallengeResponseResponse::Default)
+                            .build(),
+                    };
+                    let response = match response {
+                        Ok(response) => response,
+                        Err(e) => {
+                            warn!("Failed to build auth challenge response: {}", e);
+                            continue;
+                        }
+                    };
+                    let params =
+                        cdp_fetch::ContinueWithAuthParams::new(evt.request_id.clone(), response);
+                    if let Err(e) = cdp_page.execute(params).await {
+                        warn!("Failed to answer auth challenge: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The most recent failed requests; all retained ones when `limit` is 0.
+    pub async fn get_network_failures_tail(&self, limit: usize) -> Vec<NetworkFailure> {
+        self.network_log.lock().await.tail(limit)
+    }
+
+    /// Network activity since the page was created as a HAR 1.2 document,
+    /// with the number of requests it holds.
+    pub async fn har(&self) -> (serde_json::Value, usize) {
+        let har = self.har_log.lock().await;
+        (har.to_har(), har.len())
+    }
+
+    /// Console errors/warnings and failed requests recorded at or after `since_ms`.
+    pub async fn diagnostics_since(&self, since_ms: i128) -> PageDiagnostics {
+        let console = {
+            let buf = self.console_logs.lock().await;
+            diagnostics::console_problems_since(&buf, since_ms)
+        };
+        let network = self.network_log.lock().await.since(since_ms);
+        PageDiagnostics { console, network }
+    }
+
     /// Return a snapshot (tail) of the CDP-captured console buffer.
     pub async fn get_console_logs_tail(&self, lines: Option<usize>) -> serde_json::Value {
         let buf = self.console_logs.lock().await;
@@ -2279,6 +2615,55 @@ impl Page {
         Ok(())
     }
 
+    /// Scroll the window (or bring an element into view) and report where
+    /// the page ended up, so callers can tell when an infinite list ran out.
+    pub async fn scroll_page(&self, target: &ScrollTarget) -> Result<ScrollPosition> {
+        let action = match target {
+            ScrollTarget::By { dx, dy } => format!("window.scrollBy({dx}, {dy});"),
+            ScrollTarget::Pages(pages) => {
+                format!("window.scrollBy(0, Math.round(window.innerHeight * 0.9 * {pages}));")
+            }
+            ScrollTarget::Top => "window.scrollTo(0, 0);".to_string(),
+            ScrollTarget::Bottom => {
+                "window.scrollTo(0, document.documentElement.scrollHeight);".to_string()
+            }
+            ScrollTarget::Element(selector) => format!(
+                "const el = document.querySelector({}); \
+                 if (!el) return null; \
+                 el.scrollIntoView({{ block: 'center', inline: 'nearest' }});",
+                serde_json::to_string(selector).unwrap_or_default()
+            ),
+        };
+        let js = format!(
+            "(() => {{ {action} const doc = document.documentElement; \
+             return {{ x: window.scrollX, y: window.scrollY, \
+             height: doc.scrollHeight, viewport: window.innerHeight }}; }})()"
+        );
+        let value = self.inject_js(&js).await?;
+        if let (true, ScrollTarget::Element(selector)) = (value.is_null(), target) {
+            return Err(BrowserError::CdpError(format!("no element matches {selector}")));
+        }
+        serde_json::from_value(value)
+            .map_err(|e| BrowserError::CdpError(format!("unexpected scroll result: {e}")))
+    }
+
+    /// Scroll the first element matching `selector` into view and return the
+    /// viewport coordinates of its center.
+    pub async fn element_center(&self, selector: &str) -> Result<(f64, f64)> {
+        let js = format!(
+            "(() => {{ const el = document.querySelector({}); if (!el) return null; \
+             el.scrollIntoView({{ block: 'center', inline: 'nearest' }}); \
+             const r = el.getBoundingClientRect(); \
+             return [r.left + r.width / 2, r.top + r.height / 2]; }})()",
+            serde_json::to_string(selector).unwrap_or_default()
+        );
+        let value = self.inject_js(&js).await?;
+        match value.as_array().map(|point| (point.first(), point.get(1))) {
+            Some((Some(x), Some(y))) => Ok((x.as_f64().unwrap_or(0.0), y.as_f64().unwrap_or(0.0))),
+            _ => Err(BrowserError::CdpError(format!("no element matches {selector}"))),
+        }
+    }
+
     /// Navigate browser history backward one entry
     pub async fn go_back(&self) -> Result<()> {
         debug!("History back");
@@ -2386,6 +2771,34 @@ pub struct SetViewportParams {
     pub mobile: Option<bool>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrollTarget {
+    By { dx: f64, dy: f64 },
+    /// Viewport heights; negative values scroll up. A page step keeps a
+    /// little overlap so rows on the fold are not skipped.
+    Pages(f64),
+    Top,
+    Bottom,
+    /// CSS selector of an element to bring into view.
+    Element(String),
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub struct ScrollPosition {
+    pub x: f64,
+    pub y: f64,
+    /// Total scrollable height of the document.
+    pub height: f64,
+    /// Height of the visible area.
+    pub viewport: f64,
+}
+
+impl ScrollPosition {
+    pub fn at_bottom(&self) -> bool {
+        self.y + self.viewport >= self.height - 1.0
+    }
+}
+
 #[derive(Debug, serde::Serialize)]
 pub struct ViewportResult {
     pub width: u32,
@@ -0,0 +1,73 @@
+//! Named, persistent browser profiles.
+//!
+//! A profile is a Chrome user data directory under `<code_home>/browser/<name>`,
+//! so cookies and localStorage survive across sessions. Sites only need to be
+//! logged into once per profile.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Directory holding all named profiles.
+pub fn profiles_root(code_home: &Path) -> PathBuf {
+    code_home.join("browser")
+}
+
+/// User data directory for profile `name`. Names are restricted to
+/// `[A-Za-z0-9_-]` so they can never escape the profiles directory.
+pub fn profile_dir(code_home: &Path, name: &str) -> Result<PathBuf, String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "invalid browser profile name '{name}'; use letters, digits, '-' or '_'"
+        ));
+    }
+    Ok(profiles_root(code_home).join(name))
+}
+
+/// Names of the profiles that exist on disk, sorted.
+pub fn list_profiles(code_home: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(profiles_root(code_home)) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Delete everything stored for profile `name`. Returns `false` when the
+/// profile did not exist.
+pub fn clear_profile(code_home: &Path, name: &str) -> io::Result<bool> {
+    let dir = profile_dir(code_home, name).map_err(io::Error::other)?;
+    match std::fs::remove_dir_all(&dir) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_live_under_code_home_and_can_be_cleared() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let dir = profile_dir(home.path(), "work").expect("valid name");
+        assert_eq!(dir, home.path().join("browser").join("work"));
+        assert!(profile_dir(home.path(), "../etc").is_err());
+
+        std::fs::create_dir_all(dir.join("Default")).expect("create profile");
+        assert_eq!(list_profiles(home.path()), vec!["work".to_string()]);
+        assert!(clear_profile(home.path(), "work").expect("clear"));
+        assert!(!clear_profile(home.path(), "work").expect("clear again"));
+        assert!(list_profiles(home.path()).is_empty());
+    }
+}
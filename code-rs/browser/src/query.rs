@@ -0,0 +1,152 @@
+//! CSS selector queries against the current page.
+//!
+//! Returns just the matched elements' text and attributes (and optionally
+//! their HTML), which is far cheaper for the model than a converted copy of
+//! the whole page when it only needs a price, a table row or a list of links.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::BrowserError;
+use crate::Result;
+
+const QUERY_JS: &str = include_str!("js/query.js");
+
+/// Matches returned when the caller does not ask for a limit.
+pub const DEFAULT_QUERY_LIMIT: usize = 20;
+/// Hard cap on matches per query.
+pub const MAX_QUERY_LIMIT: usize = 200;
+
+const MAX_TEXT_CHARS: usize = 500;
+const MAX_HTML_CHARS: usize = 2_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryOptions {
+    pub selector: String,
+    pub limit: usize,
+    /// Include each match's `outerHTML`.
+    pub include_html: bool,
+    /// Only report these attributes; `None` reports all but inline styles
+    /// and event handlers.
+    pub attributes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct QueryMatch {
+    pub tag: String,
+    pub text: String,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+    #[serde(default)]
+    pub html: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryResult {
+    /// Number of elements the selector matched, including any past the limit.
+    pub total: usize,
+    pub matches: Vec<QueryMatch>,
+}
+
+#[derive(Deserialize)]
+struct RawResult {
+    ok: bool,
+    #[serde(default)]
+    total: usize,
+    #[serde(default)]
+    matches: Vec<QueryMatch>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Script that runs the query and evaluates to a result object for
+/// [`parse_result`].
+pub fn query_script(options: &QueryOptions) -> String {
+    let request = json!({
+        "selector": options.selector,
+        "limit": options.limit.clamp(1, MAX_QUERY_LIMIT),
+        "html": options.include_html,
+        "attributes": options.attributes,
+        "maxText": MAX_TEXT_CHARS,
+        "maxHtml": MAX_HTML_CHARS,
+    });
+    format!("{}({request})", QUERY_JS.trim_end())
+}
+
+/// Interpret the value returned by [`query_script`].
+pub fn parse_result(value: serde_json::Value) -> Result<QueryResult> {
+    let raw: RawResult = serde_json::from_value(value)
+        .map_err(|err| BrowserError::QueryError(format!("unexpected page response: {err}")))?;
+    if !raw.ok {
+        return Err(BrowserError::QueryError(
+            raw.error.unwrap_or_else(|| "query failed".to_string()),
+        ));
+    }
+    Ok(QueryResult {
+        total: raw.total,
+        matches: raw.matches,
+    })
+}
+
+impl QueryResult {
+    /// Plain-text listing of the matches for the model.
+    pub fn render(&self, selector: &str) -> String {
+        if self.matches.is_empty() {
+            return format!("No elements match {selector}");
+        }
+        let mut out = if self.total > self.matches.len() {
+            format!(
+                "{} elements match {selector}; showing the first {}.\n",
+                self.total,
+                self.matches.len()
+            )
+        } else {
+            format!("{} element(s) match {selector}.\n", self.total)
+        };
+        for (idx, found) in self.matches.iter().enumerate() {
+            out.push_str(&format!("\n[{}] <{}>", idx + 1, found.tag));
+            for (name, value) in &found.attributes {
+                out.push_str(&format!(" {name}=\"{value}\""));
+            }
+            out.push('\n');
+            if !found.text.is_empty() {
+                out.push_str(&found.text);
+                out.push('\n');
+            }
+            if let Some(html) = found.html.as_deref() {
+                out.push_str(&format!("```html\n{html}\n```\n"));
+            }
+        }
+        out.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_matches_and_reports_truncation() {
+        let result = parse_result(json!({
+            "ok": true,
+            "total": 3,
+            "matches": [
+                {"tag": "a", "text": "Docs", "attributes": {"href": "https://example.com/docs"}},
+                {"tag": "a", "text": "", "attributes": {}, "html": "<a></a>"},
+            ],
+        }))
+        .expect("result");
+        assert_eq!(
+            result.render("nav a"),
+            "3 elements match nav a; showing the first 2.\n\n\
+             [1] <a> href=\"https://example.com/docs\"\nDocs\n\n\
+             [2] <a>\n```html\n<a></a>\n```"
+        );
+
+        let err = parse_result(json!({"ok": false, "error": "Invalid selector: ]"}))
+            .expect_err("failure");
+        assert_eq!(err.to_string(), "Query failed: Invalid selector: ]");
+    }
+}
@@ -0,0 +1,155 @@
+//! Value hints baked into `code completions <shell>` scripts.
+//!
+//! clap_complete only knows what the parser declares, so before generating a
+//! script the command tree is annotated with the current `--profile` names
+//! from config.toml, the built-in model names, and the slash commands `code
+//! exec` accepts as a prompt. The script is a snapshot: regenerate it after
+//! adding a profile or subagent command.
+
+use clap::Command;
+use clap::builder::PossibleValue;
+use clap::builder::PossibleValuesParser;
+use code_common::model_presets::all_model_presets;
+use code_core::config::find_code_home;
+use code_core::config::load_config_as_toml;
+
+/// Slash commands every `code exec` prompt understands.
+const EXEC_SLASH_COMMANDS: [&str; 5] = ["/plan", "/solve", "/code", "/review", "/auto"];
+
+#[derive(Debug, Default)]
+pub(crate) struct CompletionHints {
+    profiles: Vec<&'static str>,
+    models: Vec<&'static str>,
+    slash_commands: Vec<&'static str>,
+}
+
+impl CompletionHints {
+    /// Reads hints from the user's config.toml. A missing or unreadable
+    /// config still yields model and slash-command hints.
+    pub(crate) fn load() -> Self {
+        let config = find_code_home()
+            .and_then(|home| load_config_as_toml(&home))
+            .ok();
+        let table_names = |key: &str| -> Vec<String> {
+            config
+                .as_ref()
+                .and_then(|config| config.get(key))
+                .and_then(|value| value.as_table())
+                .map(|table| table.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        let subagent_commands: Vec<String> = config
+            .as_ref()
+            .and_then(|config| config.get("subagents"))
+            .and_then(|subagents| subagents.get("commands"))
+            .and_then(|commands| commands.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|command| command.get("name")?.as_str())
+            .map(|name| format!("/{name}"))
+            .collect();
+        Self::new(table_names("profiles"), subagent_commands)
+    }
+
+    fn new(profiles: Vec<String>, subagent_commands: Vec<String>) -> Self {
+        let mut models: Vec<&'static str> = all_model_presets()
+            .iter()
+            .filter(|preset| preset.show_in_picker)
+            .map(|preset| preset.model.as_str())
+            .collect();
+        models.dedup();
+        let mut slash_commands = EXEC_SLASH_COMMANDS.to_vec();
+        for command in subagent_commands {
+            if !slash_commands.contains(&command.as_str()) {
+                slash_commands.push(leak(command));
+            }
+        }
+        Self {
+            profiles: profiles.into_iter().map(leak).collect(),
+            models,
+            slash_commands,
+        }
+    }
+}
+
+/// clap is built without its `string` feature, so possible values must be
+/// `'static`. The process exits right after printing the script.
+fn leak(value: String) -> &'static str {
+    value.leak()
+}
+
+/// Adds the hints as possible values to `--profile`, `--model` and the
+/// prompt positional of `cmd` and all of its subcommands.
+pub(crate) fn annotate(mut cmd: Command, hints: &CompletionHints) -> Command {
+    let targets: Vec<(clap::Id, &[&'static str])> = cmd
+        .get_arguments()
+        .filter_map(|arg| {
+            let values = match arg.get_id().as_str() {
+                "config_profile" => &hints.profiles,
+                "model" if arg.get_long() == Some("model") => &hints.models,
+                "prompt" if arg.is_positional() => &hints.slash_commands,
+                _ => return None,
+            };
+            (!values.is_empty()).then(|| (arg.get_id().clone(), values.as_slice()))
+        })
+        .collect();
+    for (id, values) in targets {
+        cmd = cmd.mut_arg(id, |arg| {
+            arg.value_parser(PossibleValuesParser::new(
+                values.iter().copied().map(PossibleValue::new),
+            ))
+            .hide_possible_values(true)
+        });
+    }
+
+    let subcommands: Vec<String> = cmd
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        cmd = cmd.mut_subcommand(name, |sub| annotate(sub, hints));
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+    use pretty_assertions::assert_eq;
+
+    fn possible_values(cmd: &Command, id: &str) -> Vec<String> {
+        cmd.get_arguments()
+            .find(|arg| arg.get_id() == id)
+            .map(|arg| {
+                arg.get_possible_values()
+                    .iter()
+                    .map(|value| value.get_name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn profiles_models_and_slash_commands_become_possible_values() {
+        let hints = CompletionHints::new(
+            vec!["work".to_string(), "o3".to_string()],
+            vec!["/audit".to_string(), "/plan".to_string()],
+        );
+        let cmd = Command::new("code").subcommand(
+            Command::new("exec")
+                .arg(Arg::new("config_profile").long("profile"))
+                .arg(Arg::new("model").long("model"))
+                .arg(Arg::new("prompt")),
+        );
+        let cmd = annotate(cmd, &hints);
+        let exec = cmd.find_subcommand("exec").expect("exec subcommand");
+
+        assert_eq!(possible_values(exec, "config_profile"), vec!["work", "o3"]);
+        assert_eq!(
+            possible_values(exec, "prompt"),
+            vec!["/plan", "/solve", "/code", "/review", "/auto", "/audit"]
+        );
+        assert!(!possible_values(exec, "model").is_empty());
+    }
+}
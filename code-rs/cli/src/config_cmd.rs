@@ -0,0 +1,68 @@
+use anyhow::Context;
+use anyhow::Result;
+use code_core::config::apply_migration;
+use code_core::config::find_code_home;
+use code_core::config::load_config_as_toml_with_cli_overrides;
+use code_core::config::plan_migration;
+
+/// Subcommands:
+/// - `migrate` — rewrite renamed keys and copy files left in the legacy
+///   `~/.codex` home into the current layout (with `--dry-run`)
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Update an outdated config.toml and auth layout, keeping a backup.
+    Migrate(MigrateArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct MigrateArgs {
+    /// List the changes without writing anything.
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+}
+
+impl ConfigCli {
+    pub fn run(self) -> Result<()> {
+        match self.subcommand {
+            ConfigSubcommand::Migrate(args) => run_migrate(args),
+        }
+    }
+}
+
+fn run_migrate(args: MigrateArgs) -> Result<()> {
+    let code_home = find_code_home().context("failed to resolve CODE_HOME")?;
+    // The auth.json check must look in the configured credential store.
+    let store_mode = load_config_as_toml_with_cli_overrides(&code_home, Vec::new())
+        .with_context(|| format!("failed to read the config in {}", code_home.display()))?
+        .cli_auth_credentials_store
+        .unwrap_or_default();
+    code_core::auth_store::set_store_mode(store_mode);
+    let plan = plan_migration(&code_home)
+        .with_context(|| format!("failed to inspect {}", code_home.display()))?;
+    if plan.is_empty() {
+        println!("Config is already up to date.");
+        return Ok(());
+    }
+
+    for change in &plan.changes {
+        println!("{change}");
+    }
+    if args.dry_run {
+        println!("Dry run: nothing was changed.");
+        return Ok(());
+    }
+
+    let backup = apply_migration(&code_home, &plan)
+        .with_context(|| format!("failed to migrate {}", code_home.display()))?;
+    if let Some(backup) = backup {
+        println!("Previous config.toml saved as {}", backup.display());
+    }
+    println!("Applied {} change(s).", plan.changes.len());
+    Ok(())
+}
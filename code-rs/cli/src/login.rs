@@ -1,18 +1,29 @@
 use code_app_server_protocol::AuthMode;
 use code_common::CliConfigOverrides;
 use code_core::CodexAuth;
+use code_core::ModelProviderInfo;
 use code_core::auth::CLIENT_ID;
 use code_core::auth::OPENAI_API_KEY_ENV_VAR;
+use code_core::auth::activate_profile;
 use code_core::auth::login_with_api_key;
 use code_core::auth::logout;
+use code_core::auth_accounts;
+use code_core::auth_accounts::BillingTarget;
 use code_core::config::Config;
 use code_core::config::ConfigOverrides;
+use code_core::provider_keys;
 use code_login::ServerOptions;
 use code_login::run_device_code_login;
 use code_login::run_login_server;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use std::env;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub async fn login_with_chatgpt(code_home: PathBuf, originator: String) -> std::io::Result<()> {
@@ -27,17 +38,21 @@ pub async fn login_with_chatgpt(code_home: PathBuf, originator: String) -> std::
     server.block_until_done().await
 }
 
-pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) -> ! {
+pub async fn run_login_with_chatgpt(
+    cli_config_overrides: CliConfigOverrides,
+    profile: Option<String>,
+) -> ! {
     let config = load_config_or_exit(cli_config_overrides);
 
     match login_with_chatgpt(
-        config.code_home,
+        config.code_home.clone(),
         config.responses_originator_header.clone(),
     )
     .await
     {
         Ok(_) => {
             eprintln!("Successfully logged in");
+            name_active_account_or_exit(&config.code_home, profile.as_deref());
             std::process::exit(0);
         }
         Err(e) => {
@@ -50,12 +65,18 @@ pub async fn run_login_with_chatgpt(cli_config_overrides: CliConfigOverrides) ->
 pub async fn run_login_with_api_key(
     cli_config_overrides: CliConfigOverrides,
     api_key: String,
+    profile: Option<String>,
+    billing: BillingTarget,
 ) -> ! {
     let config = load_config_or_exit(cli_config_overrides);
 
     match login_with_api_key(&config.code_home, &api_key) {
         Ok(_) => {
             eprintln!("Successfully logged in");
+            name_active_account_or_exit(&config.code_home, profile.as_deref());
+            if billing != BillingTarget::default() {
+                set_active_account_billing_or_exit(&config.code_home, billing);
+            }
             std::process::exit(0);
         }
         Err(e) => {
@@ -97,10 +118,11 @@ pub async fn run_login_with_device_code(
     cli_config_overrides: CliConfigOverrides,
     issuer_base_url: Option<String>,
     client_id: Option<String>,
+    profile: Option<String>,
 ) -> ! {
     let config = load_config_or_exit(cli_config_overrides);
     let mut opts = ServerOptions::new(
-        config.code_home,
+        config.code_home.clone(),
         client_id.unwrap_or(CLIENT_ID.to_string()),
         config.responses_originator_header.clone(),
     );
@@ -110,6 +132,7 @@ pub async fn run_login_with_device_code(
     match run_device_code_login(opts).await {
         Ok(()) => {
             eprintln!("Successfully logged in");
+            name_active_account_or_exit(&config.code_home, profile.as_deref());
             std::process::exit(0);
         }
         Err(e) => {
@@ -119,8 +142,96 @@ pub async fn run_login_with_device_code(
     }
 }
 
+/// Save the account that was just signed in under `profile`.
+fn name_active_account_or_exit(code_home: &Path, profile: Option<&str>) {
+    let Some(profile) = profile else {
+        return;
+    };
+    let active = match auth_accounts::get_active_account_id(code_home) {
+        Ok(Some(active)) => active,
+        Ok(None) => {
+            eprintln!("Could not save profile '{profile}': no active account after login");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Could not save profile '{profile}': {e}");
+            std::process::exit(1);
+        }
+    };
+    match auth_accounts::set_account_profile(code_home, &active, Some(profile)) {
+        Ok(account) => {
+            let name = account.profile.unwrap_or_default();
+            eprintln!("Saved as profile '{name}'. Switch to it with `code login switch {name}`.");
+        }
+        Err(e) => {
+            eprintln!("Could not save profile '{profile}': {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Bill the account that was just signed in to `billing`.
+fn set_active_account_billing_or_exit(code_home: &Path, billing: BillingTarget) {
+    let result = auth_accounts::get_active_account_id(code_home).and_then(|active| {
+        let active = active.ok_or_else(|| std::io::Error::other("no active account after login"))?;
+        auth_accounts::set_account_billing(code_home, &active, billing)
+    });
+    match result {
+        Ok(account) => {
+            if let Some(organization) = account.organization {
+                eprintln!("Organization: {organization}");
+            }
+            if let Some(project) = account.project {
+                eprintln!("Project: {project}");
+            }
+        }
+        Err(e) => {
+            eprintln!("Could not save the organization and project: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+pub async fn run_login_switch(cli_config_overrides: CliConfigOverrides, profile: String) -> ! {
+    let config = load_config_or_exit(cli_config_overrides);
+
+    match activate_profile(&config.code_home, &profile) {
+        Ok(_) => {
+            eprintln!("Switched to profile '{profile}'");
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("Error switching profile: {e}");
+            let names: Vec<String> = auth_accounts::list_accounts(&config.code_home)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|account| account.profile)
+                .collect();
+            if !names.is_empty() {
+                eprintln!("Available profiles: {}", names.join(", "));
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
 pub async fn run_login_status(cli_config_overrides: CliConfigOverrides) -> ! {
     let config = load_config_or_exit(cli_config_overrides);
+    if let Some(account) = auth_accounts::get_active_account_id(&config.code_home)
+        .ok()
+        .flatten()
+        .and_then(|id| auth_accounts::find_account(&config.code_home, &id).ok().flatten())
+    {
+        if let Some(profile) = account.profile {
+            eprintln!("Profile: {profile}");
+        }
+        if let Some(organization) = account.organization {
+            eprintln!("Organization: {organization}");
+        }
+        if let Some(project) = account.project {
+            eprintln!("Project: {project}");
+        }
+    }
 
     match CodexAuth::from_code_home(
         &config.code_home,
@@ -181,6 +292,163 @@ pub async fn run_logout(cli_config_overrides: CliConfigOverrides) -> ! {
     }
 }
 
+/// Prompt on stderr and read one line from the terminal without echoing it.
+fn read_hidden_line(prompt: &str) -> std::io::Result<String> {
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    crossterm::terminal::enable_raw_mode()?;
+    let line = read_raw_line();
+    let restored = crossterm::terminal::disable_raw_mode();
+    eprintln!();
+    restored?;
+    line
+}
+
+fn read_raw_line() -> std::io::Result<String> {
+    let mut line = String::new();
+    loop {
+        let Event::Key(key) = crossterm::event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(line),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Save an API key for a configured model provider, checking it first unless
+/// `verify` is false.
+pub async fn run_auth_set(
+    cli_config_overrides: CliConfigOverrides,
+    provider_id: String,
+    verify: bool,
+) -> ! {
+    let config = load_config_or_exit(cli_config_overrides);
+    let (provider, env_key) = provider_with_env_key_or_exit(&config, &provider_id);
+
+    let mut stdin = std::io::stdin();
+    let mut buffer = String::new();
+    let read = if stdin.is_terminal() {
+        read_hidden_line(&format!("API key for {} ({env_key}): ", provider.name))
+            .map(|line| buffer = line)
+    } else {
+        stdin.read_to_string(&mut buffer).map(drop)
+    };
+    if let Err(err) = read {
+        eprintln!("Failed to read API key from stdin: {err}");
+        std::process::exit(1);
+    }
+    let api_key = buffer.trim();
+    if api_key.is_empty() {
+        eprintln!("No API key provided.");
+        std::process::exit(1);
+    }
+
+    if verify {
+        eprintln!("Checking the key against {}...", provider.name);
+        if let Err(err) = provider.verify_api_key(api_key).await {
+            eprintln!("{err}");
+            eprintln!("Nothing was saved. Pass --no-verify to save the key anyway.");
+            std::process::exit(1);
+        }
+    }
+
+    match provider_keys::set_key(&config.code_home, &env_key, api_key) {
+        Ok(()) => {
+            eprintln!("Saved the API key for {provider_id} - {}", safe_format_key(api_key));
+            if env::var(&env_key).is_ok_and(|value| !value.trim().is_empty()) {
+                eprintln!("   {env_key} is set in the environment and still takes precedence");
+            }
+            std::process::exit(0);
+        }
+        Err(err) => {
+            eprintln!("Error saving the API key: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+pub async fn run_auth_remove(cli_config_overrides: CliConfigOverrides, provider_id: String) -> ! {
+    let config = load_config_or_exit(cli_config_overrides);
+    let (_, env_key) = provider_with_env_key_or_exit(&config, &provider_id);
+
+    match provider_keys::remove_key(&config.code_home, &env_key) {
+        Ok(true) => {
+            eprintln!("Removed the saved API key for {provider_id}");
+            std::process::exit(0);
+        }
+        Ok(false) => {
+            eprintln!("No API key saved for {provider_id}");
+            std::process::exit(0);
+        }
+        Err(err) => {
+            eprintln!("Error removing the API key: {err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+pub async fn run_auth_list(cli_config_overrides: CliConfigOverrides) -> ! {
+    let config = load_config_or_exit(cli_config_overrides);
+    let mut providers: Vec<(&String, &String)> = config
+        .model_providers
+        .iter()
+        .filter_map(|(id, provider)| provider.env_key.as_ref().map(|env_key| (id, env_key)))
+        .collect();
+    providers.sort();
+    if providers.is_empty() {
+        eprintln!("No configured providers read an API key from the environment");
+        std::process::exit(0);
+    }
+    for (id, env_key) in providers {
+        eprintln!("{id:<16} {env_key:<24} {}", provider_key_status(env_key));
+    }
+    std::process::exit(0);
+}
+
+/// How the key named by `env_key` is currently supplied.
+fn provider_key_status(env_key: &str) -> &'static str {
+    if env::var(env_key).is_ok_and(|value| !value.trim().is_empty()) {
+        "from environment"
+    } else if provider_keys::stored_key(env_key).is_some() {
+        "saved"
+    } else {
+        "missing"
+    }
+}
+
+fn provider_with_env_key_or_exit(
+    config: &Config,
+    provider_id: &str,
+) -> (ModelProviderInfo, String) {
+    let Some(provider) = config.model_providers.get(provider_id) else {
+        let mut known: Vec<&str> = config.model_providers.keys().map(String::as_str).collect();
+        known.sort_unstable();
+        eprintln!("Unknown provider '{provider_id}'. Configured providers: {}", known.join(", "));
+        std::process::exit(1);
+    };
+    match &provider.env_key {
+        Some(env_key) => (provider.clone(), env_key.clone()),
+        None => {
+            eprintln!(
+                "Provider '{provider_id}' does not read an API key from the environment. For OpenAI, use `code login`."
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
 fn load_config_or_exit(cli_config_overrides: CliConfigOverrides) -> Config {
     let cli_overrides = match cli_config_overrides.parse_overrides() {
         Ok(v) => v,
@@ -10,7 +10,11 @@ use code_chatgpt::apply_command::run_apply_command;
 use code_cli::LandlockCommand;
 use code_cli::SeatbeltCommand;
 use code_cli::login::read_api_key_from_stdin;
+use code_cli::login::run_auth_list;
+use code_cli::login::run_auth_remove;
+use code_cli::login::run_auth_set;
 use code_cli::login::run_login_status;
+use code_cli::login::run_login_switch;
 use code_cli::login::run_login_with_api_key;
 use code_cli::login::run_login_with_chatgpt;
 use code_cli::login::run_login_with_device_code;
@@ -20,6 +24,7 @@ mod llm;
 use llm::{LlmCli, run_llm};
 use code_common::CliConfigOverrides;
 use code_core::{entry_to_rollout_path, SessionCatalog, SessionQuery};
+use code_core::auth_accounts::BillingTarget;
 use code_core::spawn::spawn_std_command_with_retry;
 use code_protocol::protocol::SessionSource;
 use code_cloud_tasks::Cli as CloudTasksCli;
@@ -36,8 +41,13 @@ use std::path::PathBuf;
 use std::process;
 use tokio::runtime::{Builder as TokioRuntimeBuilder, Handle as TokioHandle};
 
+mod completions;
+mod config_cmd;
 mod mcp_cmd;
+mod mcp_scaffold;
 
+use crate::completions::CompletionHints;
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
 
 const CLI_COMMAND_NAME: &str = "code";
@@ -112,6 +122,12 @@ enum Subcommand {
     /// Remove stored authentication credentials.
     Logout(LogoutCommand),
 
+    /// Manage API keys for configured model providers.
+    Auth(AuthCommand),
+
+    /// Inspect and update config.toml.
+    Config(ConfigCli),
+
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
     #[clap(visible_alias = "acp")]
     Mcp(McpCli),
@@ -123,6 +139,7 @@ enum Subcommand {
     AppServer,
 
     /// Generate shell completion scripts.
+    #[clap(visible_alias = "completions")]
     Completion(CompletionCommand),
 
     /// Internal debugging commands.
@@ -164,6 +181,16 @@ enum Subcommand {
 }
 
 #[derive(Debug, Parser)]
+#[clap(after_help = "\
+The script also completes --profile names from config.toml, --model names, and
+slash commands (including [[subagents.commands]]) for the prompt argument.
+Regenerate it after changing profiles or subagent commands.
+
+Install:
+  bash        code completions bash > ~/.local/share/bash-completion/completions/code
+  zsh         code completions zsh > \"${fpath[1]}/_code\"
+  fish        code completions fish > ~/.config/fish/completions/code.fish
+  powershell  code completions powershell >> $PROFILE")]
 struct CompletionCommand {
     /// Shell to generate completions for
     #[clap(value_enum, default_value_t = Shell::Bash)]
@@ -346,6 +373,18 @@ struct LoginCommand {
     #[arg(long = "experimental_client-id", value_name = "CLIENT_ID", hide = true)]
     client_id: Option<String>,
 
+    /// Save the new sign-in as a named profile (e.g. `work`, `personal`).
+    #[arg(long = "profile", value_name = "NAME")]
+    profile: Option<String>,
+
+    /// OpenAI organization to bill for requests made with this API key.
+    #[arg(long = "organization", value_name = "ORG_ID", requires = "with_api_key")]
+    organization: Option<String>,
+
+    /// OpenAI project to bill for requests made with this API key.
+    #[arg(long = "project", value_name = "PROJECT_ID", requires = "with_api_key")]
+    project: Option<String>,
+
     #[command(subcommand)]
     action: Option<LoginSubcommand>,
 }
@@ -354,6 +393,12 @@ struct LoginCommand {
 enum LoginSubcommand {
     /// Show login status.
     Status,
+
+    /// Switch to a sign-in saved with `--profile`.
+    Switch {
+        /// Profile name.
+        name: String,
+    },
 }
 
 #[derive(Debug, Parser)]
@@ -362,6 +407,37 @@ struct LogoutCommand {
     config_overrides: CliConfigOverrides,
 }
 
+#[derive(Debug, Parser)]
+struct AuthCommand {
+    #[clap(skip)]
+    config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    action: AuthSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum AuthSubcommand {
+    /// Save an API key for a provider, read from stdin or prompted for.
+    Set {
+        /// Provider id from `model_providers` (e.g. `openrouter`).
+        provider: String,
+
+        /// Save the key without sending a test request to the provider.
+        #[arg(long = "no-verify")]
+        no_verify: bool,
+    },
+
+    /// Remove the saved API key for a provider.
+    Remove {
+        /// Provider id from `model_providers`.
+        provider: String,
+    },
+
+    /// Show which providers have an API key available.
+    List,
+}
+
 #[derive(Debug, Parser)]
 struct GenerateTsCommand {
     /// Output directory where .ts files will be written
@@ -535,12 +611,16 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
                 Some(LoginSubcommand::Status) => {
                     run_login_status(login_cli.config_overrides).await;
                 }
+                Some(LoginSubcommand::Switch { name }) => {
+                    run_login_switch(login_cli.config_overrides, name).await;
+                }
                 None => {
                     if login_cli.use_device_code {
                         run_login_with_device_code(
                             login_cli.config_overrides,
                             login_cli.issuer_base_url,
                             login_cli.client_id,
+                            login_cli.profile,
                         )
                         .await;
                     } else if login_cli.api_key.is_some() {
@@ -550,9 +630,19 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
                         std::process::exit(1);
                     } else if login_cli.with_api_key {
                         let api_key = read_api_key_from_stdin();
-                        run_login_with_api_key(login_cli.config_overrides, api_key).await;
+                        let billing = BillingTarget {
+                            organization: login_cli.organization,
+                            project: login_cli.project,
+                        };
+                        run_login_with_api_key(
+                            login_cli.config_overrides,
+                            api_key,
+                            login_cli.profile,
+                            billing,
+                        )
+                        .await;
                     } else {
-                        run_login_with_chatgpt(login_cli.config_overrides).await;
+                        run_login_with_chatgpt(login_cli.config_overrides, login_cli.profile).await;
                     }
                 }
             }
@@ -564,6 +654,26 @@ async fn cli_main(code_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()>
             );
             run_logout(logout_cli.config_overrides).await;
         }
+        Some(Subcommand::Auth(mut auth_cli)) => {
+            prepend_config_flags(
+                &mut auth_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            match auth_cli.action {
+                AuthSubcommand::Set { provider, no_verify } => {
+                    run_auth_set(auth_cli.config_overrides, provider, !no_verify).await;
+                }
+                AuthSubcommand::Remove { provider } => {
+                    run_auth_remove(auth_cli.config_overrides, provider).await;
+                }
+                AuthSubcommand::List => {
+                    run_auth_list(auth_cli.config_overrides).await;
+                }
+            }
+        }
+        Some(Subcommand::Config(config_cli)) => {
+            config_cli.run()?;
+        }
         Some(Subcommand::Completion(completion_cli)) => {
             print_completion(completion_cli);
         }
@@ -1144,13 +1254,13 @@ fn push_experimental_resume_override(interactive: &mut TuiCli, path: &Path) {
         .push(format!("experimental_resume=\"{escaped}\""));
 }
 
-fn write_completion<W: std::io::Write>(shell: Shell, out: &mut W) {
-    let mut app = MultitoolCli::command();
+fn write_completion<W: std::io::Write>(shell: Shell, hints: &CompletionHints, out: &mut W) {
+    let mut app = completions::annotate(MultitoolCli::command(), hints);
     generate(shell, &mut app, CLI_COMMAND_NAME, out);
 }
 
 fn print_completion(cmd: CompletionCommand) {
-    write_completion(cmd.shell, &mut std::io::stdout());
+    write_completion(cmd.shell, &CompletionHints::load(), &mut std::io::stdout());
 }
 
 fn order_replay_main(args: OrderReplayArgs) -> anyhow::Result<()> {
@@ -1582,7 +1692,7 @@ mod tests {
     #[test]
     fn bash_completion_uses_code_command_name() {
         let mut buf = Vec::new();
-        write_completion(Shell::Bash, &mut buf);
+        write_completion(Shell::Bash, &CompletionHints::default(), &mut buf);
         let script = String::from_utf8(buf).expect("completion output should be valid UTF-8");
         assert!(script.contains("_code()"), "expected bash completion function to be named _code");
         assert!(!script.contains("_codex()"), "bash completion output should not use legacy codex prefix");
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -13,12 +14,16 @@ use code_core::config::write_global_mcp_servers;
 use code_core::config_types::McpServerConfig;
 use code_core::config_types::McpServerTransportConfig;
 
+use crate::mcp_scaffold;
+use crate::mcp_scaffold::ScaffoldLanguage;
+
 /// Subcommands:
 /// - `serve`  — run the MCP server on stdio
 /// - `list`   — list configured servers (with `--json`)
 /// - `get`    — show a single server (with `--json`)
 /// - `add`    — add a server launcher entry to `~/.code/config.toml` (Code also reads legacy `~/.codex/config.toml`)
 /// - `remove` — delete a server entry
+/// - `new`    — scaffold a new MCP server project and print (or register) its config
 #[derive(Debug, clap::Parser)]
 pub struct McpCli {
     #[clap(flatten)]
@@ -37,6 +42,8 @@ pub enum McpSubcommand {
     Add(AddArgs),
 
     Remove(RemoveArgs),
+
+    New(NewArgs),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -84,6 +91,28 @@ pub struct AddArgs {
     pub command: Vec<String>,
 }
 
+#[derive(Debug, clap::Parser)]
+pub struct NewArgs {
+    /// Name of the new MCP server; also used for the project directory.
+    pub name: String,
+
+    /// Language of the generated server.
+    #[arg(long, value_enum, default_value_t = ScaffoldLanguage::Rust)]
+    pub lang: ScaffoldLanguage,
+
+    /// Directory to create the project in (defaults to `./<name>`).
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+
+    /// Also add the server to the global config.
+    #[arg(long)]
+    pub register: bool,
+
+    /// Overwrite files that already exist in the target directory.
+    #[arg(long)]
+    pub force: bool,
+}
+
 #[derive(Debug, clap::Parser)]
 pub struct RemoveArgs {
     /// Name of the MCP server configuration to remove.
@@ -110,6 +139,9 @@ impl McpCli {
             McpSubcommand::Remove(args) => {
                 run_remove(&config_overrides, args)?;
             }
+            McpSubcommand::New(args) => {
+                run_new(&config_overrides, args)?;
+            }
         }
 
         Ok(())
@@ -195,6 +227,8 @@ fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Result<(
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        approval_required_tools: None,
+        tool_timeouts_sec: None,
     };
 
     servers.insert(name.clone(), new_entry);
@@ -207,6 +241,75 @@ fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Result<(
     Ok(())
 }
 
+fn run_new(config_overrides: &CliConfigOverrides, new_args: NewArgs) -> Result<()> {
+    config_overrides.parse_overrides().map_err(|e| anyhow!(e))?;
+
+    let NewArgs {
+        name,
+        lang,
+        dir,
+        register,
+        force,
+    } = new_args;
+
+    validate_server_name(&name)?;
+
+    let dir = dir.unwrap_or_else(|| PathBuf::from(&name));
+    let dir = if dir.is_absolute() {
+        dir
+    } else {
+        std::env::current_dir()
+            .context("failed to resolve the current directory")?
+            .join(dir)
+    };
+
+    let scaffold = mcp_scaffold::render(&name, lang, &dir);
+    mcp_scaffold::write(&scaffold, &dir, force)?;
+    println!("Created MCP server '{name}' in {}", dir.display());
+
+    if register {
+        let code_home = find_code_home().context("failed to resolve CODEX_HOME")?;
+        let mut servers = load_global_mcp_servers(&code_home)
+            .with_context(|| format!("failed to load MCP servers from {}", code_home.display()))?;
+        if servers.contains_key(&name) && !force {
+            bail!("MCP server '{name}' is already configured; pass --force to replace it");
+        }
+        servers.insert(
+            name.clone(),
+            McpServerConfig {
+                transport: McpServerTransportConfig::Stdio {
+                    command: scaffold.command.clone(),
+                    args: scaffold.args.clone(),
+                    env: None,
+                },
+                startup_timeout_sec: None,
+                tool_timeout_sec: None,
+                enabled_tools: None,
+                disabled_tools: None,
+                approval_required_tools: None,
+                tool_timeouts_sec: None,
+            },
+        );
+        write_global_mcp_servers(&code_home, &servers)
+            .with_context(|| format!("failed to write MCP servers to {}", code_home.display()))?;
+        println!("Registered global MCP server '{name}'.");
+    } else {
+        println!("\nAdd it to config.toml to use it:\n");
+        print!(
+            "{}",
+            mcp_scaffold::config_snippet(&name, &scaffold.command, &scaffold.args)
+        );
+    }
+
+    let next = match lang {
+        ScaffoldLanguage::Rust => "cargo test",
+        ScaffoldLanguage::Typescript => "npm install && npm test",
+    };
+    println!("\nNext: cd {} && {next}", dir.display());
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,356 @@
+//! `code mcp new` — generate a starter MCP server project.
+//!
+//! The templates are intentionally small: one example tool, a handler that is
+//! unit-tested without a transport, and the config snippet that registers the
+//! server with Code. Placeholders use `{{name}}`-style tokens so the templates
+//! stay readable as source.
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ScaffoldLanguage {
+    Rust,
+    #[value(alias = "ts")]
+    Typescript,
+}
+
+/// A generated project, ready to be written to disk.
+#[derive(Debug)]
+pub(crate) struct Scaffold {
+    pub(crate) files: Vec<(PathBuf, String)>,
+    /// Launcher recorded in config. Arguments use absolute paths because MCP
+    /// servers are spawned from the session's working directory.
+    pub(crate) command: String,
+    pub(crate) args: Vec<String>,
+}
+
+pub(crate) fn render(name: &str, language: ScaffoldLanguage, dir: &Path) -> Scaffold {
+    let substitute = |template: &str| template.replace("{{name}}", name);
+
+    let (templates, command, args): (&[(&str, &str)], &str, Vec<String>) = match language {
+        ScaffoldLanguage::Rust => (
+            &[
+                ("Cargo.toml", RUST_CARGO_TOML),
+                ("src/main.rs", RUST_MAIN_RS),
+                ("src/tools.rs", RUST_TOOLS_RS),
+                (".gitignore", "/target\n"),
+            ],
+            "cargo",
+            vec![
+                "run".to_string(),
+                "--quiet".to_string(),
+                "--manifest-path".to_string(),
+                dir.join("Cargo.toml").display().to_string(),
+            ],
+        ),
+        ScaffoldLanguage::Typescript => (
+            &[
+                ("package.json", TS_PACKAGE_JSON),
+                ("tsconfig.json", TS_TSCONFIG_JSON),
+                ("src/index.ts", TS_INDEX_TS),
+                ("src/tools.ts", TS_TOOLS_TS),
+                ("test/tools.test.ts", TS_TOOLS_TEST_TS),
+                (".gitignore", "node_modules/\ndist/\n"),
+            ],
+            "npm",
+            vec![
+                "--prefix".to_string(),
+                dir.display().to_string(),
+                "run".to_string(),
+                "--silent".to_string(),
+                "start".to_string(),
+            ],
+        ),
+    };
+
+    let mut files: Vec<(PathBuf, String)> = templates
+        .iter()
+        .map(|(path, template)| (PathBuf::from(path), substitute(template)))
+        .collect();
+    files.push((PathBuf::from("README.md"), substitute(README_MD)));
+
+    Scaffold {
+        files,
+        command: command.to_string(),
+        args,
+    }
+}
+
+/// Write the scaffold under `dir`, refusing to overwrite existing files
+/// unless `force` is set.
+pub(crate) fn write(scaffold: &Scaffold, dir: &Path, force: bool) -> Result<()> {
+    if !force
+        && let Some((path, _)) = scaffold
+            .files
+            .iter()
+            .find(|(path, _)| dir.join(path).exists())
+    {
+        bail!(
+            "{} already exists; pass --force to overwrite",
+            dir.join(path).display()
+        );
+    }
+
+    for (path, contents) in &scaffold.files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// TOML snippet that registers the generated server, for users who prefer to
+/// edit `config.toml` by hand.
+pub(crate) fn config_snippet(name: &str, command: &str, args: &[String]) -> String {
+    let mut server = toml_edit::Table::new();
+    server["command"] = toml_edit::value(command);
+    server["args"] = toml_edit::value(args.iter().collect::<toml_edit::Array>());
+    let mut servers = toml_edit::Table::new();
+    servers.set_implicit(true);
+    servers[name] = toml_edit::Item::Table(server);
+    let mut doc = toml_edit::DocumentMut::new();
+    doc["mcp_servers"] = toml_edit::Item::Table(servers);
+    doc.to_string()
+}
+
+const README_MD: &str = r#"# {{name}}
+
+An MCP server generated by `code mcp new`.
+
+- `tools` holds the tool implementations; each is a plain function with unit
+  tests, so behaviour can be checked without running a transport.
+- The entry point wires those functions into an MCP server on stdio.
+
+Add more tools next to `echo`, then restart Code (or run `/mcp` to check the
+server status). Logs written to stderr are visible with `/mcp logs {{name}}`.
+"#;
+
+const RUST_CARGO_TOML: &str = r#"[package]
+name = "{{name}}"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+anyhow = "1"
+rmcp = { version = "0.7", features = ["server", "transport-io", "macros"] }
+schemars = "1"
+serde = { version = "1", features = ["derive"] }
+tokio = { version = "1", features = ["macros", "rt-multi-thread", "io-std"] }
+"#;
+
+const RUST_MAIN_RS: &str = r#"mod tools;
+
+use rmcp::ErrorData as McpError;
+use rmcp::ServerHandler;
+use rmcp::ServiceExt;
+use rmcp::handler::server::router::tool::ToolRouter;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::model::Content;
+use rmcp::model::ServerCapabilities;
+use rmcp::model::ServerInfo;
+use rmcp::tool;
+use rmcp::tool_handler;
+use rmcp::tool_router;
+use rmcp::transport::stdio;
+
+use crate::tools::EchoArgs;
+
+#[derive(Clone)]
+struct Server {
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl Server {
+    fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "Echo the provided text back to the caller")]
+    async fn echo(&self, Parameters(args): Parameters<EchoArgs>) -> Result<CallToolResult, McpError> {
+        Ok(CallToolResult::success(vec![Content::text(tools::echo(args))]))
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for Server {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            instructions: Some("{{name}}: example MCP server".to_string()),
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // stdout carries the protocol; keep diagnostics on stderr.
+    eprintln!("{{name}} starting");
+    let service = Server::new().serve(stdio()).await?;
+    service.waiting().await?;
+    Ok(())
+}
+"#;
+
+const RUST_TOOLS_RS: &str = r#"use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct EchoArgs {
+    /// Text to echo back.
+    pub text: String,
+}
+
+pub fn echo(args: EchoArgs) -> String {
+    args.text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn echo_returns_input() {
+        let text = "hello".to_string();
+        assert_eq!(echo(EchoArgs { text: text.clone() }), text);
+    }
+}
+"#;
+
+const TS_PACKAGE_JSON: &str = r#"{
+  "name": "{{name}}",
+  "version": "0.1.0",
+  "private": true,
+  "type": "module",
+  "scripts": {
+    "start": "tsx src/index.ts",
+    "build": "tsc",
+    "test": "vitest run"
+  },
+  "dependencies": {
+    "@modelcontextprotocol/sdk": "^1.17.0",
+    "zod": "^3.23.0"
+  },
+  "devDependencies": {
+    "tsx": "^4.19.0",
+    "typescript": "^5.6.0",
+    "vitest": "^2.1.0"
+  }
+}
+"#;
+
+const TS_TSCONFIG_JSON: &str = r#"{
+  "compilerOptions": {
+    "target": "ES2022",
+    "module": "NodeNext",
+    "moduleResolution": "NodeNext",
+    "strict": true,
+    "outDir": "dist",
+    "skipLibCheck": true
+  },
+  "include": ["src"]
+}
+"#;
+
+const TS_INDEX_TS: &str = r#"import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { StdioServerTransport } from "@modelcontextprotocol/sdk/server/stdio.js";
+import { z } from "zod";
+
+import { echo } from "./tools.js";
+
+const server = new McpServer({ name: "{{name}}", version: "0.1.0" });
+
+server.registerTool(
+  "echo",
+  {
+    description: "Echo the provided text back to the caller",
+    inputSchema: { text: z.string().describe("Text to echo back.") },
+  },
+  async ({ text }) => ({ content: [{ type: "text", text: echo(text) }] }),
+);
+
+// stdout carries the protocol; keep diagnostics on stderr.
+console.error("{{name}} starting");
+await server.connect(new StdioServerTransport());
+"#;
+
+const TS_TOOLS_TS: &str = r#"export function echo(text: string): string {
+  return text;
+}
+"#;
+
+const TS_TOOLS_TEST_TS: &str = r#"import { describe, expect, it } from "vitest";
+
+import { echo } from "../src/tools.js";
+
+describe("echo", () => {
+  it("returns its input", () => {
+    expect(echo("hello")).toBe("hello");
+  });
+});
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rust_scaffold_substitutes_names() {
+        let scaffold = render("acme-tools", ScaffoldLanguage::Rust, Path::new("/work/acme-tools"));
+        let cargo = scaffold
+            .files
+            .iter()
+            .find(|(path, _)| path == Path::new("Cargo.toml"))
+            .map(|(_, contents)| contents.as_str())
+            .expect("Cargo.toml");
+        assert!(cargo.contains("name = \"acme-tools\""));
+        assert!(scaffold.files.iter().all(|(_, contents)| !contents.contains("{{")));
+        assert_eq!(scaffold.command, "cargo");
+        assert_eq!(
+            scaffold.args.last().map(String::as_str),
+            Some("/work/acme-tools/Cargo.toml")
+        );
+    }
+
+    #[test]
+    fn write_refuses_to_overwrite_without_force() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let scaffold = render("demo", ScaffoldLanguage::Typescript, dir.path());
+        write(&scaffold, dir.path(), false).expect("first write");
+        assert!(dir.path().join("src/index.ts").exists());
+        assert!(write(&scaffold, dir.path(), false).is_err());
+        write(&scaffold, dir.path(), true).expect("forced write");
+    }
+
+    #[test]
+    fn config_snippet_escapes_paths() {
+        let snippet = config_snippet(
+            "demo",
+            "npm",
+            &["--prefix".to_string(), "C:\\work\\demo".to_string()],
+        );
+        assert_eq!(
+            snippet,
+            "[mcp_servers.demo]\ncommand = \"npm\"\nargs = [\"--prefix\", 'C:\\work\\demo']\n"
+        );
+        let snippet = config_snippet("my.server", "say \"hi\"", &[]);
+        assert_eq!(
+            snippet,
+            "[mcp_servers.\"my.server\"]\ncommand = \"say \\\"hi\\\"\"\nargs = []\n"
+        );
+    }
+}
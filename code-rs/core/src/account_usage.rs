@@ -17,6 +17,54 @@ const HOURLY_HISTORY_DAYS: i64 = 183; // retain ~6 months of hourly usage for hi
 const UNKNOWN_RESET_RELOG_INTERVAL: Duration = Duration::hours(24);
 const RESET_PASSED_TOLERANCE: Duration = Duration::seconds(5);
 const RATE_LIMIT_REFRESH_STALE_INTERVAL_SECS: i64 = 30 * 60;
+const TOKENS_PER_MILLION: f64 = 1_000_000.0;
+
+/// List prices in US dollars per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrices {
+    pub input: f64,
+    pub cached_input: f64,
+    pub output: f64,
+}
+
+const fn prices(input: f64, cached_input: f64, output: f64) -> ModelPrices {
+    ModelPrices {
+        input,
+        cached_input,
+        output,
+    }
+}
+
+/// Used for models not listed in [`MODEL_PRICES`] and for totals that mix
+/// models, such as the account usage history.
+pub const DEFAULT_MODEL_PRICES: ModelPrices = prices(1.25, 0.125, 10.0);
+
+/// Model slug prefixes and their prices; the first matching prefix wins, so
+/// `gpt-5-mini` is listed before `gpt-5`.
+const MODEL_PRICES: &[(&str, ModelPrices)] = &[
+    ("gpt-5-nano", prices(0.05, 0.005, 0.40)),
+    ("gpt-5-mini", prices(0.25, 0.025, 2.0)),
+    ("gpt-5", DEFAULT_MODEL_PRICES),
+    ("gpt-4.1-nano", prices(0.10, 0.025, 0.40)),
+    ("gpt-4.1-mini", prices(0.40, 0.10, 1.60)),
+    ("gpt-4.1", prices(2.0, 0.50, 8.0)),
+    ("gpt-4o-mini", prices(0.15, 0.075, 0.60)),
+    ("gpt-4o", prices(2.50, 1.25, 10.0)),
+    ("o4-mini", prices(1.10, 0.275, 4.40)),
+    ("o3", prices(2.0, 0.50, 8.0)),
+];
+
+impl ModelPrices {
+    /// Prices for `model`'s family, falling back to [`DEFAULT_MODEL_PRICES`].
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_ascii_lowercase();
+        let model = model.strip_prefix("code-").unwrap_or(&model);
+        MODEL_PRICES
+            .iter()
+            .find(|(prefix, _)| model.starts_with(prefix))
+            .map_or(DEFAULT_MODEL_PRICES, |(_, prices)| *prices)
+    }
+}
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RateLimitWarningScope {
@@ -72,11 +120,26 @@ impl TokenTotals {
         self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
     }
 
-    fn from_usage(usage: &TokenUsage) -> Self {
+    pub fn from_usage(usage: &TokenUsage) -> Self {
         let mut totals = TokenTotals::default();
         totals.add_usage(usage);
         totals
     }
+
+    /// Estimated API cost in US dollars at [`DEFAULT_MODEL_PRICES`].
+    pub fn estimated_cost_usd(&self) -> f64 {
+        self.estimated_cost_usd_at(DEFAULT_MODEL_PRICES)
+    }
+
+    /// Estimated API cost in US dollars at `prices`.
+    pub fn estimated_cost_usd_at(&self, prices: ModelPrices) -> f64 {
+        let non_cached_input = self.input_tokens.saturating_sub(self.cached_input_tokens);
+        let input_cost = (non_cached_input as f64 / TOKENS_PER_MILLION) * prices.input;
+        let cached_cost =
+            (self.cached_input_tokens as f64 / TOKENS_PER_MILLION) * prices.cached_input;
+        let output_cost = (self.output_tokens as f64 / TOKENS_PER_MILLION) * prices.output;
+        input_cost + cached_cost + output_cost
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -4,15 +4,11 @@ use reqwest::StatusCode;
 use serde::Deserialize;
 use serde::Serialize;
 use std::env;
-use std::fs::File;
-use std::io::Read;
-use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
-use tempfile::NamedTempFile;
 
 use code_app_server_protocol::AuthMode;
 
@@ -240,6 +236,12 @@ impl CodexAuth {
         }
     }
 
+    /// When the current ChatGPT access token expires, if it carries an `exp` claim.
+    pub fn access_token_expires_at(&self) -> Option<DateTime<Utc>> {
+        let tokens = self.get_current_token_data()?;
+        parse_jwt_expiration(&tokens.access_token).ok().flatten()
+    }
+
     pub fn get_account_id(&self) -> Option<String> {
         self.get_current_token_data()
             .and_then(|t| t.account_id.clone())
@@ -370,6 +372,25 @@ fn should_proactively_refresh_auth(
     })
 }
 
+/// How long the background refresher sleeps before looking at the token again:
+/// until the refresh window opens, but at least a minute and at most 15 minutes.
+fn next_proactive_refresh_check(
+    expires_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Duration {
+    const MIN_DELAY: Duration = Duration::from_secs(60);
+    const MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+    let Some(expires_at) = expires_at else {
+        return MAX_DELAY;
+    };
+    let window_opens =
+        expires_at - chrono::Duration::minutes(CHATGPT_ACCESS_TOKEN_REFRESH_WINDOW_MINUTES);
+    (window_opens - now)
+        .to_std()
+        .unwrap_or(Duration::ZERO)
+        .clamp(MIN_DELAY, MAX_DELAY)
+}
+
 pub const OPENAI_API_KEY_ENV_VAR: &str = "OPENAI_API_KEY";
 pub const CODEX_API_KEY_ENV_VAR: &str = "CODEX_API_KEY";
 
@@ -393,12 +414,7 @@ pub fn get_auth_file(code_home: &Path) -> PathBuf {
 /// Delete the auth.json file inside `code_home` if it exists. Returns `Ok(true)`
 /// if a file was removed, `Ok(false)` if no auth file was present.
 pub fn logout(code_home: &Path) -> std::io::Result<bool> {
-    let auth_file = get_auth_file(code_home);
-    let removed = match std::fs::remove_file(&auth_file) {
-        Ok(_) => true,
-        Err(err) if err.kind() == std::io::ErrorKind::NotFound => false,
-        Err(err) => return Err(err),
-    };
+    let removed = crate::auth_store::remove(&get_auth_file(code_home))?;
 
     let _ = crate::auth_accounts::set_active_account_id(code_home, None)?;
     Ok(removed)
@@ -617,6 +633,21 @@ pub fn activate_account(code_home: &Path, account_id: &str) -> std::io::Result<(
     Ok(())
 }
 
+/// Activate the stored account named `profile`.
+pub fn activate_profile(
+    code_home: &Path,
+    profile: &str,
+) -> std::io::Result<crate::auth_accounts::StoredAccount> {
+    let Some(account) = crate::auth_accounts::find_account_by_profile(code_home, profile)? else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no sign-in profile named '{profile}'"),
+        ));
+    };
+    activate_account(code_home, &account.id)?;
+    Ok(account)
+}
+
 fn load_auth(
     code_home: &Path,
     include_env_var: bool,
@@ -629,7 +660,15 @@ fn load_auth(
     let auth_file = get_auth_file(code_home);
     let auth_read_path = resolve_code_path_for_read(code_home, Path::new("auth.json"));
     let client = crate::default_client::create_client(originator);
-    let auth_dot_json = match try_read_auth_json(&auth_read_path) {
+    // The keyring holds credentials under the CODE_HOME path, so look there
+    // before falling back to a legacy auth.json.
+    let auth_dot_json = match try_read_auth_json(&auth_file) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && auth_read_path != auth_file => {
+            try_read_auth_json(&auth_read_path)
+        }
+        result => result,
+    };
+    let auth_dot_json = match auth_dot_json {
         Ok(auth) => auth,
         // If auth.json does not exist, try to read the OPENAI_API_KEY from the
         // environment variable.
@@ -747,9 +786,7 @@ fn load_auth(
 /// Attempt to read and refresh the `auth.json` file in the given `CODEX_HOME` directory.
 /// Returns the full AuthDotJson structure after refreshing if necessary.
 pub fn try_read_auth_json(auth_file: &Path) -> std::io::Result<AuthDotJson> {
-    let mut file = File::open(auth_file)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
+    let contents = crate::auth_store::read(auth_file)?;
     let auth_dot_json: AuthDotJson = serde_json::from_str(&contents)?;
 
     Ok(auth_dot_json)
@@ -757,21 +794,7 @@ pub fn try_read_auth_json(auth_file: &Path) -> std::io::Result<AuthDotJson> {
 
 pub fn write_auth_json(auth_file: &Path, auth_dot_json: &AuthDotJson) -> std::io::Result<()> {
     let json_data = serde_json::to_string_pretty(auth_dot_json)?;
-    let parent = auth_file.parent().ok_or_else(|| {
-        std::io::Error::new(
-            std::io::ErrorKind::InvalidInput,
-            format!("auth path has no parent: {}", auth_file.display()),
-        )
-    })?;
-    if !parent.exists() {
-        std::fs::create_dir_all(parent)?;
-    }
-    let mut file = NamedTempFile::new_in(parent)?;
-    file.write_all(json_data.as_bytes())?;
-    file.flush()?;
-    file.as_file_mut().sync_all()?;
-    file.persist(auth_file).map_err(|err| err.error)?;
-    Ok(())
+    crate::auth_store::write(auth_file, &json_data)
 }
 
 async fn update_tokens(
@@ -1428,6 +1451,20 @@ mod tests {
         assert!(should_proactively_refresh_auth(Some(fresh), Some(&expired_access)));
     }
 
+    #[test]
+    fn background_refresh_wakes_when_the_refresh_window_opens() {
+        let now = Utc::now();
+        let in_an_hour = now + chrono::Duration::minutes(60);
+        let in_ten_minutes = now + chrono::Duration::minutes(10);
+        let expired = now - chrono::Duration::minutes(1);
+
+        let check = |expires_at| next_proactive_refresh_check(expires_at, now).as_secs();
+        assert_eq!(check(None), 15 * 60);
+        assert_eq!(check(Some(in_an_hour)), 15 * 60);
+        assert_eq!(check(Some(in_ten_minutes)), 5 * 60);
+        assert_eq!(check(Some(expired)), 60);
+    }
+
     #[tokio::test]
     async fn auth_manager_skips_refresh_for_api_key_auth() {
         let manager = AuthManager::from_auth_for_testing(CodexAuth::from_api_key("sk-test"));
@@ -1858,6 +1895,64 @@ impl AuthManager {
             .map_err(|err| std::io::Error::other(err))
     }
 
+    /// Refresh the ChatGPT access token if it expires within the refresh
+    /// window. Returns whether the token was refreshed.
+    pub async fn refresh_if_expiring(&self) -> Result<bool, RefreshTokenError> {
+        let Some(auth) = self.auth() else {
+            return Ok(false);
+        };
+        if auth.mode != AuthMode::ChatGPT {
+            return Ok(false);
+        }
+        let Some(auth_dot_json) = auth.get_current_auth_json() else {
+            return Ok(false);
+        };
+        let access_token = auth_dot_json
+            .tokens
+            .as_ref()
+            .map(|tokens| tokens.access_token.as_str());
+        if !should_proactively_refresh_auth(auth_dot_json.last_refresh, access_token) {
+            return Ok(false);
+        }
+        self.refresh_token_classified().await.map(|token| token.is_some())
+    }
+
+    /// Keep the ChatGPT access token fresh in the background so turns do not
+    /// start with an expired token. `on_permanent_failure` runs once for a
+    /// sign-in that can no longer be refreshed; signing in again re-arms it.
+    pub fn spawn_proactive_refresh<F>(
+        self: &Arc<Self>,
+        on_permanent_failure: F,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(RefreshTokenError) + Send + 'static,
+    {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut failed_auth: Option<CodexAuth> = None;
+            loop {
+                let expires_at = manager.auth().and_then(|auth| auth.access_token_expires_at());
+                tokio::time::sleep(next_proactive_refresh_check(expires_at, Utc::now())).await;
+
+                let auth = manager.auth();
+                if failed_auth.is_some() {
+                    if Self::auths_equal_for_refresh(&failed_auth, &auth) {
+                        continue;
+                    }
+                    failed_auth = None;
+                }
+                match manager.refresh_if_expiring().await {
+                    Ok(_) => {}
+                    Err(err) if err.is_permanent() => {
+                        failed_auth = auth;
+                        on_permanent_failure(err);
+                    }
+                    Err(err) => tracing::warn!("background token refresh failed: {err}"),
+                }
+            }
+        })
+    }
+
     /// Log out by deleting the on‑disk auth.json (if present). Returns Ok(true)
     /// if a file was removed, Ok(false) if no auth file existed. On success,
     /// reloads the in‑memory auth cache so callers immediately observe the
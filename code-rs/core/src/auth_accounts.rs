@@ -1,10 +1,9 @@
 use chrono::{DateTime, Utc};
 use code_app_server_protocol::AuthMode;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
-use std::io::{self, Read, Write};
+use std::io;
 use std::path::{Path, PathBuf};
-use tempfile::NamedTempFile;
+use std::sync::{LazyLock, PoisonError, RwLock};
 use uuid::Uuid;
 
 use crate::token_data::TokenData;
@@ -19,6 +18,18 @@ pub struct StoredAccount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
 
+    /// Short name (`work`, `personal`) used to switch to this account with
+    /// `code login switch` or `/account`. Unique across the store.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    /// OpenAI organization and project billed for requests made with this API key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub openai_api_key: Option<String>,
 
@@ -57,6 +68,18 @@ impl Default for AccountsFile {
     }
 }
 
+/// Organization and project sent as `OpenAI-Organization` / `OpenAI-Project`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BillingTarget {
+    pub organization: Option<String>,
+    pub project: Option<String>,
+}
+
+/// Billing target of the active API-key account, kept in memory so building a
+/// request never reads the accounts file. Refreshed on every write.
+static ACTIVE_BILLING: LazyLock<RwLock<BillingTarget>> =
+    LazyLock::new(|| RwLock::new(BillingTarget::default()));
+
 fn default_version() -> u32 {
     1
 }
@@ -66,10 +89,8 @@ fn accounts_file_path(code_home: &Path) -> PathBuf {
 }
 
 fn read_accounts_file(path: &Path) -> io::Result<AccountsFile> {
-    match File::open(path) {
-        Ok(mut file) => {
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
+    match crate::auth_store::read(path) {
+        Ok(contents) => {
             let (parsed, repaired) = parse_accounts_file(&contents)?;
             if repaired {
                 write_accounts_file(path, &parsed)?;
@@ -110,23 +131,38 @@ fn parse_accounts_file(contents: &str) -> io::Result<(AccountsFile, bool)> {
     }
 }
 
-fn write_accounts_file(path: &Path, data: &AccountsFile) -> io::Result<()> {
-    let parent = path.parent().ok_or_else(|| {
-        io::Error::new(
-            io::ErrorKind::InvalidInput,
-            format!("accounts path has no parent: {}", path.display()),
-        )
-    })?;
-    if !parent.exists() {
-        std::fs::create_dir_all(parent)?;
-    }
+fn cache_active_billing(data: &AccountsFile) {
+    let target = data
+        .active_account_id
+        .as_ref()
+        .and_then(|id| data.accounts.iter().find(|acc| &acc.id == id))
+        .filter(|acc| acc.mode == AuthMode::ApiKey)
+        .map(|acc| BillingTarget {
+            organization: acc.organization.clone(),
+            project: acc.project.clone(),
+        })
+        .unwrap_or_default();
+    *ACTIVE_BILLING.write().unwrap_or_else(PoisonError::into_inner) = target;
+}
 
+/// Load the active account's billing target. Called while loading the config.
+pub fn load_active_billing(code_home: &Path) -> io::Result<()> {
+    let data = read_accounts_file(&accounts_file_path(code_home))?;
+    cache_active_billing(&data);
+    Ok(())
+}
+
+pub fn active_billing() -> BillingTarget {
+    ACTIVE_BILLING
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+fn write_accounts_file(path: &Path, data: &AccountsFile) -> io::Result<()> {
     let json = serde_json::to_string_pretty(data)?;
-    let mut file = NamedTempFile::new_in(parent)?;
-    file.write_all(json.as_bytes())?;
-    file.flush()?;
-    file.as_file_mut().sync_all()?;
-    file.persist(path).map_err(|err| err.error)?;
+    crate::auth_store::write(path, &json)?;
+    cache_active_billing(data);
     Ok(())
 }
 
@@ -212,6 +248,12 @@ fn upsert_account(mut data: AccountsFile, mut new_account: StoredAccount) -> (Ac
         if let Some(api_key) = new_account.openai_api_key {
             account.openai_api_key = Some(api_key);
         }
+        if new_account.organization.is_some() {
+            account.organization = new_account.organization;
+        }
+        if new_account.project.is_some() {
+            account.project = new_account.project;
+        }
         if let Some(last_used) = new_account.last_used_at {
             account.last_used_at = Some(last_used);
         }
@@ -248,6 +290,91 @@ pub fn find_account(code_home: &Path, account_id: &str) -> io::Result<Option<Sto
         .find(|acc| acc.id == account_id))
 }
 
+pub fn find_account_by_profile(code_home: &Path, profile: &str) -> io::Result<Option<StoredAccount>> {
+    let path = accounts_file_path(code_home);
+    let data = read_accounts_file(&path)?;
+    Ok(data
+        .accounts
+        .into_iter()
+        .find(|acc| acc.profile.as_deref() == Some(profile)))
+}
+
+/// Name an account so it can be switched to by profile. The name moves off
+/// any other account that held it; `None` clears the account's name.
+pub fn set_account_profile(
+    code_home: &Path,
+    account_id: &str,
+    profile: Option<&str>,
+) -> io::Result<StoredAccount> {
+    let profile = profile.map(validate_profile_name).transpose()?;
+    let path = accounts_file_path(code_home);
+    let mut data = read_accounts_file(&path)?;
+    let Some(idx) = data.accounts.iter().position(|acc| acc.id == account_id) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("account with id {account_id} was not found"),
+        ));
+    };
+    if profile.is_some() {
+        for account in data.accounts.iter_mut().filter(|acc| acc.profile == profile) {
+            account.profile = None;
+        }
+    }
+    data.accounts[idx].profile = profile;
+    let updated = data.accounts[idx].clone();
+    write_accounts_file(&path, &data)?;
+    Ok(updated)
+}
+
+/// Set the organization and project billed for an API-key account. Blank
+/// values are cleared.
+pub fn set_account_billing(
+    code_home: &Path,
+    account_id: &str,
+    target: BillingTarget,
+) -> io::Result<StoredAccount> {
+    let path = accounts_file_path(code_home);
+    let mut data = read_accounts_file(&path)?;
+    let Some(account) = data.accounts.iter_mut().find(|acc| acc.id == account_id) else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("account with id {account_id} was not found"),
+        ));
+    };
+    if account.mode != AuthMode::ApiKey {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "organization and project apply to API key sign-ins only",
+        ));
+    }
+    let clean = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    account.organization = clean(target.organization);
+    account.project = clean(target.project);
+    let updated = account.clone();
+    write_accounts_file(&path, &data)?;
+    Ok(updated)
+}
+
+fn validate_profile_name(name: &str) -> io::Result<String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'));
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid profile name '{name}': use letters, digits, '-', '_' or '.'"),
+        ))
+    }
+}
+
 pub fn set_active_account_id(
     code_home: &Path,
     account_id: Option<String>,
@@ -307,6 +434,9 @@ pub fn upsert_api_key_account(
         id: next_id(),
         mode: AuthMode::ApiKey,
         label,
+        profile: None,
+        organization: None,
+        project: None,
         openai_api_key: Some(api_key),
         tokens: None,
         last_refresh: None,
@@ -346,6 +476,9 @@ pub fn upsert_chatgpt_account(
         id: next_id(),
         mode: AuthMode::ChatGPT,
         label,
+        profile: None,
+        organization: None,
+        project: None,
         openai_api_key: None,
         tokens: Some(tokens),
         last_refresh: Some(last_refresh),
@@ -525,6 +658,57 @@ mod tests {
         assert_eq!(accounts.len(), 2, "both accounts should remain listed");
     }
 
+    #[test]
+    fn profile_names_are_unique_and_validated() {
+        let home = tempdir().expect("tempdir");
+        let work = upsert_api_key_account(home.path(), "sk-work".to_string(), None, true)
+            .expect("insert work key");
+        let personal = upsert_api_key_account(home.path(), "sk-personal".to_string(), None, false)
+            .expect("insert personal key");
+
+        set_account_profile(home.path(), &work.id, Some("work")).expect("name work");
+        let found = find_account_by_profile(home.path(), "work").expect("find work");
+        assert_eq!(found.map(|acc| acc.id), Some(work.id.clone()));
+
+        // Re-using a name moves it to the new account.
+        set_account_profile(home.path(), &personal.id, Some(" work ")).expect("move name");
+        let found = find_account_by_profile(home.path(), "work").expect("find moved");
+        assert_eq!(found.map(|acc| acc.id), Some(personal.id));
+        let work_after = find_account(home.path(), &work.id).expect("find").expect("exists");
+        assert_eq!(work_after.profile, None);
+
+        let err = set_account_profile(home.path(), &work.id, Some("my work")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        let err = set_account_profile(home.path(), "missing", Some("x")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn billing_target_is_saved_per_api_key_account() {
+        let home = tempdir().expect("tempdir");
+        let key = upsert_api_key_account(home.path(), "sk-team".to_string(), None, true)
+            .expect("insert key");
+        let target = BillingTarget {
+            organization: Some(" org-team ".to_string()),
+            project: Some(String::new()),
+        };
+        let updated = set_account_billing(home.path(), &key.id, target).expect("set billing");
+        assert_eq!(updated.organization.as_deref(), Some("org-team"));
+        assert_eq!(updated.project, None);
+
+        // Logging in again with the same key keeps the billing target.
+        let again = upsert_api_key_account(home.path(), "sk-team".to_string(), None, true)
+            .expect("re-insert key");
+        assert_eq!(again.organization.as_deref(), Some("org-team"));
+
+        let tokens = make_chatgpt_tokens(Some("acct-billing"), Some("user@example.com"));
+        let chatgpt = upsert_chatgpt_account(home.path(), tokens, Utc::now(), None, false)
+            .expect("insert chatgpt");
+        let err = set_account_billing(home.path(), &chatgpt.id, BillingTarget::default())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
     #[test]
     fn remove_account_clears_active() {
         let home = tempdir().expect("tempdir");
@@ -560,6 +744,9 @@ mod tests {
                 id: "first-active".to_string(),
                 mode: AuthMode::ApiKey,
                 label: Some("first".to_string()),
+                profile: None,
+                organization: None,
+                project: None,
                 openai_api_key: Some("sk-first".to_string()),
                 tokens: None,
                 last_refresh: None,
@@ -574,6 +761,9 @@ mod tests {
                 id: "second-active".to_string(),
                 mode: AuthMode::ApiKey,
                 label: Some("second".to_string()),
+                profile: None,
+                organization: None,
+                project: None,
                 openai_api_key: Some("sk-second".to_string()),
                 tokens: None,
                 last_refresh: None,
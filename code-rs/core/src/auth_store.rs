@@ -0,0 +1,208 @@
+//! Storage backend for `auth.json`, `auth_accounts.json` and
+//! `provider_keys.json`.
+//!
+//! These files hold API keys and OAuth tokens. With
+//! `cli_auth_credentials_store = "keyring"` their contents live in the OS
+//! keyring (Keychain, Secret Service, Windows Credential Manager) under one
+//! entry per file path, and the plaintext files are removed. `auto` uses the
+//! keyring when it works and falls back to the files otherwise; `file`, the
+//! default, keeps today's behaviour.
+
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use code_keyring_store::DefaultKeyringStore;
+use code_keyring_store::KeyringStore;
+use tempfile::NamedTempFile;
+
+use crate::config_types::AuthCredentialsStoreMode;
+
+const KEYRING_SERVICE: &str = "Code Auth";
+
+/// Files in `CODE_HOME` that are moved into the keyring.
+const SECRET_FILES: [&str; 3] = ["auth.json", "auth_accounts.json", "provider_keys.json"];
+
+static MODE: LazyLock<RwLock<AuthCredentialsStoreMode>> =
+    LazyLock::new(|| RwLock::new(AuthCredentialsStoreMode::File));
+
+/// Select the backend for this process. Called while loading the config.
+pub fn set_store_mode(mode: AuthCredentialsStoreMode) {
+    *MODE.write().unwrap_or_else(PoisonError::into_inner) = mode;
+}
+
+pub fn store_mode() -> AuthCredentialsStoreMode {
+    *MODE.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Move plaintext credential files in `code_home` into the keyring. Does
+/// nothing in file mode; in auto mode a keyring failure leaves the files.
+pub fn migrate_files_to_keyring(code_home: &Path) -> io::Result<usize> {
+    migrate_with(&DefaultKeyringStore, store_mode(), code_home)
+}
+
+pub(crate) fn read(path: &Path) -> io::Result<String> {
+    read_with(&DefaultKeyringStore, store_mode(), path)
+}
+
+pub(crate) fn write(path: &Path, contents: &str) -> io::Result<()> {
+    write_with(&DefaultKeyringStore, store_mode(), path, contents)
+}
+
+/// Remove the credentials stored for `path`. Returns whether anything existed.
+pub(crate) fn remove(path: &Path) -> io::Result<bool> {
+    remove_with(&DefaultKeyringStore, store_mode(), path)
+}
+
+fn keyring_account(path: &Path) -> String {
+    let parent = path
+        .parent()
+        .and_then(|dir| dir.canonicalize().ok())
+        .unwrap_or_else(|| path.parent().unwrap_or(path).to_path_buf());
+    match path.file_name() {
+        Some(name) => parent.join(name).display().to_string(),
+        None => path.display().to_string(),
+    }
+}
+
+fn keyring_error(err: code_keyring_store::CredentialStoreError) -> io::Error {
+    io::Error::other(format!("OS keyring: {}", err.message()))
+}
+
+fn read_with(
+    store: &dyn KeyringStore,
+    mode: AuthCredentialsStoreMode,
+    path: &Path,
+) -> io::Result<String> {
+    if mode != AuthCredentialsStoreMode::File {
+        match store.load(KEYRING_SERVICE, &keyring_account(path)) {
+            Ok(Some(contents)) => return Ok(contents),
+            Ok(None) => {}
+            Err(err) if mode == AuthCredentialsStoreMode::Keyring => return Err(keyring_error(err)),
+            Err(err) => tracing::warn!("reading {} from the keyring failed: {err}", path.display()),
+        }
+    }
+    std::fs::read_to_string(path)
+}
+
+fn write_with(
+    store: &dyn KeyringStore,
+    mode: AuthCredentialsStoreMode,
+    path: &Path,
+    contents: &str,
+) -> io::Result<()> {
+    if mode != AuthCredentialsStoreMode::File {
+        match store.save(KEYRING_SERVICE, &keyring_account(path), contents) {
+            Ok(()) => return remove_file_if_present(path).map(|_| ()),
+            Err(err) if mode == AuthCredentialsStoreMode::Keyring => return Err(keyring_error(err)),
+            Err(err) => tracing::warn!(
+                "saving {} to the keyring failed, writing the file instead: {err}",
+                path.display()
+            ),
+        }
+    }
+    write_file_atomically(path, contents)
+}
+
+fn remove_with(
+    store: &dyn KeyringStore,
+    mode: AuthCredentialsStoreMode,
+    path: &Path,
+) -> io::Result<bool> {
+    let mut removed = false;
+    if mode != AuthCredentialsStoreMode::File {
+        match store.delete(KEYRING_SERVICE, &keyring_account(path)) {
+            Ok(deleted) => removed = deleted,
+            Err(err) if mode == AuthCredentialsStoreMode::Keyring => return Err(keyring_error(err)),
+            Err(err) => tracing::warn!("deleting {} from the keyring failed: {err}", path.display()),
+        }
+    }
+    Ok(remove_file_if_present(path)? || removed)
+}
+
+fn migrate_with(
+    store: &dyn KeyringStore,
+    mode: AuthCredentialsStoreMode,
+    code_home: &Path,
+) -> io::Result<usize> {
+    if mode == AuthCredentialsStoreMode::File {
+        return Ok(0);
+    }
+    let mut migrated = 0;
+    for name in SECRET_FILES {
+        let path = code_home.join(name);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        write_with(store, mode, &path, &contents)?;
+        if !path.exists() {
+            migrated += 1;
+        }
+    }
+    Ok(migrated)
+}
+
+fn remove_file_if_present(path: &Path) -> io::Result<bool> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Write `contents` to `path` through a temp file so readers never see a
+/// partial file.
+fn write_file_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let parent = path.parent().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("credentials path has no parent: {}", path.display()),
+        )
+    })?;
+    if !parent.exists() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = NamedTempFile::new_in(parent)?;
+    file.write_all(contents.as_bytes())?;
+    file.flush()?;
+    file.as_file_mut().sync_all()?;
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn keyring_mode_migrates_files_and_keeps_them_out_of_code_home() {
+        let home = tempdir().expect("tempdir");
+        let store = MockKeyringStore::default();
+        let auth = home.path().join("auth.json");
+        std::fs::write(&auth, r#"{"OPENAI_API_KEY":"sk-test"}"#).expect("write auth.json");
+
+        let mode = AuthCredentialsStoreMode::Keyring;
+        assert_eq!(migrate_with(&store, mode, home.path()).expect("migrate"), 1);
+        assert!(!auth.exists());
+        assert_eq!(read_with(&store, mode, &auth).expect("read"), r#"{"OPENAI_API_KEY":"sk-test"}"#);
+        // File mode only looks at the file, which is gone.
+        let err = read_with(&store, AuthCredentialsStoreMode::File, &auth).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        write_with(&store, mode, &auth, "{}").expect("write");
+        assert!(!auth.exists());
+        assert_eq!(read_with(&store, mode, &auth).expect("read"), "{}");
+
+        assert!(remove_with(&store, mode, &auth).expect("remove"));
+        assert_eq!(read_with(&store, mode, &auth).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert!(!remove_with(&store, mode, &auth).expect("remove again"));
+    }
+}
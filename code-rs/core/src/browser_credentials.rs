@@ -0,0 +1,88 @@
+//! Browser sign-in credentials saved in the OS keyring.
+//!
+//! Each origin (`https://docs.example.com`) gets one keyring entry holding a
+//! small JSON object with the username and password the user entered for
+//! the browser `login` action. Values are read and written only by the tool
+//! and never appear in model-visible output.
+
+use code_browser::login::Credentials;
+use code_keyring_store::KeyringStore;
+use serde::Deserialize;
+use serde::Serialize;
+
+const KEYRING_SERVICE: &str = "Code Browser Credentials";
+
+#[derive(Serialize, Deserialize)]
+struct StoredCredentials {
+    username: String,
+    password: String,
+}
+
+pub(crate) fn load(store: &dyn KeyringStore, origin: &str) -> Result<Option<Credentials>, String> {
+    let Some(raw) = store
+        .load(KEYRING_SERVICE, origin)
+        .map_err(|err| format!("could not read the keyring: {}", err.message()))?
+    else {
+        return Ok(None);
+    };
+    let stored: StoredCredentials = serde_json::from_str(&raw)
+        .map_err(|_| format!("the keyring entry for {origin} is not in the expected format"))?;
+    Ok(Some(Credentials {
+        username: stored.username,
+        password: stored.password,
+    }))
+}
+
+pub(crate) fn save(
+    store: &dyn KeyringStore,
+    origin: &str,
+    credentials: &Credentials,
+) -> Result<(), String> {
+    let stored = StoredCredentials {
+        username: credentials.username.clone(),
+        password: credentials.password.clone(),
+    };
+    let raw = serde_json::to_string(&stored).map_err(|err| err.to_string())?;
+    store
+        .save(KEYRING_SERVICE, origin, &raw)
+        .map_err(|err| format!("could not write to the keyring: {}", err.message()))
+}
+
+pub(crate) fn delete(store: &dyn KeyringStore, origin: &str) -> Result<bool, String> {
+    store
+        .delete(KEYRING_SERVICE, origin)
+        .map_err(|err| format!("could not update the keyring: {}", err.message()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_keyring_store::tests::MockKeyringStore;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trips_per_origin_and_rejects_foreign_entries() {
+        let store = MockKeyringStore::default();
+        let origin = "https://docs.example.com";
+        assert_eq!(load(&store, origin), Ok(None));
+
+        let credentials = Credentials {
+            username: "ada".to_string(),
+            password: "correct horse".to_string(),
+        };
+        save(&store, origin, &credentials).expect("save");
+        assert_eq!(load(&store, origin), Ok(Some(credentials)));
+        assert_eq!(load(&store, "https://example.com"), Ok(None));
+
+        store
+            .save(KEYRING_SERVICE, "https://other.test", "hunter2")
+            .expect("raw save");
+        assert_eq!(
+            load(&store, "https://other.test"),
+            Err("the keyring entry for https://other.test is not in the expected format".to_string())
+        );
+
+        assert_eq!(delete(&store, origin), Ok(true));
+        assert_eq!(load(&store, origin), Ok(None));
+    }
+}
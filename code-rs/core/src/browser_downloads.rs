@@ -0,0 +1,241 @@
+//! Browser downloads into a per-session directory.
+//!
+//! Files are fetched with the browser's cookies for the target URL, so
+//! artifacts behind a login the user completed in the browser download the
+//! same way a click would, and are written under
+//! `<code_home>/downloads/<session-id>/` rather than wherever a page asks.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use futures::StreamExt;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+use url::Url;
+
+use crate::navigation_policy::NavigationPolicy;
+
+/// Largest file a single download may write.
+pub(crate) const MAX_DOWNLOAD_BYTES: u64 = 512 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DownloadedFile {
+    pub(crate) path: PathBuf,
+    pub(crate) size: u64,
+    pub(crate) sha256: String,
+    pub(crate) content_type: Option<String>,
+}
+
+impl DownloadedFile {
+    /// Report shown in history and returned to the model.
+    pub(crate) fn summary(&self) -> String {
+        let mut out = format!(
+            "Downloaded {}\nsize: {} bytes\nsha256: {}",
+            self.path.display(),
+            self.size,
+            self.sha256
+        );
+        if let Some(content_type) = &self.content_type {
+            out.push_str(&format!("\ncontent-type: {content_type}"));
+        }
+        out
+    }
+}
+
+pub(crate) fn downloads_dir(code_home: &Path, session_id: &str) -> PathBuf {
+    code_home.join("downloads").join(session_id)
+}
+
+/// `Cookie` header value from a CDP `Network.getCookies` response.
+pub(crate) fn cookie_header(cookies: &serde_json::Value) -> Option<String> {
+    let pairs: Vec<String> = cookies
+        .get("cookies")?
+        .as_array()?
+        .iter()
+        .filter_map(|cookie| {
+            let name = cookie.get("name")?.as_str()?;
+            let value = cookie.get("value")?.as_str()?;
+            Some(format!("{name}={value}"))
+        })
+        .collect();
+    (!pairs.is_empty()).then(|| pairs.join("; "))
+}
+
+/// Download `url` into `dir`. The file name comes from `requested_name`, the
+/// `Content-Disposition` header or the URL path, in that order, and never
+/// overwrites an earlier download. Redirects are checked against `policy`
+/// like any other navigation.
+pub(crate) async fn download(
+    url: &Url,
+    cookie_header: Option<String>,
+    policy: &NavigationPolicy,
+    dir: &Path,
+    requested_name: Option<&str>,
+) -> Result<DownloadedFile, String> {
+    let origin_host = url.host_str().map(str::to_ascii_lowercase);
+    let redirects = policy.clone().redirect_policy(
+        origin_host.into_iter().collect(),
+        crate::fetch_url::MAX_REDIRECTS_LIMIT,
+    );
+    let client = crate::http_client::apply_proxy(reqwest::Client::builder())
+        .redirect(redirects)
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {err}"))?;
+    let mut request = client.get(url.clone());
+    if let Some(cookies) = cookie_header {
+        request = request.header(reqwest::header::COOKIE, cookies);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("request failed: {err}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("server returned {status}"));
+    }
+    if response.content_length().is_some_and(|len| len > MAX_DOWNLOAD_BYTES) {
+        return Err(format!("file is larger than {MAX_DOWNLOAD_BYTES} bytes"));
+    }
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE);
+    let disposition = header(reqwest::header::CONTENT_DISPOSITION);
+    let name = file_name(url, disposition.as_deref(), requested_name);
+
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|err| format!("failed to create {}: {err}", dir.display()))?;
+    let path = unique_path(dir, &name);
+    let mut file = tokio::fs::File::create(&path)
+        .await
+        .map_err(|err| format!("failed to create {}: {err}", path.display()))?;
+
+    let mut hasher = Sha256::new();
+    let mut size = 0u64;
+    let mut stream = response.bytes_stream();
+    let mut failure = None;
+    while let Some(chunk) = stream.next().await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                failure = Some(format!("download interrupted: {err}"));
+                break;
+            }
+        };
+        size += chunk.len() as u64;
+        if size > MAX_DOWNLOAD_BYTES {
+            failure = Some(format!("file is larger than {MAX_DOWNLOAD_BYTES} bytes"));
+            break;
+        }
+        hasher.update(&chunk);
+        if let Err(err) = file.write_all(&chunk).await {
+            failure = Some(format!("failed to write {}: {err}", path.display()));
+            break;
+        }
+    }
+    if failure.is_none()
+        && let Err(err) = file.flush().await
+    {
+        failure = Some(format!("failed to write {}: {err}", path.display()));
+    }
+    drop(file);
+    if let Some(failure) = failure {
+        let _ = tokio::fs::remove_file(&path).await;
+        return Err(failure);
+    }
+
+    Ok(DownloadedFile {
+        path,
+        size,
+        sha256: format!("{:x}", hasher.finalize()),
+        content_type,
+    })
+}
+
+fn file_name(url: &Url, disposition: Option<&str>, requested: Option<&str>) -> String {
+    let from_disposition = disposition.and_then(|value| {
+        value.split(';').find_map(|part| {
+            let (key, name) = part.trim().split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case("filename")
+                .then(|| name.trim().trim_matches('"').to_string())
+        })
+    });
+    let from_url = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(str::to_string);
+    [requested.map(str::to_string), from_disposition, from_url]
+        .into_iter()
+        .flatten()
+        .map(|name| sanitize(&name))
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| "download".to_string())
+}
+
+/// Keep only the final path component and characters that are safe in file
+/// names on every platform.
+fn sanitize(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ' ' | '+') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    cleaned.trim_matches(|c| c == '.' || c == ' ').to_string()
+}
+
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{stem} ({n}){extension}")))
+        .find(|path| !path.exists())
+        .unwrap_or(candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn names_files_safely_and_never_overwrites() {
+        let url = Url::parse("https://example.com/releases/v1.2/app.tar.gz?sig=1").unwrap();
+        assert_eq!(file_name(&url, None, None), "app.tar.gz");
+        assert_eq!(
+            file_name(&url, Some("attachment; filename=\"../../etc/passwd\""), None),
+            "passwd"
+        );
+        assert_eq!(file_name(&url, None, Some("report:2024.csv")), "report_2024.csv");
+        let root = Url::parse("https://example.com/").unwrap();
+        assert_eq!(file_name(&root, None, None), "download");
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.tar.gz"), b"old").unwrap();
+        assert_eq!(unique_path(dir.path(), "app.tar.gz"), dir.path().join("app.tar (1).gz"));
+
+        let cookies = serde_json::json!({"cookies": [
+            {"name": "session", "value": "abc"},
+            {"name": "theme", "value": "dark"},
+        ]});
+        assert_eq!(cookie_header(&cookies), Some("session=abc; theme=dark".to_string()));
+    }
+}
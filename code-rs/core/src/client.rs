@@ -59,7 +59,6 @@ use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use crate::config_types::ContextMode;
 use crate::config_types::TextVerbosity as TextVerbosityConfig;
 use crate::debug_logger::DebugLogger;
-use crate::default_client::create_client;
 use crate::error::{CodexErr, RetryAfter};
 use crate::error::Result;
 use crate::error::ModelCapError;
@@ -320,7 +319,7 @@ impl ModelClient {
     ) -> Self {
         let effective_verbosity = clamp_text_verbosity_for_model(config.model.as_str(), verbosity);
         let clamped_effort = clamp_reasoning_effort_for_model(config.model.as_str(), effort);
-        let client = create_client(&config.responses_originator_header);
+        let client = provider.create_http_client(&config.responses_originator_header);
 
         Self {
             config,
@@ -339,7 +338,8 @@ impl ModelClient {
     }
 
     fn active_ws_version_for_prompt(&self, prompt: &Prompt) -> Option<ResponsesWebsocketVersion> {
-        if self.websockets_disabled.load(Ordering::Relaxed) {
+        // The websocket transport does not carry the provider's tls settings.
+        if self.websockets_disabled.load(Ordering::Relaxed) || self.provider.tls.is_some() {
             return None;
         }
 
@@ -502,6 +502,11 @@ impl ModelClient {
         tools_config.web_search_external = self.config.tools_web_search_external;
         tools_config.web_search_indexed = self.config.tools_web_search_indexed;
         tools_config.search_tool = self.config.tools_search_tool;
+        tools_config.fetch_url_tool = self.config.tools_fetch_url;
+        tools_config.read_file_tool = self.config.tools_read_file;
+        tools_config.code_index_tools = self.config.tools_code_index;
+        tools_config.git_history_tools = self.config.tools_git_history;
+        tools_config.web_search_backend = self.config.tools_web_search_backend.is_some();
 
         let auth_mode = self
             .auth_manager
@@ -3277,6 +3282,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         }
     }
 
@@ -3390,6 +3396,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let client = reqwest::Client::builder()
@@ -3440,6 +3447,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let client = reqwest::Client::builder()
@@ -3492,6 +3500,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let client = reqwest::Client::builder()
@@ -3534,6 +3543,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let client = reqwest::Client::builder()
@@ -3684,6 +3694,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(
@@ -3773,6 +3784,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(
@@ -3821,6 +3833,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3875,6 +3888,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -3972,6 +3986,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -4081,6 +4096,7 @@ mod tests {
                 websocket_connect_timeout_ms: None,
                 requires_openai_auth: false,
                 openrouter: None,
+                tls: None,
             };
 
             let out = run_sse(evs, provider).await;
@@ -4323,6 +4339,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -4356,6 +4373,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -4392,6 +4410,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -4425,6 +4444,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
@@ -4458,6 +4478,7 @@ mod tests {
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let events = collect_events(&[sse1.as_bytes()], provider).await;
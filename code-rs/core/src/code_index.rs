@@ -0,0 +1,783 @@
+//! Symbol index behind the `code_search` and `goto_definition` tools and
+//! `/outline`.
+//!
+//! Workspace files are listed with `git ls-files` (or walked directly outside
+//! a repository) and parsed for definitions. Each file's entry is keyed by its
+//! size and modification time, so the refresh before every lookup only
+//! re-parses files that changed. Rust, Python, JavaScript/TypeScript, Go and
+//! shell sources are parsed with tree-sitter, so declarations split across
+//! lines are found; definitions generated by macros are not.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+use tree_sitter_bash::LANGUAGE as BASH;
+use tree_sitter_go::LANGUAGE as GO;
+use tree_sitter_python::LANGUAGE as PYTHON;
+use tree_sitter_rust::LANGUAGE as RUST;
+use tree_sitter_typescript::LANGUAGE_TSX as TSX;
+use tree_sitter_typescript::LANGUAGE_TYPESCRIPT as TYPESCRIPT;
+
+const MAX_FILES: usize = 50_000;
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+const MAX_SIGNATURE_CHARS: usize = 160;
+pub(crate) const DEFAULT_LIMIT: usize = 20;
+pub(crate) const MAX_LIMIT: usize = 200;
+/// Directories skipped when walking a workspace that is not a git checkout.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build", "__pycache__", "venv"];
+
+static INDEXES: LazyLock<Mutex<HashMap<PathBuf, CodeIndex>>> = LazyLock::new(Default::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Class,
+    Type,
+    Module,
+    Constant,
+    Macro,
+}
+
+impl SymbolKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SymbolKind::Function => "fn",
+            SymbolKind::Method => "method",
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Class => "class",
+            SymbolKind::Type => "type",
+            SymbolKind::Module => "mod",
+            SymbolKind::Constant => "const",
+            SymbolKind::Macro => "macro",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// 1-based line of the declaration.
+    pub line: usize,
+    /// Enclosing type, impl block, class or module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Definition {
+    path: String,
+    #[serde(flatten)]
+    symbol: Symbol,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Reference {
+    path: String,
+    line: usize,
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SearchResult {
+    indexed_files: usize,
+    definitions: Vec<Definition>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    references: Vec<Reference>,
+    /// More matches exist than were returned.
+    truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    Rust,
+    Python,
+    /// JavaScript and TSX, parsed with the TSX grammar so JSX is accepted.
+    JavaScript,
+    TypeScript,
+    Go,
+    Shell,
+}
+
+impl Language {
+    fn for_path(path: &Path) -> Option<Self> {
+        let language = match path.extension()?.to_str()? {
+            "rs" => Language::Rust,
+            "py" | "pyi" => Language::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "tsx" => Language::JavaScript,
+            "ts" | "mts" | "cts" => Language::TypeScript,
+            "go" => Language::Go,
+            "sh" | "bash" | "zsh" => Language::Shell,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    fn grammar(self) -> tree_sitter::Language {
+        match self {
+            Language::Rust => RUST.into(),
+            Language::Python => PYTHON.into(),
+            Language::JavaScript => TSX.into(),
+            Language::TypeScript => TYPESCRIPT.into(),
+            Language::Go => GO.into(),
+            Language::Shell => BASH.into(),
+        }
+    }
+}
+
+/// A syntax node that declares something or opens a container.
+struct Declaration {
+    /// `None` for a node that only opens a container (a Rust `impl` block).
+    kind: Option<SymbolKind>,
+    name: String,
+    /// Container named by the declaration itself (a Go method receiver).
+    receiver: Option<String>,
+    /// Nodes below it are members of `name`.
+    opens_container: bool,
+}
+
+impl Declaration {
+    fn new(kind: SymbolKind, name: String) -> Self {
+        Self {
+            kind: Some(kind),
+            name,
+            receiver: None,
+            opens_container: false,
+        }
+    }
+
+    fn opening(self) -> Self {
+        Self {
+            opens_container: true,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FileEntry {
+    modified: Option<SystemTime>,
+    len: u64,
+    symbols: Vec<Symbol>,
+    identifiers: HashSet<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CodeIndex {
+    root: PathBuf,
+    files: HashMap<PathBuf, FileEntry>,
+}
+
+impl CodeIndex {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Re-parses files that were added or changed since the last refresh and
+    /// drops the ones that are gone.
+    fn refresh(&mut self) {
+        let listed = list_files(&self.root);
+        let keep: HashSet<&PathBuf> = listed.iter().collect();
+        self.files.retain(|path, _| keep.contains(path));
+        for relative in &listed {
+            let Some(language) = Language::for_path(relative) else {
+                continue;
+            };
+            let metadata = match std::fs::metadata(self.root.join(relative)) {
+                Ok(metadata) if metadata.is_file() && metadata.len() <= MAX_FILE_BYTES => metadata,
+                _ => {
+                    self.files.remove(relative);
+                    continue;
+                }
+            };
+            let modified = metadata.modified().ok();
+            let unchanged = self
+                .files
+                .get(relative)
+                .is_some_and(|entry| entry.modified == modified && entry.len == metadata.len());
+            if unchanged {
+                continue;
+            }
+            let Ok(source) = std::fs::read_to_string(self.root.join(relative)) else {
+                self.files.remove(relative);
+                continue;
+            };
+            self.files.insert(
+                relative.clone(),
+                FileEntry {
+                    modified,
+                    len: metadata.len(),
+                    symbols: extract_symbols(language, &source),
+                    identifiers: identifiers(&source),
+                },
+            );
+        }
+    }
+
+    /// Definitions whose name matches `query`: exact matches first, then
+    /// case-insensitive, prefix and substring matches. `Type::name` and
+    /// `Type.name` also match on the container.
+    pub(crate) fn search(
+        &self,
+        query: &str,
+        kind: Option<SymbolKind>,
+        include_references: bool,
+        limit: usize,
+    ) -> SearchResult {
+        let (container, name) = split_qualified(query.trim());
+        let needle = name.to_lowercase();
+        let mut ranked: Vec<(u8, Definition)> = Vec::new();
+        for (path, entry) in &self.files {
+            for symbol in &entry.symbols {
+                if kind.is_some_and(|kind| kind != symbol.kind)
+                    || !container_matches(container, symbol)
+                {
+                    continue;
+                }
+                let lower = symbol.name.to_lowercase();
+                let rank = if symbol.name == name {
+                    0
+                } else if lower == needle {
+                    1
+                } else if lower.starts_with(&needle) {
+                    2
+                } else if lower.contains(&needle) {
+                    3
+                } else {
+                    continue;
+                };
+                ranked.push((rank, definition(path, symbol)));
+            }
+        }
+        ranked.sort_by(|(a_rank, a), (b_rank, b)| {
+            a_rank
+                .cmp(b_rank)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.symbol.line.cmp(&b.symbol.line))
+        });
+        let mut truncated = ranked.len() > limit;
+        let definitions: Vec<Definition> =
+            ranked.into_iter().take(limit).map(|(_, definition)| definition).collect();
+        let references = if include_references {
+            let (references, more) = self.references(name, limit);
+            truncated |= more;
+            references
+        } else {
+            Vec::new()
+        };
+        SearchResult {
+            indexed_files: self.files.len(),
+            definitions,
+            references,
+            truncated,
+        }
+    }
+
+    /// Definitions named exactly `symbol`, those in `near` (a path relative
+    /// to the root) first.
+    pub(crate) fn goto_definition(&self, symbol: &str, near: Option<&Path>) -> SearchResult {
+        let (container, name) = split_qualified(symbol.trim());
+        let mut definitions: Vec<Definition> = self
+            .files
+            .iter()
+            .flat_map(|(path, entry)| {
+                entry
+                    .symbols
+                    .iter()
+                    .filter(|symbol| symbol.name == name && container_matches(container, symbol))
+                    .map(move |symbol| definition(path, symbol))
+            })
+            .collect();
+        let near = near.map(|path| path.to_string_lossy().into_owned());
+        definitions.sort_by(|a, b| {
+            let a_far = near.as_deref() != Some(a.path.as_str());
+            let b_far = near.as_deref() != Some(b.path.as_str());
+            a_far
+                .cmp(&b_far)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.symbol.line.cmp(&b.symbol.line))
+        });
+        let truncated = definitions.len() > MAX_LIMIT;
+        definitions.truncate(MAX_LIMIT);
+        SearchResult {
+            indexed_files: self.files.len(),
+            definitions,
+            references: Vec::new(),
+            truncated,
+        }
+    }
+
+    /// Whole-word uses of `name` outside its own definitions. Only files
+    /// whose identifier set contains `name` are read.
+    fn references(&self, name: &str, limit: usize) -> (Vec<Reference>, bool) {
+        let mut paths: Vec<&PathBuf> = self
+            .files
+            .iter()
+            .filter(|(_, entry)| entry.identifiers.contains(name))
+            .map(|(path, _)| path)
+            .collect();
+        paths.sort();
+        let mut references = Vec::new();
+        for path in paths {
+            let Ok(source) = std::fs::read_to_string(self.root.join(path)) else {
+                continue;
+            };
+            let definition_lines: HashSet<usize> = self.files[path]
+                .symbols
+                .iter()
+                .filter(|symbol| symbol.name == name)
+                .map(|symbol| symbol.line)
+                .collect();
+            for (index, line) in source.lines().enumerate() {
+                if definition_lines.contains(&(index + 1)) || !contains_word(line, name) {
+                    continue;
+                }
+                if references.len() == limit {
+                    return (references, true);
+                }
+                references.push(Reference {
+                    path: path.to_string_lossy().into_owned(),
+                    line: index + 1,
+                    text: signature(line),
+                });
+            }
+        }
+        (references, false)
+    }
+}
+
+/// Refreshes the index for `root` and runs `f` on it. Indexes are kept for
+/// the life of the process, so later lookups only re-parse changed files.
+pub(crate) fn with_index<T>(root: &Path, f: impl FnOnce(&CodeIndex) -> T) -> T {
+    let mut indexes = INDEXES.lock().unwrap_or_else(PoisonError::into_inner);
+    let index = indexes
+        .entry(root.to_path_buf())
+        .or_insert_with(|| CodeIndex::new(root));
+    index.refresh();
+    f(index)
+}
+
+/// Definitions in a single file, in source order.
+pub fn outline(path: &Path) -> std::io::Result<Vec<Symbol>> {
+    let Some(language) = Language::for_path(path) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!(
+                "no outline support for {}; supported: Rust, Python, \
+                 JavaScript/TypeScript, Go, shell",
+                path.display()
+            ),
+        ));
+    };
+    let source = std::fs::read_to_string(path)?;
+    Ok(extract_symbols(language, &source))
+}
+
+fn definition(path: &Path, symbol: &Symbol) -> Definition {
+    Definition {
+        path: path.to_string_lossy().into_owned(),
+        symbol: symbol.clone(),
+    }
+}
+
+fn split_qualified(query: &str) -> (Option<&str>, &str) {
+    if let Some((container, name)) = query.rsplit_once("::") {
+        return (Some(container.rsplit("::").next().unwrap_or(container)), name);
+    }
+    match query.rsplit_once('.') {
+        Some((container, name)) => (Some(container), name),
+        None => (None, query),
+    }
+}
+
+fn container_matches(container: Option<&str>, symbol: &Symbol) -> bool {
+    match container {
+        None => true,
+        Some(container) => symbol
+            .container
+            .as_deref()
+            .is_some_and(|own| own.eq_ignore_ascii_case(container)),
+    }
+}
+
+fn extract_symbols(language: Language, source: &str) -> Vec<Symbol> {
+    let mut parser = Parser::new();
+    if parser.set_language(&language.grammar()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = source.lines().collect();
+    let mut symbols = Vec::new();
+    // Nodes left to visit in source order, each with the innermost container
+    // around it and whether that container is a module.
+    let mut stack: Vec<(Node, Option<(String, bool)>)> = vec![(tree.root_node(), None)];
+    while let Some((node, container)) = stack.pop() {
+        let mut inner = container.clone();
+        if let Some(declaration) = declaration(language, node, source) {
+            let in_module = container.as_ref().is_some_and(|(_, module)| *module);
+            let owner = declaration
+                .receiver
+                .clone()
+                .or_else(|| container.as_ref().map(|(name, _)| name.clone()));
+            if let Some(kind) = declaration.kind {
+                let kind = if kind == SymbolKind::Function && owner.is_some() && !in_module {
+                    SymbolKind::Method
+                } else {
+                    kind
+                };
+                let row = node.start_position().row;
+                symbols.push(Symbol {
+                    name: declaration.name.clone(),
+                    kind,
+                    line: row + 1,
+                    container: owner,
+                    signature: lines.get(row).map(|line| signature(line)).unwrap_or_default(),
+                });
+            }
+            if declaration.opens_container {
+                let module = declaration.kind == Some(SymbolKind::Module);
+                inner = Some((declaration.name, module));
+            }
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev().map(|child| (child, inner.clone())));
+    }
+    symbols
+}
+
+/// What `node` declares, if anything. Node kinds are those of each
+/// language's tree-sitter grammar.
+fn declaration(language: Language, node: Node, source: &str) -> Option<Declaration> {
+    let text = |node: Node| node.utf8_text(source.as_bytes()).ok().map(str::to_string);
+    let named = |kind: SymbolKind| {
+        let name = text(node.child_by_field_name("name")?)?;
+        Some(Declaration::new(kind, name))
+    };
+    let scripted = matches!(language, Language::JavaScript | Language::TypeScript);
+    match (language, node.kind()) {
+        (Language::Rust, "function_item" | "function_signature_item") => {
+            named(SymbolKind::Function)
+        }
+        (Language::Rust, "struct_item" | "union_item") => named(SymbolKind::Struct),
+        (Language::Rust, "enum_item") => named(SymbolKind::Enum),
+        (Language::Rust, "trait_item") => named(SymbolKind::Trait).map(Declaration::opening),
+        (Language::Rust, "mod_item") => named(SymbolKind::Module).map(Declaration::opening),
+        (Language::Rust, "type_item") => named(SymbolKind::Type),
+        (Language::Rust, "const_item" | "static_item") => named(SymbolKind::Constant),
+        (Language::Rust, "macro_definition") => named(SymbolKind::Macro),
+        (Language::Rust, "impl_item") => Some(Declaration {
+            kind: None,
+            name: type_name(node.child_by_field_name("type")?, source)?,
+            receiver: None,
+            opens_container: true,
+        }),
+        (Language::Python, "function_definition") => named(SymbolKind::Function),
+        (Language::Python, "class_definition") => {
+            named(SymbolKind::Class).map(Declaration::opening)
+        }
+        // Module-level `UPPER_CASE = ...` assignments.
+        (Language::Python, "assignment")
+            if node
+                .parent()
+                .and_then(|statement| statement.parent())
+                .is_some_and(|parent| parent.kind() == "module") =>
+        {
+            let left = node.child_by_field_name("left")?;
+            let name = text(left).filter(|_| left.kind() == "identifier")?;
+            let constant = name.starts_with(|ch: char| ch.is_ascii_uppercase())
+                && name
+                    .chars()
+                    .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_');
+            constant.then(|| Declaration::new(SymbolKind::Constant, name))
+        }
+        (_, "function_declaration" | "generator_function_declaration") if scripted => {
+            named(SymbolKind::Function)
+        }
+        (_, "class_declaration" | "abstract_class_declaration") if scripted => {
+            named(SymbolKind::Class).map(Declaration::opening)
+        }
+        (_, "method_definition") if scripted => named(SymbolKind::Method),
+        (_, "interface_declaration") if scripted => named(SymbolKind::Interface),
+        (_, "type_alias_declaration") if scripted => named(SymbolKind::Type),
+        (_, "enum_declaration") if scripted => named(SymbolKind::Enum),
+        // `const handler = (...) => ...` and `const f = function () {}`.
+        (_, "variable_declarator")
+            if scripted
+                && node.child_by_field_name("value").is_some_and(|value| {
+                    matches!(
+                        value.kind(),
+                        "arrow_function" | "function_expression" | "function" | "generator_function"
+                    )
+                }) =>
+        {
+            named(SymbolKind::Function)
+        }
+        (Language::Go, "function_declaration") => named(SymbolKind::Function),
+        (Language::Go, "method_declaration") => {
+            let receiver = node.child_by_field_name("receiver")?;
+            let mut cursor = receiver.walk();
+            let parameter = receiver.named_children(&mut cursor).next()?;
+            let receiver = type_name(parameter.child_by_field_name("type")?, source)?;
+            Some(Declaration {
+                receiver: Some(receiver),
+                ..named(SymbolKind::Method)?
+            })
+        }
+        (Language::Go, "type_spec" | "type_alias") => {
+            let kind = match node.child_by_field_name("type").map(|ty| ty.kind()) {
+                Some("struct_type") => SymbolKind::Struct,
+                Some("interface_type") => SymbolKind::Interface,
+                _ => SymbolKind::Type,
+            };
+            named(kind)
+        }
+        // Package-level constants only.
+        (Language::Go, "const_spec")
+            if node
+                .parent()
+                .and_then(|declaration| declaration.parent())
+                .is_some_and(|parent| parent.kind() == "source_file") =>
+        {
+            named(SymbolKind::Constant)
+        }
+        (Language::Shell, "function_definition") => named(SymbolKind::Function),
+        _ => None,
+    }
+}
+
+/// Bare name of a type: `Parser` for `&'a mut parse::Parser<T>` or `*Parser`.
+fn type_name(node: Node, source: &str) -> Option<String> {
+    match node.kind() {
+        "generic_type" | "reference_type" => type_name(node.child_by_field_name("type")?, source),
+        "scoped_type_identifier" => type_name(node.child_by_field_name("name")?, source),
+        "pointer_type" => {
+            let mut cursor = node.walk();
+            let pointee = node.named_children(&mut cursor).last()?;
+            type_name(pointee, source)
+        }
+        _ => node.utf8_text(source.as_bytes()).ok().map(str::to_string),
+    }
+}
+
+fn signature(line: &str) -> String {
+    let trimmed = line.trim();
+    if trimmed.chars().count() <= MAX_SIGNATURE_CHARS {
+        return trimmed.to_string();
+    }
+    let mut cut: String = trimmed.chars().take(MAX_SIGNATURE_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+fn is_identifier_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_'
+}
+
+fn identifiers(source: &str) -> HashSet<String> {
+    source
+        .split(|ch: char| !is_identifier_char(ch))
+        .filter(|word| word.len() > 1 && !word.starts_with(|ch: char| ch.is_ascii_digit()))
+        .map(str::to_string)
+        .collect()
+}
+
+fn contains_word(line: &str, word: &str) -> bool {
+    line.match_indices(word).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + word.len()..].chars().next();
+        !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+    })
+}
+
+/// Source files under `root`, relative to it: tracked and untracked files
+/// that git does not ignore, or a walk that skips hidden and build
+/// directories when `root` is not in a git checkout.
+fn list_files(root: &Path) -> Vec<PathBuf> {
+    let output = std::process::Command::new("git")
+        .args(["ls-files", "-z", "--cached", "--others", "--exclude-standard"])
+        .current_dir(root)
+        .output();
+    let mut files: Vec<PathBuf> = match output {
+        Ok(output) if output.status.success() => output
+            .stdout
+            .split(|byte| *byte == 0)
+            .filter(|path| !path.is_empty())
+            .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+            .filter(|path| Language::for_path(path).is_some())
+            .collect(),
+        _ => walk(root),
+    };
+    files.sort();
+    files.dedup();
+    files.truncate(MAX_FILES);
+    files
+}
+
+fn walk(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                    dirs.push(path);
+                }
+            } else if file_type.is_file()
+                && Language::for_path(&path).is_some()
+                && let Ok(relative) = path.strip_prefix(root)
+            {
+                files.push(relative.to_path_buf());
+                if files.len() >= MAX_FILES {
+                    return files;
+                }
+            }
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn indexes_definitions_references_and_outlines() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(
+            root.join("lib.rs"),
+            "pub struct Parser {\n    depth: usize,\n}\n\n\
+             impl Parser {\n    pub fn parse(&self) {}\n}\n\n\
+             fn parse_all(p: &Parser) {\n    p.parse();\n}\n",
+        )
+        .expect("write rust");
+        std::fs::write(
+            root.join("tool.py"),
+            "MAX_DEPTH = 3\n\nclass Walker:\n    def parse(self):\n        pass\n\n\
+             def main():\n    Walker().parse()\n",
+        )
+        .expect("write python");
+        std::fs::write(root.join("build.sh"), "setup() {\n  echo hi\n}\nsetup\n")
+            .expect("write sh");
+
+        let outline: Vec<(String, SymbolKind, Option<String>)> = outline(&root.join("lib.rs"))
+            .expect("outline")
+            .into_iter()
+            .map(|symbol| (symbol.name, symbol.kind, symbol.container))
+            .collect();
+        assert_eq!(
+            outline,
+            vec![
+                ("Parser".to_string(), SymbolKind::Struct, None),
+                ("parse".to_string(), SymbolKind::Method, Some("Parser".to_string())),
+                ("parse_all".to_string(), SymbolKind::Function, None),
+            ]
+        );
+
+        let mut index = CodeIndex::new(root);
+        index.refresh();
+        assert_eq!(index.files.len(), 3);
+
+        let found = index.goto_definition("Walker.parse", None);
+        let found: Vec<(&str, usize)> = found
+            .definitions
+            .iter()
+            .map(|definition| (definition.path.as_str(), definition.symbol.line))
+            .collect();
+        assert_eq!(found, vec![("tool.py", 4)]);
+
+        let result = index.search("parse", None, true, 10);
+        let names: Vec<&str> =
+            result.definitions.iter().map(|definition| definition.symbol.name.as_str()).collect();
+        assert_eq!(names, vec!["parse", "parse", "Parser", "parse_all"]);
+        let references: Vec<(&str, usize)> = result
+            .references
+            .iter()
+            .map(|reference| (reference.path.as_str(), reference.line))
+            .collect();
+        assert_eq!(references, vec![("lib.rs", 10), ("tool.py", 8)]);
+
+        let shell = index.goto_definition("setup", None);
+        assert_eq!(shell.definitions[0].symbol.line, 1);
+    }
+
+    #[test]
+    fn parses_typescript_and_go_declarations() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(
+            root.join("app.ts"),
+            "export class Store {\n  get(key: string): string {\n    return key;\n  }\n}\n\
+             export const load = async (\n  path: string,\n) => path;\ninterface Options {}\n",
+        )
+        .expect("write ts");
+        std::fs::write(
+            root.join("main.go"),
+            "package main\n\nconst Version = \"1\"\n\ntype Server struct{}\n\n\
+             func (s *Server) Start() {}\n\nfunc main() {}\n",
+        )
+        .expect("write go");
+
+        let summarize = |path: &Path| -> Vec<(String, SymbolKind, usize, Option<String>)> {
+            outline(path)
+                .expect("outline")
+                .into_iter()
+                .map(|symbol| (symbol.name, symbol.kind, symbol.line, symbol.container))
+                .collect()
+        };
+        assert_eq!(
+            summarize(&root.join("app.ts")),
+            vec![
+                ("Store".to_string(), SymbolKind::Class, 1, None),
+                ("get".to_string(), SymbolKind::Method, 2, Some("Store".to_string())),
+                ("load".to_string(), SymbolKind::Function, 6, None),
+                ("Options".to_string(), SymbolKind::Interface, 9, None),
+            ]
+        );
+        assert_eq!(
+            summarize(&root.join("main.go")),
+            vec![
+                ("Version".to_string(), SymbolKind::Constant, 3, None),
+                ("Server".to_string(), SymbolKind::Struct, 5, None),
+                ("Start".to_string(), SymbolKind::Method, 7, Some("Server".to_string())),
+                ("main".to_string(), SymbolKind::Function, 9, None),
+            ]
+        );
+    }
+}
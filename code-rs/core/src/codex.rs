@@ -73,6 +73,7 @@ use crate::protocol::ApprovedCommandMatchKind;
 use crate::protocol::WebSearchBeginEvent;
 use crate::protocol::WebSearchCompleteEvent;
 use crate::account_usage;
+use crate::usage_quota;
 use crate::auth_accounts;
 use crate::agent_defaults::{
     agent_model_spec,
@@ -1011,6 +1012,7 @@ use code_protocol::models::ReasoningItemReasoningSummary;
 use code_protocol::models::ResponseInputItem;
 use code_protocol::models::ResponseItem;
 use code_protocol::models::SandboxPermissions;
+use crate::openai_tools::OpenAiTool;
 use crate::openai_tools::ToolsConfig;
 use crate::openai_tools::get_openai_tools;
 use crate::slash_commands::get_enabled_agents;
@@ -1154,6 +1156,7 @@ impl Codex {
             resume_path: resume_path.clone(),
             demo_developer_message: config.demo_developer_message.clone(),
             dynamic_tools: config.dynamic_tools.clone(),
+            active_profile: config.active_profile.clone(),
         };
 
         let config = Arc::new(config);
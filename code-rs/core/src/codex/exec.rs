@@ -76,6 +76,7 @@ impl Drop for ExecDropGuard {
             stderr,
             exit_code,
             duration: Duration::ZERO,
+            redactions: 0,
         });
 
         if let Some(session) = self.session.upgrade() {
@@ -381,7 +382,7 @@ impl Session {
         let ExecToolCallOutput {
             stdout,
             stderr,
-            aggregated_output: _,
+            aggregated_output,
             duration,
             exit_code,
             timed_out: _,
@@ -407,6 +408,7 @@ impl Session {
                 stderr,
                 exit_code: *exit_code,
                 duration: *duration,
+                redactions: aggregated_output.redactions,
             })
         };
         let order = crate::protocol::OrderMeta { request_ordinal: attempt_req, output_index, sequence_number: seq_hint };
@@ -530,6 +532,7 @@ impl Session {
                     stderr,
                     exit_code,
                     duration: Duration::ZERO,
+                    redactions: 0,
                 });
                 let event = self.make_event_with_order(sub_id, msg, order_meta.clone(), order_meta.sequence_number);
                 let _ = self.tx_event.send(event).await;
@@ -120,11 +120,28 @@ impl EnvironmentContextStreamRegistry {
 #[derive(Default)]
 pub(super) struct State {
     pub(super) approved_commands: HashSet<ApprovedCommandPattern>,
+    /// `(server, tool)` pairs from `approval_required_tools` approved for the session.
+    pub(super) approved_mcp_tools: HashSet<(String, String)>,
+    /// The user allowed `browser` `eval`, `javascript` and `cdp` for the rest
+    /// of the session.
+    pub(super) browser_eval_approved: bool,
+    /// The user allowed browser downloads for the rest of the session.
+    pub(super) browser_downloads_approved: bool,
+    /// Hosts outside the navigation allowlist approved for the session.
+    pub(super) approved_navigation_domains: HashSet<String>,
+    /// Browser sign-in credentials the user chose not to save, by origin.
+    pub(super) browser_session_credentials: HashMap<String, code_browser::login::Credentials>,
+    /// Origins whose login form was filled; page scripts there always ask.
+    pub(super) browser_filled_login_origins: HashSet<String>,
+    /// Markdown of recently visited pages, compared by `browser diff`.
+    pub(super) page_snapshots: crate::page_snapshots::PageSnapshots,
     pub(super) current_task: Option<AgentTask>,
     pub(super) pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
     pub(super) pending_request_user_input: HashMap<String, oneshot::Sender<crate::protocol::RequestUserInputResponse>>,
     pub(super) pending_dynamic_tools: HashMap<String, oneshot::Sender<DynamicToolResponse>>,
     pub(super) selected_mcp_tools: Vec<String>,
+    /// Parameter schemas of the built-in function tools advertised this turn.
+    pub(super) builtin_tool_schemas: HashMap<String, Value>,
     pub(super) pending_input: Vec<ResponseInputItem>,
     pub(super) pending_post_turn_input: Vec<ResponseInputItem>,
     pub(super) pending_user_input: Vec<QueuedUserInput>,
@@ -153,6 +170,8 @@ pub(super) struct State {
     pub(super) dry_run_guard: DryRunGuardState,
     /// Background execs by call_id
     pub(super) background_execs: std::collections::HashMap<String, BackgroundExecState>,
+    /// Commands started in a multiplexer pane, by call_id
+    pub(super) pane_jobs: HashMap<String, crate::terminal_panes::PaneJob>,
     /// Active foreground exec calls keyed by call_id (ExecCommandBegin/End lifecycle)
     pub(super) running_execs: HashMap<String, RunningExecMeta>,
     pub(super) next_internal_sub_id: u64,
@@ -503,6 +522,10 @@ pub(crate) struct Session {
     pub(super) last_screenshot_info: Mutex<Option<(PathBuf, Vec<u8>, Vec<u8>)>>, // (path, phash, dhash)
     pub(super) time_budget: Mutex<Option<RunTimeBudget>>,
     pub(super) confirm_guard: ConfirmGuardRuntime,
+    /// Command prefixes from `[terminal_panes]`.
+    pub(super) terminal_pane_commands: Vec<String>,
+    /// Language servers from `[lsp]`; `None` when disabled.
+    pub(super) lsp: Option<crate::lsp::LspManager>,
     pub(super) project_hooks: ProjectHooks,
     pub(super) project_commands: Vec<ProjectCommand>,
     pub(super) tool_output_max_bytes: usize,
@@ -1511,6 +1534,130 @@ impl Session {
         state.approved_commands.insert(pattern);
     }
 
+    pub(crate) fn approve_mcp_tool_for_session(&self, server: &str, tool: &str) {
+        let mut state = self.state.lock().unwrap();
+        state
+            .approved_mcp_tools
+            .insert((server.to_string(), tool.to_string()));
+    }
+
+    pub(crate) fn is_mcp_tool_approved_for_session(&self, server: &str, tool: &str) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .approved_mcp_tools
+            .contains(&(server.to_string(), tool.to_string()))
+    }
+
+    pub(crate) fn approve_browser_eval_for_session(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.browser_eval_approved = true;
+    }
+
+    pub(crate) fn is_browser_eval_approved_for_session(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.browser_eval_approved
+    }
+
+    pub(crate) fn approve_browser_downloads_for_session(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.browser_downloads_approved = true;
+    }
+
+    pub(crate) fn are_browser_downloads_approved_for_session(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.browser_downloads_approved
+    }
+
+    pub(crate) fn approve_navigation_domain(&self, host: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.approved_navigation_domains.insert(host.to_string());
+    }
+
+    pub(crate) fn is_navigation_domain_approved(&self, host: &str) -> bool {
+        let state = self.state.lock().unwrap();
+        state.approved_navigation_domains.contains(host)
+    }
+
+    pub(crate) fn approved_navigation_domains(&self) -> HashSet<String> {
+        let state = self.state.lock().unwrap();
+        state.approved_navigation_domains.clone()
+    }
+
+    pub(crate) fn remember_browser_credentials(
+        &self,
+        origin: &str,
+        credentials: code_browser::login::Credentials,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.browser_session_credentials.insert(origin.to_string(), credentials);
+    }
+
+    pub(crate) fn browser_session_credentials(
+        &self,
+        origin: &str,
+    ) -> Option<code_browser::login::Credentials> {
+        let state = self.state.lock().unwrap();
+        state.browser_session_credentials.get(origin).cloned()
+    }
+
+    pub(crate) fn forget_browser_credentials(&self, origin: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.browser_session_credentials.remove(origin);
+    }
+
+    pub(crate) fn mark_browser_login_filled(&self, origin: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.browser_filled_login_origins.insert(origin.to_string());
+    }
+
+    pub(crate) fn is_browser_login_filled(&self, origin: &str) -> bool {
+        let state = self.state.lock().unwrap();
+        state.browser_filled_login_origins.contains(origin)
+    }
+
+    /// Store the Markdown of a page just visited and return the previous visit, if any.
+    pub(crate) fn record_page_snapshot(
+        &self,
+        url: &str,
+        markdown: String,
+    ) -> Option<crate::page_snapshots::PreviousVisit> {
+        let mut state = self.state.lock().unwrap();
+        state.page_snapshots.record(url, markdown)
+    }
+
+    /// Remember the schemas of the built-in function tools sent with the
+    /// current request so calls can be validated before dispatch. MCP and
+    /// dynamic tools are validated against their own declarations instead.
+    pub(super) fn set_builtin_tool_schemas(&self, tools: &[OpenAiTool]) {
+        let schemas = tools
+            .iter()
+            .filter_map(|tool| match tool {
+                OpenAiTool::Function(tool)
+                    if self.mcp_connection_manager.parse_tool_name(&tool.name).is_none()
+                        && !self.is_dynamic_tool(None, &tool.name) =>
+                {
+                    serde_json::to_value(&tool.parameters)
+                        .ok()
+                        .map(|schema| (tool.name.clone(), schema))
+                }
+                _ => None,
+            })
+            .collect();
+        self.state.lock().unwrap().builtin_tool_schemas = schemas;
+    }
+
+    pub(super) fn builtin_tool_names(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        let mut names: Vec<String> = state.builtin_tool_schemas.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub(super) fn builtin_tool_schema(&self, name: &str) -> Option<Value> {
+        let state = self.state.lock().unwrap();
+        state.builtin_tool_schemas.get(name).cloned()
+    }
+
     /// Records items to both the rollout and the chat completions/ZDR
     /// transcript, if enabled.
     pub(super) async fn record_conversation_items(&self, items: &[ResponseItem]) {
@@ -2217,15 +2364,16 @@ impl Session {
         state.pending_input.push(input);
     }
 
-    pub async fn call_tool(
+    pub async fn call_tool_with_progress(
         &self,
         server: &str,
         tool: &str,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+        progress_tx: tokio::sync::mpsc::UnboundedSender<code_rmcp_client::McpToolCallProgress>,
     ) -> anyhow::Result<mcp_types::CallToolResult> {
         self.mcp_connection_manager
-            .call_tool(server, tool, arguments, timeout)
+            .call_tool_with_progress(server, tool, arguments, timeout, Some(progress_tx))
             .await
     }
 
@@ -2651,6 +2799,13 @@ impl State {
     pub fn partial_clone(&self) -> Self {
         Self {
             approved_commands: self.approved_commands.clone(),
+            approved_mcp_tools: self.approved_mcp_tools.clone(),
+            browser_eval_approved: self.browser_eval_approved,
+            browser_downloads_approved: self.browser_downloads_approved,
+            approved_navigation_domains: self.approved_navigation_domains.clone(),
+            browser_session_credentials: self.browser_session_credentials.clone(),
+            browser_filled_login_origins: self.browser_filled_login_origins.clone(),
+            page_snapshots: self.page_snapshots.clone(),
             history: self.history.clone(),
             // Preserve request_ordinal so reconfigurations (e.g., /reasoning)
             // do not reset provider ordering mid-session.
@@ -2658,6 +2813,7 @@ impl State {
             background_seq_by_sub_id: self.background_seq_by_sub_id.clone(),
             selected_mcp_tools: self.selected_mcp_tools.clone(),
             dry_run_guard: self.dry_run_guard.clone(),
+            pane_jobs: self.pane_jobs.clone(),
             next_internal_sub_id: self.next_internal_sub_id,
             context_timeline: self.context_timeline.clone(),
             environment_context_tracker: self.environment_context_tracker.clone(),
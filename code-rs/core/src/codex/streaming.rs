@@ -27,8 +27,14 @@ use crate::auth_accounts;
 use crate::account_switching::RateLimitSwitchState;
 use crate::agent_tool::current_agent_spawn_depth;
 use crate::agent_tool::external_agent_command_exists;
+use crate::mcp_connection_manager::McpManagerOptions;
 use crate::protocol::McpListToolsResponseEvent;
+use crate::protocol::McpCompletionRef;
+use crate::protocol::McpCompletionResponseEvent;
+use crate::protocol::McpServerLogEntry;
+use crate::protocol::McpServerLogsResponseEvent;
 use crate::protocol::TaskLifecycleEvent;
+use crate::protocol::ToolNamesResponseEvent;
 use crate::protocol::TaskLifecyclePhase;
 use crate::protocol::TaskOriginKind;
 use code_app_server_protocol::AuthMode as AppAuthMode;
@@ -226,6 +232,8 @@ pub(super) async fn submission_loop(
     tx_event: Sender<Event>,
 ) {
     let mut config = config;
+    // Resolving the project runs `git`, so do it once per configured session.
+    let mut history_project = crate::message_history::project_key(&config);
     let mut sess: Option<Arc<Session>> = None;
     let mut agent_manager_initialized = false;
     // shorthand - send an event when there is no active session
@@ -372,6 +380,7 @@ pub(super) async fn submission_loop(
                 resume_path,
                 demo_developer_message,
                 dynamic_tools,
+                active_profile,
             } => {
                 debug!(
                     "Configuring session: model={model}; provider={provider:?}; resume={resume_path:?}"
@@ -426,6 +435,7 @@ pub(super) async fn submission_loop(
                 updated_config.notify = notify.clone();
                 updated_config.cwd = cwd.clone();
                 updated_config.dynamic_tools = dynamic_tools.clone();
+                updated_config.active_profile = active_profile;
 
                 updated_config.model_family = find_family_for_model(&updated_config.model)
                     .unwrap_or_else(|| derive_default_model_family(&updated_config.model));
@@ -522,6 +532,8 @@ pub(super) async fn submission_loop(
                 }
 
                 config = Arc::clone(&new_config);
+                history_project = crate::message_history::project_key(&config);
+                crate::redaction::configure(&config.redaction);
 
                 let rollout_recorder = match rollout_recorder {
                     Some(rec) => Some(rec),
@@ -671,9 +683,45 @@ pub(super) async fn submission_loop(
                     drop(old_session_arc);
                 }
 
-                let (mcp_connection_manager, failed_clients) = match McpConnectionManager::new(
+                if let Some(profile) = config.browser_profile.as_deref() {
+                    match code_browser::profiles::profile_dir(&config.code_home, profile) {
+                        Ok(dir) => code_browser::global::set_active_profile_dir(Some(dir)).await,
+                        Err(err) => warn!("ignoring browser.profile: {err}"),
+                    }
+                }
+                if let Some(headless) = config.browser_headless {
+                    code_browser::global::set_headless_preference(headless).await;
+                }
+                if let Some(connect) = config.browser_connect.as_ref() {
+                    code_browser::global::set_cdp_target(Some(code_browser::global::CdpTarget {
+                        ws: connect.ws.clone(),
+                        port: connect.port,
+                        host: connect.host.clone(),
+                    }))
+                    .await;
+                }
+                code_browser::global::set_chrome_proxy(config.network_proxy.as_ref().map(|proxy| {
+                    code_browser::global::ChromeProxy {
+                        server: proxy.server(),
+                        // Chrome separates bypass rules with semicolons.
+                        bypass_list: proxy.bypass_list().replace(',', ";"),
+                        credentials: proxy.username.clone().map(|username| {
+                            code_browser::login::Credentials {
+                                username,
+                                password: proxy.password.clone().unwrap_or_default(),
+                            }
+                        }),
+                    }
+                }));
+
+                let (mcp_connection_manager, failed_clients) = match McpConnectionManager::new_with_options(
                     config.mcp_servers.clone(),
                     excluded_tools,
+                    McpManagerOptions {
+                        tool_naming: config.mcp_tool_naming,
+                        tool_priority: config.mcp_tool_priority.clone(),
+                        tool_cache_dir: Some(config.code_home.join("cache").join("mcp_tools")),
+                    },
                 )
                 .await
                 {
@@ -718,6 +766,11 @@ pub(super) async fn submission_loop(
                 tools_config.web_search_external = config.tools_web_search_external;
                 tools_config.web_search_indexed = config.tools_web_search_indexed;
                 tools_config.search_tool = config.tools_search_tool;
+                tools_config.fetch_url_tool = config.tools_fetch_url;
+                tools_config.read_file_tool = config.tools_read_file;
+                tools_config.code_index_tools = config.tools_code_index;
+                tools_config.git_history_tools = config.tools_git_history;
+                tools_config.web_search_backend = config.tools_web_search_backend.is_some();
 
                 let auth_mode = auth_manager
                     .as_ref()
@@ -776,6 +829,7 @@ pub(super) async fn submission_loop(
                         remote.refresh_remote_models().await;
                     });
                 }
+                let lsp = crate::lsp::LspManager::new(&config.lsp, &cwd);
                 let mut new_session = Arc::new(Session {
                     id: session_id,
                     client,
@@ -816,6 +870,8 @@ pub(super) async fn submission_loop(
                         RunTimeBudget::new(deadline, total)
                     })),
                     confirm_guard: ConfirmGuardRuntime::from_config(&config.confirm_guard),
+                    terminal_pane_commands: config.terminal_panes.commands.clone(),
+                    lsp,
                     project_hooks: config.project_hooks.clone(),
                     project_commands: config.project_commands.clone(),
                     tool_output_max_bytes: config.tool_output_max_bytes,
@@ -873,7 +929,7 @@ pub(super) async fn submission_loop(
 
                 // Gather history metadata for SessionConfiguredEvent.
                 let (history_log_id, history_entry_count) =
-                    crate::message_history::history_metadata(&config).await;
+                    crate::message_history::history_metadata(&history_project, &config).await;
 
                 // ack
                 let sess_arc = sess.as_ref().expect("session initialized");
@@ -1133,8 +1189,10 @@ pub(super) async fn submission_loop(
                 // currently, if ConfigureSession has resume path, this history will be ignored
                 let id = session_id;
                 let config = config.clone();
+                let project = history_project.clone();
                 tokio::spawn(async move {
-                    if let Err(e) = crate::message_history::append_entry(&text, &id, &config).await
+                    if let Err(e) =
+                        crate::message_history::append_entry(&text, &id, &project, &config).await
                     {
                         warn!("failed to append to message history: {e}");
                     }
@@ -1171,13 +1229,14 @@ pub(super) async fn submission_loop(
 
             Op::GetHistoryEntryRequest { offset, log_id } => {
                 let config = config.clone();
+                let project = history_project.clone();
                 let tx_event = tx_event.clone();
                 let sub_id = sub.id.clone();
 
                 tokio::spawn(async move {
                     // Run lookup in blocking thread because it does file IO + locking.
                     let entry_opt = tokio::task::spawn_blocking(move || {
-                        crate::message_history::lookup(log_id, offset, &config)
+                        crate::message_history::lookup(log_id, offset, &project, &config)
                     })
                     .await
                     .unwrap_or(None);
@@ -1209,37 +1268,187 @@ pub(super) async fn submission_loop(
                     }
                 };
 
-                let tools = sess
-                    .mcp_connection_manager
-                    .list_all_tools()
+                let event = Event {
+                    id: sub.id.clone(),
+                    event_seq: 0,
+                    msg: EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess)),
+                    order: None,
+                };
+
+                if let Err(e) = tx_event.send(event).await {
+                    warn!("failed to send McpListToolsResponse event: {e}");
+                }
+            }
+            Op::RefreshMcpTools { server } => {
+                let sess = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+
+                let tx_event = tx_event.clone();
+                let sub_id = sub.id.clone();
+                tokio::spawn(async move {
+                    let message = match sess
+                        .mcp_connection_manager
+                        .refresh_tools(server.as_deref())
+                        .await
+                    {
+                        Ok(results) if results.is_empty() => "No MCP servers are running".to_string(),
+                        Ok(results) => {
+                            let mut message = "Refreshed MCP tools:".to_string();
+                            for (server_name, result) in results {
+                                match result {
+                                    Ok(count) => message.push_str(&format!(
+                                        "\n• {server_name}: {count} tools"
+                                    )),
+                                    Err(err) => message.push_str(&format!(
+                                        "\n• {server_name}: failed: {err:#}"
+                                    )),
+                                }
+                            }
+                            message
+                        }
+                        Err(err) => format!("{err:#}"),
+                    };
+
+                    for msg in [
+                        EventMsg::BackgroundEvent(BackgroundEventEvent { message }),
+                        EventMsg::McpListToolsResponse(mcp_list_tools_response(&sess)),
+                    ] {
+                        let event = Event {
+                            id: sub_id.clone(),
+                            event_seq: 0,
+                            msg,
+                            order: None,
+                        };
+                        if let Err(e) = tx_event.send(event).await {
+                            warn!("failed to send MCP refresh event: {e}");
+                        }
+                    }
+                });
+            }
+            Op::ListToolNames => {
+                let sess = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+
+                let event = Event {
+                    id: sub.id.clone(),
+                    event_seq: 0,
+                    msg: EventMsg::ToolNamesResponse(ToolNamesResponseEvent {
+                        builtin: sess.builtin_tool_names(),
+                        mcp: sess.mcp_connection_manager.tool_name_mappings(),
+                    }),
+                    order: None,
+                };
+
+                if let Err(e) = tx_event.send(event).await {
+                    warn!("failed to send ToolNamesResponse event: {e}");
+                }
+            }
+            Op::McpServerLogs { server } => {
+                let sess = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+
+                let lines = sess.mcp_connection_manager.server_logs(&server);
+                let known = lines.is_some();
+                let entries = lines
+                    .unwrap_or_default()
                     .into_iter()
-                    .filter_map(|(name, tool)| {
-                        let value = serde_json::to_value(tool).ok()?;
-                        let converted = code_protocol::mcp::Tool::from_mcp_value(value).ok()?;
-                        Some((name, converted))
+                    .map(|line| McpServerLogEntry {
+                        timestamp: chrono::DateTime::<chrono::Utc>::from(line.at).to_rfc3339(),
+                        source: match line.source {
+                            code_rmcp_client::McpServerLogSource::Stderr => "stderr".to_string(),
+                            code_rmcp_client::McpServerLogSource::Notification => {
+                                "notification".to_string()
+                            }
+                        },
+                        level: line.level,
+                        message: line.message,
                     })
                     .collect();
-                let server_tools = sess.mcp_connection_manager.list_tools_by_server();
-                let server_failures = sess.mcp_connection_manager.list_server_failures();
 
                 let event = Event {
                     id: sub.id.clone(),
                     event_seq: 0,
-                    msg: EventMsg::McpListToolsResponse(McpListToolsResponseEvent {
-                        tools,
-                        server_tools: Some(server_tools),
-                        server_failures: Some(server_failures),
-                        resources: std::collections::HashMap::new(),
-                        resource_templates: std::collections::HashMap::new(),
-                        auth_statuses: std::collections::HashMap::new(),
+                    msg: EventMsg::McpServerLogsResponse(McpServerLogsResponseEvent {
+                        server,
+                        known,
+                        entries,
                     }),
                     order: None,
                 };
 
                 if let Err(e) = tx_event.send(event).await {
-                    warn!("failed to send McpListToolsResponse event: {e}");
+                    warn!("failed to send McpServerLogsResponse event: {e}");
                 }
             }
+            Op::McpCompleteArgument {
+                server,
+                reference,
+                argument,
+                value,
+            } => {
+                let sess = match sess.as_ref() {
+                    Some(sess) => Arc::clone(sess),
+                    None => {
+                        send_no_session_event(sub.id).await;
+                        continue;
+                    }
+                };
+
+                // Servers may be slow to answer; don't hold up the submission loop.
+                let tx_event = tx_event.clone();
+                let sub_id = sub.id.clone();
+                tokio::spawn(async move {
+                    let reference = match reference {
+                        McpCompletionRef::Prompt { name } => {
+                            code_rmcp_client::McpCompletionReference::Prompt(name)
+                        }
+                        McpCompletionRef::Resource { uri } => {
+                            code_rmcp_client::McpCompletionReference::ResourceTemplate(uri)
+                        }
+                    };
+                    let result = sess
+                        .mcp_connection_manager
+                        .complete_argument(&server, reference, argument.clone(), value.clone())
+                        .await;
+                    let (values, has_more, error) = match result {
+                        Ok(completion) => (completion.values, completion.has_more, None),
+                        Err(err) => (Vec::new(), false, Some(format!("{err:#}"))),
+                    };
+
+                    let event = Event {
+                        id: sub_id,
+                        event_seq: 0,
+                        msg: EventMsg::McpCompletionResponse(McpCompletionResponseEvent {
+                            server,
+                            argument,
+                            value,
+                            values,
+                            has_more,
+                            error,
+                        }),
+                        order: None,
+                    };
+
+                    if let Err(e) = tx_event.send(event).await {
+                        warn!("failed to send McpCompletionResponse event: {e}");
+                    }
+                });
+            }
             Op::ListCustomPrompts => {
                 let sess = match sess.as_ref() {
                     Some(sess) => Arc::clone(sess),
@@ -1440,6 +1649,28 @@ pub(super) async fn submission_loop(
     debug!("Agent loop exited");
 }
 
+fn mcp_list_tools_response(sess: &Session) -> McpListToolsResponseEvent {
+    let tools = sess
+        .mcp_connection_manager
+        .list_all_tools()
+        .into_iter()
+        .filter_map(|(name, tool)| {
+            let value = serde_json::to_value(tool).ok()?;
+            let converted = code_protocol::mcp::Tool::from_mcp_value(value).ok()?;
+            Some((name, converted))
+        })
+        .collect();
+
+    McpListToolsResponseEvent {
+        tools,
+        server_tools: Some(sess.mcp_connection_manager.list_tools_by_server()),
+        server_failures: Some(sess.mcp_connection_manager.list_server_failures()),
+        resources: std::collections::HashMap::new(),
+        resource_templates: std::collections::HashMap::new(),
+        auth_statuses: std::collections::HashMap::new(),
+    }
+}
+
 fn merge_developer_message(existing: Option<String>, extra: &str) -> Option<String> {
     let extra_trimmed = extra.trim();
     if extra_trimmed.is_empty() {
@@ -3175,6 +3406,7 @@ async fn run_turn(
             tc.sandbox_policy.clone(),
             effective_family,
         );
+        sess.mcp_connection_manager.refresh_changed_tools().await;
         let mcp_tools = select_mcp_tools_for_turn(
             sess.mcp_connection_manager.list_all_tools(),
             sess.get_mcp_tool_selection(),
@@ -3198,6 +3430,7 @@ async fn run_turn(
             agents_active,
             sess.dynamic_tools.as_slice(),
         );
+        sess.set_builtin_tool_schemas(&prompt.tools);
 
         // Start a new scratchpad for this HTTP attempt
         sess.begin_attempt_scratchpad();
@@ -4525,6 +4758,31 @@ async fn try_run_turn(
                             }
                         });
                     }
+
+                    // Awaited so front-ends see the new totals when the turn completes.
+                    let quota_home = sess.client.code_home().to_path_buf();
+                    let quota_scope = usage_quota::scope_for_profile(
+                        sess.client.config().active_profile.as_deref(),
+                    )
+                    .to_string();
+                    let quota_model = sess.client.get_model();
+                    let quota_usage = usage.clone();
+                    let recorded = tokio::task::spawn_blocking(move || {
+                        let now = Utc::now();
+                        usage_quota::record_usage(
+                            &quota_home,
+                            &quota_scope,
+                            &quota_model,
+                            &quota_usage,
+                            now,
+                        )
+                    })
+                    .await;
+                    match recorded {
+                        Ok(Err(err)) => warn!("Failed to persist quota usage: {err}"),
+                        Err(err) => warn!("Quota usage task failed: {err}"),
+                        Ok(Ok(())) => {}
+                    }
                 }
 
                 let unified_diff = turn_diff_tracker.get_unified_diff();
@@ -4684,21 +4942,32 @@ async fn handle_response_item(
             ..
         } => {
             info!("FunctionCall: {name}({arguments})");
-            Some(
-                handle_function_call(
-                    sess,
-                    turn_diff_tracker,
-                    sub_id.to_string(),
-                    namespace,
-                    name,
-                    arguments,
-                    call_id,
-                    seq_hint,
-                    output_index,
-                    attempt_req,
-                )
-                .await,
+            let otel_event_manager = sess.client.get_otel_event_manager();
+            let tool_call = otel_event_manager
+                .as_ref()
+                .map(|_| (name.clone(), call_id.clone(), arguments.clone()));
+            let started = Instant::now();
+            let output = handle_function_call(
+                sess,
+                turn_diff_tracker,
+                sub_id.to_string(),
+                namespace,
+                name,
+                arguments,
+                call_id,
+                seq_hint,
+                output_index,
+                attempt_req,
             )
+            .await;
+            if let (Some(manager), Some((name, call_id, arguments))) =
+                (otel_event_manager.as_ref(), tool_call)
+            {
+                let (success, text) = tool_call_outcome(&output);
+                let duration = started.elapsed();
+                manager.tool_result(&name, &call_id, &arguments, duration, success, &text);
+            }
+            Some(output)
         }
         ResponseItem::ToolSearchCall {
             call_id,
@@ -4831,7 +5100,7 @@ async fn handle_response_item(
         ResponseItem::GhostSnapshot { .. } => None,
         ResponseItem::Other => None,
     };
-    Ok(output)
+    Ok(output.map(crate::redaction::redact_tool_output))
 }
 
 async fn handle_image_generation_call(
@@ -5121,6 +5390,26 @@ mod preview_tests {
     }
 }
 
+/// Whether a tool call succeeded, plus its output for the tool-result event.
+fn tool_call_outcome(output: &ResponseInputItem) -> (bool, String) {
+    match output {
+        ResponseInputItem::FunctionCallOutput { output, .. }
+        | ResponseInputItem::CustomToolCallOutput { output, .. } => {
+            (output.success != Some(false), output.to_string())
+        }
+        ResponseInputItem::McpToolCallOutput { result, .. } => match result {
+            Ok(result) => (
+                result.is_error != Some(true),
+                serde_json::to_string(&result.content).unwrap_or_default(),
+            ),
+            Err(err) => (false, err.clone()),
+        },
+        ResponseInputItem::Message { .. } | ResponseInputItem::ToolSearchOutput { .. } => {
+            (true, String::new())
+        }
+    }
+}
+
 async fn handle_function_call(
     sess: &Session,
     turn_diff_tracker: &mut TurnDiffTracker,
@@ -5134,6 +5423,11 @@ async fn handle_function_call(
     attempt_req: u64,
 ) -> ResponseInputItem {
     let ctx = ToolCallCtx::new(sub_id.clone(), call_id.clone(), seq_hint, output_index);
+    if namespace.is_none()
+        && let Some(rejection) = validate_builtin_tool_arguments(sess, &name, &arguments, &call_id)
+    {
+        return rejection;
+    }
     match name.as_str() {
         "container.exec" | "shell" | "local_shell" => {
             let params = match parse_container_exec_arguments(arguments, sess, &call_id) {
@@ -5190,6 +5484,15 @@ async fn handle_function_call(
         // unified browser tool
         "browser" => handle_browser_tool(sess, &ctx, arguments).await,
         "web_fetch" => handle_web_fetch(sess, &ctx, arguments).await,
+        "fetch_url" => handle_fetch_url(sess, &ctx, arguments).await,
+        "read_file" => handle_read_file(sess, &ctx, arguments).await,
+        "code_search" | "goto_definition" => {
+            handle_code_index_tool(sess, &ctx, &name, arguments).await
+        }
+        "git_log" | "git_blame" | "git_show" => {
+            handle_git_history_tool(sess, &ctx, &name, arguments).await
+        }
+        "web_search" => handle_web_search(sess, &ctx, arguments).await,
         "image_view" => handle_image_view(sess, &ctx, arguments).await,
         "wait" => handle_wait(sess, &ctx, arguments).await,
         "gh_run_wait" => handle_gh_run_wait(sess, &ctx, arguments).await,
@@ -5241,6 +5544,32 @@ async fn handle_function_call(
     }
 }
 
+/// Check a built-in tool call against the schema advertised for it. Unparsable
+/// JSON is left to the tool's own parser so its existing error is preserved.
+fn validate_builtin_tool_arguments(
+    sess: &Session,
+    name: &str,
+    arguments: &str,
+    call_id: &str,
+) -> Option<ResponseInputItem> {
+    let schema = sess.builtin_tool_schema(name)?;
+    let value = if arguments.trim().is_empty() {
+        serde_json::Value::Object(serde_json::Map::new())
+    } else {
+        serde_json::from_str::<serde_json::Value>(arguments).ok()?
+    };
+    let errors = crate::tool_arguments_validation::validate_tool_arguments(&schema, &value, true).err()?;
+    Some(ResponseInputItem::FunctionCallOutput {
+        call_id: call_id.to_string(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(
+                crate::tool_arguments_validation::format_validation_errors(name, &errors),
+            ),
+            success: Some(false),
+        },
+    })
+}
+
 #[derive(serde::Deserialize)]
 struct ApplyPatchToolCallParams {
     input: String,
@@ -5955,6 +6284,266 @@ mod bridge_tool_tests {
     }
 }
 
+// Helper: remove obvious noisy blocks before markdown conversion.
+// This uses a lightweight ASCII-insensitive scan to drop whole
+// elements whose contents should never be surfaced to the model
+// (scripts, styles, templates, headers/footers/navigation, etc.).
+fn strip_noisy_tags(mut html: String) -> String {
+    // Remove <script>, <style>, and <noscript> blocks with a simple
+    // ASCII case-insensitive scan that preserves UTF-8 boundaries.
+    // This avoids allocating lowercase copies and accidentally using
+    // indices from a different string representation.
+    fn eq_ascii_ci(a: u8, b: u8) -> bool {
+        a.to_ascii_lowercase() == b.to_ascii_lowercase()
+    }
+    fn starts_with_tag_ci(bytes: &[u8], tag: &[u8]) -> bool {
+        if bytes.len() < tag.len() { return false; }
+        for i in 0..tag.len() {
+            if !eq_ascii_ci(bytes[i], tag[i]) { return false; }
+        }
+        true
+    }
+    // Find the next opening tag like "<script" (allowing whitespace after '<').
+    fn find_open_tag_ci(s: &str, tag: &str, from: usize) -> Option<usize> {
+        let bytes = s.as_bytes();
+        let tag_bytes = tag.as_bytes();
+        let mut i = from;
+        while i + 1 < bytes.len() {
+            if bytes[i] == b'<' {
+                let mut j = i + 1;
+                while j < bytes.len() && (bytes[j] == b' ' || bytes[j] == b'\t' || bytes[j] == b'\n' || bytes[j] == b'\r') {
+                    j += 1;
+                }
+                if j < bytes.len() && starts_with_tag_ci(&bytes[j..], tag_bytes) {
+                    return Some(i);
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+    // Find the corresponding closing tag like "</script>" starting at or after `from`.
+    // Returns the byte index just after the closing '>' if found.
+    fn find_close_after_ci(s: &str, tag: &str, from: usize) -> Option<usize> {
+        let bytes = s.as_bytes();
+        let tag_bytes = tag.as_bytes();
+        let mut i = from;
+        while i + 2 < bytes.len() { // need at least '<' '/' and one tag byte
+            if bytes[i] == b'<' && i + 1 < bytes.len() && bytes[i + 1] == b'/' {
+                let mut j = i + 2;
+                // Optional whitespace before tag name
+                while j < bytes.len() && (bytes[j] == b' ' || bytes[j] == b'\t' || bytes[j] == b'\n' || bytes[j] == b'\r') {
+                    j += 1;
+                }
+                if starts_with_tag_ci(&bytes[j..], tag_bytes) {
+                    // Advance past tag name
+                    j += tag_bytes.len();
+                    // Skip optional whitespace until '>'
+                    while j < bytes.len() && bytes[j] != b'>' {
+                        j += 1;
+                    }
+                    if j < bytes.len() && bytes[j] == b'>' {
+                        return Some(j + 1);
+                    }
+                    return None; // No closing '>'
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+
+    // Keep this conservative to avoid dropping content.
+    let tags = ["script", "style", "noscript"];
+    for tag in tags.iter() {
+        let mut guard = 0;
+        loop {
+            if guard > 64 { break; }
+            let Some(start) = find_open_tag_ci(&html, tag, 0) else { break; };
+            let search_from = start + 1; // after '<'
+            if let Some(end) = find_close_after_ci(&html, tag, search_from) {
+                // Safe because both start and end are on ASCII boundaries ('<' and '>')
+                html.replace_range(start..end, "");
+            } else {
+                // No close tag found; drop from the opening tag to end
+                html.truncate(start);
+                break;
+            }
+            guard += 1;
+        }
+    }
+    html
+}
+
+// Try to keep only <main> content if present; drastically reduces
+// boilerplate from navigation and login banners on many sites.
+fn extract_main(html: &str) -> Option<String> {
+    // Find opening <main ...>
+    let bytes = html.as_bytes();
+    let open = {
+        let mut i = 0usize;
+        let tag = b"main";
+        while i + 5 < bytes.len() { // < m a i n > (min)
+            if bytes[i] == b'<' {
+                // skip '<' and whitespace
+                let mut j = i + 1;
+                while j < bytes.len() && bytes[j].is_ascii_whitespace() { j += 1; }
+                if j + tag.len() <= bytes.len() && bytes[j..j+tag.len()].eq_ignore_ascii_case(tag) {
+                    // Found '<main'; now find '>'
+                    while j < bytes.len() && bytes[j] != b'>' { j += 1; }
+                    if j < bytes.len() { Some((i, j + 1)) } else { None }
+                } else { None }
+            } else { None }
+                .map(|pair| return pair);
+            i += 1;
+        }
+        None
+    };
+    let (start, after_open) = open?;
+    // Find closing </main>
+    let mut i = after_open;
+    let tag_close = b"</main";
+    while i + tag_close.len() + 1 < bytes.len() {
+        if bytes[i] == b'<' && bytes[i+1] == b'/' {
+            if bytes[i..].len() >= tag_close.len() && bytes[i..i+tag_close.len()].eq_ignore_ascii_case(tag_close) {
+                // Find closing '>'
+                let mut j = i + tag_close.len();
+                while j < bytes.len() && bytes[j] != b'>' { j += 1; }
+                if j < bytes.len() {
+                    return Some(html[start..j+1].to_string());
+                } else {
+                    return Some(html[start..].to_string());
+                }
+            }
+        }
+        i += 1;
+    }
+    Some(html[start..].to_string())
+}
+
+// Inside fenced code blocks, collapse massively-escaped Windows paths like
+// `C:\\Users\\...` to `C:\Users\...`. Only applies to drive-rooted paths.
+fn unescape_windows_paths(line: &str) -> String {
+    let bytes = line.as_bytes();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0usize;
+    while i < bytes.len() {
+        // Pattern: [A-Za-z] : \\+
+        if i + 3 < bytes.len()
+            && bytes[i].is_ascii_alphabetic()
+            && bytes[i+1] == b':'
+            && bytes[i+2] == b'\\'
+            && bytes[i+3] == b'\\'
+        {
+            // Emit drive and a single backslash
+            out.push(bytes[i] as char);
+            out.push(':');
+            out.push('\\');
+            // Skip all following backslashes in this run
+            i += 4;
+            while i < bytes.len() && bytes[i] == b'\\' { i += 1; }
+            continue;
+        }
+        out.push(bytes[i] as char);
+        i += 1;
+    }
+    out
+}
+
+// Lightweight cleanup on the resulting markdown to remove leaked
+// JSON blobs and obvious client boot payloads that sometimes escape
+// the <script> filter on complex sites. Avoids touching fenced code.
+fn postprocess_markdown(md: &str) -> String {
+    let mut out: Vec<String> = Vec::with_capacity(md.len() / 64 + 1);
+    let mut in_fence = false;
+    let mut empty_run = 0usize;
+    for line in md.lines() {
+        // Track fenced code blocks
+        if let Some(rest) = line.trim_start().strip_prefix("```") {
+            in_fence = !in_fence;
+            let _lang = if in_fence { Some(rest.trim()) } else { None };
+            out.push(line.to_string());
+            empty_run = 0;
+            continue;
+        }
+        if in_fence {
+            // Only normalize Windows path over-escaping; do not alter other content.
+            let normalized = unescape_windows_paths(line);
+            out.push(normalized);
+            continue;
+        }
+
+        let trimmed = line.trim();
+        // Drop extremely long single lines only if they're likely SPA boot payloads
+        if trimmed.len() > 8000 { continue; }
+        // Common SPA boot keys that shouldn't appear in human output.
+        // Keep this list tight to avoid dropping legitimate examples.
+        if trimmed.contains("\"payload\"") || trimmed.contains("\"props\"") || trimmed.contains("\"preloaded_records\"") || trimmed.contains("\"appPayload\"") || trimmed.contains("\"preloadedQueries\"") {
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            // Collapse multiple empty lines to max 1
+            if empty_run == 0 {
+                out.push(String::new());
+            }
+            empty_run += 1;
+        } else {
+            out.push(line.to_string());
+            empty_run = 0;
+        }
+    }
+    // Trim leading/trailing blank lines
+    let mut s = out.join("\n");
+    while s.starts_with('\n') { s.remove(0); }
+    while s.ends_with('\n') { s.pop(); }
+    s
+}
+
+// Helper: convert HTML to markdown and truncate if too large.
+fn convert_html_to_markdown_trimmed(html: String, max_chars: usize, readability: bool, base_url: &str) -> crate::error::Result<(String, bool)> {
+    let options = htmd::options::Options {
+        heading_style: htmd::options::HeadingStyle::Atx,
+        code_block_style: htmd::options::CodeBlockStyle::Fenced,
+        link_style: htmd::options::LinkStyle::Inlined,
+        ..Default::default()
+    };
+    let converter = htmd::HtmlToMarkdown::builder().options(options).build();
+    let article = if readability {
+        crate::web_readability::extract_article(&html)
+    } else {
+        None
+    };
+    let reduced = article.or_else(|| extract_main(&html)).unwrap_or(html);
+    let sanitized = strip_noisy_tags(reduced);
+    let (sanitized, links) = crate::html_links::replace_links(&sanitized, base_url);
+    let (sanitized, tables) = crate::html_tables::replace_tables(&sanitized, |cell| {
+        converter.convert(cell).unwrap_or_default()
+    });
+    let markdown = converter.convert(&sanitized)?;
+    let markdown = crate::html_tables::restore_tables(&markdown, &tables);
+    let markdown = crate::html_links::restore_links(&markdown);
+    let markdown = postprocess_markdown(&markdown);
+    let mut truncated = false;
+    let rendered = {
+        let char_count = markdown.chars().count();
+        if char_count > max_chars {
+            truncated = true;
+            let mut s: String = markdown.chars().take(max_chars).collect();
+            s.push_str("\n\n… (truncated)\n");
+            s
+        } else {
+            markdown
+        }
+    };
+    // Only list links whose markers survived truncation.
+    let rendered = match crate::html_links::link_references(&rendered, &links) {
+        Some(references) => format!("{}\n\n{references}", rendered.trim_end()),
+        None => rendered,
+    };
+    Ok((rendered, truncated))
+}
+
 async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
     // Include raw params in begin event for observability
     let mut params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
@@ -5967,10 +6556,28 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
             }
         }
     }
+    let requested_url = params_for_event
+        .as_ref()
+        .and_then(|value| value.get("url"))
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    if let Some(url) = requested_url.as_deref()
+        && let Err(message) = check_navigation_policy(sess, ctx, url).await
+    {
+        return ResponseInputItem::FunctionCallOutput {
+            call_id: ctx.call_id.clone(),
+            output: FunctionCallOutputPayload {
+                body: code_protocol::models::FunctionCallOutputBody::Text(format!("Cannot fetch {url}: {message}")),
+                success: Some(false)},
+        };
+    }
     let arguments_clone = arguments.clone();
     let call_id_clone = ctx.call_id.clone();
+    let readability = sess.client.config().tools_web_fetch_readability;
+    let navigation_policy = sess.client.config().navigation_policy.clone();
+    let approved_hosts = sess.approved_navigation_domains();
 
-    execute_custom_tool(
+    let output = execute_custom_tool(
         sess,
         ctx,
         "web_fetch".to_string(),
@@ -6191,10 +6798,12 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 ua: &str,
                 timeout: Duration,
                 extra_headers: Option<&[(reqwest::header::HeaderName, &'static str)]>,
+                redirects: reqwest::redirect::Policy,
             ) -> Result<reqwest::Response, reqwest::Error> {
-                let client = reqwest::Client::builder()
+                let client = crate::http_client::apply_proxy(reqwest::Client::builder())
                     .timeout(timeout)
                     .user_agent(ua)
+                    .redirect(redirects)
                     .build()?;
                 let mut req = client.get(url)
                     // Add a few browser-like headers to reduce blocks
@@ -6208,222 +6817,6 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 req.send().await
             }
 
-            // Helper: remove obvious noisy blocks before markdown conversion.
-            // This uses a lightweight ASCII-insensitive scan to drop whole
-            // elements whose contents should never be surfaced to the model
-            // (scripts, styles, templates, headers/footers/navigation, etc.).
-            fn strip_noisy_tags(mut html: String) -> String {
-                // Remove <script>, <style>, and <noscript> blocks with a simple
-                // ASCII case-insensitive scan that preserves UTF-8 boundaries.
-                // This avoids allocating lowercase copies and accidentally using
-                // indices from a different string representation.
-                fn eq_ascii_ci(a: u8, b: u8) -> bool {
-                    a.to_ascii_lowercase() == b.to_ascii_lowercase()
-                }
-                fn starts_with_tag_ci(bytes: &[u8], tag: &[u8]) -> bool {
-                    if bytes.len() < tag.len() { return false; }
-                    for i in 0..tag.len() {
-                        if !eq_ascii_ci(bytes[i], tag[i]) { return false; }
-                    }
-                    true
-                }
-                // Find the next opening tag like "<script" (allowing whitespace after '<').
-                fn find_open_tag_ci(s: &str, tag: &str, from: usize) -> Option<usize> {
-                    let bytes = s.as_bytes();
-                    let tag_bytes = tag.as_bytes();
-                    let mut i = from;
-                    while i + 1 < bytes.len() {
-                        if bytes[i] == b'<' {
-                            let mut j = i + 1;
-                            while j < bytes.len() && (bytes[j] == b' ' || bytes[j] == b'\t' || bytes[j] == b'\n' || bytes[j] == b'\r') {
-                                j += 1;
-                            }
-                            if j < bytes.len() && starts_with_tag_ci(&bytes[j..], tag_bytes) {
-                                return Some(i);
-                            }
-                        }
-                        i += 1;
-                    }
-                    None
-                }
-                // Find the corresponding closing tag like "</script>" starting at or after `from`.
-                // Returns the byte index just after the closing '>' if found.
-                fn find_close_after_ci(s: &str, tag: &str, from: usize) -> Option<usize> {
-                    let bytes = s.as_bytes();
-                    let tag_bytes = tag.as_bytes();
-                    let mut i = from;
-                    while i + 2 < bytes.len() { // need at least '<' '/' and one tag byte
-                        if bytes[i] == b'<' && i + 1 < bytes.len() && bytes[i + 1] == b'/' {
-                            let mut j = i + 2;
-                            // Optional whitespace before tag name
-                            while j < bytes.len() && (bytes[j] == b' ' || bytes[j] == b'\t' || bytes[j] == b'\n' || bytes[j] == b'\r') {
-                                j += 1;
-                            }
-                            if starts_with_tag_ci(&bytes[j..], tag_bytes) {
-                                // Advance past tag name
-                                j += tag_bytes.len();
-                                // Skip optional whitespace until '>'
-                                while j < bytes.len() && bytes[j] != b'>' {
-                                    j += 1;
-                                }
-                                if j < bytes.len() && bytes[j] == b'>' {
-                                    return Some(j + 1);
-                                }
-                                return None; // No closing '>'
-                            }
-                        }
-                        i += 1;
-                    }
-                    None
-                }
-
-                // Keep this conservative to avoid dropping content.
-                let tags = ["script", "style", "noscript"];
-                for tag in tags.iter() {
-                    let mut guard = 0;
-                    loop {
-                        if guard > 64 { break; }
-                        let Some(start) = find_open_tag_ci(&html, tag, 0) else { break; };
-                        let search_from = start + 1; // after '<'
-                        if let Some(end) = find_close_after_ci(&html, tag, search_from) {
-                            // Safe because both start and end are on ASCII boundaries ('<' and '>')
-                            html.replace_range(start..end, "");
-                        } else {
-                            // No close tag found; drop from the opening tag to end
-                            html.truncate(start);
-                            break;
-                        }
-                        guard += 1;
-                    }
-                }
-                html
-            }
-
-            // Try to keep only <main> content if present; drastically reduces
-            // boilerplate from navigation and login banners on many sites.
-            fn extract_main(html: &str) -> Option<String> {
-                // Find opening <main ...>
-                let bytes = html.as_bytes();
-                let open = {
-                    let mut i = 0usize;
-                    let tag = b"main";
-                    while i + 5 < bytes.len() { // < m a i n > (min)
-                        if bytes[i] == b'<' {
-                            // skip '<' and whitespace
-                            let mut j = i + 1;
-                            while j < bytes.len() && bytes[j].is_ascii_whitespace() { j += 1; }
-                            if j + tag.len() <= bytes.len() && bytes[j..j+tag.len()].eq_ignore_ascii_case(tag) {
-                                // Found '<main'; now find '>'
-                                while j < bytes.len() && bytes[j] != b'>' { j += 1; }
-                                if j < bytes.len() { Some((i, j + 1)) } else { None }
-                            } else { None }
-                        } else { None }
-                            .map(|pair| return pair);
-                        i += 1;
-                    }
-                    None
-                };
-                let (start, after_open) = open?;
-                // Find closing </main>
-                let mut i = after_open;
-                let tag_close = b"</main";
-                while i + tag_close.len() + 1 < bytes.len() {
-                    if bytes[i] == b'<' && bytes[i+1] == b'/' {
-                        if bytes[i..].len() >= tag_close.len() && bytes[i..i+tag_close.len()].eq_ignore_ascii_case(tag_close) {
-                            // Find closing '>'
-                            let mut j = i + tag_close.len();
-                            while j < bytes.len() && bytes[j] != b'>' { j += 1; }
-                            if j < bytes.len() {
-                                return Some(html[start..j+1].to_string());
-                            } else {
-                                return Some(html[start..].to_string());
-                            }
-                        }
-                    }
-                    i += 1;
-                }
-                Some(html[start..].to_string())
-            }
-
-            // Inside fenced code blocks, collapse massively-escaped Windows paths like
-            // `C:\\Users\\...` to `C:\Users\...`. Only applies to drive-rooted paths.
-            fn unescape_windows_paths(line: &str) -> String {
-                let bytes = line.as_bytes();
-                let mut out = String::with_capacity(line.len());
-                let mut i = 0usize;
-                while i < bytes.len() {
-                    // Pattern: [A-Za-z] : \\+
-                    if i + 3 < bytes.len()
-                        && bytes[i].is_ascii_alphabetic()
-                        && bytes[i+1] == b':'
-                        && bytes[i+2] == b'\\'
-                        && bytes[i+3] == b'\\'
-                    {
-                        // Emit drive and a single backslash
-                        out.push(bytes[i] as char);
-                        out.push(':');
-                        out.push('\\');
-                        // Skip all following backslashes in this run
-                        i += 4;
-                        while i < bytes.len() && bytes[i] == b'\\' { i += 1; }
-                        continue;
-                    }
-                    out.push(bytes[i] as char);
-                    i += 1;
-                }
-                out
-            }
-
-            // Lightweight cleanup on the resulting markdown to remove leaked
-            // JSON blobs and obvious client boot payloads that sometimes escape
-            // the <script> filter on complex sites. Avoids touching fenced code.
-            fn postprocess_markdown(md: &str) -> String {
-                let mut out: Vec<String> = Vec::with_capacity(md.len() / 64 + 1);
-                let mut in_fence = false;
-                let mut empty_run = 0usize;
-                for line in md.lines() {
-                    // Track fenced code blocks
-                    if let Some(rest) = line.trim_start().strip_prefix("```") {
-                        in_fence = !in_fence;
-                        let _lang = if in_fence { Some(rest.trim()) } else { None };
-                        out.push(line.to_string());
-                        empty_run = 0;
-                        continue;
-                    }
-                    if in_fence {
-                        // Only normalize Windows path over-escaping; do not alter other content.
-                        let normalized = unescape_windows_paths(line);
-                        out.push(normalized);
-                        continue;
-                    }
-
-                    let trimmed = line.trim();
-                    // Drop extremely long single lines only if they're likely SPA boot payloads
-                    if trimmed.len() > 8000 { continue; }
-                    // Common SPA boot keys that shouldn't appear in human output.
-                    // Keep this list tight to avoid dropping legitimate examples.
-                    if trimmed.contains("\"payload\"") || trimmed.contains("\"props\"") || trimmed.contains("\"preloaded_records\"") || trimmed.contains("\"appPayload\"") || trimmed.contains("\"preloadedQueries\"") {
-                        continue;
-                    }
-
-                    if trimmed.is_empty() {
-                        // Collapse multiple empty lines to max 1
-                        if empty_run == 0 {
-                            out.push(String::new());
-                        }
-                        empty_run += 1;
-                    } else {
-                        out.push(line.to_string());
-                        empty_run = 0;
-                    }
-                }
-                // Trim leading/trailing blank lines
-                let mut s = out.join("\n");
-                while s.starts_with('\n') { s.remove(0); }
-                while s.ends_with('\n') { s.pop(); }
-                s
-            }
-
             // Domain-specific: extract rich content from GitHub issue/PR pages
             // without requiring a JS-capable browser. We parse JSON-LD and the
             // inlined GraphQL payload (preloadedQueries) to reconstruct the
@@ -6571,34 +6964,6 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 Some(out)
             }
 
-            // Helper: convert HTML to markdown and truncate if too large.
-            fn convert_html_to_markdown_trimmed(html: String, max_chars: usize) -> crate::error::Result<(String, bool)> {
-                let options = htmd::options::Options {
-                    heading_style: htmd::options::HeadingStyle::Atx,
-                    code_block_style: htmd::options::CodeBlockStyle::Fenced,
-                    link_style: htmd::options::LinkStyle::Inlined,
-                    ..Default::default()
-                };
-                let converter = htmd::HtmlToMarkdown::builder().options(options).build();
-                let reduced = extract_main(&html).unwrap_or(html);
-                let sanitized = strip_noisy_tags(reduced);
-                let markdown = converter.convert(&sanitized)?;
-                let markdown = postprocess_markdown(&markdown);
-                let mut truncated = false;
-                let rendered = {
-                    let char_count = markdown.chars().count();
-                    if char_count > max_chars {
-                        truncated = true;
-                        let mut s: String = markdown.chars().take(max_chars).collect();
-                        s.push_str("\n\n… (truncated)\n");
-                        s
-                    } else {
-                        markdown
-                    }
-                };
-                Ok((rendered, truncated))
-            }
-
             // Helper: detect WAF/challenge pages to avoid dumping challenge content.
             fn detect_block_vendor(_status: reqwest::StatusCode, body: &str) -> Option<&'static str> {
                 // Identify common bot-challenge pages regardless of HTTP status.
@@ -6636,7 +7001,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
 
             if matches!(params.mode.as_deref(), Some("browser")) {
                 if let Some(browser_fetch) = fetch_html_via_browser(&params.url, timeout, true).await {
-                    let (markdown, truncated) = match convert_html_to_markdown_trimmed(browser_fetch.html, 120_000) {
+                    let (markdown, truncated) = match convert_html_to_markdown_trimmed(browser_fetch.html, 120_000, readability, browser_fetch.final_url.as_deref().unwrap_or(&params.url)) {
                         Ok(t) => t,
                         Err(e) => {
                             return ResponseInputItem::FunctionCallOutput {
@@ -6663,13 +7028,18 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                     };
                 }
             }
+            // Every redirect hop is checked, not just where the request lands.
+            let mut trusted_hosts = approved_hosts;
+            trusted_hosts.extend(crate::navigation_policy::host_of(&params.url));
+            let redirects = || navigation_policy.clone().redirect_policy(trusted_hosts.clone(), 10);
             // Attempt 1: Codex UA + polite headers
-            let resp = match do_request(&params.url, &code_ua, timeout, None).await {
+            let resp = match do_request(&params.url, &code_ua, timeout, None, redirects()).await {
                 Ok(r) => r,
                 Err(e) => {
+                    let message = crate::fetch_url::describe_error(e);
                     return ResponseInputItem::FunctionCallOutput {
                         call_id: call_id_clone,
-                        output: FunctionCallOutputPayload {body: code_protocol::models::FunctionCallOutputBody::Text(format!("Request failed: {e}")), success: Some(false)},
+                        output: FunctionCallOutputPayload {body: code_protocol::models::FunctionCallOutputBody::Text(message), success: Some(false)},
                     };
                 }
             };
@@ -6678,8 +7048,104 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
             let mut status = resp.status();
             let mut final_url = resp.url().to_string();
             let mut headers = resp.headers().clone();
+            // Redirects must not escape the navigation policy.
+            if let Some(host) = crate::navigation_policy::host_of(&final_url) {
+                let refusal = match navigation_policy.evaluate(&final_url) {
+                    crate::navigation_policy::NavigationDecision::Allow => None,
+                    crate::navigation_policy::NavigationDecision::Deny(message) => Some(message),
+                    crate::navigation_policy::NavigationDecision::Ask(_)
+                        if crate::navigation_policy::host_of(&params.url).as_ref() == Some(&host)
+                            || sess.is_navigation_domain_approved(&host) =>
+                    {
+                        None
+                    }
+                    crate::navigation_policy::NavigationDecision::Ask(_) => Some(format!(
+                        "{host} needs approval first; fetch {final_url} directly to be asked"
+                    )),
+                };
+                if let Some(message) = refusal {
+                    return ResponseInputItem::FunctionCallOutput {
+                        call_id: call_id_clone,
+                        output: FunctionCallOutputPayload {body: code_protocol::models::FunctionCallOutputBody::Text(format!("Redirected to {final_url}: {message}")), success: Some(false)},
+                    };
+                }
+            }
+            // PDFs are read as bytes and converted to text with page markers.
+            let may_be_pdf = crate::pdf_text::response_may_be_pdf(
+                headers
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or(""),
+                &final_url,
+            );
+            let body_result = if may_be_pdf {
+                use futures::StreamExt;
+
+                // Read at most the fetch_url cap so a huge download can't exhaust memory.
+                let mut bytes = Vec::new();
+                let mut read_error = None;
+                let mut stream = resp.bytes_stream();
+                while let Some(chunk) = stream.next().await {
+                    let chunk = match chunk {
+                        Ok(chunk) => chunk,
+                        Err(e) => {
+                            read_error = Some(e.to_string());
+                            break;
+                        }
+                    };
+                    let room = crate::fetch_url::MAX_BYTES_LIMIT.saturating_sub(bytes.len());
+                    if chunk.len() > room {
+                        bytes.extend_from_slice(&chunk[..room]);
+                        break;
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+                match read_error {
+                    None if status.is_success() && crate::pdf_text::is_pdf(&bytes) => {
+                        // Parsing and inflating is CPU-bound; keep it off the async workers.
+                        let extracted = tokio::task::spawn_blocking(move || {
+                            crate::pdf_text::extract_text(&bytes)
+                        })
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()));
+                        let pdf = match extracted {
+                            Ok(pdf) => pdf,
+                            Err(e) => {
+                                return ResponseInputItem::FunctionCallOutput {
+                                    call_id: call_id_clone,
+                                    output: FunctionCallOutputPayload {body: code_protocol::models::FunctionCallOutputBody::Text(format!("PDF text extraction failed: {e}")), success: Some(false)},
+                                };
+                            }
+                        };
+                        let mut markdown = pdf.to_markdown();
+                        let truncated = markdown.chars().count() > 120_000;
+                        if truncated {
+                            markdown = markdown.chars().take(120_000).collect();
+                            markdown.push_str("\n\n… (truncated)\n");
+                        }
+                        let body = serde_json::json!({
+                            "url": params.url,
+                            "status": status.as_u16(),
+                            "final_url": final_url,
+                            "content_type": "application/pdf",
+                            "used_browser_ua": false,
+                            "pdf_pages": pdf.pages.len(),
+                            "truncated": truncated,
+                            "markdown": markdown,
+                        });
+                        return ResponseInputItem::FunctionCallOutput {
+                            call_id: call_id_clone,
+                            output: FunctionCallOutputPayload {body: code_protocol::models::FunctionCallOutputBody::Text(body.to_string()), success: Some(true)},
+                        };
+                    }
+                    None => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+                    Some(e) => Err(e),
+                }
+            } else {
+                resp.text().await.map_err(|e| e.to_string())
+            };
             // Read body
-            let mut body_text = match resp.text().await {
+            let mut body_text = match body_result {
                 Ok(t) => t,
                 Err(e) => {
                     return ResponseInputItem::FunctionCallOutput {
@@ -6695,7 +7161,9 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 let extra = [
                     (reqwest::header::HeaderName::from_static("upgrade-insecure-requests"), "1"),
                 ];
-                if let Ok(r2) = do_request(&params.url, browser_ua, timeout, Some(&extra)).await {
+                if let Ok(r2) =
+                    do_request(&params.url, browser_ua, timeout, Some(&extra), redirects()).await
+                {
                     let status2 = r2.status();
                     let final_url2 = r2.url().to_string();
                     let headers2 = r2.headers().clone();
@@ -6739,7 +7207,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 if let Some(ray) = cf_ray { diag["cf_ray"] = serde_json::json!(ray); }
 
                 if let Some(browser_fetch) = fetch_html_via_browser(&params.url, timeout, false).await {
-                    let (markdown, truncated) = match convert_html_to_markdown_trimmed(browser_fetch.html, 120_000) {
+                    let (markdown, truncated) = match convert_html_to_markdown_trimmed(browser_fetch.html, 120_000, readability, browser_fetch.final_url.as_deref().unwrap_or(&params.url)) {
                         Ok(t) => t,
                         Err(e) => {
                             return ResponseInputItem::FunctionCallOutput {
@@ -6771,7 +7239,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                     };
                 }
 
-                let (md_preview, _trunc) = match convert_html_to_markdown_trimmed(body_text, 2000) {
+                let (md_preview, _trunc) = match convert_html_to_markdown_trimmed(body_text, 2000, readability, &final_url) {
                     Ok(t) => t,
                     Err(_) => ("".to_string(), false),
                 };
@@ -6812,7 +7280,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
                 if let Some(ray) = cf_ray { diag["cf_ray"] = serde_json::json!(ray); }
 
                 // Provide a tiny, safe preview of visible text only (converted and truncated).
-                let (md_preview, _trunc) = match convert_html_to_markdown_trimmed(body_text, 2000) {
+                let (md_preview, _trunc) = match convert_html_to_markdown_trimmed(body_text, 2000, readability, &final_url) {
                     Ok(t) => t,
                     Err(_) => ("".to_string(), false),
                 };
@@ -6849,7 +7317,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
             }
 
             // Success: convert to markdown (sanitized and size-limited)
-            let (markdown, truncated) = match convert_html_to_markdown_trimmed(body_text, 120_000) {
+            let (markdown, truncated) = match convert_html_to_markdown_trimmed(body_text, 120_000, readability, &final_url) {
                 Ok(t) => t,
                 Err(e) => {
                     return ResponseInputItem::FunctionCallOutput {
@@ -6862,7 +7330,7 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
             // If the rendered markdown still looks like a challenge page, attempt browser fallback (unless http-only).
             if !matches!(params.mode.as_deref(), Some("http")) && looks_like_challenge_markdown(&markdown) {
                 if let Some(browser_fetch) = fetch_html_via_browser(&params.url, timeout, false).await {
-                    let (md2, truncated2) = match convert_html_to_markdown_trimmed(browser_fetch.html, 120_000) {
+                    let (md2, truncated2) = match convert_html_to_markdown_trimmed(browser_fetch.html, 120_000, readability, browser_fetch.final_url.as_deref().unwrap_or(&params.url)) {
                         Ok(t) => t,
                         Err(e) => {
                             return ResponseInputItem::FunctionCallOutput {
@@ -6912,155 +7380,101 @@ async fn handle_web_fetch(sess: &Session, ctx: &ToolCallCtx, arguments: String)
 
             ResponseInputItem::FunctionCallOutput { call_id: call_id_clone, output: FunctionCallOutputPayload {body: code_protocol::models::FunctionCallOutputBody::Text(body.to_string()), success: Some(true)} }
         },
-    ).await
+    ).await;
+    remember_fetched_page(sess, &output);
+    output
 }
 
-async fn handle_image_view(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
-    use crate::protocol::ViewImageToolCallEvent;
-    use serde::Deserialize;
-    use serde_json::Value;
-    use std::path::PathBuf;
+/// Keep the Markdown of a successful fetch as the page's latest visit so a
+/// later `browser diff` of the same URL can report what changed.
+fn remember_fetched_page(sess: &Session, output: &ResponseInputItem) {
+    let ResponseInputItem::FunctionCallOutput { output, .. } = output else {
+        return;
+    };
+    if output.success != Some(true) {
+        return;
+    }
+    let code_protocol::models::FunctionCallOutputBody::Text(body) = &output.body else {
+        return;
+    };
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        return;
+    };
+    let url = value
+        .get("final_url")
+        .or_else(|| value.get("url"))
+        .and_then(Value::as_str);
+    if let (Some(url), Some(markdown)) = (url, value.get("markdown").and_then(Value::as_str)) {
+        sess.record_page_snapshot(url, markdown.to_string());
+    }
+}
 
-    #[derive(Deserialize)]
-    struct Params {
-        path: String,
+async fn handle_web_search(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    #[derive(serde::Deserialize)]
+    struct WebSearchParams {
+        query: String,
         #[serde(default)]
-        alt_text: Option<String>,
+        count: Option<usize>,
     }
 
-    let mut params_for_event = serde_json::from_str::<Value>(&arguments).ok();
-    let parsed: Params = match serde_json::from_str(&arguments) {
-        Ok(p) => p,
-        Err(e) => {
-            return ResponseInputItem::FunctionCallOutput {
-                call_id: ctx.call_id.clone(),
-                output: FunctionCallOutputPayload {
-                    body: code_protocol::models::FunctionCallOutputBody::Text(format!("Invalid image_view arguments: {e}")),
-                    success: Some(false)},
-            };
-        }
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
     };
+    let Some(backend) = sess.client.config().tools_web_search_backend.clone() else {
+        return failure("web_search is not configured; set [tools.web_search_backend]".to_string());
+    };
+    let params: WebSearchParams = match serde_json::from_str(&arguments) {
+        Ok(params) => params,
+        Err(e) => return failure(format!("Invalid web_search arguments: {e}")),
+    };
+    let query = params.query.trim().to_string();
+    if query.is_empty() {
+        return failure("web_search needs a non-empty query".to_string());
+    }
+    let count = params
+        .count
+        .or(backend.max_results)
+        .unwrap_or(crate::web_search::DEFAULT_RESULTS);
+    let navigation_policy = sess.client.config().navigation_policy.clone();
+    let user_agent = crate::default_client::get_code_user_agent(Some("web_search"));
+    let call_id = ctx.call_id.clone();
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
 
     execute_custom_tool(
         sess,
         ctx,
-        "image_view".to_string(),
-        params_for_event.take(),
-        move || async move {
-            let call_id = ctx.call_id.clone();
-            let path_str = parsed.path.trim();
-            if path_str.is_empty() {
-                return ResponseInputItem::FunctionCallOutput {
-                    call_id,
-                    output: FunctionCallOutputPayload {
-                    body: code_protocol::models::FunctionCallOutputBody::Text("image_view requires a non-empty path".to_string()),
-                        success: Some(false)},
-                };
-            }
-
-            let mut resolved = PathBuf::from(path_str);
-            if resolved.is_relative() {
-                resolved = sess.get_cwd().join(&resolved);
-            }
-            if let Ok(canon) = resolved.canonicalize() {
-                resolved = canon;
-            }
-            let metadata = match std::fs::metadata(&resolved) {
-                Ok(meta) => meta,
-                Err(err) => {
-                    return ResponseInputItem::FunctionCallOutput {
-                        call_id,
-                        output: FunctionCallOutputPayload {
-                            body: code_protocol::models::FunctionCallOutputBody::Text(format!(
-                                "image_view could not read {}: {err}",
-                                resolved.display()
-                            )),
-                            success: Some(false)},
-                    };
-                }
-            };
-            if !metadata.is_file() {
-                return ResponseInputItem::FunctionCallOutput {
-                    call_id,
-                    output: FunctionCallOutputPayload {
-                        body: code_protocol::models::FunctionCallOutputBody::Text(format!(
-                            "image_view requires a file path, got {}",
-                            resolved.display()
-                        )),
-                        success: Some(false)},
-                };
-            }
-
-            let bytes = match std::fs::read(&resolved) {
-                Ok(bytes) => bytes,
-                Err(err) => {
-                    return ResponseInputItem::FunctionCallOutput {
-                        call_id,
-                        output: FunctionCallOutputPayload {
-                            body: code_protocol::models::FunctionCallOutputBody::Text(format!(
-                                "image_view could not read {}: {err}",
-                                resolved.display()
-                            )),
-                            success: Some(false)},
-                    };
-                }
+        "web_search".to_string(),
+        params_for_event,
+        || async move {
+            let outcome = crate::web_search::search(
+                &backend,
+                &navigation_policy,
+                &query,
+                count,
+                &user_agent,
+            )
+            .await;
+            let (body, success) = match outcome {
+                Ok(results) => (
+                    serde_json::json!({
+                        "query": query,
+                        "provider": backend.provider.to_string(),
+                        "results": results,
+                    })
+                    .to_string(),
+                    true,
+                ),
+                Err(err) => (format!("Search for {query:?} failed: {err}"), false),
             };
-            let mime = mime_guess::from_path(&resolved)
-                .first()
-                .map(|m| m.essence_str().to_owned())
-                .unwrap_or_else(|| "application/octet-stream".to_string());
-            if !mime.starts_with("image/") {
-                return ResponseInputItem::FunctionCallOutput {
-                    call_id,
-                    output: FunctionCallOutputPayload {
-                        body: code_protocol::models::FunctionCallOutputBody::Text(format!(
-                            "image_view only supports image files (got {mime})"
-                        )),
-                        success: Some(false)},
-                };
-            }
-            let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
-            let filename = resolved
-                .file_name()
-                .and_then(|name| name.to_str())
-                .unwrap_or("image");
-            let label = parsed
-                .alt_text
-                .as_ref()
-                .map(|text| text.trim())
-                .filter(|text| !text.is_empty())
-                .unwrap_or(filename);
-            let marker = format!("[image: {label}]");
-            let image_url = format!("data:{mime};base64,{encoded}");
-            let image_detail = sess
-                .client
-                .get_model_family()
-                .supports_image_detail_original
-                .then_some(ImageDetail::Original);
-
-            let order = ctx.order_meta(sess.current_request_ordinal());
-            let event = sess.make_event_with_order(
-                &ctx.sub_id,
-                EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
-                    call_id: ctx.call_id.clone(),
-                    path: resolved.clone(),
-                }),
-                order,
-                ctx.seq_hint,
-            );
-            let _ = sess.send_event(event).await;
-
             ResponseInputItem::FunctionCallOutput {
                 call_id,
                 output: FunctionCallOutputPayload {
-                    body: code_protocol::models::FunctionCallOutputBody::ContentItems(vec![
-                        FunctionCallOutputContentItem::InputText { text: marker },
-                        FunctionCallOutputContentItem::InputImage {
-                            image_url,
-                            detail: image_detail,
-                        },
-                    ]),
-                    success: Some(true),
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
                 },
             }
         },
@@ -7068,24 +7482,659 @@ async fn handle_image_view(sess: &Session, ctx: &ToolCallCtx, arguments: String)
     .await
 }
 
-// Wait for a background shell execution to complete.
-// Parameters: { call_id?: string, timeout_ms?: number }
-async fn handle_wait(
-    sess: &Session,
-    ctx: &ToolCallCtx,
-    arguments: String,
-) -> ResponseInputItem {
-    use serde::Deserialize;
-    #[derive(Deserialize, Clone)]
-    struct Params { #[serde(default)] call_id: Option<String>, #[serde(default)] timeout_ms: Option<u64> }
-    let mut params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
-    if let Some(serde_json::Value::Object(map)) = params_for_event.as_mut() {
-        if let Some(serde_json::Value::String(cid)) = map.get("call_id") {
-            let st = sess.state.lock().unwrap();
-            if let Some(bg) = st.background_execs.get(cid) {
-                map.insert("for".to_string(), serde_json::Value::String(bg.cmd_display.clone()));
-            }
-        }
+async fn handle_fetch_url(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use crate::fetch_url;
+
+    #[derive(serde::Deserialize)]
+    struct FetchUrlParams {
+        url: String,
+        #[serde(default)]
+        format: Option<String>,
+        #[serde(default)]
+        max_bytes: Option<usize>,
+        #[serde(default)]
+        max_redirects: Option<usize>,
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+    }
+
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    };
+    let params: FetchUrlParams = match serde_json::from_str(&arguments) {
+        Ok(params) => params,
+        Err(e) => return failure(format!("Invalid fetch_url arguments: {e}")),
+    };
+    let url = match url::Url::parse(params.url.trim()) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+        Ok(url) => return failure(format!("Cannot fetch {url}: only http(s) URLs are supported")),
+        Err(e) => return failure(format!("Invalid URL {}: {e}", params.url)),
+    };
+    if let Err(message) = check_navigation_policy(sess, ctx, url.as_str()).await {
+        return failure(format!("Cannot fetch {url}: {message}"));
+    }
+
+    let request = fetch_url::FetchRequest {
+        url: url.clone(),
+        max_bytes: params
+            .max_bytes
+            .unwrap_or(fetch_url::DEFAULT_MAX_BYTES)
+            .clamp(1, fetch_url::MAX_BYTES_LIMIT),
+        max_redirects: params
+            .max_redirects
+            .unwrap_or(fetch_url::DEFAULT_MAX_REDIRECTS)
+            .min(fetch_url::MAX_REDIRECTS_LIMIT),
+        timeout: Duration::from_millis(
+            params
+                .timeout_ms
+                .unwrap_or(fetch_url::DEFAULT_TIMEOUT_MS)
+                .clamp(fetch_url::MIN_TIMEOUT_MS, fetch_url::MAX_TIMEOUT_MS),
+        ),
+        user_agent: crate::default_client::get_code_user_agent(Some("fetch_url")),
+    };
+    let raw = matches!(params.format.as_deref(), Some("raw"));
+    let readability = sess.client.config().tools_web_fetch_readability;
+    let navigation_policy = sess.client.config().navigation_policy.clone();
+    let call_id = ctx.call_id.clone();
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "fetch_url".to_string(),
+        params_for_event,
+        || async move {
+            let (body, success) = match fetch_url::fetch(&request, &navigation_policy).await {
+                Ok(fetched) => {
+                    // PDF extraction and HTML conversion are CPU-bound.
+                    let target = url.clone();
+                    tokio::task::spawn_blocking(move || {
+                        render_fetched_url(&target, fetched, raw, readability)
+                    })
+                    .await
+                    .unwrap_or_else(|err| (format!("Failed to render {url}: {err}"), false))
+                }
+                Err(err) => (format!("Failed to fetch {url}: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+/// `fetch_url` result in the JSON shape `web_fetch` uses, so the two share a
+/// history cell. Non-2xx responses keep their body (API errors are useful) but
+/// are reported as failures.
+fn render_fetched_url(
+    url: &url::Url,
+    fetched: crate::fetch_url::FetchedBody,
+    raw: bool,
+    readability: bool,
+) -> (String, bool) {
+    use crate::fetch_url::BodyKind;
+    const MAX_CHARS: usize = 120_000;
+
+    fn fenced(text: &str, lang: &str) -> String {
+        let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest.max(2) + 1);
+        format!("{fence}{lang}\n{}\n{fence}", text.trim_end())
+    }
+
+    let content_type = fetched.content_type.as_str();
+    let kind = crate::fetch_url::body_kind(content_type, &fetched.body);
+    let mut pdf_pages = None;
+    let mut truncated = fetched.truncated;
+    let text = match kind {
+        BodyKind::Pdf if !raw => match crate::pdf_text::extract_text(&fetched.body) {
+            Ok(pdf) => {
+                pdf_pages = Some(pdf.pages.len());
+                pdf.to_markdown()
+            }
+            Err(err) => return (format!("PDF text extraction failed for {url}: {err}"), false),
+        },
+        BodyKind::Binary | BodyKind::Pdf => {
+            let content_type = if content_type.is_empty() { "unknown type" } else { content_type };
+            return (
+                format!(
+                    "{url} returned binary content ({content_type}, {} bytes); use the browser download action to save it",
+                    fetched.body.len()
+                ),
+                false,
+            );
+        }
+        BodyKind::Html if !raw => {
+            let html = crate::fetch_url::decode_text(content_type, &fetched.body);
+            // The converter applies the length cap itself, before its link list.
+            match convert_html_to_markdown_trimmed(html, MAX_CHARS, readability, &fetched.final_url) {
+                Ok((markdown, capped)) => {
+                    truncated |= capped;
+                    markdown
+                }
+                Err(err) => return (format!("Markdown conversion failed: {err}"), false),
+            }
+        }
+        BodyKind::Json if !raw => {
+            let text = crate::fetch_url::decode_text(content_type, &fetched.body);
+            let pretty = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|value| serde_json::to_string_pretty(&value).ok())
+                .unwrap_or(text);
+            fenced(&pretty, "json")
+        }
+        BodyKind::Markdown if !raw => crate::fetch_url::decode_text(content_type, &fetched.body),
+        BodyKind::Html | BodyKind::Json | BodyKind::Markdown | BodyKind::Text => {
+            fenced(&crate::fetch_url::decode_text(content_type, &fetched.body), "")
+        }
+    };
+    let markdown = if (raw || kind != BodyKind::Html) && text.chars().count() > MAX_CHARS {
+        truncated = true;
+        let mut cut: String = text.chars().take(MAX_CHARS).collect();
+        cut.push_str("\n\n… (truncated)\n");
+        cut
+    } else {
+        text
+    };
+
+    let mut body = serde_json::json!({
+        "url": url.as_str(),
+        "status": fetched.status,
+        "final_url": fetched.final_url,
+        "content_type": fetched.content_type,
+        "cached": fetched.cached,
+        "truncated": truncated,
+        "markdown": markdown,
+    });
+    if let Some(pages) = pdf_pages {
+        body["pdf_pages"] = serde_json::json!(pages);
+    }
+    (body.to_string(), (200..300).contains(&fetched.status))
+}
+
+async fn handle_read_file(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use crate::read_file;
+
+    #[derive(serde::Deserialize)]
+    struct ReadFileParams {
+        path: String,
+        #[serde(default)]
+        start_line: Option<usize>,
+        #[serde(default)]
+        end_line: Option<usize>,
+        #[serde(default)]
+        offset: Option<u64>,
+        #[serde(default)]
+        length: Option<usize>,
+    }
+
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    };
+    let params: ReadFileParams = match serde_json::from_str(&arguments) {
+        Ok(params) => params,
+        Err(e) => return failure(format!("Invalid read_file arguments: {e}")),
+    };
+    let path_str = params.path.trim();
+    if path_str.is_empty() {
+        return failure("read_file requires a non-empty path".to_string());
+    }
+    let max_bytes = params.length.unwrap_or(read_file::DEFAULT_MAX_BYTES);
+    let range = match (params.offset, params.start_line, params.end_line) {
+        (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+            return failure(
+                "read_file takes either offset/length or start_line/end_line, not both".to_string(),
+            );
+        }
+        (Some(offset), None, None) => read_file::ReadRange::Bytes { offset, length: max_bytes },
+        (None, start, end) => {
+            let start = start.unwrap_or(1);
+            if end.is_some_and(|end| end < start) {
+                return failure(format!("end_line must not be before start_line ({start})"));
+            }
+            read_file::ReadRange::Lines { start, end }
+        }
+    };
+    let mut path = std::path::PathBuf::from(path_str);
+    if path.is_relative() {
+        path = sess.get_cwd().join(&path);
+    }
+    let call_id = ctx.call_id.clone();
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "read_file".to_string(),
+        params_for_event,
+        || async move {
+            let display = path.display().to_string();
+            let result =
+                tokio::task::spawn_blocking(move || read_file::read(&path, range, max_bytes)).await;
+            let (body, success) = match result {
+                Ok(Ok(mut slice)) => {
+                    let redacted = crate::redaction::redact(&slice.content);
+                    if let std::borrow::Cow::Owned(redacted) = redacted {
+                        slice.content = redacted;
+                    }
+                    match serde_json::to_value(&slice) {
+                        Ok(mut body) => {
+                            body["path"] = serde_json::json!(display);
+                            (body.to_string(), true)
+                        }
+                        Err(err) => (format!("Failed to encode {display}: {err}"), false),
+                    }
+                }
+                Ok(Err(err)) => (format!("Failed to read {display}: {err}"), false),
+                Err(err) => (format!("Failed to read {display}: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_code_index_tool(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    name: &str,
+    arguments: String,
+) -> ResponseInputItem {
+    use crate::code_index;
+
+    #[derive(serde::Deserialize)]
+    struct CodeIndexParams {
+        #[serde(default)]
+        query: Option<String>,
+        #[serde(default)]
+        symbol: Option<String>,
+        #[serde(default)]
+        kind: Option<code_index::SymbolKind>,
+        #[serde(default)]
+        references: bool,
+        #[serde(default)]
+        limit: Option<usize>,
+        #[serde(default)]
+        path: Option<String>,
+    }
+
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    };
+    let params: CodeIndexParams = match serde_json::from_str(&arguments) {
+        Ok(params) => params,
+        Err(e) => return failure(format!("Invalid {name} arguments: {e}")),
+    };
+    let root = sess.get_cwd().to_path_buf();
+    let lookup: Box<dyn FnOnce(&code_index::CodeIndex) -> code_index::SearchResult + Send> =
+        if name == "goto_definition" {
+            let symbol = params.symbol.unwrap_or_default().trim().to_string();
+            if symbol.is_empty() {
+                return failure("goto_definition requires a non-empty symbol".to_string());
+            }
+            // Definitions are keyed by root-relative paths.
+            let near = params.path.map(|path| {
+                let path = std::path::PathBuf::from(path.trim());
+                path.strip_prefix(&root).map(std::path::Path::to_path_buf).unwrap_or(path)
+            });
+            Box::new(move |index| index.goto_definition(&symbol, near.as_deref()))
+        } else {
+            let query = params.query.unwrap_or_default().trim().to_string();
+            if query.is_empty() {
+                return failure("code_search requires a non-empty query".to_string());
+            }
+            let limit = params
+                .limit
+                .unwrap_or(code_index::DEFAULT_LIMIT)
+                .clamp(1, code_index::MAX_LIMIT);
+            let (kind, references) = (params.kind, params.references);
+            Box::new(move |index| index.search(&query, kind, references, limit))
+        };
+    let call_id = ctx.call_id.clone();
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        name.to_string(),
+        params_for_event,
+        || async move {
+            let result =
+                tokio::task::spawn_blocking(move || code_index::with_index(&root, lookup)).await;
+            let (body, success) = match result {
+                Ok(result) => match serde_json::to_string(&result) {
+                    Ok(body) => (body, true),
+                    Err(err) => (format!("Failed to encode index results: {err}"), false),
+                },
+                Err(err) => (format!("Failed to search the code index: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_git_history_tool(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    name: &str,
+    arguments: String,
+) -> ResponseInputItem {
+    use crate::git_history;
+
+    #[derive(serde::Deserialize)]
+    struct GitHistoryParams {
+        #[serde(default)]
+        path: Option<String>,
+        #[serde(default)]
+        revision: Option<String>,
+        #[serde(default)]
+        author: Option<String>,
+        #[serde(default)]
+        grep: Option<String>,
+        #[serde(default)]
+        limit: Option<usize>,
+        #[serde(default)]
+        start_line: Option<usize>,
+        #[serde(default)]
+        end_line: Option<usize>,
+        #[serde(default)]
+        patch: Option<bool>,
+    }
+
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    };
+    let params: GitHistoryParams = match serde_json::from_str(&arguments) {
+        Ok(params) => params,
+        Err(e) => return failure(format!("Invalid {name} arguments: {e}")),
+    };
+    let non_empty = |value: Option<String>| {
+        value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+    };
+    let path = non_empty(params.path);
+    let revision = non_empty(params.revision);
+    match name {
+        "git_blame" if path.is_none() => {
+            return failure("git_blame requires a non-empty path".to_string());
+        }
+        "git_show" if revision.is_none() => {
+            return failure("git_show requires a non-empty revision".to_string());
+        }
+        _ => {}
+    }
+    let cwd = sess.get_cwd().to_path_buf();
+    let call_id = ctx.call_id.clone();
+    let params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let tool = name.to_string();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        name.to_string(),
+        params_for_event,
+        || async move {
+            let redact = |text: &mut String| {
+                let redacted = crate::redaction::redact(text);
+                if let std::borrow::Cow::Owned(redacted) = redacted {
+                    *text = redacted;
+                }
+            };
+            let result = match tool.as_str() {
+                "git_blame" => {
+                    let path = path.unwrap_or_default();
+                    let start = params.start_line.unwrap_or(1);
+                    git_history::blame(&cwd, &path, start, params.end_line, revision.as_deref())
+                        .await
+                        .map(|mut hunks| {
+                            hunks.iter_mut().for_each(|hunk| redact(&mut hunk.content));
+                            serde_json::json!({ "path": path, "hunks": hunks }).to_string()
+                        })
+                }
+                "git_show" => {
+                    let revision = revision.unwrap_or_default();
+                    let patch = params.patch.unwrap_or(true);
+                    git_history::show(&cwd, &revision, path.as_deref(), patch).await.and_then(
+                        |mut shown| {
+                            if let Some(patch) = shown.patch.as_mut() {
+                                redact(patch);
+                            }
+                            Ok(serde_json::to_string(&shown)?)
+                        },
+                    )
+                }
+                _ => {
+                    let query = git_history::LogQuery {
+                        revision,
+                        path,
+                        author: non_empty(params.author),
+                        grep: non_empty(params.grep),
+                        limit: params.limit.unwrap_or(git_history::DEFAULT_LOG_LIMIT),
+                    };
+                    git_history::log(&cwd, &query)
+                        .await
+                        .and_then(|log| Ok(serde_json::to_string(&log)?))
+                }
+            };
+            let (body, success) = match result {
+                Ok(body) => (body, true),
+                Err(err) => (format!("{tool} failed: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_image_view(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use crate::protocol::ViewImageToolCallEvent;
+    use serde::Deserialize;
+    use serde_json::Value;
+    use std::path::PathBuf;
+
+    #[derive(Deserialize)]
+    struct Params {
+        path: String,
+        #[serde(default)]
+        alt_text: Option<String>,
+    }
+
+    let mut params_for_event = serde_json::from_str::<Value>(&arguments).ok();
+    let parsed: Params = match serde_json::from_str(&arguments) {
+        Ok(p) => p,
+        Err(e) => {
+            return ResponseInputItem::FunctionCallOutput {
+                call_id: ctx.call_id.clone(),
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(format!("Invalid image_view arguments: {e}")),
+                    success: Some(false)},
+            };
+        }
+    };
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "image_view".to_string(),
+        params_for_event.take(),
+        move || async move {
+            let call_id = ctx.call_id.clone();
+            let path_str = parsed.path.trim();
+            if path_str.is_empty() {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id,
+                    output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text("image_view requires a non-empty path".to_string()),
+                        success: Some(false)},
+                };
+            }
+
+            let mut resolved = PathBuf::from(path_str);
+            if resolved.is_relative() {
+                resolved = sess.get_cwd().join(&resolved);
+            }
+            if let Ok(canon) = resolved.canonicalize() {
+                resolved = canon;
+            }
+            let metadata = match std::fs::metadata(&resolved) {
+                Ok(meta) => meta,
+                Err(err) => {
+                    return ResponseInputItem::FunctionCallOutput {
+                        call_id,
+                        output: FunctionCallOutputPayload {
+                            body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                                "image_view could not read {}: {err}",
+                                resolved.display()
+                            )),
+                            success: Some(false)},
+                    };
+                }
+            };
+            if !metadata.is_file() {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                            "image_view requires a file path, got {}",
+                            resolved.display()
+                        )),
+                        success: Some(false)},
+                };
+            }
+
+            let bytes = match std::fs::read(&resolved) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    return ResponseInputItem::FunctionCallOutput {
+                        call_id,
+                        output: FunctionCallOutputPayload {
+                            body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                                "image_view could not read {}: {err}",
+                                resolved.display()
+                            )),
+                            success: Some(false)},
+                    };
+                }
+            };
+            let mime = mime_guess::from_path(&resolved)
+                .first()
+                .map(|m| m.essence_str().to_owned())
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            if !mime.starts_with("image/") {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                            "image_view only supports image files (got {mime})"
+                        )),
+                        success: Some(false)},
+                };
+            }
+            let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+            let filename = resolved
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("image");
+            let label = parsed
+                .alt_text
+                .as_ref()
+                .map(|text| text.trim())
+                .filter(|text| !text.is_empty())
+                .unwrap_or(filename);
+            let marker = format!("[image: {label}]");
+            let image_url = format!("data:{mime};base64,{encoded}");
+            let image_detail = sess
+                .client
+                .get_model_family()
+                .supports_image_detail_original
+                .then_some(ImageDetail::Original);
+
+            let order = ctx.order_meta(sess.current_request_ordinal());
+            let event = sess.make_event_with_order(
+                &ctx.sub_id,
+                EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
+                    call_id: ctx.call_id.clone(),
+                    path: resolved.clone(),
+                }),
+                order,
+                ctx.seq_hint,
+            );
+            let _ = sess.send_event(event).await;
+
+            ResponseInputItem::FunctionCallOutput {
+                call_id,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::ContentItems(vec![
+                        FunctionCallOutputContentItem::InputText { text: marker },
+                        FunctionCallOutputContentItem::InputImage {
+                            image_url,
+                            detail: image_detail,
+                        },
+                    ]),
+                    success: Some(true),
+                },
+            }
+        },
+    )
+    .await
+}
+
+// Wait for a background shell execution to complete.
+// Parameters: { call_id?: string, timeout_ms?: number }
+async fn handle_wait(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    arguments: String,
+) -> ResponseInputItem {
+    use serde::Deserialize;
+    #[derive(Deserialize, Clone)]
+    struct Params { #[serde(default)] call_id: Option<String>, #[serde(default)] timeout_ms: Option<u64> }
+    let mut params_for_event = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    if let Some(serde_json::Value::Object(map)) = params_for_event.as_mut() {
+        if let Some(serde_json::Value::String(cid)) = map.get("call_id") {
+            let st = sess.state.lock().unwrap();
+            if let Some(bg) = st.background_execs.get(cid) {
+                map.insert("for".to_string(), serde_json::Value::String(bg.cmd_display.clone()));
+            }
+        }
     }
     let arguments_clone = arguments.clone();
     let ctx_clone = ToolCallCtx::new(ctx.sub_id.clone(), ctx.call_id.clone(), ctx.seq_hint, ctx.output_index);
@@ -7119,7 +8168,7 @@ async fn handle_wait(
                 let timeout_ms = parsed.timeout_ms.unwrap_or(default_ms).min(max_ms);
                 use std::sync::atomic::Ordering;
                 let (initial_wait_epoch, _) = sess.wait_interrupt_snapshot();
-                let (notify_opt, done_opt, tail, suppress_flag) = {
+                let (notify_opt, done_opt, tail, suppress_flag, command) = {
                     let st = sess.state.lock().unwrap();
                     match st.background_execs.get(&call_id) {
                         Some(bg) => (
@@ -7127,8 +8176,9 @@ async fn handle_wait(
                             bg.result_cell.lock().unwrap().clone(),
                             bg.tail_buf.clone(),
                             Some(bg.suppress_event.clone()),
+                            bg.cmd_display.clone(),
                         ),
-                        None => (None, None, None, None),
+                        None => (None, None, None, None, String::new()),
                     }
                 };
 
@@ -7168,6 +8218,7 @@ async fn handle_wait(
                         sess.get_cwd(),
                         &ctx_inner.sub_id,
                         &ctx_inner.call_id,
+                        &command,
                         &done,
                         sess.tool_output_max_bytes,
                     );
@@ -7277,7 +8328,11 @@ async fn handle_wait(
                     if now >= deadline {
                         let tail_text = tail
                             .as_ref()
-                            .map(|arc| String::from_utf8_lossy(&arc.lock().unwrap()).to_string())
+                            .map(|arc| {
+                                let bytes = arc.lock().unwrap().clone();
+                                let tail = String::from_utf8_lossy(&bytes);
+                                crate::redaction::redact(&tail).into_owned()
+                            })
                             .unwrap_or_default();
                         let msg = if tail_text.is_empty() {
                             format!("Background job {call_id} still running...")
@@ -7307,8 +8362,12 @@ async fn handle_wait(
 
                 let done = {
                     let mut st = sess.state.lock().unwrap();
+                    let finished = |bg: BackgroundExecState| {
+                        let output = bg.result_cell.lock().unwrap().clone();
+                        output.map(|output| (bg.cmd_display, output))
+                    };
                     if let Some(bg) = st.background_execs.remove(&call_id) {
-                        bg.result_cell.lock().unwrap().clone()
+                        finished(bg)
                     } else {
                         let found = st
                             .background_execs
@@ -7316,14 +8375,15 @@ async fn handle_wait(
                             .find_map(|(k, v)| if v.result_cell.lock().unwrap().is_some() { Some(k.clone()) } else { None });
                         found
                             .and_then(|k| st.background_execs.remove(&k))
-                            .and_then(|bg| bg.result_cell.lock().unwrap().clone())
+                            .and_then(finished)
                     }
                 };
-                if let Some(done) = done {
+                if let Some((command, done)) = done {
                     let content = format_exec_output_with_limit(
                         sess.get_cwd(),
                         &ctx_inner.sub_id,
                         &ctx_inner.call_id,
+                        &command,
                         &done,
                         sess.tool_output_max_bytes,
                     );
@@ -8154,6 +9214,23 @@ async fn handle_kill(
 
             use std::sync::atomic::Ordering;
 
+            let pane_job = sess.state.lock().unwrap().pane_jobs.remove(&parsed.call_id);
+            if let Some(job) = pane_job {
+                let (text, success) = match crate::terminal_panes::stop(&job).await {
+                    Ok(()) => {
+                        let pane = job.multiplexer.name();
+                        (format!("Stopped `{}` in its {pane} pane.", job.label), true)
+                    }
+                    Err(err) => (format!("Failed to stop `{}`: {err}", job.label), false),
+                };
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id: ctx_inner.call_id.clone(),
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(text),
+                        success: Some(success)},
+                };
+            }
+
             let (
                 notify,
                 result_cell,
@@ -8232,6 +9309,7 @@ async fn handle_kill(
                 stderr: output.stderr.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
+                redactions: output.aggregated_output.redactions,
             });
             let event = Event {
                 id: sub_id_for_end.clone(),
@@ -10743,6 +11821,16 @@ async fn handle_container_exec_with_params(
 
             let changes = convert_apply_patch_to_protocol(&action);
             turn_diff_tracker.on_patch_begin(&changes);
+            let written_paths: Vec<PathBuf> = changes
+                .iter()
+                .filter_map(|(path, change)| match change {
+                    FileChange::Add { .. } => Some(path.clone()),
+                    FileChange::Update { move_path, .. } => {
+                        Some(move_path.clone().unwrap_or_else(|| path.clone()))
+                    }
+                    FileChange::Delete => None,
+                })
+                .collect();
 
             let mut hook_ctx = ExecCommandContext {
                 sub_id: sub_id.clone(),
@@ -10767,6 +11855,11 @@ async fn handle_container_exec_with_params(
                 )
                 .await;
 
+            if let Some(lsp) = sess.lsp.as_ref() {
+                let touched: Vec<PathBuf> = changes.keys().cloned().collect();
+                lsp.before_patch(&touched).await;
+            }
+
             let patch_start = std::time::Instant::now();
 
             match apply_patch::apply_patch(
@@ -10848,9 +11941,31 @@ async fn handle_container_exec_with_params(
                     if let Ok(Some(unified_diff)) = turn_diff_tracker.get_unified_diff() {
                         let diff_event = sess.make_event(
                             &sub_id,
-                            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }),
+                            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }),
+                        );
+                        let _ = sess.tx_event.send(diff_event).await;
+                    }
+
+                    let lsp_report = match sess.lsp.as_ref() {
+                        Some(lsp) if run.success => {
+                            lsp.diagnostics_after_patch(&written_paths).await
+                        }
+                        _ => None,
+                    };
+                    if let Some(report) = lsp_report.as_ref() {
+                        let seq = seq_hint.map(|h| h.saturating_add(2));
+                        let order = crate::protocol::OrderMeta {
+                            request_ordinal: attempt_req,
+                            output_index,
+                            sequence_number: seq,
+                        };
+                        let event = sess.make_event_with_order(
+                            &sub_id,
+                            EventMsg::LspDiagnostics(report.to_event(call_id.clone())),
+                            order,
+                            seq,
                         );
-                        let _ = sess.tx_event.send(diff_event).await;
+                        let _ = sess.tx_event.send(event).await;
                     }
 
                     let mut content = run.stdout;
@@ -10868,6 +11983,15 @@ async fn handle_container_exec_with_params(
                             content.push_str(&summary);
                         }
                     }
+                    if let Some(report) = lsp_report
+                        && let Ok(report) =
+                            serde_json::to_string(&serde_json::json!({ "diagnostics": report }))
+                    {
+                        if !content.is_empty() {
+                            content.push('\n');
+                        }
+                        content.push_str(&report);
+                    }
 
                     return ResponseInputItem::FunctionCallOutput {
                         call_id,
@@ -10988,6 +12112,58 @@ async fn handle_container_exec_with_params(
         }
     };
 
+    // Configured long-running commands go to a tmux/zellij pane. Panes run
+    // outside the sandbox, so only commands that would run unsandboxed anyway
+    // (full access, or approved by the user) are moved.
+    if sandbox_type == SandboxType::None
+        && !sess.terminal_pane_commands.is_empty()
+        && let Some(multiplexer) = crate::terminal_panes::Multiplexer::detect()
+    {
+        let label = crate::util::strip_bash_lc_and_escape(&params.command);
+        if crate::terminal_panes::wants_pane(&sess.terminal_pane_commands, &label) {
+            let launched = crate::terminal_panes::launch(
+                multiplexer,
+                &sess.client.config().code_home,
+                &call_id,
+                &label,
+                &params.command,
+                &params.cwd,
+            )
+            .await;
+            let order = sess.next_background_order(&sub_id, attempt_req, output_index);
+            match launched {
+                Ok(job) => {
+                    let message = crate::terminal_panes::started_message(&job, &call_id);
+                    sess.notify_background_event_with_order(
+                        &sub_id,
+                        order,
+                        format!("Started `{label}` in a {} pane", multiplexer.name()),
+                    )
+                    .await;
+                    sess.state.lock().unwrap().pane_jobs.insert(call_id.clone(), job);
+                    return ResponseInputItem::FunctionCallOutput {
+                        call_id,
+                        output: FunctionCallOutputPayload {
+                            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+                            success: Some(true),
+                        },
+                    };
+                }
+                Err(err) => {
+                    sess.notify_background_event_with_order(
+                        &sub_id,
+                        order,
+                        format!(
+                            "Could not open a {} pane for `{label}` ({err}); running it here.",
+                            multiplexer.name()
+                        ),
+                    )
+                    .await;
+                }
+            }
+        }
+    }
+
     let exec_command_context = ExecCommandContext {
         sub_id: sub_id.clone(),
         call_id: call_id.clone(),
@@ -11141,6 +12317,7 @@ async fn handle_container_exec_with_params(
             stderr: out.stderr.text.clone(),
             exit_code,
             duration: out.duration,
+            redactions: out.aggregated_output.redactions,
         });
         let ev = Event { id: sub_id_for_events.clone(), event_seq: 0, msg: end_msg, order: Some(order_meta_for_end) };
         let _ = tx_event.send(ev).await;
@@ -11241,6 +12418,7 @@ async fn handle_container_exec_with_params(
                 sess.get_cwd(),
                 &sub_id,
                 &call_id,
+                &display_label,
                 &done,
                 sess.tool_output_max_bytes,
             );
@@ -11279,6 +12457,7 @@ async fn handle_container_exec_with_params(
     // Still running: mark as backgrounded and return background notice + tail and instructions
     backgrounded.store(true, std::sync::atomic::Ordering::Relaxed);
     let tail = String::from_utf8_lossy(&tail_buf.lock().unwrap()).to_string();
+    let tail = crate::redaction::redact(&tail).into_owned();
     let header = format!(
         "Command running in background (call_id={}).\nTo wait: wait(call_id=\"{}\")\nYou can continue other work or wait. You'll be notified when the command completes.",
         call_id,
@@ -11321,6 +12500,7 @@ async fn handle_sandbox_error(
             sess.get_cwd(),
             &sub_id,
             &call_id,
+            &params.command.join(" "),
             output.as_ref(),
             sess.tool_output_max_bytes,
         );
@@ -11485,6 +12665,7 @@ async fn handle_sandbox_error(
                 sess.get_cwd(),
                 &sub_id,
                 &call_id,
+                &exec_command_context.command_for_display.join(" "),
                 &retry_output,
                 sess.tool_output_max_bytes,
             );
@@ -11609,12 +12790,44 @@ fn truncate_exec_output_for_storage(
     truncated
 }
 
+/// Test runs reach the model as their parsed report followed by the output
+/// the report does not cover, instead of the raw log. The full log is still
+/// saved to a file for when that is not enough.
+fn compact_test_output(
+    cwd: &Path,
+    sub_id: &str,
+    call_id: &str,
+    exec_output: &ExecToolCallOutput,
+    report: &crate::test_results::TestReport,
+    full: &str,
+    max_tool_output_bytes: usize,
+) -> String {
+    let mut compact = report.compact();
+    let other = crate::test_results::unreported_output(&exec_output.aggregated_output.text);
+    if !other.is_empty() {
+        compact.push_str(&format!("\n\nOther output:\n{other}"));
+    }
+    if exec_output.timed_out {
+        let timeout_ms = exec_output.duration.as_millis();
+        compact = format!("command timed out after {timeout_ms} milliseconds\n{compact}");
+    }
+    let (mut compact, _, _, _) = truncate_middle_bytes(&compact, max_tool_output_bytes);
+    let safe_call_id = crate::fs_sanitize::safe_path_component(call_id, "exec");
+    let filename = format!("exec-{safe_call_id}.txt");
+    match ensure_agent_dir(cwd, sub_id).and_then(|dir| write_agent_file(&dir, &filename, full)) {
+        Ok(path) => compact.push_str(&format!("\n\n[Full output saved to: {}]", path.display())),
+        Err(e) => compact.push_str(&format!("\n\n[Failed to save the full test output: {e}]")),
+    }
+    compact
+}
+
 /// Exec output serialized for the model. If the payload is too large,
 /// write the full output to a file and include a truncated preview here.
 fn format_exec_output_with_limit(
     cwd: &Path,
     sub_id: &str,
     call_id: &str,
+    command: &str,
     exec_output: &ExecToolCallOutput,
     max_tool_output_bytes: usize,
 ) -> String {
@@ -11637,8 +12850,22 @@ fn format_exec_output_with_limit(
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
     let full = format_exec_output_str(exec_output);
-    let final_output =
-        truncate_exec_output_for_storage(cwd, sub_id, call_id, &full, max_tool_output_bytes);
+    let test_report =
+        crate::test_results::parse_command_output(command, &exec_output.aggregated_output.text);
+    let final_output = match test_report {
+        Some(report) => compact_test_output(
+            cwd,
+            sub_id,
+            call_id,
+            exec_output,
+            &report,
+            &full,
+            max_tool_output_bytes,
+        ),
+        None => {
+            truncate_exec_output_for_storage(cwd, sub_id, call_id, &full, max_tool_output_bytes)
+        }
+    };
 
     let payload = ExecOutput {
         output: &final_output,
@@ -12289,7 +13516,35 @@ async fn handle_browser_tool(sess: &Session, ctx: &ToolCallCtx, arguments: Strin
 
     let action_lower = action.to_lowercase();
 
-    match action_lower.as_str() {
+    // Actions that can leave the current site without naming a URL are checked
+    // against the navigation policy once they finish.
+    let may_navigate = matches!(
+        action_lower.as_str(),
+        "click" | "type" | "fill" | "select" | "key" | "submit" | "login" | "javascript" | "eval"
+            | "history" | "cdp"
+    ) && !sess.client.config().navigation_policy.is_unrestricted();
+    let host_before = if may_navigate {
+        match get_browser_manager_for_session(sess).await {
+            Some(browser_manager) => browser_manager
+                .get_current_url()
+                .await
+                .and_then(|url| crate::navigation_policy::host_of(&url)),
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    // Actions that load documents or run page code report the console errors
+    // and failed requests that showed up while they ran.
+    let reports_diagnostics = matches!(
+        action_lower.as_str(),
+        "open" | "click" | "type" | "key" | "fill" | "select" | "submit" | "login" | "javascript"
+            | "eval" | "scroll" | "history" | "diff"
+    );
+    let diagnostics_since = code_browser::diagnostics::now_unix_ms();
+
+    let result = match action_lower.as_str() {
         "open" => handle_browser_open(sess, ctx, payload_string.clone()).await,
         "close" => handle_browser_close(sess, ctx).await,
         "status" => handle_browser_status(sess, ctx).await,
@@ -12297,11 +13552,21 @@ async fn handle_browser_tool(sess: &Session, ctx: &ToolCallCtx, arguments: Strin
         "move" => handle_browser_move(sess, ctx, payload_string.clone()).await,
         "type" => handle_browser_type(sess, ctx, payload_string.clone()).await,
         "key" => handle_browser_key(sess, ctx, payload_string.clone()).await,
+        "fill" | "select" | "submit" => {
+            handle_browser_form(sess, ctx, &action_lower, payload_string.clone()).await
+        }
+        "query" => handle_browser_query(sess, ctx, payload_string.clone()).await,
         "javascript" => handle_browser_javascript(sess, ctx, payload_string.clone()).await,
+        "eval" => handle_browser_eval(sess, ctx, payload_string.clone()).await,
         "scroll" => handle_browser_scroll(sess, ctx, payload_string.clone()).await,
+        "viewport" => handle_browser_viewport(sess, ctx, payload_string.clone()).await,
+        "download" => handle_browser_download(sess, ctx, payload_string.clone()).await,
         "history" => handle_browser_history(sess, ctx, payload_string.clone()).await,
         "inspect" => handle_browser_inspect(sess, ctx, payload_string.clone()).await,
         "console" => handle_browser_console(sess, ctx, payload_string.clone()).await,
+        "network" => handle_browser_network(sess, ctx, payload_string.clone()).await,
+        "diff" => handle_browser_diff(sess, ctx, payload_string.clone()).await,
+        "login" => handle_browser_login(sess, ctx, payload_string.clone()).await,
         "cdp" => handle_browser_cdp(sess, ctx, payload_string.clone()).await,
         "cleanup" => handle_browser_cleanup(sess, ctx).await,
         "fetch" => handle_web_fetch(sess, ctx, payload_string.clone()).await,
@@ -12311,12 +13576,178 @@ async fn handle_browser_tool(sess: &Session, ctx: &ToolCallCtx, arguments: Strin
                 body: code_protocol::models::FunctionCallOutputBody::Text(format!("Unknown browser action: {}", action)),
                 success: Some(false)},
         },
+    };
+    let result = if may_navigate {
+        enforce_navigation_policy_after_action(sess, ctx, host_before, result).await
+    } else {
+        result
+    };
+    if reports_diagnostics {
+        append_page_diagnostics(sess, ctx, diagnostics_since, result).await
+    } else {
+        result
+    }
+}
+
+const MAX_DIAGNOSTICS_PER_KIND: usize = 10;
+
+/// Add console errors/warnings and failed requests recorded since `since_ms`
+/// to the action's output, and forward them to the browser card.
+async fn append_page_diagnostics(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    since_ms: i128,
+    result: ResponseInputItem,
+) -> ResponseInputItem {
+    let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+        return result;
+    };
+    let Some(diagnostics) = browser_manager.diagnostics_since(since_ms).await else {
+        return result;
+    };
+    if diagnostics.is_empty() {
+        return result;
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for problem in diagnostics.console.iter().take(MAX_DIAGNOSTICS_PER_KIND) {
+        lines.push(format!("console {}: {}", problem.level, problem.message.trim()));
+    }
+    if diagnostics.console.len() > MAX_DIAGNOSTICS_PER_KIND {
+        let more = diagnostics.console.len() - MAX_DIAGNOSTICS_PER_KIND;
+        lines.push(format!("… {more} more console messages"));
+    }
+    for failure in diagnostics.network.iter().take(MAX_DIAGNOSTICS_PER_KIND) {
+        lines.push(format!("request failed: {}", failure.summary()));
+    }
+    if diagnostics.network.len() > MAX_DIAGNOSTICS_PER_KIND {
+        let more = diagnostics.network.len() - MAX_DIAGNOSTICS_PER_KIND;
+        lines.push(format!("… {more} more failed requests"));
+    }
+
+    let order = sess.background_order_for_ctx(ctx, sess.current_request_ordinal());
+    sess.notify_background_event_with_order(
+        &ctx.sub_id,
+        order,
+        format!("[browser diagnostics]\n{}", lines.join("\n")),
+    )
+    .await;
+
+    let report = format!(
+        "Page diagnostics (new during this action):\n{}\nUse action=console or action=network for the full logs.",
+        lines.iter().map(|line| format!("- {line}")).collect::<Vec<_>>().join("\n")
+    );
+    match result {
+        ResponseInputItem::FunctionCallOutput { call_id, mut output } => {
+            match &mut output.body {
+                code_protocol::models::FunctionCallOutputBody::Text(text) => {
+                    text.push_str("\n\n");
+                    text.push_str(&report);
+                }
+                code_protocol::models::FunctionCallOutputBody::ContentItems(items) => {
+                    items.push(FunctionCallOutputContentItem::InputText { text: report });
+                }
+            }
+            ResponseInputItem::FunctionCallOutput { call_id, output }
+        }
+        other => other,
+    }
+}
+
+/// Apply the configured navigation policy to `url`, asking the user about new
+/// domains when `browser.prompt_on_new_domain` is set. Returns the message to
+/// report when the navigation must not happen.
+async fn check_navigation_policy(sess: &Session, ctx: &ToolCallCtx, url: &str) -> Result<(), String> {
+    use crate::navigation_policy::NavigationDecision;
+
+    let host = match sess.client.config().navigation_policy.evaluate(url) {
+        NavigationDecision::Allow => return Ok(()),
+        NavigationDecision::Deny(message) => return Err(message),
+        NavigationDecision::Ask(host) => host,
+    };
+    if sess.is_navigation_domain_approved(&host) {
+        return Ok(());
+    }
+    if sess.get_approval_policy() == AskForApproval::Never {
+        return Err(format!(
+            "{host} is not on the navigation allowlist and approval prompts are disabled"
+        ));
+    }
+    let rx_approve = sess
+        .request_command_approval(
+            ctx.sub_id.clone(),
+            ctx.call_id.clone(),
+            None,
+            None,
+            vec!["browser".to_string(), "navigate".to_string(), url.to_string()],
+            sess.get_cwd().to_path_buf(),
+            Some(format!("Visit {host}, which is not on the navigation allowlist")),
+            None,
+            None,
+        )
+        .await;
+    match rx_approve.await.unwrap_or_default() {
+        ReviewDecision::Approved => Ok(()),
+        ReviewDecision::ApprovedForSession => {
+            sess.approve_navigation_domain(&host);
+            Ok(())
+        }
+        ReviewDecision::Denied | ReviewDecision::Abort => {
+            Err(format!("user declined navigation to {host}"))
+        }
+    }
+}
+
+/// Leave the page on `about:blank` when an action took the browser to a host
+/// the navigation policy refuses.
+async fn enforce_navigation_policy_after_action(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    host_before: Option<String>,
+    result: ResponseInputItem,
+) -> ResponseInputItem {
+    let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+        return result;
+    };
+    let Some(url) = browser_manager.get_current_url().await else {
+        return result;
+    };
+    if crate::navigation_policy::host_of(&url) == host_before {
+        return result;
+    }
+    match check_navigation_policy(sess, ctx, &url).await {
+        Ok(()) => result,
+        Err(message) => {
+            let _ = browser_manager.goto("about:blank").await;
+            ResponseInputItem::FunctionCallOutput {
+                call_id: ctx.call_id.clone(),
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                        "The page navigated to {url}: {message}. The browser was moved to about:blank."
+                    )),
+                    success: Some(false),
+                },
+            }
+        }
     }
 }
 
 async fn handle_browser_open(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
     // Parse arguments as JSON for the event
-    let params = serde_json::from_str(&arguments).ok();
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    let target = params
+        .as_ref()
+        .and_then(|value| value.get("url"))
+        .and_then(|value| value.as_str())
+        .unwrap_or("about:blank");
+    if let Err(message) = check_navigation_policy(sess, ctx, target).await {
+        return ResponseInputItem::FunctionCallOutput {
+            call_id: ctx.call_id.clone(),
+            output: FunctionCallOutputPayload {
+                body: code_protocol::models::FunctionCallOutputBody::Text(format!("Cannot open {target}: {message}")),
+                success: Some(false)},
+        };
+    }
 
     let arguments_clone = arguments.clone();
     let call_id_clone = ctx.call_id.clone();
@@ -12550,6 +13981,7 @@ async fn handle_browser_click(sess: &Session, ctx: &ToolCallCtx, arguments: Stri
     let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
     let sess_clone = sess;
     let call_id_clone = ctx.call_id.clone();
+    let wants_screenshot = browser_screenshot_requested(params.as_ref());
 
     execute_custom_tool(
         sess,
@@ -12563,6 +13995,33 @@ async fn handle_browser_click(sess: &Session, ctx: &ToolCallCtx, arguments: Stri
                 let _ = browser_manager
                     .execute_cdp("Overlay.hideHighlight", serde_json::json!({}))
                     .await;
+                if let Some(selector) = params
+                    .as_ref()
+                    .and_then(|v| v.get("selector"))
+                    .and_then(|v| v.as_str())
+                    .filter(|selector| !selector.trim().is_empty())
+                {
+                    let result = match browser_manager.click_selector(selector).await {
+                        Ok((x, y)) => ResponseInputItem::FunctionCallOutput {
+                            call_id: call_id_clone.clone(),
+                            output: FunctionCallOutputPayload {
+                                body: code_protocol::models::FunctionCallOutputBody::Text(format!("Clicked {selector} at ({x:.0}, {y:.0})")),
+                                success: Some(true)},
+                        },
+                        Err(e) => ResponseInputItem::FunctionCallOutput {
+                            call_id: call_id_clone.clone(),
+                            output: FunctionCallOutputPayload {
+                                body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to click {selector}: {e}")),
+                                success: Some(false)},
+                        },
+                    };
+                    return if wants_screenshot {
+                        attach_browser_screenshot(sess_clone, result).await
+                    } else {
+                        result
+                    };
+                }
+
                 // Determine click type: default 'click', or 'mousedown'/'mouseup'
                 let click_type = params
                     .as_ref()
@@ -12625,7 +14084,7 @@ async fn handle_browser_click(sess: &Session, ctx: &ToolCallCtx, arguments: Stri
                     },
                 };
 
-                match action_result {
+                let result = match action_result {
                     Ok((x, y, label)) => {
                         ResponseInputItem::FunctionCallOutput {
                             call_id: call_id_clone.clone(),
@@ -12640,6 +14099,11 @@ async fn handle_browser_click(sess: &Session, ctx: &ToolCallCtx, arguments: Stri
                             body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to perform mouse action: {}", e)),
                             success: Some(false)},
                     },
+                };
+                if wants_screenshot {
+                    attach_browser_screenshot(sess_clone, result).await
+                } else {
+                    result
                 }
     } else {
         ResponseInputItem::FunctionCallOutput {
@@ -12830,38 +14294,212 @@ async fn handle_browser_key(sess: &Session, ctx: &ToolCallCtx, arguments: String
                             };
                         }
 
-                        match browser_manager.press_key(key).await {
-                            Ok(_) => {
-                                ResponseInputItem::FunctionCallOutput {
-                                    call_id: call_id_clone.clone(),
-                                    output: FunctionCallOutputPayload {
-                                        body: code_protocol::models::FunctionCallOutputBody::Text(format!("Pressed key: {}", key)),
-                                        success: Some(true)},
-                                }
-                            }
-                            Err(e) => ResponseInputItem::FunctionCallOutput {
-                                call_id: call_id_clone.clone(),
-                                output: FunctionCallOutputPayload {
-                                    body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to press key: {}", e)),
-                                    success: Some(false)},
-                            },
-                        }
-                    }
-                    Err(e) => ResponseInputItem::FunctionCallOutput {
-                        call_id: call_id_clone,
-                        output: FunctionCallOutputPayload {
-                            body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to parse browser_key arguments: {}", e)),
-                            success: Some(false)},
+                        match browser_manager.press_key(key).await {
+                            Ok(_) => {
+                                ResponseInputItem::FunctionCallOutput {
+                                    call_id: call_id_clone.clone(),
+                                    output: FunctionCallOutputPayload {
+                                        body: code_protocol::models::FunctionCallOutputBody::Text(format!("Pressed key: {}", key)),
+                                        success: Some(true)},
+                                }
+                            }
+                            Err(e) => ResponseInputItem::FunctionCallOutput {
+                                call_id: call_id_clone.clone(),
+                                output: FunctionCallOutputPayload {
+                                    body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to press key: {}", e)),
+                                    success: Some(false)},
+                            },
+                        }
+                    }
+                    Err(e) => ResponseInputItem::FunctionCallOutput {
+                        call_id: call_id_clone,
+                        output: FunctionCallOutputPayload {
+                            body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to parse browser_key arguments: {}", e)),
+                            success: Some(false)},
+                    },
+                }
+            } else {
+                ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text("Browser is not initialized. Use browser_open to start the browser."
+                                .to_string()),
+                        success: Some(false)},
+                }
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_browser_form(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    action: &str,
+    arguments: String,
+) -> ResponseInputItem {
+    use code_browser::forms::FormAction;
+    use code_browser::forms::FormTarget;
+
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let sess_clone = sess;
+    let call_id_clone = ctx.call_id.clone();
+    let field = |name: &str| {
+        params
+            .as_ref()
+            .and_then(|value| value.get(name))
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.trim().is_empty())
+            .map(str::to_string)
+    };
+    let target = match (field("selector"), field("label")) {
+        (Some(selector), _) => Some(FormTarget::Selector(selector)),
+        (None, Some(label)) => Some(FormTarget::Label(label)),
+        (None, None) => None,
+    };
+    // Checkbox values may arrive as JSON booleans.
+    let value = params
+        .as_ref()
+        .and_then(|value| value.get("value"))
+        .map(|value| match value {
+            serde_json::Value::String(text) => text.clone(),
+            other => other.to_string(),
+        });
+    let form_action = match action {
+        "fill" => value.map(FormAction::Fill),
+        "select" => value.map(FormAction::Select),
+        _ => Some(FormAction::Submit),
+    };
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        format!("browser_{action}"),
+        params.clone(),
+        || async move {
+            let (Some(target), Some(form_action)) = (target, form_action) else {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                            "browser {action} requires 'selector' or 'label'{}",
+                            if action == "submit" { "" } else { " and 'value'" }
+                        )),
+                        success: Some(false),
+                    },
+                };
+            };
+            let Some(browser_manager) = get_browser_manager_for_session(sess_clone).await else {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(
+                            "Browser is not initialized. Use browser_open to start the browser."
+                                .to_string(),
+                        ),
+                        success: Some(false),
+                    },
+                };
+            };
+            let _ = browser_manager
+                .execute_cdp("Overlay.hideHighlight", serde_json::json!({}))
+                .await;
+            let (body, success) = match browser_manager.form_action(&target, &form_action).await {
+                Ok(outcome) => (outcome.summary(), true),
+                Err(err) => (format!("Failed to {action}: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_browser_query(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use code_browser::query::DEFAULT_QUERY_LIMIT;
+    use code_browser::query::QueryOptions;
+
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let sess_clone = sess;
+    let call_id_clone = ctx.call_id.clone();
+    let selector = params
+        .as_ref()
+        .and_then(|value| value.get("selector"))
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|selector| !selector.is_empty())
+        .map(str::to_string);
+    let limit = params
+        .as_ref()
+        .and_then(|value| value.get("limit"))
+        .and_then(|value| value.as_u64())
+        .map_or(DEFAULT_QUERY_LIMIT, |limit| limit as usize);
+    let include_html = params
+        .as_ref()
+        .and_then(|value| value.get("html"))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+    let attributes = params
+        .as_ref()
+        .and_then(|value| value.get("attributes"))
+        .and_then(|value| value.as_array())
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        });
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_query".to_string(),
+        params.clone(),
+        || async move {
+            let Some(selector) = selector else {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(
+                            "browser query requires a CSS 'selector'".to_string(),
+                        ),
+                        success: Some(false),
                     },
-                }
-            } else {
-                ResponseInputItem::FunctionCallOutput {
+                };
+            };
+            let Some(browser_manager) = get_browser_manager_for_session(sess_clone).await else {
+                return ResponseInputItem::FunctionCallOutput {
                     call_id: call_id_clone,
                     output: FunctionCallOutputPayload {
-                        body: code_protocol::models::FunctionCallOutputBody::Text("Browser is not initialized. Use browser_open to start the browser."
-                                .to_string()),
-                        success: Some(false)},
-                }
+                        body: code_protocol::models::FunctionCallOutputBody::Text(
+                            "Browser is not initialized. Use browser_open to start the browser."
+                                .to_string(),
+                        ),
+                        success: Some(false),
+                    },
+                };
+            };
+            let options = QueryOptions {
+                selector,
+                limit,
+                include_html,
+                attributes,
+            };
+            let (body, success) = match browser_manager.query(&options).await {
+                Ok(result) => (result.render(&options.selector), true),
+                Err(err) => (format!("Failed to query the page: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
             }
         },
     )
@@ -12869,7 +14507,24 @@ async fn handle_browser_key(sess: &Session, ctx: &ToolCallCtx, arguments: String
 }
 
 async fn handle_browser_javascript(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
-    let params = serde_json::from_str(&arguments).ok();
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    if get_browser_manager_for_session(sess).await.is_some() {
+        let code = params
+            .as_ref()
+            .and_then(|value| value.get("code"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        if let Err(message) = approve_browser_script(sess, ctx, "javascript", code).await {
+            return ResponseInputItem::FunctionCallOutput {
+                call_id: ctx.call_id.clone(),
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(message),
+                    success: Some(false),
+                },
+            };
+        }
+    }
     let sess_clone = sess;
     let arguments_clone = arguments.clone();
     let call_id_clone = ctx.call_id.clone();
@@ -12987,66 +14642,516 @@ async fn handle_browser_javascript(sess: &Session, ctx: &ToolCallCtx, arguments:
     .await
 }
 
+/// Like `javascript`, but the user approves each snippet (or the session)
+/// before it runs, and the result comes back as JSON.
+async fn handle_browser_eval(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let code = params
+        .as_ref()
+        .and_then(|value| value.get("code"))
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    };
+    if code.trim().is_empty() {
+        return failure("browser eval requires JavaScript 'code'".to_string());
+    }
+    let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+        return failure(
+            "Browser is not initialized. Use browser_open to start the browser.".to_string(),
+        );
+    };
+
+    if let Err(message) = approve_browser_script(sess, ctx, "eval", code.clone()).await {
+        return failure(message);
+    }
+
+    let call_id_clone = ctx.call_id.clone();
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_eval".to_string(),
+        params,
+        || async move {
+            let (body, success) = match browser_manager.execute_javascript(&code).await {
+                Ok(result) => format_browser_eval_result(&result),
+                Err(err) => (format!("Failed to evaluate JavaScript: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+/// Ask the user before model-written page script (`javascript`, `eval`) or a
+/// raw DevTools command (`cdp`) runs. Approving for the session covers all
+/// three, since each can read and change anything on the page, except on
+/// sites where `login` filled a password: there every call asks.
+async fn approve_browser_script(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    action: &str,
+    detail: String,
+) -> Result<(), String> {
+    let url = match get_browser_manager_for_session(sess).await {
+        Some(browser_manager) => browser_manager.get_current_url().await,
+        None => None,
+    };
+    let login_filled = url
+        .as_deref()
+        .and_then(code_browser::login::origin_of)
+        .is_some_and(|origin| sess.is_browser_login_filled(&origin));
+    if sess.is_browser_eval_approved_for_session() && !login_filled {
+        return Ok(());
+    }
+    if sess.get_approval_policy() == AskForApproval::Never {
+        return Err(format!(
+            "browser {action} requires approval, but the approval policy never prompts"
+        ));
+    }
+    let page = url.unwrap_or_else(|| "the current page".to_string());
+    let mut reason = if action == "cdp" {
+        format!("Send a DevTools command to {page}")
+    } else {
+        format!("Run JavaScript in {page}")
+    };
+    if login_filled {
+        reason.push_str(" (a password was filled in on this site and can be read)");
+    }
+    let rx_approve = sess
+        .request_command_approval(
+            ctx.sub_id.clone(),
+            ctx.call_id.clone(),
+            None,
+            None,
+            vec!["browser".to_string(), action.to_string(), detail],
+            sess.get_cwd().to_path_buf(),
+            Some(reason),
+            None,
+            None,
+        )
+        .await;
+    match rx_approve.await.unwrap_or_default() {
+        ReviewDecision::Approved => Ok(()),
+        ReviewDecision::ApprovedForSession => {
+            sess.approve_browser_eval_for_session();
+            Ok(())
+        }
+        ReviewDecision::Denied | ReviewDecision::Abort => {
+            Err(format!("user rejected browser {action}"))
+        }
+    }
+}
+
+/// Serialize the page harness result: the value as JSON, then any console
+/// output and page errors raised while the snippet ran.
+fn format_browser_eval_result(result: &serde_json::Value) -> (String, bool) {
+    let success = result
+        .get("success")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    let mut out = if success {
+        let value = result.get("value").unwrap_or(&serde_json::Value::Null);
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".to_string())
+    } else {
+        let error = result
+            .get("error")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("unknown error");
+        format!("Error: {error}")
+    };
+    let logs = result
+        .get("logs")
+        .and_then(serde_json::Value::as_array)
+        .filter(|logs| !logs.is_empty());
+    if let Some(logs) = logs {
+        out.push_str("\n\nConsole:");
+        for log in logs {
+            let level = log.get("level").and_then(serde_json::Value::as_str).unwrap_or("log");
+            let args = log
+                .get("args")
+                .and_then(serde_json::Value::as_array)
+                .map(|args| {
+                    args.iter()
+                        .map(|arg| match arg {
+                            serde_json::Value::String(text) => text.clone(),
+                            other => other.to_string(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+            out.push_str(&format!("\n  [{level}] {args}"));
+        }
+    }
+    let errors = result
+        .get("errors")
+        .and_then(serde_json::Value::as_array)
+        .filter(|errors| !errors.is_empty());
+    if let Some(errors) = errors {
+        out.push_str("\n\nPage errors:");
+        for error in errors.iter().filter_map(serde_json::Value::as_str) {
+            out.push_str(&format!("\n  {error}"));
+        }
+    }
+    (out, success)
+}
+
+#[cfg(test)]
+mod browser_eval_tests {
+    use super::format_browser_eval_result;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_value_then_console_and_errors() {
+        let result = json!({
+            "success": true,
+            "value": {"count": 2},
+            "logs": [{"level": "warn", "args": ["slow", 3]}],
+            "errors": ["unhandledrejection: boom"],
+        });
+        assert_eq!(
+            format_browser_eval_result(&result),
+            (
+                "{\n  \"count\": 2\n}\n\nConsole:\n  [warn] slow 3\n\nPage errors:\n  unhandledrejection: boom"
+                    .to_string(),
+                true
+            )
+        );
+
+        let failed = json!({"success": false, "error": "ReferenceError: x is not defined"});
+        assert_eq!(
+            format_browser_eval_result(&failed),
+            ("Error: ReferenceError: x is not defined".to_string(), false)
+        );
+    }
+}
+
 async fn handle_browser_scroll(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
-    let params = serde_json::from_str(&arguments).ok();
+    use code_browser::page::ScrollTarget;
+
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let sess_clone = sess;
+    let call_id_clone = ctx.call_id.clone();
+    let wants_screenshot = browser_screenshot_requested(params.as_ref());
+    let number = |name: &str| params.as_ref().and_then(|v| v.get(name)).and_then(|v| v.as_f64());
+    let selector = params
+        .as_ref()
+        .and_then(|v| v.get("selector"))
+        .and_then(|v| v.as_str())
+        .filter(|selector| !selector.trim().is_empty());
+    let to = params.as_ref().and_then(|v| v.get("to")).and_then(|v| v.as_str());
+    let target = if let Some(selector) = selector {
+        ScrollTarget::Element(selector.to_string())
+    } else if let Some(to) = to {
+        match to.to_ascii_lowercase().as_str() {
+            "top" => ScrollTarget::Top,
+            _ => ScrollTarget::Bottom,
+        }
+    } else if let Some(pages) = number("pages") {
+        ScrollTarget::Pages(pages)
+    } else {
+        ScrollTarget::By {
+            dx: number("dx").unwrap_or(0.0),
+            dy: number("dy").unwrap_or(0.0),
+        }
+    };
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_scroll".to_string(),
+        params.clone(),
+        || async move {
+            let Some(browser_manager) = get_browser_manager_for_session(sess_clone).await else {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(
+                            "Browser is not initialized. Use browser_open to start the browser."
+                                .to_string(),
+                        ),
+                        success: Some(false),
+                    },
+                };
+            };
+            let _ = browser_manager
+                .execute_cdp("Overlay.hideHighlight", serde_json::json!({}))
+                .await;
+            let (body, success) = match browser_manager.scroll_page(&target).await {
+                Ok(position) => {
+                    let mut message = format!(
+                        "Scrolled to ({:.0}, {:.0}); page height {:.0}px",
+                        position.x, position.y, position.height
+                    );
+                    if position.at_bottom() {
+                        message.push_str(" (at the bottom; new content may still load)");
+                    }
+                    (message, true)
+                }
+                Err(e) => (format!("Failed to scroll: {e}"), false),
+            };
+            let result = ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            };
+            if wants_screenshot {
+                attach_browser_screenshot(sess_clone, result).await
+            } else {
+                result
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_browser_viewport(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
     let sess_clone = sess;
-    let arguments_clone = arguments.clone();
     let call_id_clone = ctx.call_id.clone();
+    let wants_screenshot = browser_screenshot_requested(params.as_ref());
+    let dimension = |name: &str| {
+        params
+            .as_ref()
+            .and_then(|v| v.get(name))
+            .and_then(|v| v.as_u64())
+            .filter(|&value| (200..=4096).contains(&value))
+            .map(|value| value as u32)
+    };
+    let size = dimension("width").zip(dimension("height"));
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_viewport".to_string(),
+        params.clone(),
+        || async move {
+            let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone.clone(),
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(message),
+                    success: Some(false),
+                },
+            };
+            let Some((width, height)) = size else {
+                return failure(
+                    "browser viewport requires 'width' and 'height' between 200 and 4096 pixels"
+                        .to_string(),
+                );
+            };
+            let Some(browser_manager) = get_browser_manager_for_session(sess_clone).await else {
+                return failure(
+                    "Browser is not initialized. Use browser_open to start the browser."
+                        .to_string(),
+                );
+            };
+            let result = match browser_manager.set_viewport_size(width, height).await {
+                Ok(viewport) => ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone.clone(),
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(format!(
+                            "Viewport set to {}x{}",
+                            viewport.width, viewport.height
+                        )),
+                        success: Some(true),
+                    },
+                },
+                Err(e) => return failure(format!("Failed to set viewport: {e}")),
+            };
+            if wants_screenshot {
+                attach_browser_screenshot(sess_clone, result).await
+            } else {
+                result
+            }
+        },
+    )
+    .await
+}
+
+/// Download a file with the browser's cookies into the session downloads
+/// directory. Sandboxes without network access need the user's approval.
+async fn handle_browser_download(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    use crate::browser_downloads;
+
+    let params = serde_json::from_str::<serde_json::Value>(&arguments).ok();
+    let field = |name: &str| {
+        params
+            .as_ref()
+            .and_then(|value| value.get(name))
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let filename = field("filename");
+    let failure = |message: String| ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    };
+    let url = match field("url").map(|url| url::Url::parse(&url)) {
+        Some(Ok(url)) if matches!(url.scheme(), "http" | "https") => url,
+        Some(Ok(url)) => {
+            return failure(format!("cannot download {url}: only http(s) URLs are supported"));
+        }
+        Some(Err(err)) => return failure(format!("invalid download URL: {err}")),
+        None => return failure("browser download requires a 'url'".to_string()),
+    };
+    if let Err(message) = check_navigation_policy(sess, ctx, url.as_str()).await {
+        return failure(format!("cannot download {url}: {message}"));
+    }
+    let dir = browser_downloads::downloads_dir(
+        sess.client.code_home(),
+        &sess.session_uuid().to_string(),
+    );
+
+    if !sess.get_sandbox_policy().has_full_network_access()
+        && !sess.are_browser_downloads_approved_for_session()
+    {
+        if sess.get_approval_policy() == AskForApproval::Never {
+            return failure(
+                "browser download needs network access, which the sandbox policy does not allow"
+                    .to_string(),
+            );
+        }
+        let rx_approve = sess
+            .request_command_approval(
+                ctx.sub_id.clone(),
+                ctx.call_id.clone(),
+                None,
+                None,
+                vec!["browser".to_string(), "download".to_string(), url.to_string()],
+                dir.clone(),
+                Some(format!("Download {url} into {}", dir.display())),
+                None,
+                None,
+            )
+            .await;
+        match rx_approve.await.unwrap_or_default() {
+            ReviewDecision::Approved => {}
+            ReviewDecision::ApprovedForSession => sess.approve_browser_downloads_for_session(),
+            ReviewDecision::Denied | ReviewDecision::Abort => {
+                return failure("user rejected browser download".to_string());
+            }
+        }
+    }
 
+    let navigation_policy = sess.client.config().navigation_policy.clone();
+    let call_id_clone = ctx.call_id.clone();
     execute_custom_tool(
         sess,
         ctx,
-        "browser_scroll".to_string(),
-        params,
+        "browser_download".to_string(),
+        params.clone(),
         || async move {
-            let browser_manager = get_browser_manager_for_session(sess_clone).await;
-            if let Some(browser_manager) = browser_manager {
-                let _ = browser_manager
-                    .execute_cdp("Overlay.hideHighlight", serde_json::json!({}))
-                    .await;
-                let args: Result<Value, _> = serde_json::from_str(&arguments_clone);
-                match args {
-                    Ok(json) => {
-                        let dx = json.get("dx").and_then(|v| v.as_f64()).unwrap_or(0.0);
-                        let dy = json.get("dy").and_then(|v| v.as_f64()).unwrap_or(0.0);
-
-                        match browser_manager.scroll_by(dx, dy).await {
-                    Ok(_) => {
-                        ResponseInputItem::FunctionCallOutput {
-                            call_id: call_id_clone.clone(),
-                            output: FunctionCallOutputPayload {
-                                body: code_protocol::models::FunctionCallOutputBody::Text(format!("Scrolled by ({}, {})", dx, dy)),
-                                success: Some(true)},
-                        }
-                    }
-                    Err(e) => ResponseInputItem::FunctionCallOutput {
-                        call_id: call_id_clone.clone(),
-                        output: FunctionCallOutputPayload {
-                            body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to scroll: {}", e)),
-                            success: Some(false)},
-                    },
-                }
-            }
-            Err(e) => ResponseInputItem::FunctionCallOutput {
+            // Reuse the browser's login for the target site when it is open.
+            let cookies = match get_browser_manager_for_session(sess).await {
+                Some(browser_manager) => browser_manager
+                    .execute_cdp(
+                        "Network.getCookies",
+                        serde_json::json!({ "urls": [url.as_str()] }),
+                    )
+                    .await
+                    .ok()
+                    .and_then(|response| browser_downloads::cookie_header(&response)),
+                None => None,
+            };
+            let download = browser_downloads::download(
+                &url,
+                cookies,
+                &navigation_policy,
+                &dir,
+                filename.as_deref(),
+            );
+            let (body, success) = match download.await {
+                Ok(file) => (file.summary(), true),
+                Err(err) => (format!("Failed to download {url}: {err}"), false),
+            };
+            ResponseInputItem::FunctionCallOutput {
                 call_id: call_id_clone,
                 output: FunctionCallOutputPayload {
-                    body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to parse browser_scroll arguments: {}", e)),
-                    success: Some(false)},
-            },
-        }
-    } else {
-        ResponseInputItem::FunctionCallOutput {
-            call_id: call_id_clone,
-            output: FunctionCallOutputPayload {
-                body: code_protocol::models::FunctionCallOutputBody::Text("Browser is not initialized. Use browser_open to start the browser.".to_string()),
-                success: Some(false)},
-        }
-    }
+                    body: code_protocol::models::FunctionCallOutputBody::Text(body),
+                    success: Some(success),
+                },
+            }
         },
     )
     .await
 }
 
+/// Whether the model asked for a screenshot right after the action.
+fn browser_screenshot_requested(params: Option<&serde_json::Value>) -> bool {
+    params
+        .and_then(|value| value.get("screenshot"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Append a fresh screenshot to a successful browser action result so the
+/// model sees the outcome immediately instead of on its next turn.
+async fn attach_browser_screenshot(sess: &Session, result: ResponseInputItem) -> ResponseInputItem {
+    let ResponseInputItem::FunctionCallOutput { call_id, output } = result else {
+        return result;
+    };
+    let text = match &output.body {
+        code_protocol::models::FunctionCallOutputBody::Text(text) if output.success != Some(false) => {
+            text.clone()
+        }
+        _ => return ResponseInputItem::FunctionCallOutput { call_id, output },
+    };
+    let image = match capture_browser_screenshot(sess).await {
+        Ok((path, url)) => {
+            add_pending_screenshot(sess, path.clone(), url);
+            std::fs::read(&path).map_err(|e| e.to_string()).map(|bytes| {
+                let mime = mime_guess::from_path(&path)
+                    .first()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "image/png".to_string());
+                let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+                format!("data:{mime};base64,{encoded}")
+            })
+        }
+        Err(e) => Err(e),
+    };
+    let body = match image {
+        Ok(image_url) => code_protocol::models::FunctionCallOutputBody::ContentItems(vec![
+            FunctionCallOutputContentItem::InputText { text },
+            FunctionCallOutputContentItem::InputImage {
+                image_url,
+                detail: None,
+            },
+        ]),
+        Err(e) => code_protocol::models::FunctionCallOutputBody::Text(format!(
+            "{text}\n(Screenshot unavailable: {e})"
+        )),
+    };
+    ResponseInputItem::FunctionCallOutput {
+        call_id,
+        output: FunctionCallOutputPayload {
+            body,
+            success: output.success,
+        },
+    }
+}
+
 async fn handle_browser_console(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
     let params = serde_json::from_str(&arguments).ok();
     let sess_clone = sess;
@@ -13124,8 +15229,434 @@ async fn handle_browser_console(sess: &Session, ctx: &ToolCallCtx, arguments: St
     .await
 }
 
+async fn handle_browser_network(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    let limit = params
+        .as_ref()
+        .and_then(|json| json.get("lines"))
+        .and_then(Value::as_u64)
+        .map(|n| n as usize)
+        .unwrap_or(50);
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_network".to_string(),
+        params,
+        || async move {
+            let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+                return ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text("Browser is not enabled. Use browser_open to enable it first.".to_string()),
+                        success: Some(false)},
+                };
+            };
+            match browser_manager.get_network_failures(limit).await {
+                Ok(failures) => {
+                    let formatted = if failures.is_empty() {
+                        "No failed network requests captured.".to_string()
+                    } else {
+                        let mut output = String::from("Failed network requests:\n");
+                        for failure in &failures {
+                            output.push_str(&format!("{}\n", failure.summary()));
+                        }
+                        output
+                    };
+                    ResponseInputItem::FunctionCallOutput {
+                        call_id: call_id_clone,
+                        output: FunctionCallOutputPayload {
+                            body: code_protocol::models::FunctionCallOutputBody::Text(formatted),
+                            success: Some(true)},
+                    }
+                }
+                Err(e) => ResponseInputItem::FunctionCallOutput {
+                    call_id: call_id_clone,
+                    output: FunctionCallOutputPayload {
+                        body: code_protocol::models::FunctionCallOutputBody::Text(format!("Failed to get network requests: {e}")),
+                        success: Some(false)},
+                },
+            }
+        },
+    )
+    .await
+}
+
+async fn handle_browser_diff(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    let target = params
+        .as_ref()
+        .and_then(|json| json.get("url"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    if let Some(url) = target.as_deref()
+        && let Err(message) = check_navigation_policy(sess, ctx, url).await
+    {
+        return ResponseInputItem::FunctionCallOutput {
+            call_id: ctx.call_id.clone(),
+            output: FunctionCallOutputPayload {
+                body: code_protocol::models::FunctionCallOutputBody::Text(format!("Cannot open {url}: {message}")),
+                success: Some(false),
+            },
+        };
+    }
+    let readability = sess.client.config().tools_web_fetch_readability;
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_diff".to_string(),
+        params,
+        || async move {
+            let (text, success) = match diff_current_page(sess, target.as_deref(), readability).await {
+                Ok(text) => (text, true),
+                Err(text) => (text, false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(text),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+/// Load `target` (or keep the current page), convert it to Markdown and
+/// compare it with the previous visit of the same URL in this session.
+async fn diff_current_page(
+    sess: &Session,
+    target: Option<&str>,
+    readability: bool,
+) -> Result<String, String> {
+    use crate::page_snapshots::PageChange;
+    const HTML_JS: &str = "(function(){ return document.documentElement.outerHTML; })()";
+
+    let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+        return Err("Browser is not enabled. Use browser_open to enable it first.".to_string());
+    };
+    let url = match target {
+        Some(url) => {
+            browser_manager
+                .goto(url)
+                .await
+                .map_err(|e| format!("Failed to open {url}: {e}"))?
+                .url
+        }
+        None => browser_manager
+            .get_current_url()
+            .await
+            .ok_or_else(|| "No page is open; pass a url to diff.".to_string())?,
+    };
+    let html = browser_manager
+        .execute_javascript(HTML_JS)
+        .await
+        .map_err(|e| format!("Failed to read {url}: {e}"))?
+        .get("value")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_default();
+    let (markdown, _truncated) = convert_html_to_markdown_trimmed(html, 120_000, readability, &url)
+        .map_err(|e| format!("Failed to convert {url} to Markdown: {e}"))?;
+
+    let Some(previous) = sess.record_page_snapshot(&url, markdown.clone()) else {
+        return Ok(format!(
+            "First visit to {url} in this session ({} lines of Markdown recorded). Call diff again after the page changes to see what is different.",
+            markdown.lines().count()
+        ));
+    };
+    let seconds = previous.age.as_secs();
+    let age = if seconds < 120 {
+        format!("{seconds}s")
+    } else if seconds < 2 * 3600 {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{}h", seconds / 3600)
+    };
+    Ok(match crate::page_snapshots::compare(&previous.markdown, &markdown) {
+        PageChange::Unchanged => format!("No changes on {url} since the last visit {age} ago."),
+        PageChange::Changed { diff, added, removed } => format!(
+            "{url} changed since the last visit {age} ago ({added} lines added, {removed} removed):\n\n```diff\n{diff}\n```"
+        ),
+    })
+}
+
+async fn handle_browser_login(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    let force_prompt = params
+        .as_ref()
+        .and_then(|json| json.get("prompt"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let submit = params
+        .as_ref()
+        .and_then(|json| json.get("submit"))
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    let call_id_clone = ctx.call_id.clone();
+
+    execute_custom_tool(
+        sess,
+        ctx,
+        "browser_login".to_string(),
+        params,
+        || async move {
+            let (text, success) = match sign_in_current_page(sess, ctx, force_prompt, submit).await {
+                Ok(text) => (text, true),
+                Err(text) => (text, false),
+            };
+            ResponseInputItem::FunctionCallOutput {
+                call_id: call_id_clone,
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(text),
+                    success: Some(success),
+                },
+            }
+        },
+    )
+    .await
+}
+
+const SAVE_CREDENTIALS_OPTION: &str = "Save in the OS keyring";
+const SESSION_CREDENTIALS_OPTION: &str = "This session only";
+
+/// Sign in to the current page's HTTP Basic challenge or login form with
+/// credentials from this session, the OS keyring or a secure prompt. The
+/// returned message never includes the credentials.
+async fn sign_in_current_page(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    force_prompt: bool,
+    submit: bool,
+) -> Result<String, String> {
+    use code_browser::login::origin_of;
+
+    let Some(browser_manager) = get_browser_manager_for_session(sess).await else {
+        return Err("Browser is not enabled. Use browser_open to enable it first.".to_string());
+    };
+    let url = browser_manager
+        .get_current_url()
+        .await
+        .ok_or_else(|| "No page is open; open the sign-in page first.".to_string())?;
+    let origin = origin_of(&url).ok_or_else(|| format!("{url} is not an http(s) page."))?;
+    let realm = browser_manager
+        .basic_auth_realm(&origin)
+        .await
+        .map_err(|e| format!("Failed to inspect the page: {e}"))?;
+    if realm.is_none() {
+        let form = browser_manager
+            .detect_login_form()
+            .await
+            .map_err(|e| format!("Failed to inspect the page: {e}"))?;
+        if form.is_none() {
+            return Err(format!(
+                "No login form or HTTP authentication challenge found on {url}."
+            ));
+        }
+    }
+
+    let store = code_keyring_store::DefaultKeyringStore;
+    let mut notes: Vec<String> = Vec::new();
+    let stored = if force_prompt {
+        None
+    } else {
+        sess.browser_session_credentials(&origin).or_else(|| {
+            crate::browser_credentials::load(&store, &origin).unwrap_or_else(|err| {
+                notes.push(format!("Saved credentials were not used: {err}."));
+                None
+            })
+        })
+    };
+    let from_storage = stored.is_some();
+    let (credentials, remember) = match stored {
+        Some(credentials) => (credentials, false),
+        None => prompt_for_browser_credentials(sess, ctx, &origin, realm.as_deref()).await?,
+    };
+    let source = if from_storage {
+        "the credentials saved for this site"
+    } else {
+        "the credentials the user entered"
+    };
+
+    let summary = if realm.is_some() {
+        browser_manager
+            .provide_basic_auth(&origin, credentials.clone())
+            .await
+            .map_err(|e| format!("Failed to set up HTTP authentication: {e}"))?;
+        let reload_started = code_browser::diagnostics::now_unix_ms();
+        browser_manager
+            .goto(&url)
+            .await
+            .map_err(|e| format!("Failed to reload {url}: {e}"))?;
+        let rejected = browser_manager
+            .diagnostics_since(reload_started)
+            .await
+            .is_some_and(|diagnostics| {
+                diagnostics.network.iter().any(|failure| {
+                    failure.status == Some(401) && failure.resource_type.as_deref() == Some("Document")
+                })
+            });
+        if rejected {
+            let retry = if from_storage {
+                sess.forget_browser_credentials(&origin);
+                " Call login again with \"prompt\":true to ask the user for new ones."
+            } else {
+                ""
+            };
+            return Err(format!("{origin} rejected {source}.{retry}"));
+        }
+        format!("Answered the HTTP authentication challenge from {origin} with {source} and reloaded {url}.")
+    } else {
+        // Marked first: a failed fill may still leave the password in the page.
+        sess.mark_browser_login_filled(&origin);
+        let submitted = browser_manager
+            .fill_login_form(&credentials, submit)
+            .await
+            .map_err(|e| format!("Failed to fill the login form: {e}"))?;
+        let action = if submitted { "filled and submitted" } else { "filled" };
+        format!(
+            "The login form on {origin} was {action} with {source}. Check the page to confirm the sign-in worked; if it was rejected, call login again with \"prompt\":true."
+        )
+    };
+
+    if !from_storage {
+        if remember {
+            match crate::browser_credentials::save(&store, &origin, &credentials) {
+                Ok(()) => notes.push("They were saved in the OS keyring.".to_string()),
+                Err(err) => {
+                    notes.push(format!("They are kept for this session only; {err}."));
+                    sess.remember_browser_credentials(&origin, credentials);
+                }
+            }
+        } else {
+            sess.remember_browser_credentials(&origin, credentials);
+        }
+    }
+
+    let mut message = summary;
+    for note in notes {
+        message.push(' ');
+        message.push_str(&note);
+    }
+    Ok(message)
+}
+
+/// Ask the user for a username and password through the TUI's masked input
+/// prompt. Returns the credentials and whether to save them in the keyring.
+async fn prompt_for_browser_credentials(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    origin: &str,
+    realm: Option<&str>,
+) -> Result<(code_browser::login::Credentials, bool), String> {
+    use code_protocol::request_user_input::RequestUserInputEvent;
+    use code_protocol::request_user_input::RequestUserInputQuestion;
+    use code_protocol::request_user_input::RequestUserInputQuestionOption;
+
+    if sess.get_approval_policy() == AskForApproval::Never {
+        return Err(format!(
+            "{origin} needs credentials, but prompts are disabled by the approval policy."
+        ));
+    }
+    let site = match realm {
+        Some(realm) if !realm.is_empty() => format!("{origin} (\"{realm}\")"),
+        _ => origin.to_string(),
+    };
+    let questions = vec![
+        RequestUserInputQuestion {
+            id: "username".to_string(),
+            header: "Username".to_string(),
+            question: format!(
+                "Sign in to {site}. What you enter is passed to the page and never shown to the model. Leave empty to cancel."
+            ),
+            is_other: false,
+            is_secret: false,
+            options: None,
+        },
+        RequestUserInputQuestion {
+            id: "password".to_string(),
+            header: "Password".to_string(),
+            question: format!("Password for {site}."),
+            is_other: false,
+            is_secret: true,
+            options: None,
+        },
+        RequestUserInputQuestion {
+            id: "remember".to_string(),
+            header: "Remember".to_string(),
+            question: "Keep these credentials for next time?".to_string(),
+            is_other: false,
+            is_secret: false,
+            options: Some(vec![
+                RequestUserInputQuestionOption {
+                    label: SESSION_CREDENTIALS_OPTION.to_string(),
+                    description: "Forget them when this session ends".to_string(),
+                },
+                RequestUserInputQuestionOption {
+                    label: SAVE_CREDENTIALS_OPTION.to_string(),
+                    description: format!("Store them for {origin}"),
+                },
+            ]),
+        },
+    ];
+
+    let rx_response = sess.register_pending_user_input(ctx.sub_id.clone())?;
+    sess.send_ordered_from_ctx(
+        ctx,
+        EventMsg::RequestUserInput(RequestUserInputEvent {
+            call_id: ctx.call_id.clone(),
+            turn_id: ctx.sub_id.clone(),
+            questions,
+            auto_resolution_ms: None,
+        }),
+    )
+    .await;
+    let response = rx_response
+        .await
+        .map_err(|_| "The sign-in prompt was cancelled.".to_string())?;
+
+    let answer = |id: &str| {
+        response
+            .answers
+            .get(id)
+            .and_then(|answer| answer.answers.first())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let username = answer("username").trim().to_string();
+    if username.is_empty() {
+        return Err(format!("The user chose not to sign in to {origin}."));
+    }
+    let password = answer("password").trim_end_matches(['\r', '\n']).to_string();
+    let remember = answer("remember") == SAVE_CREDENTIALS_OPTION;
+    Ok((code_browser::login::Credentials { username, password }, remember))
+}
+
 async fn handle_browser_cdp(sess: &Session, ctx: &ToolCallCtx, arguments: String) -> ResponseInputItem {
-    let params = serde_json::from_str(&arguments).ok();
+    let params: Option<Value> = serde_json::from_str(&arguments).ok();
+    if get_browser_manager_for_session(sess).await.is_some() {
+        let field = |name: &str| params.as_ref().and_then(|value| value.get(name)).cloned();
+        let method = field("method")
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let detail = match field("params") {
+            Some(method_params) => format!("{method} {method_params}"),
+            None => method,
+        };
+        if let Err(message) = approve_browser_script(sess, ctx, "cdp", detail).await {
+            return ResponseInputItem::FunctionCallOutput {
+                call_id: ctx.call_id.clone(),
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(message),
+                    success: Some(false),
+                },
+            };
+        }
+    }
     let sess_clone = sess;
     let arguments_clone = arguments.clone();
     let call_id_clone = ctx.call_id.clone();
@@ -14720,7 +17251,7 @@ mod tests {
         let exec_output = make_exec_output(output);
 
         let payload =
-            format_exec_output_with_limit(dir.path(), "sub", "call", &exec_output, 64);
+            format_exec_output_with_limit(dir.path(), "sub", "call", "seq", &exec_output, 64);
         let parsed: Value = serde_json::from_str(&payload).expect("parse payload");
         let content = parsed
             .get("output")
@@ -14739,6 +17270,7 @@ mod tests {
             dir.path(),
             "sub",
             "call",
+            "seq",
             &exec_output,
             output.len() + 32,
         );
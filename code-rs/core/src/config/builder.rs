@@ -2,6 +2,7 @@ use crate::config_loader::LoaderOverrides;
 use std::path::PathBuf;
 use toml::Value as TomlValue;
 
+use super::diagnostics::{config_file_warnings, locate_load_error};
 use super::sources;
 use super::validation::{apply_toml_override, deserialize_config_toml_with_cli_warnings};
 use super::{Config, ConfigOverrides, ConfigToml};
@@ -39,14 +40,18 @@ impl ConfigBuilder {
             None => sources::find_code_home()?,
         };
 
-        let mut root_value = sources::load_config_as_toml(&code_home)?;
+        let mut root_value = sources::load_config_as_toml(&code_home)
+            .map_err(|err| locate_load_error(&code_home, err))?;
         let cli_paths: Vec<String> = self.cli_overrides.iter().map(|(path, _)| path.clone()).collect();
         for (path, value) in self.cli_overrides.into_iter() {
             apply_toml_override(&mut root_value, &path, value);
         }
 
-        let cfg = deserialize_config_toml_with_cli_warnings(&root_value, &cli_paths)?;
+        let cfg = deserialize_config_toml_with_cli_warnings(&root_value, &cli_paths)
+            .map_err(|err| locate_load_error(&code_home, err))?;
+        let config_diagnostics = config_file_warnings(&code_home);
         let mut config = Config::load_from_base_config_with_overrides(cfg, self.overrides, code_home)?;
+        config.config_diagnostics.extend(config_diagnostics);
 
         let requirements = crate::config_loader::load_config_requirements_blocking(
             &config.code_home,
@@ -68,12 +73,14 @@ impl ConfigBuilder {
             None => sources::find_code_home()?,
         };
 
-        let mut root_value = sources::load_config_as_toml(&code_home)?;
+        let mut root_value = sources::load_config_as_toml(&code_home)
+            .map_err(|err| locate_load_error(&code_home, err))?;
         let cli_paths: Vec<String> = self.cli_overrides.iter().map(|(path, _)| path.clone()).collect();
         for (path, value) in self.cli_overrides.into_iter() {
             apply_toml_override(&mut root_value, &path, value);
         }
 
         deserialize_config_toml_with_cli_warnings(&root_value, &cli_paths)
+            .map_err(|err| locate_load_error(&code_home, err))
     }
 }
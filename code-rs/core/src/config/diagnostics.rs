@@ -0,0 +1,455 @@
+//! Located diagnostics for `config.toml`.
+//!
+//! Serde silently skips keys it does not know, so a typo like `modle = "o3"`
+//! used to be ignored. This module re-reads the user's config file and reports
+//! unknown keys (with the closest valid key as a suggestion), deprecated
+//! options and type errors as `file:line:column` messages.
+
+use super::CONFIG_TOML_FILE;
+use super::ConfigToml;
+use serde::Deserialize;
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::de::IntoDeserializer;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use toml::Value as TomlValue;
+
+/// Options that still parse (or used to) but no longer do what they say.
+const DEPRECATED_KEYS: &[(&str, &str)] = &[
+    ("request_max_retries", "set it inside the `[model_providers.<id>]` table instead"),
+    ("stream_max_retries", "set it inside the `[model_providers.<id>]` table instead"),
+    ("stream_idle_timeout_ms", "set it inside the `[model_providers.<id>]` table instead"),
+    ("instructions", "it is ignored; use `experimental_instructions_file` or AGENTS.md"),
+    ("tui.auto_drive", "use the top-level `[auto_drive]` table instead"),
+    ("auto_drive.use_chat_model", "use the top-level `auto_drive_use_chat_model` instead"),
+    ("auto_review_enabled", "use `tui.auto_review_enabled` instead"),
+    ("history.max_bytes", "it is not enforced"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// One problem found in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub file: PathBuf,
+    /// 1-based position, when the key could be found in the file.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+            if let Some(column) = self.column {
+                write!(f, ":{column}")?;
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Check the `config.toml` in `code_home`. A missing file has no diagnostics.
+pub fn check_config_file(code_home: &Path) -> io::Result<Vec<ConfigDiagnostic>> {
+    let path = code_home.join(CONFIG_TOML_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Ok(check_config_str(&path, &contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Warnings for the config that was just loaded, also written to the log.
+pub(crate) fn config_file_warnings(code_home: &Path) -> Vec<ConfigDiagnostic> {
+    let diagnostics = match check_config_file(code_home) {
+        Ok(diagnostics) => diagnostics,
+        Err(err) => {
+            tracing::debug!("skipping config.toml diagnostics: {err}");
+            return Vec::new();
+        }
+    };
+    diagnostics
+        .into_iter()
+        .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Warning)
+        .inspect(|diagnostic| tracing::warn!("{diagnostic}"))
+        .collect()
+}
+
+/// Replace a failed load's error with the located one from `config.toml`, if
+/// the file is at fault.
+pub(crate) fn locate_load_error(code_home: &Path, err: io::Error) -> io::Error {
+    let located = check_config_file(code_home).ok().and_then(|diagnostics| {
+        diagnostics
+            .into_iter()
+            .find(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+    });
+    match located {
+        Some(diagnostic) => io::Error::new(io::ErrorKind::InvalidData, diagnostic.to_string()),
+        None => err,
+    }
+}
+
+fn check_config_str(file: &Path, contents: &str) -> Vec<ConfigDiagnostic> {
+    let value: TomlValue = match toml::from_str(contents) {
+        Ok(value) => value,
+        Err(err) => return vec![toml_error(file, contents, &err)],
+    };
+    let mut diagnostics = Vec::new();
+    if let Err(err) = toml::from_str::<ConfigToml>(contents) {
+        diagnostics.push(toml_error(file, contents, &err));
+    }
+
+    let locator = KeyLocator::new(contents);
+    let warning = |path: &[String], message: String| {
+        let position = locator.locate(path);
+        ConfigDiagnostic {
+            severity: DiagnosticSeverity::Warning,
+            file: file.to_path_buf(),
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            message,
+        }
+    };
+
+    for (key, hint) in DEPRECATED_KEYS {
+        let path: Vec<String> = key.split('.').map(str::to_string).collect();
+        if lookup(&value, &path).is_some() {
+            diagnostics.push(warning(&path, format!("`{key}` is deprecated; {hint}")));
+        }
+    }
+
+    let mut ignored: Vec<Vec<String>> = Vec::new();
+    if let Ok(json) = serde_json::to_value(&value) {
+        let _: Result<ConfigToml, _> = serde_ignored::deserialize(json, |path| {
+            // Option and newtype wrappers show up as `?` segments.
+            ignored.push(
+                path.to_string()
+                    .split('.')
+                    .filter(|segment| *segment != "?")
+                    .map(str::to_string)
+                    .collect(),
+            );
+        });
+    }
+    for path in ignored {
+        let key = path.join(".");
+        if DEPRECATED_KEYS.iter().any(|(deprecated, _)| *deprecated == key) {
+            continue;
+        }
+        let Some((last, parent)) = path.split_last() else {
+            continue;
+        };
+        let message = match suggest(last, known_fields(parent)) {
+            Some(field) => {
+                let mut suggestion = parent.to_vec();
+                suggestion.push(field.to_string());
+                format!("unknown key `{key}`; did you mean `{}`?", suggestion.join("."))
+            }
+            None => format!("unknown key `{key}` is ignored"),
+        };
+        diagnostics.push(warning(&path, message));
+    }
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    diagnostics
+}
+
+fn toml_error(file: &Path, contents: &str, err: &toml::de::Error) -> ConfigDiagnostic {
+    let position = err.span().map(|span| line_column(contents, span.start));
+    ConfigDiagnostic {
+        severity: DiagnosticSeverity::Error,
+        file: file.to_path_buf(),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+        message: err.message().trim().to_string(),
+    }
+}
+
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = contents.get(..offset).unwrap_or(contents);
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |line| line.chars().count()) + 1;
+    (line, column)
+}
+
+fn lookup<'a>(value: &'a TomlValue, path: &[String]) -> Option<&'a TomlValue> {
+    path.iter().try_fold(value, |value, segment| value.get(segment.as_str()))
+}
+
+/// The closest field to `key`, allowing about one typo per three characters.
+fn suggest(key: &str, fields: &[&'static str]) -> Option<&'static str> {
+    let max_distance = (key.chars().count() / 3).max(1);
+    fields
+        .iter()
+        .map(|field| (edit_distance(key, field), *field))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| field)
+}
+
+/// Optimal string alignment distance: edits plus adjacent transpositions.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Maps dotted keys to where they are defined. Understands table headers,
+/// arrays of tables and dotted keys; anything inside an inline table or a
+/// multi-line value resolves to the line of its enclosing key.
+struct KeyLocator {
+    entries: Vec<(Vec<String>, usize, usize)>,
+}
+
+impl KeyLocator {
+    fn new(contents: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut table: Vec<String> = Vec::new();
+        let mut array_lengths: std::collections::HashMap<String, usize> = Default::default();
+        for (index, line) in contents.lines().enumerate() {
+            let trimmed = line.trim_start();
+            let column = line.len() - trimmed.len() + 1;
+            let line_number = index + 1;
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(rest) = trimmed.strip_prefix("[[") {
+                let Some((header, _)) = rest.split_once("]]") else {
+                    continue;
+                };
+                table = split_key(header);
+                let length = array_lengths.entry(table.join(".")).or_default();
+                table.push(length.to_string());
+                *length += 1;
+                entries.push((table.clone(), line_number, column));
+            } else if let Some(rest) = trimmed.strip_prefix('[') {
+                let Some((header, _)) = rest.split_once(']') else {
+                    continue;
+                };
+                table = split_key(header);
+                entries.push((table.clone(), line_number, column));
+            } else if let Some((key, _)) = trimmed.split_once('=') {
+                let mut path = table.clone();
+                path.extend(split_key(key));
+                entries.push((path, line_number, column));
+            }
+        }
+        Self { entries }
+    }
+
+    fn locate(&self, path: &[String]) -> Option<(usize, usize)> {
+        self.entries
+            .iter()
+            .find(|(entry, _, _)| entry.starts_with(path))
+            .map(|(_, line, column)| (*line, *column))
+    }
+}
+
+/// Split a TOML key on dots that are not inside quotes.
+fn split_key(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for ch in key.chars() {
+        match (quote, ch) {
+            (Some(open), ch) if ch == open => quote = None,
+            (Some(_), ch) => current.push(ch),
+            (None, '"' | '\'') => quote = Some(ch),
+            (None, '.') => segments.push(std::mem::take(&mut current).trim().to_string()),
+            (None, ch) => current.push(ch),
+        }
+    }
+    segments.push(current.trim().to_string());
+    segments
+}
+
+/// Field names of the struct at `path` inside [`ConfigToml`], learned from
+/// serde's `deserialize_struct` calls so they never drift from the types.
+fn known_fields(path: &[String]) -> &'static [&'static str] {
+    let found = Cell::new(None);
+    let _ = ConfigToml::deserialize(FieldProbe { path, found: &found });
+    found.get().unwrap_or(&[])
+}
+
+struct FieldProbe<'a> {
+    path: &'a [String],
+    found: &'a Cell<Option<&'static [&'static str]>>,
+}
+
+impl FieldProbe<'_> {
+    fn descend<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, de::value::Error> {
+        let Some((key, rest)) = self.path.split_first() else {
+            return Err(de::Error::custom("no fields here"));
+        };
+        let value = FieldProbe { path: rest, found: self.found };
+        if key.parse::<usize>().is_ok() {
+            visitor.visit_seq(ProbeEntry { key: None, value: Some(value) })
+        } else {
+            visitor.visit_map(ProbeEntry { key: Some(key), value: Some(value) })
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldProbe<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if self.path.is_empty() {
+            self.found.set(Some(fields));
+            return Err(de::Error::custom("found"));
+        }
+        self.descend(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.descend(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.descend(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+/// A single map entry or sequence element leading further down the probe.
+struct ProbeEntry<'a> {
+    key: Option<&'a str>,
+    value: Option<FieldProbe<'a>>,
+}
+
+impl<'de> MapAccess<'de> for ProbeEntry<'_> {
+    type Error = de::value::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.key.take() {
+            Some(key) => seed.deserialize(key.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("value already taken")),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for ProbeEntry<'_> {
+    type Error = de::value::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.value.take().map(|value| seed.deserialize(value)).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn messages(contents: &str) -> Vec<String> {
+        check_config_str(Path::new("config.toml"), contents)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn reports_typos_deprecations_and_type_errors_with_locations() {
+        let contents = r#"
+modle = "o3"
+request_max_retries = 3
+
+[tui]
+alternate_screen = true
+notificatons = true
+
+[profiles.fast]
+model_provder = "openai"
+"#;
+        assert_eq!(
+            messages(contents),
+            vec![
+                "config.toml:2:1: unknown key `modle`; did you mean `model`?",
+                "config.toml:3:1: `request_max_retries` is deprecated; set it inside the \
+                 `[model_providers.<id>]` table instead",
+                "config.toml:7:1: unknown key `tui.notificatons`; did you mean \
+                 `tui.notifications`?",
+                "config.toml:10:1: unknown key `profiles.fast.model_provder`; did you mean \
+                 `profiles.fast.model_provider`?",
+            ]
+        );
+
+        let type_error =
+            check_config_str(Path::new("config.toml"), "\n[tui]\nalternate_screen = 1\n");
+        assert_eq!(type_error.len(), 1);
+        assert_eq!(type_error[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(type_error[0].line, Some(3));
+
+        assert_eq!(messages("model = \"o3\"\n[tui]\n"), Vec::<String>::new());
+    }
+}
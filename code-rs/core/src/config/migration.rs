@@ -0,0 +1,304 @@
+//! Migration of outdated `config.toml` and auth layouts.
+//!
+//! Options that moved to a new key keep working for a while, but once their
+//! fallback is dropped an old install silently loses them. [`plan_migration`]
+//! finds what still uses an old layout: renamed keys, and a `config.toml` or
+//! `auth.json` that only exists in the legacy `~/.codex` home.
+//! [`apply_migration`] rewrites them in place after saving a timestamped
+//! backup of `config.toml`. Front-ends run [`migrate_on_startup`] before the
+//! config is loaded; the legacy `auth.json` is copied later, by
+//! [`migrate_legacy_auth`] during the load, once the credential store is
+//! known. `code config migrate` does both on demand.
+
+use super::CONFIG_TOML_FILE;
+use super::diagnostics::{ConfigDiagnostic, DiagnosticSeverity};
+use super::sources::resolve_code_path_for_read;
+use crate::auth::get_auth_file;
+use crate::auth_store;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+use toml_edit::DocumentMut;
+use toml_edit::TableLike;
+
+/// `(old, new)` key paths. The new key wins when both are set, so the old one
+/// is dropped in that case.
+const MOVED_KEYS: &[(&[&str], &[&str])] = &[
+    (&["tui", "auto_drive"], &["auto_drive"]),
+    (&["auto_drive", "use_chat_model"], &["auto_drive_use_chat_model"]),
+    (&["auto_review_enabled"], &["tui", "auto_review_enabled"]),
+];
+
+/// One rewrite made (or to be made) by a migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationChange {
+    pub file: PathBuf,
+    pub description: String,
+}
+
+impl fmt::Display for MigrationChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.file.display(), self.description)
+    }
+}
+
+/// Everything that would bring `code_home` up to the current layout.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationPlan {
+    pub changes: Vec<MigrationChange>,
+    /// New `config.toml` contents, when the file needs rewriting or copying.
+    config_contents: Option<String>,
+    /// A legacy `auth.json` to copy into `code_home`.
+    legacy_auth: Option<PathBuf>,
+}
+
+impl MigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Work out which migrations `code_home` needs without changing anything.
+/// The `auth.json` check uses the credential store selected for this process.
+pub fn plan_migration(code_home: &Path) -> io::Result<MigrationPlan> {
+    let mut plan = plan_config_migration(code_home)?;
+    if let Some((auth_path, legacy_auth)) = legacy_auth_to_copy(code_home) {
+        plan.changes.push(MigrationChange {
+            file: auth_path,
+            description: format!("copy from {}", legacy_auth.display()),
+        });
+        plan.legacy_auth = Some(legacy_auth);
+    }
+    Ok(plan)
+}
+
+fn plan_config_migration(code_home: &Path) -> io::Result<MigrationPlan> {
+    let mut plan = MigrationPlan::default();
+    let config_path = code_home.join(CONFIG_TOML_FILE);
+    let read_path = resolve_code_path_for_read(code_home, Path::new(CONFIG_TOML_FILE));
+    let contents = match std::fs::read_to_string(&read_path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err),
+    };
+    if let Some(contents) = contents {
+        let copied = read_path != config_path;
+        if copied {
+            plan.changes.push(MigrationChange {
+                file: config_path.clone(),
+                description: format!("copy from {}", read_path.display()),
+            });
+        }
+        // Syntax errors are reported by the config diagnostics instead.
+        let rewritten = contents.parse::<DocumentMut>().ok().and_then(|mut doc| {
+            let descriptions = rewrite_moved_keys(&mut doc);
+            if descriptions.is_empty() {
+                return None;
+            }
+            plan.changes.extend(descriptions.into_iter().map(|description| MigrationChange {
+                file: config_path.clone(),
+                description,
+            }));
+            Some(doc.to_string())
+        });
+        if rewritten.is_some() || copied {
+            plan.config_contents = Some(rewritten.unwrap_or(contents));
+        }
+    }
+
+    Ok(plan)
+}
+
+/// `(auth.json path, legacy auth.json)` when only the legacy home has
+/// credentials. The current store is checked, so credentials already in the
+/// keyring are never overwritten.
+fn legacy_auth_to_copy(code_home: &Path) -> Option<(PathBuf, PathBuf)> {
+    let auth_path = get_auth_file(code_home);
+    let auth_read_path = resolve_code_path_for_read(code_home, Path::new("auth.json"));
+    let missing =
+        matches!(auth_store::read(&auth_path), Err(err) if err.kind() == io::ErrorKind::NotFound);
+    (auth_read_path != auth_path && auth_read_path.is_file() && missing)
+        .then_some((auth_path, auth_read_path))
+}
+
+/// Apply `plan`. Returns where the previous `config.toml` was saved, if it was
+/// rewritten. Legacy files are copied and left in place for older versions.
+pub fn apply_migration(code_home: &Path, plan: &MigrationPlan) -> io::Result<Option<PathBuf>> {
+    let mut backup = None;
+    if let Some(contents) = &plan.config_contents {
+        std::fs::create_dir_all(code_home)?;
+        let config_path = code_home.join(CONFIG_TOML_FILE);
+        if config_path.exists() {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            let backup_path = code_home.join(format!("{CONFIG_TOML_FILE}.bak.{stamp}"));
+            std::fs::copy(&config_path, &backup_path)?;
+            backup = Some(backup_path);
+        }
+        let tmp_file = NamedTempFile::new_in(code_home)?;
+        std::fs::write(tmp_file.path(), contents)?;
+        tmp_file.persist(&config_path)?;
+    }
+    if let Some(legacy_auth) = &plan.legacy_auth {
+        let contents = std::fs::read_to_string(legacy_auth)?;
+        auth_store::write(&get_auth_file(code_home), &contents)?;
+    }
+    Ok(backup)
+}
+
+fn warning(file: PathBuf, message: String) -> ConfigDiagnostic {
+    ConfigDiagnostic {
+        severity: DiagnosticSeverity::Warning,
+        file,
+        line: None,
+        column: None,
+        message,
+    }
+}
+
+/// Bring `config.toml` up to date before the config is loaded. The changes
+/// are returned as warnings so front-ends show them next to other config
+/// issues.
+pub fn migrate_on_startup(code_home: &Path) -> Vec<ConfigDiagnostic> {
+    let config_path = code_home.join(CONFIG_TOML_FILE);
+    let result = plan_config_migration(code_home).and_then(|plan| {
+        let backup = apply_migration(code_home, &plan)?;
+        Ok((plan, backup))
+    });
+    let (plan, backup) = match result {
+        Ok(migrated) => migrated,
+        Err(err) => {
+            tracing::warn!("config migration failed: {err}");
+            let message =
+                format!("could not migrate old settings ({err}); run `code config migrate`");
+            return vec![warning(config_path, message)];
+        }
+    };
+    let mut diagnostics: Vec<ConfigDiagnostic> = plan
+        .changes
+        .into_iter()
+        .inspect(|change| tracing::info!("migrated config: {change}"))
+        .map(|change| warning(change.file, format!("migrated: {}", change.description)))
+        .collect();
+    if let Some(backup) = backup {
+        let message = format!("previous version saved as {}", backup.display());
+        diagnostics.push(warning(config_path, message));
+    }
+    diagnostics
+}
+
+/// Copy a legacy `auth.json` into `code_home`'s credential store. Called
+/// while loading the config, after [`auth_store::set_store_mode`], so a
+/// keyring install gets the copy in the keyring rather than as plaintext.
+pub(crate) fn migrate_legacy_auth(code_home: &Path) -> Vec<ConfigDiagnostic> {
+    let Some((auth_path, legacy_auth)) = legacy_auth_to_copy(code_home) else {
+        return Vec::new();
+    };
+    let copied = std::fs::read_to_string(&legacy_auth)
+        .and_then(|contents| auth_store::write(&auth_path, &contents));
+    match copied {
+        Ok(()) => {
+            tracing::info!("migrated credentials from {}", legacy_auth.display());
+            let message = format!("migrated: copy from {}", legacy_auth.display());
+            vec![warning(auth_path, message)]
+        }
+        Err(err) => {
+            tracing::warn!("credential migration failed: {err}");
+            let message = format!(
+                "could not copy {} ({err}); run `code config migrate`",
+                legacy_auth.display()
+            );
+            vec![warning(auth_path, message)]
+        }
+    }
+}
+
+fn rewrite_moved_keys(doc: &mut DocumentMut) -> Vec<String> {
+    MOVED_KEYS
+        .iter()
+        .filter_map(|(from, to)| move_key(doc, from, to))
+        .collect()
+}
+
+/// Move the item at `from` to `to`, or drop it when `to` is already set.
+fn move_key(doc: &mut DocumentMut, from: &[&str], to: &[&str]) -> Option<String> {
+    let (from_key, from_parent) = from.split_last()?;
+    let (to_key, to_parent) = to.split_last()?;
+    if !table_at(doc, from_parent)?.contains_key(from_key) {
+        return None;
+    }
+    let target_set = ensure_table(doc, to_parent)?.contains_key(to_key);
+    let item = table_at(doc, from_parent)?.remove(from_key)?;
+    let (from, to) = (from.join("."), to.join("."));
+    if target_set {
+        return Some(format!("removed `{from}`, which `{to}` overrides"));
+    }
+    ensure_table(doc, to_parent)?.insert(to_key, item);
+    Some(format!("moved `{from}` to `{to}`"))
+}
+
+fn table_at<'a>(doc: &'a mut DocumentMut, path: &[&str]) -> Option<&'a mut dyn TableLike> {
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for key in path {
+        table = table.get_mut(key)?.as_table_like_mut()?;
+    }
+    Some(table)
+}
+
+fn ensure_table<'a>(doc: &'a mut DocumentMut, path: &[&str]) -> Option<&'a mut dyn TableLike> {
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for key in path {
+        table = table.entry(key).or_insert(toml_edit::table()).as_table_like_mut()?;
+    }
+    Some(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigToml;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn moves_renamed_keys_and_keeps_a_backup() {
+        let home = TempDir::new().expect("tempdir");
+        let original = r#"model = "gpt-5.5"
+auto_review_enabled = false
+
+[tui]
+alternate_screen = false
+
+[tui.auto_drive]
+review_enabled = false
+use_chat_model = true
+"#;
+        std::fs::write(home.path().join(CONFIG_TOML_FILE), original).expect("write config");
+
+        let plan = plan_migration(home.path()).expect("plan");
+        let descriptions: Vec<&str> =
+            plan.changes.iter().map(|change| change.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "moved `tui.auto_drive` to `auto_drive`",
+                "moved `auto_drive.use_chat_model` to `auto_drive_use_chat_model`",
+                "moved `auto_review_enabled` to `tui.auto_review_enabled`",
+            ]
+        );
+
+        let backup = apply_migration(home.path(), &plan).expect("apply").expect("backup");
+        assert_eq!(std::fs::read_to_string(backup).expect("read backup"), original);
+        let migrated = std::fs::read_to_string(home.path().join(CONFIG_TOML_FILE))
+            .expect("read config");
+        let cfg: ConfigToml = toml::from_str(&migrated).expect("parse migrated config");
+        assert_eq!(cfg.auto_drive_use_chat_model, Some(true));
+        assert!(cfg.auto_drive.is_some_and(|auto_drive| !auto_drive.review_enabled));
+        let tui = cfg.tui.expect("tui table");
+        assert!(tui.auto_drive.is_none());
+        assert!(!tui.auto_review_enabled);
+        assert!(!tui.alternate_screen);
+
+        assert!(plan_migration(home.path()).expect("plan again").is_empty());
+    }
+}
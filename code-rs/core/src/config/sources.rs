@@ -1439,6 +1439,20 @@ pub fn list_mcp_servers(code_home: &Path) -> anyhow::Result<(
                         tool_timeout_sec,
                         enabled_tools: parse_string_array("enabled_tools"),
                         disabled_tools: parse_string_array("disabled_tools"),
+                        approval_required_tools: parse_string_array("approval_required_tools"),
+                        tool_timeouts_sec: t
+                            .get("tool_timeouts_sec")
+                            .and_then(|v| v.as_table())
+                            .map(|tbl| {
+                                tbl.iter()
+                                    .filter_map(|(tool, v)| {
+                                        let secs = v
+                                            .as_float()
+                                            .or_else(|| v.as_integer().map(|i| i as f64))?;
+                                        Some((tool.to_string(), secs))
+                                    })
+                                    .collect()
+                            }),
                     },
                 ));
             }
@@ -1498,6 +1512,8 @@ pub fn add_mcp_server(
         tool_timeout_sec,
         enabled_tools,
         disabled_tools,
+        approval_required_tools,
+        tool_timeouts_sec,
     } = cfg;
 
     // Build table for this server
@@ -1588,6 +1604,29 @@ pub fn add_mcp_server(
             TomlItem::Value(toml_edit::Value::Array(arr)),
         );
     }
+    if let Some(approval_required_tools) = approval_required_tools
+        && !approval_required_tools.is_empty()
+    {
+        let mut arr = toml_edit::Array::new();
+        for tool in approval_required_tools {
+            arr.push(tool);
+        }
+        server_tbl.insert(
+            "approval_required_tools",
+            TomlItem::Value(toml_edit::Value::Array(arr)),
+        );
+    }
+    if let Some(tool_timeouts_sec) = tool_timeouts_sec
+        && !tool_timeouts_sec.is_empty()
+    {
+        let mut timeouts_tbl = toml_edit::Table::new();
+        let mut entries: Vec<_> = tool_timeouts_sec.into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        for (tool, secs) in entries {
+            timeouts_tbl.insert(&tool, toml_edit::value(secs));
+        }
+        server_tbl.insert("tool_timeouts_sec", TomlItem::Table(timeouts_tbl));
+    }
 
     // Write into enabled table
     tbl.insert(name, TomlItem::Table(server_tbl));
@@ -6,8 +6,10 @@ use crate::config_types::ReasoningSummary;
 use crate::config_types::ContextMode;
 use crate::config_types::ServiceTier;
 use crate::config_types::TextVerbosity;
+use crate::config_types::UsageLimits;
 use crate::config_types::Personality;
 use crate::protocol::AskForApproval;
+use code_protocol::config_types::SandboxMode;
 
 /// Collection of common configuration options that a user can define as a unit
 /// in `config.toml`.
@@ -25,6 +27,7 @@ pub struct ConfigProfile {
     /// [`ModelProviderInfo`] to use.
     pub model_provider: Option<String>,
     pub approval_policy: Option<AskForApproval>,
+    pub sandbox_mode: Option<SandboxMode>,
     pub disable_response_storage: Option<bool>,
     pub model_reasoning_effort: Option<ReasoningEffort>,
     pub preferred_model_reasoning_effort: Option<ReasoningEffort>,
@@ -53,4 +56,7 @@ pub struct ConfigProfile {
     /// When true, fall back to an API key account only if every connected
     /// ChatGPT account is rate/usage limited.
     pub api_key_fallback_on_all_accounts_limited: Option<bool>,
+
+    /// Usage limits for this profile; replaces the top-level `[usage_limits]`.
+    pub usage_limits: Option<UsageLimits>,
 }
@@ -136,6 +136,26 @@ pub struct OtelConfigToml {
     pub log_user_prompt: Option<bool>,
     pub environment: Option<String>,
     pub exporter: Option<OtelExporterKind>,
+    pub metrics: Option<OtelMetricsToml>,
+}
+
+/// Opt-in session metrics (`[otel.metrics]`): turn latency, tool calls,
+/// token usage and error rates.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct OtelMetricsToml {
+    /// Write each session's totals to a JSON file. Defaults to false.
+    pub json: Option<bool>,
+    /// Directory for the JSON files. Defaults to `CODE_HOME/metrics`.
+    pub json_dir: Option<PathBuf>,
+    /// Export the metrics through `[otel].exporter`. Defaults to false.
+    pub otlp: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OtelMetricsConfig {
+    /// Set when JSON metrics files are enabled.
+    pub json_dir: Option<PathBuf>,
+    pub otlp: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -143,6 +163,7 @@ pub struct OtelConfig {
     pub log_user_prompt: bool,
     pub environment: String,
     pub exporter: OtelExporterKind,
+    pub metrics: OtelMetricsConfig,
 }
 
 impl Default for OtelConfig {
@@ -151,10 +172,33 @@ impl Default for OtelConfig {
             log_user_prompt: false,
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
+            metrics: OtelMetricsConfig::default(),
         }
     }
 }
 
+/// Local usage limits (`[usage_limits]`, or `[profiles.<name>.usage_limits]`).
+/// Usage is tracked per profile across sessions in `CODE_HOME/usage/quota.json`;
+/// days start at local midnight and weeks on Monday.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct UsageLimits {
+    pub daily_tokens: Option<u64>,
+    pub weekly_tokens: Option<u64>,
+    /// Estimated cost in US dollars.
+    pub daily_cost_usd: Option<f64>,
+    /// Estimated cost in US dollars.
+    pub weekly_cost_usd: Option<f64>,
+}
+
+impl UsageLimits {
+    pub fn is_empty(&self) -> bool {
+        self.daily_tokens.is_none()
+            && self.weekly_tokens.is_none()
+            && self.daily_cost_usd.is_none()
+            && self.weekly_cost_usd.is_none()
+    }
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct McpServerConfig {
     #[serde(flatten)]
@@ -172,13 +216,25 @@ pub struct McpServerConfig {
     #[serde(default, with = "option_duration_secs")]
     pub tool_timeout_sec: Option<Duration>,
 
-    /// Explicit allow-list of tools exposed from this server.
+    /// Explicit allow-list of tools exposed from this server. Entries may be
+    /// exact names or glob patterns (`*`, `?`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enabled_tools: Option<Vec<String>>,
 
-    /// Explicit deny-list of tools. Applied after `enabled_tools`.
+    /// Explicit deny-list of tools (names or glob patterns). Applied after
+    /// `enabled_tools`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disabled_tools: Option<Vec<String>>,
+
+    /// Tools (names or glob patterns) that always prompt for approval before
+    /// each call, regardless of the session approval policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_required_tools: Option<Vec<String>>,
+
+    /// Per-tool timeout overrides in seconds, keyed by tool name or glob.
+    /// Takes precedence over `tool_timeout_sec`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_timeouts_sec: Option<HashMap<String, f64>>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -213,6 +269,10 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             enabled_tools: Option<Vec<String>>,
             #[serde(default)]
             disabled_tools: Option<Vec<String>>,
+            #[serde(default)]
+            approval_required_tools: Option<Vec<String>>,
+            #[serde(default)]
+            tool_timeouts_sec: Option<HashMap<String, f64>>,
         }
 
         let raw = RawMcpServerConfig::deserialize(deserializer)?;
@@ -227,6 +287,17 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         };
         let enabled_tools = raw.enabled_tools.clone();
         let disabled_tools = raw.disabled_tools.clone();
+        let approval_required_tools = raw.approval_required_tools.clone();
+        if let Some(timeouts) = &raw.tool_timeouts_sec
+            && let Some((tool, secs)) = timeouts
+                .iter()
+                .find(|(_, secs)| !secs.is_finite() || **secs <= 0.0)
+        {
+            return Err(SerdeError::custom(format!(
+                "tool_timeouts_sec.{tool} must be a positive number of seconds, got {secs}"
+            )));
+        }
+        let tool_timeouts_sec = raw.tool_timeouts_sec.clone();
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -302,10 +373,38 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             tool_timeout_sec: raw.tool_timeout_sec,
             enabled_tools,
             disabled_tools,
+            approval_required_tools,
+            tool_timeouts_sec,
         })
     }
 }
 
+/// Local speech-to-text applied to audio returned by MCP tools.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct McpAudioTranscriptionConfig {
+    /// Program and arguments. `{input}` is replaced with the path of a
+    /// temporary file holding the audio; the transcript is read from stdout.
+    pub command: Vec<String>,
+    /// Seconds to wait for the command before giving up (default: 120).
+    #[serde(default)]
+    pub timeout_sec: Option<u64>,
+}
+
+/// How MCP tool names are exposed to the model when several servers are
+/// combined.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum McpToolNaming {
+    /// Always expose `server__tool`.
+    #[default]
+    Qualified,
+    /// Expose the bare tool name when a single server provides it. Tools
+    /// that share a name are exposed as `server__tool`, except the one from
+    /// the first server listed in `mcp_tool_priority`, which keeps the bare
+    /// name.
+    OnConflict,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged, deny_unknown_fields, rename_all = "snake_case")]
 pub enum McpServerTransportConfig {
@@ -366,6 +465,120 @@ mod option_duration_secs {
     }
 }
 
+/// `[terminal_panes]`: commands started in a tmux or zellij pane.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TerminalPanesConfig {
+    /// Command prefixes (`npm run dev`, `tail -f`) that run in a new pane of
+    /// the surrounding multiplexer instead of inside the exec tool.
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+/// `[lsp]`: language servers asked for diagnostics after each patch.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LspConfig {
+    /// Start language servers for patched files and report the errors and
+    /// warnings the patch introduced. Off by default: servers such as
+    /// rust-analyzer run build scripts outside the sandbox.
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long to wait for diagnostics after a patch, in milliseconds.
+    #[serde(default = "default_lsp_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Servers to use besides the built-in ones. A server with the name of a
+    /// built-in one replaces it.
+    #[serde(default)]
+    pub servers: Vec<LspServerConfig>,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout_ms: default_lsp_timeout_ms(),
+            servers: Vec::new(),
+        }
+    }
+}
+
+fn default_lsp_timeout_ms() -> u64 {
+    5_000
+}
+
+/// One `[[lsp.servers]]` entry.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct LspServerConfig {
+    pub name: String,
+    /// Program and arguments; the server must speak LSP over stdio.
+    pub command: Vec<String>,
+    /// File extensions (without the dot) the server handles.
+    pub extensions: Vec<String>,
+}
+
+/// Secret redaction for command output (`[redaction]`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RedactionToml {
+    /// Redact API keys, AWS credentials, JWTs and private keys. Defaults to true.
+    pub enabled: Option<bool>,
+    /// Extra regular expressions to redact. When a pattern has a capture group
+    /// named `secret`, only that group is replaced.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Key bindings (`[keys]`). Each action takes one chord such as `"ctrl+j"`
+/// or a list of them; unset actions keep their defaults.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct KeysToml {
+    /// Send the composer contents. Defaults to `enter`.
+    pub submit: Option<KeyChordsToml>,
+    /// Insert a line break. Defaults to `shift+enter` and `ctrl+j`.
+    pub newline: Option<KeyChordsToml>,
+    /// Recall the previous prompt. Defaults to `shift+up`.
+    pub history_previous: Option<KeyChordsToml>,
+    /// Recall the next prompt. Defaults to `shift+down`.
+    pub history_next: Option<KeyChordsToml>,
+    /// Approve a command or patch once. Defaults to `y`.
+    pub approve: Option<KeyChordsToml>,
+    /// Always allow the exact command in this project. Defaults to `a`.
+    pub approve_always: Option<KeyChordsToml>,
+    /// Always allow commands with the same prefix. Defaults to `p`.
+    pub approve_prefix: Option<KeyChordsToml>,
+    /// Decline and give feedback. Defaults to `n`.
+    pub deny: Option<KeyChordsToml>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum KeyChordsToml {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChordsToml {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            KeyChordsToml::One(chord) => std::slice::from_ref(chord),
+            KeyChordsToml::Many(chords) => chords,
+        }
+    }
+}
+
 /// Configuration for commands that require an explicit `confirm:` prefix.
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "kebab-case")]
@@ -770,6 +983,10 @@ pub struct History {
     /// If set, the maximum size of the history file in bytes.
     /// TODO(mbolin): Not currently honored.
     pub max_bytes: Option<usize>,
+
+    /// Do not write prompts marked sensitive (typed with a leading space).
+    #[serde(default)]
+    pub exclude_sensitive: bool,
 }
 
 #[derive(Deserialize, Debug, Copy, Clone, PartialEq, Default)]
@@ -862,6 +1079,22 @@ pub struct Tui {
     /// Run a background `/review` after turns that modify code.
     #[serde(default = "default_true")]
     pub auto_review_enabled: bool,
+
+    /// Plain, linear output for terminal screen readers: no animations, and
+    /// labeled text instead of gutter icons and borders. Implies
+    /// `alternate_screen = false`.
+    #[serde(default)]
+    pub screen_reader: bool,
+
+    /// Show turn progress on the terminal tab or taskbar with `OSC 9;4`.
+    /// Unset means only terminals known to support it (Windows Terminal,
+    /// ConEmu, Ghostty) get the sequence.
+    #[serde(default)]
+    pub terminal_progress: Option<bool>,
+
+    /// Segments shown in the status bar above the conversation.
+    #[serde(default)]
+    pub status_bar: StatusBarConfig,
 }
 
 // Important: Provide a manual Default so that when no config file exists and we
@@ -883,6 +1116,56 @@ impl Default for Tui {
             alternate_screen: true,
             review_auto_resolve: true,
             auto_review_enabled: true,
+            screen_reader: false,
+            terminal_progress: None,
+            status_bar: StatusBarConfig::default(),
+        }
+    }
+}
+
+/// One item of the status bar.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusBarSegment {
+    /// Model name with reasoning effort and service tier.
+    Model,
+    /// Working directory, shortened to its last component when space is tight.
+    Directory,
+    /// Current git branch; hidden outside a repository.
+    Branch,
+    /// Tokens used this session.
+    Tokens,
+    /// Estimated API cost of this session.
+    Cost,
+    /// Sandbox policy in effect.
+    Sandbox,
+    /// Number of agents still running; hidden when there are none.
+    Agents,
+    /// Local time of day.
+    Clock,
+}
+
+/// `[tui.status_bar]`: which segments the status bar shows, left to right.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct StatusBarConfig {
+    /// Segments in display order. Segments not listed are hidden; an empty
+    /// list leaves only the title.
+    #[serde(default = "default_status_bar_segments")]
+    pub segments: Vec<StatusBarSegment>,
+}
+
+fn default_status_bar_segments() -> Vec<StatusBarSegment> {
+    vec![
+        StatusBarSegment::Model,
+        StatusBarSegment::Directory,
+        StatusBarSegment::Branch,
+    ]
+}
+
+impl Default for StatusBarConfig {
+    fn default() -> Self {
+        Self {
+            segments: default_status_bar_segments(),
         }
     }
 }
@@ -1322,6 +1605,37 @@ pub struct ThemeColors {
     pub progress: Option<String>,
 }
 
+/// Where sign-in credentials (`auth.json`, `auth_accounts.json`) are kept.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthCredentialsStoreMode {
+    /// Plaintext files in `CODE_HOME`.
+    #[default]
+    File,
+    /// The OS keyring; fail when it is unavailable.
+    Keyring,
+    /// The OS keyring when available, files otherwise.
+    Auto,
+}
+
+/// `[network]`: explicit proxy for outbound traffic. Without a `proxy`, the
+/// system settings (`HTTPS_PROXY`, `ALL_PROXY`, `NO_PROXY`, ...) apply.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct NetworkConfig {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` proxy URL.
+    pub proxy: Option<String>,
+    pub proxy_username: Option<String>,
+    /// Environment variable holding the proxy password.
+    pub proxy_password_env: Option<String>,
+    /// Hosts, domains (`.corp.example`) or CIDR ranges reached directly.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+    /// Model providers (ids from `model_providers`) whose `base_url` host is
+    /// reached directly, e.g. an internal gateway.
+    #[serde(default)]
+    pub no_proxy_providers: Vec<String>,
+}
+
 /// Browser configuration for integrated screenshot capabilities.
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct BrowserConfig {
@@ -1345,6 +1659,91 @@ pub struct BrowserConfig {
 
     #[serde(default)]
     pub format: Option<BrowserImageFormat>,
+
+    /// Named persistent profile (`<code_home>/browser/<name>`) for the
+    /// internal browser. When unset, each launch uses a throwaway profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    /// Domains the browser and fetch tools may visit (subdomains included).
+    /// When non-empty, every other domain is refused or, with
+    /// `prompt_on_new_domain`, put to the user.
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+
+    /// Domains the browser and fetch tools must never visit. Takes precedence
+    /// over `allowed_domains`.
+    #[serde(default)]
+    pub denied_domains: Vec<String>,
+
+    /// Ask before visiting a domain that is not in `allowed_domains`.
+    #[serde(default)]
+    pub prompt_on_new_domain: bool,
+
+    /// Run the internal browser without a window (`true`) or visibly
+    /// (`false`). Can be switched per session with `/browser headless|headful`.
+    #[serde(default)]
+    pub headless: Option<bool>,
+
+    /// Attach to an already-running Chrome over CDP instead of launching one.
+    #[serde(default)]
+    pub connect: Option<BrowserConnectConfig>,
+}
+
+/// `[browser.connect]`: the DevTools endpoint of a Chrome started with
+/// `--remote-debugging-port`. `ws` wins over `port` when both are set.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct BrowserConnectConfig {
+    /// Full DevTools WebSocket URL, e.g. `ws://127.0.0.1:9222/devtools/browser/<id>`.
+    #[serde(default)]
+    pub ws: Option<String>,
+
+    /// Remote debugging port to discover the WebSocket URL from.
+    #[serde(default)]
+    pub port: Option<u16>,
+
+    /// Host for `port`; defaults to 127.0.0.1.
+    #[serde(default)]
+    pub host: Option<String>,
+}
+
+/// Search API used by the function-style `web_search` tool.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum WebSearchProvider {
+    Brave,
+    Searxng,
+    Bing,
+    #[serde(alias = "google_cse")]
+    Google,
+}
+
+/// `[tools.web_search_backend]`: answer `web_search` with a search API of
+/// your choice instead of the model provider's built-in search.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebSearchBackendConfig {
+    pub provider: WebSearchProvider,
+
+    /// API key (Brave, Bing, Google). Prefer `env_key` to keep it out of the file.
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Environment variable holding the API key.
+    #[serde(default)]
+    pub env_key: Option<String>,
+
+    /// Endpoint override; required for SearXNG (the instance URL).
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Programmable Search Engine ID (`cx`) for Google.
+    #[serde(default)]
+    pub search_engine_id: Option<String>,
+
+    /// Results returned when the model does not ask for a count.
+    #[serde(default)]
+    pub max_results: Option<usize>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -186,6 +186,33 @@ fn sanitize_user_agent(candidate: String, fallback: &str) -> String {
 
 /// Create a reqwest client with default `originator` and `User-Agent` headers set.
 pub fn create_client(originator: &str) -> reqwest::Client {
+    client_builder(originator)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Like [`create_client`], additionally applying a provider's `tls` settings.
+/// Invalid settings are reported at config load, so here they only log.
+pub fn create_client_with_tls(
+    originator: &str,
+    tls: Option<&crate::model_provider_info::ProviderTlsConfig>,
+) -> reqwest::Client {
+    let Some(tls) = tls else {
+        return create_client(originator);
+    };
+    match crate::http_client::apply_provider_tls(client_builder(originator), tls) {
+        Ok(builder) => builder.build().unwrap_or_else(|err| {
+            tracing::warn!("provider tls settings rejected: {err}");
+            create_client(originator)
+        }),
+        Err(err) => {
+            tracing::warn!("ignoring provider tls settings: {err}");
+            create_client(originator)
+        }
+    }
+}
+
+fn client_builder(originator: &str) -> reqwest::ClientBuilder {
     use reqwest::header::HeaderMap;
     use reqwest::header::HeaderValue;
 
@@ -204,9 +231,10 @@ pub fn create_client(originator: &str) -> reqwest::Client {
         .default_headers(headers);
     if is_sandboxed() {
         builder = builder.no_proxy();
+    } else {
+        builder = crate::http_client::apply_proxy(builder);
     }
-
-    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+    builder
 }
 
 fn is_sandboxed() -> bool {
@@ -1,6 +1,7 @@
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io;
 use std::path::Path;
@@ -24,6 +25,7 @@ use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
 use crate::landlock::spawn_command_under_linux_sandbox;
+use crate::redaction;
 use crate::text_encoding::bytes_to_string_smart;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
@@ -225,9 +227,9 @@ pub async fn process_exec_tool_call(
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
 
-            let stdout = raw_output.stdout.from_utf8_lossy();
-            let stderr = raw_output.stderr.from_utf8_lossy();
-            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
+            let stdout = raw_output.stdout.from_utf8_lossy().redacted();
+            let stderr = raw_output.stderr.from_utf8_lossy().redacted();
+            let aggregated_output = raw_output.aggregated_output.from_utf8_lossy().redacted();
             let exec_output = ExecToolCallOutput {
                 exit_code,
                 stdout,
@@ -290,6 +292,8 @@ pub struct StreamOutput<T> {
     pub text: T,
     pub truncated_after_lines: Option<u32>,
     pub truncated_before_bytes: Option<usize>,
+    /// Secrets replaced in `text` by redaction.
+    pub redactions: usize,
 }
 #[derive(Debug)]
 struct RawExecToolCallOutput {
@@ -308,8 +312,18 @@ impl StreamOutput<String> {
             text,
             truncated_after_lines: None,
             truncated_before_bytes: None,
+            redactions: 0,
         }
     }
+
+    fn redacted(mut self) -> Self {
+        let (redacted, count) = redaction::redact_counted(&self.text);
+        if let Cow::Owned(text) = redacted {
+            self.text = text;
+        }
+        self.redactions += count;
+        self
+    }
 }
 
 impl StreamOutput<Vec<u8>> {
@@ -318,6 +332,7 @@ impl StreamOutput<Vec<u8>> {
             text: bytes_to_string_smart(&self.text),
             truncated_after_lines: self.truncated_after_lines,
             truncated_before_bytes: self.truncated_before_bytes,
+            redactions: self.redactions,
         }
     }
 }
@@ -540,6 +555,7 @@ async fn consume_truncated_output(
                 .then_some(combined_truncated_lines.max(1)),
             truncated_before_bytes: (combined_truncated_bytes > 0)
                 .then_some(combined_truncated_bytes),
+            redactions: 0,
         }
     });
 
@@ -636,11 +652,13 @@ async fn consume_truncated_output(
                 text: Vec::new(),
                 truncated_after_lines: None,
                 truncated_before_bytes: None,
+                redactions: 0,
             },
             StreamOutput {
                 text: Vec::new(),
                 truncated_after_lines: None,
                 truncated_before_bytes: None,
+                redactions: 0,
             },
         )
     } else {
@@ -654,6 +672,7 @@ async fn consume_truncated_output(
             text: Vec::new(),
             truncated_after_lines: None,
             truncated_before_bytes: None,
+            redactions: 0,
         }
     } else {
         combined_handle.await.map_err(CodexErr::from)?
@@ -696,11 +715,15 @@ async fn consume_truncated_output(
     })
 }
 
+/// Emits the buffered output. Unless `flush_all` is set (or the buffer has
+/// grown past twice the flush size), only complete lines go out so that
+/// redaction sees each secret whole; the rest waits for more output.
 async fn emit_pending_delta(
     stream: &StdoutStream,
     is_stderr: bool,
     pending_delta: &mut Vec<u8>,
     emitted_deltas: &mut usize,
+    flush_all: bool,
 ) {
     if pending_delta.is_empty() || *emitted_deltas >= MAX_EXEC_OUTPUT_DELTAS_PER_CALL {
         if *emitted_deltas >= MAX_EXEC_OUTPUT_DELTAS_PER_CALL {
@@ -709,7 +732,23 @@ async fn emit_pending_delta(
         return;
     }
 
-    let chunk = std::mem::take(pending_delta);
+    let split = if flush_all || pending_delta.len() >= EXEC_DELTA_FLUSH_BYTES * 2 {
+        pending_delta.len()
+    } else {
+        redaction::streamable_len(pending_delta)
+    };
+    if split == 0 {
+        return;
+    }
+    let rest = pending_delta.split_off(split);
+    let mut chunk = std::mem::replace(pending_delta, rest);
+    let redacted = match redaction::redact(&String::from_utf8_lossy(&chunk)) {
+        Cow::Owned(redacted) => Some(redacted),
+        Cow::Borrowed(_) => None,
+    };
+    if let Some(redacted) = redacted {
+        chunk = redacted.into_bytes();
+    }
     let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
         call_id: stream.call_id.clone(),
         stream: if is_stderr {
@@ -773,7 +812,7 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
                         pending_delta.clear();
                         flush_deadline = None;
                     } else if pending_delta.len() >= EXEC_DELTA_FLUSH_BYTES {
-                        emit_pending_delta(stream, is_stderr, &mut pending_delta, &mut emitted_deltas).await;
+                        emit_pending_delta(stream, is_stderr, &mut pending_delta, &mut emitted_deltas, false).await;
                         flush_deadline = None;
                     } else if flush_deadline.is_none() {
                         flush_deadline = Some(Box::pin(tokio::time::sleep(EXEC_DELTA_FLUSH_INTERVAL)));
@@ -803,7 +842,7 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
                 }
             }, if flush_deadline.is_some() => {
                 if let Some(stream) = &stream {
-                    emit_pending_delta(stream, is_stderr, &mut pending_delta, &mut emitted_deltas).await;
+                    emit_pending_delta(stream, is_stderr, &mut pending_delta, &mut emitted_deltas, false).await;
                 } else {
                     pending_delta.clear();
                 }
@@ -814,13 +853,14 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
 
     // Emit any remaining buffered delta
     if let Some(stream) = &stream {
-        emit_pending_delta(stream, is_stderr, &mut pending_delta, &mut emitted_deltas).await;
+        emit_pending_delta(stream, is_stderr, &mut pending_delta, &mut emitted_deltas, true).await;
     }
 
     Ok(StreamOutput {
         text: buf,
         truncated_after_lines: truncated.then_some(truncated_lines.max(1)),
         truncated_before_bytes: (truncated_bytes > 0).then_some(truncated_bytes),
+        redactions: 0,
     })
 }
 
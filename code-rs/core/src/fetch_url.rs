@@ -0,0 +1,447 @@
+//! Plain HTTP fetches for the `fetch_url` tool.
+//!
+//! No browser is involved: one request (plus redirects), a capped body and an
+//! in-memory cache revalidated with `ETag` / `Last-Modified`, so re-reading the
+//! same API docs or raw file is cheap. Redirects are limited and re-checked
+//! against the navigation policy, and the site's `robots.txt` is honoured.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use futures::StreamExt;
+use url::Url;
+
+use crate::navigation_policy::NavigationPolicy;
+
+pub(crate) const DEFAULT_MAX_BYTES: usize = 2 * 1024 * 1024;
+pub(crate) const MAX_BYTES_LIMIT: usize = 10 * 1024 * 1024;
+pub(crate) const DEFAULT_MAX_REDIRECTS: usize = 5;
+pub(crate) const MAX_REDIRECTS_LIMIT: usize = 10;
+pub(crate) const DEFAULT_TIMEOUT_MS: u64 = 15_000;
+pub(crate) const MIN_TIMEOUT_MS: u64 = 1_000;
+pub(crate) const MAX_TIMEOUT_MS: u64 = 120_000;
+
+/// Product token matched against `User-agent` lines in `robots.txt`.
+const ROBOTS_AGENT: &str = "code";
+const ROBOTS_TIMEOUT: Duration = Duration::from_secs(5);
+const CACHE_ENTRIES: usize = 64;
+
+#[derive(Debug, Clone)]
+pub(crate) struct FetchRequest {
+    pub(crate) url: Url,
+    pub(crate) max_bytes: usize,
+    pub(crate) max_redirects: usize,
+    pub(crate) timeout: Duration,
+    pub(crate) user_agent: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FetchedBody {
+    pub(crate) final_url: String,
+    pub(crate) status: u16,
+    pub(crate) content_type: String,
+    pub(crate) body: Vec<u8>,
+    /// The body hit `max_bytes` and was cut short.
+    pub(crate) truncated: bool,
+    /// Served from the cache after the server answered `304 Not Modified`.
+    pub(crate) cached: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BodyKind {
+    Html,
+    Json,
+    Markdown,
+    Pdf,
+    Text,
+    Binary,
+}
+
+struct CacheEntry {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    response: FetchedBody,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<String, CacheEntry>,
+    clock: u64,
+}
+
+static CACHE: LazyLock<Mutex<Cache>> = LazyLock::new(|| Mutex::new(Cache::default()));
+/// `robots.txt` bodies by origin; `None` when the site has none.
+static ROBOTS: LazyLock<Mutex<HashMap<String, Option<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) async fn fetch(
+    request: &FetchRequest,
+    policy: &NavigationPolicy,
+) -> Result<FetchedBody, String> {
+    let client = client(request, policy)?;
+    if !robots_allowed(&client, &request.url).await {
+        return Err(format!(
+            "{} disallows this path for automated clients in robots.txt",
+            request.url.origin().ascii_serialization()
+        ));
+    }
+
+    let key = request.url.as_str().to_string();
+    let validators = CACHE.lock().ok().and_then(|cache| {
+        cache
+            .entries
+            .get(&key)
+            .map(|entry| (entry.etag.clone(), entry.last_modified.clone()))
+    });
+    let mut builder = client.get(request.url.clone());
+    if let Some((etag, last_modified)) = &validators {
+        if let Some(etag) = etag {
+            builder = builder.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = last_modified {
+            builder = builder.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let response = builder.send().await.map_err(describe_error)?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED
+        && let Some(cached) = cache_hit(&key)
+    {
+        return Ok(cached);
+    }
+
+    let status = response.status().as_u16();
+    let final_url = response.url().to_string();
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE).unwrap_or_default();
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+
+    let mut body = Vec::new();
+    let mut truncated = false;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|err| format!("failed to read response body: {err}"))?;
+        let room = request.max_bytes.saturating_sub(body.len());
+        if chunk.len() > room {
+            body.extend_from_slice(&chunk[..room]);
+            truncated = true;
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let fetched = FetchedBody {
+        final_url,
+        status,
+        content_type,
+        body,
+        truncated,
+        cached: false,
+    };
+    if (200..300).contains(&status) && !truncated && (etag.is_some() || last_modified.is_some()) {
+        store(key, etag, last_modified, fetched.clone());
+    }
+    Ok(fetched)
+}
+
+fn client(request: &FetchRequest, policy: &NavigationPolicy) -> Result<reqwest::Client, String> {
+    let limit = request.max_redirects;
+    let origin_host = request.url.host_str().map(str::to_ascii_lowercase);
+    let redirects = policy.clone().redirect_policy(origin_host.into_iter().collect(), limit);
+    crate::http_client::apply_proxy(reqwest::Client::builder())
+        .user_agent(request.user_agent.clone())
+        .timeout(request.timeout)
+        .redirect(redirects)
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {err}"))
+}
+
+pub(crate) fn describe_error(err: reqwest::Error) -> String {
+    if err.is_redirect() {
+        // Surface the policy's own message rather than reqwest's wrapper.
+        let mut source = std::error::Error::source(&err);
+        while let Some(inner) = source {
+            if inner.source().is_none() {
+                return format!("redirect refused: {inner}");
+            }
+            source = inner.source();
+        }
+    }
+    if err.is_timeout() {
+        return "request timed out".to_string();
+    }
+    format!("request failed: {err}")
+}
+
+fn cache_hit(key: &str) -> Option<FetchedBody> {
+    let mut cache = CACHE.lock().ok()?;
+    cache.clock += 1;
+    let clock = cache.clock;
+    let entry = cache.entries.get_mut(key)?;
+    entry.last_used = clock;
+    let mut response = entry.response.clone();
+    response.cached = true;
+    Some(response)
+}
+
+fn store(key: String, etag: Option<String>, last_modified: Option<String>, response: FetchedBody) {
+    let Ok(mut cache) = CACHE.lock() else {
+        return;
+    };
+    cache.clock += 1;
+    let last_used = cache.clock;
+    if cache.entries.len() >= CACHE_ENTRIES && !cache.entries.contains_key(&key) {
+        let oldest = cache
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            cache.entries.remove(&oldest);
+        }
+    }
+    cache.entries.insert(
+        key,
+        CacheEntry {
+            etag,
+            last_modified,
+            response,
+            last_used,
+        },
+    );
+}
+
+/// Local development servers are not expected to publish `robots.txt`.
+fn is_local(url: &Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => domain == "localhost" || domain.ends_with(".localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback() || ip.is_private(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => true,
+    }
+}
+
+async fn robots_allowed(client: &reqwest::Client, url: &Url) -> bool {
+    if is_local(url) || url.path() == "/robots.txt" {
+        return true;
+    }
+    let origin = url.origin().ascii_serialization();
+    let known = ROBOTS.lock().ok().and_then(|robots| robots.get(&origin).cloned());
+    let robots = match known {
+        Some(robots) => robots,
+        None => {
+            // Missing or unreachable robots.txt means no restrictions.
+            let fetched = match client
+                .get(format!("{origin}/robots.txt"))
+                .timeout(ROBOTS_TIMEOUT)
+                .send()
+                .await
+            {
+                Ok(response) if response.status().is_success() => response.text().await.ok(),
+                _ => None,
+            };
+            if let Ok(mut robots) = ROBOTS.lock() {
+                robots.insert(origin, fetched.clone());
+            }
+            fetched
+        }
+    };
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+    robots.is_none_or(|robots| robots_allows(&robots, ROBOTS_AGENT, &path))
+}
+
+/// Whether `robots_txt` lets `agent` fetch `path`, following RFC 9309: the
+/// most specific matching group applies, within it the longest matching rule
+/// wins and `Allow` wins ties.
+pub(crate) fn robots_allows(robots_txt: &str, agent: &str, path: &str) -> bool {
+    struct Group {
+        agents: Vec<String>,
+        rules: Vec<(bool, String)>,
+    }
+    let mut groups: Vec<Group> = Vec::new();
+    let mut in_agents = false;
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                if !in_agents {
+                    groups.push(Group {
+                        agents: Vec::new(),
+                        rules: Vec::new(),
+                    });
+                }
+                in_agents = true;
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+            }
+            key @ ("allow" | "disallow") => {
+                in_agents = false;
+                if let Some(group) = groups.last_mut()
+                    && !value.is_empty()
+                {
+                    group.rules.push((key == "allow", value.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let agent = agent.to_ascii_lowercase();
+    let specific: Vec<&Group> = groups
+        .iter()
+        .filter(|group| group.agents.iter().any(|name| name == &agent))
+        .collect();
+    let applicable = if specific.is_empty() {
+        groups
+            .iter()
+            .filter(|group| group.agents.iter().any(|name| name == "*"))
+            .collect()
+    } else {
+        specific
+    };
+    let best = applicable
+        .iter()
+        .flat_map(|group| group.rules.iter())
+        .filter(|(_, pattern)| robots_pattern_matches(pattern, path))
+        .max_by_key(|(allow, pattern)| (pattern.len(), *allow));
+    best.is_none_or(|(allow, _)| *allow)
+}
+
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(stripped) => (stripped, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(first) = parts.next() else {
+        return true;
+    };
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (idx, part) in parts.iter().enumerate() {
+        let last = idx + 1 == parts.len();
+        if last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(found) => rest = &rest[found + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || (parts.is_empty() && rest.is_empty())
+}
+
+pub(crate) fn body_kind(content_type: &str, body: &[u8]) -> BodyKind {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if crate::pdf_text::is_pdf(body) {
+        return BodyKind::Pdf;
+    }
+    match mime.as_str() {
+        "text/html" | "application/xhtml+xml" => BodyKind::Html,
+        "application/json" | "text/json" => BodyKind::Json,
+        "text/markdown" | "text/x-markdown" => BodyKind::Markdown,
+        _ if mime.ends_with("+json") => BodyKind::Json,
+        _ if mime.starts_with("text/")
+            || mime.ends_with("+xml")
+            || matches!(
+                mime.as_str(),
+                "application/xml"
+                    | "application/javascript"
+                    | "application/x-yaml"
+                    | "application/yaml"
+                    | "application/toml"
+                    | "application/x-sh"
+            ) =>
+        {
+            BodyKind::Text
+        }
+        // Servers often omit or misreport the type of raw files.
+        _ if looks_like_text(body) => BodyKind::Text,
+        _ => BodyKind::Binary,
+    }
+}
+
+fn looks_like_text(body: &[u8]) -> bool {
+    let head = &body[..body.len().min(4096)];
+    if head.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => true,
+        // A multi-byte character cut off by the sample window is fine.
+        Err(err) => err.error_len().is_none(),
+    }
+}
+
+/// Decode a text body using the charset named in `content_type`.
+pub(crate) fn decode_text(content_type: &str, body: &[u8]) -> String {
+    let charset = content_type.split(';').find_map(|param| {
+        let (key, value) = param.trim().split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"').to_string())
+    });
+    let encoding = charset
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    let (text, _, _) = encoding.decode(body);
+    text.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn robots_rules_pick_the_most_specific_group_and_longest_match() {
+        let robots = "\
+User-agent: *
+Disallow: /private
+Allow: /private/docs
+Disallow: /*.json$
+
+User-agent: code
+User-agent: other
+Disallow: /admin
+";
+        assert!(robots_allows(robots, "code", "/private/page"));
+        assert!(!robots_allows(robots, "code", "/admin/users"));
+        assert!(!robots_allows(robots, "someone", "/private/page"));
+        assert!(robots_allows(robots, "someone", "/private/docs/index.html"));
+        assert!(!robots_allows(robots, "someone", "/api/data.json"));
+        assert!(robots_allows(robots, "someone", "/api/data.json?x=1"));
+        assert!(robots_allows("", "code", "/anything"));
+
+        assert_eq!(body_kind("application/problem+json", b"{}"), BodyKind::Json);
+        assert_eq!(body_kind("application/octet-stream", b"fn main() {}"), BodyKind::Text);
+        assert_eq!(body_kind("image/png", b"\x89PNG\r\n\x1a\n\0\0"), BodyKind::Binary);
+        assert_eq!(decode_text("text/plain; charset=ISO-8859-1", b"caf\xe9"), "café");
+    }
+}
@@ -0,0 +1,445 @@
+//! Structured `git log`, `git blame` and `git show` for the history tools.
+//!
+//! The model asks "why is this code like this" questions; answering them from
+//! raw porcelain means guessing at formats. These helpers run git in the
+//! session cwd and return commits, blame hunks and diff stats as
+//! serializable records. Revisions that look like options are rejected and
+//! paths always follow `--`, so arguments cannot turn into git flags.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::Offset;
+use chrono::Utc;
+use serde::Serialize;
+use tokio::process::Command;
+
+const GIT_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const DEFAULT_LOG_LIMIT: usize = 20;
+pub(crate) const MAX_LOG_LIMIT: usize = 100;
+/// Lines blamed when the request gives no end line.
+pub(crate) const MAX_BLAME_LINES: usize = 400;
+/// Bytes of patch text returned by [`show`].
+const MAX_PATCH_BYTES: usize = 48 * 1024;
+const FIELD: char = '\u{1f}';
+const RECORD: char = '\u{1e}';
+/// One record per commit: sha, author, email, date, subject and body, then
+/// whatever git prints after the format (file names or numstat lines).
+const COMMIT_FORMAT: &str = "--format=%x1e%H%x1f%an%x1f%ae%x1f%aI%x1f%s%x1f%b%x1f";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Commit {
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    pub(crate) email: String,
+    /// Author date, ISO 8601.
+    pub(crate) date: String,
+    pub(crate) subject: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub(crate) body: String,
+    /// Files the commit touched (within the requested path).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) files: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LogQuery {
+    pub(crate) revision: Option<String>,
+    pub(crate) path: Option<String>,
+    pub(crate) author: Option<String>,
+    /// Only commits whose message matches this pattern.
+    pub(crate) grep: Option<String>,
+    pub(crate) limit: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct LogResult {
+    pub(crate) commits: Vec<Commit>,
+    /// More commits match than were returned.
+    pub(crate) truncated: bool,
+}
+
+/// Consecutive lines last changed by the same commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct BlameHunk {
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    pub(crate) date: String,
+    pub(crate) summary: String,
+    /// Set when the lines were last changed under another path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) original_path: Option<String>,
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct FileStat {
+    pub(crate) path: String,
+    /// `None` for binary files.
+    pub(crate) added: Option<u64>,
+    pub(crate) deleted: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ShowResult {
+    pub(crate) commit: Commit,
+    pub(crate) stats: Vec<FileStat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) patch: Option<String>,
+    /// The patch was cut at [`MAX_PATCH_BYTES`].
+    pub(crate) patch_truncated: bool,
+}
+
+/// `git log` with author, date, message and touched files per commit.
+pub(crate) async fn log(cwd: &Path, query: &LogQuery) -> std::io::Result<LogResult> {
+    let limit = query.limit.clamp(1, MAX_LOG_LIMIT);
+    let mut args = vec![
+        "log".to_string(),
+        format!("--max-count={}", limit + 1),
+        COMMIT_FORMAT.to_string(),
+        "--name-only".to_string(),
+    ];
+    if let Some(author) = &query.author {
+        args.push(format!("--author={author}"));
+    }
+    if let Some(grep) = &query.grep {
+        args.push(format!("--grep={grep}"));
+        args.push("--regexp-ignore-case".to_string());
+    }
+    if let Some(revision) = &query.revision {
+        args.push(checked_revision(revision)?.to_string());
+    }
+    push_path(&mut args, query.path.as_deref());
+    let output = run_git(cwd, &args).await?;
+    let mut commits = parse_log(&output);
+    let truncated = commits.len() > limit;
+    commits.truncate(limit);
+    Ok(LogResult { commits, truncated })
+}
+
+/// `git blame` for `start..=end` (1-based) of `path`, grouped into hunks.
+pub(crate) async fn blame(
+    cwd: &Path,
+    path: &str,
+    start: usize,
+    end: Option<usize>,
+    revision: Option<&str>,
+) -> std::io::Result<Vec<BlameHunk>> {
+    let (start, end) = blame_range(start, end);
+    let mut args = vec![
+        "blame".to_string(),
+        "--porcelain".to_string(),
+        format!("-L{start},{end}"),
+    ];
+    if let Some(revision) = revision {
+        args.push(checked_revision(revision)?.to_string());
+    }
+    args.push("--".to_string());
+    args.push(path.to_string());
+    let output = run_git(cwd, &args).await?;
+    Ok(parse_blame(&output, path))
+}
+
+/// The 1-based line range to blame, at most [`MAX_BLAME_LINES`] long.
+fn blame_range(start: usize, end: Option<usize>) -> (usize, usize) {
+    let start = start.max(1);
+    let last = start.saturating_add(MAX_BLAME_LINES - 1);
+    (start, end.unwrap_or(last).clamp(start, last))
+}
+
+/// Metadata, per-file stats and (optionally) the patch of one commit.
+pub(crate) async fn show(
+    cwd: &Path,
+    revision: &str,
+    path: Option<&str>,
+    include_patch: bool,
+) -> std::io::Result<ShowResult> {
+    let revision = checked_revision(revision)?;
+    let mut args = vec![
+        "show".to_string(),
+        "--no-color".to_string(),
+        COMMIT_FORMAT.to_string(),
+        "--numstat".to_string(),
+        revision.to_string(),
+    ];
+    push_path(&mut args, path);
+    let output = run_git(cwd, &args).await?;
+    let (commit, stats) = parse_show(&output)
+        .ok_or_else(|| std::io::Error::other(format!("{revision} is not a commit")))?;
+
+    let (patch, patch_truncated) = if include_patch {
+        let mut args = vec![
+            "show".to_string(),
+            "--no-color".to_string(),
+            "--no-ext-diff".to_string(),
+            "--format=".to_string(),
+            "--patch".to_string(),
+            revision.to_string(),
+        ];
+        push_path(&mut args, path);
+        let mut patch = run_git(cwd, &args).await?;
+        let truncated = patch.len() > MAX_PATCH_BYTES;
+        if truncated {
+            let mut cut = MAX_PATCH_BYTES;
+            while !patch.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            patch.truncate(cut);
+        }
+        (Some(patch.trim_start_matches('\n').to_string()), truncated)
+    } else {
+        (None, false)
+    };
+    Ok(ShowResult {
+        commit,
+        stats,
+        patch,
+        patch_truncated,
+    })
+}
+
+fn push_path(args: &mut Vec<String>, path: Option<&str>) {
+    if let Some(path) = path {
+        args.push("--".to_string());
+        args.push(path.to_string());
+    }
+}
+
+fn checked_revision(revision: &str) -> std::io::Result<&str> {
+    let revision = revision.trim();
+    if revision.is_empty() || revision.starts_with('-') {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid revision {revision:?}"),
+        ));
+    }
+    Ok(revision)
+}
+
+async fn run_git(cwd: &Path, args: &[String]) -> std::io::Result<String> {
+    let output = tokio::time::timeout(
+        GIT_TIMEOUT,
+        Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .env_remove("GIT_DIR")
+            .env_remove("GIT_WORK_TREE")
+            .env_remove("GIT_INDEX_FILE")
+            .env_remove("GIT_COMMON_DIR")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| std::io::Error::other(format!("git {} timed out", args[0])))??;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "git {} failed: {}",
+            args[0],
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Splits output of [`COMMIT_FORMAT`] into commits and the text after each.
+fn parse_records(output: &str) -> Vec<(Commit, &str)> {
+    output
+        .split(RECORD)
+        .filter_map(|record| {
+            let mut fields = record.splitn(7, FIELD);
+            let sha = fields.next()?.trim();
+            if sha.is_empty() {
+                return None;
+            }
+            let commit = Commit {
+                sha: sha.to_string(),
+                author: fields.next()?.to_string(),
+                email: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                body: fields.next()?.trim().to_string(),
+                files: Vec::new(),
+            };
+            Some((commit, fields.next().unwrap_or_default()))
+        })
+        .collect()
+}
+
+fn parse_log(output: &str) -> Vec<Commit> {
+    parse_records(output)
+        .into_iter()
+        .map(|(mut commit, rest)| {
+            commit.files = rest
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect();
+            commit
+        })
+        .collect()
+}
+
+fn parse_show(output: &str) -> Option<(Commit, Vec<FileStat>)> {
+    let (commit, rest) = parse_records(output).into_iter().next()?;
+    let stats = rest
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let added = parts.next()?;
+            let deleted = parts.next()?;
+            Some(FileStat {
+                path: parts.next()?.to_string(),
+                added: added.parse().ok(),
+                deleted: deleted.parse().ok(),
+            })
+        })
+        .collect();
+    Some((commit, stats))
+}
+
+#[derive(Debug, Default, Clone)]
+struct BlameCommit {
+    author: String,
+    time: i64,
+    tz: String,
+    summary: String,
+    filename: String,
+}
+
+/// Parses `git blame --porcelain`. Commit details are printed only the
+/// first time a commit appears, so they are remembered by sha.
+fn parse_blame(output: &str, path: &str) -> Vec<BlameHunk> {
+    let mut commits: HashMap<String, BlameCommit> = HashMap::new();
+    let mut hunks: Vec<BlameHunk> = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            let Some((sha, line_number)) = current.take() else {
+                continue;
+            };
+            let info = commits.get(&sha).cloned().unwrap_or_default();
+            match hunks.last_mut() {
+                Some(hunk) if hunk.sha == sha && hunk.end_line + 1 == line_number => {
+                    hunk.end_line = line_number;
+                    hunk.content.push('\n');
+                    hunk.content.push_str(content);
+                }
+                _ => hunks.push(BlameHunk {
+                    start_line: line_number,
+                    end_line: line_number,
+                    sha: sha.clone(),
+                    author: info.author.clone(),
+                    date: blame_date(info.time, &info.tz),
+                    summary: info.summary.clone(),
+                    original_path: (!info.filename.is_empty() && info.filename != path)
+                        .then(|| info.filename.clone()),
+                    content: content.to_string(),
+                }),
+            }
+            continue;
+        }
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        if matches!(key.len(), 40 | 64) && key.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            let final_line = value.split(' ').nth(1).and_then(|n| n.parse().ok());
+            if let Some(final_line) = final_line {
+                commits.entry(key.to_string()).or_default();
+                current = Some((key.to_string(), final_line));
+            }
+            continue;
+        }
+        let Some(entry) = current.as_ref().and_then(|(sha, _)| commits.get_mut(sha)) else {
+            continue;
+        };
+        match key {
+            "author" => entry.author = value.to_string(),
+            "author-time" => entry.time = value.parse().unwrap_or_default(),
+            "author-tz" => entry.tz = value.to_string(),
+            "summary" => entry.summary = value.to_string(),
+            "filename" => entry.filename = value.to_string(),
+            _ => {}
+        }
+    }
+    hunks
+}
+
+/// ISO 8601 date from blame's epoch seconds and `+hhmm` offset.
+fn blame_date(time: i64, tz: &str) -> String {
+    let sign = if tz.starts_with('-') { -1 } else { 1 };
+    let digits = tz.trim_start_matches(['+', '-']);
+    let hours: i32 = digits.get(..2).and_then(|h| h.parse().ok()).unwrap_or(0);
+    let minutes: i32 = digits.get(2..4).and_then(|m| m.parse().ok()).unwrap_or(0);
+    let offset = FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        .unwrap_or_else(|| Utc.fix());
+    DateTime::from_timestamp(time, 0)
+        .map(|date| date.with_timezone(&offset).to_rfc3339())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn blame_range_is_capped() {
+        assert_eq!(blame_range(0, None), (1, MAX_BLAME_LINES));
+        assert_eq!(blame_range(10, Some(5)), (10, 10));
+        assert_eq!(blame_range(1, Some(100_000)), (1, MAX_BLAME_LINES));
+        assert_eq!(blame_range(usize::MAX, None), (usize::MAX, usize::MAX));
+    }
+
+    #[test]
+    fn parses_log_show_and_blame_output() {
+        let sha_a = "a".repeat(40);
+        let sha_b = "b".repeat(40);
+        let log = format!(
+            "{RECORD}{sha_a}{FIELD}Ada{FIELD}ada@example.com{FIELD}2024-05-01T10:00:00+02:00\
+             {FIELD}Fix parser{FIELD}Handle empty input.\n{FIELD}\n\nsrc/parser.rs\nREADME.md\n\
+             {RECORD}{sha_b}{FIELD}Bob{FIELD}bob@example.com{FIELD}2024-04-01T09:00:00+00:00\
+             {FIELD}Initial commit{FIELD}{FIELD}\n\nsrc/parser.rs\n"
+        );
+        let commits = parse_log(&log);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "Fix parser");
+        assert_eq!(commits[0].body, "Handle empty input.");
+        assert_eq!(commits[0].files, vec!["src/parser.rs", "README.md"]);
+        assert_eq!(commits[1].body, "");
+
+        let show = format!(
+            "{RECORD}{sha_a}{FIELD}Ada{FIELD}ada@example.com{FIELD}2024-05-01T10:00:00+02:00\
+             {FIELD}Fix parser{FIELD}{FIELD}\n\n3\t1\tsrc/parser.rs\n-\t-\tlogo.png\n"
+        );
+        let (commit, stats) = parse_show(&show).expect("commit");
+        assert_eq!(commit.author, "Ada");
+        assert_eq!(
+            stats,
+            vec![
+                FileStat { path: "src/parser.rs".to_string(), added: Some(3), deleted: Some(1) },
+                FileStat { path: "logo.png".to_string(), added: None, deleted: None },
+            ]
+        );
+
+        let blame = format!(
+            "{sha_a} 1 1 2\nauthor Ada\nauthor-time 1714550400\nauthor-tz +0200\n\
+             summary Fix parser\nfilename src/parser.rs\n\tfn parse() {{\n\
+             {sha_a} 2 2\n\t}}\n\
+             {sha_b} 7 3 1\nauthor Bob\nauthor-time 1711962000\nauthor-tz +0000\n\
+             summary Initial commit\nfilename src/old.rs\n\t// end\n"
+        );
+        let hunks = parse_blame(&blame, "src/parser.rs");
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].start_line, hunks[0].end_line), (1, 2));
+        assert_eq!(hunks[0].content, "fn parse() {\n}");
+        assert_eq!(hunks[0].date, "2024-05-01T10:00:00+02:00");
+        assert_eq!(hunks[0].original_path, None);
+        assert_eq!(hunks[1].summary, "Initial commit");
+        assert_eq!(hunks[1].original_path.as_deref(), Some("src/old.rs"));
+    }
+}
@@ -88,6 +88,7 @@ pub enum HistoryDomainEvent {
         wait_total: Option<Duration>,
         wait_active: bool,
         wait_notes: Vec<ExecWaitNote>,
+        redactions: usize,
     },
 }
 
@@ -638,6 +639,9 @@ pub struct ExecRecord {
     pub env: Vec<(String, String)>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Secrets redacted from the command output.
+    #[serde(default)]
+    pub redactions: usize,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -2081,6 +2085,7 @@ impl HistoryState {
                     working_dir,
                     env,
                     tags,
+                    redactions: 0,
                 };
                 self.apply_event(HistoryEvent::Insert {
                     index: insert_index,
@@ -2098,6 +2103,7 @@ impl HistoryState {
                 wait_notes,
                 stdout_tail,
                 stderr_tail,
+                redactions,
             } => {
                 let mut target_idx = id.and_then(|hid| self.index_of(hid));
                 if target_idx.is_none() {
@@ -2117,6 +2123,7 @@ impl HistoryState {
                         updated.wait_total = wait_total;
                         updated.wait_active = wait_active;
                         updated.wait_notes = wait_notes;
+                        updated.redactions = redactions;
 
                         if let Some(tail) = stdout_tail {
                             if !tail.is_empty() {
@@ -2503,6 +2510,7 @@ mod tests {
             }],
             stdout_tail: Some("output".into()),
             stderr_tail: Some("warn".into()),
+            redactions: 0,
         });
 
         assert!(matches!(finish, HistoryMutation::Replaced { id, .. } if id == inserted_id));
@@ -2823,6 +2831,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redactions: 0,
         };
         let exec_id = state.push(HistoryRecord::Exec(exec_record));
         assert_ne!(exec_id, HistoryId::ZERO);
@@ -2878,6 +2887,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redactions: 0,
         }));
 
         let tool_id = state.push(HistoryRecord::ToolCall(ToolCallState {
@@ -3056,6 +3066,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redactions: 0,
         }));
 
         records.push(HistoryRecord::AssistantStream(AssistantStreamState {
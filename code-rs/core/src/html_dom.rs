@@ -0,0 +1,402 @@
+//! Minimal, forgiving HTML tree used by the `web_fetch` Markdown pipeline.
+//!
+//! This is not a spec-compliant parser. It records element byte ranges,
+//! parent/child links and text statistics, which is all the content passes
+//! need; the HTML itself is still converted by `htmd`. Raw-text elements
+//! (`script`, `style`, ...) are skipped wholesale, and unclosed elements are
+//! closed when an ancestor closes or the input ends.
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+const RAW_TEXT_ELEMENTS: &[&str] =
+    &["script", "style", "textarea", "title", "noscript", "template"];
+
+const PARAGRAPH_CLOSERS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "div", "dl", "fieldset", "footer", "form", "h1",
+    "h2", "h3", "h4", "h5", "h6", "header", "hr", "main", "nav", "ol", "p", "pre", "section",
+    "table", "ul",
+];
+
+pub(crate) struct HtmlDocument<'a> {
+    html: &'a str,
+    elements: Vec<HtmlElement<'a>>,
+}
+
+pub(crate) struct HtmlElement<'a> {
+    /// Lowercased tag name.
+    pub(crate) name: String,
+    attrs: &'a str,
+    /// Byte offset of the opening `<`.
+    pub(crate) start: usize,
+    /// Byte offset just past the opening tag.
+    pub(crate) content_start: usize,
+    /// Byte offset of the closing tag, or where the element was implicitly closed.
+    pub(crate) content_end: usize,
+    /// Byte offset just past the closing tag.
+    pub(crate) end: usize,
+    pub(crate) parent: Option<usize>,
+    pub(crate) children: Vec<usize>,
+    /// Non-whitespace text characters inside the element, descendants included.
+    pub(crate) text_len: usize,
+    /// Portion of `text_len` that sits inside `<a>` elements.
+    pub(crate) link_text_len: usize,
+    /// Commas in the element's text; a cheap signal for prose.
+    pub(crate) commas: usize,
+}
+
+enum Tag<'a> {
+    Open {
+        name: String,
+        attrs: &'a str,
+        end: usize,
+        self_closing: bool,
+    },
+    Close {
+        name: String,
+        end: usize,
+    },
+    /// Comments, doctypes and processing instructions.
+    Skip { end: usize },
+}
+
+impl<'a> HtmlDocument<'a> {
+    pub(crate) fn parse(html: &'a str) -> Self {
+        let bytes = html.as_bytes();
+        let mut elements: Vec<HtmlElement<'a>> = Vec::new();
+        let mut stack: Vec<usize> = Vec::new();
+        let mut text_start = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i] != b'<' {
+                i += 1;
+                continue;
+            }
+            let Some(tag) = scan_tag(html, i) else {
+                i += 1;
+                continue;
+            };
+            record_text(&mut elements, &stack, &html[text_start..i]);
+
+            match tag {
+                Tag::Skip { end } => i = end,
+                Tag::Close { name, end } => {
+                    if let Some(pos) = stack.iter().rposition(|&idx| elements[idx].name == name) {
+                        for &idx in &stack[pos + 1..] {
+                            close(&mut elements[idx], i, i);
+                        }
+                        close(&mut elements[stack[pos]], i, end);
+                        stack.truncate(pos);
+                    }
+                    i = end;
+                }
+                Tag::Open {
+                    name,
+                    attrs,
+                    end,
+                    self_closing,
+                } => {
+                    while let Some(&top) = stack.last()
+                        && closes_implicitly(&elements[top].name, &name)
+                    {
+                        close(&mut elements[top], i, i);
+                        stack.pop();
+                    }
+
+                    let raw_text = RAW_TEXT_ELEMENTS.contains(&name.as_str());
+                    let void = VOID_ELEMENTS.contains(&name.as_str());
+                    let idx = elements.len();
+                    let parent = stack.last().copied();
+                    if let Some(parent) = parent {
+                        elements[parent].children.push(idx);
+                    }
+                    let (content_end, close_end) = if raw_text && !self_closing {
+                        find_raw_text_end(html, end, &name)
+                    } else {
+                        (end, end)
+                    };
+                    elements.push(HtmlElement {
+                        name,
+                        attrs,
+                        start: i,
+                        content_start: end,
+                        content_end,
+                        end: close_end,
+                        parent,
+                        children: Vec::new(),
+                        text_len: 0,
+                        link_text_len: 0,
+                        commas: 0,
+                    });
+                    if !raw_text && !void && !self_closing {
+                        stack.push(idx);
+                    }
+                    i = close_end;
+                }
+            }
+            text_start = i;
+        }
+        record_text(&mut elements, &stack, &html[text_start..]);
+        for &idx in &stack {
+            close(&mut elements[idx], html.len(), html.len());
+        }
+
+        // Children always follow their parent, so a reverse walk aggregates
+        // descendant statistics bottom-up.
+        for idx in (0..elements.len()).rev() {
+            if elements[idx].name == "a" {
+                elements[idx].link_text_len = elements[idx].text_len;
+            }
+            if let Some(parent) = elements[idx].parent {
+                let (text, links, commas) = (
+                    elements[idx].text_len,
+                    elements[idx].link_text_len,
+                    elements[idx].commas,
+                );
+                let parent = &mut elements[parent];
+                parent.text_len += text;
+                parent.link_text_len += links;
+                parent.commas += commas;
+            }
+        }
+
+        Self { html, elements }
+    }
+
+    pub(crate) fn elements(&self) -> &[HtmlElement<'a>] {
+        &self.elements
+    }
+
+    /// Source text between the element's opening and closing tags.
+    pub(crate) fn inner_html(&self, idx: usize) -> &'a str {
+        let element = &self.elements[idx];
+        &self.html[element.content_start..element.content_end]
+    }
+
+    /// Source of the element with every descendant for which `skip` returns
+    /// true cut out.
+    pub(crate) fn outer_html_without(&self, idx: usize, skip: impl Fn(usize) -> bool) -> String {
+        let mut ranges = Vec::new();
+        self.collect_skipped(idx, &skip, &mut ranges);
+        let element = &self.elements[idx];
+        let mut out = String::with_capacity(element.end - element.start);
+        let mut pos = element.start;
+        for (start, end) in ranges {
+            out.push_str(&self.html[pos..start]);
+            pos = end;
+        }
+        out.push_str(&self.html[pos..element.end]);
+        out
+    }
+
+    fn collect_skipped(
+        &self,
+        idx: usize,
+        skip: &impl Fn(usize) -> bool,
+        ranges: &mut Vec<(usize, usize)>,
+    ) {
+        for &child in &self.elements[idx].children {
+            if skip(child) {
+                ranges.push((self.elements[child].start, self.elements[child].end));
+            } else {
+                self.collect_skipped(child, skip, ranges);
+            }
+        }
+    }
+}
+
+impl<'a> HtmlElement<'a> {
+    /// Raw (not entity-decoded) value of attribute `name`; `Some("")` for
+    /// boolean attributes.
+    pub(crate) fn attr(&self, name: &str) -> Option<&'a str> {
+        let attrs = self.attrs;
+        let bytes = attrs.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            while i < bytes.len() && (bytes[i].is_ascii_whitespace() || bytes[i] == b'/') {
+                i += 1;
+            }
+            let key_start = i;
+            while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'=' {
+                i += 1;
+            }
+            let key = &attrs[key_start..i];
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            let mut value = "";
+            if i < bytes.len() && bytes[i] == b'=' {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if i < bytes.len() && (bytes[i] == b'"' || bytes[i] == b'\'') {
+                    let quote = bytes[i];
+                    let value_start = i + 1;
+                    let value_end = bytes[value_start..]
+                        .iter()
+                        .position(|&b| b == quote)
+                        .map_or(bytes.len(), |pos| value_start + pos);
+                    value = &attrs[value_start..value_end];
+                    i = (value_end + 1).min(bytes.len());
+                } else {
+                    let value_start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+                        i += 1;
+                    }
+                    value = &attrs[value_start..i];
+                }
+            }
+            if key.is_empty() {
+                i += 1;
+            } else if key.eq_ignore_ascii_case(name) {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+fn scan_tag(html: &str, start: usize) -> Option<Tag<'_>> {
+    let bytes = html.as_bytes();
+    let rest = &html[start..];
+    if rest.starts_with("<!--") {
+        let end = rest[4..]
+            .find("-->")
+            .map_or(html.len(), |pos| start + 4 + pos + 3);
+        return Some(Tag::Skip { end });
+    }
+    let next = *bytes.get(start + 1)?;
+    if next == b'!' || next == b'?' {
+        return Some(Tag::Skip {
+            end: find_tag_end(bytes, start + 2),
+        });
+    }
+    let (closing, name_start) = if next == b'/' {
+        (true, start + 2)
+    } else {
+        (false, start + 1)
+    };
+    if !bytes.get(name_start)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let name_end = (name_start..bytes.len())
+        .find(|&j| !(bytes[j].is_ascii_alphanumeric() || bytes[j] == b'-' || bytes[j] == b':'))
+        .unwrap_or(bytes.len());
+    let name = html[name_start..name_end].to_ascii_lowercase();
+    let end = find_tag_end(bytes, name_end);
+    if closing {
+        return Some(Tag::Close { name, end });
+    }
+    let inner_end = if end > name_end && bytes[end - 1] == b'>' {
+        end - 1
+    } else {
+        end
+    };
+    let attrs = html[name_end..inner_end].trim();
+    let self_closing = attrs.ends_with('/');
+    Some(Tag::Open {
+        name,
+        attrs: attrs.trim_end_matches('/'),
+        end,
+        self_closing,
+    })
+}
+
+/// Offset just past the `>` that ends a tag, ignoring `>` inside quoted
+/// attribute values.
+fn find_tag_end(bytes: &[u8], from: usize) -> usize {
+    let mut quote: Option<u8> = None;
+    let mut after_equals = false;
+    for (j, &b) in bytes.iter().enumerate().skip(from) {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'>' => return j + 1,
+            None if after_equals && (b == b'"' || b == b'\'') => quote = Some(b),
+            None => {}
+        }
+        if !b.is_ascii_whitespace() {
+            after_equals = b == b'=';
+        }
+    }
+    bytes.len()
+}
+
+fn find_raw_text_end(html: &str, from: usize, name: &str) -> (usize, usize) {
+    let bytes = html.as_bytes();
+    let needle_len = name.len() + 2;
+    let mut i = from;
+    while i + needle_len <= bytes.len() {
+        if bytes[i] == b'<'
+            && bytes[i + 1] == b'/'
+            && bytes[i + 2..i + needle_len].eq_ignore_ascii_case(name.as_bytes())
+        {
+            return (i, find_tag_end(bytes, i + needle_len));
+        }
+        i += 1;
+    }
+    (bytes.len(), bytes.len())
+}
+
+fn closes_implicitly(open: &str, new: &str) -> bool {
+    match open {
+        "p" => PARAGRAPH_CLOSERS.contains(&new),
+        "li" => new == "li",
+        "dt" | "dd" => matches!(new, "dt" | "dd"),
+        "option" => new == "option",
+        "td" | "th" => matches!(new, "td" | "th" | "tr" | "thead" | "tbody" | "tfoot"),
+        "tr" => matches!(new, "tr" | "thead" | "tbody" | "tfoot"),
+        "thead" | "tbody" | "tfoot" => matches!(new, "thead" | "tbody" | "tfoot"),
+        _ => false,
+    }
+}
+
+fn close(element: &mut HtmlElement<'_>, content_end: usize, end: usize) {
+    element.content_end = content_end;
+    element.end = end;
+}
+
+fn record_text(elements: &mut [HtmlElement<'_>], stack: &[usize], text: &str) {
+    let Some(&top) = stack.last() else {
+        return;
+    };
+    let element = &mut elements[top];
+    element.text_len += text.chars().filter(|c| !c.is_whitespace()).count();
+    element.commas += text.matches(',').count();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn builds_tree_with_implied_end_tags_and_raw_text() {
+        let html = "<div class='a b' data-x=\"1>2\"><p>one, two<p>three<script>if (a < b) {}</script></div>";
+        let doc = HtmlDocument::parse(html);
+        let names: Vec<&str> = doc.elements().iter().map(|el| el.name.as_str()).collect();
+        assert_eq!(names, vec!["div", "p", "p", "script"]);
+
+        let div = &doc.elements()[0];
+        assert_eq!(div.attr("class"), Some("a b"));
+        assert_eq!(div.attr("data-x"), Some("1>2"));
+        assert_eq!(div.children, vec![1, 2]);
+        assert_eq!(div.text_len, "one,two".len() + "three".len());
+        assert_eq!(div.commas, 1);
+        assert_eq!(doc.inner_html(1), "one, two");
+        assert_eq!(doc.elements()[3].parent, Some(2));
+    }
+
+    #[test]
+    fn outer_html_without_cuts_skipped_descendants() {
+        let html = "<main><nav><a href=\"/\">Home</a></nav><p>Body</p></main>";
+        let doc = HtmlDocument::parse(html);
+        assert_eq!(doc.elements()[0].link_text_len, "Home".len());
+        let out = doc.outer_html_without(0, |idx| doc.elements()[idx].name == "nav");
+        assert_eq!(out, "<main><p>Body</p></main>");
+    }
+}
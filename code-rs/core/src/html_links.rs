@@ -0,0 +1,139 @@
+//! Numbered link citations for `web_fetch` Markdown.
+//!
+//! Anchors are unwrapped to their text followed by a `[n]` marker, and the
+//! URLs are listed once at the end of the page as `[n]: url`. The model can
+//! cite a link by number and fetch it without copying long inline URLs, and
+//! the body reads like prose instead of a wall of `[text](url)`.
+
+use url::Url;
+
+use crate::html_dom::HtmlDocument;
+
+const LINK_PLACEHOLDER_PREFIX: &str = "CODEWEBFETCHLINK";
+const LINK_PLACEHOLDER_SUFFIX: &str = "END";
+
+/// Replace anchors in `html` with their content plus a placeholder that
+/// [`restore_links`] turns into a `[n]` marker. Returns the rewritten HTML
+/// and the distinct link targets, numbered from 1 in order of appearance.
+pub(crate) fn replace_links(html: &str, base_url: &str) -> (String, Vec<String>) {
+    let base = Url::parse(base_url).ok();
+    let doc = HtmlDocument::parse(html);
+    let mut out = String::with_capacity(html.len());
+    let mut links: Vec<String> = Vec::new();
+    let mut pos = 0;
+    for (idx, element) in doc.elements().iter().enumerate() {
+        if element.name != "a" || element.start < pos {
+            continue;
+        }
+        let Some(href) = element.attr("href") else {
+            continue;
+        };
+        out.push_str(&html[pos..element.start]);
+        out.push_str(doc.inner_html(idx));
+        if let Some(target) = resolve(base.as_ref(), href) {
+            let number = match links.iter().position(|link| *link == target) {
+                Some(existing) => existing,
+                None => {
+                    links.push(target);
+                    links.len() - 1
+                }
+            };
+            out.push_str(&format!(
+                "{LINK_PLACEHOLDER_PREFIX}{number}{LINK_PLACEHOLDER_SUFFIX}"
+            ));
+        }
+        pos = element.end;
+    }
+    out.push_str(&html[pos..]);
+    (out, links)
+}
+
+/// Turn the placeholders left by [`replace_links`] into `[n]` markers.
+pub(crate) fn restore_links(markdown: &str) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+    while let Some(start) = rest.find(LINK_PLACEHOLDER_PREFIX) {
+        out.push_str(&rest[..start]);
+        let after_prefix = &rest[start + LINK_PLACEHOLDER_PREFIX.len()..];
+        let digits = after_prefix
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        let number = after_prefix[..digits].parse::<usize>().ok();
+        match number {
+            Some(number) if after_prefix[digits..].starts_with(LINK_PLACEHOLDER_SUFFIX) => {
+                out.push_str(&format!("[{}]", number + 1));
+                rest = &after_prefix[digits + LINK_PLACEHOLDER_SUFFIX.len()..];
+            }
+            _ => {
+                out.push_str(LINK_PLACEHOLDER_PREFIX);
+                rest = after_prefix;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Reference list for the links whose markers appear in `markdown`, e.g.
+/// after truncation dropped the rest.
+pub(crate) fn link_references(markdown: &str, links: &[String]) -> Option<String> {
+    let references: Vec<String> = links
+        .iter()
+        .enumerate()
+        .map(|(idx, link)| (idx + 1, link))
+        .filter(|(number, _)| markdown.contains(&format!("[{number}]")))
+        .map(|(number, link)| format!("[{number}]: {link}"))
+        .collect();
+    if references.is_empty() {
+        None
+    } else {
+        Some(format!("Links:\n\n{}", references.join("\n")))
+    }
+}
+
+/// Absolute URL for `href`, or `None` for targets the model cannot follow
+/// (in-page fragments, `javascript:` handlers).
+fn resolve(base: Option<&Url>, href: &str) -> Option<String> {
+    let href = href.trim().replace("&amp;", "&");
+    if href.is_empty() || href.starts_with('#') {
+        return None;
+    }
+    let url = match base {
+        Some(base) => base.join(&href).ok()?,
+        None => Url::parse(&href).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https" | "mailto").then(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn numbers_links_and_lists_references() {
+        let html = r##"<p>See <a href="/docs?a=1&amp;b=2">the docs</a>, the
+            <a href="https://example.org/faq">FAQ</a> and <a href="/docs?a=1&b=2">docs again</a>.
+            <a href="#top">Top</a> <a href="javascript:void(0)">Menu</a></p>"##;
+
+        let (rewritten, links) = replace_links(html, "https://example.com/guide/");
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/docs?a=1&b=2".to_string(),
+                "https://example.org/faq".to_string(),
+            ]
+        );
+        let markdown = restore_links(&rewritten);
+        assert!(markdown.contains("See the docs[1], the"));
+        assert!(markdown.contains("FAQ[2] and docs again[1]."));
+        assert!(markdown.contains("Top Menu</p>"));
+
+        assert_eq!(
+            link_references("Only the FAQ[2] survived truncation.", &links),
+            Some("Links:\n\n[2]: https://example.org/faq".to_string())
+        );
+        assert_eq!(link_references("No markers.", &links), None);
+    }
+}
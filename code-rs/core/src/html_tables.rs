@@ -0,0 +1,310 @@
+//! Markdown rendering for HTML data tables.
+//!
+//! `htmd` loses column structure when tables use `colspan`/`rowspan`, so
+//! `web_fetch` renders tables itself: a spanned cell is repeated into every
+//! grid position it covers, and `align`/`text-align` become GFM alignment
+//! markers. Each table is swapped for a placeholder paragraph before the page
+//! is converted and put back into the Markdown afterwards.
+
+use crate::html_dom::HtmlDocument;
+use crate::html_dom::HtmlElement;
+
+/// Upper bound on `colspan`, so a hostile page cannot blow up the grid.
+const MAX_COLSPAN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    text: String,
+    align: Option<Alignment>,
+}
+
+struct Cell {
+    slot: Slot,
+    colspan: usize,
+    rowspan: usize,
+}
+
+/// Cell content still owed to rows below the one that declared `rowspan`.
+struct Carry {
+    slot: Slot,
+    remaining: usize,
+}
+
+/// Replace every data table in `html` with a placeholder paragraph and
+/// return the rewritten HTML along with the rendered Markdown tables.
+/// `render_cell` converts a cell's inner HTML to Markdown.
+pub(crate) fn replace_tables(
+    html: &str,
+    render_cell: impl Fn(&str) -> String,
+) -> (String, Vec<String>) {
+    let doc = HtmlDocument::parse(html);
+    let mut out = String::with_capacity(html.len());
+    let mut tables = Vec::new();
+    let mut pos = 0;
+    for (idx, element) in doc.elements().iter().enumerate() {
+        if element.name != "table" || element.start < pos {
+            continue;
+        }
+        let Some(markdown) = render_table(&doc, idx, &render_cell) else {
+            continue;
+        };
+        out.push_str(&html[pos..element.start]);
+        out.push_str(&format!("<p>{}</p>", placeholder(tables.len())));
+        tables.push(markdown);
+        pos = element.end;
+    }
+    out.push_str(&html[pos..]);
+    (out, tables)
+}
+
+/// Put the tables produced by [`replace_tables`] back into the Markdown.
+pub(crate) fn restore_tables(markdown: &str, tables: &[String]) -> String {
+    let mut out = markdown.to_string();
+    for (idx, table) in tables.iter().enumerate() {
+        out = out.replace(&placeholder(idx), table);
+    }
+    out
+}
+
+fn placeholder(idx: usize) -> String {
+    format!("CODEWEBFETCHTABLE{idx}END")
+}
+
+fn render_table(
+    doc: &HtmlDocument<'_>,
+    table: usize,
+    render_cell: &impl Fn(&str) -> String,
+) -> Option<String> {
+    let elements = doc.elements();
+    let table_element = &elements[table];
+    if table_element.attr("role").is_some_and(|role| {
+        role.eq_ignore_ascii_case("presentation") || role.eq_ignore_ascii_case("none")
+    }) {
+        return None;
+    }
+
+    let mut caption = None;
+    let mut rows: Vec<Vec<Cell>> = Vec::new();
+    // Descendants directly follow the table in document order.
+    for (idx, element) in elements
+        .iter()
+        .enumerate()
+        .skip(table + 1)
+        .take_while(|(_, element)| element.start < table_element.end)
+    {
+        match element.name.as_str() {
+            // Nested tables are layout, not data; leave them to `htmd`.
+            "table" => return None,
+            "caption" if caption.is_none() => {
+                caption = Some(cell_text(render_cell(doc.inner_html(idx))));
+            }
+            "tr" => {
+                let cells: Vec<Cell> = element
+                    .children
+                    .iter()
+                    .copied()
+                    .filter(|&child| matches!(elements[child].name.as_str(), "td" | "th"))
+                    .map(|child| Cell {
+                        slot: Slot {
+                            text: cell_text(render_cell(doc.inner_html(child))),
+                            align: cell_alignment(&elements[child]),
+                        },
+                        colspan: span(&elements[child], "colspan").clamp(1, MAX_COLSPAN),
+                        rowspan: span(&elements[child], "rowspan"),
+                    })
+                    .collect();
+                if !cells.is_empty() {
+                    rows.push(cells);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let grid = expand_spans(rows);
+    let columns = grid.iter().map(Vec::len).max().unwrap_or(0);
+    if columns < 2 {
+        return None;
+    }
+
+    let mut out = String::new();
+    if let Some(caption) = caption.filter(|caption| !caption.is_empty()) {
+        out.push_str(&caption);
+        out.push_str("\n\n");
+    }
+    for (row_idx, row) in grid.iter().enumerate() {
+        out.push('|');
+        for col in 0..columns {
+            let text = row
+                .get(col)
+                .and_then(Option::as_ref)
+                .map_or("", |slot| slot.text.as_str());
+            out.push(' ');
+            out.push_str(text);
+            out.push_str(" |");
+        }
+        out.push('\n');
+        if row_idx == 0 {
+            out.push('|');
+            for col in 0..columns {
+                let align = grid
+                    .iter()
+                    .find_map(|row| row.get(col)?.as_ref()?.align);
+                out.push_str(match align {
+                    Some(Alignment::Left) => " :--- |",
+                    Some(Alignment::Center) => " :---: |",
+                    Some(Alignment::Right) => " ---: |",
+                    None => " --- |",
+                });
+            }
+            out.push('\n');
+        }
+    }
+    Some(out.trim_end().to_string())
+}
+
+/// Lay cells out on a grid, repeating spanned cells into each position they
+/// cover. Positions no cell reaches are `None`.
+fn expand_spans(rows: Vec<Vec<Cell>>) -> Vec<Vec<Option<Slot>>> {
+    let row_count = rows.len();
+    let mut carry: Vec<Option<Carry>> = Vec::new();
+    let mut grid = Vec::with_capacity(row_count);
+    for (row_idx, cells) in rows.into_iter().enumerate() {
+        let rows_left = row_count - row_idx;
+        let mut line = Vec::new();
+        let mut col = 0;
+        for cell in cells {
+            while let Some(slot) = take_carried(&mut carry, col) {
+                line.push(Some(slot));
+                col += 1;
+            }
+            // `rowspan="0"` spans the rest of the table.
+            let rowspan = match cell.rowspan {
+                0 => rows_left,
+                rowspan => rowspan.min(rows_left),
+            };
+            for _ in 0..cell.colspan {
+                if rowspan > 1 {
+                    if carry.len() <= col {
+                        carry.resize_with(col + 1, || None);
+                    }
+                    carry[col] = Some(Carry {
+                        slot: cell.slot.clone(),
+                        remaining: rowspan - 1,
+                    });
+                }
+                line.push(Some(cell.slot.clone()));
+                col += 1;
+            }
+        }
+        // Rowspans that reach past this row's last cell.
+        while col < carry.len() {
+            line.push(take_carried(&mut carry, col));
+            col += 1;
+        }
+        grid.push(line);
+    }
+    grid
+}
+
+fn take_carried(carry: &mut [Option<Carry>], col: usize) -> Option<Slot> {
+    let entry = carry.get_mut(col)?;
+    let pending = entry.as_mut()?;
+    let slot = pending.slot.clone();
+    pending.remaining -= 1;
+    if pending.remaining == 0 {
+        *entry = None;
+    }
+    Some(slot)
+}
+
+fn span(element: &HtmlElement<'_>, attr: &str) -> usize {
+    element
+        .attr(attr)
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(1)
+}
+
+fn cell_alignment(element: &HtmlElement<'_>) -> Option<Alignment> {
+    let value = element.attr("align").map(str::to_string).or_else(|| {
+        element.attr("style")?.split(';').find_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            property
+                .trim()
+                .eq_ignore_ascii_case("text-align")
+                .then(|| value.trim().to_string())
+        })
+    })?;
+    match value.to_ascii_lowercase().as_str() {
+        "left" | "start" => Some(Alignment::Left),
+        "center" => Some(Alignment::Center),
+        "right" | "end" => Some(Alignment::Right),
+        _ => None,
+    }
+}
+
+/// Fit converted cell Markdown on one table line.
+fn cell_text(markdown: String) -> String {
+    markdown
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("<br>")
+        .replace("\\|", "|")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn expands_spans_and_emits_alignment() {
+        let html = r#"<p>Intro</p><table><caption>Plans</caption>
+            <thead><tr><th style="text-align: center">Plan</th><th colspan="2">Limits</th></tr></thead>
+            <tbody>
+              <tr><td rowspan="2">Pro</td><td>10</td><td align="right">20</td></tr>
+              <tr><td>30</td><td>a | b</td></tr>
+            </tbody></table><p>Outro</p>"#;
+
+        let (rewritten, tables) = replace_tables(html, |cell| cell.trim().to_string());
+        assert_eq!(
+            rewritten,
+            format!("<p>Intro</p><p>{}</p><p>Outro</p>", placeholder(0))
+        );
+        assert_eq!(
+            tables,
+            vec![
+                "Plans\n\n\
+                 | Plan | Limits | Limits |\n\
+                 | :---: | --- | ---: |\n\
+                 | Pro | 10 | 20 |\n\
+                 | Pro | 30 | a \\| b |"
+                    .to_string()
+            ]
+        );
+
+        let markdown = format!("Intro\n\n{}\n\nOutro", placeholder(0));
+        assert!(restore_tables(&markdown, &tables).contains("| Pro | 30 |"));
+    }
+
+    #[test]
+    fn leaves_layout_tables_alone() {
+        let html = "<table><tr><td><table><tr><td>a</td><td>b</td></tr></table></td></tr></table>";
+        let (_, tables) = replace_tables(html, str::to_string);
+        // Only the inner data table is rendered.
+        assert_eq!(tables, vec!["| a | b |\n| --- | --- |".to_string()]);
+
+        let single_column = "<table><tr><td>only</td></tr></table>";
+        assert_eq!(replace_tables(single_column, str::to_string).1, Vec::<String>::new());
+    }
+}
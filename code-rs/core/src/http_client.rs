@@ -1,14 +1,25 @@
 use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
 
 use reqwest::cookie::CookieStore;
 use reqwest::cookie::Jar;
 use reqwest::header::HeaderValue;
 
+use crate::model_provider_info::ProviderTlsConfig;
+
 pub const CODEX_CA_CERTIFICATE_ENV_VAR: &str = "CODEX_CA_CERTIFICATE";
 
+/// Loopback hosts are never sent through the configured proxy.
+const ALWAYS_DIRECT_HOSTS: [&str; 3] = ["localhost", "127.0.0.1", "::1"];
+
+static PROXY_SETTINGS: LazyLock<RwLock<Option<ProxySettings>>> =
+    LazyLock::new(|| RwLock::new(None));
+
 static SHARED_CHATGPT_CLOUDFLARE_COOKIE_STORE: LazyLock<Arc<ChatGptCloudflareCookieStore>> =
     LazyLock::new(|| Arc::new(ChatGptCloudflareCookieStore::default()));
 
@@ -117,6 +128,105 @@ fn is_allowed_cloudflare_cookie_name(name: &str) -> bool {
     ) || name.starts_with("cf_chl_")
 }
 
+/// Proxy from `[network]` in config.toml, with credentials resolved.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProxySettings {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Hosts, domains and CIDR ranges reached without the proxy.
+    pub no_proxy: Vec<String>,
+}
+
+impl std::fmt::Debug for ProxySettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxySettings")
+            .field("url", &self.server())
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("no_proxy", &self.no_proxy)
+            .finish()
+    }
+}
+
+impl ProxySettings {
+    /// The proxy URL with credentials attached, as reqwest expects them.
+    fn url_with_credentials(&self) -> Result<reqwest::Url, String> {
+        let mut url = reqwest::Url::parse(&self.url)
+            .map_err(|err| format!("invalid proxy URL {}: {err}", self.server()))?;
+        if let Some(username) = &self.username {
+            url.set_username(username)
+                .map_err(|()| format!("proxy URL {} cannot carry a username", self.server()))?;
+        }
+        if let Some(password) = &self.password {
+            url.set_password(Some(password))
+                .map_err(|()| format!("proxy URL {} cannot carry a password", self.server()))?;
+        }
+        Ok(url)
+    }
+
+    /// The proxy URL without any credentials, safe to log or pass to Chrome.
+    pub fn server(&self) -> String {
+        match reqwest::Url::parse(&self.url) {
+            Ok(mut url) => {
+                let _ = url.set_username("");
+                let _ = url.set_password(None);
+                url.to_string().trim_end_matches('/').to_string()
+            }
+            Err(_) => self.url.clone(),
+        }
+    }
+
+    /// Comma-separated bypass list, loopback hosts included.
+    pub fn bypass_list(&self) -> String {
+        ALWAYS_DIRECT_HOSTS
+            .iter()
+            .map(|host| host.to_string())
+            .chain(self.no_proxy.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    pub fn to_reqwest_proxy(&self) -> Result<reqwest::Proxy, String> {
+        let proxy = reqwest::Proxy::all(self.url_with_credentials()?)
+            .map_err(|err| format!("invalid proxy {}: {err}", self.server()))?;
+        Ok(proxy.no_proxy(reqwest::NoProxy::from_string(&self.bypass_list())))
+    }
+}
+
+/// Select the proxy for this process. Called while loading the config.
+pub fn set_proxy_settings(settings: Option<ProxySettings>) {
+    *PROXY_SETTINGS.write().unwrap_or_else(PoisonError::into_inner) = settings;
+}
+
+pub fn proxy_settings() -> Option<ProxySettings> {
+    PROXY_SETTINGS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// The configured proxy for HTTP clients built outside this crate (MCP).
+/// `None` leaves reqwest on the system proxy settings.
+pub fn configured_proxy() -> Option<reqwest::Proxy> {
+    let settings = proxy_settings()?;
+    match settings.to_reqwest_proxy() {
+        Ok(proxy) => Some(proxy),
+        Err(err) => {
+            tracing::warn!("ignoring [network] proxy: {err}");
+            None
+        }
+    }
+}
+
+/// Route `builder` through the configured proxy, if any.
+pub fn apply_proxy(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    match configured_proxy() {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    }
+}
+
 pub fn apply_extra_root_certificates(
     mut builder: reqwest::ClientBuilder,
 ) -> reqwest::ClientBuilder {
@@ -183,14 +293,57 @@ pub fn apply_extra_root_certificates(
     builder
 }
 
+fn read_tls_file(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|err| format!("cannot read {}: {err}", path.display()))
+}
+
+/// Add a provider's CA bundle and mTLS identity to `builder`.
+pub fn apply_provider_tls(
+    mut builder: reqwest::ClientBuilder,
+    tls: &ProviderTlsConfig,
+) -> Result<reqwest::ClientBuilder, String> {
+    if let Some(path) = &tls.ca_certificate {
+        let bytes = read_tls_file(path)?;
+        let certs = match reqwest::Certificate::from_pem_bundle(&bytes) {
+            Ok(certs) if !certs.is_empty() => certs,
+            _ => vec![reqwest::Certificate::from_der(&bytes).map_err(|err| {
+                format!("{} is not a PEM or DER certificate: {err}", path.display())
+            })?],
+        };
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    match (&tls.client_certificate, &tls.client_key) {
+        (Some(cert_path), Some(key_path)) => {
+            let cert = read_tls_file(cert_path)?;
+            let key = read_tls_file(key_path)?;
+            let identity = reqwest::Identity::from_pkcs8_pem(&cert, &key).map_err(|err| {
+                format!(
+                    "invalid client certificate {} / key {} (the key must be PKCS#8 PEM, \
+                     see `openssl pkcs8 -topk8 -nocrypt`): {err}",
+                    cert_path.display(),
+                    key_path.display()
+                )
+            })?;
+            // Stay on native-tls so the system trust store still applies;
+            // rustls would only trust its bundled roots.
+            builder = builder.use_native_tls().identity(identity);
+        }
+        (None, None) => {}
+        _ => return Err("client_certificate and client_key must be set together".to_string()),
+    }
+    Ok(builder)
+}
+
 /// Build a reqwest Client with optional extra root certificates loaded from
 /// common environment variables. `CODEX_CA_CERTIFICATE` takes precedence over
 /// `SSL_CERT_FILE`, and other ecosystem-standard CA bundle variables continue
 /// to work as fallbacks.
 pub fn build_http_client() -> reqwest::Client {
-    apply_extra_root_certificates(with_chatgpt_cloudflare_cookie_store(
+    apply_proxy(apply_extra_root_certificates(with_chatgpt_cloudflare_cookie_store(
         reqwest::Client::builder(),
-    ))
+    )))
         .build()
         .unwrap_or_else(|_| reqwest::Client::new())
 }
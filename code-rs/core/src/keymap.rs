@@ -0,0 +1,408 @@
+//! Configurable key bindings (`[keys]`).
+//!
+//! Each [`KeyAction`] is bound to one or more [`KeyChord`]s written as
+//! `"ctrl+j"`, `"shift+enter"` or `"y"`. Unset actions keep their default
+//! chords. [`Keymap::from_toml`] rejects chords that cannot be parsed and
+//! chords bound to two actions of the same input context (or to a key the
+//! context already uses), as well as composer chords that are plain typed
+//! characters, so a clash is reported when the config is loaded rather than
+//! discovered as a key that silently does the wrong thing.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config_types::KeyChordsToml;
+use crate::config_types::KeysToml;
+
+/// A key without modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyName {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Esc,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    F(u8),
+}
+
+/// A key plus the modifiers held with it. Character keys are matched
+/// case-insensitively, so `shift` is dropped for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyName,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    const fn plain(key: KeyName) -> Self {
+        Self { key, ctrl: false, alt: false, shift: false }
+    }
+
+    const fn ctrl(key: KeyName) -> Self {
+        Self { key, ctrl: true, alt: false, shift: false }
+    }
+
+    const fn shift(key: KeyName) -> Self {
+        Self { key, ctrl: false, alt: false, shift: true }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_lowercase();
+        // A trailing `+` is the plus key itself, as in `ctrl++`.
+        let (mods, key) = match normalized.strip_suffix("++") {
+            Some(mods) => (mods, "+"),
+            None if normalized == "+" => ("", "+"),
+            None => normalized.rsplit_once('+').unwrap_or(("", normalized.as_str())),
+        };
+        let mut chord = KeyChord::plain(parse_key_name(s, key)?);
+        for modifier in mods.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier {
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" | "option" | "meta" => chord.alt = true,
+                "shift" => chord.shift = true,
+                other => return Err(format!("unknown modifier `{other}` in `{s}`")),
+            }
+        }
+        if matches!(chord.key, KeyName::Char(_)) {
+            chord.shift = false;
+        }
+        Ok(chord)
+    }
+}
+
+fn parse_key_name(chord: &str, key: &str) -> Result<KeyName, String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyName::Char(c));
+    }
+    Ok(match key {
+        "enter" | "return" => KeyName::Enter,
+        "tab" => KeyName::Tab,
+        "backspace" => KeyName::Backspace,
+        "delete" | "del" => KeyName::Delete,
+        "esc" | "escape" => KeyName::Esc,
+        "space" => KeyName::Char(' '),
+        "up" => KeyName::Up,
+        "down" => KeyName::Down,
+        "left" => KeyName::Left,
+        "right" => KeyName::Right,
+        "home" => KeyName::Home,
+        "end" => KeyName::End,
+        "pageup" => KeyName::PageUp,
+        "pagedown" => KeyName::PageDown,
+        "insert" => KeyName::Insert,
+        _ => match key.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=24) => KeyName::F(n),
+            _ => {
+                let message = if key.is_empty() {
+                    format!("missing key in `{chord}`")
+                } else {
+                    format!("unknown key `{key}` in `{chord}`")
+                };
+                return Err(message);
+            }
+        },
+    })
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "alt+")?;
+        }
+        if self.shift {
+            write!(f, "shift+")?;
+        }
+        match self.key {
+            KeyName::Char(' ') => write!(f, "space"),
+            KeyName::Char(c) => write!(f, "{c}"),
+            KeyName::Enter => write!(f, "enter"),
+            KeyName::Tab => write!(f, "tab"),
+            KeyName::Backspace => write!(f, "backspace"),
+            KeyName::Delete => write!(f, "delete"),
+            KeyName::Esc => write!(f, "esc"),
+            KeyName::Up => write!(f, "up"),
+            KeyName::Down => write!(f, "down"),
+            KeyName::Left => write!(f, "left"),
+            KeyName::Right => write!(f, "right"),
+            KeyName::Home => write!(f, "home"),
+            KeyName::End => write!(f, "end"),
+            KeyName::PageUp => write!(f, "pageup"),
+            KeyName::PageDown => write!(f, "pagedown"),
+            KeyName::Insert => write!(f, "insert"),
+            KeyName::F(n) => write!(f, "f{n}"),
+        }
+    }
+}
+
+/// Where a binding applies. Chords only conflict within one context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum KeyContext {
+    Composer,
+    Approval,
+}
+
+impl KeyContext {
+    /// Keys the context handles itself and that cannot be rebound.
+    fn reserved(self) -> &'static [KeyChord] {
+        const COMPOSER: &[KeyChord] = &[
+            KeyChord::plain(KeyName::Esc),
+            KeyChord::plain(KeyName::Tab),
+            KeyChord::shift(KeyName::Tab),
+            KeyChord::ctrl(KeyName::Char('c')),
+            KeyChord::ctrl(KeyName::Char('r')),
+        ];
+        const APPROVAL: &[KeyChord] = &[
+            KeyChord::plain(KeyName::Enter),
+            KeyChord::plain(KeyName::Esc),
+            KeyChord::plain(KeyName::Up),
+            KeyChord::plain(KeyName::Down),
+            KeyChord::plain(KeyName::Left),
+            KeyChord::plain(KeyName::Right),
+            KeyChord::ctrl(KeyName::Char('c')),
+        ];
+        match self {
+            KeyContext::Composer => COMPOSER,
+            KeyContext::Approval => APPROVAL,
+        }
+    }
+}
+
+/// An action that can be bound under `[keys]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    /// Send the composer contents.
+    Submit,
+    /// Insert a line break in the composer.
+    Newline,
+    /// Recall the previous prompt from history.
+    HistoryPrevious,
+    /// Recall the next prompt from history.
+    HistoryNext,
+    /// Approve a command or patch once.
+    Approve,
+    /// Always allow the exact command in this project.
+    ApproveAlways,
+    /// Always allow commands with the same prefix in this project.
+    ApprovePrefix,
+    /// Decline and give feedback.
+    Deny,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 8] = [
+        KeyAction::Submit,
+        KeyAction::Newline,
+        KeyAction::HistoryPrevious,
+        KeyAction::HistoryNext,
+        KeyAction::Approve,
+        KeyAction::ApproveAlways,
+        KeyAction::ApprovePrefix,
+        KeyAction::Deny,
+    ];
+
+    /// The key under `[keys]`.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            KeyAction::Submit => "submit",
+            KeyAction::Newline => "newline",
+            KeyAction::HistoryPrevious => "history_previous",
+            KeyAction::HistoryNext => "history_next",
+            KeyAction::Approve => "approve",
+            KeyAction::ApproveAlways => "approve_always",
+            KeyAction::ApprovePrefix => "approve_prefix",
+            KeyAction::Deny => "deny",
+        }
+    }
+
+    fn context(self) -> KeyContext {
+        match self {
+            KeyAction::Submit
+            | KeyAction::Newline
+            | KeyAction::HistoryPrevious
+            | KeyAction::HistoryNext => KeyContext::Composer,
+            KeyAction::Approve
+            | KeyAction::ApproveAlways
+            | KeyAction::ApprovePrefix
+            | KeyAction::Deny => KeyContext::Approval,
+        }
+    }
+
+    fn defaults(self) -> Vec<KeyChord> {
+        match self {
+            KeyAction::Submit => vec![KeyChord::plain(KeyName::Enter)],
+            KeyAction::Newline => vec![
+                KeyChord::shift(KeyName::Enter),
+                KeyChord::ctrl(KeyName::Char('j')),
+            ],
+            KeyAction::HistoryPrevious => vec![KeyChord::shift(KeyName::Up)],
+            KeyAction::HistoryNext => vec![KeyChord::shift(KeyName::Down)],
+            KeyAction::Approve => vec![KeyChord::plain(KeyName::Char('y'))],
+            KeyAction::ApproveAlways => vec![KeyChord::plain(KeyName::Char('a'))],
+            KeyAction::ApprovePrefix => vec![KeyChord::plain(KeyName::Char('p'))],
+            KeyAction::Deny => vec![KeyChord::plain(KeyName::Char('n'))],
+        }
+    }
+
+    fn configured(self, keys: &KeysToml) -> Option<&KeyChordsToml> {
+        match self {
+            KeyAction::Submit => keys.submit.as_ref(),
+            KeyAction::Newline => keys.newline.as_ref(),
+            KeyAction::HistoryPrevious => keys.history_previous.as_ref(),
+            KeyAction::HistoryNext => keys.history_next.as_ref(),
+            KeyAction::Approve => keys.approve.as_ref(),
+            KeyAction::ApproveAlways => keys.approve_always.as_ref(),
+            KeyAction::ApprovePrefix => keys.approve_prefix.as_ref(),
+            KeyAction::Deny => keys.deny.as_ref(),
+        }
+    }
+}
+
+/// The resolved chords for every [`KeyAction`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: HashMap<KeyAction, Vec<KeyChord>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: KeyAction::ALL
+                .into_iter()
+                .map(|action| (action, action.defaults()))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Resolve `[keys]` over the defaults, rejecting bad or clashing chords.
+    pub fn from_toml(keys: &KeysToml) -> Result<Self, String> {
+        let mut bindings = HashMap::new();
+        for action in KeyAction::ALL {
+            let chords = match action.configured(keys) {
+                Some(configured) => configured
+                    .as_slice()
+                    .iter()
+                    .map(|chord| {
+                        chord
+                            .parse::<KeyChord>()
+                            .map_err(|err| format!("keys.{}: {err}", action.config_key()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => action.defaults(),
+            };
+            bindings.insert(action, chords);
+        }
+
+        let mut seen: HashMap<(KeyContext, KeyChord), KeyAction> = HashMap::new();
+        for action in KeyAction::ALL {
+            let context = action.context();
+            for chord in &bindings[&action] {
+                let name = action.config_key();
+                if context.reserved().contains(chord) {
+                    return Err(format!("keys.{name}: `{chord}` is already used by Code"));
+                }
+                if context == KeyContext::Composer
+                    && matches!(chord.key, KeyName::Char(_))
+                    && !chord.ctrl
+                    && !chord.alt
+                {
+                    return Err(format!(
+                        "keys.{name}: `{chord}` is typed into the composer; add ctrl or alt"
+                    ));
+                }
+                if let Some(other) = seen.insert((context, *chord), action)
+                    && other != action
+                {
+                    let other = other.config_key();
+                    return Err(format!("keys.{name}: `{chord}` is also bound to keys.{other}"));
+                }
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    /// Chords bound to `action`; never empty unless configured as `[]`.
+    pub fn chords(&self, action: KeyAction) -> &[KeyChord] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_chords_and_rejects_conflicts() {
+        let chord: KeyChord = "Ctrl+Shift+Enter".parse().expect("chord");
+        assert_eq!(
+            chord,
+            KeyChord { key: KeyName::Enter, ctrl: true, alt: false, shift: true }
+        );
+        assert_eq!(chord.to_string(), "ctrl+shift+enter");
+        assert_eq!("ctrl++".parse::<KeyChord>().map(|c| c.key), Ok(KeyName::Char('+')));
+        assert_eq!(
+            "hyper+x".parse::<KeyChord>(),
+            Err("unknown modifier `hyper` in `hyper+x`".to_string())
+        );
+
+        let keys = KeysToml {
+            submit: Some(KeyChordsToml::One("ctrl+s".to_string())),
+            newline: Some(KeyChordsToml::Many(vec!["enter".to_string(), "alt+n".to_string()])),
+            approve: Some(KeyChordsToml::One("ctrl+s".to_string())),
+            ..KeysToml::default()
+        };
+        let keymap = Keymap::from_toml(&keys).expect("keymap");
+        let ctrl_s: KeyChord = "ctrl+s".parse().expect("chord");
+        assert_eq!(keymap.chords(KeyAction::Submit), &[ctrl_s]);
+        assert_eq!(keymap.chords(KeyAction::Newline).len(), 2);
+        assert_eq!(keymap.chords(KeyAction::Deny), Keymap::default().chords(KeyAction::Deny));
+
+        let clash = KeysToml {
+            history_next: Some(KeyChordsToml::One("enter".to_string())),
+            ..KeysToml::default()
+        };
+        assert_eq!(
+            Keymap::from_toml(&clash),
+            Err("keys.history_next: `enter` is also bound to keys.submit".to_string())
+        );
+        let reserved = KeysToml {
+            deny: Some(KeyChordsToml::One("esc".to_string())),
+            ..KeysToml::default()
+        };
+        assert_eq!(
+            Keymap::from_toml(&reserved),
+            Err("keys.deny: `esc` is already used by Code".to_string())
+        );
+        let printable = KeysToml {
+            submit: Some(KeyChordsToml::One("shift+s".to_string())),
+            ..KeysToml::default()
+        };
+        assert_eq!(
+            Keymap::from_toml(&printable),
+            Err("keys.submit: `s` is typed into the composer; add ctrl or alt".to_string())
+        );
+    }
+}
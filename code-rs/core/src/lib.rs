@@ -10,6 +10,7 @@ mod apply_patch;
 mod fs_sanitize;
 pub mod auth;
 pub mod auth_accounts;
+pub mod auth_store;
 pub mod account_usage;
 mod account_switching;
 pub use account_switching::{RateLimitSwitchState, switch_active_account_on_rate_limit};
@@ -19,8 +20,11 @@ mod chat_completions;
 mod client;
 mod client_common;
 pub mod codex;
+pub mod code_index;
 mod code_conversation;
 mod bridge_client;
+mod browser_credentials;
+mod browser_downloads;
 pub mod token_data;
 pub use code_conversation::CodexConversation;
 mod command_safety;
@@ -54,14 +58,23 @@ pub mod exec;
 mod exec_command;
 pub mod exec_env;
 pub mod external_agent_config;
+mod fetch_url;
 mod flags;
 pub mod git_info;
+mod git_history;
 pub mod landlock;
 pub mod http_client;
 pub mod housekeeping;
+mod html_dom;
+mod html_links;
+mod html_tables;
+mod lsp;
+mod mcp_audio_transcription;
 pub mod mcp_connection_manager;
+mod mcp_tool_cache;
 mod mcp_tool_call;
 mod message_history;
+pub use message_history::load_project_history;
 mod memories;
 mod model_provider_info;
 pub mod remote_models;
@@ -76,19 +89,26 @@ pub mod git_worktree;
 pub mod slash_commands;
 pub mod parse_command;
 pub mod history;
+pub mod keymap;
+mod tool_arguments_validation;
 mod truncate;
 mod unified_exec;
+pub mod usage_quota;
 mod user_instructions;
+mod web_readability;
+mod web_search;
 mod skills;
 pub use model_provider_info::BUILT_IN_OSS_MODEL_PROVIDER_ID;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OpenRouterConfig;
 pub use model_provider_info::OpenRouterProviderConfig;
+pub use model_provider_info::ProviderTlsConfig;
 pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod conversation_manager;
 pub mod protocol;
+pub mod redaction;
 mod event_mapping;
 pub mod review_format;
 #[cfg(test)]
@@ -104,11 +124,16 @@ pub use auth::RefreshTokenErrorKind;
 pub use tool_apply_patch::ApplyPatchToolType;
 pub mod default_client;
 pub mod model_family;
+pub mod navigation_policy;
 mod openai_tools;
+mod page_snapshots;
 mod patch_harness;
+mod pdf_text;
 pub mod plan_tool;
 pub mod project_doc;
 pub mod project_features;
+pub mod provider_keys;
+mod read_file;
 mod rollout;
 pub(crate) mod safety;
 pub mod session_catalog;
@@ -116,6 +141,8 @@ pub mod seatbelt;
 pub mod shell;
 pub mod spawn;
 pub mod terminal;
+mod terminal_panes;
+pub mod test_results;
 pub mod otel_init;
 mod text_encoding;
 mod tool_apply_patch;
@@ -0,0 +1,717 @@
+//! Language server diagnostics after patches.
+//!
+//! With `[lsp] enabled = true`, the first patch that touches a file a
+//! language server handles starts that server over stdio, rooted at the
+//! session cwd. The files are sent to their servers once before the patch,
+//! to record what was already wrong, and again after it; diagnostics
+//! published in response are compared with that baseline. Errors and
+//! warnings that are new go back to the model with the patch result, so it
+//! can fix them without waiting for a full build, and are shown in the
+//! transcript.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::Child;
+use tokio::process::ChildStdin;
+use tokio::process::Command;
+use tokio::sync::Notify;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+use url::Url;
+
+use crate::config_types::LspConfig;
+use crate::protocol::LspDiagnostic;
+use crate::protocol::LspDiagnosticsEvent;
+use crate::protocol::LspSeverity;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Quiet period after every file has been published once: servers often
+/// publish again as slower checks finish.
+const SETTLE: Duration = Duration::from_millis(300);
+/// Diagnostics included in one report.
+const MAX_REPORTED: usize = 20;
+/// Largest message body accepted from a server.
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Servers used when they are on `PATH`: name, command and extensions.
+const BUILTIN_SERVERS: &[(&str, &[&str], &[&str])] = &[
+    ("rust-analyzer", &["rust-analyzer"], &["rs"]),
+    ("pyright", &["pyright-langserver", "--stdio"], &["py", "pyi"]),
+    (
+        "typescript-language-server",
+        &["typescript-language-server", "--stdio"],
+        &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+    ),
+    ("gopls", &["gopls"], &["go"]),
+];
+
+/// What the language servers said about one patch.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct LspReport {
+    /// Servers that checked the patched files.
+    pub(crate) servers: Vec<String>,
+    /// New errors and warnings, at most [`MAX_REPORTED`].
+    pub(crate) diagnostics: Vec<LspDiagnostic>,
+    pub(crate) total: usize,
+    /// Servers that could not be started.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) failures: Vec<String>,
+}
+
+impl LspReport {
+    pub(crate) fn to_event(&self, call_id: String) -> LspDiagnosticsEvent {
+        LspDiagnosticsEvent {
+            call_id,
+            servers: self.servers.clone(),
+            diagnostics: self.diagnostics.clone(),
+            total: self.total,
+            failures: self.failures.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ServerSpec {
+    name: String,
+    command: Vec<String>,
+    extensions: Vec<String>,
+    builtin: bool,
+}
+
+/// The language servers of one session, started on first use.
+pub(crate) struct LspManager {
+    root: PathBuf,
+    timeout: Duration,
+    specs: Vec<ServerSpec>,
+    /// `None` records a server that failed to start, so it is not retried.
+    servers: tokio::sync::Mutex<HashMap<String, Option<Arc<Server>>>>,
+}
+
+impl LspManager {
+    /// `None` unless `[lsp]` is enabled.
+    pub(crate) fn new(config: &LspConfig, root: &Path) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let mut specs: Vec<ServerSpec> = config
+            .servers
+            .iter()
+            .filter(|server| !server.command.is_empty())
+            .map(|server| ServerSpec {
+                name: server.name.clone(),
+                command: server.command.clone(),
+                extensions: server.extensions.clone(),
+                builtin: false,
+            })
+            .collect();
+        for (name, command, extensions) in BUILTIN_SERVERS {
+            if specs.iter().any(|spec| spec.name == *name) {
+                continue;
+            }
+            specs.push(ServerSpec {
+                name: name.to_string(),
+                command: command.iter().map(|arg| arg.to_string()).collect(),
+                extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+                builtin: true,
+            });
+        }
+        Some(Self {
+            root: root.to_path_buf(),
+            timeout: Duration::from_millis(config.timeout_ms),
+            specs,
+            servers: tokio::sync::Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Sends the current contents of `paths` to their servers before a patch
+    /// touches them, so what the servers publish now is the baseline the
+    /// patch is judged against rather than a pile of pre-existing problems.
+    pub(crate) async fn before_patch(&self, paths: &[PathBuf]) {
+        let deadline = Instant::now() + self.timeout;
+        for (spec, files) in self.group(paths) {
+            // Failures to start are reported after the patch.
+            let Ok(Some(server)) = self.server(spec).await else {
+                continue;
+            };
+            let mark = server.shared.generation.load(Ordering::SeqCst);
+            let mut uris = Vec::new();
+            for file in files.iter().filter(|file| file.is_file()) {
+                match server.sync(file).await {
+                    Ok(uri) => uris.push(uri),
+                    Err(err) => tracing::debug!("lsp: {} sync failed: {err}", spec.name),
+                }
+            }
+            if !uris.is_empty() {
+                server.shared.wait_for_publish(&uris, mark, deadline).await;
+            }
+            *server.reported.lock().unwrap_or_else(PoisonError::into_inner) =
+                server.shared.snapshot();
+        }
+    }
+
+    /// Sends the current contents of `paths` to their servers and collects
+    /// the errors and warnings that were not there before the patch. Files
+    /// a server publishes for the first time only count when the patch
+    /// wrote them; anything else is taken as the baseline for next time.
+    /// `None` when no server handles any of the files.
+    pub(crate) async fn diagnostics_after_patch(&self, paths: &[PathBuf]) -> Option<LspReport> {
+        let groups = self.group(paths);
+        if groups.is_empty() {
+            return None;
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        let mut report = LspReport::default();
+        let mut found = Vec::new();
+        for (spec, files) in groups {
+            let server = match self.server(spec).await {
+                Ok(Some(server)) => server,
+                Ok(None) => continue,
+                Err(err) => {
+                    report.failures.push(format!("{} failed to start: {err}", spec.name));
+                    continue;
+                }
+            };
+            let mark = server.shared.generation.load(Ordering::SeqCst);
+            let mut uris = Vec::new();
+            for file in files.iter().filter(|file| file.is_file()) {
+                match server.sync(file).await {
+                    Ok(uri) => uris.push(uri),
+                    Err(err) => tracing::debug!("lsp: {} sync failed: {err}", spec.name),
+                }
+            }
+            if uris.is_empty() {
+                continue;
+            }
+            server.shared.wait_for_publish(&uris, mark, deadline).await;
+            let current = server.shared.snapshot();
+            let mut reported = server.reported.lock().unwrap_or_else(PoisonError::into_inner);
+            found.extend(new_diagnostics(&reported, &current, &uris));
+            *reported = current;
+            report.servers.push(spec.name.clone());
+        }
+        if report.servers.is_empty() && report.failures.is_empty() {
+            return None;
+        }
+        found.sort_by(|a, b| {
+            (a.severity != LspSeverity::Error)
+                .cmp(&(b.severity != LspSeverity::Error))
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        report.total = found.len();
+        found.truncate(MAX_REPORTED);
+        report.diagnostics = found;
+        Some(report)
+    }
+
+    /// `paths`, resolved against the root, grouped by the server that
+    /// handles their extension.
+    fn group(&self, paths: &[PathBuf]) -> Vec<(&ServerSpec, Vec<PathBuf>)> {
+        let mut groups: Vec<(&ServerSpec, Vec<PathBuf>)> = Vec::new();
+        for path in paths {
+            let path = self.root.join(path);
+            let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+                continue;
+            };
+            let Some(spec) = self
+                .specs
+                .iter()
+                .find(|spec| spec.extensions.iter().any(|ext| ext == extension))
+            else {
+                continue;
+            };
+            match groups.iter_mut().find(|(group, _)| group.name == spec.name) {
+                Some((_, files)) => files.push(path),
+                None => groups.push((spec, vec![path])),
+            }
+        }
+        groups
+    }
+
+    /// The running server for `spec`, starting it if needed. `Ok(None)` for a
+    /// server that failed before, or a built-in one that is not installed.
+    async fn server(&self, spec: &ServerSpec) -> std::io::Result<Option<Arc<Server>>> {
+        let mut servers = self.servers.lock().await;
+        if let Some(server) = servers.get(&spec.name) {
+            return Ok(server.clone());
+        }
+        if spec.builtin && which::which(&spec.command[0]).is_err() {
+            servers.insert(spec.name.clone(), None);
+            return Ok(None);
+        }
+        match Server::start(spec, &self.root).await {
+            Ok(server) => {
+                let server = Arc::new(server);
+                servers.insert(spec.name.clone(), Some(Arc::clone(&server)));
+                Ok(Some(server))
+            }
+            Err(err) => {
+                servers.insert(spec.name.clone(), None);
+                Err(err)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Published {
+    /// Value of [`Shared::generation`] when this was published.
+    generation: u64,
+    diagnostics: Vec<LspDiagnostic>,
+}
+
+/// State shared with the task reading the server's output.
+struct Shared {
+    root: PathBuf,
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    pending: Mutex<HashMap<i64, oneshot::Sender<Value>>>,
+    published: Mutex<HashMap<String, Published>>,
+    generation: AtomicU64,
+    changed: Notify,
+    closed: AtomicBool,
+}
+
+impl Shared {
+    fn snapshot(&self) -> HashMap<String, Vec<LspDiagnostic>> {
+        let published = self.published.lock().unwrap_or_else(PoisonError::into_inner);
+        published
+            .iter()
+            .map(|(uri, entry)| (uri.clone(), entry.diagnostics.clone()))
+            .collect()
+    }
+
+    /// Waits until every one of `uris` was published after `mark` and the
+    /// server has gone quiet, or until `deadline`.
+    async fn wait_for_publish(&self, uris: &[String], mark: u64, deadline: Instant) {
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.closed.load(Ordering::SeqCst) {
+                return;
+            }
+            let all_published = {
+                let published = self.published.lock().unwrap_or_else(PoisonError::into_inner);
+                uris.iter()
+                    .all(|uri| published.get(uri).is_some_and(|entry| entry.generation > mark))
+            };
+            let until = if all_published {
+                deadline.min(Instant::now() + SETTLE)
+            } else {
+                deadline
+            };
+            if tokio::time::timeout_at(until, notified).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    async fn send(&self, message: &Value) -> std::io::Result<()> {
+        let body = serde_json::to_vec(message)?;
+        let mut stdin = self.stdin.lock().await;
+        stdin
+            .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+            .await?;
+        stdin.write_all(&body).await?;
+        stdin.flush().await
+    }
+
+    async fn handle(&self, message: Value) {
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        match (method, id) {
+            (Some(method), Some(id)) => {
+                // Requests from the server. Answer the ones that block
+                // servers until they are answered; the rest get `null`.
+                let result = match method {
+                    "workspace/configuration" => {
+                        let items = message["params"]["items"].as_array().map_or(0, Vec::len);
+                        Value::Array(vec![Value::Null; items])
+                    }
+                    _ => Value::Null,
+                };
+                let response = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                if let Err(err) = self.send(&response).await {
+                    tracing::debug!("lsp: failed to answer {method}: {err}");
+                }
+            }
+            (Some("textDocument/publishDiagnostics"), None) => {
+                let Some((uri, diagnostics)) = parse_publish(&message["params"], &self.root)
+                else {
+                    return;
+                };
+                let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+                self.published
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(uri, Published { generation, diagnostics });
+                self.changed.notify_waiters();
+            }
+            (None, Some(id)) => {
+                let sender = id.as_i64().and_then(|id| {
+                    self.pending.lock().unwrap_or_else(PoisonError::into_inner).remove(&id)
+                });
+                if let Some(sender) = sender {
+                    let _ = sender.send(message);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+struct Server {
+    shared: Arc<Shared>,
+    next_id: AtomicI64,
+    /// Versions of the documents opened so far, by URI.
+    versions: tokio::sync::Mutex<HashMap<String, i64>>,
+    /// Diagnostics as of the last report, by URI.
+    reported: Mutex<HashMap<String, Vec<LspDiagnostic>>>,
+    /// Killed when the session (and with it the manager) goes away.
+    _child: Child,
+}
+
+impl Server {
+    async fn start(spec: &ServerSpec, root: &Path) -> std::io::Result<Self> {
+        let mut child = Command::new(&spec.command[0])
+            .args(&spec.command[1..])
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(std::io::Error::other("language server stdio unavailable"));
+        };
+        let shared = Arc::new(Shared {
+            root: root.to_path_buf(),
+            stdin: tokio::sync::Mutex::new(stdin),
+            pending: Mutex::new(HashMap::new()),
+            published: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+            changed: Notify::new(),
+            closed: AtomicBool::new(false),
+        });
+        let reader_shared = Arc::clone(&shared);
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(stdout);
+            while let Ok(Some(message)) = read_message(&mut reader).await {
+                reader_shared.handle(message).await;
+            }
+            reader_shared.closed.store(true, Ordering::SeqCst);
+            reader_shared.pending.lock().unwrap_or_else(PoisonError::into_inner).clear();
+            reader_shared.changed.notify_waiters();
+        });
+
+        let server = Self {
+            shared,
+            next_id: AtomicI64::new(1),
+            versions: tokio::sync::Mutex::new(HashMap::new()),
+            reported: Mutex::new(HashMap::new()),
+            _child: child,
+        };
+        let root_uri = file_uri(root)?;
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        server
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "workspaceFolders": [{ "uri": root_uri, "name": name }],
+                    "capabilities": {
+                        "textDocument": {
+                            "synchronization": { "didSave": true },
+                            "publishDiagnostics": { "relatedInformation": false },
+                        },
+                        "workspace": { "configuration": true, "workspaceFolders": true },
+                    },
+                }),
+            )
+            .await?;
+        server.notify("initialized", json!({})).await?;
+        Ok(server)
+    }
+
+    async fn request(&self, method: &str, params: Value) -> std::io::Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        self.shared
+            .pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id, tx);
+        self.shared
+            .send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+        let response = match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => return Err(std::io::Error::other("language server exited")),
+            Err(_) => return Err(std::io::Error::other(format!("{method} timed out"))),
+        };
+        if let Some(error) = response.get("error") {
+            let message = error["message"].as_str().unwrap_or("unknown error");
+            return Err(std::io::Error::other(format!("{method} failed: {message}")));
+        }
+        Ok(response["result"].clone())
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> std::io::Result<()> {
+        self.shared
+            .send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    /// Sends the file's current contents and saves it, which is what makes
+    /// servers like rust-analyzer run their full check. Returns the URI.
+    async fn sync(&self, path: &Path) -> std::io::Result<String> {
+        let text = tokio::fs::read_to_string(path).await?;
+        let uri = file_uri(path)?;
+        let version = {
+            let mut versions = self.versions.lock().await;
+            let version = versions.entry(uri.clone()).or_insert(0);
+            *version += 1;
+            *version
+        };
+        if version == 1 {
+            let language_id = language_id(path);
+            self.notify(
+                "textDocument/didOpen",
+                json!({ "textDocument": {
+                    "uri": uri, "languageId": language_id, "version": version, "text": text,
+                } }),
+            )
+            .await?;
+        } else {
+            self.notify(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": uri, "version": version },
+                    "contentChanges": [{ "text": text }],
+                }),
+            )
+            .await?;
+        }
+        self.notify("textDocument/didSave", json!({ "textDocument": { "uri": uri } }))
+            .await?;
+        Ok(uri)
+    }
+}
+
+fn file_uri(path: &Path) -> std::io::Result<String> {
+    Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|()| std::io::Error::other(format!("{} is not absolute", path.display())))
+}
+
+fn language_id(path: &Path) -> &str {
+    match path.extension().and_then(|ext| ext.to_str()).unwrap_or_default() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "ts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        other => other,
+    }
+}
+
+/// Reads one `Content-Length` framed JSON-RPC message. `Ok(None)` at end of
+/// stream; a missing, malformed or oversized length is an error.
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> std::io::Result<Option<Value>> {
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+    let mut length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            let value = value.trim();
+            let parsed = value.parse::<usize>();
+            length = Some(parsed.map_err(|_| invalid(format!("bad Content-Length {value:?}")))?);
+        }
+    }
+    let length = length.ok_or_else(|| invalid("message without Content-Length".to_string()))?;
+    if length > MAX_MESSAGE_BYTES {
+        return Err(invalid(format!(
+            "message of {length} bytes exceeds the {MAX_MESSAGE_BYTES} byte limit"
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Errors and warnings from a `textDocument/publishDiagnostics` notification.
+fn parse_publish(params: &Value, root: &Path) -> Option<(String, Vec<LspDiagnostic>)> {
+    let uri = params["uri"].as_str()?;
+    let path = Url::parse(uri)
+        .ok()
+        .and_then(|url| url.to_file_path().ok())
+        .map(|path| path.strip_prefix(root).map(Path::to_path_buf).unwrap_or(path))
+        .map_or_else(|| uri.to_string(), |path| path.display().to_string());
+    let diagnostics = params["diagnostics"]
+        .as_array()?
+        .iter()
+        .filter_map(|diagnostic| {
+            // A missing severity is left to the client; treat it as an error.
+            let severity = match diagnostic["severity"].as_u64() {
+                None | Some(1) => LspSeverity::Error,
+                Some(2) => LspSeverity::Warning,
+                _ => return None,
+            };
+            let start = &diagnostic["range"]["start"];
+            let code = match &diagnostic["code"] {
+                Value::String(code) => Some(code.clone()),
+                Value::Number(code) => Some(code.to_string()),
+                _ => None,
+            };
+            Some(LspDiagnostic {
+                path: path.clone(),
+                line: start["line"].as_u64()? + 1,
+                column: start["character"].as_u64()? + 1,
+                severity,
+                source: diagnostic["source"].as_str().map(str::to_string),
+                code,
+                message: diagnostic["message"].as_str()?.to_string(),
+            })
+        })
+        .collect();
+    Some((uri.to_string(), diagnostics))
+}
+
+/// Diagnostics in `after` with no counterpart in `before`. Each earlier
+/// diagnostic accounts for one later one, so a second copy of an existing
+/// error still counts as new. A URI missing from `before` is only compared
+/// when it is one of `patched`; otherwise there is nothing to compare with.
+fn new_diagnostics(
+    before: &HashMap<String, Vec<LspDiagnostic>>,
+    after: &HashMap<String, Vec<LspDiagnostic>>,
+    patched: &[String],
+) -> Vec<LspDiagnostic> {
+    let mut found = Vec::new();
+    for (uri, diagnostics) in after {
+        let mut previous: Vec<&LspDiagnostic> = match before.get(uri) {
+            Some(list) => list.iter().collect(),
+            None if patched.contains(uri) => Vec::new(),
+            None => continue,
+        };
+        for diagnostic in diagnostics {
+            match previous.iter().position(|old| same_issue(old, diagnostic)) {
+                Some(index) => {
+                    previous.swap_remove(index);
+                }
+                None => found.push(diagnostic.clone()),
+            }
+        }
+    }
+    found
+}
+
+/// Identity that survives edits above the diagnostic moving its line.
+fn same_issue(a: &LspDiagnostic, b: &LspDiagnostic) -> bool {
+    a.path == b.path && a.severity == b.severity && a.code == b.code && a.message == b.message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn reads_framed_publishes_and_reports_only_new_diagnostics() {
+        let publish = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": "file:///work/src/lib.rs",
+                "diagnostics": [
+                    {
+                        "range": { "start": { "line": 4, "character": 8 } },
+                        "severity": 1,
+                        "code": "E0308",
+                        "source": "rustc",
+                        "message": "mismatched types",
+                    },
+                    {
+                        "range": { "start": { "line": 9, "character": 0 } },
+                        "severity": 2,
+                        "message": "unused variable: `x`",
+                    },
+                    {
+                        "range": { "start": { "line": 1, "character": 0 } },
+                        "severity": 4,
+                        "message": "a hint",
+                    },
+                ],
+            },
+        })
+        .to_string();
+        let framed = format!("Content-Length: {}\r\n\r\n{publish}", publish.len());
+        let mut reader = framed.as_bytes();
+        let message = read_message(&mut reader).await.expect("read").expect("message");
+        assert!(read_message(&mut reader).await.expect("eof").is_none());
+
+        let malformed = [
+            "\r\n{}",
+            "Content-Length: 99999999999\r\n\r\n",
+            "Content-Length: x\r\n\r\n",
+        ];
+        for framed in malformed {
+            let mut reader = framed.as_bytes();
+            assert!(read_message(&mut reader).await.is_err(), "{framed:?}");
+        }
+
+        let (uri, diagnostics) =
+            parse_publish(&message["params"], Path::new("/work")).expect("publish");
+        assert_eq!(uri, "file:///work/src/lib.rs");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].path, "src/lib.rs");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (5, 9));
+        assert_eq!(diagnostics[0].code.as_deref(), Some("E0308"));
+
+        // The warning was there before (on another line); the error is new.
+        let mut moved = diagnostics[1].clone();
+        moved.line = 3;
+        let before = HashMap::from([(uri.clone(), vec![moved])]);
+        let after = HashMap::from([(uri.clone(), diagnostics.clone())]);
+        assert_eq!(new_diagnostics(&before, &after, &[]), vec![diagnostics[0].clone()]);
+
+        // A file published for the first time is baseline unless it was patched.
+        let before = HashMap::new();
+        assert_eq!(new_diagnostics(&before, &after, &[]), Vec::<LspDiagnostic>::new());
+        assert_eq!(new_diagnostics(&before, &after, &[uri]), diagnostics);
+    }
+}
@@ -0,0 +1,208 @@
+//! Local transcription of audio returned by MCP tools.
+//!
+//! When `mcp_audio_transcription` is configured, every `AudioContent` block
+//! in a tool result is written to a temporary file and passed to the
+//! configured command (typically a whisper CLI). The transcript is inserted as
+//! a text block right after the audio, so both the history cell and the model
+//! see it.
+
+use std::time::Duration;
+
+use base64::Engine;
+use mcp_types::AudioContent;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
+use tokio::process::Command;
+use tracing::warn;
+
+use crate::config_types::McpAudioTranscriptionConfig;
+
+const DEFAULT_TRANSCRIPTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Placeholder in the configured command replaced with the audio file path.
+const INPUT_PLACEHOLDER: &str = "{input}";
+
+/// Insert a transcript after each audio block in `result`. Failures are
+/// reported inline so the model knows the audio could not be read.
+pub(crate) async fn transcribe_audio_content(
+    config: &McpAudioTranscriptionConfig,
+    result: &mut CallToolResult,
+) {
+    if !result
+        .content
+        .iter()
+        .any(|block| matches!(block, ContentBlock::AudioContent(_)))
+    {
+        return;
+    }
+
+    let mut content = Vec::with_capacity(result.content.len() + 1);
+    for block in std::mem::take(&mut result.content) {
+        let transcript = match &block {
+            ContentBlock::AudioContent(audio) => Some(match transcribe(config, audio).await {
+                Ok(text) if text.is_empty() => "[audio transcript: no speech detected]".to_string(),
+                Ok(text) => format!("[audio transcript]\n{text}"),
+                Err(err) => {
+                    warn!("failed to transcribe MCP audio content: {err:#}");
+                    format!("[audio transcription failed: {err:#}]")
+                }
+            }),
+            _ => None,
+        };
+        content.push(block);
+        if let Some(text) = transcript {
+            content.push(ContentBlock::TextContent(TextContent {
+                annotations: None,
+                text,
+                r#type: "text".to_string(),
+            }));
+        }
+    }
+    result.content = content;
+}
+
+async fn transcribe(
+    config: &McpAudioTranscriptionConfig,
+    audio: &AudioContent,
+) -> anyhow::Result<String> {
+    let Some((program, args)) = config.command.split_first() else {
+        anyhow::bail!("`mcp_audio_transcription.command` is empty");
+    };
+
+    let bytes = base64::engine::general_purpose::STANDARD.decode(audio.data.trim())?;
+    let file = tempfile::Builder::new()
+        .prefix("code-mcp-audio-")
+        .suffix(&format!(".{}", extension_for_mime(&audio.mime_type)))
+        .tempfile()?;
+    tokio::fs::write(file.path(), &bytes).await?;
+    let input = file.path().display().to_string();
+
+    let mut args: Vec<String> = args
+        .iter()
+        .map(|arg| arg.replace(INPUT_PLACEHOLDER, &input))
+        .collect();
+    if !config
+        .command
+        .iter()
+        .any(|arg| arg.contains(INPUT_PLACEHOLDER))
+    {
+        args.push(input);
+    }
+
+    let timeout = config
+        .timeout_sec
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TRANSCRIPTION_TIMEOUT);
+    let output = tokio::time::timeout(
+        timeout,
+        Command::new(program).args(&args).kill_on_drop(true).output(),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("`{program}` timed out after {}s", timeout.as_secs()))??;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("`{program}` exited with {}: {}", output.status, stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn extension_for_mime(mime_type: &str) -> &'static str {
+    match mime_type.split(';').next().unwrap_or_default().trim() {
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "m4a",
+        "audio/ogg" | "audio/opus" => "ogg",
+        "audio/webm" => "webm",
+        "audio/flac" | "audio/x-flac" => "flac",
+        _ => "audio",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn audio_result() -> CallToolResult {
+        CallToolResult {
+            content: vec![
+                ContentBlock::TextContent(TextContent {
+                    annotations: None,
+                    text: "clip ready".to_string(),
+                    r#type: "text".to_string(),
+                }),
+                ContentBlock::AudioContent(AudioContent {
+                    annotations: None,
+                    data: base64::engine::general_purpose::STANDARD.encode(b"RIFF"),
+                    mime_type: "audio/wav".to_string(),
+                    r#type: "audio".to_string(),
+                }),
+            ],
+            is_error: None,
+            structured_content: None,
+        }
+    }
+
+    fn texts(result: &CallToolResult) -> Vec<String> {
+        result
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                ContentBlock::TextContent(text) => Some(text.text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn inserts_transcript_after_audio_block() {
+        let config = McpAudioTranscriptionConfig {
+            command: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "test -s \"$1\" && echo 'hello world'".to_string(),
+                "sh".to_string(),
+                INPUT_PLACEHOLDER.to_string(),
+            ],
+            timeout_sec: None,
+        };
+        let mut result = audio_result();
+
+        transcribe_audio_content(&config, &mut result).await;
+
+        assert_eq!(result.content.len(), 3);
+        assert!(matches!(result.content[1], ContentBlock::AudioContent(_)));
+        assert_eq!(
+            texts(&result),
+            vec![
+                "clip ready".to_string(),
+                "[audio transcript]\nhello world".to_string()
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn reports_failures_inline() {
+        let config = McpAudioTranscriptionConfig {
+            command: vec!["false".to_string()],
+            timeout_sec: None,
+        };
+        let mut result = audio_result();
+
+        transcribe_audio_content(&config, &mut result).await;
+
+        let texts = texts(&result);
+        assert_eq!(texts.len(), 2);
+        assert!(texts[1].starts_with("[audio transcription failed: `false` exited with"));
+    }
+
+    #[test]
+    fn maps_common_audio_mime_types() {
+        assert_eq!(extension_for_mime("audio/mpeg"), "mp3");
+        assert_eq!(extension_for_mime("audio/ogg; codecs=opus"), "ogg");
+        assert_eq!(extension_for_mime("application/octet-stream"), "audio");
+    }
+}
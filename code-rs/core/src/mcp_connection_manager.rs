@@ -4,32 +4,49 @@
 //! configured server (keyed by the *server name*). It offers convenience
 //! helpers to query the available tools across *all* servers and returns them
 //! in a single aggregated map using the fully-qualified tool name
-//! `"<server><MCP_TOOL_NAME_DELIMITER><tool>"` as the key.
+//! `"<server><MCP_TOOL_NAME_DELIMITER><tool>"` as the key, or the bare tool
+//! name when [`McpToolNaming::OnConflict`] is configured and it is unambiguous.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::ffi::OsString;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::PoisonError;
 use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
+use code_rmcp_client::McpCompletion;
+use code_rmcp_client::McpCompletionReference;
+use code_rmcp_client::McpServerLog;
+use code_rmcp_client::McpServerLogLine;
+use code_rmcp_client::McpToolCallProgress;
 use code_rmcp_client::RmcpClient;
 use mcp_types::ClientCapabilities;
 use mcp_types::Implementation;
+use mcp_types::InitializeResult;
+use mcp_types::ListToolsResult;
 use mcp_types::Tool;
 
 use serde_json::json;
 use sha1::Digest;
 use sha1::Sha1;
 use tokio::sync::RwLock;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
 use tokio::task::JoinSet;
 use tracing::info;
 use tracing::warn;
+use wildmatch::WildMatch;
 
 use crate::config_types::McpServerConfig;
+use crate::config_types::McpToolNaming;
 use crate::config_types::McpServerTransportConfig;
+use crate::mcp_tool_cache;
+use crate::mcp_tool_cache::McpToolCache;
+use crate::protocol::McpToolNameMapping;
 use crate::protocol::{McpServerFailure, McpServerFailurePhase};
 
 /// Delimiter used to separate the server name from the tool name in a fully
@@ -85,11 +102,76 @@ const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 /// Map that holds startup or tool-list errors for MCP servers.
 pub type ClientStartErrors = HashMap<String, McpServerFailure>;
 
-fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
+/// Built-in tool names an MCP tool may never shadow when exposed without a
+/// server prefix. Keep in sync with the names dispatched in
+/// `codex::streaming::handle_function_call`; a test checks that every tool
+/// `get_openai_tools` can emit is listed.
+const RESERVED_TOOL_NAMES: &[&str] = &[
+    "agent",
+    "apply_patch",
+    "browser",
+    "code_bridge",
+    "code_bridge_subscription",
+    "code_search",
+    "container.exec",
+    "exec_command",
+    "fetch_url",
+    "gh_run_wait",
+    "git_blame",
+    "git_log",
+    "git_show",
+    "goto_definition",
+    "image_view",
+    "kill",
+    "local_shell",
+    "read_file",
+    "request_user_input",
+    "search_tool_bm25",
+    "shell",
+    "shell_command",
+    "tool_search",
+    "update_plan",
+    "view_image",
+    "wait",
+    "web_fetch",
+    "web_search",
+    "write_stdin",
+];
+
+/// Pick the name each tool is exposed under.
+///
+/// With [`McpToolNaming::Qualified`] every tool is exposed as
+/// `server__tool`. With [`McpToolNaming::OnConflict`] a tool keeps its bare
+/// name unless another server offers the same name (or it would shadow a
+/// built-in); on conflict the first server listed in `priority` keeps the bare
+/// name and the rest are qualified.
+fn qualify_tools(
+    mut tools: Vec<ToolInfo>,
+    naming: McpToolNaming,
+    priority: &[String],
+) -> HashMap<String, ToolInfo> {
+    // Sort so hash-suffix disambiguation does not depend on the order in
+    // which servers answered.
+    tools.sort_by(|a, b| {
+        (&a.server_name, &a.tool_name).cmp(&(&b.server_name, &b.tool_name))
+    });
+
+    let mut providers: HashMap<String, Vec<String>> = HashMap::new();
+    for tool in &tools {
+        let servers = providers.entry(tool.tool_name.clone()).or_default();
+        if !servers.contains(&tool.server_name) {
+            servers.push(tool.server_name.clone());
+        }
+    }
+    for servers in providers.values_mut() {
+        servers.sort();
+    }
+
     let mut used_names = HashSet::new();
     let mut seen_raw_names = HashSet::new();
     let mut qualified_tools = HashMap::new();
-    for tool in tools {
+    for mut tool in tools {
+        let servers = providers.get(&tool.tool_name).cloned().unwrap_or_default();
         let qualified_name_raw = format!(
             "{}{}{}",
             tool.server_name, MCP_TOOL_NAME_DELIMITER, tool.tool_name
@@ -99,10 +181,27 @@ fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
             continue;
         }
 
+        let keeps_bare_name = naming == McpToolNaming::OnConflict
+            && !RESERVED_TOOL_NAMES.contains(&tool.tool_name.as_str())
+            && (servers.len() == 1
+                || priority
+                    .iter()
+                    .find(|server| servers.contains(server))
+                    .is_some_and(|winner| *winner == tool.server_name));
+        let exposed_name_raw = if keeps_bare_name {
+            tool.tool_name.clone()
+        } else {
+            qualified_name_raw.clone()
+        };
+        tool.shared_with = servers
+            .into_iter()
+            .filter(|server| *server != tool.server_name)
+            .collect();
+
         // Start from a "pretty" name (sanitized), then deterministically disambiguate on
         // collisions by appending a hash of the *raw* (unsanitized) qualified name. This
         // ensures tools like `foo.bar` and `foo_bar` don't collapse to the same key.
-        let mut qualified_name = sanitize_responses_api_tool_name(&qualified_name_raw);
+        let mut qualified_name = sanitize_responses_api_tool_name(&exposed_name_raw);
 
         // Enforce length constraints early; use the raw name for the hash input so the
         // output remains stable even when sanitization changes.
@@ -126,48 +225,120 @@ fn qualify_tools(tools: Vec<ToolInfo>) -> HashMap<String, ToolInfo> {
     qualified_tools
 }
 
+#[derive(Clone)]
 struct ToolInfo {
     server_name: String,
     tool_name: String,
     tool: Tool,
+    /// Whether every call must be approved by the user (`approval_required_tools`).
+    requires_approval: bool,
+    /// Other servers exposing a tool with the same name.
+    shared_with: Vec<String>,
 }
 
 struct ManagedClient {
     client: McpClientAdapter,
     startup_timeout: Duration,
-    tool_timeout: Option<Duration>,
+    tool_timeouts: ToolTimeouts,
     tool_filter: ToolFilter,
+    /// Tool cache key for this server build.
+    fingerprint: String,
+    /// Background `tools/list` confirming a listing served from the cache.
+    revalidation: Option<JoinHandle<Result<ListToolsResult>>>,
+    /// The server advertised the `completions` capability.
+    completions: bool,
+}
+
+/// Tools known for a server right after its handshake.
+enum InitialListing {
+    /// Served from the on-disk cache; revalidated in the background.
+    Cached(Vec<Tool>),
+    Listed(Result<ListToolsResult>),
+}
+
+/// Resolves the timeout for a call from `tool_timeouts_sec` overrides, falling
+/// back to the server-wide `tool_timeout_sec`.
+#[derive(Debug, Default, Clone)]
+struct ToolTimeouts {
+    default: Option<Duration>,
+    overrides: Vec<(WildMatch, Duration)>,
+}
+
+impl ToolTimeouts {
+    fn from_config(cfg: &McpServerConfig) -> Self {
+        let mut overrides: Vec<(String, Duration)> = cfg
+            .tool_timeouts_sec
+            .iter()
+            .flatten()
+            .filter_map(|(pattern, secs)| {
+                Duration::try_from_secs_f64(*secs)
+                    .ok()
+                    .map(|timeout| (pattern.clone(), timeout))
+            })
+            .collect();
+        // Exact names win over globs; otherwise the longer (more specific)
+        // pattern wins.
+        overrides.sort_by_key(|(pattern, _)| {
+            let is_glob = pattern.contains(['*', '?']);
+            (is_glob, std::cmp::Reverse(pattern.len()))
+        });
+
+        Self {
+            default: cfg.tool_timeout_sec,
+            overrides: overrides
+                .into_iter()
+                .map(|(pattern, timeout)| (WildMatch::new(&pattern), timeout))
+                .collect(),
+        }
+    }
+
+    fn for_tool(&self, tool_name: &str) -> Option<Duration> {
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| pattern.matches(tool_name))
+            .map(|(_, timeout)| *timeout)
+            .or(self.default)
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 struct ToolFilter {
-    enabled: Option<HashSet<String>>,
-    disabled: HashSet<String>,
+    enabled: Option<Vec<WildMatch>>,
+    disabled: Vec<WildMatch>,
+    approval_required: Vec<WildMatch>,
 }
 
 impl ToolFilter {
     fn from_config(cfg: &McpServerConfig) -> Self {
-        let enabled = cfg
-            .enabled_tools
-            .as_ref()
-            .map(|tools| tools.iter().cloned().collect::<HashSet<_>>());
-        let disabled = cfg
-            .disabled_tools
-            .as_ref()
-            .map(|tools| tools.iter().cloned().collect::<HashSet<_>>())
-            .unwrap_or_default();
+        fn patterns(tools: &[String]) -> Vec<WildMatch> {
+            tools.iter().map(|tool| WildMatch::new(tool)).collect()
+        }
 
-        Self { enabled, disabled }
+        Self {
+            enabled: cfg.enabled_tools.as_deref().map(patterns),
+            disabled: cfg.disabled_tools.as_deref().map(patterns).unwrap_or_default(),
+            approval_required: cfg
+                .approval_required_tools
+                .as_deref()
+                .map(patterns)
+                .unwrap_or_default(),
+        }
     }
 
     fn allows(&self, tool_name: &str) -> bool {
         if let Some(enabled) = &self.enabled
-            && !enabled.contains(tool_name)
+            && !enabled.iter().any(|pattern| pattern.matches(tool_name))
         {
             return false;
         }
 
-        !self.disabled.contains(tool_name)
+        !self.disabled.iter().any(|pattern| pattern.matches(tool_name))
+    }
+
+    fn requires_approval(&self, tool_name: &str) -> bool {
+        self.approval_required
+            .iter()
+            .any(|pattern| pattern.matches(tool_name))
     }
 }
 
@@ -183,13 +354,14 @@ impl McpClientAdapter {
         env: Option<HashMap<String, String>>,
         params: mcp_types::InitializeRequestParams,
         startup_timeout: Duration,
-    ) -> Result<Self> {
+        server_log: McpServerLog,
+    ) -> Result<(Self, InitializeResult)> {
         tracing::debug!(
             "new_stdio_client program: {program:?} args: {args:?} env: {env:?} params: {params:?} startup_timeout: {startup_timeout:?}"
         );
-        let client = Arc::new(RmcpClient::new_stdio_client(program, args, env).await?);
-        client.initialize(params, Some(startup_timeout)).await?;
-        Ok(McpClientAdapter::Rmcp(client))
+        let client = Arc::new(RmcpClient::new_stdio_client(program, args, env, server_log).await?);
+        let initialize_result = client.initialize(params, Some(startup_timeout)).await?;
+        Ok((McpClientAdapter::Rmcp(client), initialize_result))
     }
 
     async fn new_streamable_http_client(
@@ -200,36 +372,66 @@ impl McpClientAdapter {
         env_http_headers: Option<HashMap<String, String>>,
         params: mcp_types::InitializeRequestParams,
         startup_timeout: Duration,
-    ) -> Result<Self> {
+        server_log: McpServerLog,
+    ) -> Result<(Self, InitializeResult)> {
         let client = Arc::new(RmcpClient::new_streamable_http_client(
             url,
             bearer_token,
             bearer_token_env_var,
             http_headers,
             env_http_headers,
+            crate::http_client::configured_proxy(),
+            server_log,
         )?);
-        client.initialize(params, Some(startup_timeout)).await?;
-        Ok(McpClientAdapter::Rmcp(client))
+        let initialize_result = client.initialize(params, Some(startup_timeout)).await?;
+        Ok((McpClientAdapter::Rmcp(client), initialize_result))
     }
 
     async fn list_tools(
         &self,
         params: Option<mcp_types::ListToolsRequestParams>,
         timeout: Option<Duration>,
-    ) -> Result<mcp_types::ListToolsResult> {
+    ) -> Result<ListToolsResult> {
         match self {
             McpClientAdapter::Rmcp(client) => client.list_tools(params, timeout).await,
         }
     }
 
+    fn take_tools_changed(&self) -> bool {
+        match self {
+            McpClientAdapter::Rmcp(client) => client.take_tools_changed(),
+        }
+    }
+
     async fn call_tool(
         &self,
         name: String,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+        progress_tx: Option<UnboundedSender<McpToolCallProgress>>,
     ) -> Result<mcp_types::CallToolResult> {
         match self {
-            McpClientAdapter::Rmcp(client) => client.call_tool(name, arguments, timeout).await,
+            McpClientAdapter::Rmcp(client) => {
+                client
+                    .call_tool_with_progress(name, arguments, timeout, progress_tx)
+                    .await
+            }
+        }
+    }
+
+    async fn complete(
+        &self,
+        reference: McpCompletionReference,
+        argument_name: String,
+        argument_value: String,
+        timeout: Option<Duration>,
+    ) -> Result<McpCompletion> {
+        match self {
+            McpClientAdapter::Rmcp(client) => {
+                client
+                    .complete(reference, argument_name, argument_value, timeout)
+                    .await
+            }
         }
     }
 
@@ -242,6 +444,16 @@ impl McpClientAdapter {
     }
 }
 
+/// Session-level settings for [`McpConnectionManager::new_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct McpManagerOptions {
+    pub tool_naming: McpToolNaming,
+    /// Servers that keep a conflicting bare tool name, highest priority first.
+    pub tool_priority: Vec<String>,
+    /// Directory for cached `tools/list` results; `None` disables the cache.
+    pub tool_cache_dir: Option<PathBuf>,
+}
+
 /// A thin wrapper around a set of running [`RmcpClient`] instances.
 #[derive(Default)]
 pub struct McpConnectionManager {
@@ -251,10 +463,19 @@ pub struct McpConnectionManager {
     /// the user configuration.
     clients: RwLock<HashMap<String, ManagedClient>>,
 
-    /// Fully qualified tool name -> tool instance.
-    tools: HashMap<String, ToolInfo>,
+    /// Exposed tool name -> tool instance. Replaced when a server's tool list
+    /// is refreshed.
+    tools: std::sync::RwLock<HashMap<String, ToolInfo>>,
     server_names: Vec<String>,
-    failures: HashMap<String, McpServerFailure>,
+    failures: std::sync::RwLock<HashMap<String, McpServerFailure>>,
+
+    /// Server-name -> recent stderr/log output, kept for failed servers too.
+    server_logs: HashMap<String, McpServerLog>,
+
+    excluded_tools: HashSet<(String, String)>,
+    tool_naming: McpToolNaming,
+    tool_priority: Vec<String>,
+    tool_cache: Option<McpToolCache>,
 }
 
 impl McpConnectionManager {
@@ -269,15 +490,34 @@ impl McpConnectionManager {
     pub async fn new(
         mcp_servers: HashMap<String, McpServerConfig>,
         excluded_tools: HashSet<(String, String)>,
+    ) -> Result<(Self, ClientStartErrors)> {
+        Self::new_with_options(mcp_servers, excluded_tools, McpManagerOptions::default()).await
+    }
+
+    /// Like [`Self::new`], with control over tool naming and caching.
+    pub async fn new_with_options(
+        mcp_servers: HashMap<String, McpServerConfig>,
+        excluded_tools: HashSet<(String, String)>,
+        options: McpManagerOptions,
     ) -> Result<(Self, ClientStartErrors)> {
         // Early exit if no servers are configured.
         if mcp_servers.is_empty() {
             return Ok((Self::default(), ClientStartErrors::default()));
         }
 
-        // Launch all configured servers concurrently.
+        let McpManagerOptions {
+            tool_naming,
+            tool_priority,
+            tool_cache_dir,
+        } = options;
+        let tool_cache = tool_cache_dir.map(McpToolCache::new);
+
+        // Launch all configured servers concurrently. Each server lists its
+        // tools right after its own handshake, so a slow server does not hold
+        // up the others.
         let mut join_set = JoinSet::new();
         let mut errors = ClientStartErrors::new();
+        let mut server_logs: HashMap<String, McpServerLog> = HashMap::new();
 
         for (server_name, cfg) in mcp_servers {
             // Validate server name before spawning
@@ -293,11 +533,15 @@ impl McpConnectionManager {
             }
 
             let startup_timeout = cfg.startup_timeout_sec.unwrap_or(DEFAULT_STARTUP_TIMEOUT);
-            let tool_timeout = cfg.tool_timeout_sec;
+            let tool_timeouts = ToolTimeouts::from_config(&cfg);
             let tool_filter = ToolFilter::from_config(&cfg);
+            let server_log = McpServerLog::default();
+            server_logs.insert(server_name.clone(), server_log.clone());
+            let tool_cache = tool_cache.clone();
 
             join_set.spawn(async move {
                 let McpServerConfig { transport, .. } = cfg;
+                let transport_for_cache = transport.clone();
                 let server_name_for_error = server_name.clone();
                 let params = mcp_types::InitializeRequestParams {
                     capabilities: ClientCapabilities {
@@ -320,7 +564,7 @@ impl McpConnectionManager {
                     protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
                 };
 
-                let client = match transport {
+                let started = match transport {
                     McpServerTransportConfig::Stdio { command, args, env } => {
                         let command_for_error = command.clone();
                         let args_for_error = args.clone();
@@ -332,6 +576,7 @@ impl McpConnectionManager {
                             env,
                             params.clone(),
                             startup_timeout,
+                            server_log,
                         )
                         .await
                         .with_context(|| {
@@ -364,20 +609,42 @@ impl McpConnectionManager {
                             env_http_headers,
                             params,
                             startup_timeout,
+                            server_log,
                         )
                         .await
                     }
-                }
-                .map(|c| (c, startup_timeout));
+                };
+
+                let started = match started {
+                    Ok((client, initialize_result)) => {
+                        let fingerprint = mcp_tool_cache::fingerprint(
+                            &transport_for_cache,
+                            &initialize_result.server_info,
+                        );
+                        let cached = tool_cache
+                            .as_ref()
+                            .and_then(|cache| cache.load(&server_name, &fingerprint));
+                        let listing = match cached {
+                            Some(tools) => InitialListing::Cached(tools),
+                            None => InitialListing::Listed(
+                                client.list_tools(None, Some(startup_timeout)).await,
+                            ),
+                        };
+                        let completions = initialize_result.capabilities.completions.is_some();
+                        Ok((client, fingerprint, listing, completions))
+                    }
+                    Err(e) => Err(e),
+                };
 
-                ((server_name, tool_timeout, tool_filter), client)
+                ((server_name, startup_timeout, tool_timeouts, tool_filter), started)
             });
         }
 
         let mut clients: HashMap<String, ManagedClient> = HashMap::with_capacity(join_set.len());
+        let mut all_tools: Vec<ToolInfo> = Vec::new();
 
         while let Some(res) = join_set.join_next().await {
-            let ((server_name, tool_timeout, tool_filter), client_res) = match res {
+            let ((server_name, startup_timeout, tool_timeouts, tool_filter), started) = match res {
                 Ok(result) => result,
                 Err(e) => {
                     warn!("Task panic when starting MCP server: {e:#}");
@@ -385,18 +652,8 @@ impl McpConnectionManager {
                 }
             };
 
-            match client_res {
-                Ok((client, startup_timeout)) => {
-                    clients.insert(
-                        server_name,
-                        ManagedClient {
-                            client,
-                            startup_timeout,
-                            tool_timeout,
-                            tool_filter,
-                        },
-                    );
-                }
+            let (client, fingerprint, listing, completions) = match started {
+                Ok(started) => started,
                 Err(e) => {
                     let message = format!("server '{server_name}': {e:#}");
                     errors.insert(
@@ -406,13 +663,63 @@ impl McpConnectionManager {
                             message,
                         },
                     );
+                    continue;
+                }
+            };
+
+            let mut revalidation = None;
+            match listing {
+                InitialListing::Cached(tools) => {
+                    all_tools.extend(tool_infos(&server_name, tools, &tool_filter, &excluded_tools));
+                    let client = client.clone();
+                    revalidation = Some(tokio::spawn(async move {
+                        client.list_tools(None, Some(startup_timeout)).await
+                    }));
+                }
+                InitialListing::Listed(Ok(result)) => {
+                    if let Some(cache) = &tool_cache {
+                        cache.store(&server_name, &fingerprint, &result.tools);
+                    }
+                    all_tools.extend(tool_infos(
+                        &server_name,
+                        result.tools,
+                        &tool_filter,
+                        &excluded_tools,
+                    ));
+                }
+                InitialListing::Listed(Err(err)) => {
+                    warn!("Failed to list tools for MCP server '{server_name}': {err:#?}");
+                    errors.insert(
+                        server_name.clone(),
+                        McpServerFailure {
+                            phase: McpServerFailurePhase::ListTools,
+                            message: format!("{err:#}"),
+                        },
+                    );
                 }
             }
+
+            clients.insert(
+                server_name,
+                ManagedClient {
+                    client,
+                    startup_timeout,
+                    tool_timeouts,
+                    tool_filter,
+                    fingerprint,
+                    revalidation,
+                    completions,
+                },
+            );
         }
 
-        let all_tools = list_all_tools(&clients, &excluded_tools, &mut errors).await;
+        info!(
+            "aggregated {} tools from {} servers",
+            all_tools.len(),
+            clients.len()
+        );
 
-        let tools = qualify_tools(all_tools);
+        let tools = qualify_tools(all_tools, tool_naming, &tool_priority);
 
         let mut server_names: Vec<String> = clients.keys().cloned().collect();
         server_names.sort();
@@ -420,23 +727,32 @@ impl McpConnectionManager {
 
         Ok((Self {
             clients: RwLock::new(clients),
-            tools,
+            tools: std::sync::RwLock::new(tools),
             server_names,
-            failures,
+            failures: std::sync::RwLock::new(failures),
+            server_logs,
+            excluded_tools,
+            tool_naming,
+            tool_priority,
+            tool_cache,
         }, errors))
     }
 
+    fn tools(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, ToolInfo>> {
+        self.tools.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
     /// Returns a single map that contains **all** tools. Each key is the
     /// fully-qualified name for the tool.
     pub fn list_all_tools(&self) -> HashMap<String, Tool> {
-        self.tools
+        self.tools()
             .iter()
             .map(|(name, tool)| (name.clone(), tool.tool.clone()))
             .collect()
     }
 
     pub fn list_all_tools_with_server_names(&self) -> Vec<(String, String, Tool)> {
-        self.tools
+        self.tools()
             .iter()
             .map(|(qualified_name, tool_info)| {
                 (
@@ -450,7 +766,7 @@ impl McpConnectionManager {
 
     pub fn list_tools_by_server(&self) -> HashMap<String, Vec<String>> {
         let mut tools_by_server: HashMap<String, Vec<String>> = HashMap::new();
-        for tool in self.tools.values() {
+        for tool in self.tools().values() {
             tools_by_server
                 .entry(tool.server_name.clone())
                 .or_default()
@@ -470,7 +786,153 @@ impl McpConnectionManager {
     }
 
     pub fn list_server_failures(&self) -> HashMap<String, McpServerFailure> {
-        self.failures.clone()
+        self.failures
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the buffered stderr/log output for `server`, oldest first, or
+    /// `None` when no server with that name was configured.
+    pub fn server_logs(&self, server: &str) -> Option<Vec<McpServerLogLine>> {
+        self.server_logs.get(server).map(McpServerLog::snapshot)
+    }
+
+    /// Bring tool lists up to date before a turn: re-list servers that sent
+    /// `notifications/tools/list_changed`, and adopt finished background
+    /// revalidations of cached listings. Revalidations still in flight are
+    /// left alone so the turn never waits on them.
+    pub async fn refresh_changed_tools(&self) {
+        let mut changed = Vec::new();
+        let mut revalidated = Vec::new();
+        {
+            let mut clients = self.clients.write().await;
+            for (server_name, managed) in clients.iter_mut() {
+                if managed.client.take_tools_changed() {
+                    // A fresh listing supersedes any revalidation in flight.
+                    if let Some(handle) = managed.revalidation.take() {
+                        handle.abort();
+                    }
+                    changed.push(server_name.clone());
+                } else if let Some(handle) =
+                    managed.revalidation.take_if(|handle| handle.is_finished())
+                {
+                    revalidated.push((server_name.clone(), handle));
+                }
+            }
+        }
+
+        for (server_name, handle) in revalidated {
+            match handle.await {
+                Ok(Ok(result)) => {
+                    let _ = self.apply_listing(&server_name, Ok(result)).await;
+                }
+                // Keep serving the cached listing; `/mcp refresh` retries.
+                Ok(Err(err)) => {
+                    warn!("Failed to revalidate cached tools for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when revalidating tools for MCP server '{server_name}': {err:#}");
+                }
+            }
+        }
+
+        if !changed.is_empty() {
+            self.relist_tools(changed).await;
+        }
+    }
+
+    /// Re-list tools for `server` (or every running server), bypassing the
+    /// cache. Returns the number of tools now exposed per server.
+    pub async fn refresh_tools(&self, server: Option<&str>) -> Result<Vec<(String, Result<usize>)>> {
+        let server_names: Vec<String> = {
+            let clients = self.clients.read().await;
+            match server {
+                Some(server) if clients.contains_key(server) => vec![server.to_string()],
+                Some(server) => return Err(anyhow!("unknown MCP server '{server}'")),
+                None => clients.keys().cloned().collect(),
+            }
+        };
+        let mut results = self.relist_tools(server_names).await;
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(results)
+    }
+
+    async fn relist_tools(&self, server_names: Vec<String>) -> Vec<(String, Result<usize>)> {
+        let requests: Vec<(String, McpClientAdapter, Duration)> = {
+            let clients = self.clients.read().await;
+            server_names
+                .into_iter()
+                .filter_map(|server_name| {
+                    let managed = clients.get(&server_name)?;
+                    Some((server_name, managed.client.clone(), managed.startup_timeout))
+                })
+                .collect()
+        };
+
+        let listings = futures::future::join_all(requests.into_iter().map(
+            |(server_name, client, startup_timeout)| async move {
+                let result = client.list_tools(None, Some(startup_timeout)).await;
+                (server_name, result)
+            },
+        ))
+        .await;
+
+        let mut results = Vec::with_capacity(listings.len());
+        for (server_name, result) in listings {
+            let applied = self.apply_listing(&server_name, result).await;
+            results.push((server_name, applied));
+        }
+        results
+    }
+
+    /// Adopt a `tools/list` result for `server`: update the cache, swap its
+    /// tools and re-resolve exposed names across all servers.
+    async fn apply_listing(
+        &self,
+        server: &str,
+        result: Result<ListToolsResult>,
+    ) -> Result<usize> {
+        let (tool_filter, fingerprint) = {
+            let clients = self.clients.read().await;
+            let managed = clients
+                .get(server)
+                .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
+            (managed.tool_filter.clone(), managed.fingerprint.clone())
+        };
+
+        let mut failures = self.failures.write().unwrap_or_else(PoisonError::into_inner);
+        let result = match result {
+            Ok(result) => result,
+            Err(err) => {
+                warn!("Failed to list tools for MCP server '{server}': {err:#?}");
+                failures.insert(
+                    server.to_string(),
+                    McpServerFailure {
+                        phase: McpServerFailurePhase::ListTools,
+                        message: format!("{err:#}"),
+                    },
+                );
+                return Err(err);
+            }
+        };
+        failures.remove(server);
+
+        if let Some(cache) = &self.tool_cache {
+            cache.store(server, &fingerprint, &result.tools);
+        }
+        let server_tools = tool_infos(server, result.tools, &tool_filter, &self.excluded_tools);
+        let count = server_tools.len();
+
+        let mut tools = self.tools.write().unwrap_or_else(PoisonError::into_inner);
+        let mut all_tools: Vec<ToolInfo> = tools
+            .drain()
+            .map(|(_, info)| info)
+            .filter(|info| info.server_name != server)
+            .collect();
+        all_tools.extend(server_tools);
+        *tools = qualify_tools(all_tools, self.tool_naming, &self.tool_priority);
+        Ok(count)
     }
 
     /// Invoke the tool indicated by the (server, tool) pair.
@@ -480,24 +942,99 @@ impl McpConnectionManager {
         tool: &str,
         arguments: Option<serde_json::Value>,
         timeout_override: Option<Duration>,
+    ) -> Result<mcp_types::CallToolResult> {
+        self.call_tool_with_progress(server, tool, arguments, timeout_override, None)
+            .await
+    }
+
+    /// Invoke the tool indicated by the (server, tool) pair, forwarding any
+    /// progress notifications the server emits for this call to `progress_tx`.
+    pub async fn call_tool_with_progress(
+        &self,
+        server: &str,
+        tool: &str,
+        arguments: Option<serde_json::Value>,
+        timeout_override: Option<Duration>,
+        progress_tx: Option<UnboundedSender<McpToolCallProgress>>,
     ) -> Result<mcp_types::CallToolResult> {
         let (client, timeout) = {
             let clients = self.clients.read().await;
             let managed = clients
                 .get(server)
                 .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
-            let timeout = timeout_override.or(managed.tool_timeout);
+            let timeout = timeout_override.or_else(|| managed.tool_timeouts.for_tool(tool));
             (managed.client.clone(), timeout)
         };
 
         client
-            .call_tool(tool.to_string(), arguments, timeout)
+            .call_tool(tool.to_string(), arguments, timeout, progress_tx)
             .await
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))
     }
 
+    /// Request completions for an argument of a prompt or resource template
+    /// exposed by `server`. Fails without a request when the server did not
+    /// advertise the `completions` capability. Completions are interactive,
+    /// so the server's startup timeout bounds the request rather than the
+    /// tool timeout.
+    pub async fn complete_argument(
+        &self,
+        server: &str,
+        reference: McpCompletionReference,
+        argument_name: String,
+        argument_value: String,
+    ) -> Result<McpCompletion> {
+        let (client, timeout) = {
+            let clients = self.clients.read().await;
+            let managed = clients
+                .get(server)
+                .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
+            if !managed.completions {
+                return Err(anyhow!("MCP server '{server}' does not support argument completion"));
+            }
+            (managed.client.clone(), managed.startup_timeout)
+        };
+
+        client
+            .complete(reference, argument_name, argument_value, Some(timeout))
+            .await
+            .with_context(|| format!("completion request failed for `{server}`"))
+    }
+
+    /// Returns true when the server config lists this tool under
+    /// `approval_required_tools`.
+    pub fn tool_requires_approval(&self, server: &str, tool: &str) -> bool {
+        self.tools()
+            .values()
+            .any(|info| info.requires_approval && info.server_name == server && info.tool_name == tool)
+    }
+
+    /// Returns the raw `inputSchema` declared by `server` for `tool`.
+    pub fn tool_input_schema(&self, server: &str, tool: &str) -> Option<serde_json::Value> {
+        self.tools()
+            .values()
+            .find(|info| info.server_name == server && info.tool_name == tool)
+            .and_then(|info| serde_json::to_value(&info.tool.input_schema).ok())
+    }
+
+    /// Exposed name -> originating server/tool, sorted by exposed name.
+    pub fn tool_name_mappings(&self) -> Vec<McpToolNameMapping> {
+        let mut mappings: Vec<McpToolNameMapping> = self
+            .tools()
+            .iter()
+            .map(|(name, info)| McpToolNameMapping {
+                name: name.clone(),
+                server: info.server_name.clone(),
+                tool: info.tool_name.clone(),
+                shared_with: info.shared_with.clone(),
+            })
+            .collect();
+        mappings.sort_by(|a, b| a.name.cmp(&b.name));
+        mappings
+    }
+
     pub fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
-        self.tools
+        self.tools()
             .get(tool_name)
             .map(|tool| (tool.server_name.clone(), tool.tool_name.clone()))
     }
@@ -516,82 +1053,32 @@ impl McpConnectionManager {
 
 impl ManagedClient {
     async fn shutdown(self) {
+        if let Some(handle) = self.revalidation {
+            handle.abort();
+        }
         self.client.into_shutdown().await;
     }
 }
 
-/// Query every server for its available tools and return a single map that
-/// contains **all** tools. Each key is the fully-qualified name for the tool.
-async fn list_all_tools(
-    clients: &HashMap<String, ManagedClient>,
+/// Apply the server's tool filter and `excluded_tools` to a `tools/list` result.
+fn tool_infos(
+    server_name: &str,
+    tools: Vec<Tool>,
+    tool_filter: &ToolFilter,
     excluded_tools: &HashSet<(String, String)>,
-    errors: &mut ClientStartErrors,
 ) -> Vec<ToolInfo> {
-    let mut join_set = JoinSet::new();
-
-    // Spawn one task per server so we can query them concurrently. This
-    // keeps the overall latency roughly at the slowest server instead of
-    // the cumulative latency.
-    for (server_name, managed_client) in clients {
-        let server_name_cloned = server_name.clone();
-        let client_clone = managed_client.client.clone();
-        let startup_timeout = managed_client.startup_timeout;
-        let tool_filter = managed_client.tool_filter.clone();
-        join_set.spawn(async move {
-            let res = client_clone.list_tools(None, Some(startup_timeout)).await;
-            (server_name_cloned, tool_filter, res)
-        });
-    }
-
-    let mut aggregated: Vec<ToolInfo> = Vec::with_capacity(join_set.len());
-
-    while let Some(join_res) = join_set.join_next().await {
-        let (server_name, tool_filter, list_result) = if let Ok(result) = join_res {
-            result
-        } else {
-            warn!("Task panic when listing tools for MCP server: {join_res:#?}");
-            continue;
-        };
-
-        match list_result {
-            Ok(result) => {
-                for tool in result.tools {
-                    if !tool_filter.allows(&tool.name) {
-                        continue;
-                    }
-                    if excluded_tools.contains(&(server_name.clone(), tool.name.clone())) {
-                        continue;
-                    }
-                    let tool_info = ToolInfo {
-                        server_name: server_name.clone(),
-                        tool_name: tool.name.clone(),
-                        tool,
-                    };
-                    aggregated.push(tool_info);
-                }
-            }
-            Err(err) => {
-                warn!(
-                    "Failed to list tools for MCP server '{server_name}': {err:#?}"
-                );
-                errors.insert(
-                    server_name,
-                    McpServerFailure {
-                        phase: McpServerFailurePhase::ListTools,
-                        message: format!("{err:#}"),
-                    },
-                );
-            }
-        }
-    }
-
-    info!(
-        "aggregated {} tools from {} servers",
-        aggregated.len(),
-        clients.len()
-    );
-
-    aggregated
+    tools
+        .into_iter()
+        .filter(|tool| tool_filter.allows(&tool.name))
+        .filter(|tool| !excluded_tools.contains(&(server_name.to_string(), tool.name.clone())))
+        .map(|tool| ToolInfo {
+            server_name: server_name.to_string(),
+            tool_name: tool.name.clone(),
+            requires_approval: tool_filter.requires_approval(&tool.name),
+            tool,
+            shared_with: Vec::new(),
+        })
+        .collect()
 }
 
 fn is_valid_mcp_server_name(server_name: &str) -> bool {
@@ -608,8 +1095,9 @@ mod tests {
 
     fn filter(enabled: Option<&[&str]>, disabled: &[&str]) -> ToolFilter {
         ToolFilter {
-            enabled: enabled.map(|tools| tools.iter().map(|tool| (*tool).to_string()).collect()),
-            disabled: disabled.iter().map(|tool| (*tool).to_string()).collect(),
+            enabled: enabled.map(|tools| tools.iter().map(|tool| WildMatch::new(tool)).collect()),
+            disabled: disabled.iter().map(|tool| WildMatch::new(tool)).collect(),
+            approval_required: Vec::new(),
         }
     }
 
@@ -629,6 +1117,8 @@ mod tests {
                 output_schema: None,
                 title: None,
             },
+            requires_approval: false,
+            shared_with: Vec::new(),
         }
     }
 
@@ -639,7 +1129,7 @@ mod tests {
             create_test_tool("server1", "tool2"),
         ];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, McpToolNaming::Qualified, &[]);
 
         assert_eq!(qualified_tools.len(), 2);
         assert!(qualified_tools.contains_key("server1__tool1"));
@@ -653,7 +1143,7 @@ mod tests {
             create_test_tool("server1", "duplicate_tool"),
         ];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, McpToolNaming::Qualified, &[]);
 
         // Only the first tool should remain, the second is skipped
         assert_eq!(qualified_tools.len(), 1);
@@ -675,7 +1165,7 @@ mod tests {
             ),
         ];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, McpToolNaming::Qualified, &[]);
 
         assert_eq!(qualified_tools.len(), 2);
 
@@ -699,7 +1189,7 @@ mod tests {
     fn test_qualify_tools_sanitizes_invalid_characters() {
         let tools = vec![create_test_tool("server.one", "tool.two")];
 
-        let qualified_tools = qualify_tools(tools);
+        let qualified_tools = qualify_tools(tools, McpToolNaming::Qualified, &[]);
 
         assert_eq!(qualified_tools.len(), 1);
         let (qualified_name, tool) = qualified_tools.into_iter().next().expect("one tool");
@@ -717,6 +1207,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn on_conflict_naming_keeps_unique_names_bare() {
+        let tools = vec![
+            create_test_tool("github", "search"),
+            create_test_tool("gitlab", "search"),
+            create_test_tool("github", "create_issue"),
+            create_test_tool("files", "shell"),
+        ];
+
+        let qualified_tools = qualify_tools(tools, McpToolNaming::OnConflict, &[]);
+
+        let mut keys: Vec<_> = qualified_tools.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "create_issue".to_string(),
+                "files__shell".to_string(),
+                "github__search".to_string(),
+                "gitlab__search".to_string(),
+            ]
+        );
+        assert_eq!(qualified_tools["github__search"].shared_with, vec!["gitlab".to_string()]);
+        assert!(qualified_tools["create_issue"].shared_with.is_empty());
+    }
+
+    #[test]
+    fn on_conflict_naming_gives_bare_name_to_priority_server() {
+        let tools = vec![
+            create_test_tool("github", "search"),
+            create_test_tool("gitlab", "search"),
+        ];
+        let priority = vec!["docs".to_string(), "gitlab".to_string()];
+
+        let qualified_tools = qualify_tools(tools, McpToolNaming::OnConflict, &priority);
+
+        assert_eq!(qualified_tools["search"].server_name, "gitlab");
+        assert_eq!(qualified_tools["github__search"].server_name, "github");
+    }
+
     #[test]
     fn tool_filter_allows_all_tools_by_default() {
         let filter = ToolFilter::default();
@@ -738,6 +1268,61 @@ mod tests {
         assert!(!filter.allows("delete_repo"));
     }
 
+    #[test]
+    fn tool_filter_matches_glob_patterns() {
+        let filter = filter(Some(&["read_*", "search_?ode"]), &["read_secrets"]);
+        assert!(filter.allows("read_file"));
+        assert!(filter.allows("search_code"));
+        assert!(!filter.allows("read_secrets"));
+        assert!(!filter.allows("write_file"));
+    }
+
+    #[test]
+    fn tool_filter_marks_approval_required_tools() {
+        let filter = ToolFilter {
+            approval_required: vec![WildMatch::new("delete_*"), WildMatch::new("push")],
+            ..ToolFilter::default()
+        };
+        assert!(filter.requires_approval("delete_repo"));
+        assert!(filter.requires_approval("push"));
+        assert!(!filter.requires_approval("list_issues"));
+        assert!(filter.allows("delete_repo"));
+    }
+
+    #[test]
+    fn tool_timeouts_prefer_exact_then_specific_patterns() {
+        let cfg = McpServerConfig {
+            transport: McpServerTransportConfig::Stdio {
+                command: "server".to_string(),
+                args: Vec::new(),
+                env: None,
+            },
+            startup_timeout_sec: None,
+            tool_timeout_sec: Some(Duration::from_secs(60)),
+            enabled_tools: None,
+            disabled_tools: None,
+            approval_required_tools: None,
+            tool_timeouts_sec: Some(HashMap::from([
+                ("*".to_string(), 30.0),
+                ("build_*".to_string(), 600.0),
+                ("build_docs".to_string(), 5.0),
+            ])),
+        };
+        let timeouts = ToolTimeouts::from_config(&cfg);
+        assert_eq!(timeouts.for_tool("build_docs"), Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.for_tool("build_app"), Some(Duration::from_secs(600)));
+        assert_eq!(timeouts.for_tool("search"), Some(Duration::from_secs(30)));
+
+        let cfg = McpServerConfig {
+            tool_timeouts_sec: None,
+            ..cfg
+        };
+        assert_eq!(
+            ToolTimeouts::from_config(&cfg).for_tool("search"),
+            Some(Duration::from_secs(60))
+        );
+    }
+
     #[tokio::test]
     async fn stdio_spawn_error_mentions_server_and_command() {
         let mut servers = HashMap::new();
@@ -753,6 +1338,8 @@ mod tests {
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                approval_required_tools: None,
+                tool_timeouts_sec: None,
             },
         );
 
@@ -771,4 +1358,60 @@ mod tests {
             "error should include the missing command, got: {msg}"
         );
     }
+
+    #[test]
+    fn reserved_tool_names_cover_builtin_tools() {
+        use crate::model_family::find_family_for_model;
+        use crate::openai_tools::ConfigShellToolType;
+        use crate::openai_tools::OpenAiTool;
+        use crate::openai_tools::ToolsConfig;
+        use crate::openai_tools::get_openai_tools;
+        use crate::protocol::AskForApproval;
+        use crate::protocol::SandboxPolicy;
+
+        let model_family = find_family_for_model("o3").expect("o3 should be a valid model family");
+        let mut config = ToolsConfig::new(
+            &model_family,
+            AskForApproval::Never,
+            SandboxPolicy::ReadOnly,
+            true,
+            true,
+            true,
+            false,
+            true,
+        );
+        config.search_tool = true;
+        config.fetch_url_tool = true;
+        config.read_file_tool = true;
+        config.code_index_tools = true;
+        config.git_history_tools = true;
+        config.web_search_backend = true;
+
+        let shell_types = [
+            ConfigShellToolType::DefaultShell,
+            ConfigShellToolType::ShellCommand {
+                sandbox_policy: SandboxPolicy::ReadOnly,
+            },
+            ConfigShellToolType::LocalShell,
+            ConfigShellToolType::StreamableShell,
+        ];
+        let mut missing = Vec::new();
+        for shell_type in shell_types {
+            config.shell_type = shell_type;
+            for tool in get_openai_tools(&config, None, true, false, &[]) {
+                let name = match tool {
+                    OpenAiTool::Function(tool) => tool.name,
+                    OpenAiTool::Freeform(tool) => tool.name,
+                    OpenAiTool::LocalShell {} => "local_shell".to_string(),
+                    OpenAiTool::WebSearch(_) => "web_search".to_string(),
+                    OpenAiTool::ToolSearch { .. } => "tool_search".to_string(),
+                    OpenAiTool::Namespace(_) | OpenAiTool::ImageGeneration { .. } => continue,
+                };
+                if !RESERVED_TOOL_NAMES.contains(&name.as_str()) && !missing.contains(&name) {
+                    missing.push(name);
+                }
+            }
+        }
+        assert_eq!(missing, Vec::<String>::new());
+    }
 }
@@ -0,0 +1,111 @@
+//! On-disk cache of `tools/list` results, one file per MCP server.
+//!
+//! Entries are keyed by a fingerprint of the server's transport config and
+//! the name/version it reports during `initialize`, so editing the config or
+//! upgrading the server invalidates the entry. A cached listing only spares
+//! startup the round trip; the manager still revalidates it in the
+//! background.
+
+use std::fs;
+use std::path::PathBuf;
+
+use mcp_types::Implementation;
+use mcp_types::Tool;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use tracing::warn;
+
+use crate::config_types::McpServerTransportConfig;
+
+#[derive(Debug, Clone)]
+pub(crate) struct McpToolCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedToolList {
+    fingerprint: String,
+    tools: Vec<Tool>,
+}
+
+impl McpToolCache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub(crate) fn load(&self, server: &str, fingerprint: &str) -> Option<Vec<Tool>> {
+        let contents = fs::read_to_string(self.path(server)).ok()?;
+        let cached: CachedToolList = serde_json::from_str(&contents).ok()?;
+        (cached.fingerprint == fingerprint).then_some(cached.tools)
+    }
+
+    pub(crate) fn store(&self, server: &str, fingerprint: &str, tools: &[Tool]) {
+        let cached = CachedToolList {
+            fingerprint: fingerprint.to_string(),
+            tools: tools.to_vec(),
+        };
+        let result = fs::create_dir_all(&self.dir).and_then(|()| {
+            let contents = serde_json::to_string(&cached).map_err(std::io::Error::other)?;
+            fs::write(self.path(server), contents)
+        });
+        if let Err(err) = result {
+            warn!("failed to cache tools for MCP server '{server}': {err}");
+        }
+    }
+
+    fn path(&self, server: &str) -> PathBuf {
+        // Server names are validated to `^[a-zA-Z0-9_-]+$`, so they are safe
+        // as file names.
+        self.dir.join(format!("{server}.json"))
+    }
+}
+
+/// Identify a server build: same transport config and same reported version.
+pub(crate) fn fingerprint(
+    transport: &McpServerTransportConfig,
+    server_info: &Implementation,
+) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(serde_json::to_string(transport).unwrap_or_default().as_bytes());
+    hasher.update([0]);
+    hasher.update(server_info.name.as_bytes());
+    hasher.update([0]);
+    hasher.update(server_info.version.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::ToolInputSchema;
+
+    fn tool(name: &str) -> Tool {
+        Tool {
+            annotations: None,
+            description: None,
+            input_schema: ToolInputSchema {
+                properties: None,
+                required: None,
+                r#type: "object".to_string(),
+            },
+            name: name.to_string(),
+            output_schema: None,
+            title: None,
+        }
+    }
+
+    #[test]
+    fn load_requires_matching_fingerprint() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = McpToolCache::new(dir.path().join("mcp_tools"));
+        cache.store("docs", "v1", &[tool("search")]);
+
+        let tools = cache.load("docs", "v1").expect("cached tools");
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "search");
+        assert!(cache.load("docs", "v2").is_none());
+        assert!(cache.load("other", "v1").is_none());
+    }
+}
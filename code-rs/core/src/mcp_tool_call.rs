@@ -1,17 +1,25 @@
 use std::time::Instant;
 
+use tokio::sync::mpsc;
 use tracing::error;
 
 use crate::codex::{Session, ToolCallCtx};
+use crate::mcp_audio_transcription::transcribe_audio_content;
+use crate::protocol::AskForApproval;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::protocol::McpToolCallProgressEvent;
+use crate::protocol::ReviewDecision;
+use crate::tool_arguments_validation::format_validation_errors;
+use crate::tool_arguments_validation::validate_tool_arguments;
 use code_protocol::models::FunctionCallOutputPayload;
 use code_protocol::models::ResponseInputItem;
 
 /// Handles the specified tool call dispatches the appropriate
-/// `McpToolCallBegin` and `McpToolCallEnd` events to the `Session`.
+/// `McpToolCallBegin`, `McpToolCallProgress`, and `McpToolCallEnd` events to
+/// the `Session`.
 pub(crate) async fn handle_mcp_tool_call(
     sess: &Session,
     ctx: &ToolCallCtx,
@@ -38,6 +46,39 @@ pub(crate) async fn handle_mcp_tool_call(
         }
     };
 
+    // Catch schema mismatches locally so the model can correct itself without a
+    // round trip to the server.
+    if let Some(schema) = sess
+        .mcp_connection_manager()
+        .tool_input_schema(&server, &tool_name)
+    {
+        let value = arguments_value
+            .clone()
+            .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+        if let Err(errors) = validate_tool_arguments(&schema, &value, false) {
+            let qualified = format!("{server}/{tool_name}");
+            return ResponseInputItem::FunctionCallOutput {
+                call_id: ctx.call_id.clone(),
+                output: FunctionCallOutputPayload {
+                    body: code_protocol::models::FunctionCallOutputBody::Text(
+                        format_validation_errors(&qualified, &errors),
+                    ),
+                    success: Some(false),
+                },
+            };
+        }
+    }
+
+    if sess
+        .mcp_connection_manager()
+        .tool_requires_approval(&server, &tool_name)
+        && !sess.is_mcp_tool_approved_for_session(&server, &tool_name)
+        && let Some(rejection) =
+            request_mcp_tool_approval(sess, ctx, &server, &tool_name, &arguments).await
+    {
+        return rejection;
+    }
+
     let invocation = McpInvocation {
         server: server.clone(),
         tool: tool_name.clone(),
@@ -48,11 +89,35 @@ pub(crate) async fn handle_mcp_tool_call(
     notify_mcp_tool_call_event(sess, ctx, tool_call_begin_event).await;
 
     let start = Instant::now();
-    // Perform the tool call.
-    let result = sess
-        .call_tool(&server, &tool_name, arguments_value.clone(), None)
-        .await
-        .map_err(|e| format!("tool call error: {e}"));
+    // Perform the tool call, relaying progress notifications while it runs so
+    // the UI can show partial output before the final result arrives.
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+    let call = sess.call_tool_with_progress(&server, &tool_name, arguments_value.clone(), None, progress_tx);
+    tokio::pin!(call);
+    let mut call_result = loop {
+        tokio::select! {
+            result = &mut call => break result,
+            Some(progress) = progress_rx.recv() => {
+                let progress_event = EventMsg::McpToolCallProgress(McpToolCallProgressEvent {
+                    call_id: ctx.call_id.clone(),
+                    progress: progress.progress,
+                    total: progress.total,
+                    message: progress.message,
+                });
+                notify_mcp_tool_call_event(sess, ctx, progress_event).await;
+            }
+        }
+    };
+    if let (Ok(result), Some(transcription)) = (
+        call_result.as_mut(),
+        sess.client().config().mcp_audio_transcription.as_ref(),
+    ) {
+        transcribe_audio_content(transcription, result).await;
+    }
+    if let Ok(result) = call_result.as_mut() {
+        crate::redaction::redact_mcp_result(result);
+    }
+    let result = call_result.map_err(|e| format!("tool call error: {e}"));
     let protocol_result = result.clone().and_then(|value| {
         serde_json::to_value(value)
             .map_err(|e| format!("failed to encode MCP tool result: {e}"))
@@ -71,6 +136,58 @@ pub(crate) async fn handle_mcp_tool_call(
     }
 }
 
+/// Prompts for approval of a tool listed under `approval_required_tools`.
+/// Returns the output to send back to the model when the call must not run.
+async fn request_mcp_tool_approval(
+    sess: &Session,
+    ctx: &ToolCallCtx,
+    server: &str,
+    tool_name: &str,
+    arguments: &str,
+) -> Option<ResponseInputItem> {
+    let message = if sess.get_approval_policy() == AskForApproval::Never {
+        format!(
+            "MCP tool `{server}/{tool_name}` requires approval, but the approval policy never prompts"
+        )
+    } else {
+        let mut command = vec!["mcp".to_string(), format!("{server}/{tool_name}")];
+        if !arguments.trim().is_empty() {
+            command.push(arguments.to_string());
+        }
+        let rx_approve = sess
+            .request_command_approval(
+                ctx.sub_id.clone(),
+                ctx.call_id.clone(),
+                None,
+                None,
+                command,
+                sess.get_cwd().to_path_buf(),
+                Some(format!("`{server}` requires approval for `{tool_name}`")),
+                None,
+                None,
+            )
+            .await;
+        match rx_approve.await.unwrap_or_default() {
+            ReviewDecision::Approved => return None,
+            ReviewDecision::ApprovedForSession => {
+                sess.approve_mcp_tool_for_session(server, tool_name);
+                return None;
+            }
+            ReviewDecision::Denied | ReviewDecision::Abort => {
+                format!("user rejected MCP tool call `{server}/{tool_name}`")
+            }
+        }
+    };
+
+    Some(ResponseInputItem::FunctionCallOutput {
+        call_id: ctx.call_id.clone(),
+        output: FunctionCallOutputPayload {
+            body: code_protocol::models::FunctionCallOutputBody::Text(message),
+            success: Some(false),
+        },
+    })
+}
+
 async fn notify_mcp_tool_call_event(sess: &Session, ctx: &ToolCallCtx, event: EventMsg) {
     sess.send_ordered_from_ctx(ctx, event).await;
 }
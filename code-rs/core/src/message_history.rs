@@ -6,9 +6,14 @@
 //! JSON-Lines tooling. Each record has the following schema:
 //!
 //! ````text
-//! {"session_id":"<uuid>","ts":<unix_seconds>,"text":"<message>"}
+//! {"session_id":"<uuid>","ts":<unix_seconds>,"text":"<message>","project":"<path>"}
 //! ````
 //!
+//! `project` is the repository root (or working directory outside a
+//! repository) the prompt was sent from. Offsets handed to the TUI count only
+//! entries of the current project; entries written before the field existed
+//! have no `project` and are shared by every project.
+//!
 //! To minimise the chance of interleaved writes when multiple processes are
 //! appending concurrently, callers should *prepare the full line* (record +
 //! trailing `\n`) and write it with a **single `write(2)` system call** while
@@ -30,11 +35,16 @@ use uuid::Uuid;
 
 use crate::config::Config;
 use crate::config_types::HistoryPersistence;
+use crate::git_info::resolve_root_git_project_for_trust;
 
 #[cfg(unix)]
 use std::os::unix::fs::OpenOptionsExt;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
+#[cfg(unix)]
+use std::sync::Mutex;
+#[cfg(unix)]
+use std::sync::PoisonError;
 
 /// Filename that stores the message history inside `~/.code` (legacy `~/.codex`
 /// is still read).
@@ -43,11 +53,26 @@ const HISTORY_FILENAME: &str = "history.jsonl";
 const MAX_RETRIES: usize = 10;
 const RETRY_SLEEP: Duration = Duration::from_millis(100);
 
+/// Byte offsets of one project's entries, extended as the file grows so each
+/// `lookup` only parses the lines appended since the previous one.
+#[cfg(unix)]
+struct ProjectIndex {
+    log_id: u64,
+    project: String,
+    scanned: u64,
+    offsets: Vec<u64>,
+}
+
+#[cfg(unix)]
+static PROJECT_INDEX: Mutex<Option<ProjectIndex>> = Mutex::new(None);
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HistoryEntry {
     pub session_id: String,
     pub ts: u64,
     pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
 }
 
 fn history_filepath(config: &Config) -> PathBuf {
@@ -56,10 +81,41 @@ fn history_filepath(config: &Config) -> PathBuf {
     path
 }
 
+/// Key identifying the current project in history entries. This runs `git`,
+/// so callers compute it once per session and pass it along.
+pub(crate) fn project_key(config: &Config) -> String {
+    resolve_root_git_project_for_trust(&config.cwd)
+        .unwrap_or_else(|| config.cwd.clone())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn in_project(entry: &HistoryEntry, project: &str) -> bool {
+    entry.project.as_deref().is_none_or(|key| key == project)
+}
+
+/// Parse `line` and keep it only when it belongs to `project`.
+fn parse_project_entry(line: &str, project: &str) -> Option<HistoryEntry> {
+    serde_json::from_str::<HistoryEntry>(line)
+        .ok()
+        .filter(|entry| in_project(entry, project))
+}
+
+/// A prompt typed with a leading space is marked sensitive, as with the
+/// shell's `HISTCONTROL=ignorespace`.
+fn is_sensitive(text: &str) -> bool {
+    text.starts_with([' ', '\t'])
+}
+
 /// Append a `text` entry associated with `session_id` to the history file. Uses
 /// advisory file locking to ensure that concurrent writes do not interleave,
 /// which entails a small amount of blocking I/O internally.
-pub(crate) async fn append_entry(text: &str, session_id: &Uuid, config: &Config) -> Result<()> {
+pub(crate) async fn append_entry(
+    text: &str,
+    session_id: &Uuid,
+    project: &str,
+    config: &Config,
+) -> Result<()> {
     match config.history.persistence {
         HistoryPersistence::SaveAll => {
             // Save everything: proceed.
@@ -70,7 +126,9 @@ pub(crate) async fn append_entry(text: &str, session_id: &Uuid, config: &Config)
         }
     }
 
-    // TODO: check `text` for sensitive patterns
+    if config.history.exclude_sensitive && is_sensitive(text) {
+        return Ok(());
+    }
 
     // Resolve `~/.code/history.jsonl` and ensure the parent directory exists.
     let path = history_filepath(config);
@@ -89,6 +147,7 @@ pub(crate) async fn append_entry(text: &str, session_id: &Uuid, config: &Config)
         session_id: session_id.to_string(),
         ts,
         text: text.to_string(),
+        project: Some(project.to_string()),
     };
     let mut line = serde_json::to_string(&entry)
         .map_err(|e| std::io::Error::other(format!("failed to serialise history entry: {e}")))?;
@@ -167,8 +226,8 @@ async fn acquire_exclusive_lock_with_retry(file: &File) -> Result<()> {
 }
 
 /// Asynchronously fetch the history file's *identifier* (inode on Unix) and
-/// the current number of entries by counting newline characters.
-pub(crate) async fn history_metadata(config: &Config) -> (u64, usize) {
+/// the current number of entries recorded for `project`.
+pub(crate) async fn history_metadata(project: &str, config: &Config) -> (u64, usize) {
     let path = history_filepath(config);
 
     #[cfg(unix)]
@@ -191,33 +250,33 @@ pub(crate) async fn history_metadata(config: &Config) -> (u64, usize) {
         Err(_) => return (log_id, 0),
     };
 
-    // Count newline bytes.
-    let mut buf = [0u8; 8192];
-    let mut count = 0usize;
-    loop {
-        match file.read(&mut buf).await {
-            Ok(0) => break,
-            Ok(n) => {
-                count += buf[..n].iter().filter(|&&b| b == b'\n').count();
-            }
-            Err(_) => return (log_id, 0),
-        }
+    let mut contents = String::new();
+    if file.read_to_string(&mut contents).await.is_err() {
+        return (log_id, 0);
     }
+    let count = contents
+        .lines()
+        .filter(|line| parse_project_entry(line, project).is_some())
+        .count();
 
     (log_id, count)
 }
 
 /// Given a `log_id` (on Unix this is the file's inode number) and a zero-based
-/// `offset`, return the corresponding `HistoryEntry` if the identifier matches
-/// the current history file **and** the requested offset exists. Any I/O or
-/// parsing errors are logged and result in `None`.
+/// `offset` among `project`'s entries, return the corresponding
+/// `HistoryEntry` if the identifier matches the current history file **and**
+/// the requested offset exists. I/O errors are logged and result in `None`;
+/// lines that fail to parse are skipped.
 ///
 /// Note this function is not async because it uses a sync advisory file
 /// locking API.
 #[cfg(unix)]
-pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<HistoryEntry> {
-    use std::io::BufRead;
-    use std::io::BufReader;
+pub(crate) fn lookup(
+    log_id: u64,
+    offset: usize,
+    project: &str,
+    config: &Config,
+) -> Option<HistoryEntry> {
     use std::os::unix::fs::MetadataExt;
 
     let path = history_filepath(config);
@@ -246,32 +305,15 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
     for _ in 0..MAX_RETRIES {
         match fs2::FileExt::try_lock_shared(&file) {
             Ok(()) => {
-                let reader = BufReader::new(&file);
-                for (idx, line_res) in reader.lines().enumerate() {
-                    let line = match line_res {
-                        Ok(l) => l,
-                        Err(e) => {
-                            tracing::warn!(error = %e, "failed to read line from history file");
-                            let _ = fs2::FileExt::unlock(&file);
-                            return None;
-                        }
-                    };
-
-                    if idx == offset {
-                        let res = match serde_json::from_str::<HistoryEntry>(&line) {
-                            Ok(entry) => Some(entry),
-                            Err(e) => {
-                                tracing::warn!(error = %e, "failed to parse history entry");
-                                None
-                            }
-                        };
-                        let _ = fs2::FileExt::unlock(&file);
-                        return res;
-                    }
-                }
+                let entry = read_indexed_entry(&file, log_id, metadata.len(), offset, project);
                 let _ = fs2::FileExt::unlock(&file);
-                // Not found at requested offset.
-                return None;
+                return match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "failed to read history file");
+                        None
+                    }
+                };
             }
             Err(e) => {
                 if e.kind() == std::io::ErrorKind::WouldBlock {
@@ -287,10 +329,86 @@ pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<Hist
     None
 }
 
+/// Entry at `offset` among `project`'s entries, reading `file` only past the
+/// point the cached index has already covered.
+#[cfg(unix)]
+fn read_indexed_entry(
+    file: &File,
+    log_id: u64,
+    len: u64,
+    offset: usize,
+    project: &str,
+) -> Result<Option<HistoryEntry>> {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+
+    let mut guard = PROJECT_INDEX.lock().unwrap_or_else(PoisonError::into_inner);
+    // A different file, project or a truncated file invalidates the index.
+    if guard.as_ref().is_some_and(|index| {
+        index.log_id != log_id || index.project != project || index.scanned > len
+    }) {
+        *guard = None;
+    }
+    let index = guard.get_or_insert_with(|| ProjectIndex {
+        log_id,
+        project: project.to_string(),
+        scanned: 0,
+        offsets: Vec::new(),
+    });
+
+    let mut reader = BufReader::new(file);
+    if index.offsets.len() <= offset && index.scanned < len {
+        reader.seek(SeekFrom::Start(index.scanned))?;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            // Stop before a line another process is still writing.
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            if parse_project_entry(&line, project).is_some() {
+                index.offsets.push(index.scanned);
+            }
+            index.scanned += read as u64;
+        }
+    }
+
+    let Some(&start) = index.offsets.get(offset) else {
+        return Ok(None);
+    };
+    reader.seek(SeekFrom::Start(start))?;
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    Ok(parse_project_entry(&line, project))
+}
+
+/// Text of every entry recorded for this project, oldest first, in the same
+/// offset order `lookup` uses. Reverse search in the TUI scans this once
+/// instead of requesting entries one offset at a time.
+pub fn load_project_history(config: &Config) -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string(history_filepath(config)) else {
+        return Vec::new();
+    };
+    let project = project_key(config);
+    contents
+        .lines()
+        .filter_map(|line| parse_project_entry(line, &project))
+        .map(|entry| entry.text)
+        .collect()
+}
+
 /// Fallback stub for non-Unix systems: currently always returns `None`.
 #[cfg(not(unix))]
-pub(crate) fn lookup(log_id: u64, offset: usize, config: &Config) -> Option<HistoryEntry> {
-    let _ = (log_id, offset, config);
+pub(crate) fn lookup(
+    log_id: u64,
+    offset: usize,
+    project: &str,
+    config: &Config,
+) -> Option<HistoryEntry> {
+    let _ = (log_id, offset, project, config);
     None
 }
 
@@ -337,3 +455,62 @@ async fn ensure_owner_only_permissions(_file: &File) -> Result<()> {
     // For now, on non-Unix, simply succeed.
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigOverrides;
+    use crate::config::ConfigToml;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn config_in(code_home: &TempDir, cwd: &TempDir) -> Config {
+        let mut config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            code_home.path().to_path_buf(),
+        )
+        .expect("default config");
+        config.cwd = cwd.path().to_path_buf();
+        config
+    }
+
+    #[tokio::test]
+    async fn history_is_scoped_to_project_and_skips_sensitive_prompts() {
+        let code_home = TempDir::new().expect("code home");
+        let (project_a, project_b) = (TempDir::new().expect("a"), TempDir::new().expect("b"));
+        let mut config_a = config_in(&code_home, &project_a);
+        let config_b = config_in(&code_home, &project_b);
+        let (key_a, key_b) = (project_key(&config_a), project_key(&config_b));
+        let session = Uuid::new_v4();
+
+        let legacy = r#"{"session_id":"old","ts":1,"text":"legacy prompt"}"#;
+        std::fs::write(history_filepath(&config_a), format!("{legacy}\n")).expect("seed");
+        append_entry("fix the build", &session, &key_a, &config_a).await.expect("append");
+        append_entry("write docs", &session, &key_b, &config_b).await.expect("append");
+        config_a.history.exclude_sensitive = true;
+        append_entry(" export TOKEN=secret", &session, &key_a, &config_a)
+            .await
+            .expect("append");
+
+        assert_eq!(
+            load_project_history(&config_a),
+            vec!["legacy prompt".to_string(), "fix the build".to_string()]
+        );
+        assert_eq!(history_metadata(&key_b, &config_b).await.1, 2);
+        #[cfg(unix)]
+        {
+            let (log_id, _) = history_metadata(&key_b, &config_b).await;
+            let entry = lookup(log_id, 1, &key_b, &config_b).expect("entry");
+            assert_eq!(entry.text, "write docs");
+            assert!(lookup(log_id, 2, &key_b, &config_b).is_none());
+
+            // Entries appended after the first lookup are picked up too.
+            append_entry("run the tests", &session, &key_b, &config_b).await.expect("append");
+            let entry = lookup(log_id, 2, &key_b, &config_b).expect("entry");
+            assert_eq!(entry.text, "run the tests");
+            let entry = lookup(log_id, 0, &key_a, &config_a).expect("entry");
+            assert_eq!(entry.text, "legacy prompt");
+        }
+    }
+}
@@ -134,6 +134,9 @@ pub struct ModelFamily {
     /// Whether this model supports image generation via the native Responses tool.
     pub supports_image_generation: bool,
 
+    /// Whether user messages may carry image inputs (pasted or attached screenshots).
+    pub supports_image_input: bool,
+
     // Instructions to use for querying the model
     pub base_instructions: String,
 }
@@ -189,6 +192,7 @@ macro_rules! model_family {
             web_search_tool_type: WebSearchToolType::Text,
             supports_image_detail_original: false,
             supports_image_generation: false,
+            supports_image_input: true,
             base_instructions: BASE_INSTRUCTIONS.to_string(),
         };
         // apply overrides
@@ -244,6 +248,7 @@ fn apply_upstream_model_overrides(mut family: ModelFamily) -> ModelFamily {
     family.prefer_websockets = model_info.prefer_websockets;
     family.supports_image_detail_original = model_info.supports_image_detail_original;
     family.supports_image_generation = supports_image_generation(model_info);
+    family.supports_image_input = supports_image_input(model_info);
     family.uses_local_shell_tool = matches!(model_info.shell_type, ConfigShellToolType::Local);
     family.uses_shell_command_tool =
         matches!(model_info.shell_type, ConfigShellToolType::ShellCommand);
@@ -493,6 +498,7 @@ pub fn derive_default_model_family(model: &str) -> ModelFamily {
         web_search_tool_type: WebSearchToolType::Text,
         supports_image_detail_original: false,
         supports_image_generation: false,
+        supports_image_input: true,
         base_instructions: BASE_INSTRUCTIONS.to_string(),
     })
 }
@@ -501,6 +507,10 @@ fn supports_image_generation(model_info: &ModelInfo) -> bool {
     model_info.input_modalities.contains(&InputModality::Image)
 }
 
+fn supports_image_input(model_info: &ModelInfo) -> bool {
+    model_info.input_modalities.contains(&InputModality::Image)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config_types::ReasoningEffort;
@@ -513,6 +523,7 @@ mod tests {
         let family = find_family_for_model("gpt-5.4").expect("known upstream model");
 
         assert!(family.supports_image_generation);
+        assert!(family.supports_image_input);
     }
 
     #[test]
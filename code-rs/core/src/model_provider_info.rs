@@ -7,6 +7,8 @@
 //!      key. These override or extend the defaults at runtime.
 
 use crate::CodexAuth;
+use crate::default_client::DEFAULT_ORIGINATOR;
+use crate::default_client::create_client_with_tls;
 use crate::error::CodexErr;
 use code_protocol::config_types::ModelProviderAuthInfo;
 use serde::Deserialize;
@@ -142,6 +144,21 @@ pub struct ModelProviderInfo {
     /// Optional OpenRouter-specific configuration for routing preferences and metadata.
     #[serde(default)]
     pub openrouter: Option<OpenRouterConfig>,
+
+    /// Private CA and client certificate for gateways that terminate TLS themselves.
+    #[serde(default)]
+    pub tls: Option<ProviderTlsConfig>,
+}
+
+/// TLS settings for one provider, applied on top of the system trust store.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct ProviderTlsConfig {
+    /// PEM bundle (or single DER certificate) of additional CAs to trust.
+    pub ca_certificate: Option<PathBuf>,
+    /// PEM client certificate presented for mutual TLS.
+    pub client_certificate: Option<PathBuf>,
+    /// PEM private key for `client_certificate`.
+    pub client_key: Option<PathBuf>,
 }
 
 /// OpenRouter-specific configuration, allowing users to control routing and pricing metadata.
@@ -235,6 +252,11 @@ pub struct OpenRouterMaxPrice {
 
 impl ModelProviderInfo {
     pub(crate) fn validate(&self) -> std::result::Result<(), String> {
+        if let Some(tls) = self.tls.as_ref() {
+            crate::http_client::apply_provider_tls(reqwest::Client::builder(), tls)
+                .map_err(|message| format!("tls: {message}"))?;
+        }
+
         let Some(auth) = self.auth.as_ref() else {
             return Ok(());
         };
@@ -555,12 +577,44 @@ impl ModelProviderInfo {
                 }
             }
         }
+
+        // Organization/project chosen for the active API-key sign-in, unless
+        // the config or environment already supplies the header.
+        if self.requires_openai_auth {
+            let billing = crate::auth_accounts::active_billing();
+            for (header, value) in [
+                ("OpenAI-Organization", billing.organization),
+                ("OpenAI-Project", billing.project),
+            ] {
+                if let Some(value) = value
+                    && !self.sets_header(header)
+                {
+                    builder = builder.header(header, value);
+                }
+            }
+        }
         builder
     }
 
+    /// Whether `http_headers` or a set `env_http_headers` variable provides `header`.
+    fn sets_header(&self, header: &str) -> bool {
+        let from_config = self
+            .http_headers
+            .as_ref()
+            .is_some_and(|headers| headers.keys().any(|name| name.eq_ignore_ascii_case(header)));
+        let from_env = self.env_http_headers.as_ref().is_some_and(|headers| {
+            headers.iter().any(|(name, env_var)| {
+                name.eq_ignore_ascii_case(header)
+                    && std::env::var(env_var).is_ok_and(|val| !val.trim().is_empty())
+            })
+        });
+        from_config || from_env
+    }
+
     /// If `env_key` is Some, returns the API key for this provider if present
-    /// (and non-empty) in the environment. If `env_key` is required but
-    /// cannot be found, returns an error.
+    /// (and non-empty) in the environment, falling back to a key saved with
+    /// `code auth set`. If `env_key` is required but cannot be found, returns
+    /// an error.
     pub fn api_key(&self) -> crate::error::Result<Option<String>> {
         match &self.env_key {
             Some(env_key) => {
@@ -573,6 +627,9 @@ impl ModelProviderInfo {
                             Ok(Some(v))
                         }
                     })
+                    .or_else(|err| {
+                        crate::provider_keys::stored_key(env_key).map(Some).ok_or(err)
+                    })
                     .map_err(|_| {
                         crate::error::CodexErr::EnvVar(EnvVarError {
                             var: env_key.clone(),
@@ -584,6 +641,37 @@ impl ModelProviderInfo {
         }
     }
 
+    /// HTTP client for this provider, trusting its `tls` CA and presenting
+    /// its client certificate when configured.
+    pub fn create_http_client(&self, originator: &str) -> reqwest::Client {
+        create_client_with_tls(originator, self.tls.as_ref())
+    }
+
+    /// Check `api_key` against this provider by listing its models. Returns a
+    /// message suitable for the user when the key is rejected or the provider
+    /// cannot be reached.
+    pub async fn verify_api_key(&self, api_key: &str) -> Result<(), String> {
+        let base_url = self.base_url.as_deref().unwrap_or("https://api.openai.com/v1");
+        let url = format!("{}/models{}", base_url.trim_end_matches('/'), self.get_query_string());
+        let builder = self
+            .create_http_client(DEFAULT_ORIGINATOR)
+            .get(&url)
+            .bearer_auth(api_key)
+            .timeout(Duration::from_secs(15));
+        let response = self
+            .apply_http_headers(builder)
+            .send()
+            .await
+            .map_err(|err| format!("could not reach {url}: {err}"))?;
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                Err(format!("{} rejected the key ({})", self.name, response.status()))
+            }
+            status => Err(format!("{url} returned {status}")),
+        }
+    }
+
     /// Effective maximum number of request retries for this provider.
     pub fn request_max_retries(&self) -> u64 {
         self.request_max_retries
@@ -785,6 +873,7 @@ pub fn built_in_model_providers(
                 websocket_connect_timeout_ms: None,
                 requires_openai_auth: true,
                 openrouter: None,
+                tls: None,
             },
         ),
         (BUILT_IN_OSS_MODEL_PROVIDER_ID, create_oss_provider()),
@@ -833,6 +922,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str) -> ModelProviderInfo {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         openrouter: None,
+        tls: None,
     }
 }
 
@@ -880,6 +970,7 @@ base_url = "http://localhost:11434/v1"
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -913,6 +1004,7 @@ query_params = { api-version = "2025-04-01-preview" }
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -949,6 +1041,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -975,6 +1068,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 websocket_connect_timeout_ms: None,
                 requires_openai_auth: false,
                 openrouter: None,
+                tls: None,
             }
         }
 
@@ -1011,6 +1105,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
         assert!(named_provider.is_azure_responses_endpoint());
 
@@ -1048,6 +1143,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 websocket_connect_timeout_ms: None,
                 requires_openai_auth: false,
                 openrouter: None,
+                tls: None,
             }
         }
 
@@ -1144,6 +1240,7 @@ refresh_interval_ms = 0
             websocket_connect_timeout_ms: None,
             requires_openai_auth: false,
             openrouter: None,
+            tls: None,
         };
 
         assert_eq!(
@@ -1151,4 +1248,44 @@ refresh_interval_ms = 0
             Err("provider auth cannot be combined with env_key".to_string())
         );
     }
+
+    #[test]
+    fn provider_tls_validation_reports_unusable_settings() {
+        let dir = tempdir().unwrap();
+        let not_a_cert = dir.path().join("ca.pem");
+        std::fs::write(&not_a_cert, "not a certificate").unwrap();
+        let provider_toml = format!(
+            r#"
+name = "Gateway"
+base_url = "https://llm.corp.example/v1"
+
+[tls]
+ca_certificate = {ca:?}
+client_certificate = {ca:?}
+"#,
+            ca = not_a_cert.display().to_string()
+        );
+        let mut provider: ModelProviderInfo = toml::from_str(&provider_toml).unwrap();
+        assert_eq!(
+            provider.tls.as_ref().and_then(|tls| tls.ca_certificate.clone()),
+            Some(not_a_cert.clone())
+        );
+        assert!(provider.validate().is_err());
+
+        provider.tls = Some(ProviderTlsConfig {
+            client_certificate: Some(not_a_cert),
+            ..Default::default()
+        });
+        assert_eq!(
+            provider.validate(),
+            Err("tls: client_certificate and client_key must be set together".to_string())
+        );
+
+        provider.tls = Some(ProviderTlsConfig {
+            ca_certificate: Some(dir.path().join("missing.pem")),
+            ..Default::default()
+        });
+        let err = provider.validate().unwrap_err();
+        assert!(err.starts_with("tls: cannot read"), "{err}");
+    }
 }
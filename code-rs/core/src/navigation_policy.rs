@@ -0,0 +1,215 @@
+//! Domain allow/deny policy for the browser and fetch tools.
+//!
+//! Configured under `[browser]` with `allowed_domains`, `denied_domains` and
+//! `prompt_on_new_domain`. A domain entry matches the host itself and every
+//! subdomain (`example.com` covers `docs.example.com`); a leading `*.` is
+//! accepted and means the same thing. The deny list always wins. With
+//! `prompt_on_new_domain`, hosts outside the allow list are put to the user
+//! instead of being refused, so the allow list becomes the set of domains
+//! that never need asking.
+
+use std::collections::HashSet;
+
+use url::Url;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NavigationPolicy {
+    pub allowed_domains: Vec<String>,
+    pub denied_domains: Vec<String>,
+    pub prompt_on_new_domain: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NavigationDecision {
+    Allow,
+    /// Refused by configuration; carries the message for the model.
+    Deny(String),
+    /// The user must approve visiting this host.
+    Ask(String),
+}
+
+impl NavigationPolicy {
+    pub(crate) fn is_unrestricted(&self) -> bool {
+        self.allowed_domains.is_empty()
+            && self.denied_domains.is_empty()
+            && !self.prompt_on_new_domain
+    }
+
+    pub(crate) fn evaluate(&self, url: &str) -> NavigationDecision {
+        if self.is_unrestricted() {
+            return NavigationDecision::Allow;
+        }
+        let Some(parsed) = parse(url) else {
+            return NavigationDecision::Deny(format!(
+                "{url} is not a valid URL, so the navigation policy cannot allow it"
+            ));
+        };
+        if parsed.scheme() == "about" {
+            return NavigationDecision::Allow;
+        }
+        let Some(host) = web_host(&parsed) else {
+            if self.allowed_domains.is_empty() && !self.prompt_on_new_domain {
+                return NavigationDecision::Allow;
+            }
+            return NavigationDecision::Deny(format!(
+                "{} URLs are not allowed by the navigation policy",
+                parsed.scheme()
+            ));
+        };
+        let denied = self.denied_domains.iter().find(|entry| domain_matches(&host, entry));
+        if let Some(entry) = denied {
+            return NavigationDecision::Deny(format!(
+                "{host} is blocked by the navigation policy (denied domain: {entry})"
+            ));
+        }
+        if self.allowed_domains.iter().any(|entry| domain_matches(&host, entry)) {
+            return NavigationDecision::Allow;
+        }
+        if self.prompt_on_new_domain {
+            return NavigationDecision::Ask(host);
+        }
+        if self.allowed_domains.is_empty() {
+            NavigationDecision::Allow
+        } else {
+            NavigationDecision::Deny(format!(
+                "{host} is not on the navigation allowlist ({})",
+                self.allowed_domains.join(", ")
+            ))
+        }
+    }
+}
+
+impl NavigationPolicy {
+    /// Redirect policy that runs every hop through [`Self::evaluate`].
+    /// Hosts that would need approval are followed only when they are in
+    /// `trusted_hosts` (the starting host and any approved this session).
+    pub(crate) fn redirect_policy(
+        self,
+        trusted_hosts: HashSet<String>,
+        limit: usize,
+    ) -> reqwest::redirect::Policy {
+        reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() > limit {
+                return attempt.error(format!("stopped after {limit} redirects"));
+            }
+            match self.check_redirect(attempt.url().as_str(), &trusted_hosts) {
+                Ok(()) => attempt.follow(),
+                Err(message) => attempt.error(message),
+            }
+        })
+    }
+
+    fn check_redirect(&self, target: &str, trusted_hosts: &HashSet<String>) -> Result<(), String> {
+        match self.evaluate(target) {
+            NavigationDecision::Allow => Ok(()),
+            NavigationDecision::Ask(host) if trusted_hosts.contains(&host) => Ok(()),
+            NavigationDecision::Ask(host) => Err(format!(
+                "redirect to {host} needs approval; fetch {target} directly to be asked"
+            )),
+            NavigationDecision::Deny(message) => Err(message),
+        }
+    }
+}
+
+/// Lower-cased host of an http(s) or ws(s) URL.
+pub(crate) fn host_of(url: &str) -> Option<String> {
+    parse(url).as_ref().and_then(web_host)
+}
+
+fn parse(url: &str) -> Option<Url> {
+    let url = url.trim();
+    // `goto` accepts bare hosts such as `example.com/path` or `localhost:3000`.
+    let has_scheme = url.contains("://")
+        || ["about:", "data:", "javascript:", "blob:", "file:", "mailto:"]
+            .iter()
+            .any(|scheme| {
+                url.get(..scheme.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+            });
+    if has_scheme {
+        Url::parse(url).ok()
+    } else {
+        Url::parse(&format!("https://{url}")).ok()
+    }
+}
+
+fn web_host(url: &Url) -> Option<String> {
+    if !matches!(url.scheme(), "http" | "https" | "ws" | "wss") {
+        return None;
+    }
+    url.host_str()
+        .map(|host| host.trim_end_matches('.').to_ascii_lowercase())
+}
+
+fn domain_matches(host: &str, entry: &str) -> bool {
+    let entry = entry.trim().trim_start_matches("*.").trim_end_matches('.');
+    if entry.is_empty() {
+        return false;
+    }
+    let entry = entry.to_ascii_lowercase();
+    host == entry || host.strip_suffix(entry.as_str()).is_some_and(|rest| rest.ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn deny_wins_allowlist_restricts_and_prompt_mode_asks() {
+        let policy = NavigationPolicy {
+            allowed_domains: vec!["example.com".to_string(), "*.rust-lang.org".to_string()],
+            denied_domains: vec!["secret.example.com".to_string()],
+            prompt_on_new_domain: false,
+        };
+        assert_eq!(policy.evaluate("https://docs.example.com/a"), NavigationDecision::Allow);
+        assert_eq!(policy.evaluate("doc.rust-lang.org/std"), NavigationDecision::Allow);
+        assert_eq!(policy.evaluate("about:blank"), NavigationDecision::Allow);
+        assert_eq!(
+            policy.evaluate("https://api.secret.example.com"),
+            NavigationDecision::Deny(
+                "api.secret.example.com is blocked by the navigation policy \
+                 (denied domain: secret.example.com)"
+                    .to_string()
+            )
+        );
+        assert!(matches!(
+            policy.evaluate("https://notexample.com"),
+            NavigationDecision::Deny(_)
+        ));
+        assert!(matches!(policy.evaluate("file:///etc/passwd"), NavigationDecision::Deny(_)));
+
+        let prompting = NavigationPolicy {
+            prompt_on_new_domain: true,
+            ..policy
+        };
+        assert_eq!(
+            prompting.evaluate("https://Other.ORG/page"),
+            NavigationDecision::Ask("other.org".to_string())
+        );
+        assert_eq!(prompting.evaluate("https://example.com"), NavigationDecision::Allow);
+        assert_eq!(
+            NavigationPolicy::default().evaluate("file:///tmp/x"),
+            NavigationDecision::Allow
+        );
+    }
+
+    #[test]
+    fn redirect_hops_follow_only_allowed_or_trusted_hosts() {
+        let policy = NavigationPolicy {
+            allowed_domains: vec!["example.com".to_string()],
+            denied_domains: vec!["internal.example.com".to_string()],
+            prompt_on_new_domain: true,
+        };
+        let trusted = HashSet::from(["start.org".to_string()]);
+        assert_eq!(policy.check_redirect("https://example.com/next", &trusted), Ok(()));
+        assert_eq!(policy.check_redirect("https://start.org/login", &trusted), Ok(()));
+        assert_eq!(
+            policy.check_redirect("https://other.net/x", &trusted),
+            Err("redirect to other.net needs approval; fetch https://other.net/x directly \
+                 to be asked"
+                .to_string())
+        );
+        assert!(policy.check_redirect("http://internal.example.com/", &trusted).is_err());
+    }
+}
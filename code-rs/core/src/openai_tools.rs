@@ -166,6 +166,14 @@ pub struct ToolsConfig {
     pub web_search_tool_type: WebSearchToolType,
     pub image_gen_tool: bool,
     pub search_tool: bool,
+    pub fetch_url_tool: bool,
+    pub read_file_tool: bool,
+    /// `code_search` and `goto_definition` over the workspace symbol index.
+    pub code_index_tools: bool,
+    /// `git_log`, `git_blame` and `git_show` with structured output.
+    pub git_history_tools: bool,
+    /// A configured search API answers `web_search` as a function tool.
+    pub web_search_backend: bool,
     #[allow(dead_code)]
     pub include_view_image_tool: bool,
     pub web_search_allowed_domains: Option<Vec<String>>,
@@ -230,6 +238,11 @@ impl ToolsConfig {
             web_search_tool_type: model_family.web_search_tool_type,
             image_gen_tool: false,
             search_tool: false,
+            fetch_url_tool: false,
+            read_file_tool: false,
+            code_index_tools: false,
+            git_history_tools: false,
+            web_search_backend: false,
             include_view_image_tool,
             web_search_allowed_domains: None,
             agent_model_allowed_values: Vec::new(),
@@ -1504,6 +1517,21 @@ pub fn get_openai_tools(
     }
 
     tools.push(create_browser_tool(browser_enabled));
+    if config.fetch_url_tool {
+        tools.push(create_fetch_url_tool());
+    }
+    if config.read_file_tool {
+        tools.push(create_read_file_tool());
+    }
+    if config.code_index_tools {
+        tools.push(create_code_search_tool());
+        tools.push(create_goto_definition_tool());
+    }
+    if config.git_history_tools {
+        tools.push(create_git_log_tool());
+        tools.push(create_git_blame_tool());
+        tools.push(create_git_show_tool());
+    }
 
     // Add agent management tool for launching and monitoring asynchronous agents
     tools.push(create_agent_tool(config.agent_models()));
@@ -1514,7 +1542,9 @@ pub fn get_openai_tools(
     tools.push(create_gh_run_wait_tool());
     tools.push(create_bridge_tool());
 
-    if config.web_search_request {
+    if config.web_search_backend {
+        tools.push(create_web_search_function_tool());
+    } else if config.web_search_request {
         let external_web_access = Some(config.web_search_external || config.web_search_indexed);
         let indexed_web_access = config.web_search_indexed.then_some(true);
         let search_content_types = match config.web_search_tool_type {
@@ -1645,6 +1675,350 @@ pub fn create_kill_tool() -> OpenAiTool {
     })
 }
 
+pub fn create_fetch_url_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "url".to_string(),
+        JsonSchema::String {
+            description: Some("http(s) URL to fetch.".to_string()),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "format".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "auto (default) converts HTML to Markdown, pretty-prints JSON and extracts PDF text; raw returns the body as text."
+                    .to_string(),
+            ),
+            allowed_values: Some(vec!["auto".to_string(), "raw".to_string()]),
+        },
+    );
+    properties.insert(
+        "max_bytes".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Stop reading the body after this many bytes (default {}, max {}).",
+                crate::fetch_url::DEFAULT_MAX_BYTES,
+                crate::fetch_url::MAX_BYTES_LIMIT
+            )),
+        },
+    );
+    properties.insert(
+        "max_redirects".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Redirects to follow before giving up (default {}, max {}).",
+                crate::fetch_url::DEFAULT_MAX_REDIRECTS,
+                crate::fetch_url::MAX_REDIRECTS_LIMIT
+            )),
+        },
+    );
+    properties.insert(
+        "timeout_ms".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Request timeout in milliseconds (default {}, min {}, max {}).",
+                crate::fetch_url::DEFAULT_TIMEOUT_MS,
+                crate::fetch_url::MIN_TIMEOUT_MS,
+                crate::fetch_url::MAX_TIMEOUT_MS
+            )),
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "fetch_url".to_string(),
+        description: "Fetch a URL over plain HTTP without a browser. Responses are cached and revalidated with ETags, robots.txt is honoured, and the body is converted by content type. Prefer this for API docs, raw files and JSON endpoints; use the browser for pages that need JavaScript or a login.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub fn create_read_file_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File to read, absolute or relative to the working directory.".to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "start_line".to_string(),
+        JsonSchema::Number {
+            description: Some("First line to return, 1-based (default 1).".to_string()),
+        },
+    );
+    properties.insert(
+        "end_line".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Last line to return, inclusive. Omit to read as many lines as fit in length."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "offset".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Byte offset to start from. Use instead of start_line/end_line for byte ranges."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "length".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Bytes to read from offset, or the byte budget for a line range \
+                 (default {}, max {}).",
+                crate::read_file::DEFAULT_MAX_BYTES,
+                crate::read_file::MAX_BYTES_LIMIT
+            )),
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "read_file".to_string(),
+        description: "Read a window of a text file by line range or byte offset. The result reports total_bytes (and total_lines for line reads) and, when more remains, next_line or next_offset to continue from. Prefer this over cat/sed for large logs and generated files.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub fn create_code_search_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Symbol name or part of one; `Type::name` or `Type.name` narrows to members."
+                    .to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "kind".to_string(),
+        JsonSchema::String {
+            description: Some("Only return definitions of this kind.".to_string()),
+            allowed_values: Some(
+                [
+                    "function", "method", "struct", "enum", "trait", "interface", "class", "type",
+                    "module", "constant", "macro",
+                ]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            ),
+        },
+    );
+    properties.insert(
+        "references".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "Also list whole-word uses of the name outside its definitions.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Most definitions (and references) to return (default {}, max {}).",
+                crate::code_index::DEFAULT_LIMIT,
+                crate::code_index::MAX_LIMIT
+            )),
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "code_search".to_string(),
+        description: "Search the workspace symbol index for definitions (functions, types, classes, constants) by name, optionally with references. The index is kept up to date incrementally; use it instead of grep to find where something is defined or used.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub fn create_goto_definition_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "symbol".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Exact symbol name, optionally qualified as `Type::name` or `Type.name`."
+                    .to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "File the symbol is used in; definitions there are listed first.".to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "goto_definition".to_string(),
+        description: "Find where a symbol is defined: file, line, kind, enclosing type and the declaration line.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["symbol".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn string_param(description: &str) -> JsonSchema {
+    JsonSchema::String {
+        description: Some(description.to_string()),
+        allowed_values: None,
+    }
+}
+
+pub fn create_git_log_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        string_param("Only commits that touched this file or directory."),
+    );
+    properties.insert(
+        "revision".to_string(),
+        string_param("Revision or range to list, e.g. `main`, `v1.2..HEAD` (default HEAD)."),
+    );
+    properties.insert("author".to_string(), string_param("Only commits by a matching author."));
+    properties.insert(
+        "grep".to_string(),
+        string_param("Only commits whose message matches this pattern (case-insensitive)."),
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Most commits to return (default {}, max {}).",
+                crate::git_history::DEFAULT_LOG_LIMIT,
+                crate::git_history::MAX_LOG_LIMIT
+            )),
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "git_log".to_string(),
+        description: "List commits with sha, author, date, full message and touched files. Use it to find when and why a file or feature changed.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub fn create_git_blame_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert("path".to_string(), string_param("File to blame."));
+    properties.insert(
+        "start_line".to_string(),
+        JsonSchema::Number {
+            description: Some("First line to blame, 1-based (default 1).".to_string()),
+        },
+    );
+    properties.insert(
+        "end_line".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Last line to blame (default and maximum: {} lines from start_line).",
+                crate::git_history::MAX_BLAME_LINES
+            )),
+        },
+    );
+    properties.insert(
+        "revision".to_string(),
+        string_param("Blame the file as of this revision instead of the working tree."),
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "git_blame".to_string(),
+        description: "Attribute lines of a file to the commits that last changed them. Returns hunks of consecutive lines with sha, author, date, commit summary and the line text.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub fn create_git_show_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert("revision".to_string(), string_param("Commit to show (sha, tag, branch)."));
+    properties.insert(
+        "path".to_string(),
+        string_param("Limit stats and patch to this file or directory."),
+    );
+    properties.insert(
+        "patch".to_string(),
+        JsonSchema::Boolean {
+            description: Some("Include the diff (default true).".to_string()),
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "git_show".to_string(),
+        description: "Show one commit: author, date, full message, per-file added/deleted line counts and the patch.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["revision".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+pub fn create_web_search_function_tool() -> OpenAiTool {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some("Search query.".to_string()),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "count".to_string(),
+        JsonSchema::Number {
+            description: Some(format!(
+                "Number of results (default {}, max {}).",
+                crate::web_search::DEFAULT_RESULTS,
+                crate::web_search::MAX_RESULTS
+            )),
+        },
+    );
+    OpenAiTool::Function(ResponsesApiTool {
+        name: "web_search".to_string(),
+        description: "Search the web. Returns a JSON list of results with title, url and snippet; open the promising ones with fetch_url or the browser before relying on them.".to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 pub fn create_gh_run_wait_tool() -> OpenAiTool {
     let mut properties = BTreeMap::new();
     properties.insert(
@@ -1971,6 +2345,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_web_search_backend_replaces_native_search() {
+        let model_family = find_family_for_model("o3").expect("o3 should be a valid model family");
+        let mut config = ToolsConfig::new(
+            &model_family,
+            AskForApproval::Never,
+            SandboxPolicy::ReadOnly,
+            false,
+            false,
+            true,
+            /*use_experimental_streamable_shell_tool*/ false,
+            false,
+        );
+        config.web_search_backend = true;
+        apply_default_agent_models(&mut config);
+
+        let tools = get_openai_tools(&config, Some(HashMap::new()), false, false, &[]);
+        assert!(!tools.iter().any(|tool| matches!(tool, OpenAiTool::WebSearch(_))));
+        assert!(tools.iter().any(|tool| matches!(
+            tool,
+            OpenAiTool::Function(ResponsesApiTool { name, .. }) if name == "web_search"
+        )));
+    }
+
     #[test]
     fn test_web_search_indexed_access_sets_canonical_field() {
         let model_family = find_family_for_model("o3").expect("o3 should be a valid model family");
@@ -2798,11 +3196,21 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
             "move",
             "type",
             "key",
+            "fill",
+            "select",
+            "submit",
+            "login",
+            "query",
             "javascript",
+            "eval",
             "scroll",
+            "viewport",
+            "download",
             "history",
             "inspect",
             "console",
+            "network",
+            "diff",
             "cleanup",
             "cdp",
         ]);
@@ -2824,7 +3232,7 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "url".to_string(),
         JsonSchema::String {
             description: Some(
-                "For action=open or fetch: URL to navigate to or retrieve (e.g., https://example.com)."
+                "For action=open, fetch or download: URL to navigate to, retrieve or save (e.g., https://example.com). For action=diff: optional URL to (re)load before comparing; defaults to the current page."
                     .to_string(),
             ),
             allowed_values: None,
@@ -2876,6 +3284,53 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
             ),
         },
     );
+    properties.insert(
+        "filename".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For action=download: optional file name; defaults to the server's or the URL's."
+                    .to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "pages".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "For action=scroll: scroll by this many viewport heights (negative scrolls up)."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "to".to_string(),
+        JsonSchema::String {
+            description: Some("For action=scroll: jump to the 'top' or 'bottom' of the page.".to_string()),
+            allowed_values: Some(vec!["top".to_string(), "bottom".to_string()]),
+        },
+    );
+    properties.insert(
+        "width".to_string(),
+        JsonSchema::Number {
+            description: Some("For action=viewport: viewport width in CSS pixels (200-4096).".to_string()),
+        },
+    );
+    properties.insert(
+        "height".to_string(),
+        JsonSchema::Number {
+            description: Some("For action=viewport: viewport height in CSS pixels (200-4096).".to_string()),
+        },
+    );
+    properties.insert(
+        "screenshot".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For actions=click/scroll/viewport: return a screenshot taken right after the action."
+                    .to_string(),
+            ),
+        },
+    );
     properties.insert(
         "text".to_string(),
         JsonSchema::String {
@@ -2892,11 +3347,89 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
             allowed_values: None,
         },
     );
+    properties.insert(
+        "selector".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For actions=fill/select/submit: CSS selector of the field, form or button. For action=query: CSS selector to match. For action=click: element to click instead of x/y. For action=scroll: element to scroll into view."
+                    .to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "For action=query: maximum number of matches to return (default 20, max 200)."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "html".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For action=query: also return each match's outer HTML.".to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "attributes".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String {
+                description: None,
+                allowed_values: None,
+            }),
+            description: Some(
+                "For action=query: only return these attributes (e.g., ['href', 'title'])."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "label".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For actions=fill/select/submit: visible label, placeholder or button text used when no selector is given."
+                    .to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "value".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For action=fill: text to enter ('true'/'false' for checkboxes). For action=select: option value or text."
+                    .to_string(),
+            ),
+            allowed_values: None,
+        },
+    );
+    properties.insert(
+        "prompt".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For action=login: ask the user for credentials even if some are saved (e.g., after they were rejected)."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "submit".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For action=login: submit the login form after filling it (default true)."
+                    .to_string(),
+            ),
+        },
+    );
     properties.insert(
         "code".to_string(),
         JsonSchema::String {
             description: Some(
-                "For action=javascript: JavaScript source to execute in the browser context.".to_string(),
+                "For action=javascript or eval: JavaScript source to execute in the browser context. Both ask the user first; eval returns the result as JSON.".to_string(),
             ),
             allowed_values: None,
         },
@@ -2921,7 +3454,8 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "lines".to_string(),
         JsonSchema::Number {
             description: Some(
-                "For action=console: optional number of recent console lines to return.".to_string(),
+                "For action=console or network: optional number of recent console lines or failed requests to return."
+                    .to_string(),
             ),
         },
     );
@@ -2929,7 +3463,7 @@ fn create_browser_tool(browser_enabled: bool) -> OpenAiTool {
         "method".to_string(),
         JsonSchema::String {
             description: Some(
-                "For action=cdp: Chrome DevTools Protocol method name (e.g., 'Page.navigate')."
+                "For action=cdp: Chrome DevTools Protocol method name (e.g., 'Page.navigate'). Asks the user first."
                     .to_string(),
             ),
             allowed_values: None,
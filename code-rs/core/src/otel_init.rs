@@ -44,12 +44,18 @@ pub fn build_provider(
         },
     };
 
+    code_otel::metrics::configure(config.otel.metrics.json_dir.clone());
+    if config.otel.metrics.otlp && matches!(exporter, OtelExporter::None) {
+        tracing::warn!("otel.metrics.otlp is set but no [otel] exporter is configured");
+    }
+
     OtelProvider::from(&OtelSettings {
         service_name: config.responses_originator_header.clone(),
         service_version: service_version.to_string(),
         code_home: config.code_home.clone(),
         environment: config.otel.environment.to_string(),
         exporter,
+        metrics: config.otel.metrics.otlp,
     })
 }
 
@@ -0,0 +1,143 @@
+//! Markdown snapshots of visited pages, kept per session for `browser diff`.
+//!
+//! Each fetch or diff of a URL stores the page converted to Markdown; the next
+//! diff of the same URL reports what changed as a unified diff. Only the most
+//! recently used pages are kept.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use similar::ChangeTag;
+use similar::TextDiff;
+
+const MAX_PAGES: usize = 32;
+const MAX_DIFF_LINES: usize = 400;
+const CONTEXT_LINES: usize = 2;
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    markdown: String,
+    captured_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PageSnapshots {
+    entries: HashMap<String, Snapshot>,
+    clock: u64,
+}
+
+/// The previous visit of a page, as returned by [`PageSnapshots::record`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreviousVisit {
+    pub(crate) markdown: String,
+    pub(crate) age: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PageChange {
+    Unchanged,
+    Changed {
+        diff: String,
+        added: usize,
+        removed: usize,
+    },
+}
+
+impl PageSnapshots {
+    /// Store `markdown` as the latest visit of `url` and return the visit it replaces.
+    pub(crate) fn record(&mut self, url: &str, markdown: String) -> Option<PreviousVisit> {
+        self.clock += 1;
+        let key = snapshot_key(url);
+        let snapshot = Snapshot {
+            markdown,
+            captured_at: Instant::now(),
+            last_used: self.clock,
+        };
+        let previous = self.entries.insert(key, snapshot);
+        if self.entries.len() > MAX_PAGES
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, snapshot)| snapshot.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        previous.map(|snapshot| PreviousVisit {
+            markdown: snapshot.markdown,
+            age: snapshot.captured_at.elapsed(),
+        })
+    }
+}
+
+/// Pages that differ only in their fragment are the same visit.
+fn snapshot_key(url: &str) -> String {
+    url.split('#').next().unwrap_or(url).to_string()
+}
+
+pub(crate) fn compare(previous: &str, current: &str) -> PageChange {
+    if previous == current {
+        return PageChange::Unchanged;
+    }
+    let diff = TextDiff::from_lines(previous, current);
+    let (mut added, mut removed) = (0, 0);
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => added += 1,
+            ChangeTag::Delete => removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let unified = diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header("previous visit", "current")
+        .to_string();
+    let lines: Vec<&str> = unified.lines().collect();
+    let mut diff = lines[..lines.len().min(MAX_DIFF_LINES)].join("\n");
+    if lines.len() > MAX_DIFF_LINES {
+        diff.push_str(&format!("\n… {} more diff lines", lines.len() - MAX_DIFF_LINES));
+    }
+    PageChange::Changed {
+        diff,
+        added,
+        removed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn records_visits_and_diffs_changes() {
+        let mut snapshots = PageSnapshots::default();
+        let url = "http://localhost:3000/status#build";
+        assert_eq!(snapshots.record(url, "# Status\n\nBuild: running\n".to_string()), None);
+
+        let previous = snapshots
+            .record("http://localhost:3000/status", "# Status\n\nBuild: passed\n".to_string())
+            .expect("same page without the fragment");
+        assert_eq!(previous.markdown, "# Status\n\nBuild: running\n");
+
+        assert_eq!(compare("same\n", "same\n"), PageChange::Unchanged);
+        assert_eq!(
+            compare(&previous.markdown, "# Status\n\nBuild: passed\n"),
+            PageChange::Changed {
+                diff: "--- previous visit\n+++ current\n@@ -1,3 +1,3 @@\n # Status\n \n-Build: running\n+Build: passed"
+                    .to_string(),
+                added: 1,
+                removed: 1,
+            }
+        );
+
+        for page in 0..MAX_PAGES {
+            snapshots.record(&format!("http://localhost:3000/{page}"), String::new());
+        }
+        assert_eq!(snapshots.entries.len(), MAX_PAGES);
+        assert!(!snapshots.entries.contains_key("http://localhost:3000/status"));
+    }
+}
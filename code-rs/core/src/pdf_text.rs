@@ -0,0 +1,1137 @@
+//! Plain-text extraction for PDFs returned by `web_fetch`.
+//!
+//! This is deliberately small: it indexes the file's objects (including
+//! compressed object streams), walks the page tree, inflates each page's
+//! content streams and replays the text operators. Fonts with a `ToUnicode`
+//! CMap are decoded through it; simple fonts are read as Latin-1. Layout is
+//! approximated from text positioning, which is enough for docs, papers and
+//! reports but not for scanned images (those yield no text).
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+/// Most bytes one FlateDecode pass may inflate to.
+const MAX_INFLATED_BYTES: u64 = 16 * 1024 * 1024;
+/// Inflated bytes allowed across every stream of a document, so many small
+/// compression bombs cannot add up either.
+const MAX_DOCUMENT_INFLATED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Text of each page, in page order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PdfText {
+    pub(crate) pages: Vec<String>,
+}
+
+impl PdfText {
+    /// Pages joined with `--- Page N ---` markers.
+    pub(crate) fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for (idx, page) in self.pages.iter().enumerate() {
+            if idx > 0 {
+                out.push_str("\n\n");
+            }
+            out.push_str(&format!("--- Page {} ---\n\n", idx + 1));
+            out.push_str(if page.is_empty() { "(no text)" } else { page });
+        }
+        out
+    }
+}
+
+pub(crate) fn is_pdf(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"%PDF-")
+}
+
+/// Whether a response should be read as bytes and checked for a PDF.
+pub(crate) fn response_may_be_pdf(content_type: &str, url: &str) -> bool {
+    let content_type = content_type.to_ascii_lowercase();
+    content_type.contains("application/pdf")
+        || content_type.contains("application/x-pdf")
+        || url
+            .split(['?', '#'])
+            .next()
+            .is_some_and(|path| path.to_ascii_lowercase().ends_with(".pdf"))
+}
+
+pub(crate) fn extract_text(bytes: &[u8]) -> Result<PdfText, String> {
+    if !is_pdf(bytes) {
+        return Err("not a PDF file".to_string());
+    }
+    let doc = Document::load(bytes);
+    if doc.encrypted {
+        return Err("the PDF is encrypted".to_string());
+    }
+    let pages = doc.pages();
+    if pages.is_empty() {
+        return Err("no pages found in the PDF".to_string());
+    }
+    Ok(PdfText {
+        pages: pages.iter().map(|page| doc.page_text(page)).collect(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Obj {
+    Null,
+    Number(f64),
+    Name(String),
+    Str(Vec<u8>),
+    Array(Vec<Obj>),
+    Dict(HashMap<String, Obj>),
+    Ref(u32),
+    Stream(HashMap<String, Obj>, Vec<u8>),
+    /// Content stream operator (only produced when lexing page content).
+    Operator(String),
+}
+
+impl Obj {
+    fn dict(&self) -> Option<&HashMap<String, Obj>> {
+        match self {
+            Obj::Dict(dict) | Obj::Stream(dict, _) => Some(dict),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self {
+            Obj::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn number(&self) -> Option<f64> {
+        match self {
+            Obj::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+struct Page {
+    contents: Vec<Obj>,
+    resources: Option<Obj>,
+}
+
+struct Document {
+    objects: HashMap<u32, Obj>,
+    root: Option<Obj>,
+    encrypted: bool,
+    /// Inflated bytes still allowed for this document.
+    inflate_budget: Cell<u64>,
+}
+
+impl Document {
+    fn load(bytes: &[u8]) -> Self {
+        let mut objects = HashMap::new();
+        let mut root = None;
+        let mut encrypted = false;
+        let mut pos = 0;
+        while let Some(found) = find(bytes, b"obj", pos) {
+            pos = found + 3;
+            let Some(number) = object_number_before(bytes, found) else {
+                continue;
+            };
+            if bytes.get(pos).is_some_and(|b| !is_delimiter(*b) && !b.is_ascii_whitespace()) {
+                continue;
+            }
+            let mut lexer = Lexer::new(bytes, pos);
+            let Some(object) = lexer.parse_object() else {
+                continue;
+            };
+            pos = lexer.pos;
+            // Later definitions win, which is how incremental updates work.
+            objects.insert(number, object);
+        }
+
+        // Trailer dictionaries, or the xref stream dictionaries of newer files.
+        let mut trailers: Vec<HashMap<String, Obj>> = Vec::new();
+        let mut pos = 0;
+        while let Some(found) = find(bytes, b"trailer", pos) {
+            pos = found + 7;
+            if let Some(Obj::Dict(dict)) = Lexer::new(bytes, pos).parse_object() {
+                trailers.push(dict);
+            }
+        }
+        for object in objects.values() {
+            if let Some(dict) = object.dict()
+                && dict.get("Type").and_then(Obj::name) == Some("XRef")
+            {
+                trailers.push(dict.clone());
+            }
+        }
+        for trailer in &trailers {
+            encrypted |= trailer.contains_key("Encrypt");
+            if root.is_none() {
+                root = trailer.get("Root").cloned();
+            }
+        }
+
+        let mut doc = Document {
+            objects,
+            root,
+            encrypted,
+            inflate_budget: Cell::new(MAX_DOCUMENT_INFLATED_BYTES),
+        };
+        doc.load_object_streams();
+        doc
+    }
+
+    /// Objects packed into `/Type /ObjStm` streams (PDF 1.5+).
+    fn load_object_streams(&mut self) {
+        let mut packed = Vec::new();
+        for object in self.objects.values() {
+            let Obj::Stream(dict, _) = object else {
+                continue;
+            };
+            if dict.get("Type").and_then(Obj::name) != Some("ObjStm") {
+                continue;
+            }
+            let Some(data) = self.decode_stream(object) else {
+                continue;
+            };
+            let count = dict.get("N").and_then(Obj::number).unwrap_or(0.0) as usize;
+            let first = dict.get("First").and_then(Obj::number).unwrap_or(0.0) as usize;
+            let mut header = Lexer::new(&data, 0);
+            let mut entries = Vec::new();
+            for _ in 0..count {
+                match (header.parse_object(), header.parse_object()) {
+                    (Some(Obj::Number(number)), Some(Obj::Number(offset))) => {
+                        entries.push((number as u32, offset as usize));
+                    }
+                    _ => break,
+                }
+            }
+            for (number, offset) in entries {
+                let Some(start) = first.checked_add(offset) else {
+                    continue;
+                };
+                if let Some(object) = Lexer::new(&data, start).parse_object() {
+                    packed.push((number, object));
+                }
+            }
+        }
+        for (number, object) in packed {
+            self.objects.entry(number).or_insert(object);
+        }
+    }
+
+    fn decode_stream(&self, object: &Obj) -> Option<Vec<u8>> {
+        let Obj::Stream(dict, data) = object else {
+            return None;
+        };
+        let filters: Vec<&str> = match dict.get("Filter") {
+            None => Vec::new(),
+            Some(Obj::Name(name)) => vec![name.as_str()],
+            Some(Obj::Array(items)) => items.iter().filter_map(Obj::name).collect(),
+            Some(_) => return None,
+        };
+        let mut data = data.clone();
+        for filter in filters {
+            match filter {
+                "FlateDecode" | "Fl" => {
+                    let limit = self.inflate_budget.get().min(MAX_INFLATED_BYTES);
+                    let mut inflated = Vec::new();
+                    // Truncated streams still yield their leading text, and
+                    // output past the limit is cut off rather than buffered.
+                    let _ = ZlibDecoder::new(data.as_slice())
+                        .take(limit)
+                        .read_to_end(&mut inflated);
+                    self.inflate_budget
+                        .set(self.inflate_budget.get().saturating_sub(inflated.len() as u64));
+                    if inflated.is_empty() {
+                        return None;
+                    }
+                    data = inflated;
+                }
+                // Image codecs and rarer text filters are not needed for text.
+                _ => return None,
+            }
+        }
+        Some(data)
+    }
+
+    fn resolve<'a>(&'a self, object: &'a Obj) -> &'a Obj {
+        let mut current = object;
+        // Bounded to survive reference cycles in broken files.
+        for _ in 0..16 {
+            match current {
+                Obj::Ref(number) => match self.objects.get(number) {
+                    Some(next) => current = next,
+                    None => return &Obj::Null,
+                },
+                _ => return current,
+            }
+        }
+        &Obj::Null
+    }
+
+    fn get<'a>(&'a self, dict: &'a HashMap<String, Obj>, key: &str) -> Option<&'a Obj> {
+        dict.get(key).map(|value| self.resolve(value))
+    }
+
+    fn pages(&self) -> Vec<Page> {
+        let mut pages = Vec::new();
+        let tree = self
+            .root
+            .as_ref()
+            .map(|root| self.resolve(root))
+            .and_then(Obj::dict)
+            .and_then(|catalog| self.get(catalog, "Pages"));
+        if let Some(tree) = tree {
+            self.collect_pages(tree, None, &mut pages, 0);
+        }
+        if pages.is_empty() {
+            // No usable catalog: fall back to page objects in file order.
+            let mut numbers: Vec<&u32> = self
+                .objects
+                .iter()
+                .filter(|(_, object)| {
+                    object.dict().and_then(|dict| dict.get("Type")).and_then(Obj::name)
+                        == Some("Page")
+                })
+                .map(|(number, _)| number)
+                .collect();
+            numbers.sort();
+            for number in numbers {
+                if let Some(dict) = self.objects.get(number).and_then(Obj::dict) {
+                    pages.push(self.page_from(dict, None));
+                }
+            }
+        }
+        pages
+    }
+
+    fn collect_pages(
+        &self,
+        node: &Obj,
+        inherited: Option<&Obj>,
+        pages: &mut Vec<Page>,
+        depth: usize,
+    ) {
+        let Some(dict) = node.dict() else {
+            return;
+        };
+        if depth > 32 {
+            return;
+        }
+        let resources = self.get(dict, "Resources").or(inherited);
+        match self.get(dict, "Kids") {
+            Some(Obj::Array(kids)) => {
+                for kid in kids {
+                    self.collect_pages(self.resolve(kid), resources, pages, depth + 1);
+                }
+            }
+            _ => pages.push(self.page_from(dict, resources)),
+        }
+    }
+
+    fn page_from(&self, dict: &HashMap<String, Obj>, inherited: Option<&Obj>) -> Page {
+        let contents = match dict.get("Contents").map(|value| self.resolve(value)) {
+            Some(Obj::Array(items)) => items.iter().map(|item| self.resolve(item).clone()).collect(),
+            Some(stream @ Obj::Stream(..)) => vec![stream.clone()],
+            _ => Vec::new(),
+        };
+        Page {
+            contents,
+            resources: self.get(dict, "Resources").or(inherited).cloned(),
+        }
+    }
+
+    fn fonts(&self, resources: Option<&Obj>) -> HashMap<String, FontDecoder> {
+        let mut fonts = HashMap::new();
+        let Some(font_dict) = resources
+            .and_then(Obj::dict)
+            .and_then(|resources| self.get(resources, "Font"))
+            .and_then(Obj::dict)
+        else {
+            return fonts;
+        };
+        for (name, font) in font_dict {
+            let Some(font) = self.resolve(font).dict() else {
+                continue;
+            };
+            let cmap = self
+                .get(font, "ToUnicode")
+                .and_then(|stream| self.decode_stream(stream))
+                .map(|data| CMap::parse(&data));
+            let two_byte = self.get(font, "Subtype").and_then(Obj::name) == Some("Type0");
+            fonts.insert(name.clone(), FontDecoder { cmap, two_byte });
+        }
+        fonts
+    }
+
+    fn page_text(&self, page: &Page) -> String {
+        let fonts = self.fonts(page.resources.as_ref());
+        let mut content = Vec::new();
+        for stream in &page.contents {
+            if let Some(data) = self.decode_stream(stream) {
+                content.extend_from_slice(&data);
+                content.push(b'\n');
+            }
+        }
+        render_content(&content, &fonts)
+    }
+}
+
+#[derive(Default)]
+struct FontDecoder {
+    cmap: Option<CMap>,
+    two_byte: bool,
+}
+
+impl FontDecoder {
+    fn decode(&self, bytes: &[u8]) -> String {
+        if let Some(cmap) = &self.cmap {
+            return cmap.decode(bytes);
+        }
+        if self.two_byte {
+            // Composite font without a ToUnicode map: glyph ids are not text.
+            return String::new();
+        }
+        bytes.iter().map(|&b| latin1(b)).collect()
+    }
+}
+
+fn latin1(byte: u8) -> char {
+    match byte {
+        0x91 | 0x92 => '\'',
+        0x93 | 0x94 => '"',
+        0x95 => '•',
+        0x96 | 0x97 => '-',
+        0x85 => '…',
+        0x00..=0x1f => ' ',
+        other => char::from(other),
+    }
+}
+
+#[derive(Default)]
+struct CMap {
+    code_len: usize,
+    chars: HashMap<u32, String>,
+    ranges: Vec<(u32, u32, Vec<u16>)>,
+}
+
+impl CMap {
+    fn parse(data: &[u8]) -> Self {
+        let mut cmap = CMap::default();
+        let mut lexer = Lexer::new(data, 0);
+        lexer.content_mode = true;
+        let mut section = "";
+        let mut operands: Vec<Obj> = Vec::new();
+        while let Some(token) = lexer.parse_object() {
+            match token {
+                Obj::Operator(op) => {
+                    match op.as_str() {
+                        "beginbfchar" => section = "bfchar",
+                        "beginbfrange" => section = "bfrange",
+                        "begincodespacerange" => section = "codespace",
+                        "endbfchar" => {
+                            for pair in operands.chunks(2) {
+                                if let [Obj::Str(src), Obj::Str(dst)] = pair {
+                                    cmap.note_code_len(src.len());
+                                    cmap.chars.insert(code(src), utf16(dst));
+                                }
+                            }
+                            section = "";
+                        }
+                        "endbfrange" => {
+                            for triple in operands.chunks(3) {
+                                if let [Obj::Str(lo), Obj::Str(hi), dst] = triple {
+                                    cmap.note_code_len(lo.len());
+                                    cmap.add_range(code(lo), code(hi), dst);
+                                }
+                            }
+                            section = "";
+                        }
+                        "endcodespacerange" => {
+                            if let Some(Obj::Str(lo)) = operands.first() {
+                                cmap.code_len = lo.len();
+                            }
+                            section = "";
+                        }
+                        _ => {}
+                    }
+                    operands.clear();
+                }
+                other if !section.is_empty() => operands.push(other),
+                _ => {}
+            }
+        }
+        if cmap.code_len == 0 {
+            cmap.code_len = 1;
+        }
+        cmap
+    }
+
+    fn note_code_len(&mut self, len: usize) {
+        if self.code_len == 0 {
+            self.code_len = len;
+        }
+    }
+
+    fn add_range(&mut self, lo: u32, hi: u32, dst: &Obj) {
+        match dst {
+            Obj::Str(base) => self.ranges.push((lo, hi, utf16_units(base))),
+            Obj::Array(items) => {
+                for (offset, item) in items.iter().enumerate() {
+                    let Some(value_code) =
+                        u32::try_from(offset).ok().and_then(|offset| lo.checked_add(offset))
+                    else {
+                        break;
+                    };
+                    if value_code > hi {
+                        break;
+                    }
+                    if let Obj::Str(value) = item {
+                        self.chars.insert(value_code, utf16(value));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> String {
+        let len = self.code_len.clamp(1, 4);
+        let mut out = String::new();
+        for chunk in bytes.chunks(len) {
+            let value = code(chunk);
+            if let Some(text) = self.chars.get(&value) {
+                out.push_str(text);
+                continue;
+            }
+            let ranged = self.ranges.iter().find(|(lo, hi, _)| (*lo..=*hi).contains(&value));
+            if let Some((lo, _, base)) = ranged {
+                let mut units = base.clone();
+                if let Some(last) = units.last_mut() {
+                    *last = last.wrapping_add((value - lo) as u16);
+                }
+                out.push_str(&String::from_utf16_lossy(&units));
+            }
+        }
+        out
+    }
+}
+
+fn code(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0u32, |acc, &b| (acc << 8) | u32::from(b))
+}
+
+fn utf16_units(bytes: &[u8]) -> Vec<u16> {
+    bytes
+        .chunks(2)
+        .map(|pair| match pair {
+            [hi, lo] => u16::from_be_bytes([*hi, *lo]),
+            [single] => u16::from(*single),
+            _ => 0,
+        })
+        .collect()
+}
+
+fn utf16(bytes: &[u8]) -> String {
+    String::from_utf16_lossy(&utf16_units(bytes))
+}
+
+/// Replay a content stream's text operators into lines of text.
+fn render_content(content: &[u8], fonts: &HashMap<String, FontDecoder>) -> String {
+    let fallback = FontDecoder::default();
+    let mut font = &fallback;
+    let mut out = String::new();
+    let mut operands: Vec<Obj> = Vec::new();
+    let mut last_y: Option<f64> = None;
+    let mut lexer = Lexer::new(content, 0);
+    lexer.content_mode = true;
+    let newline = |out: &mut String| {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+    };
+    while let Some(token) = lexer.parse_object() {
+        let Obj::Operator(op) = token else {
+            operands.push(token);
+            continue;
+        };
+        match op.as_str() {
+            "Tf" => {
+                if let Some(name) = operands.first().and_then(Obj::name) {
+                    font = fonts.get(name).unwrap_or(&fallback);
+                }
+            }
+            "Tj" => {
+                if let Some(Obj::Str(text)) = operands.last() {
+                    out.push_str(&font.decode(text));
+                }
+            }
+            "'" | "\"" => {
+                newline(&mut out);
+                if let Some(Obj::Str(text)) = operands.last() {
+                    out.push_str(&font.decode(text));
+                }
+            }
+            "TJ" => {
+                if let Some(Obj::Array(items)) = operands.last() {
+                    for item in items {
+                        match item {
+                            Obj::Str(text) => out.push_str(&font.decode(text)),
+                            // Large negative adjustments are word gaps.
+                            Obj::Number(adjust) if *adjust < -200.0 && !out.ends_with(' ') => {
+                                out.push(' ');
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            "Td" | "TD" => {
+                let tx = operands.first().and_then(Obj::number).unwrap_or(0.0);
+                let ty = operands.get(1).and_then(Obj::number).unwrap_or(0.0);
+                if ty.abs() > 0.5 {
+                    newline(&mut out);
+                } else if tx.abs() > 0.5 && !out.ends_with([' ', '\n']) && !out.is_empty() {
+                    out.push(' ');
+                }
+            }
+            "Tm" => {
+                let y = operands.get(5).and_then(Obj::number);
+                if let (Some(y), Some(previous)) = (y, last_y)
+                    && (y - previous).abs() > 0.5
+                {
+                    newline(&mut out);
+                } else if !out.ends_with([' ', '\n']) && !out.is_empty() {
+                    out.push(' ');
+                }
+                last_y = y;
+            }
+            "T*" => newline(&mut out),
+            "ET" => {
+                if !out.ends_with([' ', '\n']) && !out.is_empty() {
+                    out.push(' ');
+                }
+            }
+            _ => {}
+        }
+        operands.clear();
+    }
+    tidy(&out)
+}
+
+fn tidy(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = 0;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank += 1;
+            if blank > 1 {
+                continue;
+            }
+        } else {
+            blank = 0;
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.trim().to_string()
+}
+
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|idx| idx + from)
+}
+
+/// Object number of an `N G obj` header ending right before `obj_pos`.
+fn object_number_before(bytes: &[u8], obj_pos: usize) -> Option<u32> {
+    let mut idx = obj_pos;
+    let mut numbers = Vec::new();
+    for _ in 0..2 {
+        let end = idx;
+        while idx > 0 && bytes[idx - 1].is_ascii_whitespace() {
+            idx -= 1;
+        }
+        if idx == end {
+            return None;
+        }
+        let digits_end = idx;
+        while idx > 0 && bytes[idx - 1].is_ascii_digit() {
+            idx -= 1;
+        }
+        if idx == digits_end {
+            return None;
+        }
+        numbers.push(std::str::from_utf8(&bytes[idx..digits_end]).ok()?.parse::<u32>().ok()?);
+    }
+    numbers.last().copied()
+}
+
+fn is_delimiter(byte: u8) -> bool {
+    matches!(byte, b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%')
+}
+
+/// Deepest array/dictionary nesting parsed; deeper input ends the object so
+/// crafted files cannot exhaust the stack.
+const MAX_NESTING: usize = 64;
+
+struct Lexer<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Keywords become [`Obj::Operator`] tokens instead of ending the object.
+    content_mode: bool,
+    /// Arrays and dictionaries currently open.
+    depth: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(data: &'a [u8], pos: usize) -> Self {
+        Self {
+            data,
+            pos,
+            content_mode: false,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(byte) = self.peek() {
+            if byte.is_ascii_whitespace() || byte == 0 {
+                self.pos += 1;
+            } else if byte == b'%' {
+                while self.peek().is_some_and(|b| b != b'\n' && b != b'\r') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Option<Obj> {
+        loop {
+            if let Some(object) = self.parse_token()? {
+                return Some(object);
+            }
+        }
+    }
+
+    /// One token, or `Some(None)` after skipping a stray byte.
+    fn parse_token(&mut self) -> Option<Option<Obj>> {
+        self.skip_whitespace();
+        let byte = self.peek()?;
+        let object = match byte {
+            b'/' => {
+                self.pos += 1;
+                Obj::Name(self.read_regular())
+            }
+            b'(' => Obj::Str(self.read_literal_string()),
+            b'<' | b'[' if self.depth >= MAX_NESTING => return None,
+            b'<' if self.data.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                self.depth += 1;
+                let dict = self.read_dict();
+                self.depth -= 1;
+                return Some(Some(self.maybe_stream(dict)));
+            }
+            b'<' => Obj::Str(self.read_hex_string()),
+            b'[' => {
+                self.pos += 1;
+                self.depth += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        None => break,
+                        _ => match self.parse_object() {
+                            Some(Obj::Operator(_)) | None => break,
+                            Some(item) => items.push(item),
+                        },
+                    }
+                }
+                self.depth -= 1;
+                Obj::Array(items)
+            }
+            b']' | b'>' | b')' | b'{' | b'}' => {
+                self.pos += 1;
+                return Some(None);
+            }
+            b'0'..=b'9' | b'+' | b'-' | b'.' => self.read_number_or_ref(),
+            _ => {
+                let word = self.read_regular();
+                if word.is_empty() {
+                    self.pos += 1;
+                    return Some(None);
+                }
+                match word.as_str() {
+                    // Booleans never matter for text extraction.
+                    "true" | "false" | "null" => Obj::Null,
+                    "BI" if self.content_mode => {
+                        self.skip_inline_image();
+                        Obj::Operator(word)
+                    }
+                    _ if self.content_mode => Obj::Operator(word),
+                    // `endobj`, `stream` without a dictionary, etc.
+                    _ => return None,
+                }
+            }
+        };
+        Some(Some(object))
+    }
+
+    fn read_regular(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !b.is_ascii_whitespace() && !is_delimiter(b) && b != 0)
+        {
+            self.pos += 1;
+        }
+        let raw = String::from_utf8_lossy(&self.data[start..self.pos]).into_owned();
+        decode_name_escapes(&raw)
+    }
+
+    fn read_number_or_ref(&mut self) -> Obj {
+        let first = self.read_number();
+        if self.content_mode || first.fract() != 0.0 || first < 0.0 {
+            return Obj::Number(first);
+        }
+        // `N G R` is an indirect reference.
+        let saved = self.pos;
+        self.skip_whitespace();
+        if self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            let _generation = self.read_number();
+            self.skip_whitespace();
+            if self.peek() == Some(b'R')
+                && self
+                    .data
+                    .get(self.pos + 1)
+                    .is_none_or(|b| b.is_ascii_whitespace() || is_delimiter(*b))
+            {
+                self.pos += 1;
+                return Obj::Ref(first as u32);
+            }
+        }
+        self.pos = saved;
+        Obj::Number(first)
+    }
+
+    fn read_number(&mut self) -> f64 {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.'))
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .unwrap_or(0.0)
+    }
+
+    fn read_literal_string(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut out = Vec::new();
+        let mut depth = 1;
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            match byte {
+                b'\\' => {
+                    let Some(escaped) = self.peek() else {
+                        break;
+                    };
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => out.push(b'\n'),
+                        b'r' => out.push(b'\r'),
+                        b't' => out.push(b'\t'),
+                        b'b' => out.push(0x08),
+                        b'f' => out.push(0x0c),
+                        b'0'..=b'7' => {
+                            let mut value = u32::from(escaped - b'0');
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(digit @ b'0'..=b'7') => {
+                                        value = value * 8 + u32::from(digit - b'0');
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            out.push(value as u8);
+                        }
+                        // Line continuation.
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        other => out.push(other),
+                    }
+                }
+                b'(' => {
+                    depth += 1;
+                    out.push(byte);
+                }
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    out.push(byte);
+                }
+                _ => out.push(byte),
+            }
+        }
+        out
+    }
+
+    fn read_hex_string(&mut self) -> Vec<u8> {
+        self.pos += 1;
+        let mut digits = Vec::new();
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            if byte == b'>' {
+                break;
+            }
+            if let Some(digit) = (byte as char).to_digit(16) {
+                digits.push(digit as u8);
+            }
+        }
+        if digits.len() % 2 == 1 {
+            digits.push(0);
+        }
+        digits.chunks(2).map(|pair| (pair[0] << 4) | pair[1]).collect()
+    }
+
+    fn read_dict(&mut self) -> HashMap<String, Obj> {
+        let mut dict = HashMap::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'>') => {
+                    self.pos += 1;
+                    if self.peek() == Some(b'>') {
+                        self.pos += 1;
+                    }
+                    break;
+                }
+                Some(b'/') => {
+                    self.pos += 1;
+                    let key = self.read_regular();
+                    match self.parse_object() {
+                        Some(value) => {
+                            dict.insert(key, value);
+                        }
+                        None => break,
+                    }
+                }
+                Some(_) => {
+                    // Malformed entry; skip a token and keep going.
+                    if self.parse_object().is_none() {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+        dict
+    }
+
+    fn maybe_stream(&mut self, dict: HashMap<String, Obj>) -> Obj {
+        let saved = self.pos;
+        self.skip_whitespace();
+        if !self.data.get(self.pos..).is_some_and(|rest| rest.starts_with(b"stream")) {
+            self.pos = saved;
+            return Obj::Dict(dict);
+        }
+        self.pos += b"stream".len();
+        if self.peek() == Some(b'\r') {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'\n') {
+            self.pos += 1;
+        }
+        let start = self.pos;
+        let declared = match dict.get("Length") {
+            Some(Obj::Number(length)) => Some(*length as usize),
+            _ => None,
+        };
+        let end = declared
+            .and_then(|length| start.checked_add(length))
+            .filter(|&end| {
+                self.data
+                    .get(end..)
+                    .is_some_and(|rest| trim_leading_eol(rest).starts_with(b"endstream"))
+            })
+            .or_else(|| {
+                find(self.data, b"endstream", start).map(|end| {
+                    let mut end = end;
+                    while end > start && matches!(self.data[end - 1], b'\r' | b'\n') {
+                        end -= 1;
+                    }
+                    end
+                })
+            })
+            .unwrap_or(self.data.len());
+        let data = self.data.get(start..end).unwrap_or_default().to_vec();
+        self.pos = find(self.data, b"endstream", end).map_or(self.data.len(), |idx| idx + 9);
+        Obj::Stream(dict, data)
+    }
+
+    fn skip_inline_image(&mut self) {
+        // Inline image data sits between `ID` and `EI` and is binary.
+        if let Some(id) = find(self.data, b"ID", self.pos) {
+            let mut pos = id + 2;
+            while let Some(ei) = find(self.data, b"EI", pos) {
+                let before = ei.checked_sub(1).and_then(|idx| self.data.get(idx));
+                let after = self.data.get(ei + 2);
+                if before.is_some_and(u8::is_ascii_whitespace)
+                    && after.is_none_or(u8::is_ascii_whitespace)
+                {
+                    self.pos = ei + 2;
+                    return;
+                }
+                pos = ei + 2;
+            }
+        }
+        self.pos = self.data.len();
+    }
+}
+
+fn trim_leading_eol(bytes: &[u8]) -> &[u8] {
+    let skip = bytes.iter().take_while(|b| matches!(b, b'\r' | b'\n' | b' ')).count();
+    &bytes[skip..]
+}
+
+fn decode_name_escapes(raw: &str) -> String {
+    if !raw.contains('#') {
+        return raw.to_string();
+    }
+    let mut out = Vec::new();
+    let bytes = raw.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'#'
+            && let Some(value) = raw
+                .get(idx + 1..idx + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(value);
+            idx += 3;
+            continue;
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    fn deflate(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn pdf(objects: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = b"%PDF-1.4\n".to_vec();
+        for (idx, body) in objects.iter().enumerate() {
+            out.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+        out.extend_from_slice(b"trailer\n<< /Root 1 0 R >>\n%%EOF\n");
+        out
+    }
+
+    fn stream(dict: &str, data: &[u8]) -> Vec<u8> {
+        let mut out = format!("<< {dict} /Length {} >>\nstream\n", data.len()).into_bytes();
+        out.extend_from_slice(data);
+        out.extend_from_slice(b"\nendstream");
+        out
+    }
+
+    #[test]
+    fn extracts_text_per_page_with_markers() {
+        let page_one = b"BT /F1 12 Tf 72 720 Td (Release notes) Tj 0 -14 Td [(Fixed a) -300 (crash\\051)] TJ ET";
+        let page_two = b"BT /F2 12 Tf 72 720 Td <00480069> Tj ET";
+        let cmap = b"1 begincodespacerange <0000> <FFFF> endcodespacerange\n\
+            1 beginbfchar <0048> <0048> endbfchar\n\
+            1 beginbfrange <0069> <006A> <0069> endbfrange";
+        let bytes = pdf(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            b"<< /Type /Pages /Kids [3 0 R 4 0 R] /Count 2 /Resources << /Font << /F1 5 0 R /F2 6 0 R >> >> >>".to_vec(),
+            b"<< /Type /Page /Parent 2 0 R /Contents 7 0 R >>".to_vec(),
+            b"<< /Type /Page /Parent 2 0 R /Contents 8 0 R >>".to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_vec(),
+            b"<< /Type /Font /Subtype /Type0 /ToUnicode 9 0 R >>".to_vec(),
+            stream("/Filter /FlateDecode", &deflate(page_one)),
+            stream("", page_two),
+            stream("", cmap),
+        ]);
+
+        let text = extract_text(&bytes).expect("pdf text");
+        assert_eq!(text.pages, vec!["Release notes\nFixed a crash)", "Hi"]);
+        assert_eq!(
+            text.to_markdown(),
+            "--- Page 1 ---\n\nRelease notes\nFixed a crash)\n\n--- Page 2 ---\n\nHi"
+        );
+        assert!(extract_text(b"<html></html>").is_err());
+        assert!(response_may_be_pdf("text/plain", "https://example.com/paper.PDF?dl=1"));
+    }
+
+    #[test]
+    fn crafted_lengths_offsets_and_nesting_do_not_panic() {
+        let deep = "[".repeat(100_000);
+        let packed = stream("/Type /ObjStm /N 1 /First 18446744073709551615", b"7 5 (x)");
+        let cmap = b"1 beginbfrange <FFFFFFFF> <FFFFFFFF> [<0041> <0042>] endbfrange";
+        let bytes = pdf(&[
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            b"<< /Type /Pages /Kids [3 0 R] /Resources << /Font << /F1 6 0 R >> >> >>".to_vec(),
+            b"<< /Type /Page /Contents 4 0 R >>".to_vec(),
+            b"<< /Length 18446744073709551615 >>\nstream\nBT (ok) Tj ET\nendstream".to_vec(),
+            packed,
+            b"<< /Type /Font /Subtype /Type0 /ToUnicode 7 0 R >>".to_vec(),
+            stream("", cmap),
+            format!("<< /Deep {deep} >>").into_bytes(),
+        ]);
+
+        let text = extract_text(&bytes).expect("pdf text");
+        assert_eq!(text.pages, vec!["ok"]);
+    }
+
+    #[test]
+    fn inflated_output_is_capped_per_stream_and_per_document() {
+        let bomb = deflate(&vec![b' '; MAX_INFLATED_BYTES as usize + 1024]);
+        let doc = Document::load(&pdf(&[stream("/Filter /FlateDecode", &bomb)]));
+        let object = doc.objects.get(&1).expect("stream object");
+
+        let first = doc.decode_stream(object).expect("inflated");
+        assert_eq!(first.len() as u64, MAX_INFLATED_BYTES);
+        for _ in 1..(MAX_DOCUMENT_INFLATED_BYTES / MAX_INFLATED_BYTES) {
+            assert!(doc.decode_stream(object).is_some());
+        }
+        assert_eq!(doc.inflate_budget.get(), 0);
+        assert_eq!(doc.decode_stream(object), None);
+    }
+}
@@ -153,6 +153,12 @@ pub enum Op {
         /// Dynamic tools to include for this session.
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         dynamic_tools: Vec<DynamicToolSpec>,
+
+        /// Name of the active `[profiles.<name>]` entry, if any. Model choices
+        /// are persisted to it and usage limits are tracked under it.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[serde(default)]
+        active_profile: Option<String>,
     },
 
     /// Abort current task.
@@ -286,6 +292,30 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Re-list tools from one MCP server (or all of them), bypassing the
+    /// tool cache. Progress is reported via `EventMsg::BackgroundEvent`,
+    /// followed by an updated `EventMsg::McpListToolsResponse`.
+    RefreshMcpTools { server: Option<String> },
+
+    /// Request the names tools are exposed under, including how conflicting
+    /// MCP tool names were resolved.
+    /// Reply is delivered via `EventMsg::ToolNamesResponse`.
+    ListToolNames,
+
+    /// Request the buffered stderr/log output captured from one MCP server.
+    /// Reply is delivered via `EventMsg::McpServerLogsResponse`.
+    McpServerLogs { server: String },
+
+    /// Ask an MCP server to complete a prompt or resource-template argument.
+    /// Reply is delivered via `EventMsg::McpCompletionResponse`.
+    McpCompleteArgument {
+        server: String,
+        reference: McpCompletionRef,
+        argument: String,
+        /// Text typed so far for the argument.
+        value: String,
+    },
+
     /// Request the list of available custom prompts.
     /// Reply is delivered via `EventMsg::ListCustomPromptsResponse`.
     ListCustomPrompts,
@@ -880,6 +910,9 @@ pub enum EventMsg {
 
     McpToolCallBegin(McpToolCallBeginEvent),
 
+    /// Incremental progress or partial output from a running MCP tool call.
+    McpToolCallProgress(McpToolCallProgressEvent),
+
     McpToolCallEnd(McpToolCallEndEvent),
 
     /// Model requested a native web search
@@ -923,6 +956,9 @@ pub enum EventMsg {
     /// Notification that a patch application has finished.
     PatchApplyEnd(PatchApplyEndEvent),
 
+    /// New language server errors and warnings after an applied patch.
+    LspDiagnostics(LspDiagnosticsEvent),
+
     TurnDiff(TurnDiffEvent),
 
     /// Response to GetHistoryEntryRequest.
@@ -931,6 +967,15 @@ pub enum EventMsg {
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// Exposed tool names and their MCP origins.
+    ToolNamesResponse(ToolNamesResponseEvent),
+
+    /// Recent stderr/log output captured from a single MCP server.
+    McpServerLogsResponse(McpServerLogsResponseEvent),
+
+    /// Candidate values for an MCP argument completion request.
+    McpCompletionResponse(McpCompletionResponseEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
@@ -1278,6 +1323,74 @@ pub struct McpToolCallBeginEvent {
     pub invocation: McpInvocation,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpToolCallProgressEvent {
+    /// Identifier for the corresponding McpToolCallBegin that is still running.
+    pub call_id: String,
+    pub progress: f64,
+    pub total: Option<f64>,
+    /// Partial output or status text reported alongside the progress value.
+    pub message: Option<String>,
+}
+
+/// Target of an MCP argument completion (`ref/prompt` or `ref/resource`).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum McpCompletionRef {
+    Prompt { name: String },
+    Resource { uri: String },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpCompletionResponseEvent {
+    pub server: String,
+    pub argument: String,
+    /// The partial value the completions were computed for.
+    pub value: String,
+    pub values: Vec<String>,
+    pub has_more: bool,
+    /// Set when the server could not be reached or rejected the request.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ToolNamesResponseEvent {
+    /// Built-in tools advertised in the most recent turn, sorted.
+    pub builtin: Vec<String>,
+    /// MCP tools, sorted by exposed name.
+    pub mcp: Vec<McpToolNameMapping>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct McpToolNameMapping {
+    /// Name the model calls the tool by.
+    pub name: String,
+    pub server: String,
+    /// Tool name as declared by the server.
+    pub tool: String,
+    /// Other servers that declare a tool with the same name.
+    pub shared_with: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpServerLogsResponseEvent {
+    pub server: String,
+    /// `false` when no server with this name is configured for the session.
+    pub known: bool,
+    /// Buffered lines, oldest first.
+    pub entries: Vec<McpServerLogEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpServerLogEntry {
+    /// RFC 3339 timestamp of when the line was captured.
+    pub timestamp: String,
+    /// Either `stderr` or `notification`.
+    pub source: String,
+    pub level: Option<String>,
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct McpToolCallEndEvent {
     /// Identifier for the corresponding McpToolCallBegin that finished.
@@ -1354,6 +1467,9 @@ pub struct ExecCommandEndEvent {
     pub exit_code: i32,
     /// The duration of the command execution.
     pub duration: Duration,
+    /// Secrets redacted from the output.
+    #[serde(default)]
+    pub redactions: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1475,6 +1591,42 @@ pub struct PatchApplyEndEvent {
     pub success: bool,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LspDiagnosticsEvent {
+    /// Identifier of the apply_patch call that was checked.
+    pub call_id: String,
+    /// Servers that checked the patched files.
+    pub servers: Vec<String>,
+    /// New errors and warnings, errors first; may be capped below `total`.
+    pub diagnostics: Vec<LspDiagnostic>,
+    pub total: usize,
+    /// Servers that could not be started.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LspDiagnostic {
+    /// Path relative to the session cwd when it lies inside it.
+    pub path: String,
+    /// 1-based position of the start of the range.
+    pub line: u64,
+    pub column: u64,
+    pub severity: LspSeverity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LspSeverity {
+    Error,
+    Warning,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TurnDiffEvent {
     pub unified_diff: String,
@@ -0,0 +1,108 @@
+//! API keys for model providers saved with `code auth set` or `/auth`.
+//!
+//! Keys are indexed by the provider's `env_key` and kept in
+//! `CODE_HOME/provider_keys.json` through the same backend as `auth.json`
+//! (see [`crate::auth_store`]). The environment variable still wins when it
+//! is set, so existing setups keep working unchanged.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+const FILE_NAME: &str = "provider_keys.json";
+
+/// Keys loaded for this process, refreshed on every change.
+static LOADED: LazyLock<RwLock<BTreeMap<String, String>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+fn keys_file_path(code_home: &Path) -> PathBuf {
+    code_home.join(FILE_NAME)
+}
+
+fn read_keys(code_home: &Path) -> io::Result<BTreeMap<String, String>> {
+    match crate::auth_store::read(&keys_file_path(code_home)) {
+        Ok(contents) if contents.trim().is_empty() => Ok(BTreeMap::new()),
+        Ok(contents) => Ok(serde_json::from_str(&contents)?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_keys(code_home: &Path, keys: BTreeMap<String, String>) -> io::Result<()> {
+    let path = keys_file_path(code_home);
+    if keys.is_empty() {
+        crate::auth_store::remove(&path)?;
+    } else {
+        crate::auth_store::write(&path, &serde_json::to_string_pretty(&keys)?)?;
+    }
+    *LOADED.write().unwrap_or_else(PoisonError::into_inner) = keys;
+    Ok(())
+}
+
+/// Load the saved keys so providers can use them. Called while loading the config.
+pub fn load(code_home: &Path) -> io::Result<()> {
+    let keys = read_keys(code_home)?;
+    *LOADED.write().unwrap_or_else(PoisonError::into_inner) = keys;
+    Ok(())
+}
+
+/// The saved key for `env_key`, if any.
+pub fn stored_key(env_key: &str) -> Option<String> {
+    LOADED
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(env_key)
+        .cloned()
+}
+
+pub fn set_key(code_home: &Path, env_key: &str, api_key: &str) -> io::Result<()> {
+    let api_key = api_key.trim();
+    if api_key.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "the API key is empty"));
+    }
+    let mut keys = read_keys(code_home)?;
+    keys.insert(env_key.to_string(), api_key.to_string());
+    write_keys(code_home, keys)
+}
+
+/// Forget the saved key for `env_key`. Returns whether one was saved.
+pub fn remove_key(code_home: &Path, env_key: &str) -> io::Result<bool> {
+    let mut keys = read_keys(code_home)?;
+    let removed = keys.remove(env_key).is_some();
+    if removed {
+        write_keys(code_home, keys)?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn saves_and_removes_keys_by_env_var() {
+        // Other tests load configs concurrently and reset the process-wide
+        // cache, so check what was written rather than `stored_key`.
+        let home = tempdir().expect("tempdir");
+        set_key(home.path(), "OPENROUTER_API_KEY", " sk-or-123 \n").expect("save");
+        set_key(home.path(), "MISTRAL_API_KEY", "mistral-key").expect("save");
+        let keys = read_keys(home.path()).expect("read");
+        assert_eq!(keys.get("OPENROUTER_API_KEY").map(String::as_str), Some("sk-or-123"));
+        assert_eq!(keys.len(), 2);
+
+        assert!(remove_key(home.path(), "OPENROUTER_API_KEY").expect("remove"));
+        assert!(!remove_key(home.path(), "OPENROUTER_API_KEY").expect("remove again"));
+        assert!(remove_key(home.path(), "MISTRAL_API_KEY").expect("remove"));
+        assert_eq!(read_keys(home.path()).expect("read"), BTreeMap::new());
+        assert!(!home.path().join(FILE_NAME).exists());
+
+        let err = set_key(home.path(), "OPENROUTER_API_KEY", "  ").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
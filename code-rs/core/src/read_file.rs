@@ -0,0 +1,230 @@
+//! Ranged reads for the `read_file` tool.
+//!
+//! Multi-megabyte logs and generated files do not fit in the context window,
+//! so the agent reads them a window at a time: a line range or a byte range,
+//! capped at `max_bytes`, with the file's total size and where the next read
+//! should continue. Line reads scan the whole file, so they also report the
+//! total line count.
+
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::text_encoding::bytes_to_string_smart;
+
+pub(crate) const DEFAULT_MAX_BYTES: usize = 64 * 1024;
+pub(crate) const MAX_BYTES_LIMIT: usize = 512 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadRange {
+    /// Up to `length` bytes starting at byte `offset`.
+    Bytes { offset: u64, length: usize },
+    /// Lines `start..=end`, 1-based. Without `end`, reads until `max_bytes`.
+    Lines { start: usize, end: Option<usize> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct FileSlice {
+    pub(crate) total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) total_lines: Option<usize>,
+    /// Byte offset and length of `content` within the file.
+    pub(crate) offset: u64,
+    pub(crate) length: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) start_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) end_line: Option<usize>,
+    /// Set when the request was cut short by `max_bytes`.
+    pub(crate) truncated: bool,
+    /// Where to continue reading; absent once the range or file is done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) next_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) next_offset: Option<u64>,
+    pub(crate) content: String,
+}
+
+pub(crate) fn read(path: &Path, range: ReadRange, max_bytes: usize) -> std::io::Result<FileSlice> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    if metadata.is_dir() {
+        return Err(std::io::Error::other(format!("{} is a directory", path.display())));
+    }
+    let total_bytes = metadata.len();
+    let max_bytes = max_bytes.clamp(1, MAX_BYTES_LIMIT);
+    match range {
+        ReadRange::Bytes { offset, length } => {
+            read_bytes(&mut file, total_bytes, offset, length.min(max_bytes))
+        }
+        ReadRange::Lines { start, end } => {
+            read_lines(file, total_bytes, start.max(1), end, max_bytes)
+        }
+    }
+}
+
+fn read_bytes(
+    file: &mut File,
+    total_bytes: u64,
+    offset: u64,
+    length: usize,
+) -> std::io::Result<FileSlice> {
+    let offset = offset.min(total_bytes);
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::with_capacity(length);
+    file.take(length as u64).read_to_end(&mut bytes)?;
+    // Move both ends to character boundaries so the window decodes cleanly.
+    let skip = if offset > 0 { continuation_prefix_len(&bytes) } else { 0 };
+    let end_of_file = offset + bytes.len() as u64 >= total_bytes;
+    let cut = if end_of_file { 0 } else { incomplete_suffix_len(&bytes) };
+    bytes.truncate(bytes.len() - cut);
+    bytes.drain(..skip.min(bytes.len()));
+    let start = offset + skip as u64;
+    let next = start + bytes.len() as u64;
+    Ok(FileSlice {
+        total_bytes,
+        total_lines: None,
+        offset: start,
+        length: bytes.len(),
+        start_line: None,
+        end_line: None,
+        truncated: false,
+        next_line: None,
+        next_offset: (next < total_bytes).then_some(next),
+        content: decode(&bytes)?,
+    })
+}
+
+fn read_lines(
+    file: File,
+    total_bytes: u64,
+    start: usize,
+    end: Option<usize>,
+    max_bytes: usize,
+) -> std::io::Result<FileSlice> {
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    let mut content = Vec::new();
+    let mut position = 0u64;
+    let mut offset = None;
+    let mut line_number = 0usize;
+    let mut last_line = None;
+    let mut truncated = false;
+    let mut next_offset = None;
+
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let line_start = position;
+        position += read as u64;
+        if line_number < start || end.is_some_and(|end| line_number > end) || truncated {
+            continue;
+        }
+        if content.len() + line.len() > max_bytes {
+            truncated = true;
+            if content.is_empty() {
+                // A single line longer than the budget: return its start and
+                // continue by byte offset.
+                let mut head = line[..max_bytes].to_vec();
+                head.truncate(head.len() - incomplete_suffix_len(&head));
+                next_offset = Some(line_start + head.len() as u64);
+                content = head;
+                offset = Some(line_start);
+                last_line = Some(line_number);
+            }
+            continue;
+        }
+        offset.get_or_insert(line_start);
+        content.extend_from_slice(&line);
+        last_line = Some(line_number);
+    }
+
+    let wanted_last = end.unwrap_or(line_number).min(line_number);
+    let next_line = last_line
+        .filter(|&last| last < wanted_last && next_offset.is_none())
+        .map(|last| last + 1);
+    Ok(FileSlice {
+        total_bytes,
+        total_lines: Some(line_number),
+        offset: offset.unwrap_or(position),
+        length: content.len(),
+        start_line: last_line.map(|_| start),
+        end_line: last_line,
+        truncated,
+        next_line,
+        next_offset,
+        content: decode(&content)?,
+    })
+}
+
+fn decode(bytes: &[u8]) -> std::io::Result<String> {
+    if bytes.contains(&0) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the file looks binary (it contains NUL bytes); inspect it with a hexdump instead",
+        ));
+    }
+    Ok(bytes_to_string_smart(bytes))
+}
+
+/// UTF-8 continuation bytes at the start of a window that began mid-character.
+fn continuation_prefix_len(bytes: &[u8]) -> usize {
+    bytes.iter().take(3).take_while(|&&byte| byte & 0xC0 == 0x80).count()
+}
+
+/// Length of a multi-byte UTF-8 sequence cut off at the end of `bytes`.
+fn incomplete_suffix_len(bytes: &[u8]) -> usize {
+    let tail_start = bytes.len().saturating_sub(3);
+    for start in (tail_start..bytes.len()).rev() {
+        let needed = match bytes[start] {
+            0x80..=0xBF => continue,
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return 0,
+        };
+        let have = bytes.len() - start;
+        return if have < needed { have } else { 0 };
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[test]
+    fn reads_line_and_byte_windows_with_continuations() {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file");
+        write!(file, "one\ntwo\nthree\nfour\nfünf\n").expect("write");
+        let path = file.path();
+
+        let slice = read(path, ReadRange::Lines { start: 2, end: Some(3) }, 1024).expect("read");
+        assert_eq!(slice.content, "two\nthree\n");
+        assert_eq!(slice.total_lines, Some(5));
+        assert_eq!(slice.total_bytes, 25);
+        assert_eq!((slice.offset, slice.end_line, slice.next_line), (4, Some(3), None));
+
+        let slice = read(path, ReadRange::Lines { start: 1, end: None }, 10).expect("read");
+        assert_eq!(slice.content, "one\ntwo\n");
+        assert!(slice.truncated);
+        assert_eq!(slice.next_line, Some(3));
+
+        // Byte 21 is the second byte of "ü"; the window starts after it.
+        let slice = read(path, ReadRange::Bytes { offset: 21, length: 2 }, 1024).expect("read");
+        assert_eq!((slice.offset, slice.content.as_str()), (22, "n"));
+        assert_eq!(slice.next_offset, Some(23));
+    }
+}
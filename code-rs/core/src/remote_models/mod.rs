@@ -57,13 +57,14 @@ pub struct RemoteModelsManager {
 
 impl RemoteModelsManager {
     pub fn new(auth_manager: Arc<AuthManager>, provider: ModelProviderInfo, code_home: PathBuf) -> Self {
+        let client = provider.create_http_client(crate::default_client::DEFAULT_ORIGINATOR);
         Self {
             state: RwLock::new(RemoteModelsState::default()),
             auth_manager,
             provider,
             code_home,
             cache_ttl: DEFAULT_MODEL_CACHE_TTL,
-            client: crate::default_client::create_client(crate::default_client::DEFAULT_ORIGINATOR),
+            client,
         }
     }
 
@@ -415,6 +416,7 @@ fn apply_model_info_overrides_with_personality(
     family.web_search_tool_type = map_web_search_tool_type(info.web_search_tool_type);
     family.supports_image_detail_original = info.supports_image_detail_original;
     family.supports_image_generation = supports_image_generation(info);
+    family.supports_image_input = supports_image_input(info);
     family.additional_speed_tiers = info.additional_speed_tiers.clone();
     family.supports_search_tool = info.supports_search_tool;
 
@@ -529,6 +531,7 @@ mod tests {
         let family = apply_model_info_overrides_with_personality(&info, family, None);
 
         assert!(family.supports_image_generation);
+        assert!(family.supports_image_input);
 
         let mut text_only_info = info;
         text_only_info.input_modalities = vec![InputModality::Text];
@@ -539,6 +542,7 @@ mod tests {
         );
 
         assert!(!text_only_family.supports_image_generation);
+        assert!(!text_only_family.supports_image_input);
     }
 }
 
@@ -553,6 +557,10 @@ fn supports_image_generation(info: &ModelInfo) -> bool {
     info.input_modalities.contains(&InputModality::Image)
 }
 
+fn supports_image_input(info: &ModelInfo) -> bool {
+    info.input_modalities.contains(&InputModality::Image)
+}
+
 fn map_personality(personality: ConfigPersonality) -> ProtocolPersonality {
     match personality {
         ConfigPersonality::None => ProtocolPersonality::None,
@@ -0,0 +1,205 @@
+//! Long-running commands in a tmux or zellij pane.
+//!
+//! Commands matching `[terminal_panes] commands` (dev servers, log tails)
+//! start in a new pane next to the TUI instead of inside the exec tool, so
+//! their output stays visible without filling the conversation. The pane's
+//! output is mirrored to a log file the agent can read, and the `kill` tool
+//! stops the job by the call_id it was started with.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use tokio::process::Command;
+
+/// Multiplexer hosting the current session, from the environment variables
+/// each one sets for its panes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Multiplexer {
+    Tmux,
+    Zellij,
+}
+
+impl Multiplexer {
+    pub(crate) fn detect() -> Option<Self> {
+        if std::env::var_os("TMUX").is_some() {
+            Some(Self::Tmux)
+        } else if std::env::var_os("ZELLIJ").is_some() {
+            Some(Self::Zellij)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Tmux => "tmux",
+            Self::Zellij => "zellij",
+        }
+    }
+}
+
+/// A command started in a pane.
+#[derive(Debug, Clone)]
+pub(crate) struct PaneJob {
+    pub(crate) multiplexer: Multiplexer,
+    pub(crate) label: String,
+    pub(crate) log_file: PathBuf,
+    pid_file: PathBuf,
+}
+
+/// Whether `script` starts with one of the configured command prefixes.
+/// Prefixes match whole words, so `npm run dev` does not match
+/// `npm run devtools`.
+pub(crate) fn wants_pane(prefixes: &[String], script: &str) -> bool {
+    let script = script.trim_start();
+    prefixes.iter().any(|prefix| {
+        let prefix = prefix.trim();
+        !prefix.is_empty()
+            && script.strip_prefix(prefix).is_some_and(|rest| {
+                rest.is_empty() || rest.starts_with(char::is_whitespace)
+            })
+    })
+}
+
+/// Starts `command` in a new pane without moving focus away from the TUI.
+pub(crate) async fn launch(
+    multiplexer: Multiplexer,
+    code_home: &Path,
+    call_id: &str,
+    label: &str,
+    command: &[String],
+    cwd: &Path,
+) -> std::io::Result<PaneJob> {
+    let dir = pane_dir(code_home)?;
+    let log_file = dir.join(format!("{call_id}.log"));
+    let pid_file = dir.join(format!("{call_id}.pid"));
+    let script = wrapper_script(command, &pid_file, &log_file)?;
+
+    let mut launcher = match multiplexer {
+        Multiplexer::Tmux => {
+            let mut cmd = Command::new("tmux");
+            cmd.args(["split-window", "-d", "-h", "-c"]).arg(cwd).arg(&script);
+            cmd
+        }
+        Multiplexer::Zellij => {
+            let mut cmd = Command::new("zellij");
+            cmd.args(["run", "--cwd"])
+                .arg(cwd)
+                .args(["--name", label, "--", "sh", "-c"])
+                .arg(&script);
+            cmd
+        }
+    };
+    let output = launcher.output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "{} exited with {}: {}",
+            multiplexer.name(),
+            output.status,
+            stderr.trim()
+        )));
+    }
+    Ok(PaneJob {
+        multiplexer,
+        label: label.to_string(),
+        log_file,
+        pid_file,
+    })
+}
+
+/// `CODE_HOME/panes`, private to the current user so nobody else can plant
+/// the pid or log file a pane writes through.
+fn pane_dir(code_home: &Path) -> std::io::Result<PathBuf> {
+    let dir = code_home.join("panes");
+    std::fs::create_dir_all(&dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(dir)
+}
+
+/// Stops the job's process group; the pane closes once its command exits.
+pub(crate) async fn stop(job: &PaneJob) -> std::io::Result<()> {
+    let pid = std::fs::read_to_string(&job.pid_file)?;
+    let pid = pid.trim();
+    if pid.is_empty() || !pid.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(std::io::Error::other(format!(
+            "no process id recorded in {}",
+            job.pid_file.display()
+        )));
+    }
+    let status = Command::new("kill")
+        .args(["-TERM", "--", &format!("-{pid}")])
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!("kill exited with {status}")));
+    }
+    Ok(())
+}
+
+/// Tool output returned to the model once the pane is up.
+pub(crate) fn started_message(job: &PaneJob, call_id: &str) -> String {
+    format!(
+        "Started `{}` in a {} pane (call_id={call_id}). It keeps running there; output is \
+         mirrored to {}. Read that file to check on it, and use kill(call_id=\"{call_id}\") \
+         to stop it.",
+        job.label,
+        job.multiplexer.name(),
+        job.log_file.display()
+    )
+}
+
+/// The pane's shell records its pid (which leads the pane's process group)
+/// and tees the command's output into the log.
+fn wrapper_script(command: &[String], pid_file: &Path, log_file: &Path) -> std::io::Result<String> {
+    let quote = |text: &str| {
+        shlex::try_quote(text)
+            .map(|quoted| quoted.into_owned())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+    };
+    let command = shlex::try_join(command.iter().map(String::as_str))
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+    let pid_file = quote(&pid_file.to_string_lossy())?;
+    let log_file = quote(&log_file.to_string_lossy())?;
+    Ok(format!("echo $$ > {pid_file}; {command} 2>&1 | tee {log_file}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prefixes_match_whole_words_and_wrap_the_command() {
+        let prefixes = vec!["npm run dev".to_string(), "tail -f".to_string()];
+        assert!(wants_pane(&prefixes, "npm run dev"));
+        assert!(wants_pane(&prefixes, "  tail -f logs/app.log"));
+        assert!(!wants_pane(&prefixes, "npm run devtools"));
+        assert!(!wants_pane(&prefixes, "cargo test"));
+
+        let command = vec!["bash".to_string(), "-lc".to_string(), "npm run dev".to_string()];
+        let script =
+            wrapper_script(&command, Path::new("/tmp/c.pid"), Path::new("/tmp/my logs/c.log"))
+                .expect("script");
+        assert_eq!(
+            script,
+            "echo $$ > /tmp/c.pid; bash -lc 'npm run dev' 2>&1 | tee '/tmp/my logs/c.log'"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pane_files_live_in_a_private_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let home = tempfile::TempDir::new().expect("tempdir");
+        let dir = pane_dir(home.path()).expect("pane dir");
+        assert_eq!(dir, home.path().join("panes"));
+        let mode = std::fs::metadata(&dir).expect("metadata").permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
@@ -0,0 +1,629 @@
+//! Test results parsed from test runner output.
+//!
+//! `cargo test`, `cargo nextest`, pytest and jest each end with a summary
+//! line that identifies them. When a command runs one of them and its output
+//! contains that runner's summary, it is parsed into pass, fail and skip
+//! counts plus the name, location and message of each failure. The model
+//! gets that compact report in place of the passing-test progress lines, and
+//! the TUI shows it as a test-results cell.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex_lite::Regex;
+
+/// Failures kept in a report; the counts still cover all of them.
+const MAX_FAILURES: usize = 20;
+const MAX_MESSAGE_LINES: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestRunner {
+    CargoTest,
+    Nextest,
+    Pytest,
+    Jest,
+}
+
+impl TestRunner {
+    pub fn label(self) -> &'static str {
+        match self {
+            TestRunner::CargoTest => "cargo test",
+            TestRunner::Nextest => "cargo nextest",
+            TestRunner::Pytest => "pytest",
+            TestRunner::Jest => "jest",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+    pub name: String,
+    /// `file:line[:column]` of the failing assertion, when the runner says.
+    pub location: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestReport {
+    pub runner: TestRunner,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// The first `MAX_FAILURES` of the `failed` tests.
+    pub failures: Vec<TestFailure>,
+}
+
+impl TestReport {
+    /// `2 failed, 40 passed, 1 skipped`, leaving out zero counts other than
+    /// passed.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.failed > 0 {
+            parts.push(format!("{} failed", self.failed));
+        }
+        parts.push(format!("{} passed", self.passed));
+        if self.skipped > 0 {
+            parts.push(format!("{} skipped", self.skipped));
+        }
+        parts.join(", ")
+    }
+
+    /// The report as the model sees it: the summary, then each failure with
+    /// its location and indented message.
+    pub fn compact(&self) -> String {
+        let mut out = format!("{}: {}", self.runner.label(), self.summary());
+        for failure in &self.failures {
+            out.push_str("\nFAILED ");
+            out.push_str(&failure.name);
+            if let Some(location) = &failure.location {
+                out.push_str(&format!(" ({location})"));
+            }
+            for line in failure.message.lines() {
+                out.push_str("\n    ");
+                out.push_str(line);
+            }
+        }
+        let unlisted = self.failed.saturating_sub(self.failures.len());
+        if unlisted > 0 && !self.failures.is_empty() {
+            out.push_str(&format!("\n… and {unlisted} more failure(s)"));
+        }
+        out
+    }
+}
+
+/// Parses the output of `command` when the command runs a supported test
+/// runner and the output ends with that runner's summary. Output from other
+/// commands is left alone even if it happens to look like a test run.
+pub fn parse_command_output(command: &str, output: &str) -> Option<TestReport> {
+    let runners = command_runners(command);
+    if runners.is_empty() {
+        return None;
+    }
+    parse(output).filter(|report| runners.contains(&report.runner))
+}
+
+/// Output lines the report does not stand for: everything except the
+/// progress lines of tests that passed or were skipped. Build output,
+/// warnings and the output of other commands in the same script stay.
+pub fn unreported_output(output: &str) -> String {
+    let output = ANSI_ESCAPE.replace_all(output, "");
+    let kept: Vec<&str> = output
+        .lines()
+        .filter(|line| !PASSING_TEST.iter().any(|pattern| pattern.is_match(line)))
+        .collect();
+    kept.join("\n").trim_matches('\n').to_string()
+}
+
+/// Runners invoked anywhere in a command line or shell script, such as
+/// `cargo test`, `cargo +nightly nextest run`, `python -m pytest` or
+/// `npx jest`.
+fn command_runners(command: &str) -> Vec<TestRunner> {
+    let words: Vec<&str> = command
+        .split(|c: char| {
+            c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')' | '\'' | '"')
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+    let mut runners = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        let program = word.rsplit('/').next().unwrap_or(word);
+        let runner = match program {
+            "cargo" => match words[index + 1..].iter().find(|next| !next.starts_with('+')) {
+                Some(&"test") => Some(TestRunner::CargoTest),
+                Some(&"nextest") => Some(TestRunner::Nextest),
+                _ => None,
+            },
+            "cargo-nextest" => Some(TestRunner::Nextest),
+            "pytest" | "py.test" => Some(TestRunner::Pytest),
+            "jest" => Some(TestRunner::Jest),
+            _ => None,
+        };
+        if let Some(runner) = runner
+            && !runners.contains(&runner)
+        {
+            runners.push(runner);
+        }
+    }
+    runners
+}
+
+/// Parses the output of a test run. `None` when no supported runner's
+/// summary is found.
+pub fn parse(output: &str) -> Option<TestReport> {
+    let output = ANSI_ESCAPE.replace_all(output, "");
+    let lines: Vec<&str> = output.lines().collect();
+    let mut report = parse_nextest(&lines)
+        .or_else(|| parse_cargo_test(&lines))
+        .or_else(|| parse_pytest(&lines))
+        .or_else(|| parse_jest(&lines))?;
+    report.failures.truncate(MAX_FAILURES);
+    Some(report)
+}
+
+fn regex(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|err| panic!("invalid test output pattern: {err}"))
+}
+
+static ANSI_ESCAPE: LazyLock<Regex> = LazyLock::new(|| regex(r"\x1b\[[0-9;?]*[ -/]*[@-~]"));
+/// Progress lines of passing or skipped tests, per runner.
+static PASSING_TEST: LazyLock<[Regex; 4]> = LazyLock::new(|| {
+    [
+        regex(r"^test .+ \.\.\. (?:ok|ignored)(?:, .*)?$"),
+        regex(r"^\s*(?:PASS|SKIP)\s+\[[^\]]*\]\s"),
+        regex(r"^\S+(?:\.py [.sx]+|::\S+ (?:PASSED|SKIPPED|XFAIL|XPASS))\s*(?:\[\s*\d+%\])?$"),
+        regex(r"^\s*(?:✓|√|○) "),
+    ]
+});
+static PANIC_LOCATION: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"panicked at (?:'.*', )?([^\s:']+:\d+:\d+)"));
+static CARGO_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    regex(r"^test result: \w+\. (\d+) passed; (\d+) failed; (\d+) ignored")
+});
+static CARGO_FAILED_TEST: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^test (.+?) \.\.\. FAILED$"));
+static CARGO_SECTION: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^---- (.+?) std(?:out|err) ----$"));
+static NEXTEST_SUMMARY: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s*Summary \[[^\]]*\]\s+\d+ tests? run: (.*)$"));
+static NEXTEST_FAILED_TEST: LazyLock<Regex> = LazyLock::new(|| {
+    regex(r"^\s*(?:FAIL|SIGSEGV|SIGABRT|SIGILL|ABORT|TIMEOUT)\s+\[[^\]]*\]\s+(.+?)\s*$")
+});
+static NEXTEST_SECTION: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^-+ STD(?:OUT|ERR):\s+(.+?)\s+-+$"));
+static NEXTEST_STATUS: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s*[A-Z]{3,8}\s+\[\s*[\d.]+s\]"));
+static PYTEST_SUMMARY: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^=+ (.+?) in [\d.]+s\b.*=+$"));
+static PYTEST_SHORT: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^(?:FAILED|ERROR) (\S+)(?: - (.*))?$"));
+static PYTEST_HEADING: LazyLock<Regex> = LazyLock::new(|| regex(r"^_{3,} (.+?) _{3,}$"));
+static PYTEST_LOCATION: LazyLock<Regex> = LazyLock::new(|| regex(r"^(\S+\.py:\d+): \w"));
+static JEST_SUMMARY: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^Tests:\s+(.*\d+ total)\s*$"));
+static JEST_FAILURE: LazyLock<Regex> = LazyLock::new(|| regex(r"^\s*● (.+?)\s*$"));
+static JEST_FRAME: LazyLock<Regex> = LazyLock::new(|| regex(r"^\s*(?:>\s*)?\d+ \|"));
+static JEST_AT: LazyLock<Regex> =
+    LazyLock::new(|| regex(r"^\s*at .*?\(?([^\s()]+:\d+:\d+)\)?$"));
+
+/// `(count, word)` pairs from `1 failed, 2 passed (1 slow)`-style lists.
+fn counts(text: &str) -> Vec<(usize, String)> {
+    text.split([',', '|'])
+        .filter_map(|part| {
+            let mut words = part.split_whitespace();
+            let count = words.next()?.parse().ok()?;
+            let word: String = words.next()?.chars().filter(char::is_ascii_alphabetic).collect();
+            Some((count, word))
+        })
+        .collect()
+}
+
+fn count_of(counts: &[(usize, String)], words: &[&str]) -> usize {
+    counts
+        .iter()
+        .filter(|(_, word)| words.contains(&word.as_str()))
+        .map(|(count, _)| count)
+        .sum()
+}
+
+/// Trims blank edges, removes the common indent and caps the length.
+fn message(lines: &[&str]) -> String {
+    let start = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(start, |i| i + 1);
+    let lines = &lines[start..end];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut kept: Vec<&str> = lines
+        .iter()
+        .take(MAX_MESSAGE_LINES)
+        .map(|line| line.get(indent..).unwrap_or_else(|| line.trim_start()).trim_end())
+        .collect();
+    if lines.len() > MAX_MESSAGE_LINES {
+        kept.push("…");
+    }
+    kept.join("\n")
+}
+
+fn panic_location(lines: &[&str]) -> Option<String> {
+    lines
+        .iter()
+        .find_map(|line| PANIC_LOCATION.captures(line))
+        .map(|captures| captures[1].to_string())
+}
+
+/// Lines of a libtest or nextest failure section worth showing.
+fn panic_message(lines: &[&str]) -> String {
+    let lines: Vec<&str> = lines
+        .iter()
+        .copied()
+        .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+        // New-style panics put the message on the following lines.
+        .filter(|line| !(PANIC_LOCATION.is_match(line) && line.ends_with(':')))
+        .collect();
+    message(&lines)
+}
+
+fn parse_cargo_test(lines: &[&str]) -> Option<TestReport> {
+    let mut totals = None::<(usize, usize, usize)>;
+    for line in lines {
+        if let Some(captures) = CARGO_RESULT.captures(line) {
+            let (passed, failed, ignored) = totals.get_or_insert((0, 0, 0));
+            *passed += captures[1].parse::<usize>().unwrap_or(0);
+            *failed += captures[2].parse::<usize>().unwrap_or(0);
+            *ignored += captures[3].parse::<usize>().unwrap_or(0);
+        }
+    }
+    let (passed, failed, skipped) = totals?;
+
+    let mut names: Vec<String> = Vec::new();
+    let mut sections: HashMap<String, Vec<&str>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in lines {
+        if let Some(captures) = CARGO_FAILED_TEST.captures(line) {
+            if !names.contains(&captures[1].to_string()) {
+                names.push(captures[1].to_string());
+            }
+            current = None;
+        } else if let Some(captures) = CARGO_SECTION.captures(line) {
+            let name = captures[1].to_string();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+        } else if *line == "failures:" || line.starts_with("test result:") {
+            current = None;
+        } else if let Some(name) = &current
+            && let Some(section) = sections.get_mut(name)
+        {
+            section.push(line);
+        }
+    }
+    if names.is_empty() {
+        names = sections.keys().cloned().collect();
+        names.sort();
+    }
+    let failures = names
+        .into_iter()
+        .map(|name| {
+            let section = sections.get(&name).map(Vec::as_slice).unwrap_or_default();
+            TestFailure {
+                location: panic_location(section),
+                message: panic_message(section),
+                name,
+            }
+        })
+        .collect();
+    Some(TestReport {
+        runner: TestRunner::CargoTest,
+        passed,
+        failed,
+        skipped,
+        failures,
+    })
+}
+
+fn parse_nextest(lines: &[&str]) -> Option<TestReport> {
+    let summary = lines.iter().rev().find_map(|line| NEXTEST_SUMMARY.captures(line))?;
+    let counts = counts(&summary[1]);
+
+    let mut names: Vec<String> = Vec::new();
+    let mut sections: HashMap<String, Vec<&str>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in lines {
+        if let Some(captures) = NEXTEST_SECTION.captures(line) {
+            let name = captures[1].to_string();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+            continue;
+        }
+        if NEXTEST_STATUS.is_match(line) || NEXTEST_SUMMARY.is_match(line) {
+            current = None;
+            if let Some(captures) = NEXTEST_FAILED_TEST.captures(line)
+                && !names.contains(&captures[1].to_string())
+            {
+                names.push(captures[1].to_string());
+            }
+            continue;
+        }
+        if let Some(name) = &current
+            && let Some(section) = sections.get_mut(name)
+        {
+            section.push(line);
+        }
+    }
+    let failures = names
+        .into_iter()
+        .map(|name| {
+            let section = sections.get(&name).map(Vec::as_slice).unwrap_or_default();
+            TestFailure {
+                location: panic_location(section),
+                message: panic_message(section),
+                name,
+            }
+        })
+        .collect();
+    Some(TestReport {
+        runner: TestRunner::Nextest,
+        passed: count_of(&counts, &["passed"]),
+        failed: count_of(&counts, &["failed", "timed"]),
+        skipped: count_of(&counts, &["skipped"]),
+        failures,
+    })
+}
+
+fn parse_pytest(lines: &[&str]) -> Option<TestReport> {
+    let summary = lines.iter().rev().find_map(|line| PYTEST_SUMMARY.captures(line))?;
+    let counts = counts(&summary[1]);
+    if count_of(&counts, &["passed", "failed", "error", "errors", "skipped"]) == 0 {
+        return None;
+    }
+
+    // Tracebacks under each `____ test_name ____` heading end with the
+    // failing line's `path.py:line: Error`.
+    let mut locations: Vec<(String, String)> = Vec::new();
+    let mut heading: Option<String> = None;
+    for line in lines {
+        if let Some(captures) = PYTEST_HEADING.captures(line) {
+            heading = Some(captures[1].to_string());
+        } else if line.starts_with("====") {
+            heading = None;
+        } else if let Some(name) = &heading
+            && let Some(captures) = PYTEST_LOCATION.captures(line)
+        {
+            locations.retain(|(existing, _)| existing != name);
+            locations.push((name.clone(), captures[1].to_string()));
+        }
+    }
+    let location_for = |id: &str| {
+        locations
+            .iter()
+            .find(|(name, _)| id.ends_with(&name.replace('.', "::")))
+            .map(|(_, location)| location.clone())
+    };
+
+    let mut failures: Vec<TestFailure> = lines
+        .iter()
+        .filter_map(|line| PYTEST_SHORT.captures(line))
+        .map(|captures| TestFailure {
+            name: captures[1].to_string(),
+            location: location_for(&captures[1]),
+            message: captures.get(2).map(|m| m.as_str().to_string()).unwrap_or_default(),
+        })
+        .collect();
+    if failures.is_empty() {
+        failures = locations
+            .iter()
+            .map(|(name, location)| TestFailure {
+                name: name.clone(),
+                location: Some(location.clone()),
+                message: String::new(),
+            })
+            .collect();
+    }
+    Some(TestReport {
+        runner: TestRunner::Pytest,
+        passed: count_of(&counts, &["passed", "xpassed"]),
+        failed: count_of(&counts, &["failed", "error", "errors"]),
+        skipped: count_of(&counts, &["skipped", "xfailed"]),
+        failures,
+    })
+}
+
+fn parse_jest(lines: &[&str]) -> Option<TestReport> {
+    let summary = lines.iter().rev().find_map(|line| JEST_SUMMARY.captures(line))?;
+    let counts = counts(&summary[1]);
+
+    let mut failures: Vec<TestFailure> = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let Some(captures) = JEST_FAILURE.captures(lines[index]) else {
+            index += 1;
+            continue;
+        };
+        let name = captures[1].to_string();
+        // `● Console` introduces captured console output, not a failure.
+        let is_failure = name != "Console";
+        let block_end = lines[index + 1..]
+            .iter()
+            .position(|line| {
+                JEST_FAILURE.is_match(line)
+                    || line.starts_with(" FAIL ")
+                    || line.starts_with(" PASS ")
+                    || line.starts_with("Test Suites:")
+            })
+            .map_or(lines.len(), |offset| index + 1 + offset);
+        let block = &lines[index + 1..block_end];
+        let message_end = block
+            .iter()
+            .position(|line| JEST_FRAME.is_match(line) || JEST_AT.is_match(line))
+            .unwrap_or(block.len());
+        let location = block
+            .iter()
+            .filter(|line| !line.contains("node_modules"))
+            .find_map(|line| JEST_AT.captures(line))
+            .map(|captures| captures[1].to_string());
+        if is_failure && !failures.iter().any(|failure| failure.name == name) {
+            failures.push(TestFailure {
+                name,
+                location,
+                message: message(&block[..message_end]),
+            });
+        }
+        index = block_end;
+    }
+    Some(TestReport {
+        runner: TestRunner::Jest,
+        passed: count_of(&counts, &["passed"]),
+        failed: count_of(&counts, &["failed"]),
+        skipped: count_of(&counts, &["skipped", "todo"]),
+        failures,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_each_runner_summary_and_failures() {
+        let cargo = "\
+running 3 tests
+test tests::adds ... ok
+test tests::subtracts ... FAILED
+test tests::slow ... ignored
+
+failures:
+
+---- tests::subtracts stdout ----
+
+thread 'tests::subtracts' panicked at src/lib.rs:14:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::subtracts
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        let report = parse(cargo).expect("cargo report");
+        assert_eq!(report.runner, TestRunner::CargoTest);
+        assert_eq!((report.passed, report.failed, report.skipped), (1, 1, 1));
+        assert_eq!(
+            report.failures,
+            vec![TestFailure {
+                name: "tests::subtracts".to_string(),
+                location: Some("src/lib.rs:14:9".to_string()),
+                message: "assertion `left == right` failed\n  left: 1\n right: 2".to_string(),
+            }]
+        );
+        assert_eq!(
+            report.compact(),
+            "cargo test: 1 failed, 1 passed, 1 skipped\n\
+             FAILED tests::subtracts (src/lib.rs:14:9)\n    \
+             assertion `left == right` failed\n      left: 1\n     right: 2"
+        );
+
+        let nextest = "\
+        PASS [   0.004s] demo tests::adds
+        FAIL [   0.005s] demo tests::subtracts
+--- STDERR:              demo tests::subtracts ---
+thread 'tests::subtracts' panicked at src/lib.rs:14:9:
+boom
+
+     Summary [   0.010s] 2 tests run: 1 passed, 1 failed, 0 skipped
+        FAIL [   0.005s] demo tests::subtracts
+";
+        let report = parse(nextest).expect("nextest report");
+        assert_eq!((report.runner, report.passed, report.failed), (TestRunner::Nextest, 1, 1));
+        assert_eq!(report.failures[0].name, "demo tests::subtracts");
+        assert_eq!(report.failures[0].message, "boom");
+
+        let pytest = "\
+=================================== FAILURES ===================================
+___________________________________ test_two ___________________________________
+
+    def test_two():
+>       assert 1 == 2
+E       assert 1 == 2
+
+tests/test_math.py:5: AssertionError
+=========================== short test summary info ============================
+FAILED tests/test_math.py::test_two - assert 1 == 2
+==================== 1 failed, 2 passed, 1 skipped in 0.03s ====================
+";
+        let report = parse(pytest).expect("pytest report");
+        assert_eq!((report.passed, report.failed, report.skipped), (2, 1, 1));
+        assert_eq!(report.failures[0].name, "tests/test_math.py::test_two");
+        assert_eq!(report.failures[0].location.as_deref(), Some("tests/test_math.py:5"));
+        assert_eq!(report.failures[0].message, "assert 1 == 2");
+
+        let jest = "\
+\u{1b}[1m\u{1b}[31m FAIL \u{1b}[39m\u{1b}[22m src/sum.test.js
+  ● sum › adds numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 4
+    Received: 3
+
+      3 | test('adds numbers', () => {
+    > 4 |   expect(sum(1, 2)).toBe(4);
+        |                     ^
+
+      at Object.<anonymous> (src/sum.test.js:4:21)
+
+Test Suites: 1 failed, 1 total
+Tests:       1 failed, 1 skipped, 3 passed, 5 total
+";
+        let report = parse(jest).expect("jest report");
+        assert_eq!((report.passed, report.failed, report.skipped), (3, 1, 1));
+        assert_eq!(
+            report.failures,
+            vec![TestFailure {
+                name: "sum › adds numbers".to_string(),
+                location: Some("src/sum.test.js:4:21".to_string()),
+                message: "expect(received).toBe(expected) // Object.is equality\n\n\
+                          Expected: 4\nReceived: 3"
+                    .to_string(),
+            }]
+        );
+
+        assert_eq!(parse("Compiling demo v0.1.0\nerror[E0308]: mismatched types"), None);
+    }
+
+    #[test]
+    fn only_test_commands_are_parsed_and_other_output_is_kept() {
+        let output = "\
+   Compiling demo v0.1.0
+warning: unused import: `std::fmt`
+running 2 tests
+test tests::adds ... ok
+test tests::slow ... ignored
+
+test result: ok. 1 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        assert!(parse_command_output("cat notes.txt", output).is_none());
+        assert!(parse_command_output("pytest -q", output).is_none());
+        for command in [
+            "cargo test",
+            "bash -lc 'cargo build && cargo +nightly test -p demo'",
+        ] {
+            let report = parse_command_output(command, output).expect("cargo report");
+            assert_eq!((report.runner, report.passed), (TestRunner::CargoTest, 1));
+        }
+        assert_eq!(command_runners("python -m pytest tests/"), vec![TestRunner::Pytest]);
+        assert_eq!(command_runners("npx jest --ci"), vec![TestRunner::Jest]);
+        assert_eq!(command_runners("cargo nextest run"), vec![TestRunner::Nextest]);
+        assert_eq!(
+            unreported_output(output),
+            "   Compiling demo v0.1.0\nwarning: unused import: `std::fmt`\nrunning 2 tests\n\n\
+             test result: ok. 1 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out; \
+             finished in 0.00s"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Lightweight JSON Schema validation for model-generated tool arguments.
+//!
+//! Tool calls are checked against the schema we advertised before they are
+//! dispatched, so the model gets a precise correction ("`/path` must be a
+//! string") instead of an opaque server error. Only the keywords that matter
+//! for argument shape are enforced: `type`, `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`/`const`, and the `anyOf`/`oneOf`/
+//! `allOf` combinators. Anything else is accepted as-is.
+
+use serde_json::Value as JsonValue;
+
+/// Collect every mismatch between `value` and `schema`.
+///
+/// When `allow_additional_properties` is true, `additionalProperties: false`
+/// is not enforced; built-in tools use this to keep accepting legacy fields.
+pub(crate) fn validate_tool_arguments(
+    schema: &JsonValue,
+    value: &JsonValue,
+    allow_additional_properties: bool,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "", allow_additional_properties, &mut errors);
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// Render validation errors as the tool output returned to the model.
+pub(crate) fn format_validation_errors(tool_name: &str, errors: &[String]) -> String {
+    let mut message = format!("invalid arguments for `{tool_name}`; fix the following and retry:");
+    for error in errors {
+        message.push_str("\n- ");
+        message.push_str(error);
+    }
+    message
+}
+
+fn validate_at(
+    schema: &JsonValue,
+    value: &JsonValue,
+    path: &str,
+    allow_additional_properties: bool,
+    errors: &mut Vec<String>,
+) {
+    let JsonValue::Object(schema) = schema else {
+        if schema == &JsonValue::Bool(false) {
+            errors.push(format!("{} is not allowed", display_path(path)));
+        }
+        return;
+    };
+
+    if let Some(JsonValue::Array(variants)) = schema.get("allOf") {
+        for variant in variants {
+            validate_at(variant, value, path, allow_additional_properties, errors);
+        }
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(JsonValue::Array(variants)) = schema.get(key)
+            && !variants.is_empty()
+            && !variants.iter().any(|variant| {
+                validate_tool_arguments(variant, value, allow_additional_properties).is_ok()
+            })
+        {
+            errors.push(format!(
+                "{} does not match any of the allowed shapes",
+                display_path(path)
+            ));
+        }
+    }
+
+    if let Some(expected) = schema.get("type")
+        && !matches_type(expected, value)
+    {
+        errors.push(format!(
+            "{} must be {}, got {}",
+            display_path(path),
+            describe_type(expected),
+            json_type_name(value)
+        ));
+        // Nested keywords are meaningless once the type is wrong.
+        return;
+    }
+
+    if let Some(JsonValue::Array(allowed)) = schema.get("enum")
+        && !allowed.contains(value)
+    {
+        let allowed = allowed
+            .iter()
+            .map(JsonValue::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        errors.push(format!("{} must be one of [{allowed}]", display_path(path)));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        errors.push(format!("{} must equal {expected}", display_path(path)));
+    }
+
+    match value {
+        JsonValue::Object(map) => {
+            let properties = schema.get("properties").and_then(JsonValue::as_object);
+            if let Some(JsonValue::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(JsonValue::as_str) {
+                    if !map.contains_key(key) {
+                        errors.push(format!(
+                            "missing required property {}",
+                            display_path(&child_path(path, key))
+                        ));
+                    }
+                }
+            }
+            for (key, child) in map {
+                let child_path = child_path(path, key);
+                match properties.and_then(|properties| properties.get(key)) {
+                    Some(child_schema) => validate_at(
+                        child_schema,
+                        child,
+                        &child_path,
+                        allow_additional_properties,
+                        errors,
+                    ),
+                    None => match schema.get("additionalProperties") {
+                        Some(JsonValue::Bool(false)) if !allow_additional_properties => {
+                            errors.push(format!(
+                                "unexpected property {}",
+                                display_path(&child_path)
+                            ));
+                        }
+                        Some(additional @ JsonValue::Object(_)) => validate_at(
+                            additional,
+                            child,
+                            &child_path,
+                            allow_additional_properties,
+                            errors,
+                        ),
+                        _ => {}
+                    },
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (idx, item) in items.iter().enumerate() {
+                    validate_at(
+                        item_schema,
+                        item,
+                        &format!("{path}/{idx}"),
+                        allow_additional_properties,
+                        errors,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn matches_type(expected: &JsonValue, value: &JsonValue) -> bool {
+    match expected {
+        JsonValue::String(name) => matches_type_name(name, value),
+        JsonValue::Array(names) => names
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .any(|name| matches_type_name(name, value)),
+        _ => true,
+    }
+}
+
+fn matches_type_name(name: &str, value: &JsonValue) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        // Unknown type names are not ours to reject.
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &JsonValue) -> String {
+    match expected {
+        JsonValue::String(name) => format!("of type {name}"),
+        JsonValue::Array(names) => {
+            let names = names
+                .iter()
+                .filter_map(JsonValue::as_str)
+                .collect::<Vec<_>>()
+                .join(" | ");
+            format!("of type {names}")
+        }
+        other => other.to_string(),
+    }
+}
+
+fn json_type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    format!("{path}/{key}")
+}
+
+fn display_path(path: &str) -> String {
+    if path.is_empty() {
+        "arguments".to_string()
+    } else {
+        format!("`{path}`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> JsonValue {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "limit": { "type": "integer" },
+                "mode": { "type": "string", "enum": ["fast", "slow"] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["path"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn accepts_matching_arguments() {
+        let args = json!({ "path": "src", "limit": 3, "mode": "fast", "tags": ["a"] });
+        assert_eq!(validate_tool_arguments(&schema(), &args, false), Ok(()));
+    }
+
+    #[test]
+    fn reports_every_mismatch_with_paths() {
+        let args = json!({ "limit": "3", "mode": "medium", "tags": ["a", 1], "extra": true });
+        // Property order depends on serde_json's `preserve_order` feature.
+        let mut errors = validate_tool_arguments(&schema(), &args, false).unwrap_err();
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "`/limit` must be of type integer, got string".to_string(),
+                "`/mode` must be one of [\"fast\", \"slow\"]".to_string(),
+                "`/tags/1` must be of type string, got number".to_string(),
+                "missing required property `/path`".to_string(),
+                "unexpected property `/extra`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient_mode_ignores_unknown_properties() {
+        let args = json!({ "path": "src", "legacy_flag": true });
+        assert_eq!(validate_tool_arguments(&schema(), &args, true), Ok(()));
+    }
+
+    #[test]
+    fn any_of_accepts_any_matching_variant() {
+        let schema = json!({ "anyOf": [{ "type": "string" }, { "type": "number" }] });
+        assert_eq!(validate_tool_arguments(&schema, &json!(4), false), Ok(()));
+        assert!(validate_tool_arguments(&schema, &json!(true), false).is_err());
+    }
+}
@@ -255,7 +255,7 @@ impl UnifiedExecSessionManager {
         }
 
         let (output, _maybe_tokens) = truncate_middle(
-            &String::from_utf8_lossy(&collected),
+            &crate::redaction::redact(&String::from_utf8_lossy(&collected)),
             UNIFIED_EXEC_OUTPUT_MAX_BYTES,
         );
         let output = if let Some(warning) = timeout_warning {
@@ -0,0 +1,388 @@
+//! Local daily and weekly usage limits.
+//!
+//! Every completed model response adds its tokens and estimated cost to
+//! `CODE_HOME/usage/quota.json`, keyed by config profile, so the totals are
+//! shared by every session on this machine. Front-ends compare them against
+//! the configured [`UsageLimits`] to warn near a limit and ask before going
+//! over it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use chrono::Timelike;
+use chrono::Utc;
+use code_protocol::num_format::format_si_suffix;
+use fs2::FileExt;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::account_usage::ModelPrices;
+use crate::account_usage::TokenTotals;
+use crate::config_types::UsageLimits;
+use crate::protocol::TokenUsage;
+
+const USAGE_SUBDIR: &str = "usage";
+const QUOTA_FILE: &str = "quota.json";
+/// Quarter hours line up with every local midnight, including the 30 and
+/// 45 minute UTC offsets.
+const BUCKET_MINUTES: u32 = 15;
+const RETENTION_DAYS: i64 = 8;
+const WARNING_FRACTION: f64 = 0.8;
+
+/// Usage outside any profile is tracked under this name.
+pub const DEFAULT_SCOPE: &str = "default";
+
+pub fn scope_for_profile(profile: Option<&str>) -> &str {
+    profile.unwrap_or(DEFAULT_SCOPE)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct QuotaLedger {
+    #[serde(default)]
+    scopes: BTreeMap<String, Vec<UsageBucket>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UsageBucket {
+    start: DateTime<Utc>,
+    #[serde(default)]
+    tokens: u64,
+    #[serde(default)]
+    cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaWindow {
+    Day,
+    Week,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaMetric {
+    Tokens,
+    CostUsd,
+}
+
+/// Usage so far against one configured limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuotaUsage {
+    pub window: QuotaWindow,
+    pub metric: QuotaMetric,
+    pub used: f64,
+    pub limit: f64,
+}
+
+impl QuotaUsage {
+    pub fn fraction(&self) -> f64 {
+        if self.limit <= 0.0 {
+            1.0
+        } else {
+            self.used / self.limit
+        }
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.fraction() >= WARNING_FRACTION
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.fraction() >= 1.0
+    }
+}
+
+impl fmt::Display for QuotaUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let window = match self.window {
+            QuotaWindow::Day => "Daily",
+            QuotaWindow::Week => "Weekly",
+        };
+        let percent = (self.fraction() * 100.0).floor() as u64;
+        match self.metric {
+            QuotaMetric::Tokens => write!(
+                f,
+                "{window} tokens {percent}% ({} of {})",
+                format_si_suffix(self.used as i64),
+                format_si_suffix(self.limit as i64),
+            ),
+            QuotaMetric::CostUsd => write!(
+                f,
+                "{window} cost {percent}% (${:.2} of ${:.2})",
+                self.used, self.limit,
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuotaStatus {
+    pub usages: Vec<QuotaUsage>,
+}
+
+impl QuotaStatus {
+    /// The limit closest to (or furthest past) being reached.
+    pub fn most_used(&self) -> Option<&QuotaUsage> {
+        self.usages
+            .iter()
+            .max_by(|a, b| a.fraction().total_cmp(&b.fraction()))
+    }
+
+    /// The most used limit once it reaches the 80% warning threshold.
+    pub fn warning(&self) -> Option<&QuotaUsage> {
+        self.most_used().filter(|usage| usage.is_warning())
+    }
+
+    pub fn exceeded(&self) -> Option<&QuotaUsage> {
+        self.most_used().filter(|usage| usage.is_exceeded())
+    }
+}
+
+fn quota_file_path(code_home: &Path) -> PathBuf {
+    code_home.join(USAGE_SUBDIR).join(QUOTA_FILE)
+}
+
+fn bucket_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    let minute = now.minute() - now.minute() % BUCKET_MINUTES;
+    now.with_minute(minute)
+        .and_then(|time| time.with_second(0))
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or(now)
+}
+
+/// A ledger that no longer parses is moved to `quota.json.corrupt` so it can
+/// be inspected, and counting starts over.
+fn read_ledger(path: &Path) -> io::Result<QuotaLedger> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(QuotaLedger::default()),
+        Err(err) => return Err(err),
+    };
+    match serde_json::from_str(&contents) {
+        Ok(ledger) => Ok(ledger),
+        Err(err) => {
+            let aside = path.with_extension("json.corrupt");
+            tracing::warn!(
+                error = %err,
+                "usage ledger {} is corrupt; moving it to {}",
+                path.display(),
+                aside.display()
+            );
+            fs::rename(path, &aside)?;
+            Ok(QuotaLedger::default())
+        }
+    }
+}
+
+/// Add one model response to the totals for `scope`.
+/// The cost is estimated at the list prices of `model`'s family.
+pub fn record_usage(
+    code_home: &Path,
+    scope: &str,
+    model: &str,
+    usage: &TokenUsage,
+    now: DateTime<Utc>,
+) -> io::Result<()> {
+    if usage.is_zero() {
+        return Ok(());
+    }
+    let cost_usd =
+        TokenTotals::from_usage(usage).estimated_cost_usd_at(ModelPrices::for_model(model));
+    let start = bucket_start(now);
+
+    let path = quota_file_path(code_home);
+    let usage_dir = code_home.join(USAGE_SUBDIR);
+    fs::create_dir_all(&usage_dir)?;
+    // Lock a sibling file: the ledger itself is replaced by rename below.
+    let lock = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(usage_dir.join(format!("{QUOTA_FILE}.lock")))?;
+    lock.lock_exclusive()?;
+
+    let mut ledger = read_ledger(&path)?;
+    let buckets = ledger.scopes.entry(scope.to_string()).or_default();
+    match buckets.last_mut() {
+        Some(bucket) if bucket.start == start => {
+            bucket.tokens = bucket.tokens.saturating_add(usage.total_tokens);
+            bucket.cost_usd += cost_usd;
+        }
+        _ => buckets.push(UsageBucket {
+            start,
+            tokens: usage.total_tokens,
+            cost_usd,
+        }),
+    }
+    let cutoff = now - Duration::days(RETENTION_DAYS);
+    for buckets in ledger.scopes.values_mut() {
+        buckets.retain(|bucket| bucket.start >= cutoff);
+    }
+    ledger.scopes.retain(|_, buckets| !buckets.is_empty());
+
+    let json = serde_json::to_string_pretty(&ledger)?;
+    let tmp_path = usage_dir.join(format!("{QUOTA_FILE}.tmp"));
+    {
+        let mut tmp = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+        tmp.write_all(json.as_bytes())?;
+        tmp.sync_all()?;
+    }
+    let result = fs::rename(&tmp_path, &path);
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    lock.unlock()?;
+    result
+}
+
+/// Start of `date` in `now`'s time zone, as a UTC instant.
+fn start_of_day<Tz: TimeZone>(now: &DateTime<Tz>, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    let tz = now.timezone();
+    // Where midnight falls in a DST gap the day starts an hour later.
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(midnight + Duration::hours(1))).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Usage for `scope` against each configured limit. Days start at midnight
+/// in `now`'s time zone and weeks on Monday.
+pub fn usage_status<Tz: TimeZone>(
+    code_home: &Path,
+    scope: &str,
+    limits: &UsageLimits,
+    now: &DateTime<Tz>,
+) -> io::Result<QuotaStatus> {
+    if limits.is_empty() {
+        return Ok(QuotaStatus::default());
+    }
+    let ledger = read_ledger(&quota_file_path(code_home))?;
+    let buckets = ledger.scopes.get(scope).map(Vec::as_slice).unwrap_or_default();
+
+    let today = now.date_naive();
+    let monday = today - Duration::days(i64::from(today.weekday().num_days_from_monday()));
+    let totals_since = |start: DateTime<Utc>| {
+        buckets
+            .iter()
+            .filter(|bucket| bucket.start >= start)
+            .fold((0u64, 0.0f64), |(tokens, cost), bucket| {
+                (tokens.saturating_add(bucket.tokens), cost + bucket.cost_usd)
+            })
+    };
+    let (day_tokens, day_cost) = totals_since(start_of_day(now, today));
+    let (week_tokens, week_cost) = totals_since(start_of_day(now, monday));
+
+    let mut usages = Vec::new();
+    let mut push = |window, metric, used: f64, limit: Option<f64>| {
+        if let Some(limit) = limit {
+            usages.push(QuotaUsage {
+                window,
+                metric,
+                used,
+                limit,
+            });
+        }
+    };
+    push(
+        QuotaWindow::Day,
+        QuotaMetric::Tokens,
+        day_tokens as f64,
+        limits.daily_tokens.map(|limit| limit as f64),
+    );
+    push(
+        QuotaWindow::Week,
+        QuotaMetric::Tokens,
+        week_tokens as f64,
+        limits.weekly_tokens.map(|limit| limit as f64),
+    );
+    push(QuotaWindow::Day, QuotaMetric::CostUsd, day_cost, limits.daily_cost_usd);
+    push(QuotaWindow::Week, QuotaMetric::CostUsd, week_cost, limits.weekly_cost_usd);
+    Ok(QuotaStatus { usages })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn usage(total_tokens: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: total_tokens,
+            total_tokens,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn limits_count_local_days_and_weeks_per_profile() {
+        let home = TempDir::new().expect("tempdir");
+        let tz = FixedOffset::east_opt(5 * 3600 + 30 * 60).expect("offset");
+        let at = |day: u32, hour: u32, minute: u32| {
+            tz.with_ymd_and_hms(2026, 3, day, hour, minute, 0)
+                .single()
+                .expect("local time")
+                .with_timezone(&Utc)
+        };
+        // 2026-03-09 is a Monday.
+        record_usage(home.path(), "work", "gpt-5", &usage(300), at(8, 23, 50)).expect("record");
+        record_usage(home.path(), "work", "gpt-5", &usage(200), at(9, 0, 10)).expect("record");
+        record_usage(home.path(), "work", "gpt-5", &usage(400), at(11, 9, 0)).expect("record");
+        record_usage(home.path(), "work", "gpt-5", &usage(100), at(11, 9, 5)).expect("record");
+        record_usage(home.path(), DEFAULT_SCOPE, "gpt-5", &usage(5_000), at(11, 9, 0))
+            .expect("record");
+
+        let limits = UsageLimits {
+            daily_tokens: Some(600),
+            weekly_tokens: Some(1_000),
+            ..Default::default()
+        };
+        let now = tz.with_ymd_and_hms(2026, 3, 11, 12, 0, 0).single().expect("now");
+        let status = usage_status(home.path(), "work", &limits, &now).expect("status");
+        let used: Vec<f64> = status.usages.iter().map(|usage| usage.used).collect();
+        assert_eq!(used, vec![500.0, 700.0]);
+
+        let warning = status.warning().expect("over 80%");
+        assert_eq!(warning.to_string(), "Daily tokens 83% (500 of 600)");
+        assert!(status.exceeded().is_none());
+
+        record_usage(home.path(), "work", "gpt-5", &usage(300), at(10, 12, 0)).expect("record");
+        let status = usage_status(home.path(), "work", &limits, &now).expect("status");
+        let exceeded = status.exceeded().expect("limit reached");
+        assert_eq!(exceeded.window, QuotaWindow::Week);
+        assert_eq!(exceeded.used, 1_000.0);
+    }
+
+    #[test]
+    fn corrupt_ledger_is_moved_aside() {
+        let home = TempDir::new().expect("tempdir");
+        let path = quota_file_path(home.path());
+        fs::create_dir_all(path.parent().expect("usage dir")).expect("mkdir");
+        fs::write(&path, "{not json").expect("write");
+
+        record_usage(home.path(), "work", "gpt-5", &usage(100), Utc::now()).expect("record");
+
+        let aside = fs::read_to_string(path.with_extension("json.corrupt")).expect("kept");
+        assert_eq!(aside, "{not json");
+        let ledger = read_ledger(&path).expect("ledger");
+        assert_eq!(ledger.scopes["work"].len(), 1);
+    }
+}
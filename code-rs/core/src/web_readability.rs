@@ -0,0 +1,314 @@
+//! Readability-style boilerplate removal for `web_fetch`.
+//!
+//! Paragraph-like blocks are scored by length and comma count, and each score
+//! is credited to the block's parent and, at half weight, its grandparent. The
+//! best container, together with sibling blocks that look like part of the
+//! same article, is what gets converted to Markdown. Navigation, cookie
+//! banners, sidebars and footers are cut out along the way. Pages without
+//! enough prose return `None` so callers fall back to the whole page.
+
+use crate::html_dom::HtmlDocument;
+use crate::html_dom::HtmlElement;
+
+/// Pages with less scored prose than this are not treated as articles.
+const MIN_ARTICLE_TEXT: usize = 250;
+
+/// Blocks shorter than this do not contribute to container scores.
+const MIN_BLOCK_TEXT: usize = 25;
+
+const BOILERPLATE_TAGS: &[&str] = &[
+    "nav", "aside", "dialog", "menu", "button", "select", "iframe", "svg",
+];
+
+const BOILERPLATE_ROLES: &[&str] = &[
+    "navigation",
+    "banner",
+    "contentinfo",
+    "complementary",
+    "dialog",
+    "alertdialog",
+    "menu",
+    "menubar",
+    "search",
+];
+
+/// Class/id tokens that mark boilerplate. Short hints must match a whole
+/// token; the rest match as prefixes (`navbar`, `cookie-consent`, ...).
+const NEGATIVE_EXACT_HINTS: &[&str] = &["ad", "ads"];
+const NEGATIVE_PREFIX_HINTS: &[&str] = &[
+    "advert",
+    "banner",
+    "breadcrumb",
+    "comment",
+    "consent",
+    "cookie",
+    "disqus",
+    "footer",
+    "gdpr",
+    "masthead",
+    "menu",
+    "modal",
+    "nav",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "skip",
+    "social",
+    "sponsor",
+    "subscribe",
+    "widget",
+];
+const POSITIVE_PREFIX_HINTS: &[&str] = &[
+    "article", "blog", "body", "content", "entry", "main", "post", "story", "text",
+];
+
+const BLOCK_TAGS: &[&str] = &[
+    "article", "blockquote", "div", "dl", "figure", "form", "h1", "h2", "h3", "h4", "h5", "h6",
+    "ol", "p", "pre", "section", "table", "ul",
+];
+
+/// Return the main article of `html` as an HTML fragment, or `None` when the
+/// page does not look like an article.
+pub(crate) fn extract_article(html: &str) -> Option<String> {
+    let doc = HtmlDocument::parse(html);
+    let elements = doc.elements();
+    let count = elements.len();
+
+    let mut removed = vec![false; count];
+    let mut in_article = vec![false; count];
+    for (idx, element) in elements.iter().enumerate() {
+        let (parent_removed, parent_in_article) = element
+            .parent
+            .map_or((false, false), |parent| (removed[parent], in_article[parent]));
+        in_article[idx] = parent_in_article || element.name == "article";
+        removed[idx] = parent_removed || is_boilerplate(element, parent_in_article);
+    }
+
+    // Text and link text that survive boilerplate removal.
+    let mut hidden_text = vec![0usize; count];
+    let mut hidden_links = vec![0usize; count];
+    for idx in (0..count).rev() {
+        if removed[idx] {
+            hidden_text[idx] = elements[idx].text_len;
+            hidden_links[idx] = elements[idx].link_text_len;
+        }
+        if let Some(parent) = elements[idx].parent
+            && !removed[parent]
+        {
+            hidden_text[parent] += hidden_text[idx];
+            hidden_links[parent] += hidden_links[idx];
+        }
+    }
+    let visible_text = |idx: usize| elements[idx].text_len - hidden_text[idx];
+    let link_density = |idx: usize| {
+        let text = visible_text(idx);
+        if text == 0 {
+            0.0
+        } else {
+            (elements[idx].link_text_len - hidden_links[idx]) as f64 / text as f64
+        }
+    };
+
+    let mut scores = vec![0.0f64; count];
+    let mut is_candidate = vec![false; count];
+    let mut prose = 0usize;
+    for (idx, element) in elements.iter().enumerate() {
+        if removed[idx] || !is_scored_block(elements, element) {
+            continue;
+        }
+        let text = visible_text(idx);
+        if text < MIN_BLOCK_TEXT {
+            continue;
+        }
+        prose += text;
+        let score = 1.0 + element.commas as f64 + (text / 100).min(3) as f64;
+        let mut ancestor = element.parent;
+        for weight in [1.0, 0.5] {
+            let Some(current) = ancestor else {
+                break;
+            };
+            if !is_candidate[current] {
+                is_candidate[current] = true;
+                scores[current] += initial_score(&elements[current]);
+            }
+            scores[current] += score * weight;
+            ancestor = elements[current].parent;
+        }
+    }
+    if prose < MIN_ARTICLE_TEXT {
+        return None;
+    }
+
+    let final_score = |idx: usize| scores[idx] * (1.0 - link_density(idx));
+    let top = (0..count)
+        .filter(|&idx| is_candidate[idx])
+        .max_by(|&a, &b| final_score(a).total_cmp(&final_score(b)))?;
+
+    // Articles are often split across sibling containers; keep siblings that
+    // score close to the winner or read like prose themselves.
+    let threshold = (final_score(top) * 0.2).max(10.0);
+    let parts: Vec<usize> = match elements[top].parent {
+        Some(parent) => elements[parent]
+            .children
+            .iter()
+            .copied()
+            .filter(|&sibling| {
+                sibling == top
+                    || (!removed[sibling]
+                        && ((is_candidate[sibling] && final_score(sibling) >= threshold)
+                            || (elements[sibling].name == "p"
+                                && visible_text(sibling) > 80
+                                && link_density(sibling) < 0.25)))
+            })
+            .collect(),
+        None => vec![top],
+    };
+
+    let mut article = String::new();
+    let has_heading = elements.iter().enumerate().any(|(idx, element)| {
+        element.name == "h1"
+            && !removed[idx]
+            && parts.iter().any(|&part| {
+                element.start >= elements[part].start && element.end <= elements[part].end
+            })
+    });
+    if !has_heading
+        && let Some(title) = elements
+            .iter()
+            .position(|element| element.name == "title")
+            .map(|idx| doc.inner_html(idx).trim())
+            .filter(|title| !title.is_empty())
+    {
+        article.push_str(&format!("<h1>{title}</h1>\n"));
+    }
+    for part in parts {
+        article.push_str(&doc.outer_html_without(part, |idx| removed[idx]));
+        article.push('\n');
+    }
+    Some(article)
+}
+
+fn is_boilerplate(element: &HtmlElement<'_>, in_article: bool) -> bool {
+    let name = element.name.as_str();
+    if BOILERPLATE_TAGS.contains(&name) {
+        return true;
+    }
+    // Article headers and footers usually carry the title and byline.
+    if matches!(name, "header" | "footer") && !in_article {
+        return true;
+    }
+    if element.attr("hidden").is_some()
+        || element
+            .attr("aria-hidden")
+            .is_some_and(|value| value.eq_ignore_ascii_case("true"))
+    {
+        return true;
+    }
+    if let Some(role) = element.attr("role")
+        && BOILERPLATE_ROLES.contains(&role.to_ascii_lowercase().as_str())
+    {
+        return true;
+    }
+    if matches!(name, "html" | "body" | "main" | "article") {
+        return false;
+    }
+    let hints = class_and_id(element);
+    has_negative_hint(&hints) && !has_positive_hint(&hints)
+}
+
+fn is_scored_block(elements: &[HtmlElement<'_>], element: &HtmlElement<'_>) -> bool {
+    match element.name.as_str() {
+        "p" | "pre" | "td" | "blockquote" => true,
+        // Sites that lay out text in bare divs.
+        "div" => !element
+            .children
+            .iter()
+            .any(|&child| BLOCK_TAGS.contains(&elements[child].name.as_str())),
+        _ => false,
+    }
+}
+
+fn initial_score(element: &HtmlElement<'_>) -> f64 {
+    let tag_score = match element.name.as_str() {
+        "article" => 10.0,
+        "div" | "main" => 5.0,
+        "pre" | "td" | "blockquote" | "section" => 3.0,
+        "ol" | "ul" | "dl" | "form" | "address" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    let hints = class_and_id(element);
+    let mut class_score = 0.0;
+    if has_negative_hint(&hints) {
+        class_score -= 25.0;
+    }
+    if has_positive_hint(&hints) {
+        class_score += 25.0;
+    }
+    tag_score + class_score
+}
+
+fn class_and_id(element: &HtmlElement<'_>) -> Vec<String> {
+    [element.attr("class"), element.attr("id")]
+        .into_iter()
+        .flatten()
+        .flat_map(|value| value.split(|c: char| !c.is_ascii_alphanumeric()))
+        .filter(|token| !token.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+fn has_negative_hint(tokens: &[String]) -> bool {
+    tokens.iter().any(|token| {
+        NEGATIVE_EXACT_HINTS.contains(&token.as_str())
+            || NEGATIVE_PREFIX_HINTS
+                .iter()
+                .any(|hint| token.starts_with(hint))
+    })
+}
+
+fn has_positive_hint(tokens: &[String]) -> bool {
+    tokens.iter().any(|token| {
+        POSITIVE_PREFIX_HINTS
+            .iter()
+            .any(|hint| token.starts_with(hint))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAGRAPH: &str = "Rust programs are built from crates, modules and items, and the compiler checks ownership, borrowing and lifetimes before any code runs.";
+
+    #[test]
+    fn keeps_article_and_drops_boilerplate() {
+        let html = format!(
+            r#"<html><head><title>Ownership explained</title></head><body>
+            <nav><a href="/">Home</a><a href="/docs">Docs</a></nav>
+            <div class="cookie-banner">We use cookies to improve your experience, please accept them.</div>
+            <div id="content"><div class="post-body">
+              <p>{PARAGRAPH}</p><p>{PARAGRAPH}</p><p>{PARAGRAPH}</p>
+              <div class="share-links"><a href="/x">Share on X</a></div>
+            </div></div>
+            <footer>Copyright, all rights reserved, terms and privacy apply to this website.</footer>
+            </body></html>"#
+        );
+
+        let article = extract_article(&html).expect("article");
+        assert!(article.starts_with("<h1>Ownership explained</h1>"));
+        assert_eq!(article.matches(PARAGRAPH).count(), 3);
+        for boilerplate in ["Home", "cookies", "Share on X", "Copyright"] {
+            assert!(!article.contains(boilerplate), "{boilerplate} leaked: {article}");
+        }
+    }
+
+    #[test]
+    fn returns_none_without_enough_prose() {
+        let html = "<html><body><nav><a href=\"/\">Home</a></nav><p>Short page.</p></body></html>";
+        assert_eq!(extract_article(html), None);
+    }
+}
@@ -0,0 +1,290 @@
+//! Function-style `web_search` tool backed by a configurable search API.
+//!
+//! When `[tools.web_search_backend]` is set, the model gets a plain function
+//! tool instead of the provider's built-in search, so search works with any
+//! model provider. Each backend is a single GET request; the responses are
+//! normalised to title / URL / snippet so the model can follow up with
+//! `fetch_url` or the browser.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config_types::WebSearchBackendConfig;
+use crate::config_types::WebSearchProvider;
+use crate::navigation_policy::NavigationDecision;
+use crate::navigation_policy::NavigationPolicy;
+
+pub(crate) const DEFAULT_RESULTS: usize = 8;
+pub(crate) const MAX_RESULTS: usize = 20;
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_SNIPPET_CHARS: usize = 400;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct SearchResult {
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) snippet: String,
+}
+
+/// Run `query` against the configured backend and return at most `count`
+/// results, leaving out any the navigation policy would refuse to open.
+pub(crate) async fn search(
+    backend: &WebSearchBackendConfig,
+    policy: &NavigationPolicy,
+    query: &str,
+    count: usize,
+    user_agent: &str,
+) -> Result<Vec<SearchResult>, String> {
+    let count = count.clamp(1, MAX_RESULTS);
+    let client = crate::http_client::apply_proxy(reqwest::Client::builder())
+        .user_agent(user_agent)
+        .timeout(SEARCH_TIMEOUT)
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {err}"))?;
+    let request = build_request(&client, backend, query, count)?;
+    // Errors are shown without their URL, which can carry the query and,
+    // for self-hosted backends, credentials.
+    let response = request.send().await.map_err(|err| {
+        if err.is_timeout() {
+            "search request timed out".to_string()
+        } else {
+            format!("search request failed: {}", err.without_url())
+        }
+    })?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read the search response: {}", err.without_url()))?;
+    if !status.is_success() {
+        let detail: String = body.chars().take(300).collect();
+        return Err(format!("{} returned {status}: {}", backend.provider, detail.trim()));
+    }
+    let value: Value = serde_json::from_str(&body)
+        .map_err(|err| format!("{} returned invalid JSON: {err}", backend.provider))?;
+    Ok(parse_results(backend.provider, &value)
+        .into_iter()
+        .filter(|result| !matches!(policy.evaluate(&result.url), NavigationDecision::Deny(_)))
+        .take(count)
+        .collect())
+}
+
+fn build_request(
+    client: &reqwest::Client,
+    backend: &WebSearchBackendConfig,
+    query: &str,
+    count: usize,
+) -> Result<reqwest::RequestBuilder, String> {
+    let count_param = count.to_string();
+    let endpoint = |default: &str| {
+        backend
+            .base_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    };
+    let request = match backend.provider {
+        WebSearchProvider::Brave => client
+            .get(endpoint("https://api.search.brave.com/res/v1/web/search"))
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", api_key(backend)?)
+            .query(&[("q", query), ("count", count_param.as_str())]),
+        WebSearchProvider::Searxng => {
+            let Some(base_url) = backend.base_url.as_deref() else {
+                return Err("SearXNG needs `base_url` set to the instance URL".to_string());
+            };
+            let request = client
+                .get(format!("{}/search", base_url.trim_end_matches('/')))
+                .query(&[("q", query), ("format", "json")]);
+            // Public instances are open; private ones often sit behind a token.
+            match resolve_api_key(backend) {
+                Some(key) => request.bearer_auth(key),
+                None => request,
+            }
+        }
+        WebSearchProvider::Bing => client
+            .get(endpoint("https://api.bing.microsoft.com/v7.0/search"))
+            .header("Ocp-Apim-Subscription-Key", api_key(backend)?)
+            .query(&[("q", query), ("count", count_param.as_str())]),
+        WebSearchProvider::Google => {
+            let Some(engine_id) = backend.search_engine_id.as_deref() else {
+                return Err("Google search needs `search_engine_id` (the cx value)".to_string());
+            };
+            // The Custom Search API returns at most 10 results per request.
+            let num = count.min(10).to_string();
+            // The key goes in a header so it never appears in a URL.
+            client
+                .get(endpoint("https://www.googleapis.com/customsearch/v1"))
+                .header("X-goog-api-key", api_key(backend)?)
+                .query(&[("cx", engine_id), ("q", query), ("num", num.as_str())])
+        }
+    };
+    Ok(request)
+}
+
+fn resolve_api_key(backend: &WebSearchBackendConfig) -> Option<String> {
+    backend
+        .env_key
+        .as_deref()
+        .and_then(|name| std::env::var(name).ok())
+        .or_else(|| backend.api_key.clone())
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+fn api_key(backend: &WebSearchBackendConfig) -> Result<String, String> {
+    resolve_api_key(backend).ok_or_else(|| match backend.env_key.as_deref() {
+        Some(name) => format!("no {} API key: set the {name} environment variable", backend.provider),
+        None => format!("no {} API key: set `api_key` or `env_key`", backend.provider),
+    })
+}
+
+/// Pull title / URL / snippet triples out of a backend's JSON response.
+fn parse_results(provider: WebSearchProvider, value: &Value) -> Vec<SearchResult> {
+    let (items, title_key, url_key, snippet_key) = match provider {
+        WebSearchProvider::Brave => (value.pointer("/web/results"), "title", "url", "description"),
+        WebSearchProvider::Searxng => (value.get("results"), "title", "url", "content"),
+        WebSearchProvider::Bing => (value.pointer("/webPages/value"), "name", "url", "snippet"),
+        WebSearchProvider::Google => (value.get("items"), "title", "link", "snippet"),
+    };
+    let Some(items) = items.and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let url = item.get(url_key).and_then(Value::as_str)?.trim();
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return None;
+            }
+            let text = |key: &str| {
+                plain_text(item.get(key).and_then(Value::as_str).unwrap_or_default())
+            };
+            let mut snippet = text(snippet_key);
+            if snippet.chars().count() > MAX_SNIPPET_CHARS {
+                snippet = snippet.chars().take(MAX_SNIPPET_CHARS).collect::<String>();
+                snippet.push('…');
+            }
+            Some(SearchResult {
+                title: text(title_key),
+                url: url.to_string(),
+                snippet,
+            })
+        })
+        .collect()
+}
+
+/// Drop the `<strong>` highlighting some APIs put in snippets and decode the
+/// common entities, collapsing whitespace.
+fn plain_text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    let decoded = out
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&");
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn normalises_each_backend_response() {
+        let expected = vec![SearchResult {
+            title: "Rust & Cargo".to_string(),
+            url: "https://doc.rust-lang.org/cargo/".to_string(),
+            snippet: "The Cargo Book".to_string(),
+        }];
+        let responses = [
+            (
+                WebSearchProvider::Brave,
+                json!({"web": {"results": [{
+                    "title": "Rust &amp; Cargo",
+                    "url": "https://doc.rust-lang.org/cargo/",
+                    "description": "The <strong>Cargo</strong>\n Book"
+                }]}}),
+            ),
+            (
+                WebSearchProvider::Searxng,
+                json!({"results": [
+                    {"title": "Rust & Cargo", "url": "https://doc.rust-lang.org/cargo/", "content": "The Cargo Book"},
+                    {"title": "Local", "url": "file:///etc/passwd", "content": ""}
+                ]}),
+            ),
+            (
+                WebSearchProvider::Bing,
+                json!({"webPages": {"value": [{
+                    "name": "Rust & Cargo",
+                    "url": "https://doc.rust-lang.org/cargo/",
+                    "snippet": "The Cargo Book"
+                }]}}),
+            ),
+            (
+                WebSearchProvider::Google,
+                json!({"items": [{
+                    "title": "Rust & Cargo",
+                    "link": "https://doc.rust-lang.org/cargo/",
+                    "snippet": "The Cargo&nbsp;Book"
+                }]}),
+            ),
+        ];
+        for (provider, response) in responses {
+            assert_eq!(parse_results(provider, &response), expected, "{provider}");
+        }
+        assert_eq!(parse_results(WebSearchProvider::Bing, &json!({})), Vec::new());
+
+        let backend = WebSearchBackendConfig {
+            provider: WebSearchProvider::Google,
+            api_key: None,
+            env_key: None,
+            base_url: None,
+            search_engine_id: Some("cx".to_string()),
+            max_results: None,
+        };
+        assert_eq!(
+            api_key(&backend),
+            Err("no google API key: set `api_key` or `env_key`".to_string())
+        );
+    }
+
+    #[test]
+    fn google_key_is_sent_in_a_header_not_the_url() {
+        let backend = WebSearchBackendConfig {
+            provider: WebSearchProvider::Google,
+            api_key: Some("secret-key".to_string()),
+            env_key: None,
+            base_url: None,
+            search_engine_id: Some("cx".to_string()),
+            max_results: None,
+        };
+        let client = reqwest::Client::new();
+        let request = build_request(&client, &backend, "rust", 5)
+            .expect("request")
+            .build()
+            .expect("build");
+        assert!(!request.url().as_str().contains("secret-key"), "{}", request.url());
+        assert_eq!(
+            request.headers().get("X-goog-api-key").and_then(|value| value.to_str().ok()),
+            Some("secret-key")
+        );
+    }
+}
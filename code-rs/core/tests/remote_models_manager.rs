@@ -61,6 +61,7 @@ fn provider_for(base_url: String) -> ModelProviderInfo {
         websocket_connect_timeout_ms: None,
         requires_openai_auth: false,
         openrouter: None,
+        tls: None,
     }
 }
 
@@ -14,6 +14,8 @@ use code_core::protocol::EventMsg;
 use code_core::protocol::ExecCommandBeginEvent;
 use code_core::protocol::ExecCommandEndEvent;
 use code_core::protocol::FileChange;
+use code_core::protocol::LspDiagnosticsEvent;
+use code_core::protocol::LspSeverity;
 use code_core::protocol::McpInvocation;
 use code_core::protocol::McpToolCallBeginEvent;
 use code_core::protocol::McpToolCallEndEvent;
@@ -230,6 +232,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::McpServerLogsResponse(_)
+            | EventMsg::ToolNamesResponse(_)
+            | EventMsg::McpCompletionResponse(_)
             | EventMsg::TaskLifecycle(_)
             | EventMsg::ViewImageToolCall(_) => {
                 // Environment context events are consumed by the TUI; the CLI runner
@@ -356,7 +361,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 );
             }
             EventMsg::ExecCommandOutputDelta(_) => {}
-            EventMsg::ExecCommandEnd(ExecCommandEndEvent { call_id, stdout, stderr, duration, exit_code }) => {
+            EventMsg::ExecCommandEnd(ExecCommandEndEvent { call_id, stdout, stderr, duration, exit_code, .. }) => {
                 let exec_command = self.call_id_to_command.remove(&call_id);
                 let (duration, call) = if let Some(ExecCommandBegin { command, .. }) = exec_command
                 {
@@ -411,6 +416,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     format_mcp_invocation(&invocation).style(self.bold),
                 );
             }
+            EventMsg::McpToolCallProgress(_) => {
+                // Partial MCP output is summarized by the final McpToolCallEnd.
+            }
             EventMsg::McpToolCallEnd(tool_call_end_event) => {
                 let is_success = tool_call_end_event.is_success();
                 let McpToolCallEndEvent {
@@ -562,6 +570,37 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     println!("{}", line.style(self.dimmed));
                 }
             }
+            EventMsg::LspDiagnostics(LspDiagnosticsEvent {
+                servers,
+                diagnostics,
+                total,
+                failures,
+                ..
+            }) => {
+                let servers = servers.join(", ");
+                if total > 0 {
+                    let title = format!("diagnostics ({servers}): {total} new");
+                    ts_println!(self, "{}", title.style(self.red));
+                    for diagnostic in &diagnostics {
+                        let severity = match diagnostic.severity {
+                            LspSeverity::Error => "error",
+                            LspSeverity::Warning => "warning",
+                        };
+                        let message = diagnostic.message.lines().next().unwrap_or_default();
+                        let line = format!(
+                            "{}:{}:{} {severity}: {message}",
+                            diagnostic.path, diagnostic.line, diagnostic.column
+                        );
+                        println!("{}", line.style(self.dimmed));
+                    }
+                } else if !servers.is_empty() {
+                    let title = format!("diagnostics ({servers}): no new errors or warnings");
+                    ts_println!(self, "{}", title.style(self.green));
+                }
+                for failure in failures {
+                    println!("{}", failure.style(self.dimmed));
+                }
+            }
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
                 if self.last_turn_diff.as_deref() == Some(&unified_diff) {
                     // Suppress duplicate turn diffs; they are sometimes streamed multiple times.
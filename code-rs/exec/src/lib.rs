@@ -24,6 +24,7 @@ use code_core::config::set_default_originator;
 use code_core::config::Config;
 use code_core::config::ConfigOverrides;
 use code_core::config_types::AutoDriveContinueMode;
+use code_core::usage_quota;
 use code_core::model_family::{derive_default_model_family, find_family_for_model};
 use code_core::git_info::get_git_repo_root;
 use code_core::review_coord::{
@@ -306,7 +307,14 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         }
     };
 
+    let config_migration_notices = code_core::config::find_code_home()
+        .map(|code_home| code_core::config::migrate_on_startup(&code_home))
+        .unwrap_or_default();
     let mut config = Config::load_with_cli_overrides(cli_kv_overrides, overrides)?;
+    config.config_diagnostics.splice(0..0, config_migration_notices);
+    for diagnostic in &config.config_diagnostics {
+        eprintln!("Warning: {diagnostic}");
+    }
     config.max_run_seconds = max_seconds;
     config.max_run_deadline = run_deadline_std;
     config.demo_developer_message = cli.demo_developer_message.clone();
@@ -507,6 +515,26 @@ pub async fn run_main(cli: Cli, code_linux_sandbox_exe: Option<PathBuf>) -> anyh
         std::process::exit(1);
     }
 
+    // There is nobody to confirm a send past a usage limit, so stop instead.
+    if let Some(limits) = config.usage_limits {
+        let scope = usage_quota::scope_for_profile(config.active_profile.as_deref());
+        match usage_quota::usage_status(&config.code_home, scope, &limits, &chrono::Local::now()) {
+            Ok(status) => {
+                if let Some(exceeded) = status.exceeded() {
+                    eprintln!(
+                        "Usage limit reached for profile `{scope}`: {exceeded}. \
+                         Raise `usage_limits` in config.toml to continue."
+                    );
+                    std::process::exit(1);
+                }
+                if let Some(warning) = status.warning() {
+                    eprintln!("Warning: usage for profile `{scope}` is near its limit: {warning}");
+                }
+            }
+            Err(err) => eprintln!("Warning: could not read usage totals: {err}"),
+        }
+    }
+
     let auth_manager = AuthManager::shared_with_mode_and_originator(
         config.code_home.clone(),
         code_app_server_protocol::AuthMode::ApiKey,
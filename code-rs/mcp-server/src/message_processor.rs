@@ -1462,6 +1462,8 @@ fn convert_mcp_servers(
                         tool_timeout_sec: None,
                         enabled_tools: None,
                         disabled_tools: None,
+                        approval_required_tools: None,
+                        tool_timeouts_sec: None,
                     },
                 );
             }
@@ -1659,6 +1661,7 @@ fn configure_session_op_from_config(config: &Config) -> Op {
         resume_path: None,
         demo_developer_message: config.demo_developer_message.clone(),
         dynamic_tools: config.dynamic_tools.clone(),
+        active_profile: config.active_profile.clone(),
     }
 }
 
@@ -30,6 +30,8 @@ async fn main() -> Result<()> {
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        approval_required_tools: None,
+        tool_timeouts_sec: None,
     };
     // Slow-one: 2s but we allow 3s
     let slow_ok = McpServerConfig {
@@ -45,6 +47,8 @@ async fn main() -> Result<()> {
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        approval_required_tools: None,
+        tool_timeouts_sec: None,
     };
     // Slow-two: 3s but we allow 1s (should fail)
     let slow_fail = McpServerConfig {
@@ -60,6 +64,8 @@ async fn main() -> Result<()> {
         tool_timeout_sec: None,
         enabled_tools: None,
         disabled_tools: None,
+        approval_required_tools: None,
+        tool_timeouts_sec: None,
     };
 
     let mut servers = HashMap::new();
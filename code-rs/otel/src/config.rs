@@ -8,6 +8,8 @@ pub struct OtelSettings {
     pub service_version: String,
     pub code_home: PathBuf,
     pub exporter: OtelExporter,
+    /// Also export session metrics through `exporter`.
+    pub metrics: bool,
 }
 
 #[derive(Clone, Debug)]
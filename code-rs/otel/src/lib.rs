@@ -1,4 +1,5 @@
 pub mod config;
+pub mod metrics;
 
 pub mod otel_event_manager;
 #[cfg(feature = "otel")]
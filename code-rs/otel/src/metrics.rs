@@ -0,0 +1,346 @@
+//! Opt-in session metrics: latency per model turn, tool-call counts, token
+//! usage and error rates.
+//!
+//! Nothing is recorded until [`configure`] asks for local JSON files or the
+//! OTLP provider enables metric export, so by default no data is kept or sent
+//! anywhere.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+static RECORDER: LazyLock<Mutex<Option<Recorder>>> = LazyLock::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnMetrics {
+    pub completed: u64,
+    pub failed: u64,
+    pub total_latency_ms: u64,
+    pub max_latency_ms: u64,
+    pub mean_latency_ms: u64,
+    pub error_rate: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CallMetrics {
+    pub total: u64,
+    pub failed: u64,
+    pub error_rate: f64,
+}
+
+impl CallMetrics {
+    fn record(&mut self, success: bool) {
+        self.total += 1;
+        if !success {
+            self.failed += 1;
+        }
+        self.error_rate = rate(self.failed, self.total);
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenMetrics {
+    pub input: u64,
+    pub cached_input: u64,
+    pub output: u64,
+    pub reasoning_output: u64,
+    pub total: u64,
+}
+
+/// Totals for one conversation, written to `<json_dir>/<conversation_id>.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionMetrics {
+    pub conversation_id: String,
+    pub model: String,
+    pub started_at: String,
+    pub updated_at: String,
+    pub turns: TurnMetrics,
+    pub tool_calls: BTreeMap<String, CallMetrics>,
+    pub api_requests: CallMetrics,
+    pub tokens: TokenMetrics,
+}
+
+pub(crate) enum MetricEvent<'a> {
+    TurnCompleted {
+        latency_ms: Option<u64>,
+        tokens: TokenMetrics,
+    },
+    TurnFailed {
+        latency_ms: Option<u64>,
+    },
+    ToolCall {
+        tool: &'a str,
+        success: bool,
+    },
+    ApiRequest {
+        success: bool,
+    },
+}
+
+impl SessionMetrics {
+    fn new(conversation_id: &str, now: &str) -> Self {
+        Self {
+            conversation_id: conversation_id.to_string(),
+            started_at: now.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Fold one event into the totals. Returns whether a turn ended.
+    fn apply(&mut self, event: &MetricEvent<'_>) -> bool {
+        match event {
+            MetricEvent::TurnCompleted { latency_ms, tokens } => {
+                self.turns.completed += 1;
+                self.record_latency(*latency_ms);
+                self.tokens.input += tokens.input;
+                self.tokens.cached_input += tokens.cached_input;
+                self.tokens.output += tokens.output;
+                self.tokens.reasoning_output += tokens.reasoning_output;
+                self.tokens.total += tokens.total;
+                true
+            }
+            MetricEvent::TurnFailed { latency_ms } => {
+                self.turns.failed += 1;
+                self.record_latency(*latency_ms);
+                true
+            }
+            MetricEvent::ToolCall { tool, success } => {
+                self.tool_calls.entry((*tool).to_string()).or_default().record(*success);
+                false
+            }
+            MetricEvent::ApiRequest { success } => {
+                self.api_requests.record(*success);
+                false
+            }
+        }
+    }
+
+    fn record_latency(&mut self, latency_ms: Option<u64>) {
+        let turns = &mut self.turns;
+        if let Some(latency_ms) = latency_ms {
+            turns.total_latency_ms += latency_ms;
+            turns.max_latency_ms = turns.max_latency_ms.max(latency_ms);
+        }
+        let ended = turns.completed + turns.failed;
+        turns.mean_latency_ms = turns.total_latency_ms / ended.max(1);
+        turns.error_rate = rate(turns.failed, ended);
+    }
+}
+
+fn rate(failed: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        failed as f64 / total as f64
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    json_dir: Option<PathBuf>,
+    sessions: HashMap<String, SessionMetrics>,
+    #[cfg(feature = "otel")]
+    otlp: Option<otlp::Instruments>,
+}
+
+fn recorder() -> MutexGuard<'static, Option<Recorder>> {
+    RECORDER.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Write each session's totals to `json_dir` after every model turn.
+pub fn configure(json_dir: Option<PathBuf>) {
+    if let Some(json_dir) = json_dir {
+        recorder().get_or_insert_with(Recorder::default).json_dir = Some(json_dir);
+    }
+}
+
+/// Report metrics through the global meter provider set by the OTLP provider.
+#[cfg(feature = "otel")]
+pub(crate) fn enable_otlp() {
+    recorder().get_or_insert_with(Recorder::default).otlp = Some(otlp::Instruments::new());
+}
+
+pub(crate) fn record(conversation_id: &str, model: &str, event: MetricEvent<'_>) {
+    let mut guard = recorder();
+    let Some(recorder) = guard.as_mut() else {
+        return;
+    };
+    #[cfg(feature = "otel")]
+    if let Some(instruments) = &recorder.otlp {
+        instruments.record(model, &event);
+    }
+
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+    let session = recorder
+        .sessions
+        .entry(conversation_id.to_string())
+        .or_insert_with(|| SessionMetrics::new(conversation_id, &now));
+    session.model = model.to_string();
+    session.updated_at = now;
+    if session.apply(&event)
+        && let Some(json_dir) = &recorder.json_dir
+        && let Err(err) = write_session(json_dir, session)
+    {
+        tracing::warn!("failed to write metrics to {}: {err}", json_dir.display());
+    }
+}
+
+fn write_session(json_dir: &Path, session: &SessionMetrics) -> io::Result<()> {
+    std::fs::create_dir_all(json_dir)?;
+    let path = json_dir.join(format!("{}.json", session.conversation_id));
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(session)?)?;
+    std::fs::rename(tmp, path)
+}
+
+#[cfg(feature = "otel")]
+mod otlp {
+    use opentelemetry::KeyValue;
+    use opentelemetry::metrics::Counter;
+    use opentelemetry::metrics::Histogram;
+
+    use super::MetricEvent;
+
+    pub(super) struct Instruments {
+        turn_latency: Histogram<u64>,
+        turns: Counter<u64>,
+        tool_calls: Counter<u64>,
+        api_requests: Counter<u64>,
+        tokens: Counter<u64>,
+    }
+
+    impl Instruments {
+        pub(super) fn new() -> Self {
+            let meter = opentelemetry::global::meter("code");
+            Self {
+                turn_latency: meter
+                    .u64_histogram("code.turn.latency")
+                    .with_unit("ms")
+                    .with_description("Time from sending a model request to its outcome")
+                    .build(),
+                turns: meter
+                    .u64_counter("code.turns")
+                    .with_description("Model turns by outcome")
+                    .build(),
+                tool_calls: meter
+                    .u64_counter("code.tool_calls")
+                    .with_description("Tool calls by tool and success")
+                    .build(),
+                api_requests: meter
+                    .u64_counter("code.api_requests")
+                    .with_description("Model API requests by success")
+                    .build(),
+                tokens: meter
+                    .u64_counter("code.tokens")
+                    .with_description("Tokens used by kind")
+                    .build(),
+            }
+        }
+
+        pub(super) fn record(&self, model: &str, event: &MetricEvent<'_>) {
+            let model = KeyValue::new("model", model.to_string());
+            match event {
+                MetricEvent::TurnCompleted { latency_ms, tokens } => {
+                    self.record_turn(model.clone(), "completed", *latency_ms);
+                    for (kind, count) in [
+                        ("input", tokens.input),
+                        ("cached_input", tokens.cached_input),
+                        ("output", tokens.output),
+                        ("reasoning_output", tokens.reasoning_output),
+                    ] {
+                        self.tokens.add(count, &[model.clone(), KeyValue::new("kind", kind)]);
+                    }
+                }
+                MetricEvent::TurnFailed { latency_ms } => {
+                    self.record_turn(model, "failed", *latency_ms);
+                }
+                MetricEvent::ToolCall { tool, success } => {
+                    self.tool_calls.add(
+                        1,
+                        &[
+                            model,
+                            KeyValue::new("tool", tool.to_string()),
+                            KeyValue::new("success", *success),
+                        ],
+                    );
+                }
+                MetricEvent::ApiRequest { success } => {
+                    self.api_requests.add(1, &[model, KeyValue::new("success", *success)]);
+                }
+            }
+        }
+
+        fn record_turn(&self, model: KeyValue, outcome: &'static str, latency_ms: Option<u64>) {
+            let attributes = [model, KeyValue::new("outcome", outcome)];
+            self.turns.add(1, &attributes);
+            if let Some(latency_ms) = latency_ms {
+                self.turn_latency.record(latency_ms, &attributes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_totals_track_latency_tools_tokens_and_error_rates() {
+        let mut session = SessionMetrics::new("conv-1", "2026-01-01T00:00:00.000Z");
+        let tokens = TokenMetrics {
+            input: 100,
+            cached_input: 40,
+            output: 20,
+            reasoning_output: 5,
+            total: 120,
+        };
+        assert!(session.apply(&MetricEvent::TurnCompleted {
+            latency_ms: Some(300),
+            tokens: tokens.clone(),
+        }));
+        assert!(session.apply(&MetricEvent::TurnCompleted {
+            latency_ms: Some(900),
+            tokens,
+        }));
+        assert!(session.apply(&MetricEvent::TurnFailed { latency_ms: Some(300) }));
+        for success in [true, true, false] {
+            assert!(!session.apply(&MetricEvent::ToolCall { tool: "shell", success }));
+        }
+        assert!(!session.apply(&MetricEvent::ApiRequest { success: false }));
+        assert!(!session.apply(&MetricEvent::ApiRequest { success: true }));
+
+        assert_eq!(
+            session.turns,
+            TurnMetrics {
+                completed: 2,
+                failed: 1,
+                total_latency_ms: 1500,
+                max_latency_ms: 900,
+                mean_latency_ms: 500,
+                error_rate: 1.0 / 3.0,
+            }
+        );
+        assert_eq!(session.tokens.input, 200);
+        assert_eq!(session.tokens.total, 240);
+        assert_eq!(
+            session.tool_calls.get("shell"),
+            Some(&CallMetrics { total: 3, failed: 1, error_rate: 1.0 / 3.0 })
+        );
+        assert_eq!(session.api_requests.error_rate, 0.5);
+
+        let json = serde_json::to_value(&session).expect("serialize");
+        assert_eq!(json["tool_calls"]["shell"]["failed"], 1);
+        assert_eq!(json["turns"]["mean_latency_ms"], 500);
+    }
+}
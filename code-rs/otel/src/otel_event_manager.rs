@@ -1,3 +1,5 @@
+use crate::metrics::MetricEvent;
+use crate::metrics::TokenMetrics;
 use chrono::SecondsFormat;
 use chrono::Utc;
 use code_app_server_protocol::AuthMode;
@@ -167,6 +169,8 @@ impl OtelEventManager {
             Ok(response) => (Some(response.status().as_u16()), None),
             Err(error) => (error.status().map(|s| s.as_u16()), Some(error.to_string())),
         };
+        let success = matches!(&response, Ok(response) if response.status().is_success());
+        self.record_metric(MetricEvent::ApiRequest { success });
 
         tracing::event!(
             tracing::Level::INFO,
@@ -421,6 +425,7 @@ impl OtelEventManager {
             Ok(content) => (content, true),
             Err(error) => (&error.to_string(), false),
         };
+        self.record_metric(MetricEvent::ToolCall { tool: tool_name, success });
 
         tracing::event!(
             tracing::Level::INFO,
@@ -445,6 +450,7 @@ impl OtelEventManager {
     }
 
     pub fn log_tool_failed(&self, tool_name: &str, error: &str) {
+        self.record_metric(MetricEvent::ToolCall { tool: tool_name, success: false });
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.tool_result",
@@ -472,6 +478,7 @@ impl OtelEventManager {
         success: bool,
         output: &str,
     ) {
+        self.record_metric(MetricEvent::ToolCall { tool: tool_name, success });
         let success_str = if success { "true" } else { "false" };
 
         tracing::event!(
@@ -495,6 +502,28 @@ impl OtelEventManager {
     }
 
     pub fn turn_latency_event(&self, payload: TurnLatencyPayload) {
+        match payload.phase {
+            TurnLatencyPhase::RequestScheduled => {}
+            TurnLatencyPhase::RequestCompleted => {
+                self.record_metric(MetricEvent::TurnCompleted {
+                    latency_ms: payload.duration_ms,
+                    tokens: TokenMetrics {
+                        input: payload.token_usage_input_tokens.unwrap_or_default(),
+                        cached_input: payload.token_usage_cached_input_tokens.unwrap_or_default(),
+                        output: payload.token_usage_output_tokens.unwrap_or_default(),
+                        reasoning_output: payload
+                            .token_usage_reasoning_output_tokens
+                            .unwrap_or_default(),
+                        total: payload.token_usage_total_tokens.unwrap_or_default(),
+                    },
+                });
+            }
+            TurnLatencyPhase::RequestFailed => {
+                self.record_metric(MetricEvent::TurnFailed {
+                    latency_ms: payload.duration_ms,
+                });
+            }
+        }
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.turn_latency",
@@ -528,6 +557,14 @@ impl OtelEventManager {
             note = payload.note,
         );
     }
+
+    fn record_metric(&self, event: MetricEvent<'_>) {
+        crate::metrics::record(
+            &self.metadata.conversation_id.to_string(),
+            &self.metadata.model,
+            event,
+        );
+    }
 }
 
 fn timestamp() -> String {
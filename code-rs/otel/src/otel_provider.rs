@@ -4,16 +4,19 @@ use crate::config::OtelSettings;
 use opentelemetry::KeyValue;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::LogExporter;
+use opentelemetry_otlp::MetricExporter;
 use opentelemetry_otlp::Protocol;
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_otlp::WithHttpConfig;
 use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_semantic_conventions as semconv;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
+use std::collections::HashMap;
 use std::error::Error;
 use tonic::metadata::MetadataMap;
 use tracing::debug;
@@ -24,11 +27,15 @@ const ENV_ATTRIBUTE: &str = "env";
 
 pub struct OtelProvider {
     pub logger: SdkLoggerProvider,
+    pub meter: Option<SdkMeterProvider>,
 }
 
 impl OtelProvider {
     pub fn shutdown(&self) {
         let _ = self.logger.shutdown();
+        if let Some(meter) = &self.meter {
+            let _ = meter.shutdown();
+        }
     }
 
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
@@ -43,6 +50,16 @@ impl OtelProvider {
             ])
             .build();
 
+        let meter = if settings.metrics {
+            build_meter_provider(&settings.exporter, resource.clone())?
+        } else {
+            None
+        };
+        if let Some(meter) = &meter {
+            opentelemetry::global::set_meter_provider(meter.clone());
+            crate::metrics::enable_otlp();
+        }
+
         let mut builder = SdkLoggerProvider::builder().with_resource(resource);
 
         match &settings.exporter {
@@ -53,19 +70,10 @@ impl OtelProvider {
             OtelExporter::OtlpGrpc { endpoint, headers } => {
                 debug!("Using OTLP Grpc exporter: {}", endpoint);
 
-                let mut header_map = HeaderMap::new();
-                for (key, value) in headers {
-                    if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
-                        && let Ok(val) = HeaderValue::from_str(value)
-                    {
-                        header_map.insert(name, val);
-                    }
-                }
-
                 let exporter = LogExporter::builder()
                     .with_tonic()
                     .with_endpoint(endpoint)
-                    .with_metadata(MetadataMap::from_headers(header_map))
+                    .with_metadata(grpc_metadata(headers))
                     .build()?;
 
                 builder = builder.with_batch_exporter(exporter);
@@ -77,15 +85,10 @@ impl OtelProvider {
             } => {
                 debug!("Using OTLP Http exporter: {}", endpoint);
 
-                let protocol = match protocol {
-                    OtelHttpProtocol::Binary => Protocol::HttpBinary,
-                    OtelHttpProtocol::Json => Protocol::HttpJson,
-                };
-
                 let exporter = LogExporter::builder()
                     .with_http()
                     .with_endpoint(endpoint)
-                    .with_protocol(protocol)
+                    .with_protocol(http_protocol(protocol))
                     .with_headers(headers.clone())
                     .build()?;
 
@@ -95,6 +98,7 @@ impl OtelProvider {
 
         Ok(Some(Self {
             logger: builder.build(),
+            meter,
         }))
     }
 
@@ -109,7 +113,68 @@ impl OtelProvider {
 
 impl Drop for OtelProvider {
     fn drop(&mut self) {
-        let _ = self.logger.shutdown();
+        self.shutdown();
+    }
+}
+
+fn build_meter_provider(
+    exporter: &OtelExporter,
+    resource: Resource,
+) -> Result<Option<SdkMeterProvider>, Box<dyn Error>> {
+    let exporter = match exporter {
+        OtelExporter::None => {
+            debug!("Metrics export requested without an OTLP exporter.");
+            return Ok(None);
+        }
+        OtelExporter::OtlpGrpc { endpoint, headers } => MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .with_metadata(grpc_metadata(headers))
+            .build()?,
+        OtelExporter::OtlpHttp {
+            endpoint,
+            headers,
+            protocol,
+        } => MetricExporter::builder()
+            .with_http()
+            .with_endpoint(metrics_endpoint(endpoint))
+            .with_protocol(http_protocol(protocol))
+            .with_headers(headers.clone())
+            .build()?,
+    };
+
+    Ok(Some(
+        SdkMeterProvider::builder()
+            .with_resource(resource)
+            .with_periodic_exporter(exporter)
+            .build(),
+    ))
+}
+
+/// OTLP/HTTP endpoints are per signal; reuse a `/v1/logs` endpoint for metrics.
+fn metrics_endpoint(endpoint: &str) -> String {
+    match endpoint.strip_suffix("/v1/logs") {
+        Some(base) => format!("{base}/v1/metrics"),
+        None => endpoint.to_string(),
+    }
+}
+
+fn grpc_metadata(headers: &HashMap<String, String>) -> MetadataMap {
+    let mut header_map = HeaderMap::new();
+    for (key, value) in headers {
+        if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
+            && let Ok(val) = HeaderValue::from_str(value)
+        {
+            header_map.insert(name, val);
+        }
+    }
+    MetadataMap::from_headers(header_map)
+}
+
+fn http_protocol(protocol: &OtelHttpProtocol) -> Protocol {
+    match protocol {
+        OtelHttpProtocol::Binary => Protocol::HttpBinary,
+        OtelHttpProtocol::Json => Protocol::HttpJson,
     }
 }
 
@@ -1,5 +1,13 @@
 mod logging_client_handler;
 mod rmcp_client;
+mod server_log;
 mod utils;
 
+pub use logging_client_handler::McpToolCallProgress;
+pub use rmcp_client::McpCompletion;
+pub use rmcp_client::McpCompletionReference;
 pub use rmcp_client::RmcpClient;
+pub use server_log::DEFAULT_SERVER_LOG_CAPACITY;
+pub use server_log::McpServerLog;
+pub use server_log::McpServerLogLine;
+pub use server_log::McpServerLogSource;
@@ -1,3 +1,9 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
 use rmcp::ClientHandler;
 use rmcp::RoleClient;
 use rmcp::model::CancelledNotificationParam;
@@ -8,22 +14,53 @@ use rmcp::model::ElicitationAction;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
+use rmcp::model::ProgressToken;
 use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::server_log::McpServerLog;
+use crate::server_log::McpServerLogSource;
+
+/// Progress reported by an MCP server while a `tools/call` request is in flight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpToolCallProgress {
+    pub progress: f64,
+    pub total: Option<f64>,
+    /// Partial output or status text attached to the notification, if any.
+    pub message: Option<String>,
+}
+
+/// Listeners for in-flight requests, keyed by the progress token sent in `_meta`.
+pub(crate) type ProgressSinks = Arc<Mutex<HashMap<ProgressToken, UnboundedSender<McpToolCallProgress>>>>;
+
 #[derive(Debug, Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
+    progress_sinks: ProgressSinks,
+    server_log: McpServerLog,
+    /// Set when the server sends `notifications/tools/list_changed`.
+    tools_changed: Arc<AtomicBool>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo) -> Self {
-        Self { client_info }
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        progress_sinks: ProgressSinks,
+        server_log: McpServerLog,
+        tools_changed: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            client_info,
+            progress_sinks,
+            server_log,
+            tools_changed,
+        }
     }
 }
 
@@ -60,10 +97,22 @@ impl ClientHandler for LoggingClientHandler {
         params: ProgressNotificationParam,
         _context: NotificationContext<RoleClient>,
     ) {
-        info!(
+        debug!(
             "MCP server progress notification (token: {:?}, progress: {}, total: {:?}, message: {:?})",
             params.progress_token, params.progress, params.total, params.message
         );
+        let sink = self
+            .progress_sinks
+            .lock()
+            .ok()
+            .and_then(|sinks| sinks.get(&params.progress_token).cloned());
+        if let Some(sink) = sink {
+            let _ = sink.send(McpToolCallProgress {
+                progress: f64::from(params.progress),
+                total: params.total.map(f64::from),
+                message: params.message,
+            });
+        }
     }
 
     async fn on_resource_updated(
@@ -80,6 +129,7 @@ impl ClientHandler for LoggingClientHandler {
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.tools_changed.store(true, Ordering::Release);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
@@ -101,6 +151,19 @@ impl ClientHandler for LoggingClientHandler {
             data,
         } = params;
         let logger = logger.as_deref();
+        let text = match &data {
+            serde_json::Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        let message = match logger {
+            Some(logger) => format!("[{logger}] {text}"),
+            None => text,
+        };
+        self.server_log.push(
+            McpServerLogSource::Notification,
+            Some(format!("{level:?}").to_lowercase()),
+            message,
+        );
         match level {
             LoggingLevel::Emergency
             | LoggingLevel::Alert
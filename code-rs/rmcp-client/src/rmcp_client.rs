@@ -4,6 +4,9 @@ use std::ffi::OsString;
 use std::io;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -16,9 +19,21 @@ use mcp_types::InitializeResult;
 use mcp_types::ListToolsRequestParams;
 use mcp_types::ListToolsResult;
 use mcp_types::MCP_SCHEMA_VERSION;
+use rmcp::model::CallToolRequest;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::CancelledNotification;
+use rmcp::model::CancelledNotificationParam;
+use rmcp::model::ClientNotification;
+use rmcp::model::ClientRequest;
+use rmcp::model::CompleteRequestParam;
 use rmcp::model::InitializeRequestParam;
+use rmcp::model::Meta;
+use rmcp::model::NumberOrString;
 use rmcp::model::PaginatedRequestParam;
+use rmcp::model::ProgressToken;
+use rmcp::model::RequestId;
+use rmcp::model::ServerResult;
+use rmcp::service::PeerRequestOptions;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
 use rmcp::service::{self};
@@ -29,11 +44,16 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::time;
 use tracing::info;
 use tracing::warn;
 
 use crate::logging_client_handler::LoggingClientHandler;
+use crate::logging_client_handler::McpToolCallProgress;
+use crate::logging_client_handler::ProgressSinks;
+use crate::server_log::McpServerLog;
+use crate::server_log::McpServerLogSource;
 use crate::utils::apply_default_headers;
 use crate::utils::build_default_headers;
 use crate::utils::convert_call_tool_result;
@@ -56,10 +76,31 @@ enum ClientState {
     },
 }
 
+/// What an argument completion request refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum McpCompletionReference {
+    /// A prompt, by name.
+    Prompt(String),
+    /// A resource template, by URI template.
+    ResourceTemplate(String),
+}
+
+/// Candidate values returned by `completion/complete`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct McpCompletion {
+    pub values: Vec<String>,
+    /// The server has more candidates than it returned (the spec caps a
+    /// response at 100 values).
+    pub has_more: bool,
+}
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    progress_sinks: ProgressSinks,
+    server_log: McpServerLog,
+    tools_changed: Arc<AtomicBool>,
 }
 
 fn resolve_streamable_http_bearer_token(
@@ -74,6 +115,7 @@ impl RmcpClient {
         program: OsString,
         args: Vec<OsString>,
         env: Option<HashMap<String, String>>,
+        server_log: McpServerLog,
     ) -> io::Result<Self> {
         let program_name = program.to_string_lossy().into_owned();
         let mut last_err: Option<io::Error> = None;
@@ -114,12 +156,14 @@ impl RmcpClient {
             .ok_or_else(|| last_err.unwrap_or_else(|| io::Error::other("failed to spawn rmcp server")))?;
 
         if let Some(stderr) = stderr {
+            let stderr_log = server_log.clone();
             tokio::spawn(async move {
                 let mut reader = BufReader::new(stderr).lines();
                 loop {
                     match reader.next_line().await {
                         Ok(Some(line)) => {
                             info!("MCP server stderr ({program_name}): {line}");
+                            stderr_log.push(McpServerLogSource::Stderr, None, line);
                         }
                         Ok(None) => break,
                         Err(error) => {
@@ -135,6 +179,9 @@ impl RmcpClient {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::ChildProcess(transport)),
             }),
+            progress_sinks: ProgressSinks::default(),
+            server_log,
+            tools_changed: Arc::default(),
         })
     }
 
@@ -144,6 +191,8 @@ impl RmcpClient {
         bearer_token_env_var: Option<String>,
         http_headers: Option<HashMap<String, String>>,
         env_http_headers: Option<HashMap<String, String>>,
+        proxy: Option<reqwest::Proxy>,
+        server_log: McpServerLog,
     ) -> Result<Self> {
         let default_headers = build_default_headers(http_headers, env_http_headers)?;
         let bearer_token =
@@ -154,13 +203,20 @@ impl RmcpClient {
             config = config.auth_header(token);
         }
 
-        let http_client = apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+        let mut builder = apply_default_headers(reqwest::Client::builder(), &default_headers);
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(proxy);
+        }
+        let http_client = builder.build()?;
         let transport = StreamableHttpClientTransport::with_client(http_client, config);
 
         Ok(Self {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(PendingTransport::StreamableHttp(transport)),
             }),
+            progress_sinks: ProgressSinks::default(),
+            server_log,
+            tools_changed: Arc::default(),
         })
     }
 
@@ -184,7 +240,12 @@ impl RmcpClient {
         };
 
         let client_info = convert_to_rmcp::<_, InitializeRequestParam>(params.clone())?;
-        let client_handler = LoggingClientHandler::new(client_info);
+        let client_handler = LoggingClientHandler::new(
+            client_info,
+            Arc::clone(&self.progress_sinks),
+            self.server_log.clone(),
+            Arc::clone(&self.tools_changed),
+        );
         let service_future = match transport {
             PendingTransport::ChildProcess(transport) => {
                 service::serve_client(client_handler.clone(), transport).boxed()
@@ -230,6 +291,12 @@ impl RmcpClient {
         Ok(initialize_result)
     }
 
+    /// Returns whether the server announced a tool list change since the last
+    /// call, clearing the flag.
+    pub fn take_tools_changed(&self) -> bool {
+        self.tools_changed.swap(false, Ordering::AcqRel)
+    }
+
     pub async fn list_tools(
         &self,
         params: Option<ListToolsRequestParams>,
@@ -245,18 +312,129 @@ impl RmcpClient {
         convert_to_mcp(result)
     }
 
+    /// Ask the server for completions of a prompt or resource-template
+    /// argument via `completion/complete`.
+    /// https://modelcontextprotocol.io/specification/2025-06-18/server/utilities/completion
+    pub async fn complete(
+        &self,
+        reference: McpCompletionReference,
+        argument_name: String,
+        argument_value: String,
+        timeout: Option<Duration>,
+    ) -> Result<McpCompletion> {
+        let service = self.service().await?;
+        let reference = match reference {
+            McpCompletionReference::Prompt(name) => {
+                serde_json::json!({ "type": "ref/prompt", "name": name })
+            }
+            McpCompletionReference::ResourceTemplate(uri) => {
+                serde_json::json!({ "type": "ref/resource", "uri": uri })
+            }
+        };
+        let params: CompleteRequestParam = convert_to_rmcp(serde_json::json!({
+            "ref": reference,
+            "argument": { "name": argument_name, "value": argument_value },
+        }))?;
+
+        let fut = service.complete(params);
+        let result = run_with_timeout(fut, timeout, "completion/complete").await?;
+        let completion = serde_json::to_value(result)?
+            .get_mut("completion")
+            .map(serde_json::Value::take)
+            .unwrap_or_default();
+        Ok(McpCompletion {
+            values: completion
+                .get("values")
+                .and_then(serde_json::Value::as_array)
+                .map(|values| {
+                    values
+                        .iter()
+                        .filter_map(|value| value.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            has_more: completion
+                .get("hasMore")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+    ) -> Result<CallToolResult> {
+        self.call_tool_with_progress(name, arguments, timeout, None)
+            .await
+    }
+
+    /// Like [`RmcpClient::call_tool`], but forwards `notifications/progress`
+    /// for this request to `progress_tx` until the call completes.
+    pub async fn call_tool_with_progress(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Value>,
+        timeout: Option<Duration>,
+        progress_tx: Option<UnboundedSender<McpToolCallProgress>>,
     ) -> Result<CallToolResult> {
         let service = self.service().await?;
         let params = CallToolRequestParams { arguments, name };
         let rmcp_params: CallToolRequestParam = convert_to_rmcp(params)?;
-        let fut = service.call_tool(rmcp_params);
-        let rmcp_result = run_with_timeout(fut, timeout, "tools/call").await?;
-        convert_call_tool_result(rmcp_result)
+        let request = ClientRequest::CallToolRequest(CallToolRequest {
+            method: Default::default(),
+            params: rmcp_params,
+            extensions: Default::default(),
+        });
+        // Register the sink under our own token before sending, so progress
+        // the server reports right away is not dropped.
+        let progress_token = next_progress_token();
+        if let Some(progress_tx) = progress_tx
+            && let Ok(mut sinks) = self.progress_sinks.lock()
+        {
+            sinks.insert(progress_token.clone(), progress_tx);
+        }
+        let mut meta = Meta::new();
+        meta.set_progress_token(progress_token.clone());
+        let options = PeerRequestOptions {
+            meta: Some(meta),
+            ..PeerRequestOptions::no_options()
+        };
+        let handle = match service.send_request_with_option(request, options).await {
+            Ok(handle) => handle,
+            Err(err) => {
+                if let Ok(mut sinks) = self.progress_sinks.lock() {
+                    sinks.remove(&progress_token);
+                }
+                return Err(anyhow!("tools/call failed: {err}"));
+            }
+        };
+        let mut in_flight = InFlightToolCall {
+            service: Arc::clone(&service),
+            request_id: handle.id.clone(),
+            progress_token,
+            progress_sinks: Arc::clone(&self.progress_sinks),
+            cancel_reason: Some("cancelled by the client".to_string()),
+        };
+
+        let response = match timeout {
+            Some(duration) => match time::timeout(duration, handle.await_response()).await {
+                Ok(response) => response,
+                Err(_) => {
+                    in_flight.cancel_reason = Some(format!("timed out after {duration:?}"));
+                    return Err(anyhow!("timed out awaiting tools/call after {duration:?}"));
+                }
+            },
+            None => handle.await_response().await,
+        };
+        in_flight.cancel_reason = None;
+        drop(in_flight);
+
+        match response.map_err(|err| anyhow!("tools/call failed: {err}"))? {
+            ServerResult::CallToolResult(result) => convert_call_tool_result(result),
+            other => Err(anyhow!("tools/call returned an unexpected response: {other:?}")),
+        }
     }
 
     async fn service(&self) -> Result<Arc<RunningService<RoleClient, LoggingClientHandler>>> {
@@ -274,6 +452,55 @@ impl RmcpClient {
     }
 }
 
+/// Cleans up after a `tools/call` request when it settles or is abandoned.
+///
+/// If the call has not completed when this is dropped (it timed out, or the
+/// caller dropped the future because the user interrupted the turn), the
+/// server is sent `notifications/cancelled` so it can stop the work instead of
+/// running a hung tool to completion.
+struct InFlightToolCall {
+    service: Arc<RunningService<RoleClient, LoggingClientHandler>>,
+    request_id: RequestId,
+    progress_token: ProgressToken,
+    progress_sinks: ProgressSinks,
+    cancel_reason: Option<String>,
+}
+
+impl Drop for InFlightToolCall {
+    fn drop(&mut self) {
+        if let Ok(mut sinks) = self.progress_sinks.lock() {
+            sinks.remove(&self.progress_token);
+        }
+        let Some(reason) = self.cancel_reason.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let service = Arc::clone(&self.service);
+        let notification = ClientNotification::CancelledNotification(CancelledNotification {
+            method: Default::default(),
+            params: CancelledNotificationParam {
+                request_id: self.request_id.clone(),
+                reason: Some(reason),
+            },
+            extensions: Default::default(),
+        });
+        runtime.spawn(async move {
+            if let Err(err) = service.send_notification(notification).await {
+                warn!("failed to send notifications/cancelled: {err}");
+            }
+        });
+    }
+}
+
+/// Progress token unique to one `tools/call` from this process.
+fn next_progress_token() -> ProgressToken {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    ProgressToken(NumberOrString::String(format!("code-tool-call-{id}").into()))
+}
+
 fn handshake_failed_error(err: impl Into<anyhow::Error>) -> anyhow::Error {
     let err = err.into();
     anyhow!(
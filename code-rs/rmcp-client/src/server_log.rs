@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Number of lines retained per server unless overridden.
+pub const DEFAULT_SERVER_LOG_CAPACITY: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McpServerLogSource {
+    /// A line the server process wrote to stderr.
+    Stderr,
+    /// A `notifications/message` logging notification.
+    Notification,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpServerLogLine {
+    pub at: SystemTime,
+    pub source: McpServerLogSource,
+    /// Logging level reported by the server (notifications only).
+    pub level: Option<String>,
+    pub message: String,
+}
+
+/// Bounded, shareable buffer of recent output from a single MCP server.
+///
+/// The buffer outlives the client so startup failures can still be inspected.
+#[derive(Debug, Clone)]
+pub struct McpServerLog {
+    lines: Arc<Mutex<VecDeque<McpServerLogLine>>>,
+    capacity: usize,
+}
+
+impl Default for McpServerLog {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_SERVER_LOG_CAPACITY)
+    }
+}
+
+impl McpServerLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, source: McpServerLogSource, level: Option<String>, message: String) {
+        let Ok(mut lines) = self.lines.lock() else {
+            return;
+        };
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(McpServerLogLine {
+            at: SystemTime::now(),
+            source,
+            level,
+            message,
+        });
+    }
+
+    /// Returns the retained lines, oldest first.
+    pub fn snapshot(&self) -> Vec<McpServerLogLine> {
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_oldest_lines_past_capacity() {
+        let log = McpServerLog::with_capacity(2);
+        for idx in 0..3 {
+            log.push(McpServerLogSource::Stderr, None, format!("line {idx}"));
+        }
+        let messages: Vec<String> = log.snapshot().into_iter().map(|line| line.message).collect();
+        assert_eq!(messages, vec!["line 1".to_string(), "line 2".to_string()]);
+    }
+}
@@ -111,6 +111,8 @@ impl App<'_> {
 
         'main: loop {
             let event = match self.next_event_priority() { Some(e) => e, None => break 'main };
+            // Show the state the previous event left behind.
+            self.sync_terminal_activity();
             match event {
                 AppEvent::InsertHistory(mut lines) => match &mut self.app_state {
                     AppState::Chat { widget } => {
@@ -143,7 +145,8 @@ impl App<'_> {
                         // which we will mirror to scrollback in the handler above.
                         let to_mirror = lines.clone();
                         widget.insert_history_lines_with_kind(kind, id, lines);
-                        if !self.alt_screen_active {
+                        // Screen-reader mode writes finished items itself.
+                        if !self.alt_screen_active && !crate::screen_reader::enabled() {
                             use std::io::stdout;
                             let width = terminal.size().map(|s| s.width).unwrap_or(80);
                             let reserve = widget.desired_bottom_height(width).max(1);
@@ -160,7 +163,7 @@ impl App<'_> {
                         tracing::debug!("app: InsertFinalAnswer id={:?} lines={} source_len={}", id, lines.len(), source.len());
                         let to_mirror = lines.clone();
                         widget.insert_final_answer_with_id(id, lines, source);
-                        if !self.alt_screen_active {
+                        if !self.alt_screen_active && !crate::screen_reader::enabled() {
                             use std::io::stdout;
                             let width = terminal.size().map(|s| s.width).unwrap_or(80);
                             let reserve = widget.desired_bottom_height(width).max(1);
@@ -499,17 +502,17 @@ impl App<'_> {
                         }
                         KeyEvent {
                             code: KeyCode::Char('r'),
-                            modifiers: crossterm::event::KeyModifiers::CONTROL,
+                            modifiers: crossterm::event::KeyModifiers::ALT,
                             kind: KeyEventKind::Press,
                             ..
                         }
                         | KeyEvent {
                             code: KeyCode::Char('r'),
-                            modifiers: crossterm::event::KeyModifiers::CONTROL,
+                            modifiers: crossterm::event::KeyModifiers::ALT,
                             kind: KeyEventKind::Repeat,
                             ..
                         } => {
-                            // Toggle reasoning/thinking visibility (Ctrl+R)
+                            // Toggle reasoning/thinking visibility (Alt+R; Ctrl+R searches history)
                             match &mut self.app_state {
                                 AppState::Chat { widget } => {
                                     widget.toggle_reasoning_visibility();
@@ -601,6 +604,11 @@ impl App<'_> {
                     }
                     self.app_event_tx.send(AppEvent::RequestRedraw);
                 }
+                AppEvent::PersistentHistoryLoaded { log_id, entries } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.seed_persistent_history(log_id, entries);
+                    }
+                }
                 AppEvent::RegisterPastedImage { placeholder, path } => {
                     if let AppState::Chat { widget } = &mut self.app_state {
                         widget.register_pasted_image(placeholder, path);
@@ -1223,6 +1231,21 @@ impl App<'_> {
                                 widget.handle_login_command();
                             }
                         }
+                        SlashCommand::Account => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_account_command(command_args);
+                            }
+                        }
+                        SlashCommand::Profile => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_profile_command(command_args);
+                            }
+                        }
+                        SlashCommand::Auth => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_auth_command(command_args);
+                            }
+                        }
                         SlashCommand::Logout => {
                             if let Err(e) = code_login::logout(&self.config.code_home) { tracing::error!("failed to logout: {e}"); }
                             break 'main;
@@ -1269,7 +1292,17 @@ impl App<'_> {
                         }
                         SlashCommand::Status => {
                             if let AppState::Chat { widget } = &mut self.app_state {
-                                widget.add_status_output();
+                                widget.handle_status_command(command_args);
+                            }
+                        }
+                        SlashCommand::Outline => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_outline_command(command_args);
+                            }
+                        }
+                        SlashCommand::Stats => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.add_stats_output();
                             }
                         }
                         SlashCommand::Limits => {
@@ -1310,6 +1343,11 @@ impl App<'_> {
                                 widget.handle_mcp_command(command_args);
                             }
                         }
+                        SlashCommand::Tools => {
+                            if let AppState::Chat { widget } = &mut self.app_state {
+                                widget.handle_tools_command();
+                            }
+                        }
                         SlashCommand::Model => {
                             if let AppState::Chat { widget } = &mut self.app_state {
                                 if command_args.trim().is_empty() {
@@ -2224,6 +2262,11 @@ impl App<'_> {
                 AppEvent::LoginUsingChatGptChanged { using_chatgpt_auth } => {
                     self.handle_login_mode_change(using_chatgpt_auth);
                 }
+                AppEvent::AuthRefreshFailed { message } => {
+                    if let AppState::Chat { widget } = &mut self.app_state {
+                        widget.handle_auth_refresh_failed(message);
+                    }
+                }
                 AppEvent::OnboardingAuthComplete(result) => {
                     if let AppState::Onboarding { screen } = &mut self.app_state {
                         screen.on_auth_complete(result);
@@ -2441,6 +2484,7 @@ impl App<'_> {
                 }
             }
         }
+        self.clear_terminal_progress();
         if self.alt_screen_active {
             terminal.clear()?;
         }
@@ -1,5 +1,5 @@
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::channel;
 use std::sync::Arc;
@@ -105,6 +105,14 @@ impl App<'_> {
                 });
             }
         }
+        if !crate::chatwidget::is_test_mode() {
+            let refresh_tx = app_event_tx.clone();
+            auth_manager.spawn_proactive_refresh(move |err| {
+                refresh_tx.send(AppEvent::AuthRefreshFailed {
+                    message: err.to_string(),
+                });
+            });
+        }
         let pending_redraw = Arc::new(AtomicBool::new(false));
         let redraw_inflight = Arc::new(AtomicBool::new(false));
         let post_frame_redraw = Arc::new(AtomicBool::new(false));
@@ -261,8 +269,9 @@ impl App<'_> {
         }
 
         let login_status = get_login_status(&config);
-        let should_show_onboarding =
-            should_show_onboarding(login_status, &config, show_trust_screen);
+        let show_first_run_setup = should_show_first_run_setup(&config);
+        let should_show_onboarding = show_first_run_setup
+            || should_show_onboarding(login_status, &config, show_trust_screen);
         let app_state = if should_show_onboarding {
             let show_login_screen = should_show_login_screen(login_status, &config);
             let chat_widget_args = ChatWidgetArgs {
@@ -283,6 +292,7 @@ impl App<'_> {
                     cwd: config.cwd.clone(),
                     show_trust_screen,
                     show_login_screen,
+                    show_first_run_setup,
                     chat_widget_args,
                     login_status,
                 }),
@@ -314,6 +324,9 @@ impl App<'_> {
 
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
         let start_in_alt = config.tui.alternate_screen;
+        let terminal_progress = crate::terminal_activity::progress_enabled(
+            config.tui.terminal_progress,
+        );
         Self {
             _server: conversation_manager,
             app_event_tx,
@@ -348,6 +361,8 @@ impl App<'_> {
             alt_screen_active: start_in_alt,
             terminal_runs: HashMap::new(),
             terminal_title_override: None,
+            terminal_activity: TerminalActivity::default(),
+            terminal_progress,
             login_flow: None,
             #[cfg(unix)]
             sigterm_guard,
@@ -368,6 +383,12 @@ fn should_show_onboarding(
     matches!(login_status, crate::LoginStatus::NotAuthenticated)
 }
 
+/// First launch: no `config.toml` in CODE_HOME (or the legacy CODEX_HOME).
+fn should_show_first_run_setup(config: &Config) -> bool {
+    !code_core::config::resolve_code_path_for_read(&config.code_home, Path::new("config.toml"))
+        .exists()
+}
+
 fn should_show_login_screen(login_status: crate::LoginStatus, _config: &Config) -> bool {
     matches!(login_status, crate::LoginStatus::NotAuthenticated)
 }
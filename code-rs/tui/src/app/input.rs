@@ -41,7 +41,10 @@ impl App<'_> {
 
     pub(super) fn dispatch_code_event(&mut self, event: Event) {
         match &mut self.app_state {
-            AppState::Chat { widget } => widget.handle_code_event(event),
+            AppState::Chat { widget } => {
+                widget.handle_code_event(event);
+                widget.flush_screen_reader_transcript();
+            }
             AppState::Onboarding { .. } => {}
         }
     }
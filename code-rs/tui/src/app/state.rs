@@ -17,6 +17,7 @@ use crate::app_event_sender::AppEventSender;
 use crate::chatwidget::{ChatWidget, GhostState};
 use crate::file_search::FileSearchManager;
 use crate::history::state::HistorySnapshot;
+use crate::terminal_activity::TerminalActivity;
 use crate::onboarding::onboarding_screen::OnboardingScreen;
 use crate::thread_spawner;
 use crate::tui::TerminalInfo;
@@ -277,6 +278,10 @@ pub(crate) struct App<'a> {
     pub(super) terminal_runs: HashMap<u64, TerminalRunState>,
 
     pub(super) terminal_title_override: Option<String>,
+    /// Session state last shown in the title and taskbar progress.
+    pub(super) terminal_activity: TerminalActivity,
+    /// Whether `OSC 9;4` progress is emitted (`tui.terminal_progress`).
+    pub(super) terminal_progress: bool,
     pub(super) login_flow: Option<LoginFlowState>,
 }
 
@@ -10,6 +10,7 @@ use shlex::try_join;
 use color_eyre::eyre::Result;
 
 use crate::app_event::{AppEvent, TerminalRunController, TerminalRunEvent};
+use crate::terminal_activity::{self, TerminalActivity};
 use crate::tui;
 
 use super::state::{
@@ -22,16 +23,39 @@ use super::state::{
 
 impl App<'_> {
     pub(super) fn apply_terminal_title(&self) {
-        let title = self
+        let base = self
             .terminal_title_override
             .as_deref()
             .unwrap_or(Self::DEFAULT_TERMINAL_TITLE);
         let _ = crossterm::execute!(
             std::io::stdout(),
-            crossterm::terminal::SetTitle(title.to_string())
+            crossterm::terminal::SetTitle(self.terminal_activity.title(base))
         );
     }
 
+    /// Reflect the chat's current state in the title and taskbar progress.
+    pub(super) fn sync_terminal_activity(&mut self) {
+        let activity = match &self.app_state {
+            AppState::Chat { widget } => widget.terminal_activity(),
+            AppState::Onboarding { .. } => TerminalActivity::Idle,
+        };
+        if activity == self.terminal_activity {
+            return;
+        }
+        self.terminal_activity = activity;
+        self.apply_terminal_title();
+        if self.terminal_progress {
+            terminal_activity::emit_progress(&self.terminal_activity);
+        }
+    }
+
+    /// Remove the taskbar progress indicator before exiting.
+    pub(super) fn clear_terminal_progress(&self) {
+        if self.terminal_progress && self.terminal_activity != TerminalActivity::Idle {
+            terminal_activity::emit_progress(&TerminalActivity::Idle);
+        }
+    }
+
     fn sanitize_notification_text(input: &str) -> String {
         let mut sanitized = String::with_capacity(input.len());
         for ch in input.chars() {
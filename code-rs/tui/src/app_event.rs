@@ -592,6 +592,8 @@ pub(crate) enum AppEvent {
     LoginDeviceCodeComplete { result: Result<(), String> },
     /// The active authentication mode changed (e.g., switched accounts).
     LoginUsingChatGptChanged { using_chatgpt_auth: bool },
+    /// The background token refresh failed for good; the user must sign in again.
+    AuthRefreshFailed { message: String },
 
     /// Show Chrome launch options dialog
     #[allow(dead_code)]
@@ -619,6 +621,10 @@ pub(crate) enum AppEvent {
     /// so ChatWidget can resolve it to a LocalImage on submit.
     RegisterPastedImage { placeholder: String, path: PathBuf },
 
+    /// This project's persistent prompt history, loaded in the background for
+    /// Ctrl+R search.
+    PersistentHistoryLoaded { log_id: u64, entries: Vec<String> },
+
     /// Immediately cancel any running task in the ChatWidget. This is used by
     /// the approval modal to reflect a user's Abort decision instantly in the UI
     /// (clear spinner/status, finalize running exec/tool cells) while the core
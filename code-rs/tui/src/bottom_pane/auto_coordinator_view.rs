@@ -4,6 +4,7 @@ use crate::auto_drive_strings;
 use crate::auto_drive_style::{AutoDriveStyle, AutoDriveVariant, FrameStyle, BorderGradient};
 use crate::colors;
 use crate::glitch_animation::{gradient_multi, mix_rgb};
+use crate::i18n::tr;
 use crate::spinner;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::buffer::Buffer;
@@ -364,7 +365,7 @@ impl AutoCoordinatorView {
     }
 
     fn is_generic_status_message(message: &str) -> bool {
-        matches!(message, "Auto Drive" | "Auto Drive Goal")
+        message == tr("status.auto_drive") || message == tr("status.auto_drive_goal")
     }
 
     fn resolve_display_message(&self, model: &AutoActiveViewModel) -> String {
@@ -4,6 +4,7 @@ use ratatui::style::{Color, Modifier, Style, Stylize};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, StatefulWidgetRef, WidgetRef};
 use code_core::config_types::ContextMode;
+use code_core::keymap::KeyAction;
 use code_core::protocol::AutoContextPhase;
 use code_core::protocol::TokenUsage;
 use crossterm::event::KeyCode;
@@ -24,6 +25,7 @@ use code_core::model_family::EXTENDED_CONTEXT_WINDOW_1M;
 use crate::app_event_sender::AppEventSender;
 use crate::auto_drive_style::{BorderGradient, ComposerStyle};
 use crate::chatwidget::AutoReviewIndicatorStatus;
+use crate::i18n::tr;
 use crate::thread_spawner;
 use crate::bottom_pane::textarea::TextArea;
 use crate::bottom_pane::textarea::TextAreaState;
@@ -49,6 +51,18 @@ struct PostPasteSpaceGuard {
     cursor_pos: usize,
 }
 
+/// Reverse-i-search state while Ctrl+R is active. The composer previews the
+/// selected match; the footer shows the query.
+struct HistorySearch {
+    query: String,
+    /// Draft restored when the search is cancelled.
+    original: String,
+    /// Entries matching `query`, best first.
+    matches: Vec<String>,
+    /// Index of the previewed match; Ctrl+R steps to the next one.
+    selected: usize,
+}
+
 fn parse_slash_name(line: &str) -> Option<(&str, &str)> {
     let stripped = line.strip_prefix('/')?;
     let mut name_end = stripped.len();
@@ -179,6 +193,10 @@ pub(crate) struct ChatComposer {
     // Animation thread for spinning icon when task is running
     animation_running: Option<Arc<AtomicBool>>,
     using_chatgpt_auth: bool,
+    // Config profile shown in the footer while one is active
+    active_profile: Option<String>,
+    // Usage limit warning shown in the footer at 80% and above
+    usage_quota_notice: Option<String>,
     custom_prompts: Vec<CustomPrompt>,
     // Ephemeral footer notice and its expiry
     footer_notice: Option<(String, std::time::Instant)>,
@@ -203,6 +221,7 @@ pub(crate) struct ChatComposer {
     // Detect and coalesce paste bursts for smoother UX
     paste_burst: PasteBurst,
     post_paste_space_guard: Option<PostPasteSpaceGuard>,
+    history_search: Option<HistorySearch>,
     footer_hint_override: Option<Vec<(String, String)>>,
     embedded_mode: bool,
     render_mode: ComposerRenderMode,
@@ -267,6 +286,8 @@ impl ChatComposer {
             status_message: String::from("coding"),
             animation_running: None,
             using_chatgpt_auth,
+            active_profile: None,
+            usage_quota_notice: None,
             custom_prompts: Vec::new(),
             footer_notice: None,
             standard_terminal_hint: None,
@@ -281,6 +302,7 @@ impl ChatComposer {
             next_down_scrolls_history: false,
             paste_burst: PasteBurst::default(),
             post_paste_space_guard: None,
+            history_search: None,
             footer_hint_override: None,
             embedded_mode: false,
             render_mode: ComposerRenderMode::Full,
@@ -293,6 +315,14 @@ impl ChatComposer {
         self.using_chatgpt_auth = using;
     }
 
+    pub(crate) fn set_active_profile(&mut self, profile: Option<String>) {
+        self.active_profile = profile;
+    }
+
+    pub(crate) fn set_usage_quota_notice(&mut self, notice: Option<String>) {
+        self.usage_quota_notice = notice;
+    }
+
     pub(crate) fn set_auto_review_status(&mut self, status: Option<AutoReviewFooterStatus>) {
         self.auto_review_status = status;
     }
@@ -515,14 +545,14 @@ impl ChatComposer {
         if lower.contains("auto review") {
             let cleaned = technical_message.trim();
             if cleaned.is_empty() {
-                "Auto Review".to_string()
+                tr("status.auto_review").to_string()
             } else {
                 cleaned.to_string()
             }
         } else if lower.contains("auto drive goal") {
-            "Auto Drive Goal".to_string()
+            tr("status.auto_drive_goal").to_string()
         } else if lower.contains("auto drive") {
-            "Auto Drive".to_string()
+            tr("status.auto_drive").to_string()
         }
         // Thinking/reasoning patterns
         else if lower.contains("reasoning")
@@ -531,7 +561,7 @@ impl ChatComposer {
             || lower.contains("waiting for model")
             || lower.contains("model")
         {
-            "Thinking".to_string()
+            tr("status.thinking").to_string()
         }
         // Tool/command execution patterns
         else if lower.contains("tool")
@@ -541,7 +571,7 @@ impl ChatComposer {
             || lower.contains("bash")
             || lower.contains("shell")
         {
-            "Using tools".to_string()
+            tr("status.using_tools").to_string()
         }
         // Browser activity
         else if lower.contains("browser")
@@ -551,7 +581,7 @@ impl ChatComposer {
             || lower.contains("url")
             || lower.contains("screenshot")
         {
-            "Browsing".to_string()
+            tr("status.browsing").to_string()
         }
         // Multi-agent orchestration
         else if lower.contains("agent")
@@ -559,7 +589,7 @@ impl ChatComposer {
             || lower.contains("orchestrating")
             || lower.contains("coordinating")
         {
-            "Agents".to_string()
+            tr("status.agents").to_string()
         }
         // Response generation patterns
         else if lower.contains("generating")
@@ -570,7 +600,7 @@ impl ChatComposer {
             || lower.contains("chat completions")
             || lower.contains("completion")
         {
-            "Responding".to_string()
+            tr("status.responding").to_string()
         }
         // Transient network/stream retry patterns → keep spinner visible with a
         // clear reconnecting message so the user knows we are still working.
@@ -584,7 +614,7 @@ impl ChatComposer {
             || lower.contains("network")
             || lower.contains("connection")
         {
-            "Reconnecting".to_string()
+            tr("status.reconnecting").to_string()
         }
         // File/code editing patterns
         else if lower.contains("editing")
@@ -594,18 +624,18 @@ impl ChatComposer {
             || lower.contains("updating")
             || lower.contains("patch")
         {
-            "Coding".to_string()
+            tr("status.coding").to_string()
         }
         // Catch some common technical terms
         else if lower.contains("processing") || lower.contains("analyzing") {
-            "Thinking".to_string()
+            tr("status.thinking").to_string()
         } else if lower == "search" || lower.contains("searching") {
-            "Searching".to_string()
+            tr("status.searching").to_string()
         } else if lower.contains("reading") {
-            "Reading".to_string()
+            tr("status.reading").to_string()
         } else {
             // Default fallback - use "working" for unknown status
-            "Working".to_string()
+            tr("status.working").to_string()
         }
     }
 
@@ -710,6 +740,97 @@ impl ChatComposer {
         self.history.set_metadata(log_id, entry_count);
     }
 
+    /// Seed reverse search with this project's persistent history.
+    pub(crate) fn seed_persistent_history(&mut self, log_id: u64, entries: Vec<String>) {
+        self.history.seed_persistent_entries(log_id, entries);
+    }
+
+    pub(crate) fn history_search_active(&self) -> bool {
+        self.history_search.is_some()
+    }
+
+    /// Leave reverse search and restore the draft it started from. Returns
+    /// true if a search was active.
+    pub(crate) fn cancel_history_search(&mut self) -> bool {
+        let Some(search) = self.history_search.take() else {
+            return false;
+        };
+        self.textarea.set_text(&search.original);
+        self.textarea.set_cursor(search.original.len());
+        true
+    }
+
+    fn start_history_search(&mut self) {
+        self.history.reset_navigation();
+        self.history_search = Some(HistorySearch {
+            query: String::new(),
+            original: self.textarea.text().to_string(),
+            matches: Vec::new(),
+            selected: 0,
+        });
+    }
+
+    /// Keep the previewed match as an editable draft.
+    fn accept_history_search(&mut self) {
+        if self.history_search.take().is_some() {
+            self.textarea.set_cursor(self.textarea.text().len());
+            self.typed_anything = true;
+        }
+    }
+
+    /// Route a key while reverse search is active. Returns `None` when the key
+    /// accepted the match and should then be handled as ordinary input.
+    fn handle_history_search_key(&mut self, key_event: KeyEvent) -> Option<(InputResult, bool)> {
+        if key_event.kind == KeyEventKind::Release {
+            return Some((InputResult::None, false));
+        }
+        let search = self.history_search.as_mut()?;
+        let plain = !key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key_event.code {
+            KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => {
+                if search.selected + 1 < search.matches.len() {
+                    search.selected += 1;
+                }
+            }
+            KeyCode::Char(c) if plain => {
+                search.query.push(c);
+                search.matches = self.history.search(&search.query);
+                search.selected = 0;
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                search.matches = if search.query.is_empty() {
+                    Vec::new()
+                } else {
+                    self.history.search(&search.query)
+                };
+                search.selected = 0;
+            }
+            KeyCode::Esc => {
+                self.cancel_history_search();
+                return Some((InputResult::None, true));
+            }
+            KeyCode::Enter => {
+                self.accept_history_search();
+                return Some((InputResult::None, true));
+            }
+            _ => {
+                self.accept_history_search();
+                return None;
+            }
+        }
+        let preview = search
+            .matches
+            .get(search.selected)
+            .unwrap_or(&search.original)
+            .clone();
+        self.textarea.set_text(&preview);
+        self.textarea.set_cursor(preview.len());
+        Some((InputResult::None, true))
+    }
+
     /// Integrate an asynchronous response to an on-demand history lookup. If
     /// the entry is present and the offset matches the current cursor we
     /// immediately populate the textarea.
@@ -1027,6 +1148,24 @@ impl ChatComposer {
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
         let now = Instant::now();
 
+        if self.history_search.is_some() {
+            if let Some(result) = self.handle_history_search_key(key_event) {
+                return result;
+            }
+        } else if matches!(
+            key_event,
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            }
+        ) && matches!(self.active_popup, ActivePopup::None)
+        {
+            self.start_history_search();
+            return (InputResult::None, true);
+        }
+
         // Track rapid plain-character bursts (common when bracketed paste is
         // unavailable) so we can suppress Enter-based submissions and insert
         // literal newlines instead.
@@ -1126,11 +1265,9 @@ impl ChatComposer {
         };
 
         match key_event {
-            // Allow Shift+Up to navigate history even when slash popup is active.
-            KeyEvent { code: KeyCode::Up, modifiers, .. } => {
-                if modifiers.contains(KeyModifiers::SHIFT) {
-                    return self.handle_key_event_without_popup(key_event);
-                }
+            // Allow history navigation even when the slash popup is active.
+            key if Self::is_history_key(&key) => self.handle_key_event_without_popup(key),
+            KeyEvent { code: KeyCode::Up, .. } => {
                 // If there are 0 or 1 items, let Up behave normally (cursor/history/scroll)
                 if popup.match_count() <= 1 {
                     return self.handle_key_event_without_popup(key_event);
@@ -1138,11 +1275,7 @@ impl ChatComposer {
                 popup.move_up();
                 (InputResult::None, true)
             }
-            // Allow Shift+Down to navigate history even when slash popup is active.
-            KeyEvent { code: KeyCode::Down, modifiers, .. } => {
-                if modifiers.contains(KeyModifiers::SHIFT) {
-                    return self.handle_key_event_without_popup(key_event);
-                }
+            KeyEvent { code: KeyCode::Down, .. } => {
                 // If there are 0 or 1 items, let Down behave normally (cursor/history/scroll)
                 if popup.match_count() <= 1 {
                     return self.handle_key_event_without_popup(key_event);
@@ -1247,8 +1380,7 @@ impl ChatComposer {
                                     .starts_with(&format!("/{}", name));
                                 if starts_with {
                                     self.active_popup = ActivePopup::None;
-                                    return self
-                                        .handle_key_event_without_popup(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+                                    return self.submit_input();
                                 }
                                 self.textarea.set_text(&format!("/{} ", name));
                                 let new_cursor = self.textarea.text().len();
@@ -1299,10 +1431,8 @@ impl ChatComposer {
         };
 
         match key_event {
-            KeyEvent { code: KeyCode::Up, modifiers, .. } => {
-                if modifiers.contains(KeyModifiers::SHIFT) {
-                    return self.handle_key_event_without_popup(key_event);
-                }
+            key if Self::is_history_key(&key) => self.handle_key_event_without_popup(key),
+            KeyEvent { code: KeyCode::Up, .. } => {
                 // If there are 0 or 1 items, let Up behave normally (cursor/history/scroll)
                 if popup.match_count() <= 1 {
                     return self.handle_key_event_without_popup(key_event);
@@ -1310,10 +1440,7 @@ impl ChatComposer {
                 popup.move_up();
                 (InputResult::None, true)
             }
-            KeyEvent { code: KeyCode::Down, modifiers, .. } => {
-                if modifiers.contains(KeyModifiers::SHIFT) {
-                    return self.handle_key_event_without_popup(key_event);
-                }
+            KeyEvent { code: KeyCode::Down, .. } => {
                 // If there are 0 or 1 items, let Down behave normally (cursor/history/scroll)
                 if popup.match_count() <= 1 {
                     return self.handle_key_event_without_popup(key_event);
@@ -1606,6 +1733,11 @@ impl ChatComposer {
         self.textarea.set_cursor(new_cursor);
     }
 
+    fn is_history_key(key_event: &KeyEvent) -> bool {
+        crate::keymap::matches(KeyAction::HistoryPrevious, key_event)
+            || crate::keymap::matches(KeyAction::HistoryNext, key_event)
+    }
+
     /// Handle key event when no popup is visible.
     fn handle_key_event_without_popup(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
         match key_event {
@@ -1676,135 +1808,147 @@ impl ChatComposer {
                 (InputResult::None, false)
             }
             // -------------------------------------------------------------
-            // Up/Down key handling - check modifiers to determine action
+            // Prompt history ([keys] history_previous/history_next, Shift+Up/Down)
+            // -------------------------------------------------------------
+            key if Self::is_history_key(&key) => {
+                if self
+                    .history
+                    .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
+                {
+                    let replace_text = if crate::keymap::matches(KeyAction::HistoryPrevious, &key) {
+                        self.history.navigate_up(self.textarea.text(), &self.app_event_tx)
+                    } else {
+                        self.history.navigate_down(&self.app_event_tx)
+                    };
+                    if let Some(text) = replace_text {
+                        self.textarea.set_text(&text);
+                        self.textarea.set_cursor(0);
+                        return (InputResult::None, true);
+                    }
+                }
+                // If history navigation didn't happen, just ignore the key
+                (InputResult::None, false)
+            }
+            // -------------------------------------------------------------
+            // Up/Down — move cursor within the input first.
+            // Only when already at the top-left/bottom-right should Up/Down scroll chat.
             // -------------------------------------------------------------
             KeyEvent {
                 code: KeyCode::Up | KeyCode::Down,
-                modifiers,
                 ..
             } => {
-                // Check if Shift is held for history navigation
-                if modifiers.contains(KeyModifiers::SHIFT) {
-                    // History navigation with Shift+Up/Down
-                    if self
-                        .history
-                        .should_handle_navigation(self.textarea.text(), self.textarea.cursor())
-                    {
-                        let replace_text = match key_event.code {
-                            KeyCode::Up => self
-                                .history
-                                .navigate_up(self.textarea.text(), &self.app_event_tx),
-                            KeyCode::Down => self.history.navigate_down(&self.app_event_tx),
-                            _ => None,
-                        };
-                        if let Some(text) = replace_text {
-                            self.textarea.set_text(&text);
-                            self.textarea.set_cursor(0);
-                            return (InputResult::None, true);
+                if self.textarea.is_empty() {
+                    return match key_event.code {
+                        KeyCode::Up => (InputResult::ScrollUp, false),
+                        KeyCode::Down => (InputResult::ScrollDown, false),
+                        _ => (InputResult::None, false),
+                    };
+                }
+
+                let before = self.textarea.cursor();
+                let len = self.textarea.text().len();
+                match key_event.code {
+                    KeyCode::Up => {
+                        if before == 0 {
+                            (InputResult::ScrollUp, false)
+                        } else {
+                            // Move up a visual/logical line; if already on first line, TextArea moves to start.
+                            self.textarea.input(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+                            (InputResult::None, true)
                         }
                     }
-                    // If history navigation didn't happen, just ignore the key
-                    (InputResult::None, false)
-                } else {
-                    // No Shift modifier — move cursor within the input first.
-                    // Only when already at the top-left/bottom-right should Up/Down scroll chat.
-                    if self.textarea.is_empty() {
-                        return match key_event.code {
-                            KeyCode::Up => (InputResult::ScrollUp, false),
-                            KeyCode::Down => (InputResult::ScrollDown, false),
-                            _ => (InputResult::None, false),
-                        };
-                    }
-
-                    let before = self.textarea.cursor();
-                    let len = self.textarea.text().len();
-                    match key_event.code {
-                        KeyCode::Up => {
-                            if before == 0 {
-                                (InputResult::ScrollUp, false)
-                            } else {
-                                // Move up a visual/logical line; if already on first line, TextArea moves to start.
-                                self.textarea.input(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
-                                (InputResult::None, true)
-                            }
+                    KeyCode::Down => {
+                        // If sticky is set, prefer chat ScrollDown once
+                        if self.next_down_scrolls_history {
+                            self.next_down_scrolls_history = false;
+                            return (InputResult::ScrollDown, false);
                         }
-                        KeyCode::Down => {
-                            // If sticky is set, prefer chat ScrollDown once
-                            if self.next_down_scrolls_history {
-                                self.next_down_scrolls_history = false;
-                                return (InputResult::ScrollDown, false);
-                            }
-                            if before == len {
-                                (InputResult::ScrollDown, false)
-                            } else {
-                                // Move down a visual/logical line; if already on last line, TextArea moves to end.
-                                self.textarea.input(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-                                (InputResult::None, true)
-                            }
+                        if before == len {
+                            (InputResult::ScrollDown, false)
+                        } else {
+                            // Move down a visual/logical line; if already on last line, TextArea moves to end.
+                            self.textarea.input(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+                            (InputResult::None, true)
                         }
-                        _ => (InputResult::None, false),
                     }
+                    _ => (InputResult::None, false),
                 }
             }
+            // -------------------------------------------------------------
+            // Newline ([keys] newline, Shift+Enter / Ctrl+J by default)
+            // -------------------------------------------------------------
             KeyEvent {
-                code: KeyCode::Enter,
-                modifiers: KeyModifiers::NONE,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
-            } => {
-                if self.handle_backslash_continuation() {
-                    return (InputResult::None, true);
-                }
-                let command_text = self.textarea.text().to_string();
-                let first_line = command_text.lines().next().unwrap_or("");
-                if let Some((name, rest)) = parse_slash_name(first_line)
-                    && rest.is_empty()
-                    && let Some((_label, cmd)) = built_in_slash_commands()
-                        .into_iter()
-                        .find(|(n, _)| *n == name)
-                {
-                    if cmd.is_prompt_expanding() {
-                        self.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
-                    }
-                    self.history.record_local_submission(&command_text);
-                    self.app_event_tx
-                        .send(crate::app_event::AppEvent::DispatchCommand(cmd, command_text));
-                    self.textarea.set_text("");
-                    self.active_popup = ActivePopup::None;
-                    return (InputResult::Command(cmd), true);
-                }
+            } if crate::keymap::matches(KeyAction::Newline, &key_event) => {
+                self.handle_input_basic(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT))
+            }
+            // -------------------------------------------------------------
+            // Submit ([keys] submit, Enter by default)
+            // -------------------------------------------------------------
+            KeyEvent {
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if crate::keymap::matches(KeyAction::Submit, &key_event) => {
+                self.submit_input()
+            }
+            input => self.handle_input_basic(input),
+        }
+    }
 
-                // Record the exact text that was typed (before replacement)
-                let original_text = self.textarea.text().to_string();
+    /// Submit the composer contents, dispatching a built-in slash command
+    /// when the whole input is one.
+    fn submit_input(&mut self) -> (InputResult, bool) {
+        if self.handle_backslash_continuation() {
+            return (InputResult::None, true);
+        }
+        let command_text = self.textarea.text().to_string();
+        let first_line = command_text.lines().next().unwrap_or("");
+        if let Some((name, rest)) = parse_slash_name(first_line)
+            && rest.is_empty()
+            && let Some((_label, cmd)) = built_in_slash_commands()
+                .into_iter()
+                .find(|(n, _)| *n == name)
+        {
+            if cmd.is_prompt_expanding() {
+                self.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
+            }
+            self.history.record_local_submission(&command_text);
+            self.app_event_tx
+                .send(crate::app_event::AppEvent::DispatchCommand(cmd, command_text));
+            self.textarea.set_text("");
+            self.active_popup = ActivePopup::None;
+            return (InputResult::Command(cmd), true);
+        }
 
-                let mut text = self.textarea.text().to_string();
-                self.textarea.set_text("");
+        // Record the exact text that was typed (before replacement)
+        let original_text = self.textarea.text().to_string();
 
-                // Replace all pending pastes in the text
-                for (placeholder, actual) in &self.pending_pastes {
-                    if text.contains(placeholder) {
-                        text = text.replace(placeholder, actual);
-                    }
-                }
-                self.pending_pastes.clear();
+        let mut text = self.textarea.text().to_string();
+        self.textarea.set_text("");
 
-                if text.is_empty() {
-                    (InputResult::None, true)
-                } else {
-                    // Check if this is a prompt-expanding command that will trigger agents
-                    let trimmed = original_text.trim();
-                    if trimmed.starts_with("/plan ")
-                        || trimmed.starts_with("/solve ")
-                        || trimmed.starts_with("/code ")
-                    {
-                        self.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
-                    }
+        // Replace all pending pastes in the text
+        for (placeholder, actual) in &self.pending_pastes {
+            if text.contains(placeholder) {
+                text = text.replace(placeholder, actual);
+            }
+        }
+        self.pending_pastes.clear();
 
-                    self.history.record_local_submission(&original_text);
-                    (InputResult::Submitted(text), true)
-                }
+        if text.is_empty() {
+            (InputResult::None, true)
+        } else {
+            // Check if this is a prompt-expanding command that will trigger agents
+            let trimmed = original_text.trim();
+            if trimmed.starts_with("/plan ")
+                || trimmed.starts_with("/solve ")
+                || trimmed.starts_with("/code ")
+            {
+                self.app_event_tx.send(crate::app_event::AppEvent::PrepareAgents);
             }
-            input => self.handle_input_basic(input),
+
+            self.history.record_local_submission(&original_text);
+            (InputResult::Submitted(text), true)
         }
     }
 
@@ -2357,6 +2501,28 @@ impl ChatComposer {
                 let key_hint_style = Style::default().fg(crate::colors::function());
                 let label_style = Style::default().fg(crate::colors::text_dim());
 
+                if let Some(search) = &self.history_search {
+                    let status = match search.matches.len() {
+                        _ if search.query.is_empty() => String::new(),
+                        0 => " (no match)".to_string(),
+                        total => format!(" ({}/{total})", search.selected + 1),
+                    };
+                    Line::from(vec![
+                        Span::from("  reverse-i-search: ").style(label_style),
+                        Span::from(search.query.clone()),
+                        Span::from(status).style(label_style),
+                        Span::from("   "),
+                        Span::from("Ctrl+R").style(key_hint_style),
+                        Span::from(" next  ").style(label_style),
+                        Span::from("Enter").style(key_hint_style),
+                        Span::from(" accept  ").style(label_style),
+                        Span::from("Esc").style(key_hint_style),
+                        Span::from(" cancel").style(label_style),
+                    ])
+                    .render_ref(area, buf);
+                    return;
+                }
+
                 if let Some(hints) = &self.footer_hint_override {
                     let mut left_spans: Vec<Span<'static>> = vec![Span::from("  ")];
                     for (idx, (key, label)) in hints.iter().enumerate() {
@@ -2549,6 +2715,22 @@ impl ChatComposer {
                 // Tokens placeholder (actual spans chosen later)
                 right_sections.push((1, Vec::new(), include_tokens));
 
+                // Usage limit warning (priority 2)
+                if let Some(notice) = &self.usage_quota_notice {
+                    let warning_style = Style::default().fg(crate::colors::warning());
+                    let notice_spans = vec![Span::from(notice.clone()).style(warning_style)];
+                    right_sections.push((2, notice_spans, true));
+                }
+
+                // Active profile (priority 7)
+                if let Some(profile) = &self.active_profile {
+                    right_sections.push((
+                        7,
+                        vec![Span::from(format!("Profile: {profile}")).style(label_style)],
+                        true,
+                    ));
+                }
+
                 // Auth label (priority 7)
                 if !self.using_chatgpt_auth {
                     right_sections.push((7, vec![Span::from("API key").style(label_style)], true));
@@ -3297,4 +3479,35 @@ mod tests {
             "Working".to_string()
         );
     }
+
+    #[test]
+    fn ctrl_r_searches_history_and_esc_restores_draft() {
+        let (tx, _rx) = std::sync::mpsc::channel::<AppEvent>();
+        let mut composer = ChatComposer::new(true, AppEventSender::new(tx), true, false);
+        composer.set_history_metadata(1, 2);
+        composer.seed_persistent_history(
+            1,
+            vec!["run the migrations".to_string(), "review the diff".to_string()],
+        );
+        composer.set_text_content("draft".to_string());
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let ctrl_r = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
+
+        composer.handle_key_event(ctrl_r);
+        assert!(composer.history_search_active());
+        for c in "rev".chars() {
+            composer.handle_key_event(key(KeyCode::Char(c)));
+        }
+        assert_eq!(composer.text(), "review the diff");
+        composer.handle_key_event(key(KeyCode::Esc));
+        assert_eq!(composer.text(), "draft");
+
+        composer.handle_key_event(ctrl_r);
+        composer.handle_key_event(key(KeyCode::Char('r')));
+        composer.handle_key_event(ctrl_r);
+        assert_eq!(composer.text(), "run the migrations");
+        composer.handle_key_event(key(KeyCode::Enter));
+        assert!(!composer.history_search_active());
+        assert_eq!(composer.text(), "run the migrations");
+    }
 }
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use code_common::fuzzy_match::fuzzy_match;
 use code_core::protocol::Op;
 
 /// State machine that manages shell-style history navigation (Up/Down) inside
@@ -185,6 +187,35 @@ impl ChatComposerHistory {
         }
     }
 
+    /// Seed the cache with this project's persistent entries (oldest first) so
+    /// reverse search can scan them without a lookup per offset. Entries
+    /// appended by other sessions after startup are ignored to keep offsets
+    /// aligned with `history_entry_count`.
+    pub fn seed_persistent_entries(&mut self, log_id: u64, entries: Vec<String>) {
+        if self.history_log_id != Some(log_id) {
+            return;
+        }
+        let count = self.history_entry_count;
+        self.fetched_history
+            .extend(entries.into_iter().take(count).enumerate());
+    }
+
+    /// Entries fuzzily matching `query`, best score first and most recent
+    /// first among equal scores. Repeated prompts are listed once.
+    pub fn search(&self, query: &str) -> Vec<String> {
+        let persistent = (0..self.history_entry_count)
+            .filter_map(|idx| self.fetched_history.get(&idx));
+        let mut seen = HashSet::new();
+        let mut matches: Vec<(i32, &String)> = persistent
+            .chain(self.local_history.iter())
+            .rev()
+            .filter(|text| seen.insert(text.as_str()))
+            .filter_map(|text| fuzzy_match(text, query).map(|(_, score)| (score, text)))
+            .collect();
+        matches.sort_by_key(|(score, _)| *score);
+        matches.into_iter().map(|(_, text)| text.clone()).collect()
+    }
+
     /// Integrate a GetHistoryEntryResponse event.
     pub fn on_entry_response(
         &mut self,
@@ -237,3 +268,36 @@ impl ChatComposerHistory {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn search_ranks_tight_matches_then_recency() {
+        let mut history = ChatComposerHistory::new();
+        history.set_metadata(7, 3);
+        history.seed_persistent_entries(
+            7,
+            vec![
+                "fix the flaky login test".to_string(),
+                "format the code".to_string(),
+                "fix the build".to_string(),
+                "appended later by another session".to_string(),
+            ],
+        );
+        history.record_local_submission("refactor the fixture");
+        history.record_local_submission("fix the build");
+
+        assert_eq!(
+            history.search("fix"),
+            vec![
+                "fix the build".to_string(),
+                "fix the flaky login test".to_string(),
+                "refactor the fixture".to_string(),
+            ]
+        );
+        assert_eq!(history.search("later"), Vec::<String>::new());
+        assert_eq!(history.search("fmt code"), vec!["format the code".to_string()]);
+    }
+}
@@ -32,6 +32,7 @@ pub(crate) struct CustomPromptView {
     on_submit: PromptSubmitted,
     app_event_tx: AppEventSender,
     on_escape: Option<SelectionAction>,
+    masked: bool,
 
     // UI state
     textarea: TextArea,
@@ -55,11 +56,18 @@ impl CustomPromptView {
             on_submit,
             app_event_tx,
             on_escape,
+            masked: false,
             textarea: TextArea::new(),
             textarea_state: RefCell::new(TextAreaState::default()),
             complete: false,
         }
     }
+
+    /// Show the input as bullets, for secrets such as API keys.
+    pub(crate) fn with_masked_input(mut self) -> Self {
+        self.masked = true;
+        self
+    }
 }
 
 impl BottomPaneView<'_> for CustomPromptView {
@@ -85,7 +93,9 @@ impl BottomPaneView<'_> for CustomPromptView {
                 code: KeyCode::Enter,
                 ..
             } => {
-                self.textarea.input(key_event);
+                if !self.masked {
+                    self.textarea.input(key_event);
+                }
             }
             other => {
                 self.textarea.input(other);
@@ -178,8 +188,18 @@ impl BottomPaneView<'_> for CustomPromptView {
                     width: input_area.width.saturating_sub(2),
                     height: text_area_height,
                 };
-                let mut state = self.textarea_state.borrow_mut();
-                StatefulWidgetRef::render_ref(&(&self.textarea), textarea_rect, buf, &mut state);
+                if self.masked {
+                    let bullets = "•".repeat(self.textarea.text().chars().count());
+                    Paragraph::new(Line::from(bullets)).render(textarea_rect, buf);
+                } else {
+                    let mut state = self.textarea_state.borrow_mut();
+                    StatefulWidgetRef::render_ref(
+                        &(&self.textarea),
+                        textarea_rect,
+                        buf,
+                        &mut state,
+                    );
+                }
                 if self.textarea.text().is_empty() {
                     Paragraph::new(Line::from(self.placeholder.clone().dim()))
                         .render(textarea_rect, buf);
@@ -223,6 +243,9 @@ impl BottomPaneView<'_> for CustomPromptView {
 
 impl CustomPromptView {
     fn input_height(&self, width: u16) -> u16 {
+        if self.masked {
+            return 2;
+        }
         let usable_width = width.saturating_sub(2);
         let text_height = self.textarea.desired_height(usable_width).clamp(1, 8);
         text_height.saturating_add(1).min(9)
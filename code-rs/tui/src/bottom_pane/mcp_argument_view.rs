@@ -0,0 +1,250 @@
+//! Entry for one argument of an MCP prompt or resource template. Each edit
+//! asks the server for `completion/complete` candidates, which are listed
+//! under the input; the accepted value is inserted into the composer.
+
+use code_core::protocol::McpCompletionRef;
+use code_core::protocol::McpCompletionResponseEvent;
+use code_core::protocol::Op;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+use super::BottomPane;
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::bottom_pane_view::ConditionalUpdate;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::render_rows;
+
+pub(crate) struct McpArgumentView {
+    server: String,
+    reference: McpCompletionRef,
+    argument: String,
+    value: String,
+    candidates: Vec<String>,
+    has_more: bool,
+    error: Option<String>,
+    state: ScrollState,
+    app_event_tx: AppEventSender,
+    complete: bool,
+}
+
+impl McpArgumentView {
+    /// Opens the entry with `value` typed and requests its completions.
+    pub(crate) fn new(
+        server: String,
+        reference: McpCompletionRef,
+        argument: String,
+        value: String,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let view = Self {
+            server,
+            reference,
+            argument,
+            value,
+            candidates: Vec::new(),
+            has_more: false,
+            error: None,
+            state: ScrollState::new(),
+            app_event_tx,
+            complete: false,
+        };
+        view.request_completions();
+        view
+    }
+
+    /// Shows the candidates in `ev` if they answer the text typed now;
+    /// replies to earlier keystrokes are dropped.
+    pub(crate) fn apply_completions(&mut self, ev: &McpCompletionResponseEvent) -> bool {
+        if ev.server != self.server || ev.argument != self.argument || ev.value != self.value {
+            return false;
+        }
+        self.candidates = ev.values.clone();
+        self.has_more = ev.has_more;
+        self.error = ev.error.clone();
+        self.state.reset();
+        if !self.candidates.is_empty() {
+            self.state.selected_idx = Some(0);
+        }
+        true
+    }
+
+    fn request_completions(&self) {
+        self.app_event_tx.send(AppEvent::CodexOp(Op::McpCompleteArgument {
+            server: self.server.clone(),
+            reference: self.reference.clone(),
+            argument: self.argument.clone(),
+            value: self.value.clone(),
+        }));
+    }
+
+    fn set_value(&mut self, value: String) {
+        if value != self.value {
+            self.value = value;
+            self.request_completions();
+        }
+    }
+
+    fn selected(&self) -> Option<&String> {
+        self.state
+            .selected_idx
+            .and_then(|index| self.candidates.get(index))
+    }
+
+    fn accept(&mut self) {
+        let value = self.selected().cloned().unwrap_or_else(|| self.value.clone());
+        if value.is_empty() {
+            return;
+        }
+        self.app_event_tx.send(AppEvent::PrefillComposer(value));
+        self.complete = true;
+    }
+
+    fn target_label(&self) -> String {
+        match &self.reference {
+            McpCompletionRef::Prompt { name } => format!("prompt {name}"),
+            McpCompletionRef::Resource { uri } => format!("resource {uri}"),
+        }
+    }
+
+    fn list_rows(&self) -> u16 {
+        self.candidates.len().clamp(1, MAX_POPUP_ROWS) as u16
+    }
+}
+
+impl BottomPaneView<'_> for McpArgumentView {
+    fn as_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
+        Some(self)
+    }
+
+    fn handle_key_event(&mut self, _pane: &mut BottomPane<'_>, key_event: KeyEvent) {
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return;
+        }
+        match key_event.code {
+            KeyCode::Esc => self.complete = true,
+            KeyCode::Enter => self.accept(),
+            KeyCode::Tab => {
+                if let Some(candidate) = self.selected().cloned() {
+                    self.set_value(candidate);
+                }
+            }
+            KeyCode::Up => self.state.move_up_wrap(self.candidates.len()),
+            KeyCode::Down => self.state.move_down_wrap(self.candidates.len()),
+            KeyCode::Backspace => {
+                let mut value = self.value.clone();
+                value.pop();
+                self.set_value(value);
+            }
+            KeyCode::Char(ch)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.set_value(format!("{}{ch}", self.value));
+            }
+            _ => {}
+        }
+        self.state
+            .ensure_visible(self.candidates.len(), MAX_POPUP_ROWS);
+    }
+
+    fn on_ctrl_c(&mut self, _pane: &mut BottomPane<'_>) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn handle_paste(&mut self, text: String) -> ConditionalUpdate {
+        let text = text.trim_end_matches(['\r', '\n']);
+        if text.is_empty() {
+            return ConditionalUpdate::NoRedraw;
+        }
+        self.set_value(format!("{}{text}", self.value));
+        ConditionalUpdate::NeedsRedraw
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        // Title, input, candidates, hint.
+        1 + 1 + self.list_rows() + 1
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        Clear.render(area, buf);
+        let row = |offset: u16, height: u16| Rect {
+            x: area.x,
+            y: area.y.saturating_add(offset),
+            width: area.width,
+            height: height.min(area.height.saturating_sub(offset)),
+        };
+
+        let title = Line::from(vec![
+            gutter(),
+            self.argument.clone().bold(),
+            format!(" for {} on {}", self.target_label(), self.server).dim(),
+        ]);
+        Paragraph::new(title).render(row(0, 1), buf);
+
+        let input = if self.value.is_empty() {
+            Line::from(vec![gutter(), "type to ask the server for values".dim()])
+        } else {
+            Line::from(vec![gutter(), Span::raw(self.value.clone())])
+        };
+        Paragraph::new(input).render(row(1, 1), buf);
+
+        let list_rows = self.list_rows();
+        let list_area = row(2, list_rows);
+        if let Some(error) = &self.error {
+            Paragraph::new(Line::styled(
+                error.clone(),
+                Style::default().fg(crate::colors::error()),
+            ))
+            .render(list_area, buf);
+        } else {
+            let rows: Vec<GenericDisplayRow> = self
+                .candidates
+                .iter()
+                .map(|candidate| GenericDisplayRow {
+                    name: candidate.clone(),
+                    match_indices: None,
+                    is_current: false,
+                    description: None,
+                    name_color: None,
+                })
+                .collect();
+            render_rows(list_area, buf, &rows, &self.state, MAX_POPUP_ROWS, false);
+        }
+
+        let mut hint = "Enter insert · Tab complete · Esc cancel".to_string();
+        if self.has_more {
+            hint.push_str(" · more available, keep typing to narrow");
+        }
+        Paragraph::new(Line::from(hint.dim())).render(row(2 + list_rows, 1), buf);
+    }
+}
+
+fn gutter() -> Span<'static> {
+    "▌ ".cyan()
+}
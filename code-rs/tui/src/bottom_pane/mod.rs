@@ -12,6 +12,7 @@ pub(crate) use bottom_pane_view::ConditionalUpdate;
 use crate::util::buffer::fill_rect;
 use code_protocol::custom_prompts::CustomPrompt;
 use code_protocol::skills::Skill;
+use code_core::protocol::McpCompletionResponseEvent;
 use code_core::protocol::TokenUsage;
 use code_file_search::FileMatch;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
@@ -44,6 +45,8 @@ mod selection_popup_common;
 pub mod list_selection_view;
 pub(crate) use list_selection_view::SelectionAction;
 pub(crate) use custom_prompt_view::CustomPromptView;
+mod mcp_argument_view;
+pub(crate) use mcp_argument_view::McpArgumentView;
 mod cloud_tasks_view;
 pub(crate) use cloud_tasks_view::CloudTasksView;
 pub mod resume_selection_view;
@@ -119,6 +122,7 @@ pub(crate) use request_user_input_view::RequestUserInputView;
 enum ActiveViewKind {
     None,
     AutoCoordinator,
+    Approval,
     Other,
 }
 
@@ -296,6 +300,16 @@ impl BottomPane<'_> {
         }
     }
 
+    pub(crate) fn set_active_profile(&mut self, profile: Option<String>) {
+        self.composer.set_active_profile(profile);
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_usage_quota_notice(&mut self, notice: Option<String>) {
+        self.composer.set_usage_quota_notice(notice);
+        self.request_redraw();
+    }
+
     #[allow(dead_code)]
     pub(crate) fn has_active_view(&self) -> bool {
         self.active_view.is_some()
@@ -603,6 +617,16 @@ impl BottomPane<'_> {
         self.request_redraw();
     }
 
+    /// The current status indicator text, if any.
+    pub(crate) fn status_message(&self) -> Option<&str> {
+        self.composer.status_message()
+    }
+
+    /// Whether an approval modal is waiting for a decision.
+    pub(crate) fn has_pending_approval(&self) -> bool {
+        self.active_view.is_some() && self.active_view_kind == ActiveViewKind::Approval
+    }
+
     /// Show an ephemeral footer notice for a custom duration.
     pub(crate) fn flash_footer_notice_for(&mut self, text: String, dur: Duration) {
         self.composer.flash_footer_notice_for(text, dur);
@@ -740,7 +764,7 @@ impl BottomPane<'_> {
         // Otherwise create a new approval modal overlay.
         let modal = ApprovalModalView::new(request, ticket, self.app_event_tx.clone());
         self.active_view = Some(Box::new(modal));
-        self.active_view_kind = ActiveViewKind::Other;
+        self.active_view_kind = ActiveViewKind::Approval;
         // Hide any overlay status while a modal is visible.
         // Status shown in composer title now
         self.status_view_active = false;
@@ -824,6 +848,13 @@ impl BottomPane<'_> {
         self.request_redraw();
     }
 
+    pub(crate) fn show_mcp_argument(&mut self, view: McpArgumentView) {
+        self.active_view = Some(Box::new(view));
+        self.active_view_kind = ActiveViewKind::Other;
+        self.status_view_active = false;
+        self.request_redraw();
+    }
+
     pub(crate) fn show_request_user_input(&mut self, view: RequestUserInputView) {
         self.active_view = Some(Box::new(view));
         self.active_view_kind = ActiveViewKind::Other;
@@ -1005,6 +1036,22 @@ impl BottomPane<'_> {
         self.request_redraw();
     }
 
+    pub(crate) fn update_mcp_completions(&mut self, ev: &McpCompletionResponseEvent) {
+        let Some(view) = self.active_view.as_mut() else {
+            return;
+        };
+        let Some(argument_view) = view
+            .as_any_mut()
+            .and_then(|any| any.downcast_mut::<McpArgumentView>())
+        else {
+            return;
+        };
+
+        if argument_view.apply_completions(ev) {
+            self.request_redraw();
+        }
+    }
+
     // Immediate redraw path removed; all UI updates flow through the
     // debounced RequestRedraw/App::Redraw scheduler to reduce thrash.
 
@@ -1016,7 +1063,7 @@ impl BottomPane<'_> {
         self.request_redraw();
     }
 
-    /// Control footer hint visibility: whether to show Ctrl+R (reasoning) and Ctrl+D (diffs)
+    /// Control footer hint visibility: whether to show Alt+R (reasoning) and Ctrl+D (diffs)
     #[allow(dead_code)]
     pub(crate) fn set_footer_hints(&mut self, show_reasoning: bool, show_diffs: bool) {
         self.composer.set_show_reasoning_hint(show_reasoning);
@@ -1050,6 +1097,23 @@ impl BottomPane<'_> {
         self.composer.set_history_metadata(log_id, entry_count);
     }
 
+    pub(crate) fn seed_persistent_history(&mut self, log_id: u64, entries: Vec<String>) {
+        self.composer.seed_persistent_history(log_id, entries);
+    }
+
+    pub(crate) fn history_search_active(&self) -> bool {
+        self.composer.history_search_active()
+    }
+
+    /// Leave Ctrl+R history search, restoring the draft. Returns true if it was active.
+    pub(crate) fn cancel_history_search(&mut self) -> bool {
+        let cancelled = self.composer.cancel_history_search();
+        if cancelled {
+            self.request_redraw();
+        }
+        cancelled
+    }
+
     #[allow(dead_code)]
     pub(crate) fn flush_paste_burst_if_due(&mut self) -> bool {
         self.composer.flush_paste_burst_if_due()
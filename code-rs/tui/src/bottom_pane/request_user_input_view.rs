@@ -116,6 +116,35 @@ impl RequestUserInputView {
         }
     }
 
+    fn has_secret_question(&self) -> bool {
+        self.questions.iter().any(|q| q.is_secret)
+    }
+
+    /// Close the picker. Prompts with a secret never fall back to the
+    /// composer, which would show the answer in clear text; they are answered
+    /// with empty values instead, which the caller treats as a cancel.
+    fn dismiss(&mut self) {
+        if !self.has_secret_question() {
+            self.complete = true;
+            return;
+        }
+        let answers = self
+            .questions
+            .iter()
+            .map(|question| {
+                let answer = RequestUserInputAnswer {
+                    answers: vec![String::new()],
+                };
+                (question.id.clone(), answer)
+            })
+            .collect();
+        self.app_event_tx.send(AppEvent::RequestUserInputAnswer {
+            turn_id: self.turn_id.clone(),
+            response: RequestUserInputResponse { answers },
+        });
+        self.submitting = true;
+    }
+
     fn current_has_options(&self) -> bool {
         self.current_options_len() > 0
     }
@@ -246,8 +275,8 @@ impl BottomPaneView<'_> for RequestUserInputView {
 
         match key_event.code {
             KeyCode::Esc => {
-                // Close this UI and fall back to the composer for manual input.
-                self.complete = true;
+                // Close this UI; plain prompts fall back to the composer.
+                self.dismiss();
             }
             KeyCode::Enter => {
                 self.go_next_or_submit();
@@ -295,7 +324,7 @@ impl BottomPaneView<'_> for RequestUserInputView {
         if self.submitting {
             return CancellationEvent::Handled;
         }
-        self.complete = true;
+        self.dismiss();
         CancellationEvent::Handled
     }
 
@@ -523,19 +552,24 @@ impl BottomPaneView<'_> for RequestUserInputView {
         let footer_y = inner.y.saturating_add(inner.height).saturating_sub(1);
         let is_last = question_count > 0 && self.current_idx + 1 >= question_count;
         let enter_label = if is_last { "submit" } else { "next" };
+        let esc_label = if self.has_secret_question() {
+            "cancel"
+        } else {
+            "type in composer"
+        };
         let footer = if has_options {
             if accepts_freeform {
                 format!(
-                    "↑/↓ select | Type other answer | Enter {enter_label} | Esc type in composer | PgUp/PgDn prev/next"
+                    "↑/↓ select | Type other answer | Enter {enter_label} | Esc {esc_label} | PgUp/PgDn prev/next"
                 )
             } else {
                 format!(
-                    "↑/↓ select | Enter {enter_label} | Esc type in composer | PgUp/PgDn prev/next"
+                    "↑/↓ select | Enter {enter_label} | Esc {esc_label} | PgUp/PgDn prev/next"
                 )
             }
         } else {
             format!(
-                "Type answer | Enter {enter_label} | Esc type in composer | PgUp/PgDn prev/next"
+                "Type answer | Enter {enter_label} | Esc {esc_label} | PgUp/PgDn prev/next"
             )
         };
         Paragraph::new(Line::from(vec![Span::raw(footer)]).dim()).render(
@@ -549,3 +583,66 @@ impl BottomPaneView<'_> for RequestUserInputView {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::mpsc::channel;
+
+    fn question(id: &str, is_secret: bool) -> RequestUserInputQuestion {
+        RequestUserInputQuestion {
+            id: id.to_string(),
+            header: id.to_string(),
+            question: format!("{id}?"),
+            is_other: false,
+            is_secret,
+            options: None,
+        }
+    }
+
+    #[test]
+    fn dismissing_a_secret_prompt_cancels_instead_of_using_the_composer() {
+        let (tx, rx) = channel();
+        let questions = vec![question("username", false), question("password", true)];
+        let mut view =
+            RequestUserInputView::new("turn".to_string(), questions, AppEventSender::new(tx));
+        view.push_freeform_char('u');
+
+        view.dismiss();
+
+        assert!(!view.complete, "the composer must not take over a secret prompt");
+        let Ok(AppEvent::RequestUserInputAnswer { turn_id, response }) = rx.try_recv() else {
+            panic!("expected a cancel answer");
+        };
+        assert_eq!(turn_id, "turn");
+        let mut answers: Vec<(String, Vec<String>)> = response
+            .answers
+            .into_iter()
+            .map(|(id, answer)| (id, answer.answers))
+            .collect();
+        answers.sort();
+        assert_eq!(
+            answers,
+            vec![
+                ("password".to_string(), vec![String::new()]),
+                ("username".to_string(), vec![String::new()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn dismissing_a_plain_prompt_falls_back_to_the_composer() {
+        let (tx, rx) = channel();
+        let mut view = RequestUserInputView::new(
+            "turn".to_string(),
+            vec![question("name", false)],
+            AppEventSender::new(tx),
+        );
+
+        view.dismiss();
+
+        assert!(view.complete);
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -35,13 +35,17 @@ use code_core::agent_defaults::{agent_model_spec, enabled_agent_model_specs};
 use code_core::smoke_test_agent_blocking;
 use code_core::config::Config;
 use code_core::git_info::CommitLogEntry;
+use code_core::keymap::KeyAction;
 use code_core::config_types::AgentConfig;
 use code_core::config_types::AutoDriveContinueMode;
 use code_core::config_types::AutoDriveModelRoutingEntry;
 use code_core::config_types::ContextMode;
 use code_core::config_types::Notifications;
 use code_core::config_types::ReasoningEffort;
+use code_core::usage_quota;
+use code_core::usage_quota::QuotaStatus;
 use code_core::config_types::ServiceTier;
+use code_core::config_types::StatusBarSegment;
 use code_core::config_types::TextVerbosity;
 use code_core::spawn::spawn_std_command_with_retry;
 use code_core::plan_tool::{PlanItemArg, StepStatus, UpdatePlanArgs};
@@ -61,6 +65,7 @@ use code_login::AuthManager;
 use code_login::AuthMode;
 use code_protocol::dynamic_tools::DynamicToolResponse;
 use code_protocol::protocol::SessionSource;
+use code_protocol::num_format::format_si_suffix;
 use code_protocol::num_format::format_with_separators_u64;
 use code_core::external_agent_command_exists;
 use code_core::split_command_and_args;
@@ -95,6 +100,8 @@ mod web_search_sessions;
 mod auto_drive_cards;
 pub(crate) mod tool_cards;
 mod running_tools;
+mod session_stats;
+mod status_bar;
 #[cfg(any(test, feature = "test-helpers"))]
 pub mod smoke_helpers;
 
@@ -223,6 +230,7 @@ use crate::bottom_pane::{
 use crate::bottom_pane::agents_settings_view::SubagentEditorView;
 use crate::bottom_pane::mcp_settings_view::{McpServerRow, McpServerRows};
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::terminal_activity::TerminalActivity;
 #[cfg(feature = "code-fork")]
 use crate::tui_event_extensions::handle_browser_screenshot;
 use crate::chatwidget::message::UserMessage;
@@ -873,10 +881,6 @@ use serde_json;
 use tracing::{debug, info, warn};
 // use image::GenericImageView;
 
-const TOKENS_PER_MILLION: f64 = 1_000_000.0;
-const INPUT_COST_PER_MILLION_USD: f64 = 1.25;
-const CACHED_INPUT_COST_PER_MILLION_USD: f64 = 0.125;
-const OUTPUT_COST_PER_MILLION_USD: f64 = 10.0;
 const STATUS_LABEL_INDENT: &str = "   ";
 const STATUS_LABEL_TARGET_WIDTH: usize = 7;
 const STATUS_LABEL_GAP: usize = 2;
@@ -1015,6 +1019,7 @@ use crate::streaming::StreamKind;
 use crate::streaming::controller::AppEventHistorySink;
 use crate::util::buffer::fill_rect;
 use crate::user_approval_widget::ApprovalRequest;
+use crate::exec_output::Utf8StreamDecoder;
 use code_ansi_escape::ansi_escape_line;
 pub(crate) use self::terminal::{
     PendingCommand,
@@ -1103,6 +1108,8 @@ struct RunningCommand {
     explore_entry: Option<(usize, usize)>,
     stdout_offset: usize,
     stderr_offset: usize,
+    stdout_decoder: Utf8StreamDecoder,
+    stderr_decoder: Utf8StreamDecoder,
     wait_total: Option<Duration>,
     wait_active: bool,
     wait_notes: Vec<(String, bool)>,
@@ -1894,6 +1901,7 @@ pub(crate) struct ChatWidget<'a> {
     reconnect_notice_active: bool,
     initial_user_message: Option<UserMessage>,
     total_token_usage: TokenUsage,
+    session_stats: session_stats::SessionStats,
     last_token_usage: TokenUsage,
     session_requested_model: Option<String>,
     session_latest_response_model: Option<String>,
@@ -1905,6 +1913,8 @@ pub(crate) struct ChatWidget<'a> {
     rate_limit_secondary_next_reset_at: Option<DateTime<Utc>>,
     rate_limit_refresh_scheduled_for: Option<DateTime<Utc>>,
     rate_limit_refresh_schedule_id: Arc<AtomicU64>,
+    // Set once the user chose to send past a reached usage limit
+    usage_quota_confirmed: bool,
     content_buffer: String,
     // Buffer for streaming assistant answer text; we do not surface partial
     // We wait for the final AgentMessage event and then emit the full text
@@ -1946,11 +1956,15 @@ pub(crate) struct ChatWidget<'a> {
     browser_overlay_state: BrowserOverlayState,
     // Store pending image paths keyed by their placeholder text
     pending_images: HashMap<String, PathBuf>,
+    // Set after Ctrl+X so a following Ctrl+E opens the external editor.
+    external_editor_prefix: bool,
     // (removed) pending non-image files are no longer tracked; non-image paths remain as plain text
     welcome_shown: bool,
     test_mode: bool,
     // Path to the latest browser screenshot and URL for display
     latest_browser_screenshot: Arc<Mutex<Option<(PathBuf, String)>>>,
+    // URL whose screenshot was last shown inline in history (outside agent browser sessions)
+    browser_inline_screenshot_url: Option<String>,
     browser_autofix_requested: Arc<AtomicBool>,
     // Cached image protocol to avoid recreating every frame (path, area, protocol)
     cached_image_protocol:
@@ -1996,6 +2010,8 @@ pub(crate) struct ChatWidget<'a> {
     pending_auto_turn_config: Option<TurnConfig>,
     overall_task_status: String,
     active_plan_title: Option<String>,
+    /// Completed share of the active plan's steps, for the taskbar progress.
+    active_plan_percent: Option<u8>,
     /// Runtime timing per-agent (by id) to improve visibility in the HUD
     agent_runtime: HashMap<String, AgentRuntime>,
     agent_runtime_pruned_total: u64,
@@ -2153,6 +2169,8 @@ pub(crate) struct ChatWidget<'a> {
     /// When true, render without the top status bar and HUD so the normal
     /// terminal scrollback remains usable (Ctrl+T standard terminal mode).
     pub(crate) standard_terminal_mode: bool,
+    /// History items already written to the scrollback in screen-reader mode.
+    screen_reader_written: HashSet<HistoryId>,
     // Pending system notes to append to persistent history.
     pending_agent_notes: Vec<String>,
 
@@ -2385,7 +2403,7 @@ impl AgentTerminalEntry {
             return;
         }
         self.logs.push(AgentLogEntry {
-            timestamp: Local::now(),
+            timestamp: crate::deterministic::now_local(),
             kind,
             message: msg,
         });
@@ -6498,6 +6516,27 @@ impl ChatWidget<'_> {
 
     // Removed: pending insert sequencing is not used under strict ordering.
 
+    /// Read this project's prompt history off the UI thread so Ctrl+R can
+    /// search it; the composer is seeded when `PersistentHistoryLoaded` arrives.
+    fn load_persistent_history(&self, log_id: u64, entry_count: usize) {
+        if entry_count == 0 {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let config = self.config.clone();
+        let tx = self.app_event_tx.clone();
+        handle.spawn_blocking(move || {
+            let entries = code_core::load_project_history(&config);
+            tx.send(AppEvent::PersistentHistoryLoaded { log_id, entries });
+        });
+    }
+
+    pub(crate) fn seed_persistent_history(&mut self, log_id: u64, entries: Vec<String>) {
+        self.bottom_pane.seed_persistent_history(log_id, entries);
+    }
+
     pub(crate) fn register_pasted_image(&mut self, placeholder: String, path: std::path::PathBuf) {
         let persisted = self
             .persist_user_image_if_needed(&path)
@@ -6691,6 +6730,20 @@ impl ChatWidget<'_> {
 
         // Non-image paths are left as-is in the text; the model may choose to read them.
 
+        // Text-only models reject image inputs outright, so keep only the
+        // `[image: ...]` markers and tell the user why nothing was attached.
+        if !self.config.model_family.supports_image_input {
+            let before = ordered_items.len();
+            ordered_items.retain(|item| !matches!(item, InputItem::LocalImage { .. }));
+            let dropped = before - ordered_items.len();
+            if dropped > 0 {
+                self.push_background_tail(format!(
+                    "{} does not accept image input; sent {dropped} image placeholder(s) as text.",
+                    self.config.model
+                ));
+            }
+        }
+
         // Preserve user formatting (retain newlines) but normalize whitespace:
         // - Normalize CRLF -> LF
         // - Trim trailing spaces per line
@@ -7000,6 +7053,7 @@ impl ChatWidget<'_> {
                 initial_images,
             ),
             total_token_usage: TokenUsage::default(),
+            session_stats: session_stats::SessionStats::default(),
             last_token_usage: TokenUsage::default(),
             session_requested_model: None,
             session_latest_response_model: None,
@@ -7011,6 +7065,7 @@ impl ChatWidget<'_> {
             rate_limit_secondary_next_reset_at: None,
             rate_limit_refresh_scheduled_for: None,
             rate_limit_refresh_schedule_id: Arc::new(AtomicU64::new(0)),
+            usage_quota_confirmed: false,
             content_buffer: String::new(),
             last_assistant_message: None,
             last_answer_stream_id_in_turn: None,
@@ -7051,9 +7106,11 @@ impl ChatWidget<'_> {
             browser_overlay_visible: false,
             browser_overlay_state: BrowserOverlayState::default(),
             pending_images: HashMap::new(),
+            external_editor_prefix: false,
             welcome_shown: false,
             test_mode,
             latest_browser_screenshot: Arc::new(Mutex::new(None)),
+            browser_inline_screenshot_url: None,
             browser_autofix_requested: Arc::new(AtomicBool::new(false)),
             cached_image_protocol: RefCell::new(None),
             cached_picker: RefCell::new(terminal_info.picker.clone()),
@@ -7093,6 +7150,7 @@ impl ChatWidget<'_> {
             pending_auto_turn_config: None,
             overall_task_status: "preparing".to_string(),
             active_plan_title: None,
+            active_plan_percent: None,
             agent_runtime: HashMap::new(),
             agent_runtime_pruned_total: 0,
             agents_terminal_pruned_total: 0,
@@ -7224,13 +7282,21 @@ impl ChatWidget<'_> {
             ui_background_seq_counters: HashMap::new(),
             last_assigned_order: None,
             standard_terminal_mode: !config.tui.alternate_screen,
+            screen_reader_written: HashSet::new(),
             replay_history_depth: 0,
             resume_placeholder_visible: false,
             resume_picker_loading: false,
         };
         new_widget.load_auto_review_baseline_marker();
-        new_widget.spawn_conversation_runtime(config.clone(), auth_manager.clone(), code_op_rx);
-        if let Ok(Some(active_id)) = auth_accounts::get_active_account_id(&config.code_home) {
+        // Scripted runs take agent events from the script; they never start a
+        // conversation or call the backend.
+        let scripted = crate::deterministic::enabled();
+        if !scripted {
+            new_widget.spawn_conversation_runtime(config.clone(), auth_manager.clone(), code_op_rx);
+        }
+        if !scripted
+            && let Ok(Some(active_id)) = auth_accounts::get_active_account_id(&config.code_home)
+        {
             if let Ok(records) = account_usage::list_rate_limit_snapshots(&config.code_home) {
                 if let Some(record) = records.into_iter().find(|r| r.account_id == active_id) {
                     new_widget.rate_limit_primary_next_reset_at = record.primary_next_reset_at;
@@ -7254,6 +7320,8 @@ impl ChatWidget<'_> {
         w.auto_state.reset_countdown();
         w.auto_goal_escape_state = AutoGoalEscState::Inactive;
         w.set_standard_terminal_mode(!config.tui.alternate_screen);
+        w.bottom_pane.set_active_profile(config.active_profile.clone());
+        w.refresh_usage_quota();
         if config.experimental_resume.is_none() {
             w.history_push_top_next_req(history_cell::new_animated_welcome()); // tag: prelude
             let connecting_mcp = !w.config.mcp_servers.is_empty();
@@ -7270,6 +7338,21 @@ impl ChatWidget<'_> {
             );
             let notice_key = w.next_req_key_top();
             let _ = w.history_insert_plain_state_with_key(notice_state, notice_key, "prelude");
+            if !w.config.config_diagnostics.is_empty() {
+                let message = w
+                    .config
+                    .config_diagnostics
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let diagnostics_key = w.next_req_key_top();
+                let _ = w.history_insert_plain_state_with_key(
+                    history_cell::new_warning_event(message),
+                    diagnostics_key,
+                    "prelude",
+                );
+            }
             if connecting_mcp && !w.test_mode {
                 // Render connecting status as a separate cell with standard gutter and spacing
                 w.history_push_top_next_req(history_cell::new_connecting_mcp_status());
@@ -7373,6 +7456,7 @@ impl ChatWidget<'_> {
             reconnect_notice_active: false,
             initial_user_message: None,
             total_token_usage: TokenUsage::default(),
+            session_stats: session_stats::SessionStats::default(),
             last_token_usage: TokenUsage::default(),
             session_requested_model: None,
             session_latest_response_model: None,
@@ -7384,6 +7468,7 @@ impl ChatWidget<'_> {
             rate_limit_secondary_next_reset_at: None,
             rate_limit_refresh_scheduled_for: None,
             rate_limit_refresh_schedule_id: Arc::new(AtomicU64::new(0)),
+            usage_quota_confirmed: false,
             content_buffer: String::new(),
             last_assistant_message: None,
             last_answer_stream_id_in_turn: None,
@@ -7441,9 +7526,11 @@ impl ChatWidget<'_> {
             browser_overlay_visible: false,
             browser_overlay_state: BrowserOverlayState::default(),
             pending_images: HashMap::new(),
+            external_editor_prefix: false,
             welcome_shown: false,
             test_mode: is_test_mode(),
             latest_browser_screenshot: Arc::new(Mutex::new(None)),
+            browser_inline_screenshot_url: None,
             browser_autofix_requested: Arc::new(AtomicBool::new(false)),
             cached_image_protocol: RefCell::new(None),
             cached_picker: RefCell::new(terminal_info.picker.clone()),
@@ -7483,6 +7570,7 @@ impl ChatWidget<'_> {
             pending_auto_turn_config: None,
             overall_task_status: "preparing".to_string(),
             active_plan_title: None,
+            active_plan_percent: None,
             agent_runtime: HashMap::new(),
             agent_runtime_pruned_total: 0,
             agents_terminal_pruned_total: 0,
@@ -7609,6 +7697,7 @@ impl ChatWidget<'_> {
             scroll_history_hint_shown: false,
             access_status_idx: None,
             standard_terminal_mode: !config.tui.alternate_screen,
+            screen_reader_written: HashSet::new(),
             pending_agent_notes: Vec::new(),
             synthetic_system_req: None,
             system_cell_by_id: HashMap::new(),
@@ -7629,6 +7718,8 @@ impl ChatWidget<'_> {
             }
         }
         w.set_standard_terminal_mode(!config.tui.alternate_screen);
+        w.bottom_pane.set_active_profile(config.active_profile.clone());
+        w.refresh_usage_quota();
         if show_welcome {
             w.history_push_top_next_req(history_cell::new_animated_welcome());
         }
@@ -8633,13 +8724,24 @@ impl ChatWidget<'_> {
             }
         }
 
-        if let KeyEvent {
-            code: crossterm::event::KeyCode::Char('g'),
-            modifiers: crossterm::event::KeyModifiers::CONTROL,
-            kind: KeyEventKind::Press | KeyEventKind::Repeat,
-            ..
-        } = key_event
-        {
+        // Ctrl+G, or the readline-style Ctrl+X Ctrl+E, opens the draft in $VISUAL/$EDITOR.
+        let is_ctrl = |c: char| {
+            matches!(
+                key_event,
+                KeyEvent {
+                    code: crossterm::event::KeyCode::Char(code),
+                    modifiers: crossterm::event::KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } if code == c
+            )
+        };
+        let editor_prefix = std::mem::take(&mut self.external_editor_prefix);
+        if is_ctrl('x') && !self.bottom_pane.has_active_modal_view() {
+            self.external_editor_prefix = true;
+            return;
+        }
+        if is_ctrl('g') || (editor_prefix && is_ctrl('e')) {
             if !self.bottom_pane.has_active_modal_view() {
                 let initial = self.bottom_pane.composer_text();
                 self.app_event_tx
@@ -9285,16 +9387,7 @@ impl ChatWidget<'_> {
     }
 
     fn usage_cost_usd_from_totals(totals: &TokenTotals) -> f64 {
-        let non_cached_input = totals
-            .input_tokens
-            .saturating_sub(totals.cached_input_tokens);
-        let input_cost = (non_cached_input as f64 / TOKENS_PER_MILLION)
-            * INPUT_COST_PER_MILLION_USD;
-        let cached_cost = (totals.cached_input_tokens as f64 / TOKENS_PER_MILLION)
-            * CACHED_INPUT_COST_PER_MILLION_USD;
-        let output_cost = (totals.output_tokens as f64 / TOKENS_PER_MILLION)
-            * OUTPUT_COST_PER_MILLION_USD;
-        input_cost + cached_cost + output_cost
+        totals.estimated_cost_usd()
     }
 
     fn format_usd(amount: f64) -> String {
@@ -9420,7 +9513,7 @@ impl ChatWidget<'_> {
         is_api_key_account: bool,
     ) -> Vec<RtLine<'static>> {
         const WIDTH: usize = 14;
-        let now = Local::now();
+        let now = crate::deterministic::now_local();
         let anchor = now
             - ChronoDuration::minutes(now.minute() as i64)
             - ChronoDuration::seconds(now.second() as i64)
@@ -9531,7 +9624,7 @@ impl ChatWidget<'_> {
         is_api_key_account: bool,
     ) -> Vec<RtLine<'static>> {
         const WIDTH: usize = 14;
-        let today = Local::now().date_naive();
+        let today = crate::deterministic::now_local().date_naive();
         let day_totals = Self::aggregate_daily_totals(summary);
         let daily: Vec<(chrono::NaiveDate, TokenTotals)> = (0..7)
             .map(|offset| today - ChronoDuration::days(offset as i64))
@@ -9668,7 +9761,7 @@ impl ChatWidget<'_> {
         const WIDTH: usize = 14;
         const MONTHS: usize = 6;
 
-        let today = Local::now().date_naive();
+        let today = crate::deterministic::now_local().date_naive();
         let mut year = today.year();
         let mut month = today.month();
 
@@ -10017,6 +10110,22 @@ impl ChatWidget<'_> {
         self.history_insert_with_key_global_tagged(cell, key, "untagged", None)
     }
 
+    /// Insert the first screenshot captured for a newly visited URL as an image
+    /// cell. Later captures of the same page only refresh the overlay.
+    fn insert_inline_browser_screenshot(&mut self, screenshot_path: &Path, url: &str) {
+        if url == "about:blank" || self.browser_inline_screenshot_url.as_deref() == Some(url) {
+            return;
+        }
+        let Some(mut record) = image_record_from_path(screenshot_path) else {
+            return;
+        };
+        record.alt_text = Some(url.to_string());
+        self.browser_inline_screenshot_url = Some(url.to_string());
+        let cell = Box::new(history_cell::ImageOutputCell::from_record(record));
+        let key = self.next_internal_key();
+        let _ = self.history_insert_with_key_global(cell, key);
+    }
+
     // Internal: same as above but with a short tag for debug overlays.
     fn history_insert_with_key_global_tagged(
         &mut self,
@@ -11801,6 +11910,9 @@ impl ChatWidget<'_> {
             cell.trigger_fade();
         }
         let mut message = user_message;
+        if self.hold_for_usage_quota(&message) {
+            return;
+        }
         // If our configured cwd no longer exists (e.g., a worktree folder was
         // deleted outside the app), try to automatically recover to the repo
         // root for worktrees and re-submit the same message there.
@@ -13106,7 +13218,9 @@ impl ChatWidget<'_> {
         UndoTimelineEntry {
             label: "Current workspace".to_string(),
             summary: None,
-            timestamp_line: Some(Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+            timestamp_line: Some(
+                crate::deterministic::now_local().format("%Y-%m-%d %H:%M:%S").to_string(),
+            ),
             relative_time: Some("current point".to_string()),
             stats_line: Some("Already at this point in time".to_string()),
             commit_line: None,
@@ -13905,6 +14019,7 @@ impl ChatWidget<'_> {
                 self.session_id = Some(event.session_id);
                 self.bottom_pane
                     .set_history_metadata(event.history_log_id, event.history_entry_count);
+                self.load_persistent_history(event.history_log_id, event.history_entry_count);
                 // Record session information at the top of the conversation.
                 // If we already showed the startup prelude (Popular commands),
                 // avoid inserting a duplicate. Still surface a notice if the
@@ -14407,6 +14522,7 @@ impl ChatWidget<'_> {
                 self.stream_state.drop_streaming = false;
                 // Mark this task id as active and ensure the status stays visible
                 self.active_task_ids.insert(id.clone());
+                self.session_stats.turn_started(&self.total_token_usage);
                 // Reset per-turn UI indicators; ordering is now global-only
                 self.reasoning_index.clear();
                 self.bottom_pane.set_task_running(true);
@@ -14430,6 +14546,7 @@ impl ChatWidget<'_> {
             EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                 self.clear_reconnecting();
                 self.pending_request_user_input = None;
+                self.refresh_usage_quota();
                 let had_running_execs = !self.exec.running_commands.is_empty();
                 // Finalize any active streams
                 let finalizing_streams = self.stream.is_write_cycle_active();
@@ -14440,6 +14557,9 @@ impl ChatWidget<'_> {
                 }
                 // Remove this id from the active set (it may be a sub‑agent)
                 self.active_task_ids.remove(&id);
+                if self.active_task_ids.is_empty() {
+                    self.session_stats.turn_finished();
+                }
                 if !finalizing_streams && self.active_task_ids.is_empty() {
                     if let Some(last_id) = self.last_seen_answer_stream_id_in_turn.clone() {
                         self.mid_turn_answer_ids_in_turn.remove(&last_id);
@@ -14599,6 +14719,8 @@ impl ChatWidget<'_> {
                 if let Some(info) = &event.info {
                     self.total_token_usage = info.total_token_usage.clone();
                     self.last_token_usage = info.last_token_usage.clone();
+                    self.session_stats
+                        .usage_updated(&self.total_token_usage, &self.config.model);
                     if let Some(requested_model) = &info.requested_model {
                         self.session_requested_model = Some(requested_model.clone());
                     }
@@ -14696,6 +14818,8 @@ impl ChatWidget<'_> {
                         .filter(|p| matches!(p.status, StepStatus::Completed))
                         .count();
                     let active = total > 0 && completed < total;
+                    self.active_plan_percent =
+                        active.then(|| (completed * 100 / total).min(100) as u8);
                     (title, active)
                 };
                 // Insert plan updates at the time they occur. If the provider
@@ -14750,9 +14874,15 @@ impl ChatWidget<'_> {
                         }
                     }
                 }
-                let auto_answer = self.auto_state.is_active() && !self.auto_state.is_paused_manual();
+                // Secret questions (browser sign-in) always wait for the user.
+                let has_secret = ev.questions.iter().any(|question| question.is_secret);
+                let auto_answer = self.auto_state.is_active()
+                    && !self.auto_state.is_paused_manual()
+                    && !has_secret;
                 if auto_answer {
                     lines.push("\nAuto Drive is active; continuing automatically.".to_string());
+                } else if has_secret {
+                    lines.push("\nUse the picker below to continue (Esc to cancel).".to_string());
                 } else {
                     lines.push(
                         "\nUse the picker below to continue (Esc to type in the composer).".to_string(),
@@ -14937,6 +15067,7 @@ impl ChatWidget<'_> {
                 );
             }
             EventMsg::ExecCommandBegin(ev) => {
+                self.session_stats.command_started(&ev.call_id, &ev.command);
                 let seq = event.event_seq;
                 let om_begin = event
                     .order
@@ -14950,7 +15081,10 @@ impl ChatWidget<'_> {
                     self.ensure_spinner_for_activity("exec-output");
                 }
                 if let Some(running) = self.exec.running_commands.get_mut(&call_id) {
-                    let chunk = String::from_utf8_lossy(&ev.chunk).to_string();
+                    let chunk = match ev.stream {
+                        ExecOutputStream::Stdout => running.stdout_decoder.push(&ev.chunk),
+                        ExecOutputStream::Stderr => running.stderr_decoder.push(&ev.chunk),
+                    };
                     let chunk_len = chunk.len();
                     let (stdout_chunk, stderr_chunk) = match ev.stream {
                         ExecOutputStream::Stdout => {
@@ -15019,6 +15153,8 @@ impl ChatWidget<'_> {
                 auto_approved,
                 changes,
             }) => {
+                self.session_stats
+                    .patch_started(&call_id, changes.keys().cloned().collect());
                 let exec_call_id = ExecCallId(call_id.clone());
                 self.exec.suppress_exec_end(exec_call_id);
                 self.diffs.record_patch_set(&changes, true);
@@ -15040,13 +15176,24 @@ impl ChatWidget<'_> {
                 let _ = self.history_insert_with_key_global(Box::new(cell), ok);
             }
             EventMsg::PatchApplyEnd(ev) => {
+                self.session_stats.patch_finished(&ev.call_id, ev.success);
                 let ev2 = ev.clone();
                 self.defer_or_handle(
                     move |interrupts| interrupts.push_patch_end(event.event_seq, ev),
                     |this| this.handle_patch_apply_end_now(ev2),
                 );
             }
+            EventMsg::LspDiagnostics(ev) => {
+                let key = match event.order.as_ref() {
+                    Some(om) => self.provider_order_key_from_order_meta(om),
+                    None => self.next_internal_key(),
+                };
+                let state = history_cell::new_lsp_diagnostics(&ev);
+                let _ = self.history_insert_plain_state_with_key(state, key, "lsp_diagnostics");
+            }
             EventMsg::ExecCommandEnd(ev) => {
+                self.session_stats
+                    .command_finished(&ev.call_id, ev.exit_code, ev.duration);
                 let ev2 = ev.clone();
                 let seq = event.event_seq;
                 let order_meta_end = event
@@ -15087,7 +15234,14 @@ impl ChatWidget<'_> {
                     self.flush_interrupt_queue();
                 }
             }
+            EventMsg::McpToolCallProgress(ev) => {
+                tools::mcp_progress(self, ev);
+            }
             EventMsg::McpToolCallEnd(ev) => {
+                self.session_stats.tool_finished(
+                    format!("{}/{}", ev.invocation.server, ev.invocation.tool),
+                    ev.duration,
+                );
                 let ev2 = ev.clone();
                 let seq = event.event_seq;
                 let order_ok = match event.order.as_ref() {
@@ -15280,6 +15434,7 @@ impl ChatWidget<'_> {
                 duration,
                 result,
             }) => {
+                self.session_stats.tool_finished(tool_name.clone(), duration);
                 let params_json = parameters.clone();
                 if agent_runs::is_agent_tool(&tool_name) {
                     if agent_runs::handle_custom_tool_end(
@@ -15618,9 +15773,13 @@ impl ChatWidget<'_> {
                     return;
                 }
                 // Special-case browser/web fetch to render returned markdown nicely.
-                if tool_name == "web_fetch" || tool_name == "browser_fetch" {
+                if tool_name == "web_fetch"
+                    || tool_name == "browser_fetch"
+                    || tool_name == "fetch_url"
+                {
                     let completed = history_cell::new_completed_web_fetch_tool_call(
                         &self.config,
+                        &tool_name,
                         params_string,
                         duration,
                         success,
@@ -15639,6 +15798,24 @@ impl ChatWidget<'_> {
                     self.maybe_hide_spinner();
                     return;
                 }
+                if tool_name == "web_search" {
+                    let completed = history_cell::new_completed_web_search_tool_call(
+                        params_string,
+                        duration,
+                        success,
+                        content,
+                    );
+                    if let Some(idx) = resolved_idx {
+                        self.history_replace_at(idx, Box::new(completed));
+                    } else {
+                        running_tools::collapse_spinner(self, &call_id);
+                        let _ = self.history_insert_with_key_global(Box::new(completed), ok);
+                    }
+                    self.bottom_pane
+                        .update_status_text("responding".to_string());
+                    self.maybe_hide_spinner();
+                    return;
+                }
                 let mut completed = history_cell::new_completed_custom_tool_call(
                     tool_name,
                     params_string,
@@ -15718,6 +15895,15 @@ impl ChatWidget<'_> {
                 self.mcp_server_failures = ev.server_failures.unwrap_or_default();
                 self.refresh_mcp_settings_overlay();
             }
+            EventMsg::ToolNamesResponse(ev) => {
+                self.show_tool_names(ev);
+            }
+            EventMsg::McpServerLogsResponse(ev) => {
+                self.show_mcp_server_logs(ev);
+            }
+            EventMsg::McpCompletionResponse(ev) => {
+                self.bottom_pane.update_mcp_completions(&ev);
+            }
             EventMsg::ListSkillsResponse(ev) => {
                 let len = ev.skills.len();
                 debug!("received {len} skills");
@@ -15983,6 +16169,13 @@ impl ChatWidget<'_> {
                     url
                 );
 
+                // Screenshots from agent browser sessions are grouped into their
+                // tool cell; standalone `/browser` and `/chrome` navigations get an
+                // inline image so it is clear which page was loaded.
+                if !update.grouped {
+                    self.insert_inline_browser_screenshot(&screenshot_path, &url);
+                }
+
                 // Update the latest screenshot and URL for display
                 if let Ok(mut latest) = self.latest_browser_screenshot.lock() {
                     let old_url = latest.as_ref().map(|(_, u)| u.clone());
@@ -16731,6 +16924,101 @@ impl ChatWidget<'_> {
         ));
     }
 
+    /// Handle `/stats`: per-session breakdown of turns, tools and token spend.
+    pub(crate) fn add_stats_output(&mut self) {
+        let state = history_cell::plain_message_state_from_lines(
+            self.session_stats.lines(),
+            crate::history_cell::HistoryCellType::Notice,
+        );
+        self.history_push_plain_state(state);
+    }
+
+    pub(crate) fn handle_outline_command(&mut self, args: String) {
+        let arg = args.trim();
+        if arg.is_empty() {
+            self.history_push_plain_state(history_cell::new_error_event(
+                "Usage: /outline <file>".to_string(),
+            ));
+            return;
+        }
+        let path = self.config.cwd.join(arg);
+        let symbols = match code_core::code_index::outline(&path) {
+            Ok(symbols) => symbols,
+            Err(err) => {
+                self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "Cannot outline {arg}: {err}"
+                )));
+                return;
+            }
+        };
+        let mut lines = vec![Line::from(format!(
+            "Outline of {arg} ({} symbols)",
+            symbols.len()
+        ))];
+        let width = symbols.last().map_or(1, |symbol| symbol.line.to_string().len());
+        for symbol in &symbols {
+            let indent = if symbol.container.is_some() { "  " } else { "" };
+            lines.push(Line::from(format!(
+                "{line:>width$}  {indent}{kind} {name}",
+                line = symbol.line,
+                kind = symbol.kind.label(),
+                name = symbol.name,
+            )));
+        }
+        let state = history_cell::plain_message_state_from_lines(
+            lines,
+            crate::history_cell::HistoryCellType::Notice,
+        );
+        self.history_push_plain_state(state);
+    }
+
+    pub(crate) fn handle_status_command(&mut self, args: String) {
+        const USAGE: &str = "Usage: /status [org <org-id>|clear | project <project-id>|clear]";
+        let parts: Vec<&str> = args.split_whitespace().collect();
+        let (field, value) = match parts.as_slice() {
+            [] => {
+                self.add_status_output();
+                return;
+            }
+            [field @ ("org" | "project"), value] => (*field, *value),
+            _ => {
+                self.history_push_plain_state(history_cell::new_error_event(USAGE.to_string()));
+                return;
+            }
+        };
+        let code_home = self.config.code_home.clone();
+        let Some(account) = auth_accounts::get_active_account_id(&code_home)
+            .ok()
+            .flatten()
+            .and_then(|id| auth_accounts::find_account(&code_home, &id).ok().flatten())
+        else {
+            self.history_push_plain_state(history_cell::new_error_event(
+                "Sign in with an API key first (/login).".to_string(),
+            ));
+            return;
+        };
+        let value = (value != "clear").then(|| value.to_string());
+        let mut target = auth_accounts::BillingTarget {
+            organization: account.organization.clone(),
+            project: account.project.clone(),
+        };
+        if field == "org" {
+            target.organization = value;
+        } else {
+            target.project = value;
+        }
+        match auth_accounts::set_account_billing(&code_home, &account.id, target) {
+            Ok(updated) => self.push_background_tail(format!(
+                "API usage is billed to organization {} · project {}.",
+                updated.organization.as_deref().unwrap_or("default"),
+                updated.project.as_deref().unwrap_or("default")
+            )),
+            Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                "Failed to update the organization or project: {err}"
+            ))),
+        }
+    }
+
     pub(crate) fn show_limits_settings_ui(&mut self) {
         self.ensure_settings_overlay_section(SettingsSection::Limits);
 
@@ -17377,6 +17665,334 @@ impl ChatWidget<'_> {
         self.show_login_accounts_view();
     }
 
+    pub(crate) fn handle_account_command(&mut self, args: String) {
+        const USAGE: &str = "Usage: /account [<profile> | name <profile>]";
+        let code_home = self.config.code_home.clone();
+        let active_id = auth_accounts::get_active_account_id(&code_home).ok().flatten();
+        let parts: Vec<&str> = args.split_whitespace().collect();
+        match parts.as_slice() {
+            [] => {
+                let accounts = auth_accounts::list_accounts(&code_home).unwrap_or_default();
+                let lines: Vec<String> = accounts
+                    .iter()
+                    .filter_map(|account| {
+                        let profile = account.profile.as_deref()?;
+                        let marker = if active_id.as_deref() == Some(account.id.as_str()) {
+                            "●"
+                        } else {
+                            "○"
+                        };
+                        Some(format!("{marker} {profile} — {}", account_display_label(account)))
+                    })
+                    .collect();
+                if lines.is_empty() {
+                    self.push_background_tail(
+                        "No sign-in profiles yet. Name the current sign-in with /account name <profile>, or run `code login --profile <profile>`.",
+                    );
+                } else {
+                    self.push_background_tail(format!(
+                        "Sign-in profiles (switch with /account <profile>):\n{}",
+                        lines.join("\n")
+                    ));
+                }
+            }
+            ["name", profile] => {
+                let Some(active_id) = active_id else {
+                    self.history_push_plain_state(history_cell::new_error_event(
+                        "Not signed in; use /login first.".to_string(),
+                    ));
+                    return;
+                };
+                match auth_accounts::set_account_profile(&code_home, &active_id, Some(profile)) {
+                    Ok(account) => self.push_background_tail(format!(
+                        "Saved {} as profile '{}'.",
+                        account_display_label(&account),
+                        account.profile.unwrap_or_default()
+                    )),
+                    Err(err) => self.history_push_plain_state(history_cell::new_error_event(
+                        format!("Failed to name profile: {err}"),
+                    )),
+                }
+            }
+            [profile] => match code_core::auth::activate_profile(&code_home, profile) {
+                Ok(account) => {
+                    self.push_background_tail(format!(
+                        "Switched to profile '{profile}' ({}).",
+                        account_display_label(&account)
+                    ));
+                    self.app_event_tx.send(AppEvent::LoginUsingChatGptChanged {
+                        using_chatgpt_auth: account.mode.is_chatgpt(),
+                    });
+                }
+                Err(err) => self.history_push_plain_state(history_cell::new_error_event(
+                    format!("Failed to switch profile: {err}"),
+                )),
+            },
+            _ => self.history_push_plain_state(history_cell::new_error_event(USAGE.to_string())),
+        }
+    }
+
+    /// `/profile`: list the `[profiles.<name>]` tables in config.toml, or
+    /// switch this session to one of them.
+    pub(crate) fn handle_profile_command(&mut self, args: String) {
+        let name = args.trim();
+        if name.is_empty() {
+            let profiles = match code_core::config::load_config_as_toml_with_cli_overrides(
+                &self.config.code_home,
+                Vec::new(),
+            ) {
+                Ok(cfg) => cfg.profiles,
+                Err(err) => {
+                    self.history_push_plain_state(history_cell::new_error_event(format!(
+                        "Failed to read config.toml: {err}"
+                    )));
+                    return;
+                }
+            };
+            if profiles.is_empty() {
+                self.push_background_tail(
+                    "No profiles defined. Add a [profiles.<name>] table to config.toml.",
+                );
+                return;
+            }
+            let active = self.config.active_profile.as_deref();
+            let mut names: Vec<&String> = profiles.keys().collect();
+            names.sort();
+            let lines: Vec<String> = names
+                .into_iter()
+                .map(|profile| {
+                    let marker = if Some(profile.as_str()) == active { " (active)" } else { "" };
+                    format!("• {profile}{marker}")
+                })
+                .collect();
+            self.push_background_tail(format!(
+                "Profiles:\n{}\nSwitch with /profile <name>.",
+                lines.join("\n")
+            ));
+            return;
+        }
+
+        let overrides = code_core::config::ConfigOverrides {
+            config_profile: Some(name.to_string()),
+            cwd: Some(self.config.cwd.clone()),
+            ..Default::default()
+        };
+        match code_core::config::ConfigBuilder::new()
+            .with_code_home(self.config.code_home.clone())
+            .with_overrides(overrides)
+            .load()
+        {
+            Ok(next) => self.apply_profile_config(next),
+            Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                "Could not switch to profile `{name}`: {err}"
+            ))),
+        }
+    }
+
+    /// Adopt the model, provider, reasoning and access settings of a config
+    /// loaded for another profile, keeping the rest of the session as is.
+    fn apply_profile_config(&mut self, next: Config) {
+        self.config.active_profile = next.active_profile;
+        self.config.model = next.model;
+        self.config.model_family = next.model_family;
+        self.config.model_explicit = next.model_explicit;
+        self.config.model_provider_id = next.model_provider_id;
+        self.config.model_provider = next.model_provider;
+        self.config.model_reasoning_effort = next.model_reasoning_effort;
+        self.config.preferred_model_reasoning_effort = next.preferred_model_reasoning_effort;
+        self.config.model_reasoning_summary = next.model_reasoning_summary;
+        self.config.model_text_verbosity = next.model_text_verbosity;
+        self.config.service_tier = next.service_tier;
+        self.config.context_mode = next.context_mode;
+        self.config.model_context_window = next.model_context_window;
+        self.config.model_auto_compact_token_limit = next.model_auto_compact_token_limit;
+        self.config.approval_policy = next.approval_policy;
+        self.config.sandbox_policy = next.sandbox_policy;
+        self.config.disable_response_storage = next.disable_response_storage;
+        self.config.usage_limits = next.usage_limits;
+
+        self.submit_configure_session_op();
+        self.sync_follow_chat_models();
+        self.refresh_bottom_pane_token_usage();
+        self.apply_access_mode_indicator_from_config();
+        self.bottom_pane.set_active_profile(self.config.active_profile.clone());
+        self.usage_quota_confirmed = false;
+        self.refresh_usage_quota();
+        self.refresh_settings_overview_rows();
+        self.push_background_tail(format!(
+            "Switched to profile `{}`: {} ({}) via {}.",
+            self.config.active_profile.as_deref().unwrap_or_default(),
+            self.config.model,
+            Self::format_reasoning_effort(self.config.model_reasoning_effort),
+            self.config.model_provider.name,
+        ));
+        self.request_redraw();
+    }
+
+    fn usage_quota_status(&self) -> QuotaStatus {
+        let Some(limits) = self.config.usage_limits else {
+            return QuotaStatus::default();
+        };
+        let scope = usage_quota::scope_for_profile(self.config.active_profile.as_deref());
+        match usage_quota::usage_status(&self.config.code_home, scope, &limits, &Local::now()) {
+            Ok(status) => status,
+            Err(err) => {
+                warn!("failed to read usage totals: {err}");
+                QuotaStatus::default()
+            }
+        }
+    }
+
+    /// Show a footer warning once a usage limit for the active profile is
+    /// 80% used.
+    fn refresh_usage_quota(&mut self) {
+        let status = self.usage_quota_status();
+        if status.exceeded().is_none() {
+            self.usage_quota_confirmed = false;
+        }
+        self.bottom_pane
+            .set_usage_quota_notice(status.warning().map(ToString::to_string));
+    }
+
+    /// Once a usage limit is reached, put the message back in the composer
+    /// and ask for confirmation instead of sending it. Returns true when held.
+    fn hold_for_usage_quota(&mut self, message: &UserMessage) -> bool {
+        if self.usage_quota_confirmed || self.auto_state.is_active() {
+            return false;
+        }
+        let status = self.usage_quota_status();
+        let Some(exceeded) = status.exceeded() else {
+            return false;
+        };
+        let scope = usage_quota::scope_for_profile(self.config.active_profile.as_deref());
+        self.history_push_plain_state(history_cell::new_warning_event(format!(
+            "Usage limit reached for profile `{scope}`: {exceeded}. Press Enter to send anyway."
+        )));
+        self.usage_quota_confirmed = true;
+        self.set_composer_text(message.display_text.clone());
+        self.request_redraw();
+        true
+    }
+
+    pub(crate) fn handle_auth_command(&mut self, args: String) {
+        const USAGE: &str = "Usage: /auth [set <provider> | remove <provider>]";
+        let parts: Vec<&str> = args.split_whitespace().collect();
+        let (action, provider_id) = match parts.as_slice() {
+            [] => {
+                let mut lines: Vec<String> = self
+                    .config
+                    .model_providers
+                    .iter()
+                    .filter_map(|(id, provider)| {
+                        let env_key = provider.env_key.as_deref()?;
+                        let status = if std::env::var(env_key)
+                            .is_ok_and(|value| !value.trim().is_empty())
+                        {
+                            "from environment"
+                        } else if code_core::provider_keys::stored_key(env_key).is_some() {
+                            "saved"
+                        } else {
+                            "missing"
+                        };
+                        Some(format!("• {id} ({env_key}) — {status}"))
+                    })
+                    .collect();
+                lines.sort();
+                if lines.is_empty() {
+                    self.push_background_tail(
+                        "No configured providers read an API key from the environment.",
+                    );
+                } else {
+                    self.push_background_tail(format!(
+                        "Provider API keys (store one with /auth set <provider>):\n{}",
+                        lines.join("\n")
+                    ));
+                }
+                return;
+            }
+            [action @ ("set" | "remove"), provider_id] => (*action, *provider_id),
+            _ => {
+                self.history_push_plain_state(history_cell::new_error_event(USAGE.to_string()));
+                return;
+            }
+        };
+
+        let Some(provider) = self.config.model_providers.get(provider_id).cloned() else {
+            self.history_push_plain_state(history_cell::new_error_event(format!(
+                "Unknown provider '{provider_id}'."
+            )));
+            return;
+        };
+        let Some(env_key) = provider.env_key.clone() else {
+            self.history_push_plain_state(history_cell::new_error_event(format!(
+                "Provider '{provider_id}' does not use an API key; sign in with /login instead."
+            )));
+            return;
+        };
+        let code_home = self.config.code_home.clone();
+
+        if action == "remove" {
+            match code_core::provider_keys::remove_key(&code_home, &env_key) {
+                Ok(removed) => self.push_background_tail(if removed {
+                    format!("Removed the saved API key for {provider_id}.")
+                } else {
+                    format!("No API key saved for {provider_id}.")
+                }),
+                Err(err) => self.history_push_plain_state(history_cell::new_error_event(format!(
+                    "Failed to remove the API key: {err}"
+                ))),
+            }
+            return;
+        }
+
+        let ticket = self.make_background_tail_ticket();
+        let app_event_tx = self.app_event_tx.clone();
+        let provider_id = provider_id.to_string();
+        let on_submit: Box<dyn Fn(String) + Send + Sync> = Box::new(move |text: String| {
+            let api_key = text.trim().to_string();
+            if api_key.is_empty() {
+                return;
+            }
+            let provider = provider.clone();
+            let env_key = env_key.clone();
+            let code_home = code_home.clone();
+            let provider_id = provider_id.clone();
+            let ticket = ticket.clone();
+            let app_event_tx = app_event_tx.clone();
+            tokio::spawn(async move {
+                let saved = match provider.verify_api_key(&api_key).await {
+                    Ok(()) => code_core::provider_keys::set_key(&code_home, &env_key, &api_key)
+                        .map_err(|err| format!("Failed to save the API key: {err}")),
+                    Err(err) => Err(format!("API key not saved: {err}")),
+                };
+                let message = match saved {
+                    Ok(()) => format!("Saved the API key for {provider_id}."),
+                    Err(message) => message,
+                };
+                app_event_tx.send_background_event_with_ticket(&ticket, message);
+            });
+        });
+        let view = CustomPromptView::new(
+            format!("API key for {}", provider.name),
+            format!("Paste the key to store instead of setting {env_key}"),
+            Some("Enter to check and save · Esc cancel".to_string()),
+            self.app_event_tx.clone(),
+            None,
+            on_submit,
+        )
+        .with_masked_input();
+        self.bottom_pane.show_custom_prompt(view);
+    }
+
+    /// The ChatGPT sign-in can no longer be refreshed: say so before the next
+    /// turn fails and open the sign-in picker.
+    pub(crate) fn handle_auth_refresh_failed(&mut self, message: String) {
+        self.history_push_plain_state(history_cell::new_error_event(format!(
+            "Your ChatGPT sign-in expired and could not be renewed: {message}\nSign in again to keep working."
+        )));
+        self.show_login_accounts_view();
+    }
+
     pub(crate) fn auth_manager(&self) -> Arc<AuthManager> {
         self.auth_manager.clone()
     }
@@ -19070,6 +19686,9 @@ fi\n\
     }
 
     fn auto_reduced_motion_preference() -> bool {
+        if crate::screen_reader::enabled() || crate::deterministic::enabled() {
+            return true;
+        }
         match std::env::var("CODE_TUI_REDUCED_MOTION") {
             Ok(value) => {
                 let normalized = value.trim().to_ascii_lowercase();
@@ -22997,8 +23616,9 @@ Have we met every part of this goal and is there no further work to do?"#
 
         // Global
         lines.push(kv("F1", "Help overlay"));
-        lines.push(kv("Ctrl+G", "Open external editor"));
-        lines.push(kv("Ctrl+R", "Toggle reasoning"));
+        lines.push(kv("Ctrl+G", "Open external editor (also Ctrl+X Ctrl+E)"));
+        lines.push(kv("Ctrl+R", "Search prompt history"));
+        lines.push(kv("Alt+R", "Toggle reasoning"));
         lines.push(kv("Ctrl+T", "Toggle screen"));
         lines.push(kv("Ctrl+D", "Diff viewer"));
         lines.push(kv("Esc", &format!("{} / close popups", Self::double_esc_hint_label())));
@@ -23013,12 +23633,17 @@ Have we met every part of this goal and is there no further work to do?"#
             "Compose field",
             t_fg.add_modifier(Modifier::BOLD),
         )]));
-        lines.push(kv("Enter", "Send message"));
-        lines.push(kv("Ctrl+J", "Insert newline"));
-        lines.push(kv("Shift+Enter", "Insert newline"));
-        // Split combined shortcuts into separate rows for readability
-        lines.push(kv("Shift+Up", "Browse input history"));
-        lines.push(kv("Shift+Down", "Browse input history"));
+        // Bindings from `[keys]`, one row per chord.
+        for (action, description) in [
+            (KeyAction::Submit, "Send message"),
+            (KeyAction::Newline, "Insert newline"),
+            (KeyAction::HistoryPrevious, "Browse input history"),
+            (KeyAction::HistoryNext, "Browse input history"),
+        ] {
+            for chord in self.config.keymap.chords(action) {
+                lines.push(kv(&chord.to_string(), description));
+            }
+        }
         lines.push(kv("Ctrl+B", "Move left"));
         lines.push(kv("Ctrl+F", "Move right"));
         lines.push(kv("Alt+Left", "Move by word"));
@@ -23508,6 +24133,7 @@ Have we met every part of this goal and is there no further work to do?"#
             resume_path: None,
             demo_developer_message: self.config.demo_developer_message.clone(),
             dynamic_tools: Vec::new(),
+            active_profile: self.config.active_profile.clone(),
         };
         self.submit_op(op);
     }
@@ -25792,7 +26418,7 @@ Have we met every part of this goal and is there no further work to do?"#
     }
 
     fn maybe_start_auto_upgrade_task(&mut self) {
-        if !crate::updates::auto_upgrade_runtime_enabled() {
+        if crate::deterministic::enabled() || !crate::updates::auto_upgrade_runtime_enabled() {
             return;
         }
         if !self.config.auto_upgrade_enabled {
@@ -26287,6 +26913,28 @@ Have we met every part of this goal and is there no further work to do?"#
             || (wait_running && wait_blocks)
     }
 
+    /// What the session is doing, for the terminal title and progress.
+    pub(crate) fn terminal_activity(&self) -> TerminalActivity {
+        if self.bottom_pane.has_pending_approval() {
+            return TerminalActivity::AwaitingApproval;
+        }
+        if !self.is_task_running() {
+            return TerminalActivity::Idle;
+        }
+        let latest_command = self
+            .exec
+            .running_commands
+            .values()
+            .max_by_key(|running| running.history_id.map(|id| id.0))
+            .map(|running| format!("running {}", strip_bash_lc_and_escape(&running.command)));
+        let label = latest_command.unwrap_or_else(|| {
+            self.bottom_pane
+                .status_message()
+                .map_or_else(|| "working".to_string(), str::to_lowercase)
+        });
+        TerminalActivity::Working { label, percent: self.active_plan_percent }
+    }
+
     pub(crate) fn is_task_running(&self) -> bool {
         let wait_running = self.wait_running();
         let wait_blocks = self.wait_blocking_enabled();
@@ -26557,7 +27205,7 @@ Have we met every part of this goal and is there no further work to do?"#
             StreamKind::Reasoning => {
                 // This reasoning block is the bottom-most; show progress indicator here only
                 self.clear_reasoning_in_progress();
-                // Ensure footer shows Ctrl+R hint when reasoning content is present
+                // Ensure footer shows Alt+R hint when reasoning content is present
                 self.bottom_pane.set_reasoning_hint(true);
                 // Update footer label to reflect current visibility state
                 self.bottom_pane
@@ -27571,6 +28219,39 @@ Have we met every part of this goal and is there no further work to do?"#
         self.refresh_standard_terminal_hint();
     }
 
+    /// In screen-reader mode, append history items that finished since the
+    /// last call to the scrollback as labeled plain text.
+    pub(crate) fn flush_screen_reader_transcript(&mut self) {
+        if !crate::screen_reader::enabled() || !self.standard_terminal_mode {
+            return;
+        }
+        let turn_running = self.is_task_running();
+        let mut lines = Vec::new();
+        for id in self.history_cell_ids.iter().flatten() {
+            if self.screen_reader_written.contains(id) {
+                continue;
+            }
+            let Some(record) = self.history_state.record(*id) else {
+                continue;
+            };
+            if crate::screen_reader::is_pending(record) {
+                if turn_running {
+                    break;
+                }
+                continue;
+            }
+            self.screen_reader_written.insert(*id);
+            let text = crate::screen_reader::linear_lines(record);
+            if !text.is_empty() {
+                lines.extend(text.into_iter().map(Line::from));
+                lines.push(Line::from(""));
+            }
+        }
+        if !lines.is_empty() {
+            self.app_event_tx.send(AppEvent::InsertHistory(lines));
+        }
+    }
+
     pub(crate) fn is_reasoning_shown(&self) -> bool {
         // Check if any reasoning cell exists and if it's expanded
         for cell in &self.history_cells {
@@ -27814,6 +28495,9 @@ Have we met every part of this goal and is there no further work to do?"#
                 config.connect_ws = None;
                 config.connect_host = host.clone();
                 config.connect_port = Some(p);
+            } else if let Some(target) = code_browser::global::cdp_target() {
+                tracing::info!("[cdp] using Chrome endpoint from [browser.connect]");
+                target.apply(&mut config);
             } else {
                 // Load persisted cache from disk (if any), then fall back to in-memory
                 let (cached_port, cached_ws) = match read_cached_connection().await {
@@ -28451,6 +29135,139 @@ Have we met every part of this goal and is there no further work to do?"#
         ));
     }
 
+    /// `/browser profile ...`: pick, list or clear persistent profiles under
+    /// `<code_home>/browser`. Switching restarts a running internal browser
+    /// so the next navigation launches with the new profile.
+    fn handle_browser_profile_command(&mut self, args: &[&str]) -> String {
+        const USAGE: &str = "Usage: /browser profile [<name> | off | list | clear <name>]";
+        let code_home = self.config.code_home.clone();
+        let active = code_browser::global::active_profile_dir();
+        match args {
+            [] | ["list"] => {
+                let names = code_browser::profiles::list_profiles(&code_home);
+                let mut lines = vec![match active.as_ref() {
+                    Some(dir) => format!("Active browser profile: {}", dir.display()),
+                    None => "Active browser profile: none (temporary profile)".to_string(),
+                }];
+                if names.is_empty() {
+                    lines.push("No saved profiles.".to_string());
+                } else {
+                    lines.push(format!("Saved profiles: {}", names.join(", ")));
+                }
+                lines.push(USAGE.to_string());
+                lines.join("\n")
+            }
+            ["clear"] => USAGE.to_string(),
+            ["clear", name] => match code_browser::profiles::profile_dir(&code_home, name) {
+                Err(err) => err,
+                Ok(dir) if active.as_ref() == Some(&dir) => format!(
+                    "Browser profile '{name}' is in use; run /browser profile off before clearing it."
+                ),
+                Ok(_) => match code_browser::profiles::clear_profile(&code_home, name) {
+                    Ok(true) => format!("Cleared browser profile '{name}'."),
+                    Ok(false) => format!("No browser profile named '{name}'."),
+                    Err(err) => format!("Failed to clear browser profile '{name}': {err}"),
+                },
+            },
+            [name] => {
+                let dir = if *name == "off" {
+                    None
+                } else {
+                    match code_browser::profiles::profile_dir(&code_home, name) {
+                        Ok(dir) => Some(dir),
+                        Err(err) => return err,
+                    }
+                };
+                let message = match dir.as_ref() {
+                    Some(dir) => format!(
+                        "Using browser profile '{name}' ({}). Cookies and localStorage persist across sessions.",
+                        dir.display()
+                    ),
+                    None => "Browser profile off; the internal browser uses a temporary profile.".to_string(),
+                };
+                tokio::spawn(async move {
+                    code_browser::global::set_active_profile_dir(dir).await;
+                    let Some(browser_manager) = code_browser::global::get_browser_manager().await else {
+                        return;
+                    };
+                    let is_external = {
+                        let cfg = browser_manager.config.read().await;
+                        cfg.connect_port.is_some() || cfg.connect_ws.is_some()
+                    };
+                    if !is_external
+                        && browser_manager.get_status().await.browser_active
+                        && let Err(err) = browser_manager.stop().await
+                    {
+                        tracing::warn!("[/browser profile] failed to stop browser: {err}");
+                    }
+                });
+                message
+            }
+            _ => USAGE.to_string(),
+        }
+    }
+
+    fn handle_browser_headless_command(&self, headless: bool) -> String {
+        tokio::spawn(async move {
+            code_browser::global::set_headless_preference(headless).await;
+            let Some(browser_manager) = code_browser::global::get_browser_manager().await else {
+                return;
+            };
+            let is_external = {
+                let cfg = browser_manager.config.read().await;
+                cfg.connect_port.is_some() || cfg.connect_ws.is_some()
+            };
+            // Chrome cannot switch modes in place; the next browser action relaunches it.
+            if !is_external
+                && browser_manager.get_status().await.browser_active
+                && let Err(err) = browser_manager.stop().await
+            {
+                tracing::warn!("[/browser headless] failed to stop browser: {err}");
+            }
+        });
+        if self.browser_is_external {
+            return "Headless preference saved for the internal browser; the attached Chrome stays visible.".to_string();
+        }
+        if headless {
+            "The internal browser now runs headless; it restarts on the next browser action.".to_string()
+        } else {
+            "The internal browser now opens a visible window; it restarts on the next browser action.".to_string()
+        }
+    }
+
+    fn handle_browser_har_command(&self, args: &[&str], ticket: &BackgroundOrderTicket) -> String {
+        const USAGE: &str = "Usage: /browser har <path>";
+        if args.is_empty() {
+            return USAGE.to_string();
+        }
+        // Relative paths are resolved against the session's working directory.
+        let path = self.config.cwd.join(args.join(" "));
+        let app_event_tx = self.app_event_tx.clone();
+        let ticket = ticket.clone();
+        let display = path.display().to_string();
+        tokio::spawn(async move {
+            let har = match code_browser::global::get_browser_manager().await {
+                Some(browser_manager) => browser_manager.har().await,
+                None => None,
+            };
+            let message = match har {
+                None => "No browser page is open; there is no network activity to export.".to_string(),
+                Some((har, requests)) => match serde_json::to_vec_pretty(&har) {
+                    Err(err) => format!("❌ Failed to encode HAR: {err}"),
+                    Ok(bytes) => match tokio::fs::write(&path, bytes).await {
+                        Ok(()) => {
+                            let noun = if requests == 1 { "request" } else { "requests" };
+                            format!("✅ Exported {requests} {noun} to {}", path.display())
+                        }
+                        Err(err) => format!("❌ Failed to write {}: {err}", path.display()),
+                    },
+                },
+            };
+            app_event_tx.send_background_event_with_ticket(&ticket, message);
+        });
+        format!("Exporting browser network activity to {display}…")
+    }
+
     pub(crate) fn handle_browser_command(&mut self, command_text: String) {
         // Parse the browser subcommand
         let trimmed = command_text.trim();
@@ -28498,8 +29315,8 @@ Have we met every part of this goal and is there no further work to do?"#
                         cfg.connect_port = None;
                         cfg.connect_ws = None;
                         cfg.enabled = true;
-                        cfg.persist_profile = false;
-                        cfg.headless = true;
+                        cfg.use_profile_dir(code_browser::global::active_profile_dir());
+                        cfg.headless = code_browser::global::headless_preference().unwrap_or(true);
                     }
 
                     if let Err(e) = browser_manager.start().await {
@@ -28588,7 +29405,9 @@ Have we met every part of this goal and is there no further work to do?"#
                     browser_manager.set_enabled_sync(true);
                     {
                         let mut config = browser_manager.config.write().await;
-                        config.headless = false; // Ensure browser is visible when navigating to URL
+                        // Visible when navigating to a URL unless headless was chosen
+                        config.headless =
+                            code_browser::global::headless_preference().unwrap_or(false);
                         config.connect_port = None; // Ensure we're not trying to connect to CDP
                         config.connect_ws = None; // Ensure we're not trying to connect via WebSocket
                     }
@@ -28904,16 +29723,21 @@ Have we met every part of this goal and is there no further work to do?"#
                             "Usage: /browser config <key> <value>\nAvailable keys: viewport, segments_max".to_string()
                         }
                     }
+                    "profile" => self.handle_browser_profile_command(&parts[1..]),
+                    "har" => self.handle_browser_har_command(&parts[1..], &browser_ticket),
+                    "headless" | "headful" => {
+                        self.handle_browser_headless_command(first_arg == "headless")
+                    }
                     _ => {
                         format!(
-                            "Unknown browser command: '{}'\nUsage: /browser <url> | off | status | fullpage | config",
+                            "Unknown browser command: '{}'\nUsage: /browser <url> | off | status | fullpage | config | profile | har | headless | headful",
                             first_arg
                         )
                     }
                 }
             }
         } else {
-            "Browser commands:\n• /browser <url> - Open URL in internal browser\n• /browser off - Disable browser mode\n• /browser status - Show current status\n• /browser fullpage [on|off] - Toggle full-page mode\n• /browser config <key> <value> - Update configuration\n\nUse /chrome [port] to connect to external Chrome browser".to_string()
+            "Browser commands:\n• /browser <url> - Open URL in internal browser\n• /browser off - Disable browser mode\n• /browser status - Show current status\n• /browser fullpage [on|off] - Toggle full-page mode\n• /browser config <key> <value> - Update configuration\n• /browser profile [name|off|list|clear <name>] - Use a persistent profile\n• /browser har <path> - Export network activity as a HAR file\n• /browser headless | headful - Hide or show the internal browser window\n\nUse /chrome [port] to connect to external Chrome browser".to_string()
         };
 
         // Add the response to the UI as a ticketed background event so it stays with
@@ -29273,7 +30097,7 @@ Have we met every part of this goal and is there no further work to do?"#
         }
     }
 
-    /// Handle `/mcp` command: manage MCP servers (status/on/off/add).
+    /// Handle `/mcp` command: manage MCP servers (status/on/off/refresh/logs/complete/add).
     pub(crate) fn handle_mcp_command(&mut self, command_text: String) {
         let trimmed = command_text.trim();
         if trimmed.is_empty() {
@@ -29390,6 +30214,49 @@ Have we met every part of this goal and is there no further work to do?"#
                     }
                 }
             }
+            "refresh" => {
+                let server = parts.next().map(str::to_string);
+                self.submit_op(Op::RefreshMcpTools { server });
+            }
+            "logs" => {
+                let name = parts.next().unwrap_or("");
+                if name.is_empty() {
+                    let msg = "Usage: /mcp logs <name>".to_string();
+                    self.history_push_plain_state(history_cell::new_error_event(msg));
+                    return;
+                }
+                self.submit_op(Op::McpServerLogs {
+                    server: name.to_string(),
+                });
+            }
+            "complete" => {
+                // /mcp complete <server> <prompt | resource:<uri>> <argument> [partial…]
+                let server = parts.next().unwrap_or("");
+                let target = parts.next().unwrap_or("");
+                let argument = parts.next().unwrap_or("");
+                if server.is_empty() || target.is_empty() || argument.is_empty() {
+                    let msg = "Usage: /mcp complete <server> <prompt | resource:<uri>> <argument> [partial]".to_string();
+                    self.history_push_plain_state(history_cell::new_error_event(msg));
+                    return;
+                }
+                let reference = match target.strip_prefix("resource:") {
+                    Some(uri) => code_core::protocol::McpCompletionRef::Resource {
+                        uri: uri.to_string(),
+                    },
+                    None => code_core::protocol::McpCompletionRef::Prompt {
+                        name: target.to_string(),
+                    },
+                };
+                let value = parts.collect::<Vec<_>>().join(" ");
+                let view = crate::bottom_pane::McpArgumentView::new(
+                    server.to_string(),
+                    reference,
+                    argument.to_string(),
+                    value,
+                    self.app_event_tx.clone(),
+                );
+                self.bottom_pane.show_mcp_argument(view);
+            }
             "add" => {
                 // Support two forms:
                 //   1) /mcp add <name> <command> [args…] [ENV=VAL…]
@@ -29506,6 +30373,8 @@ Have we met every part of this goal and is there no further work to do?"#
                             tool_timeout_sec: None,
                             enabled_tools: None,
                             disabled_tools: None,
+                            approval_required_tools: None,
+                            tool_timeouts_sec: None,
                         };
                         match code_core::config::add_mcp_server(&home, &name, cfg.clone()) {
                             Ok(()) => {
@@ -29529,7 +30398,7 @@ Have we met every part of this goal and is there no further work to do?"#
             }
             _ => {
                 let msg = format!(
-                    "Unknown MCP command: '{}'\nUsage:\n  /mcp status\n  /mcp on <name>\n  /mcp off <name>\n  /mcp add <name> <command> [args…] [ENV=VAL…]",
+                    "Unknown MCP command: '{}'\nUsage:\n  /mcp status\n  /mcp on <name>\n  /mcp off <name>\n  /mcp refresh [name]\n  /mcp logs <name>\n  /mcp complete <server> <prompt | resource:<uri>> <argument> [partial]\n  /mcp add <name> <command> [args…] [ENV=VAL…]",
                     sub
                 );
                 self.history_push_plain_state(history_cell::new_error_event(msg));
@@ -29537,6 +30406,60 @@ Have we met every part of this goal and is there no further work to do?"#
         }
     }
 
+    /// Handle `/tools`: list exposed tool names and where MCP tools come from.
+    pub(crate) fn handle_tools_command(&mut self) {
+        self.submit_op(Op::ListToolNames);
+    }
+
+    fn show_tool_names(&mut self, ev: code_core::protocol::ToolNamesResponseEvent) {
+        let mut lines = String::new();
+        if ev.builtin.is_empty() {
+            lines.push_str("Built-in tools: shown after the first request of this session\n");
+        } else {
+            lines.push_str(&format!("Built-in tools: {}\n", ev.builtin.join(", ")));
+        }
+        if ev.mcp.is_empty() {
+            lines.push_str("MCP tools: none");
+        } else {
+            lines.push_str(&format!("MCP tools ({}):\n", ev.mcp.len()));
+            for mapping in ev.mcp {
+                lines.push_str(&format!("• {} → {}/{}", mapping.name, mapping.server, mapping.tool));
+                if !mapping.shared_with.is_empty() {
+                    lines.push_str(&format!(" (also on {})", mapping.shared_with.join(", ")));
+                }
+                lines.push('\n');
+            }
+        }
+        self.push_background_tail(lines.trim_end().to_string());
+    }
+
+    fn show_mcp_server_logs(&mut self, ev: code_core::protocol::McpServerLogsResponseEvent) {
+        let server = ev.server;
+        if !ev.known {
+            let msg = format!("No MCP server named '{server}' in this session");
+            self.history_push_plain_state(history_cell::new_error_event(msg));
+            return;
+        }
+        if ev.entries.is_empty() {
+            self.push_background_tail(format!("No logs captured for MCP server '{server}'"));
+            return;
+        }
+
+        let count = ev.entries.len();
+        let mut lines = format!("MCP server '{server}' logs ({count} lines):\n");
+        for entry in ev.entries {
+            let time = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+                .map(|at| at.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                .unwrap_or(entry.timestamp);
+            let tag = match entry.level {
+                Some(level) => level,
+                None => entry.source,
+            };
+            lines.push_str(&format!("{time} [{tag}] {}\n", entry.message));
+        }
+        self.push_background_tail(lines.trim_end().to_string());
+    }
+
     #[allow(dead_code)]
     fn switch_to_internal_browser(&mut self) {
         // Switch to internal browser mode
@@ -29559,8 +30482,8 @@ Have we met every part of this goal and is there no further work to do?"#
                 let mut config = browser_manager.config.write().await;
                 config.connect_port = None;
                 config.connect_ws = None;
-                config.headless = true;
-                config.persist_profile = false;
+                config.headless = code_browser::global::headless_preference().unwrap_or(true);
+                config.use_profile_dir(code_browser::global::active_profile_dir());
                 config.enabled = true;
             }
 
@@ -30042,6 +30965,7 @@ Have we met every part of this goal and is there no further work to do?"#
 
     pub(crate) fn clear_token_usage(&mut self) {
         self.total_token_usage = TokenUsage::default();
+        self.session_stats = session_stats::SessionStats::default();
         self.rate_limit_snapshot = None;
         self.rate_limit_warnings.reset();
         self.rate_limit_last_fetch_at = None;
@@ -30296,111 +31220,137 @@ Have we met every part of this goal and is there no further work to do?"#
             .unwrap_or(cwd_str.as_str())
             .to_string();
 
-        // Build status line spans with dynamic elision based on width.
-        // Removal priority when space is tight:
-        //   1) Reasoning level
-        //   2) Model
-        //   3) Branch
-        //   4) Directory
+        // Build status line spans from `[tui.status_bar]` with dynamic
+        // elision based on width. When space is tight the directory is
+        // shortened, then the reasoning level is dropped, then whole segments
+        // in `status_bar::elide_one` priority order.
         let branch_opt = self.get_git_branch();
-
-        // Helper to assemble spans based on include flags
-        let build_spans = |include_reasoning: bool,
-                           include_model: bool,
-                           include_branch: bool,
-                           include_dir: bool,
-                           dir_display: &str| {
-            let mut spans: Vec<Span> = Vec::new();
-            let model_display = self.format_model_name(&self.config.model);
-            let mut model_suffix_parts: Vec<String> = Vec::new();
-            if include_reasoning {
-                model_suffix_parts
-                    .push(Self::format_reasoning_effort(self.config.model_reasoning_effort).to_string());
-            }
-            if matches!(self.config.service_tier, Some(ServiceTier::Fast)) {
-                model_suffix_parts.push("Fast".to_string());
+        let clock = crate::deterministic::now_local();
+
+        let segment_value = |segment: StatusBarSegment,
+                             include_reasoning: bool,
+                             dir_display: &str|
+         -> Option<(&'static str, String, ratatui::style::Color)> {
+            match segment {
+                StatusBarSegment::Model => {
+                    let model_display = self.format_model_name(&self.config.model);
+                    let mut model_suffix_parts: Vec<String> = Vec::new();
+                    if include_reasoning {
+                        model_suffix_parts.push(
+                            Self::format_reasoning_effort(self.config.model_reasoning_effort)
+                                .to_string(),
+                        );
+                    }
+                    if matches!(self.config.service_tier, Some(ServiceTier::Fast)) {
+                        model_suffix_parts.push("Fast".to_string());
+                    }
+                    let model_label = if model_suffix_parts.is_empty() {
+                        model_display
+                    } else {
+                        format!("{} ({})", model_display, model_suffix_parts.join(", "))
+                    };
+                    Some(("Model: ", model_label, crate::colors::info()))
+                }
+                StatusBarSegment::Directory => {
+                    Some(("Directory: ", dir_display.to_string(), crate::colors::info()))
+                }
+                StatusBarSegment::Branch => branch_opt
+                    .clone()
+                    .map(|branch| ("Branch: ", branch, crate::colors::success_green())),
+                StatusBarSegment::Tokens => Some((
+                    "Tokens: ",
+                    format_si_suffix(
+                        i64::try_from(self.total_token_usage.blended_total()).unwrap_or(i64::MAX),
+                    ),
+                    crate::colors::info(),
+                )),
+                StatusBarSegment::Cost => Some((
+                    "Cost: ",
+                    Self::format_usd(status_bar::session_cost_usd(
+                        &self.total_token_usage,
+                        &self.config.model,
+                    )),
+                    crate::colors::info(),
+                )),
+                StatusBarSegment::Sandbox => Some((
+                    "Sandbox: ",
+                    status_bar::sandbox_label(&self.config.sandbox_policy).to_string(),
+                    crate::colors::info(),
+                )),
+                StatusBarSegment::Agents => {
+                    let running = self
+                        .active_agents
+                        .iter()
+                        .filter(|agent| {
+                            matches!(agent.status, AgentStatus::Pending | AgentStatus::Running)
+                        })
+                        .count();
+                    (running > 0).then(|| {
+                        ("Agents: ", format!("{running} running"), crate::colors::info())
+                    })
+                }
+                StatusBarSegment::Clock => {
+                    Some(("", clock.format("%H:%M").to_string(), crate::colors::text_dim()))
+                }
             }
-            let model_label = if model_suffix_parts.is_empty() {
-                model_display
-            } else {
-                format!("{} ({})", model_display, model_suffix_parts.join(", "))
-            };
-
-            // Title follows theme text color
-            spans.push(Span::styled(
-                "Every Code",
-                Style::default()
-                    .fg(crate::colors::text())
-                    .add_modifier(Modifier::BOLD),
-            ));
+        };
 
-            if include_model {
-                spans.push(Span::styled(
-                    "  •  ",
-                    Style::default().fg(crate::colors::text_dim()),
-                ));
-                spans.push(Span::styled(
-                    "Model: ",
-                    Style::default().fg(crate::colors::text_dim()),
-                ));
-                spans.push(Span::styled(
-                    model_label,
-                    Style::default().fg(crate::colors::info()),
-                ));
-            }
+        // Helper to assemble spans for the segments still shown
+        let build_spans =
+            |include_reasoning: bool, segments: &[StatusBarSegment], dir_display: &str| {
+                let mut spans: Vec<Span> = Vec::new();
 
-            if include_dir {
-                spans.push(Span::styled(
-                    "  •  ",
-                    Style::default().fg(crate::colors::text_dim()),
-                ));
-                spans.push(Span::styled(
-                    "Directory: ",
-                    Style::default().fg(crate::colors::text_dim()),
-                ));
+                // Title follows theme text color
                 spans.push(Span::styled(
-                    dir_display.to_string(),
-                    Style::default().fg(crate::colors::info()),
+                    "Every Code",
+                    Style::default()
+                        .fg(crate::colors::text())
+                        .add_modifier(Modifier::BOLD),
                 ));
-            }
 
-            if include_branch {
-                if let Some(branch) = &branch_opt {
+                for &segment in segments {
+                    let Some((label, value, color)) =
+                        segment_value(segment, include_reasoning, dir_display)
+                    else {
+                        continue;
+                    };
                     spans.push(Span::styled(
                         "  •  ",
                         Style::default().fg(crate::colors::text_dim()),
                     ));
-                    spans.push(Span::styled(
-                        "Branch: ",
-                        Style::default().fg(crate::colors::text_dim()),
-                    ));
-                    spans.push(Span::styled(
-                        branch.clone(),
-                        Style::default().fg(crate::colors::success_green()),
-                    ));
+                    if !label.is_empty() {
+                        spans.push(Span::styled(
+                            label,
+                            Style::default().fg(crate::colors::text_dim()),
+                        ));
+                    }
+                    spans.push(Span::styled(value, Style::default().fg(color)));
                 }
-            }
 
-            // Footer already shows the Ctrl+R hint; avoid duplicating it here.
+                // Footer already shows the Alt+R hint; avoid duplicating it here.
 
-            spans
-        };
+                spans
+            };
 
-        // Start with all items in production; tests can opt-in to a minimal header via env flag.
+        // Start with all configured items in production; tests can opt-in to a
+        // minimal header via env flag.
         let minimal_header = std::env::var_os("CODEX_TUI_FORCE_MINIMAL_HEADER").is_some();
         let demo_mode = self.config.demo_developer_message.is_some();
+        let mut segments: Vec<StatusBarSegment> = if minimal_header {
+            Vec::new()
+        } else {
+            self.config
+                .tui
+                .status_bar
+                .segments
+                .iter()
+                .copied()
+                .filter(|segment| !(demo_mode && *segment == StatusBarSegment::Directory))
+                .collect()
+        };
         let mut include_reasoning = !minimal_header;
-        let mut include_model = !minimal_header;
-        let mut include_branch = !minimal_header && branch_opt.is_some();
-        let mut include_dir = !minimal_header && !demo_mode;
         let mut use_short_dir = false;
-        let mut status_spans = build_spans(
-            include_reasoning,
-            include_model,
-            include_branch,
-            include_dir,
-            &cwd_str,
-        );
+        let mut status_spans = build_spans(include_reasoning, &segments, &cwd_str);
 
         // Now recompute exact available width inside the border + padding before measuring
         // Render a bordered status block and explicitly fill its background.
@@ -30419,35 +31369,23 @@ Have we met every part of this goal and is there no further work to do?"#
         let measure =
             |spans: &Vec<Span>| -> usize { spans.iter().map(|s| s.content.chars().count()).sum() };
 
-        if include_dir && !use_short_dir && measure(&status_spans) > inner_width {
+        if segments.contains(&StatusBarSegment::Directory)
+            && measure(&status_spans) > inner_width
+        {
             use_short_dir = true;
-            status_spans = build_spans(
-                include_reasoning,
-                include_model,
-                include_branch,
-                include_dir,
-                &cwd_short_str,
-            );
+            status_spans = build_spans(include_reasoning, &segments, &cwd_short_str);
         }
 
         // Elide items in priority order until content fits
         while measure(&status_spans) > inner_width {
-            if include_reasoning {
+            if include_reasoning && segments.contains(&StatusBarSegment::Model) {
                 include_reasoning = false;
-            } else if include_model {
-                include_model = false;
-            } else if include_branch {
-                include_branch = false;
-            } else if include_dir {
-                include_dir = false;
-            } else {
+            } else if !status_bar::elide_one(&mut segments) {
                 break;
             }
             status_spans = build_spans(
                 include_reasoning,
-                include_model,
-                include_branch,
-                include_dir,
+                &segments,
                 if use_short_dir { &cwd_short_str } else { &cwd_str },
             );
         }
@@ -30459,6 +31397,13 @@ Have we met every part of this goal and is there no further work to do?"#
 
         let status_line = Line::from(status_spans);
 
+        // Wake up for the next minute so the clock does not go stale while idle.
+        if segments.contains(&StatusBarSegment::Clock) {
+            let seconds_left = 60 - u64::from(clock.second());
+            self.app_event_tx
+                .send(AppEvent::ScheduleFrameIn(Duration::from_secs(seconds_left)));
+        }
+
         let now = Instant::now();
         let mut frame_needed = false;
         if ENABLE_WARP_STRIPES && self.header_wave.schedule_if_needed(now) {
@@ -31224,6 +32169,8 @@ use code_core::protocol::OrderMeta;
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                approval_required_tools: None,
+                tool_timeouts_sec: None,
             };
             let fail_cfg = McpServerConfig {
                 transport: McpServerTransportConfig::Stdio {
@@ -31235,6 +32182,8 @@ use code_core::protocol::OrderMeta;
                 tool_timeout_sec: None,
                 enabled_tools: None,
                 disabled_tools: None,
+                approval_required_tools: None,
+                tool_timeouts_sec: None,
             };
 
             let ok_summary = chat.format_mcp_server_summary("alpha", &ok_cfg, true);
@@ -31339,6 +32288,36 @@ use code_core::protocol::OrderMeta;
         assert_eq!(formatted, "GPT-5.1-Codex-Mini");
     }
 
+    #[test]
+    fn pasted_images_attach_only_for_image_capable_models() {
+        let mut harness = ChatWidgetHarness::new();
+        let chat = harness.chat();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("shot.png");
+        std::fs::write(&path, b"png").expect("write image");
+        let placeholder = "[image: shot.png]".to_string();
+        let has_image = |message: &UserMessage| {
+            message
+                .ordered_items
+                .iter()
+                .any(|item| matches!(item, InputItem::LocalImage { .. }))
+        };
+
+        chat.config.model_family.supports_image_input = true;
+        chat.register_pasted_image(placeholder.clone(), path.clone());
+        let message = chat.parse_message_with_images(format!("{placeholder} broken"));
+        assert!(has_image(&message));
+
+        chat.config.model_family.supports_image_input = false;
+        chat.register_pasted_image(placeholder.clone(), path);
+        let message = chat.parse_message_with_images(format!("{placeholder} broken"));
+        assert!(!has_image(&message));
+        assert!(message.ordered_items.iter().any(|item| matches!(
+            item,
+            InputItem::Text { text } if text == &placeholder
+        )));
+    }
+
     #[test]
     fn curated_model_presets_falls_back_when_shortlist_missing() {
         let presets = vec![ModelPreset {
@@ -31522,6 +32501,55 @@ use code_core::protocol::OrderMeta;
         }
     }
 
+    #[test]
+    fn profile_command_switches_model_and_access_settings() {
+        let _runtime_guard = enter_test_runtime_guard();
+        let code_home = tempdir().expect("temp code home");
+        std::fs::write(
+            code_home.path().join("config.toml"),
+            "[profiles.careful]\nmodel = \"gpt-5.1-codex-max\"\napproval_policy = \"untrusted\"\n\
+             sandbox_mode = \"read-only\"\n",
+        )
+        .expect("seed config");
+
+        let mut harness = ChatWidgetHarness::new();
+        harness.chat().config.code_home = code_home.path().to_path_buf();
+        harness.chat().handle_profile_command("careful".to_string());
+
+        let config = &harness.chat().config;
+        assert_eq!(config.active_profile.as_deref(), Some("careful"));
+        assert_eq!(config.model, "gpt-5.1-codex-max");
+        assert_eq!(config.approval_policy, code_core::protocol::AskForApproval::UnlessTrusted);
+        assert_eq!(config.sandbox_policy, code_core::protocol::SandboxPolicy::ReadOnly);
+    }
+
+    #[test]
+    fn usage_limit_holds_the_first_send_and_allows_a_confirmed_one() {
+        let _runtime_guard = enter_test_runtime_guard();
+        let code_home = tempdir().expect("temp code home");
+        let usage = code_core::protocol::TokenUsage {
+            input_tokens: 150,
+            total_tokens: 150,
+            ..Default::default()
+        };
+        let scope = usage_quota::DEFAULT_SCOPE;
+        usage_quota::record_usage(code_home.path(), scope, "gpt-5", &usage, Utc::now())
+            .expect("record usage");
+
+        let mut harness = ChatWidgetHarness::new();
+        let chat = harness.chat();
+        chat.config.code_home = code_home.path().to_path_buf();
+        chat.config.usage_limits = Some(code_core::config_types::UsageLimits {
+            daily_tokens: Some(100),
+            ..Default::default()
+        });
+
+        let message = UserMessage::from("keep going".to_string());
+        assert!(chat.hold_for_usage_quota(&message));
+        assert_eq!(chat.bottom_pane.composer_text(), "keep going");
+        assert!(!chat.hold_for_usage_quota(&message));
+    }
+
     #[test]
     fn apply_service_tier_selection_persists_profile_disable_as_standard() {
         let _runtime_guard = enter_test_runtime_guard();
@@ -33774,6 +34802,8 @@ use code_core::protocol::OrderMeta;
                 explore_entry: None,
                 stdout_offset: 0,
                 stderr_offset: 0,
+                stdout_decoder: Default::default(),
+                stderr_decoder: Default::default(),
                 wait_total: None,
                 wait_active: false,
                 wait_notes: Vec::new(),
@@ -33824,6 +34854,8 @@ use code_core::protocol::OrderMeta;
                 explore_entry: None,
                 stdout_offset: 0,
                 stderr_offset: 0,
+                stdout_decoder: Default::default(),
+                stderr_decoder: Default::default(),
                 wait_total: None,
                 wait_active: false,
                 wait_notes: Vec::new(),
@@ -34284,6 +35316,31 @@ use code_core::protocol::OrderMeta;
         assert_eq!(chat.bottom_pane.composer_text(), "x");
     }
 
+    #[test]
+    fn ctrl_x_ctrl_e_dispatches_external_editor_event() {
+        let mut harness = ChatWidgetHarness::new();
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        let opened = |events: Vec<AppEvent>| {
+            events
+                .iter()
+                .any(|event| matches!(event, AppEvent::OpenExternalEditor { .. }))
+        };
+
+        harness.chat().handle_key_event(ctrl('e'));
+        assert!(!opened(harness.drain_events()), "Ctrl+E alone keeps its composer meaning");
+
+        harness.chat().handle_key_event(ctrl('x'));
+        harness.chat().handle_key_event(ctrl('e'));
+        assert!(opened(harness.drain_events()), "expected external editor request");
+
+        harness.chat().handle_key_event(ctrl('x'));
+        harness
+            .chat()
+            .handle_key_event(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        harness.chat().handle_key_event(ctrl('e'));
+        assert!(!opened(harness.drain_events()), "prefix expires after another key");
+    }
+
     #[test]
     fn ctrl_g_dispatches_external_editor_event() {
         let mut harness = ChatWidgetHarness::new();
@@ -34333,6 +35390,8 @@ use code_core::protocol::OrderMeta;
                 explore_entry: None,
                 stdout_offset: 0,
                 stderr_offset: 0,
+                stdout_decoder: Default::default(),
+                stderr_decoder: Default::default(),
                 wait_total: None,
                 wait_active: false,
                 wait_notes: Vec::new(),
@@ -34386,6 +35445,8 @@ use code_core::protocol::OrderMeta;
                 explore_entry: None,
                 stdout_offset: 0,
                 stderr_offset: 0,
+                stdout_decoder: Default::default(),
+                stderr_decoder: Default::default(),
                 wait_total: None,
                 wait_active: false,
                 wait_notes: Vec::new(),
@@ -41101,6 +42162,19 @@ impl ChatWidget<'_> {
             )]));
         }
 
+        if let Some(cell) = cell_opt.filter(|cell| !cell.diagnostics().is_empty()) {
+            info_lines.push(RLine::from(vec![Span::raw(String::new())]));
+            info_lines.push(RLine::from(vec![Span::styled("Diagnostics", header_style)]));
+            let warning_style = Style::default().fg(crate::colors::warning());
+            for line in cell.diagnostics() {
+                info_lines.push(RLine::from(vec![
+                    Span::styled("•", secondary_style),
+                    Span::raw(" "),
+                    Span::styled(line.clone(), warning_style),
+                ]));
+            }
+        }
+
         info_lines.push(RLine::from(vec![Span::raw(String::new())]));
         info_lines.push(RLine::from(vec![Span::styled(
             "Controls: ←/→ or ↑/↓ select screenshot • Shift+↑/↓ or j/k scroll actions",
@@ -6,6 +6,7 @@ use crate::history_cell::{
     HistoryCellType,
     plain_message_state_from_lines,
 };
+use crate::i18n::tr_args;
 use code_core::protocol::OrderMeta;
 use ratatui::style::{Style, Stylize};
 use ratatui::text::{Line, Span};
@@ -14,6 +15,14 @@ use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 const BROWSER_CONSOLE_EVENT_PREFIX: &str = "[browser console]";
+const BROWSER_DIAGNOSTICS_EVENT_PREFIX: &str = "[browser diagnostics]";
+/// Shown instead of text entered into a password or credential field.
+const HIDDEN_VALUE: &str = "(hidden)";
+/// Selector or label fragments that mark a field as holding a secret.
+const SECRET_FIELD_HINTS: &[&str] = &[
+    "password", "passwd", "passcode", "secret", "token", "credential", "otp", "cvv", "cvc",
+    "apikey", "api_key", "api-key",
+];
 
 pub(super) struct BrowserSessionTracker {
     pub slot: ToolCardSlot,
@@ -254,15 +263,24 @@ pub(super) fn handle_background_event(
         return false;
     }
 
-    let payload = match message.strip_prefix(BROWSER_CONSOLE_EVENT_PREFIX) {
-        Some(rest) => rest.trim(),
-        None => return false,
+    let (payload, is_diagnostics) = if let Some(rest) =
+        message.strip_prefix(BROWSER_CONSOLE_EVENT_PREFIX)
+    {
+        (rest.trim(), false)
+    } else if let Some(rest) = message.strip_prefix(BROWSER_DIAGNOSTICS_EVENT_PREFIX) {
+        (rest.trim(), true)
+    } else {
+        return false;
     };
     if payload.is_empty() {
         return false;
     }
 
-    let key = key_from_order_or_last(chat, order);
+    let mut key = key_from_order_or_last(chat, order);
+    if key.is_none() && is_diagnostics {
+        // Diagnostics always belong to the action that just ran.
+        key = chat.tools_state.browser_last_key.clone();
+    }
     let Some(key) = key else { return false; };
 
     let mut tracker = match chat.tools_state.browser_sessions.remove(&key) {
@@ -275,13 +293,19 @@ pub(super) fn handle_background_event(
         .unwrap_or(tracker.slot.order_key);
     tracker.slot.set_order_key(order_key);
 
-    let console_lines = parse_console_output(payload);
-    if console_lines.is_empty() {
-        chat.tools_state.browser_sessions.insert(key, tracker);
-        return false;
-    }
-    for line in console_lines {
-        tracker.cell.add_console_message(line);
+    if is_diagnostics {
+        for line in payload.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            tracker.cell.add_diagnostic(line.to_string());
+        }
+    } else {
+        let console_lines = parse_console_output(payload);
+        if console_lines.is_empty() {
+            chat.tools_state.browser_sessions.insert(key, tracker);
+            return false;
+        }
+        for line in console_lines {
+            tracker.cell.add_console_message(line);
+        }
     }
 
     ensure_cell_picker(chat, &tracker.cell);
@@ -500,6 +524,19 @@ fn summarize_action(
             if !(dx == 0 && dy == 0) {
                 summary.value = Some(label);
             }
+            if let Some(pages) = params
+                .and_then(|value| value.get("pages"))
+                .and_then(Value::as_f64)
+            {
+                summary.value = Some(format!("{pages} page(s)"));
+            }
+            if let Some(to) = params.and_then(|value| value.get("to")).and_then(Value::as_str) {
+                summary.value = Some(format!("to {to}"));
+            }
+            summary.target = params
+                .and_then(|value| value.get("selector"))
+                .and_then(Value::as_str)
+                .map(|s| s.to_string());
         }
         "browser_type" => {
             if let Some(text) = params
@@ -523,6 +560,18 @@ fn summarize_action(
                 summary.value = Some(key.to_string());
             }
         }
+        "browser_fill" | "browser_select" | "browser_submit" => {
+            summary.target = ["selector", "label"].iter().find_map(|key| {
+                params
+                    .and_then(|value| value.get(*key))
+                    .and_then(Value::as_str)
+                    .map(|s| s.to_string())
+            });
+            summary.value = params
+                .and_then(|value| value.get("value"))
+                .and_then(Value::as_str)
+                .map(|text| truncate(text, 48));
+        }
         "browser_history" => {
             if let Some(direction) = params
                 .and_then(|value| value.get("direction"))
@@ -544,7 +593,20 @@ fn summarize_action(
                 .map(|(dx, dy)| format!("by ({:.0}, {:.0})", dx, dy));
             summary.value = absolute.or(relative);
         }
-        "browser_console" => {
+        "browser_download" | "browser_diff" => {
+            summary.target = params
+                .and_then(|value| value.get("url"))
+                .and_then(Value::as_str)
+                .map(|s| s.to_string());
+        }
+        "browser_viewport" => {
+            summary.value = params
+                .and_then(|value| value.get("width"))
+                .and_then(Value::as_u64)
+                .zip(params.and_then(|value| value.get("height")).and_then(Value::as_u64))
+                .map(|(width, height)| format!("{width}x{height}"));
+        }
+        "browser_console" | "browser_network" => {
             if let Some(lines) = params
                 .and_then(|value| value.get("lines"))
                 .and_then(Value::as_u64)
@@ -552,7 +614,7 @@ fn summarize_action(
                 summary.value = Some(format!("last {}", lines));
             }
         }
-        "browser_javascript" => {
+        "browser_javascript" | "browser_eval" => {
             if let Some(code) = params
                 .and_then(|value| value.get("code"))
                 .and_then(Value::as_str)
@@ -568,7 +630,7 @@ fn summarize_action(
                 summary.target = Some(method.to_string());
             }
         }
-        "browser_inspect" => {
+        "browser_inspect" | "browser_query" => {
             summary.target = params
                 .and_then(|value| value.get("selector"))
                 .and_then(Value::as_str)
@@ -593,9 +655,27 @@ fn summarize_action(
     summary.outcome = outcome;
     summary.status_code = status_code;
 
+    // Form fills report a password input as "a hidden value"; typing only
+    // has the selector to go on.
+    let secret = summary.target.as_deref().is_some_and(is_secret_field)
+        || matches!(result, Ok(text) if text.contains("with a hidden value"));
+    if secret && matches!(tool_name, "browser_type" | "browser_fill") {
+        if summary.value.is_some() {
+            summary.value = Some(HIDDEN_VALUE.to_string());
+        }
+        if tool_name == "browser_type" && result.is_ok() {
+            summary.outcome = Some(format!("Typed: {HIDDEN_VALUE}"));
+        }
+    }
+
     summary
 }
 
+fn is_secret_field(target: &str) -> bool {
+    let target = target.to_ascii_lowercase();
+    SECRET_FIELD_HINTS.iter().any(|hint| target.contains(hint))
+}
+
 fn summarize_action_label(tool_name: &str) -> String {
     match tool_name {
         "browser_open" => "Nav".to_string(),
@@ -603,9 +683,19 @@ fn summarize_action_label(tool_name: &str) -> String {
         "browser_scroll" => "Scroll".to_string(),
         "browser_type" => "Type".to_string(),
         "browser_key" => "Key".to_string(),
+        "browser_fill" => "Fill".to_string(),
+        "browser_select" => "Select".to_string(),
+        "browser_submit" => "Submit".to_string(),
+        "browser_query" => "Query".to_string(),
+        "browser_eval" => "Eval".to_string(),
+        "browser_viewport" => "Viewport".to_string(),
+        "browser_download" => "Download".to_string(),
         "browser_move" => "Move".to_string(),
         "browser_history" => "History".to_string(),
         "browser_console" => "Console".to_string(),
+        "browser_network" => "Network".to_string(),
+        "browser_diff" => "Diff".to_string(),
+        "browser_login" => "Login".to_string(),
         "browser_javascript" => "Script".to_string(),
         "browser_cdp" => "CDP".to_string(),
         "browser_status" => "Status".to_string(),
@@ -660,7 +750,7 @@ fn summarize_action_result(result: &Result<String, String>) -> (Option<String>,
             (Some(truncate(trimmed, 64)), extract_leading_status_code(trimmed))
         }
         Err(err) => {
-            let text = format!("error: {}", truncate(err, 48));
+            let text = tr_args("error.brief", &[("message", truncate(err, 48).as_str())]);
             (Some(text), None)
         }
     }
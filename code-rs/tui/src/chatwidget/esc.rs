@@ -8,6 +8,7 @@ pub(crate) enum EscIntent {
     DismissModal,
     CloseSettings,
     CloseFilePopup,
+    CloseHistorySearch,
     AutoPauseForEdit,
     AutoStopDuringApproval,
     AutoStopActive,
@@ -119,6 +120,10 @@ impl ChatWidget<'_> {
             return EscRoute::new(EscIntent::CloseFilePopup, false, false);
         }
 
+        if self.bottom_pane.history_search_active() {
+            return EscRoute::new(EscIntent::CloseHistorySearch, true, false);
+        }
+
         if self.auto_state.is_active() {
             let prompt_visible = self.auto_state.awaiting_coordinator_submit()
                 && !self.auto_state.is_paused_manual()
@@ -201,6 +206,7 @@ impl ChatWidget<'_> {
                 true
             }
             EscIntent::CloseFilePopup => self.close_file_popup_if_active(),
+            EscIntent::CloseHistorySearch => self.bottom_pane.cancel_history_search(),
             EscIntent::AutoPauseForEdit => {
                 self.auto_pause_for_manual_edit(false);
                 true
@@ -381,6 +387,8 @@ mod tests {
             explore_entry: None,
             stdout_offset: 0,
             stderr_offset: 0,
+            stdout_decoder: Default::default(),
+            stderr_decoder: Default::default(),
             wait_total: None,
             wait_active: false,
             wait_notes: Vec::new(),
@@ -327,6 +327,7 @@ fn exec_record_from_begin(ev: &ExecCommandBeginEvent) -> ExecRecord {
         working_dir: Some(ev.cwd.clone()),
         env: Vec::new(),
         tags: Vec::new(),
+        redactions: 0,
     }
 }
 
@@ -594,6 +595,7 @@ pub(super) fn finalize_all_running_due_to_answer(chat: &mut ChatWidget<'_>) {
                     wait_notes: wait_notes_record,
                     stdout_tail: stdout_tail_event,
                     stderr_tail: stderr_tail_event,
+                    redactions: 0,
                 },
             );
 
@@ -784,6 +786,7 @@ pub(super) fn finalize_wait_missing_exec(
             wait_notes: wait_notes_record,
             stdout_tail: None,
             stderr_tail: Some(fallback_message.to_string()),
+            redactions: 0,
         });
 
     let mut handled_via_state = false;
@@ -1293,6 +1296,8 @@ pub(super) fn handle_exec_begin_now(
                             explore_entry: Some((idx, entry_idx)),
                             stdout_offset: 0,
                             stderr_offset: 0,
+                            stdout_decoder: Default::default(),
+                            stderr_decoder: Default::default(),
                             wait_total: None,
                             wait_active: false,
                             wait_notes: Vec::new(),
@@ -1359,6 +1364,8 @@ pub(super) fn handle_exec_begin_now(
             explore_entry: None,
             stdout_offset: 0,
             stderr_offset: 0,
+            stdout_decoder: Default::default(),
+            stderr_decoder: Default::default(),
             wait_total: None,
             wait_active: false,
             wait_notes: Vec::new(),
@@ -1432,6 +1439,7 @@ pub(super) fn handle_exec_end_now(
         duration,
         stdout,
         stderr,
+        redactions,
     } = ev;
     let cmd = chat
         .exec
@@ -1631,6 +1639,7 @@ pub(super) fn handle_exec_end_now(
             wait_notes: wait_notes_record,
             stdout_tail: stdout_tail_event,
             stderr_tail: stderr_tail_event,
+            redactions,
         });
 
     let mut handled_via_state = false;
@@ -1662,6 +1671,7 @@ pub(super) fn handle_exec_end_now(
             cell.set_wait_notes(&wait_notes_pairs);
             cell.set_waiting(false);
             cell.set_run_duration(Some(duration));
+            cell.record.redactions = redactions;
             if cell.record.call_id.as_deref().is_none() {
                 cell.record.call_id = Some(call_id.clone());
             }
@@ -1730,6 +1740,13 @@ pub(super) fn handle_exec_end_now(
         }
     }
 
+    let test_output = format!("{stdout}\n{stderr}");
+    if let Some(report) =
+        code_core::test_results::parse_command_output(&command_for_watch.join(" "), &test_output)
+    {
+        chat.history_push_plain_state(history_cell::new_test_results(&report));
+    }
+
     if exit_code == 0 {
         chat
             .bottom_pane
@@ -0,0 +1,270 @@
+//! Per-session counters behind `/stats`.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use code_common::elapsed::format_duration;
+use code_core::protocol::TokenUsage;
+use code_protocol::num_format::format_with_separators_u64;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::status_bar::session_cost_usd;
+use crate::exec_command::strip_bash_lc_and_escape;
+
+/// Tool calls kept for the "slowest" table.
+const SLOWEST_LIMIT: usize = 5;
+/// Most recent turns shown in the per-turn chart.
+const TURN_ROWS: usize = 20;
+const BAR_WIDTH: usize = 20;
+
+#[derive(Default)]
+struct TurnStats {
+    tokens: u64,
+    cost_usd: f64,
+}
+
+struct ToolTiming {
+    label: String,
+    duration: Duration,
+}
+
+#[derive(Default)]
+pub(super) struct SessionStats {
+    turns: Vec<TurnStats>,
+    turn_open: bool,
+    turn_start_usage: TokenUsage,
+    commands_succeeded: usize,
+    commands_failed: usize,
+    patches_applied: usize,
+    patches_failed: usize,
+    files_touched: BTreeSet<PathBuf>,
+    pending_commands: HashMap<String, String>,
+    pending_patches: HashMap<String, Vec<PathBuf>>,
+    slowest: Vec<ToolTiming>,
+}
+
+impl SessionStats {
+    /// Opens a turn unless one is already running (sub-agent tasks start
+    /// inside the user's turn).
+    pub(super) fn turn_started(&mut self, total_usage: &TokenUsage) {
+        if self.turn_open {
+            return;
+        }
+        self.turn_open = true;
+        self.turn_start_usage = total_usage.clone();
+        self.turns.push(TurnStats::default());
+    }
+
+    pub(super) fn turn_finished(&mut self) {
+        self.turn_open = false;
+    }
+
+    /// Charges token usage reported since the turn started to that turn.
+    pub(super) fn usage_updated(&mut self, total_usage: &TokenUsage, model: &str) {
+        if !self.turn_open {
+            return;
+        }
+        let Some(turn) = self.turns.last_mut() else {
+            return;
+        };
+        let start = &self.turn_start_usage;
+        let delta = TokenUsage {
+            input_tokens: total_usage.input_tokens.saturating_sub(start.input_tokens),
+            cached_input_tokens: total_usage
+                .cached_input_tokens
+                .saturating_sub(start.cached_input_tokens),
+            cache_write_input_tokens: total_usage
+                .cache_write_input_tokens
+                .saturating_sub(start.cache_write_input_tokens),
+            output_tokens: total_usage.output_tokens.saturating_sub(start.output_tokens),
+            reasoning_output_tokens: total_usage
+                .reasoning_output_tokens
+                .saturating_sub(start.reasoning_output_tokens),
+            total_tokens: total_usage.total_tokens.saturating_sub(start.total_tokens),
+        };
+        turn.tokens = delta.blended_total();
+        turn.cost_usd = session_cost_usd(&delta, model);
+    }
+
+    pub(super) fn command_started(&mut self, call_id: &str, command: &[String]) {
+        self.pending_commands
+            .insert(call_id.to_string(), strip_bash_lc_and_escape(command));
+    }
+
+    pub(super) fn command_finished(&mut self, call_id: &str, exit_code: i32, duration: Duration) {
+        if exit_code == 0 {
+            self.commands_succeeded += 1;
+        } else {
+            self.commands_failed += 1;
+        }
+        let label = self
+            .pending_commands
+            .remove(call_id)
+            .unwrap_or_else(|| "command".to_string());
+        self.tool_finished(label, duration);
+    }
+
+    pub(super) fn patch_started(&mut self, call_id: &str, paths: Vec<PathBuf>) {
+        self.pending_patches.insert(call_id.to_string(), paths);
+    }
+
+    pub(super) fn patch_finished(&mut self, call_id: &str, success: bool) {
+        let paths = self.pending_patches.remove(call_id).unwrap_or_default();
+        if success {
+            self.patches_applied += 1;
+            self.files_touched.extend(paths);
+        } else {
+            self.patches_failed += 1;
+        }
+    }
+
+    pub(super) fn tool_finished(&mut self, label: String, duration: Duration) {
+        self.slowest.push(ToolTiming { label, duration });
+        self.slowest.sort_by(|a, b| b.duration.cmp(&a.duration));
+        self.slowest.truncate(SLOWEST_LIMIT);
+    }
+
+    pub(super) fn lines(&self) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(crate::colors::text_dim());
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let bar_style = Style::default().fg(crate::colors::info());
+        let mut lines = vec![Line::from(Span::styled("Session stats", bold))];
+
+        let total_tokens: u64 = self.turns.iter().map(|turn| turn.tokens).sum();
+        let total_cost: f64 = self.turns.iter().map(|turn| turn.cost_usd).sum();
+        let commands = self.commands_succeeded + self.commands_failed;
+        let summary = [
+            ("Turns", self.turns.len().to_string()),
+            (
+                "Commands",
+                format!(
+                    "{commands} ({} succeeded, {} failed)",
+                    self.commands_succeeded, self.commands_failed
+                ),
+            ),
+            (
+                "Patches",
+                format!("{} applied, {} failed", self.patches_applied, self.patches_failed),
+            ),
+            ("Files touched", self.files_touched.len().to_string()),
+            (
+                "Tokens",
+                format!("{} (~${total_cost:.2})", format_with_separators_u64(total_tokens)),
+            ),
+        ];
+        for (label, value) in summary {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {label:<15}"), dim),
+                Span::raw(value),
+            ]));
+        }
+
+        if !self.turns.is_empty() {
+            lines.push(Line::from(""));
+            let skipped = self.turns.len().saturating_sub(TURN_ROWS);
+            let heading = if skipped > 0 {
+                format!("Tokens per turn (last {TURN_ROWS} of {})", self.turns.len())
+            } else {
+                "Tokens per turn".to_string()
+            };
+            lines.push(Line::from(Span::styled(heading, bold)));
+            let shown = &self.turns[skipped..];
+            let max_tokens = shown.iter().map(|turn| turn.tokens).max().unwrap_or(0);
+            let number_width = self.turns.len().to_string().len() + 1;
+            let tokens: Vec<String> =
+                shown.iter().map(|turn| format_with_separators_u64(turn.tokens)).collect();
+            let tokens_width = tokens.iter().map(String::len).max().unwrap_or(0);
+            for (index, (turn, tokens)) in shown.iter().zip(tokens).enumerate() {
+                let number = format!("#{}", skipped + index + 1);
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {number:>number_width$}  "), dim),
+                    Span::styled(bar(turn.tokens as f64, max_tokens as f64), bar_style),
+                    Span::raw(format!("  {tokens:>tokens_width$}  ${:.2}", turn.cost_usd)),
+                ]));
+            }
+        }
+
+        if !self.slowest.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Slowest tool calls", bold)));
+            let max = self.slowest[0].duration.as_secs_f64();
+            let durations: Vec<String> =
+                self.slowest.iter().map(|timing| format_duration(timing.duration)).collect();
+            let duration_width = durations.iter().map(String::len).max().unwrap_or(0);
+            for (timing, duration) in self.slowest.iter().zip(durations) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {duration:>duration_width$}  "), dim),
+                    Span::styled(bar(timing.duration.as_secs_f64(), max), bar_style),
+                    Span::raw(format!("  {}", timing.label)),
+                ]));
+            }
+        }
+
+        lines
+    }
+}
+
+/// A fixed-width bar filled in proportion to `value / max`; any non-zero
+/// value gets at least one cell.
+fn bar(value: f64, max: f64) -> String {
+    let filled = if max > 0.0 && value > 0.0 {
+        ((value / max) * BAR_WIDTH as f64).round().clamp(1.0, BAR_WIDTH as f64) as usize
+    } else {
+        0
+    };
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(input: u64, output: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            output_tokens: output,
+            total_tokens: input + output,
+            ..TokenUsage::default()
+        }
+    }
+
+    #[test]
+    fn stats_break_down_turns_commands_and_patches() {
+        let mut stats = SessionStats::default();
+        stats.turn_started(&TokenUsage::default());
+        stats.command_started("c1", &["bash".into(), "-lc".into(), "cargo test".into()]);
+        stats.command_finished("c1", 101, Duration::from_secs(12));
+        stats.command_started("c2", &["ls".into()]);
+        stats.command_finished("c2", 0, Duration::from_millis(40));
+        stats.patch_started("p1", vec![PathBuf::from("src/a.rs"), PathBuf::from("src/b.rs")]);
+        stats.patch_finished("p1", true);
+        stats.usage_updated(&usage(800, 100), "gpt-5");
+        stats.turn_finished();
+
+        stats.turn_started(&usage(800, 100));
+        stats.patch_started("p2", vec![PathBuf::from("src/a.rs")]);
+        stats.patch_finished("p2", false);
+        stats.usage_updated(&usage(1_250, 100), "gpt-5");
+        stats.turn_finished();
+
+        let text: Vec<String> = stats
+            .lines()
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text[1], "  Turns          2");
+        assert_eq!(text[2], "  Commands       2 (1 succeeded, 1 failed)");
+        assert_eq!(text[3], "  Patches        1 applied, 1 failed");
+        assert_eq!(text[4], "  Files touched  2");
+        assert_eq!(text[8], format!("  #1  {}  900  $0.00", "█".repeat(BAR_WIDTH)));
+        assert_eq!(text[9], format!("  #2  {}{}  450  $0.00", "█".repeat(10), "░".repeat(10)));
+        assert_eq!(text[12], format!("   12s  {}  cargo test", "█".repeat(BAR_WIDTH)));
+        assert_eq!(text[13], format!("  40ms  █{}  ls", "░".repeat(BAR_WIDTH - 1)));
+    }
+}
@@ -581,6 +581,8 @@ impl ChatWidgetHarness {
                     explore_entry: None,
                     stdout_offset: 0,
                     stderr_offset: 0,
+                    stdout_decoder: Default::default(),
+                    stderr_decoder: Default::default(),
                     wait_total: None,
                     wait_active: false,
                     wait_notes: Vec::new(),
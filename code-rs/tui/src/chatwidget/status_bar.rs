@@ -0,0 +1,81 @@
+//! Segment selection for the status bar configured by `[tui.status_bar]`.
+
+use code_core::account_usage::ModelPrices;
+use code_core::account_usage::TokenTotals;
+use code_core::config_types::StatusBarSegment;
+use code_core::protocol::SandboxPolicy;
+use code_core::protocol::TokenUsage;
+
+/// Order in which segments are dropped when the bar is too narrow. The
+/// original model/directory/branch trio goes last, in the order it always
+/// elided, so the default bar behaves as before.
+const ELISION_ORDER: [StatusBarSegment; 8] = [
+    StatusBarSegment::Clock,
+    StatusBarSegment::Cost,
+    StatusBarSegment::Tokens,
+    StatusBarSegment::Agents,
+    StatusBarSegment::Sandbox,
+    StatusBarSegment::Model,
+    StatusBarSegment::Branch,
+    StatusBarSegment::Directory,
+];
+
+/// Removes the least important segment still shown. Returns `false` once
+/// nothing is left to drop.
+pub(super) fn elide_one(segments: &mut Vec<StatusBarSegment>) -> bool {
+    let Some(victim) = ELISION_ORDER
+        .iter()
+        .find(|segment| segments.contains(segment))
+    else {
+        return false;
+    };
+    segments.retain(|segment| segment != victim);
+    true
+}
+
+pub(super) fn sandbox_label(policy: &SandboxPolicy) -> &'static str {
+    match policy {
+        SandboxPolicy::ReadOnly => "Read Only",
+        SandboxPolicy::WorkspaceWrite { .. } => "Workspace Write",
+        SandboxPolicy::DangerFullAccess => "Full Access",
+    }
+}
+
+/// Estimated cost of `usage` at the list prices of `model`'s family.
+pub(super) fn session_cost_usd(usage: &TokenUsage, model: &str) -> f64 {
+    TokenTotals::from_usage(usage).estimated_cost_usd_at(ModelPrices::for_model(model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn session_cost_uses_the_model_family_prices() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+            total_tokens: 2_000_000,
+            ..Default::default()
+        };
+        assert_eq!(session_cost_usd(&usage, "gpt-5"), 11.25);
+        assert_eq!(session_cost_usd(&usage, "gpt-5-mini"), 2.25);
+        assert_eq!(session_cost_usd(&usage, "some-local-model"), 11.25);
+    }
+
+    #[test]
+    fn segments_elide_by_priority_not_position() {
+        use StatusBarSegment::*;
+        let mut segments = vec![Clock, Model, Directory, Branch, Tokens];
+        assert!(elide_one(&mut segments));
+        assert_eq!(segments, vec![Model, Directory, Branch, Tokens]);
+        assert!(elide_one(&mut segments));
+        assert_eq!(segments, vec![Model, Directory, Branch]);
+        assert!(elide_one(&mut segments));
+        assert_eq!(segments, vec![Directory, Branch]);
+        assert!(elide_one(&mut segments));
+        assert!(elide_one(&mut segments));
+        assert!(!elide_one(&mut segments));
+    }
+}
@@ -2,7 +2,12 @@
 
 use super::{running_tools, web_search_sessions, ChatWidget, OrderKey};
 use crate::history_cell;
-use code_core::protocol::{McpToolCallBeginEvent, McpToolCallEndEvent, OrderMeta};
+use crate::history::compat::ArgumentValue;
+use code_core::protocol::{McpToolCallBeginEvent, McpToolCallEndEvent, McpToolCallProgressEvent, OrderMeta};
+use serde_json::{Map, Value};
+
+/// Number of partial-output lines kept on a running MCP cell.
+const MCP_PARTIAL_OUTPUT_MAX_LINES: usize = 6;
 
 pub(super) fn web_search_begin(
     chat: &mut ChatWidget<'_>,
@@ -47,6 +52,67 @@ pub(super) fn mcp_begin(chat: &mut ChatWidget<'_>, ev: McpToolCallBeginEvent, ke
         );
 }
 
+pub(super) fn mcp_progress(chat: &mut ChatWidget<'_>, ev: McpToolCallProgressEvent) {
+    let McpToolCallProgressEvent { call_id, progress, total, message } = ev;
+    let mut update = Map::new();
+    update.insert(
+        "progress".to_string(),
+        Value::String(format_mcp_progress(progress, total)),
+    );
+    if let Some(message) = message
+        .map(|message| message.trim_end().to_string())
+        .filter(|message| !message.trim().is_empty())
+    {
+        let previous = running_mcp_output(chat, &call_id).unwrap_or_default();
+        update.insert(
+            "output".to_string(),
+            Value::String(append_partial_output(&previous, &message)),
+        );
+    }
+    chat.apply_custom_tool_update(&call_id, Some(Value::Object(update)));
+    chat.request_redraw();
+}
+
+fn running_mcp_output(chat: &ChatWidget<'_>, call_id: &str) -> Option<String> {
+    let idx = running_tools::find_by_call_id(chat, call_id)?;
+    let cell = chat.history_cells.get(idx)?;
+    let running = cell
+        .as_any()
+        .downcast_ref::<history_cell::RunningToolCallCell>()?;
+    running
+        .state()
+        .arguments
+        .iter()
+        .find(|arg| arg.name == "output")
+        .and_then(|arg| match &arg.value {
+            ArgumentValue::Text(text) => Some(text.clone()),
+            _ => None,
+        })
+}
+
+fn format_mcp_progress(progress: f64, total: Option<f64>) -> String {
+    match total {
+        Some(total) if total > 0.0 => {
+            let percent = ((progress / total) * 100.0).clamp(0.0, 100.0);
+            format!("{progress}/{total} ({percent:.0}%)")
+        }
+        _ => format!("{progress}"),
+    }
+}
+
+/// Append `message` to the partial output, keeping only the most recent lines.
+fn append_partial_output(previous: &str, message: &str) -> String {
+    let mut lines: Vec<&str> = previous
+        .lines()
+        .chain(message.lines())
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if lines.len() > MCP_PARTIAL_OUTPUT_MAX_LINES {
+        lines.drain(..lines.len() - MCP_PARTIAL_OUTPUT_MAX_LINES);
+    }
+    lines.join("\n")
+}
+
 pub(super) fn mcp_end(chat: &mut ChatWidget<'_>, ev: McpToolCallEndEvent, key: OrderKey) {
     let McpToolCallEndEvent { call_id, duration, invocation, result } = ev;
     let success = !result.as_ref().map(|r| r.is_error.unwrap_or(false)).unwrap_or(false);
@@ -89,3 +155,26 @@ pub(super) fn mcp_end(chat: &mut ChatWidget<'_>, ev: McpToolCallEndEvent, key: O
         let _ = chat.history_insert_with_key_global(Box::new(completed), key);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mcp_progress_includes_percent_when_total_known() {
+        assert_eq!(format_mcp_progress(3.0, Some(12.0)), "3/12 (25%)");
+        assert_eq!(format_mcp_progress(7.0, None), "7");
+    }
+
+    #[test]
+    fn partial_output_keeps_most_recent_lines() {
+        let mut output = String::new();
+        for idx in 0..10 {
+            output = append_partial_output(&output, &format!("line {idx}"));
+        }
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), MCP_PARTIAL_OUTPUT_MAX_LINES);
+        assert_eq!(lines.first(), Some(&"line 4"));
+        assert_eq!(lines.last(), Some(&"line 9"));
+    }
+}
@@ -96,6 +96,25 @@ pub struct Cli {
     #[arg(long = "timing", default_value_t = false)]
     pub timing: bool,
 
+    /// Plain, linear output for terminal screen readers (same as
+    /// `-c tui.screen_reader=true`).
+    #[arg(long = "screen-reader", default_value_t = false)]
+    pub screen_reader: bool,
+
+    /// Drive the UI from a script of inputs instead of the terminal and
+    /// write the frames it requests as plain text. Scripted runs render
+    /// deterministically and never contact a model.
+    #[arg(long = "script", value_name = "FILE")]
+    pub script: Option<PathBuf>,
+
+    /// Write `--script` frames to this file instead of stdout.
+    #[arg(long = "script-output", value_name = "FILE", requires = "script")]
+    pub script_output: Option<PathBuf>,
+
+    /// Spinner frame shown during `--script` runs.
+    #[arg(long = "spinner-seed", value_name = "N", default_value_t = 0, requires = "script")]
+    pub spinner_seed: u64,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -0,0 +1,53 @@
+//! Deterministic render mode.
+//!
+//! Scripted runs (`--script`) must draw the same screen on every machine and
+//! every run. In this mode clocks read a fixed local timestamp, running
+//! timers read zero, shimmer and the intro animation are drawn in their
+//! resting state, and spinners stay on the frame picked by `--spinner-seed`.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeZone;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPINNER_SEED: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn set_enabled(enabled: bool, spinner_seed: u64) {
+    SPINNER_SEED.store(spinner_seed, Ordering::Relaxed);
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Index of the spinner frame drawn while deterministic.
+pub(crate) fn spinner_frame(frame_count: usize) -> usize {
+    (SPINNER_SEED.load(Ordering::Relaxed) % frame_count.max(1) as u64) as usize
+}
+
+/// The current local time, or 2025-01-01 12:00:00 while deterministic.
+pub(crate) fn now_local() -> DateTime<Local> {
+    if !enabled() {
+        return Local::now();
+    }
+    Local
+        .with_ymd_and_hms(2025, 1, 1, 12, 0, 0)
+        .earliest()
+        .unwrap_or_else(Local::now)
+}
+
+/// `start.elapsed()`, or zero while deterministic.
+pub(crate) fn elapsed(start: Instant) -> Duration {
+    if enabled() {
+        Duration::ZERO
+    } else {
+        start.elapsed()
+    }
+}
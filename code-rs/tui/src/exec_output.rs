@@ -0,0 +1,190 @@
+//! Decoding and previewing raw command output.
+//!
+//! Exec output is arbitrary bytes. Streaming chunks can split a UTF-8
+//! sequence, programs print Latin-1 or random bytes, and `cat` on a binary
+//! floods the cell with control characters. Text is decoded with invalid
+//! bytes shown as `\xNN` escapes, and output that is mostly not text is
+//! previewed as a hexdump instead of being rendered.
+
+/// Characters of output inspected by [`looks_binary`].
+const BINARY_SAMPLE_CHARS: usize = 8 * 1024;
+const HEXDUMP_ROWS: usize = 8;
+const HEXDUMP_ROW_BYTES: usize = 16;
+/// Bytes shown by [`hexdump_lines`].
+pub(crate) const HEXDUMP_PREVIEW_BYTES: usize = HEXDUMP_ROWS * HEXDUMP_ROW_BYTES;
+
+/// Decodes `bytes` as UTF-8, writing each invalid byte as `\xNN` rather
+/// than collapsing runs of them into `�`.
+fn decode_lossy(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        out.push_str(chunk.valid());
+        for byte in chunk.invalid() {
+            out.push_str(&format!("\\x{byte:02x}"));
+        }
+    }
+    out
+}
+
+/// The bytes behind text from [`decode_lossy`]: each `\xNN` escape of a
+/// non-ASCII byte turns back into that byte. ASCII is never escaped, so a
+/// literal `\x1b` in the output stays as written.
+pub(crate) fn raw_bytes(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match escaped_byte(&bytes[index..]) {
+            Some(byte) => {
+                out.push(byte);
+                index += 4;
+            }
+            None => {
+                out.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    out
+}
+
+/// The byte written as a `\xNN` escape at the start of `bytes`, if any.
+fn escaped_byte(bytes: &[u8]) -> Option<u8> {
+    let [b'\\', b'x', high, low, ..] = bytes else {
+        return None;
+    };
+    let digit = |byte: u8| match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        _ => None,
+    };
+    let byte = (digit(*high)? << 4) | digit(*low)?;
+    (byte >= 0x80).then_some(byte)
+}
+
+/// Decodes a stream of output chunks. A multi-byte sequence cut off at the
+/// end of a chunk is held back until the next one completes it.
+#[derive(Debug, Default)]
+pub(crate) struct Utf8StreamDecoder {
+    pending: Vec<u8>,
+}
+
+impl Utf8StreamDecoder {
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> String {
+        let mut bytes = std::mem::take(&mut self.pending);
+        bytes.extend_from_slice(chunk);
+        let keep = incomplete_suffix_len(&bytes);
+        self.pending = bytes.split_off(bytes.len() - keep);
+        decode_lossy(&bytes)
+    }
+}
+
+/// Length of a truncated but so far valid UTF-8 sequence at the end of
+/// `bytes`.
+fn incomplete_suffix_len(bytes: &[u8]) -> usize {
+    let tail_start = bytes.len().saturating_sub(3);
+    for start in (tail_start..bytes.len()).rev() {
+        let needed = match bytes[start] {
+            0x80..=0xBF => continue,
+            0xC2..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF4 => 4,
+            _ => return 0,
+        };
+        let have = bytes.len() - start;
+        return if have < needed { have } else { 0 };
+    }
+    0
+}
+
+/// Whether `text` is better shown as a hexdump: it contains NUL, or more
+/// than a tenth of its leading characters are controls, escaped invalid
+/// bytes or replacement characters (which the final, encoding-detected
+/// output can contain). Tabs, newlines, carriage returns, backspaces, form
+/// feeds and escape sequences are ordinary terminal output and do not count.
+pub(crate) fn looks_binary(text: &str) -> bool {
+    let end = text.char_indices().nth(BINARY_SAMPLE_CHARS).map_or(text.len(), |(i, _)| i);
+    let sample = &text[..end];
+    let escaped = sample
+        .match_indices("\\x")
+        .filter(|(index, _)| escaped_byte(&sample.as_bytes()[*index..]).is_some())
+        .count();
+    let mut sampled = 0usize;
+    let mut suspicious = escaped;
+    for ch in sample.chars() {
+        sampled += 1;
+        match ch {
+            '\0' => return true,
+            '\t' | '\n' | '\r' | '\u{08}' | '\u{0C}' | '\u{1B}' => {}
+            '\u{FFFD}' => suspicious += 1,
+            ch if ch.is_control() => suspicious += 1,
+            _ => {}
+        }
+    }
+    // Each four-character escape stands for a single byte.
+    let sampled = sampled.saturating_sub(escaped * 3);
+    sampled > 0 && suspicious * 10 > sampled
+}
+
+/// `xxd`-style rows for the first [`HEXDUMP_PREVIEW_BYTES`] of `bytes`.
+pub(crate) fn hexdump_lines(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEXDUMP_ROW_BYTES)
+        .take(HEXDUMP_ROWS)
+        .enumerate()
+        .map(|(row, chunk)| {
+            let mut hex = String::with_capacity(HEXDUMP_ROW_BYTES * 3 + 1);
+            for (index, byte) in chunk.iter().enumerate() {
+                if index == HEXDUMP_ROW_BYTES / 2 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{byte:02x} "));
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            let offset = row * HEXDUMP_ROW_BYTES;
+            let width = HEXDUMP_ROW_BYTES * 3 + 1;
+            format!("{offset:08x}  {hex:<width$} |{ascii}|")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn decodes_split_and_invalid_bytes_and_dumps_binary() {
+        let mut decoder = Utf8StreamDecoder::default();
+        let snowman = "☃".as_bytes();
+        assert_eq!(decoder.push(&[b'a', snowman[0], snowman[1]]), "a");
+        assert_eq!(decoder.push(&[snowman[2], 0xff, b'b']), "☃\\xffb");
+
+        assert!(!looks_binary("plain \u{1b}[31mred\u{1b}[0m text\r\n"));
+        assert!(looks_binary("\u{7f}ELF\u{0}\u{0}"));
+        assert!(looks_binary("\u{1}\u{2}\u{3}abcdefg"));
+        let random = decode_lossy(&[0x89, b'P', b'N', b'G', 0xa3, 0xfe, 0x81, b'x']);
+        assert!(looks_binary(&random));
+        assert_eq!(raw_bytes(&random), [0x89, b'P', b'N', b'G', 0xa3, 0xfe, 0x81, b'x']);
+        assert!(!looks_binary("printf '\\x1b[0m' # \\xff is escaped in source"));
+        assert_eq!(raw_bytes("\\x1b"), b"\\x1b");
+
+        let dump = hexdump_lines(b"\x7fELF\x02\x01\x01\x00 hello, world!\n");
+        assert_eq!(
+            dump,
+            vec![
+                "00000000  7f 45 4c 46 02 01 01 00  20 68 65 6c 6c 6f 2c 20  |.ELF.... hello, |",
+                "00000010  77 6f 72 6c 64 21 0a                              |world!.|",
+            ]
+        );
+    }
+}
@@ -1,4 +1,3 @@
-use chrono::Local;
 use chrono::Timelike;
 
 fn current_hour() -> u32 {
@@ -7,7 +6,7 @@ fn current_hour() -> u32 {
             return parsed.min(23);
         }
     }
-    Local::now().hour()
+    crate::deterministic::now_local().hour()
 }
 
 /// Build a time-aware placeholder like
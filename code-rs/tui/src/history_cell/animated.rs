@@ -156,7 +156,12 @@ impl HistoryCell for AnimatedWelcomeCell {
         let fade_duration = Duration::from_millis(800);
 
         if let Some(fade_time) = self.fade_start() {
-            let fade_elapsed = fade_time.elapsed();
+            // Deterministic renders show the fade as already finished.
+            let fade_elapsed = if crate::deterministic::enabled() {
+                fade_duration
+            } else {
+                fade_time.elapsed()
+            };
             if fade_elapsed < fade_duration && !self.faded_out.get() {
                 let fade_progress = fade_elapsed.as_secs_f32() / fade_duration.as_secs_f32();
                 let alpha = 1.0 - fade_progress;
@@ -178,7 +183,7 @@ impl HistoryCell for AnimatedWelcomeCell {
         let animation_duration = Duration::from_secs(2);
 
         let elapsed = self.start_time.elapsed();
-        let progress = if variant_changed {
+        let progress = if variant_changed || crate::deterministic::enabled() {
             1.0
         } else if elapsed < animation_duration && !self.completed.get() {
             elapsed.as_secs_f32() / animation_duration.as_secs_f32()
@@ -199,6 +204,9 @@ impl HistoryCell for AnimatedWelcomeCell {
     }
 
     fn is_animating(&self) -> bool {
+        if crate::deterministic::enabled() {
+            return false;
+        }
         let animation_duration = Duration::from_secs(2);
         if !self.completed.get() {
             if self.start_time.elapsed() < animation_duration {
@@ -39,6 +39,7 @@ const BORDER_BOTTOM: &str = "╰─";
 
 const MAX_ACTIONS: usize = 24;
 const MAX_CONSOLE: usize = 12;
+const MAX_DIAGNOSTICS: usize = 40;
 const ACTION_DISPLAY_HEAD: usize = 4;
 const ACTION_DISPLAY_TAIL: usize = 4;
 const MIN_SCREENSHOT_ROWS: usize = 6;
@@ -66,6 +67,9 @@ pub(crate) struct BrowserSessionCell {
     title: Option<String>,
     actions: Vec<BrowserAction>,
     console_messages: Vec<String>,
+    // Console problems and failed requests reported after page actions;
+    // collapsed to one line in the card, listed in full in the overlay.
+    diagnostics: Vec<String>,
     screenshot_path: Option<String>,
     screenshot_history: Vec<BrowserScreenshotRecord>,
     total_duration: Duration,
@@ -84,6 +88,7 @@ impl Clone for BrowserSessionCell {
             title: self.title.clone(),
             actions: self.actions.clone(),
             console_messages: self.console_messages.clone(),
+            diagnostics: self.diagnostics.clone(),
             screenshot_path: self.screenshot_path.clone(),
             screenshot_history: self.screenshot_history.clone(),
             total_duration: self.total_duration,
@@ -104,6 +109,7 @@ impl Default for BrowserSessionCell {
             title: None,
             actions: Vec::new(),
             console_messages: Vec::new(),
+            diagnostics: Vec::new(),
             screenshot_path: None,
             screenshot_history: Vec::new(),
             total_duration: Duration::ZERO,
@@ -233,6 +239,21 @@ impl BrowserSessionCell {
         }
     }
 
+    pub(crate) fn add_diagnostic(&mut self, line: String) {
+        if self.diagnostics.last().is_some_and(|last| last == &line) {
+            return;
+        }
+        self.diagnostics.push(line);
+        if self.diagnostics.len() > MAX_DIAGNOSTICS {
+            let overflow = self.diagnostics.len() - MAX_DIAGNOSTICS;
+            self.diagnostics.drain(0..overflow);
+        }
+    }
+
+    pub(crate) fn diagnostics(&self) -> &[String] {
+        &self.diagnostics
+    }
+
     pub(crate) fn record_screenshot(
         &mut self,
         timestamp: Duration,
@@ -560,6 +581,10 @@ impl BrowserSessionCell {
             rows.extend(console_rows);
         }
 
+        if let Some(row) = self.diagnostics_row(body_width, style, indent_cols, right_padding) {
+            rows.push(row);
+        }
+
         if let Some(layout) = screenshot_layout.as_mut() {
             layout.start_row = content_start;
             let existing = rows.len().saturating_sub(content_start);
@@ -616,6 +641,46 @@ impl BrowserSessionCell {
             .collect()
     }
 
+    fn diagnostics_row(
+        &self,
+        body_width: usize,
+        style: &CardStyle,
+        indent_cols: usize,
+        right_padding: usize,
+    ) -> Option<CardRow> {
+        let console = self
+            .diagnostics
+            .iter()
+            .filter(|line| line.starts_with("console "))
+            .count();
+        let network = self
+            .diagnostics
+            .iter()
+            .filter(|line| line.starts_with("request failed"))
+            .count();
+        let mut parts = Vec::new();
+        if console > 0 {
+            let noun = if console == 1 { "problem" } else { "problems" };
+            parts.push(format!("{console} console {noun}"));
+        }
+        if network > 0 {
+            let noun = if network == 1 { "request" } else { "requests" };
+            parts.push(format!("{network} failed {noun}"));
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        let text = format!("▸ Diagnostics: {} (Ctrl+B to expand)", parts.join(" · "));
+        Some(self.body_text_row(
+            text,
+            body_width,
+            style,
+            Style::default().fg(colors::warning()),
+            indent_cols,
+            right_padding,
+        ))
+    }
+
     fn render_action_entry_rows(
         &self,
         entry: &ActionEntry,
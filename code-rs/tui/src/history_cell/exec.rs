@@ -541,6 +541,18 @@ impl ExecCell {
         ))
     }
 
+    fn redaction_line(&self) -> Option<Line<'static>> {
+        let count = self.record.redactions;
+        if count == 0 {
+            return None;
+        }
+        let noun = if count == 1 { "secret" } else { "secrets" };
+        Some(Line::styled(
+            format!("{count} {noun} redacted"),
+            Style::default().fg(crate::colors::warning()),
+        ))
+    }
+
     fn wait_extras(&self, state: &ExecWaitState) -> Vec<Line<'static>> {
         let mut extra_lines: Vec<Line<'static>> = Vec::new();
         if let Some(summary_line) = self.wait_summary_line(state) {
@@ -628,7 +640,8 @@ impl ExecCell {
         };
 
         if self.output.is_some() {
-            let extra_lines = self.wait_extras(&wait_state);
+            let mut extra_lines = self.wait_extras(&wait_state);
+            extra_lines.extend(self.redaction_line());
             if !extra_lines.is_empty() {
                 let is_blank_line = |line: &Line<'static>| {
                     line.spans
@@ -775,6 +788,9 @@ impl ExecCell {
         if !matches!(self.record.status, ExecStatus::Running) {
             return None;
         }
+        if crate::deterministic::enabled() {
+            return Some(Duration::ZERO);
+        }
         match SystemTime::now().duration_since(self.record.started_at) {
             Ok(duration) => Some(duration),
             Err(_) => self.start_time.map(|start| start.elapsed()),
@@ -861,6 +877,7 @@ fn build_exec_record(
                 working_dir: None,
                 env: Vec::new(),
                 tags: Vec::new(),
+                redactions: 0,
             }
         }
         None => ExecRecord {
@@ -881,6 +898,7 @@ fn build_exec_record(
             working_dir: None,
             env: Vec::new(),
             tags: Vec::new(),
+            redactions: 0,
         },
     }
 }
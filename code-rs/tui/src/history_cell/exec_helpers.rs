@@ -1927,13 +1927,13 @@ fn new_parsed_command(
                         None => "Running...".to_string(),
                     };
                     if let Some(start) = start_time {
-                        let elapsed = start.elapsed();
+                        let elapsed = crate::deterministic::elapsed(start);
                         message = format!("{message} ({})", format_duration(elapsed));
                     }
                     running_status = Some(running_status_line(message));
                 } else {
                     let duration_suffix = if let Some(start) = start_time {
-                        let elapsed = start.elapsed();
+                        let elapsed = crate::deterministic::elapsed(start);
                         format!(" ({})", format_duration(elapsed))
                     } else {
                         String::new()
@@ -2376,7 +2376,7 @@ fn new_exec_command_generic(
     if render_running_header {
         let mut message = "Running...".to_string();
         if let Some(start) = start_time {
-            let elapsed = start.elapsed();
+            let elapsed = crate::deterministic::elapsed(start);
             message = format!("{message} ({})", format_duration(elapsed));
         }
         running_status = Some(running_status_line(message));
@@ -1,3 +1,7 @@
+use crate::exec_output::HEXDUMP_PREVIEW_BYTES;
+use crate::exec_output::hexdump_lines;
+use crate::exec_output::looks_binary;
+use crate::exec_output::raw_bytes;
 use crate::sanitize::Mode as SanitizeMode;
 use crate::sanitize::Options as SanitizeOptions;
 use crate::sanitize::sanitize_for_tui;
@@ -244,6 +248,9 @@ pub(crate) fn normalize_overwrite_sequences(input: &str) -> String {
 }
 
 pub(crate) fn build_preview_lines(text: &str, _include_left_pipe: bool) -> Vec<Line<'static>> {
+    if looks_binary(text) {
+        return binary_preview_lines(text, Style::default());
+    }
     // Prefer UI‑themed JSON highlighting when the (ANSI‑stripped) text parses as JSON.
     let stripped_plain = sanitize_for_tui(
         text,
@@ -321,6 +328,19 @@ pub(crate) fn build_preview_lines(text: &str, _include_left_pipe: bool) -> Vec<L
     out
 }
 
+/// Hexdump of the start of output that is not text, so control bytes never
+/// reach the renderer.
+fn binary_preview_lines(text: &str, style: Style) -> Vec<Line<'static>> {
+    let bytes = raw_bytes(text);
+    let shown = bytes.len().min(HEXDUMP_PREVIEW_BYTES);
+    let mut out = vec![Line::styled(
+        format!("binary output ({} bytes), first {shown} shown", bytes.len()),
+        Style::default().fg(crate::colors::text_dim()),
+    )];
+    out.extend(hexdump_lines(&bytes).into_iter().map(|row| Line::styled(row, style)));
+    out
+}
+
 fn clip_preview_text(text: &str, limit: usize) -> (String, bool) {
     let char_count = text.chars().count();
     if char_count <= limit {
@@ -368,6 +388,14 @@ pub(crate) fn output_lines(
                 Style::default().fg(crate::colors::error()),
             ));
         }
+        if looks_binary(stderr) {
+            lines.extend(binary_preview_lines(
+                stderr,
+                Style::default().fg(crate::colors::error()),
+            ));
+            lines.push(Line::from(""));
+            return lines;
+        }
         let stderr_norm = sanitize_for_tui(
             &normalize_overwrite_sequences(stderr),
             SanitizeMode::AnsiPreserving,
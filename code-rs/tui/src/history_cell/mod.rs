@@ -105,6 +105,7 @@ pub(crate) use loading::new_loading_cell;
 pub(crate) use patch::{new_patch_apply_failure, new_patch_event, PatchSummaryCell};
 pub(crate) use plain::{
     new_error_event,
+    new_lsp_diagnostics,
     new_model_output,
     new_popular_commands_notice,
     new_prompts_output,
@@ -112,6 +113,7 @@ pub(crate) use plain::{
     new_reasoning_output,
     new_session_info,
     new_status_output,
+    new_test_results,
     new_user_prompt,
     new_warning_event,
     plain_message_state_from_lines,
@@ -132,6 +134,7 @@ pub(crate) use tool_factory::{
     new_completed_custom_tool_call,
     new_completed_mcp_tool_call,
     new_completed_web_fetch_tool_call,
+    new_completed_web_search_tool_call,
     new_running_browser_tool_call,
     new_running_custom_tool_call,
     new_running_mcp_tool_call,
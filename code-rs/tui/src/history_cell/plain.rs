@@ -15,6 +15,8 @@ use crate::history::state::{
     TextTone,
 };
 use crate::colors;
+use crate::i18n::tr;
+use crate::i18n::tr_args;
 use crate::sanitize::Mode as SanitizeMode;
 use crate::sanitize::Options as SanitizeOptions;
 use crate::sanitize::sanitize_for_tui;
@@ -793,10 +795,11 @@ pub(crate) fn new_status_output(
     lines.push(Line::from(""));
 
     // 🔧 Configuration
-    lines.push(Line::from(vec!["🔧 ".into(), "Configuration".bold()]));
+    lines.push(Line::from(vec!["🔧 ".into(), tr("status_output.configuration").bold()]));
 
     // Prepare config summary with custom prettification
     let summary_entries = create_config_summary_entries(config);
+    let bullet = |key: &'static str| -> Span<'static> { format!("  • {}: ", tr(key)).into() };
     let summary_map: HashMap<String, String> = summary_entries
         .iter()
         .map(|(key, value)| (key.to_string(), value.clone()))
@@ -823,12 +826,12 @@ pub(crate) fn new_status_output(
         config.model.clone()
     };
     lines.push(Line::from(vec![
-        "  • Name: ".into(),
+        bullet("status_output.name"),
         formatted_model.into(),
     ]));
     let provider_disp = pretty_provider_name(&config.model_provider_id);
     lines.push(Line::from(vec![
-        "  • Provider: ".into(),
+        bullet("status_output.provider"),
         provider_disp.into(),
     ]));
 
@@ -836,14 +839,14 @@ pub(crate) fn new_status_output(
     let reff = lookup("reasoning effort");
     if !reff.is_empty() {
         lines.push(Line::from(vec![
-            "  • Reasoning Effort: ".into(),
+            bullet("status_output.reasoning_effort"),
             title_case(&reff).into(),
         ]));
     }
     let rsum = lookup("reasoning summaries");
     if !rsum.is_empty() {
         lines.push(Line::from(vec![
-            "  • Reasoning Summaries: ".into(),
+            bullet("status_output.reasoning_summaries"),
             title_case(&rsum).into(),
         ]));
     }
@@ -851,10 +854,10 @@ pub(crate) fn new_status_output(
     lines.push(Line::from(""));
 
     // 🔁 Model routing
-    lines.push(Line::from(vec!["🔁 ".into(), "Model Routing".bold()]));
+    lines.push(Line::from(vec!["🔁 ".into(), tr("status_output.model_routing").bold()]));
     let requested_display = requested_model.unwrap_or(config.model.as_str());
     lines.push(Line::from(vec![
-        "  • Requested model: ".into(),
+        bullet("status_output.requested_model"),
         requested_display.to_string().into(),
     ]));
     if let Some(response_model) = latest_response_model {
@@ -862,33 +865,39 @@ pub(crate) fn new_status_output(
         let (match_marker, match_label, match_style) = if same_model {
             (
                 "✓",
-                "requested model matches response",
+                tr("status_output.model_matches"),
                 Style::default().fg(colors::success()),
             )
         } else {
             (
                 "✗",
-                "requested model does not match response",
+                tr("status_output.model_mismatch"),
                 Style::default().fg(colors::error()),
             )
         };
         lines.push(Line::from(vec![
-            "  • Latest response model: ".into(),
+            bullet("status_output.latest_response_model"),
             response_model.to_string().into(),
         ]));
         lines.push(Line::from(vec![
-            "  • Match: ".into(),
+            bullet("status_output.match"),
             Span::styled(format!("{match_marker} {match_label}"), match_style),
         ]));
     } else {
-        lines.push(Line::from("  • Latest response model: unavailable"));
-        lines.push(Line::from("  • Match: ? waiting for a completed response"));
+        lines.push(Line::from(vec![
+            bullet("status_output.latest_response_model"),
+            tr("status_output.response_unavailable").into(),
+        ]));
+        lines.push(Line::from(vec![
+            bullet("status_output.match"),
+            format!("? {}", tr("status_output.waiting_for_response")).into(),
+        ]));
     }
 
     lines.push(Line::from(""));
 
     // 🔐 Authentication
-    lines.push(Line::from(vec!["🔐 ".into(), "Authentication".bold()]));
+    lines.push(Line::from(vec!["🔐 ".into(), tr("status_output.authentication").bold()]));
     {
         use code_login::AuthMode;
         use code_login::CodexAuth;
@@ -913,19 +922,67 @@ pub(crate) fn new_status_output(
                             .or_else(|| std::env::var(OPENAI_API_KEY_ENV_VAR).ok())
                             .map(|k| key_suffix(&k))
                             .unwrap_or_else(|| "????".to_string());
-                    lines.push(Line::from(format!("  • Method: API key (…{suffix})")));
+                    lines.push(Line::from(vec![
+                        bullet("status_output.method"),
+                        tr_args("status_output.method_api_key", &[("suffix", suffix.as_str())])
+                            .into(),
+                    ]));
                 }
                 AuthMode::ChatGPT | AuthMode::ChatgptAuthTokens | AuthMode::Headers => {
                     let account_id = auth
                         .get_account_id()
                         .unwrap_or_else(|| "unknown".to_string());
-                    lines.push(Line::from(format!(
-                        "  • Method: ChatGPT account (account_id: {account_id})"
-                    )));
+                    lines.push(Line::from(vec![
+                        bullet("status_output.method"),
+                        tr_args(
+                            "status_output.method_chatgpt",
+                            &[("account_id", account_id.as_str())],
+                        )
+                        .into(),
+                    ]));
+                    if let Some(expires_at) = auth.access_token_expires_at() {
+                        let line = match (expires_at - chrono::Utc::now()).to_std() {
+                            Ok(remaining) => {
+                                let duration = code_common::elapsed::format_duration(remaining);
+                                format!(
+                                    "  • {}",
+                                    tr_args(
+                                        "status_output.token_expires_in",
+                                        &[("duration", duration.as_str())],
+                                    )
+                                )
+                            }
+                            Err(_) => format!("  • {}", tr("status_output.token_expired")),
+                        };
+                        lines.push(Line::from(line));
+                    }
                 }
             },
             _ => {
-                lines.push(Line::from("  • Method: unauthenticated"));
+                lines.push(Line::from(vec![
+                    bullet("status_output.method"),
+                    tr("status_output.method_unauthenticated").into(),
+                ]));
+            }
+        }
+
+        if let Some(account) = code_core::auth_accounts::get_active_account_id(&config.code_home)
+            .ok()
+            .flatten()
+            .and_then(|id| code_core::auth_accounts::find_account(&config.code_home, &id).ok())
+            .flatten()
+        {
+            if let Some(profile) = account.profile {
+                lines.push(Line::from(vec![bullet("status_output.profile"), profile.into()]));
+            }
+            if let Some(organization) = account.organization {
+                lines.push(Line::from(vec![
+                    bullet("status_output.organization"),
+                    organization.into(),
+                ]));
+            }
+            if let Some(project) = account.project {
+                lines.push(Line::from(vec![bullet("status_output.project"), project.into()]));
             }
         }
     }
@@ -933,17 +990,23 @@ pub(crate) fn new_status_output(
     lines.push(Line::from(""));
 
     // 📊 Token Usage
-    lines.push(Line::from(vec!["📊 ".into(), "Token Usage".bold()]));
+    lines.push(Line::from(vec!["📊 ".into(), tr("status_output.token_usage").bold()]));
     // Input: <input> [+ <cached> cached]
     let mut input_line_spans: Vec<Span<'static>> = vec![
-        "  • Input: ".into(),
+        bullet("status_output.input"),
         format_with_separators_u64(last_usage.non_cached_input()).into(),
     ];
     if last_usage.cached_input_tokens > 0 {
         input_line_spans.push(
             format!(
-                " (+ {} cached)",
-                format_with_separators_u64(last_usage.cached_input_tokens)
+                " {}",
+                tr_args(
+                    "status_output.cached",
+                    &[(
+                        "count",
+                        format_with_separators_u64(last_usage.cached_input_tokens).as_str(),
+                    )],
+                )
             )
             .into(),
         );
@@ -951,16 +1014,16 @@ pub(crate) fn new_status_output(
     lines.push(Line::from(input_line_spans));
     // Output: <output>
     lines.push(Line::from(vec![
-        "  • Output: ".into(),
+        bullet("status_output.output"),
         format_with_separators_u64(last_usage.output_tokens).into(),
     ]));
     // Total: <total>
     lines.push(Line::from(vec![
-        "  • Total: ".into(),
+        bullet("status_output.total"),
         format_with_separators_u64(last_usage.blended_total()).into(),
     ]));
     lines.push(Line::from(vec![
-        "  • Session total: ".into(),
+        bullet("status_output.session_total"),
         format_with_separators_u64(total_usage.blended_total()).into(),
     ]));
 
@@ -971,7 +1034,7 @@ pub(crate) fn new_status_output(
 
     if context_window.is_some() || max_output_tokens.is_some() || auto_compact_limit.is_some() {
         lines.push(Line::from(""));
-        lines.push(Line::from(vec!["📐 ".into(), "Model Limits".bold()]));
+        lines.push(Line::from(vec!["📐 ".into(), tr("status_output.model_limits").bold()]));
 
         if let Some(context_window) = context_window {
             let used = last_usage.tokens_in_context_window().min(context_window);
@@ -981,18 +1044,23 @@ pub(crate) fn new_status_output(
                 0.0
             };
             lines.push(Line::from(format!(
-                "  • Context window: {} used of {} ({:.0}% full)",
-                format_with_separators_u64(used),
-                format_with_separators_u64(context_window),
-                percent_full
+                "  • {}",
+                tr_args(
+                    "status_output.context_window_full",
+                    &[
+                        ("used", format_with_separators_u64(used).as_str()),
+                        ("total", format_with_separators_u64(context_window).as_str()),
+                        ("percent", format!("{percent_full:.0}").as_str()),
+                    ],
+                )
             )));
         }
 
         if let Some(max_output_tokens) = max_output_tokens {
-            lines.push(Line::from(format!(
-                "  • Max output tokens: {}",
-                format_with_separators_u64(max_output_tokens)
-            )));
+            lines.push(Line::from(vec![
+                bullet("status_output.max_output_tokens"),
+                format_with_separators_u64(max_output_tokens).into(),
+            ]));
         }
 
         match auto_compact_limit {
@@ -1000,12 +1068,19 @@ pub(crate) fn new_status_output(
                 let limit_u64 = limit as u64;
                 let remaining = limit_u64.saturating_sub(total_usage.total_tokens);
                 lines.push(Line::from(format!(
-                    "  • Auto-compact threshold: {} ({} remaining)",
-                    format_with_separators_u64(limit_u64),
-                    format_with_separators_u64(remaining)
+                    "  • {}",
+                    tr_args(
+                        "status_output.auto_compact_threshold",
+                        &[
+                            ("limit", format_with_separators_u64(limit_u64).as_str()),
+                            ("remaining", format_with_separators_u64(remaining).as_str()),
+                        ],
+                    )
                 )));
                 if total_usage.total_tokens > limit_u64 {
-                    lines.push(Line::from("    • Compacting will trigger on the next turn".dim()));
+                    lines.push(Line::from(
+                        format!("    • {}", tr("status_output.compacting_next_turn")).dim(),
+                    ));
                 }
             }
             _ => {
@@ -1019,21 +1094,38 @@ pub(crate) fn new_status_output(
                             (remaining as f64 / window as f64) * 100.0
                         };
                         lines.push(Line::from(format!(
-                            "  • Context window: {} used of {} ({:.0}% left)",
-                            format_with_separators_u64(used),
-                            format_with_separators_u64(window),
-                            percent_left
+                            "  • {}",
+                            tr_args(
+                                "status_output.context_window_left",
+                                &[
+                                    ("used", format_with_separators_u64(used).as_str()),
+                                    ("total", format_with_separators_u64(window).as_str()),
+                                    ("percent", format!("{percent_left:.0}").as_str()),
+                                ],
+                            )
+                        )));
+                        lines.push(Line::from(format!(
+                            "  • {}",
+                            tr_args(
+                                "status_output.before_overflow",
+                                &[("count", format_with_separators_u64(remaining).as_str())],
+                            )
                         )));
                         lines.push(Line::from(format!(
-                            "  • {} tokens before overflow",
-                            format_with_separators_u64(remaining)
+                            "  • {}",
+                            tr("status_output.compaction_after_overflow")
                         )));
-                        lines.push(Line::from("  • Auto-compaction runs after overflow errors".to_string()));
                     } else {
-                        lines.push(Line::from("  • Auto-compaction runs after overflow errors".to_string()));
+                        lines.push(Line::from(format!(
+                            "  • {}",
+                            tr("status_output.compaction_after_overflow")
+                        )));
                     }
                 } else {
-                    lines.push(Line::from("  • Auto-compaction runs after overflow errors".to_string()));
+                    lines.push(Line::from(format!(
+                        "  • {}",
+                        tr("status_output.compaction_after_overflow")
+                    )));
                 }
             }
         }
@@ -1056,6 +1148,109 @@ pub(crate) fn new_warning_event(message: String) -> PlainMessageState {
     plain_message_state_from_lines(lines, HistoryCellType::Notice)
 }
 
+/// Parsed test run shown under the exec cell: the counts, then each failure
+/// with its location and the first lines of its message.
+pub(crate) fn new_test_results(report: &code_core::test_results::TestReport) -> PlainMessageState {
+    const MESSAGE_PREVIEW_LINES: usize = 3;
+    let dim = Style::default().fg(crate::colors::text_dim());
+    let error = Style::default().fg(crate::colors::error());
+    let (mark, mark_style) = if report.failed == 0 {
+        ("✓", Style::default().fg(crate::colors::success()))
+    } else {
+        ("✗", error)
+    };
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{mark} "), mark_style),
+            Span::styled(
+                "Test results",
+                Style::default()
+                    .fg(crate::colors::keyword())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" · {} · {}", report.runner.label(), report.summary()), dim),
+        ]),
+    ];
+    for failure in &report.failures {
+        let mut spans = vec![Span::styled(format!("  ✗ {}", failure.name), error)];
+        if let Some(location) = &failure.location {
+            spans.push(Span::styled(format!("  {location}"), dim));
+        }
+        lines.push(Line::from(spans));
+        lines.extend(
+            failure
+                .message
+                .lines()
+                .take(MESSAGE_PREVIEW_LINES)
+                .map(|line| Line::from(Span::styled(format!("      {line}"), dim))),
+        );
+    }
+    let unlisted = report.failed.saturating_sub(report.failures.len());
+    if unlisted > 0 && !report.failures.is_empty() {
+        lines.push(Line::from(Span::styled(format!("  … and {unlisted} more"), dim)));
+    }
+    plain_message_state_from_lines(lines, HistoryCellType::Notice)
+}
+
+/// Language server check of an applied patch: the new errors and warnings,
+/// errors first, or a note that there were none.
+pub(crate) fn new_lsp_diagnostics(
+    event: &code_core::protocol::LspDiagnosticsEvent,
+) -> PlainMessageState {
+    let dim = Style::default().fg(crate::colors::text_dim());
+    let error = Style::default().fg(crate::colors::error());
+    let warning = Style::default().fg(crate::colors::warning());
+    let (mark, mark_style) = if event.total == 0 {
+        ("✓", Style::default().fg(crate::colors::success()))
+    } else {
+        ("✗", error)
+    };
+    let summary = match event.total {
+        0 => "no new errors or warnings".to_string(),
+        total => format!("{total} new"),
+    };
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{mark} "), mark_style),
+            Span::styled(
+                "Diagnostics",
+                Style::default()
+                    .fg(crate::colors::keyword())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" · {} · {summary}", event.servers.join(", ")), dim),
+        ]),
+    ];
+    for diagnostic in &event.diagnostics {
+        let (label, style) = match diagnostic.severity {
+            code_core::protocol::LspSeverity::Error => ("error", error),
+            code_core::protocol::LspSeverity::Warning => ("warning", warning),
+        };
+        let message = diagnostic.message.lines().next().unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {label} "), style),
+            Span::raw(message.to_string()),
+            Span::styled(
+                format!("  {}:{}:{}", diagnostic.path, diagnostic.line, diagnostic.column),
+                dim,
+            ),
+        ]));
+    }
+    let unlisted = event.total.saturating_sub(event.diagnostics.len());
+    if unlisted > 0 {
+        lines.push(Line::from(Span::styled(format!("  … and {unlisted} more"), dim)));
+    }
+    lines.extend(
+        event
+            .failures
+            .iter()
+            .map(|failure| Line::from(Span::styled(format!("  {failure}"), warning))),
+    );
+    plain_message_state_from_lines(lines, HistoryCellType::Notice)
+}
+
 pub(crate) fn new_prompts_output() -> PlainMessageState {
     let lines: Vec<Line<'static>> = vec![
         Line::from("/prompts").fg(crate::colors::keyword()),
@@ -165,7 +165,8 @@ impl RunningToolCallCell {
 
     fn spinner_frame(&self) -> &'static str {
         const FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
-        let idx = ((self.start_clock.elapsed().as_millis() / 100) as usize) % FRAMES.len();
+        let elapsed = crate::deterministic::elapsed(self.start_clock);
+        let idx = ((elapsed.as_millis() / 100) as usize) % FRAMES.len();
         FRAMES[idx]
     }
 
@@ -372,6 +373,9 @@ impl RunningToolCallCell {
     }
 
     fn elapsed_duration(&self) -> Duration {
+        if crate::deterministic::enabled() {
+            return Duration::ZERO;
+        }
         SystemTime::now()
             .duration_since(self.state.started_at)
             .unwrap_or_else(|_| self.start_clock.elapsed())
@@ -455,16 +459,34 @@ impl HistoryCell for RunningToolCallCell {
 }
 
 fn render_arguments(arguments: &[ToolArgument]) -> Vec<Line<'static>> {
-    arguments.iter().map(render_argument).collect()
+    arguments.iter().flat_map(render_argument).collect()
 }
 
-fn render_argument(arg: &ToolArgument) -> Line<'static> {
+fn render_argument(arg: &ToolArgument) -> Vec<Line<'static>> {
     let dim_style = Style::default().fg(crate::colors::text_dim());
     let mut spans = vec![Span::styled("└ ", dim_style)];
     spans.push(Span::styled(
         format!("{}: ", arg.name),
         dim_style,
     ));
+    // Multi-line text (e.g. partial MCP output) continues on indented lines.
+    if let ArgumentValue::Text(text) = &arg.value
+        && text.contains('\n')
+    {
+        let mut lines = Vec::new();
+        let mut text_lines = text.lines();
+        if let Some(first) = text_lines.next() {
+            spans.push(Span::styled(first.to_string(), Style::default().fg(crate::colors::text())));
+        }
+        lines.push(Line::from(spans));
+        for rest in text_lines {
+            lines.push(Line::from(vec![
+                Span::styled("  ", dim_style),
+                Span::styled(rest.to_string(), Style::default().fg(crate::colors::text())),
+            ]));
+        }
+        return lines;
+    }
     let value_span = match &arg.value {
         ArgumentValue::Text(text) => Span::styled(text.clone(), Style::default().fg(crate::colors::text())),
         ArgumentValue::Json(json) => {
@@ -474,5 +496,5 @@ fn render_argument(arg: &ToolArgument) -> Line<'static> {
         ArgumentValue::Secret => Span::styled("(secret)".to_string(), Style::default().fg(crate::colors::text_dim())),
     };
     spans.push(value_span);
-    Line::from(spans)
+    vec![Line::from(spans)]
 }
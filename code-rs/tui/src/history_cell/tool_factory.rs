@@ -37,6 +37,7 @@ use crate::history::compat::{
     ToolResultPreview,
     ToolStatus as HistoryToolStatus,
 };
+use crate::i18n::tr_args;
 use ::image::ImageReader;
 use crate::util::buffer::fill_rect;
 
@@ -89,6 +90,13 @@ fn browser_running_title(tool_name: &str) -> &'static str {
         "browser_click" => "Clicking...",
         "browser_type" => "Typing...",
         "browser_key" => "Sending key...",
+        "browser_fill" => "Filling...",
+        "browser_select" => "Selecting...",
+        "browser_submit" => "Submitting...",
+        "browser_query" => "Querying...",
+        "browser_eval" => "Evaluating...",
+        "browser_viewport" => "Resizing...",
+        "browser_download" => "Downloading...",
         "browser_javascript" => "Running JavaScript...",
         "browser_scroll" => "Scrolling...",
         "browser_open" => "Opening...",
@@ -97,6 +105,9 @@ fn browser_running_title(tool_name: &str) -> &'static str {
         "browser_history" => "Navigating...",
         "browser_inspect" => "Inspecting...",
         "browser_console" => "Reading console...",
+        "browser_network" => "Reading network...",
+        "browser_diff" => "Comparing...",
+        "browser_login" => "Signing in...",
         "browser_move" => "Moving...",
         _ => "Working...",
     }
@@ -466,13 +477,15 @@ fn gh_run_wait_title(result: &str, success: bool) -> String {
     }
 }
 
-/// Completed web_fetch tool call with markdown rendering of the `markdown` field.
+/// Completed web_fetch (or fetch_url) tool call with markdown rendering of the
+/// `markdown` field.
 // Web fetch preview sizing: show 10 lines at the start and 5 at the end.
 const WEB_FETCH_HEAD_LINES: usize = 10;
 const WEB_FETCH_TAIL_LINES: usize = 5;
 
 pub(crate) fn new_completed_web_fetch_tool_call(
     cfg: &Config,
+    tool_name: &str,
     args: Option<String>,
     duration: Duration,
     success: bool,
@@ -492,10 +505,11 @@ pub(crate) fn new_completed_web_fetch_tool_call(
         ])
     };
 
+    let invocation_name = if tool_name == "fetch_url" { "fetch_url" } else { "web_fetch" };
     let invocation_str = if let Some(args) = args {
-        format!("{}({})", "web_fetch", args)
+        format!("{invocation_name}({args})")
     } else {
-        format!("{}()", "web_fetch")
+        format!("{invocation_name}()")
     };
 
     // Header/preamble (no border)
@@ -513,7 +527,22 @@ pub(crate) fn new_completed_web_fetch_tool_call(
     let mut body_lines: Vec<Line<'static>> = Vec::new();
     if !result.is_empty() {
         if let Ok(value) = serde_json::from_str::<serde_json::Value>(&result) {
-            if let Some(md) = value.get("markdown").and_then(|v| v.as_str()) {
+            if value.get("cached").and_then(serde_json::Value::as_bool) == Some(true) {
+                pre_lines.push(Line::from("cached · not modified since last fetch".dim()));
+            }
+            let pdf_pages = value.get("pdf_pages").and_then(serde_json::Value::as_u64);
+            if let (Some(pages), Some(md)) =
+                (pdf_pages, value.get("markdown").and_then(|v| v.as_str()))
+            {
+                // PDFs: page count up front, then a collapsed head/tail of the text.
+                let noun = if pages == 1 { "page" } else { "pages" };
+                pre_lines.push(Line::from(format!("PDF · {pages} {noun}").dim()));
+                let mut pv =
+                    select_preview_from_plain_text(md, WEB_FETCH_HEAD_LINES, WEB_FETCH_TAIL_LINES);
+                dim_webfetch_emphasis_and_links(&mut pv);
+                body_lines.extend(pv);
+                appended_markdown = true;
+            } else if let Some(md) = value.get("markdown").and_then(|v| v.as_str()) {
                 // Build a smarter sectioned preview from the raw markdown.
                 let mut sect = build_web_fetch_sectioned_preview(md, cfg);
                 dim_webfetch_emphasis_and_links(&mut sect);
@@ -549,6 +578,95 @@ pub(crate) fn new_completed_web_fetch_tool_call(
     }
 }
 
+/// Completed function-style web_search call: one numbered entry per result
+/// with its URL and snippet, so the user sees what the agent may open next.
+pub(crate) fn new_completed_web_search_tool_call(
+    args: Option<String>,
+    duration: Duration,
+    success: bool,
+    result: String,
+) -> WebFetchToolCell {
+    let duration = format_duration(duration);
+    let (status_str, status_color) = if success {
+        ("Complete", crate::colors::success())
+    } else {
+        ("Error", crate::colors::error())
+    };
+    let mut pre_lines: Vec<Line<'static>> = vec![
+        Line::from(vec![
+            Span::styled(status_str, Style::default().fg(status_color)),
+            format!(", duration: {duration}").dim(),
+        ]),
+        Line::styled(
+            format!("web_search({})", args.unwrap_or_default()),
+            Style::default()
+                .fg(crate::colors::text_dim())
+                .add_modifier(Modifier::ITALIC),
+        ),
+    ];
+
+    let mut body_lines: Vec<Line<'static>> = Vec::new();
+    let parsed = serde_json::from_str::<serde_json::Value>(&result).ok();
+    let results = parsed
+        .as_ref()
+        .and_then(|value| value.get("results"))
+        .and_then(serde_json::Value::as_array);
+    if let (Some(value), Some(results)) = (parsed.as_ref(), results) {
+        let provider = value
+            .get("provider")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("search");
+        let noun = if results.len() == 1 { "result" } else { "results" };
+        pre_lines.push(Line::from(format!("{provider} · {} {noun}", results.len()).dim()));
+        for (index, item) in results.iter().enumerate() {
+            let field = |key: &str| {
+                item.get(key)
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            body_lines.push(Line::from(vec![
+                format!("{}. ", index + 1).dim(),
+                Span::styled(
+                    field("title"),
+                    Style::default()
+                        .fg(crate::colors::text_bright())
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            body_lines.push(Line::styled(
+                format!("   {}", field("url")),
+                Style::default().fg(crate::colors::primary()),
+            ));
+            let snippet = field("snippet");
+            if !snippet.is_empty() {
+                body_lines.push(Line::from(format!("   {snippet}").dim()));
+            }
+        }
+    } else if !result.is_empty() {
+        body_lines.extend(select_preview_from_plain_text(
+            &result,
+            WEB_FETCH_HEAD_LINES,
+            WEB_FETCH_TAIL_LINES,
+        ));
+    }
+
+    pre_lines.push(Line::from(""));
+    if !body_lines.is_empty() {
+        body_lines.push(Line::from(""));
+    }
+
+    WebFetchToolCell {
+        pre_lines,
+        body_lines,
+        state: if success {
+            ToolCellStatus::Success
+        } else {
+            ToolCellStatus::Failed
+        },
+    }
+}
+
 // ==================== WebFetchToolCell ====================
 
 pub(crate) struct WebFetchToolCell {
@@ -849,6 +967,13 @@ fn browser_tool_title(tool_name: &str) -> &'static str {
         "browser_click" => "Browser Click",
         "browser_type" => "Browser Type",
         "browser_key" => "Browser Key",
+        "browser_fill" => "Browser Fill",
+        "browser_select" => "Browser Select",
+        "browser_submit" => "Browser Submit",
+        "browser_query" => "Browser Query",
+        "browser_eval" => "Browser Eval",
+        "browser_viewport" => "Browser Viewport",
+        "browser_download" => "Browser Download",
         "browser_javascript" => "Browser JavaScript",
         "browser_scroll" => "Browser Scroll",
         "browser_open" => "Browser Open",
@@ -858,6 +983,9 @@ fn browser_tool_title(tool_name: &str) -> &'static str {
         "browser_history" => "Browser History",
         "browser_inspect" => "Browser Inspect",
         "browser_console" => "Browser Console",
+        "browser_network" => "Browser Network",
+        "browser_diff" => "Browser Diff",
+        "browser_login" => "Browser Login",
         "browser_cdp" => "Browser CDP",
         "browser_move" => "Browser Move",
         _ => "Browser Tool",
@@ -1185,8 +1313,8 @@ pub(crate) fn new_completed_mcp_tool_call(
                     mcp_types::ContentBlock::ImageContent(_) => {
                         preview_lines.push("<image content>".to_string());
                     }
-                    mcp_types::ContentBlock::AudioContent(_) => {
-                        preview_lines.push("<audio content>".to_string());
+                    mcp_types::ContentBlock::AudioContent(audio) => {
+                        preview_lines.push(format!("<audio content: {}>", audio.mime_type));
                     }
                     mcp_types::ContentBlock::EmbeddedResource(resource) => {
                         let uri = match resource.resource {
@@ -1205,7 +1333,7 @@ pub(crate) fn new_completed_mcp_tool_call(
             }
         }
         Err(e) => {
-            error_message = Some(format!("Error: {e}"));
+            error_message = Some(tr_args("error.message", &[("message", e.as_str())]));
         }
     }
 
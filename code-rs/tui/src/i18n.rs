@@ -0,0 +1,127 @@
+//! Locale resources for user-facing TUI strings.
+//!
+//! Each locale is a flat JSON map from dotted keys to text under
+//! `assets/locales/`. The active locale comes from the `language` config key
+//! and is picked once at startup. Lookups fall back to English and then to
+//! the key itself, so a locale may translate only part of the catalog.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// Shipped locales; English comes first and is the fallback.
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../assets/locales/en.json")),
+    ("es", include_str!("../assets/locales/es.json")),
+];
+
+static CATALOGS: LazyLock<Vec<HashMap<String, String>>> = LazyLock::new(|| {
+    LOCALES
+        .iter()
+        .map(|(code, raw)| {
+            serde_json::from_str(raw).unwrap_or_else(|err| {
+                tracing::warn!("ignoring malformed {code} locale: {err}");
+                HashMap::new()
+            })
+        })
+        .collect()
+});
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Select the locale for the rest of the process. `auto` follows
+/// `LC_ALL`, `LC_MESSAGES` or `LANG`; anything unknown keeps English.
+pub(crate) fn set_language(language: Option<&str>) {
+    let index = match language.map(str::trim).filter(|lang| !lang.is_empty()) {
+        None => 0,
+        Some(lang) if lang.eq_ignore_ascii_case("auto") => {
+            env_language().as_deref().and_then(locale_index).unwrap_or(0)
+        }
+        Some(lang) => locale_index(lang).unwrap_or_else(|| {
+            tracing::warn!("unsupported language `{lang}`; using English");
+            0
+        }),
+    };
+    ACTIVE.store(index, Ordering::Relaxed);
+}
+
+/// Text for `key` in the active locale.
+pub(crate) fn tr(key: &'static str) -> &'static str {
+    lookup(ACTIVE.load(Ordering::Relaxed), key)
+}
+
+/// Text for `key` with each `{name}` placeholder replaced by its value.
+pub(crate) fn tr_args(key: &'static str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(tr(key).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+fn lookup(index: usize, key: &'static str) -> &'static str {
+    let catalogs: &'static [HashMap<String, String>] = &CATALOGS;
+    catalogs
+        .get(index)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| catalogs.first().and_then(|catalog| catalog.get(key)))
+        .map_or(key, String::as_str)
+}
+
+/// Index of the shipped locale matching a tag such as `es`, `es-MX` or
+/// `es_ES.UTF-8`.
+fn locale_index(language: &str) -> Option<usize> {
+    let base = language.split(['_', '-', '.']).next().unwrap_or(language);
+    LOCALES.iter().position(|(code, _)| code.eq_ignore_ascii_case(base))
+}
+
+fn env_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}'))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn locales_cover_english_keys_and_placeholders() {
+        let english = &CATALOGS[0];
+        assert!(!english.is_empty());
+        for (index, (code, _)) in LOCALES.iter().enumerate().skip(1) {
+            let catalog = &CATALOGS[index];
+            for (key, text) in catalog {
+                let source = english.get(key);
+                assert!(source.is_some(), "{code} has unknown key {key}");
+                assert_eq!(
+                    source.map(|source| placeholders(source)),
+                    Some(placeholders(text)),
+                    "{code} {key}"
+                );
+            }
+            let missing: Vec<&String> =
+                english.keys().filter(|key| !catalog.contains_key(*key)).collect();
+            assert_eq!(missing, Vec::<&String>::new(), "{code} is missing keys");
+        }
+    }
+
+    #[test]
+    fn lookup_falls_back_to_english_then_key() {
+        assert_eq!(locale_index("es_ES.UTF-8"), Some(1));
+        assert_eq!(locale_index("es-MX"), Some(1));
+        assert_eq!(locale_index("fr"), None);
+        assert_eq!(lookup(1, "status.thinking"), "Pensando");
+        assert_eq!(lookup(0, "status.thinking"), "Thinking");
+        assert_eq!(lookup(7, "status.thinking"), "Thinking");
+        assert_eq!(lookup(1, "missing.key"), "missing.key");
+    }
+}
@@ -0,0 +1,88 @@
+//! Key bindings from `[keys]`, shared by the composer and approval dialogs.
+
+use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use code_core::keymap::KeyAction;
+use code_core::keymap::KeyChord;
+use code_core::keymap::KeyName;
+use code_core::keymap::Keymap;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+
+static KEYMAP: LazyLock<RwLock<Keymap>> = LazyLock::new(|| RwLock::new(Keymap::default()));
+
+pub(crate) fn set(keymap: Keymap) {
+    *KEYMAP.write().unwrap_or_else(PoisonError::into_inner) = keymap;
+}
+
+/// Whether `event` is one of the chords bound to `action`.
+pub(crate) fn matches(action: KeyAction, event: &KeyEvent) -> bool {
+    let keymap = KEYMAP.read().unwrap_or_else(PoisonError::into_inner);
+    keymap.chords(action).iter().any(|chord| chord_matches(chord, event))
+}
+
+/// The first chord bound to `action`, for hints such as `(y)`.
+pub(crate) fn label(action: KeyAction) -> Option<String> {
+    let keymap = KEYMAP.read().unwrap_or_else(PoisonError::into_inner);
+    keymap.chords(action).first().map(ToString::to_string)
+}
+
+fn chord_matches(chord: &KeyChord, event: &KeyEvent) -> bool {
+    let modifiers = event.modifiers;
+    if modifiers.contains(KeyModifiers::CONTROL) != chord.ctrl
+        || modifiers.contains(KeyModifiers::ALT) != chord.alt
+    {
+        return false;
+    }
+    // Terminals disagree on whether Shift is reported with a shifted
+    // character, so character keys compare case-insensitively.
+    if let (KeyName::Char(expected), KeyCode::Char(actual)) = (chord.key, event.code) {
+        return expected.eq_ignore_ascii_case(&actual);
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) != chord.shift {
+        return false;
+    }
+    match (chord.key, event.code) {
+        (KeyName::Enter, KeyCode::Enter)
+        | (KeyName::Backspace, KeyCode::Backspace)
+        | (KeyName::Delete, KeyCode::Delete)
+        | (KeyName::Esc, KeyCode::Esc)
+        | (KeyName::Up, KeyCode::Up)
+        | (KeyName::Down, KeyCode::Down)
+        | (KeyName::Left, KeyCode::Left)
+        | (KeyName::Right, KeyCode::Right)
+        | (KeyName::Home, KeyCode::Home)
+        | (KeyName::End, KeyCode::End)
+        | (KeyName::PageUp, KeyCode::PageUp)
+        | (KeyName::PageDown, KeyCode::PageDown)
+        | (KeyName::Insert, KeyCode::Insert)
+        | (KeyName::Tab, KeyCode::Tab) => true,
+        (KeyName::Tab, KeyCode::BackTab) => chord.shift,
+        (KeyName::F(expected), KeyCode::F(actual)) => expected == actual,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chords_match_key_events() {
+        let chord = |s: &str| s.parse::<KeyChord>().expect("chord");
+        let shift_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT);
+        assert!(chord_matches(&chord("shift+enter"), &shift_enter));
+        assert!(!chord_matches(&chord("enter"), &shift_enter));
+
+        let upper_y = KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT);
+        assert!(chord_matches(&chord("y"), &upper_y));
+        assert!(!chord_matches(&chord("ctrl+y"), &upper_y));
+
+        let ctrl_j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert!(chord_matches(&chord("ctrl+j"), &ctrl_j));
+        assert!(!chord_matches(&chord("alt+j"), &ctrl_j));
+    }
+}
@@ -66,8 +66,10 @@ mod cli;
 mod common;
 mod colors;
 pub mod card_theme;
+mod deterministic;
 mod diff_render;
 mod exec_command;
+mod exec_output;
 mod external_editor;
 mod file_search;
 pub mod gradient_background;
@@ -78,7 +80,9 @@ mod auto_drive_style;
 mod header_wave;
 mod history_cell;
 mod history;
+mod i18n;
 mod insert_history;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -92,6 +96,7 @@ pub mod public_widgets;
 mod render;
 mod model_migration;
 // mod scroll_view; // Orphaned after trait-based HistoryCell migration
+mod screen_reader;
 mod session_log;
 mod shimmer;
 mod slash_command;
@@ -100,6 +105,7 @@ pub mod resume;
 mod streaming;
 mod sanitize;
 mod layout_consts;
+mod terminal_activity;
 mod terminal_info;
 // mod text_block; // Orphaned after trait-based HistoryCell migration
 mod text_formatting;
@@ -117,6 +123,7 @@ mod tui_event_extensions;
 #[cfg(feature = "code-fork")]
 mod foundation;
 mod ui_consts;
+mod ui_script;
 mod user_approval_widget;
 mod height_manager;
 mod clipboard_copy;
@@ -487,6 +494,7 @@ pub async fn run_main(
     };
 
     code_core::config::migrate_legacy_log_dirs(&code_home);
+    let config_migration_notices = code_core::config::migrate_on_startup(&code_home);
 
     let housekeeping_home = code_home.clone();
     let housekeeping_stop = Arc::new(AtomicBool::new(false));
@@ -633,6 +641,18 @@ pub async fn run_main(
             }
         }
     }
+    config.config_diagnostics.splice(0..0, config_migration_notices);
+    if cli.screen_reader {
+        config.tui.screen_reader = true;
+    }
+    if config.tui.screen_reader {
+        // Screen readers follow the terminal's own scrollback.
+        config.tui.alternate_screen = false;
+    }
+    screen_reader::set_enabled(config.tui.screen_reader);
+    deterministic::set_enabled(cli.script.is_some(), cli.spinner_seed);
+    keymap::set(config.keymap.clone());
+    i18n::set_language(config.language.as_deref());
 
     let startup_footer_notice = None;
 
@@ -742,14 +762,18 @@ pub async fn run_main(
         None
     };
 
-    let run_result = run_ratatui_app(
-        cli,
-        config,
-        should_show_trust_screen,
-        startup_footer_notice,
-        latest_upgrade_version,
-        theme_configured_explicitly,
-    );
+    let run_result = match cli.script.clone() {
+        Some(script) => ui_script::run(&script, cli.script_output.as_deref(), config)
+            .map(|()| empty_exit_summary()),
+        None => run_ratatui_app(
+            cli,
+            config,
+            should_show_trust_screen,
+            startup_footer_notice,
+            latest_upgrade_version,
+            theme_configured_explicitly,
+        ),
+    };
 
     housekeeping_stop.store(true, Ordering::Relaxed);
     if let Some(handle) = housekeeping_handle {
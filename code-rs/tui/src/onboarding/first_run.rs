@@ -0,0 +1,449 @@
+//! First-run setup shown when `config.toml` does not exist yet.
+//!
+//! Walks through the default model, the approval/sandbox posture, the theme
+//! and an optional AGENTS.md, then writes a commented `config.toml` so the
+//! choices are easy to find and edit later.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use code_common::model_presets::builtin_model_presets;
+use code_common::model_presets::ModelPreset;
+use code_core::config_types::ReasoningEffort;
+use code_core::config_types::ThemeName;
+use code_core::model_family::derive_default_model_family;
+use code_core::model_family::find_family_for_model;
+use code_core::protocol::AskForApproval;
+use code_core::protocol::SandboxPolicy;
+use code_protocol::config_types::SandboxMode;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget, WidgetRef, Wrap};
+
+use crate::app::ChatWidgetArgs;
+use crate::colors;
+use crate::onboarding::onboarding_screen::KeyboardHandler;
+use crate::onboarding::onboarding_screen::StepStateProvider;
+
+use super::onboarding_screen::StepState;
+
+const INIT_PROMPT: &str = include_str!("../../prompt_for_init_command.md");
+
+/// Approval and sandbox combinations offered during setup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AccessPosture {
+    ReadOnly,
+    WriteWithApproval,
+    FullAccess,
+}
+
+impl AccessPosture {
+    const ALL: [AccessPosture; 3] = [
+        AccessPosture::ReadOnly,
+        AccessPosture::WriteWithApproval,
+        AccessPosture::FullAccess,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            AccessPosture::ReadOnly => {
+                "Read only - Code asks before editing files or running commands"
+            }
+            AccessPosture::WriteWithApproval => {
+                "Workspace write - edit and run in this folder, ask before anything else"
+            }
+            AccessPosture::FullAccess => "Full access - never ask, no sandbox",
+        }
+    }
+
+    fn approval_policy(self) -> AskForApproval {
+        match self {
+            AccessPosture::ReadOnly | AccessPosture::WriteWithApproval => AskForApproval::OnRequest,
+            AccessPosture::FullAccess => AskForApproval::Never,
+        }
+    }
+
+    fn sandbox_mode(self) -> SandboxMode {
+        match self {
+            AccessPosture::ReadOnly => SandboxMode::ReadOnly,
+            AccessPosture::WriteWithApproval => SandboxMode::WorkspaceWrite,
+            AccessPosture::FullAccess => SandboxMode::DangerFullAccess,
+        }
+    }
+
+    fn sandbox_policy(self) -> SandboxPolicy {
+        match self {
+            AccessPosture::ReadOnly => SandboxPolicy::new_read_only_policy(),
+            AccessPosture::WriteWithApproval => SandboxPolicy::new_workspace_write_policy(),
+            AccessPosture::FullAccess => SandboxPolicy::DangerFullAccess,
+        }
+    }
+}
+
+const THEMES: [(ThemeName, &str, &str); 4] = [
+    (ThemeName::LightPhoton, "light-photon", "Light"),
+    (ThemeName::DarkCarbonNight, "dark-carbon-night", "Dark"),
+    (ThemeName::LightPhotonAnsi16, "light-photon-ansi16", "Light (16 colors)"),
+    (ThemeName::DarkCarbonAnsi16, "dark-carbon-ansi16", "Dark (16 colors)"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SetupStage {
+    Model,
+    Access,
+    Theme,
+    AgentsMd,
+    Done,
+}
+
+/// What the user picked; rendered into `config.toml` when setup finishes.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct FirstRunChoices {
+    pub model: String,
+    pub reasoning_effort: ReasoningEffort,
+    pub access: AccessPosture,
+    pub theme: usize,
+}
+
+pub(crate) struct FirstRunSetupWidget {
+    pub code_home: PathBuf,
+    pub models: Vec<ModelPreset>,
+    pub offer_agents_md: bool,
+    stage: SetupStage,
+    highlighted: usize,
+    choices: FirstRunChoices,
+    pub error: Option<String>,
+    pub chat_widget_args: Arc<Mutex<ChatWidgetArgs>>,
+}
+
+impl FirstRunSetupWidget {
+    pub(crate) fn new(
+        code_home: PathBuf,
+        cwd: &Path,
+        chat_widget_args: Arc<Mutex<ChatWidgetArgs>>,
+    ) -> Self {
+        let models = builtin_model_presets(None, false);
+        let highlighted = models.iter().position(|preset| preset.is_default).unwrap_or(0);
+        let (model, reasoning_effort) = match models.get(highlighted) {
+            Some(preset) => (preset.model.clone(), preset.default_reasoning_effort.into()),
+            None => match chat_widget_args.lock() {
+                Ok(args) => (args.config.model.clone(), args.config.model_reasoning_effort),
+                Err(_) => (String::new(), ReasoningEffort::default()),
+            },
+        };
+        let offer_agents_md = !cwd.join("AGENTS.md").exists();
+        Self {
+            code_home,
+            models,
+            offer_agents_md,
+            stage: SetupStage::Model,
+            highlighted,
+            choices: FirstRunChoices {
+                model,
+                reasoning_effort,
+                access: AccessPosture::WriteWithApproval,
+                theme: 0,
+            },
+            error: None,
+            chat_widget_args,
+        }
+    }
+
+    fn option_count(&self) -> usize {
+        match self.stage {
+            SetupStage::Model => self.models.len(),
+            SetupStage::Access => AccessPosture::ALL.len(),
+            SetupStage::Theme => THEMES.len(),
+            SetupStage::AgentsMd => 2,
+            SetupStage::Done => 0,
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        if index >= self.option_count() {
+            return;
+        }
+        match self.stage {
+            SetupStage::Model => {
+                let preset = &self.models[index];
+                self.choices.model = preset.model.clone();
+                self.choices.reasoning_effort = preset.default_reasoning_effort.into();
+                self.advance(SetupStage::Access);
+            }
+            SetupStage::Access => {
+                self.choices.access = AccessPosture::ALL[index];
+                self.advance(SetupStage::Theme);
+            }
+            SetupStage::Theme => {
+                self.choices.theme = index;
+                crate::theme::switch_theme(THEMES[index].0);
+                if self.offer_agents_md {
+                    self.advance(SetupStage::AgentsMd);
+                } else {
+                    self.finish(false);
+                }
+            }
+            SetupStage::AgentsMd => self.finish(index == 0),
+            SetupStage::Done => {}
+        }
+    }
+
+    fn advance(&mut self, stage: SetupStage) {
+        self.stage = stage;
+        self.highlighted = match stage {
+            SetupStage::Access => 1,
+            _ => 0,
+        };
+    }
+
+    fn finish(&mut self, generate_agents_md: bool) {
+        if let Err(err) = write_first_run_config(&self.code_home, &self.choices) {
+            tracing::error!("Failed to write config.toml: {err}");
+            self.error = Some(format!("Could not write config.toml: {err}"));
+            return;
+        }
+
+        if let Ok(mut args) = self.chat_widget_args.lock() {
+            let config = &mut args.config;
+            if !config.model.eq_ignore_ascii_case(&self.choices.model) {
+                config.model = self.choices.model.clone();
+                config.model_family = find_family_for_model(&config.model)
+                    .unwrap_or_else(|| derive_default_model_family(&config.model));
+            }
+            config.model_explicit = true;
+            config.model_reasoning_effort = self.choices.reasoning_effort;
+            config.approval_policy = self.choices.access.approval_policy();
+            config.sandbox_policy = self.choices.access.sandbox_policy();
+            config.tui.theme.name = THEMES[self.choices.theme].0;
+            if generate_agents_md && args.initial_prompt.is_none() {
+                args.initial_prompt = Some(INIT_PROMPT.to_string());
+            }
+        }
+
+        self.error = None;
+        self.stage = SetupStage::Done;
+    }
+}
+
+/// Write the chosen settings as a commented `config.toml`. An existing file
+/// is left alone so nothing the user wrote is lost.
+pub(crate) fn write_first_run_config(
+    code_home: &Path,
+    choices: &FirstRunChoices,
+) -> io::Result<()> {
+    let path = code_home.join("config.toml");
+    if path.exists() {
+        tracing::info!("config.toml already exists; keeping it");
+        return Ok(());
+    }
+    std::fs::create_dir_all(code_home)?;
+    std::fs::write(path, first_run_config_toml(choices))
+}
+
+fn first_run_config_toml(choices: &FirstRunChoices) -> String {
+    let FirstRunChoices { model, reasoning_effort, access, theme } = choices;
+    let approval_policy = access.approval_policy();
+    let sandbox_mode = access.sandbox_mode();
+    let theme = THEMES[*theme].1;
+    format!(
+        "\
+# Code configuration, created by the first-run setup.
+# Every option is described in docs/config.md.
+
+# Default model and reasoning effort (\"minimal\", \"low\", \"medium\", \"high\",
+# \"xhigh\"). /model picks another one.
+model = \"{model}\"
+model_reasoning_effort = \"{reasoning_effort}\"
+
+# When Code stops to ask before acting: \"untrusted\", \"on-failure\",
+# \"on-request\" or \"never\".
+approval_policy = \"{approval_policy}\"
+
+# What commands may touch: \"read-only\", \"workspace-write\" or
+# \"danger-full-access\". /settings changes both.
+sandbox_mode = \"{sandbox_mode}\"
+
+# Named sets of settings to switch between with --profile or /profile.
+# [profiles.readonly]
+# approval_policy = \"on-request\"
+# sandbox_mode = \"read-only\"
+
+[tui.theme]
+# Any theme listed by /theme.
+name = \"{theme}\"
+"
+    )
+}
+
+impl WidgetRef for &FirstRunSetupWidget {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let header_style = Style::default()
+            .fg(colors::success())
+            .add_modifier(Modifier::BOLD);
+        let mut lines: Vec<Line> = vec![
+            Line::from(Span::styled("Let's set up Code", header_style)),
+            Line::from(""),
+        ];
+
+        let answered = |label: &str, value: String| -> Line<'static> {
+            Line::from(vec![
+                Span::raw(format!("  {label}: ")),
+                Span::styled(value, Style::default().fg(colors::success())),
+            ])
+        };
+        if self.stage != SetupStage::Model {
+            lines.push(answered(
+                "Model",
+                format!("{} ({})", self.choices.model, self.choices.reasoning_effort),
+            ));
+        }
+        if matches!(self.stage, SetupStage::Theme | SetupStage::AgentsMd | SetupStage::Done) {
+            lines.push(answered("Access", self.choices.access.sandbox_mode().to_string()));
+        }
+        if matches!(self.stage, SetupStage::AgentsMd | SetupStage::Done) {
+            lines.push(answered("Theme", THEMES[self.choices.theme].2.to_string()));
+        }
+
+        let (question, options): (&str, Vec<String>) = match self.stage {
+            SetupStage::Model => (
+                "Which model should Code use by default?",
+                self.models
+                    .iter()
+                    .map(|preset| format!("{} - {}", preset.display_name, preset.description))
+                    .collect(),
+            ),
+            SetupStage::Access => (
+                "How much should Code be allowed to do without asking?",
+                AccessPosture::ALL.iter().map(|posture| posture.label().to_string()).collect(),
+            ),
+            SetupStage::Theme => (
+                "Pick a theme",
+                THEMES.iter().map(|(_, _, label)| label.to_string()).collect(),
+            ),
+            SetupStage::AgentsMd => (
+                "This folder has no AGENTS.md. Have Code write one for it?",
+                vec![
+                    "Yes, explore the project and write AGENTS.md first".to_string(),
+                    "No, skip it".to_string(),
+                ],
+            ),
+            SetupStage::Done => {
+                lines.push(Line::from(format!(
+                    "  Saved to {}",
+                    self.code_home.join("config.toml").display()
+                )));
+                Paragraph::new(lines)
+                    .wrap(Wrap { trim: false })
+                    .render(area, buf);
+                return;
+            }
+        };
+
+        if lines.len() > 2 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(format!("  {question}")));
+        lines.push(Line::from(""));
+        for (idx, text) in options.into_iter().enumerate() {
+            if idx == self.highlighted {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("> {}. ", idx + 1),
+                        Style::default().fg(colors::primary()),
+                    ),
+                    Span::styled(text, Style::default().fg(colors::primary())),
+                ]));
+            } else {
+                lines.push(Line::from(format!("  {}. {}", idx + 1, text)));
+            }
+        }
+        lines.push(Line::from(""));
+        if let Some(error) = &self.error {
+            lines.push(Line::from(format!("  {error}")).fg(colors::error()));
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            Span::raw("  Press "),
+            Span::styled("Enter", Style::default().fg(colors::function())),
+            Span::raw(" to continue"),
+        ]));
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+impl KeyboardHandler for FirstRunSetupWidget {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.highlighted = self.highlighted.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.highlighted + 1 < self.option_count() {
+                    self.highlighted += 1;
+                }
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                self.select(usize::from(c as u8 - b'1'));
+            }
+            KeyCode::Enter => self.select(self.highlighted),
+            _ => {}
+        }
+    }
+}
+
+impl StepStateProvider for FirstRunSetupWidget {
+    fn get_step_state(&self) -> StepState {
+        match self.stage {
+            SetupStage::Done => StepState::Complete,
+            _ => StepState::InProgress,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use code_core::config::ConfigToml;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn first_run_config_is_valid_toml_with_the_chosen_settings() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let choices = FirstRunChoices {
+            model: "gpt-5.5".to_string(),
+            reasoning_effort: ReasoningEffort::High,
+            access: AccessPosture::ReadOnly,
+            theme: 1,
+        };
+        write_first_run_config(home.path(), &choices).expect("write config");
+
+        let contents =
+            std::fs::read_to_string(home.path().join("config.toml")).expect("read config");
+        assert!(contents.starts_with("# Code configuration"));
+        let parsed: ConfigToml = toml::from_str(&contents).expect("parse config");
+        assert_eq!(parsed.model.as_deref(), Some("gpt-5.5"));
+        assert_eq!(parsed.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(parsed.approval_policy, Some(AskForApproval::OnRequest));
+        assert_eq!(parsed.sandbox_mode, Some(SandboxMode::ReadOnly));
+        assert_eq!(
+            parsed.tui.map(|tui| tui.theme.name),
+            Some(ThemeName::DarkCarbonNight)
+        );
+
+        // A second run never clobbers what is already there.
+        std::fs::write(home.path().join("config.toml"), "model = \"o3\"\n").expect("write");
+        write_first_run_config(home.path(), &choices).expect("keep config");
+        let kept = std::fs::read_to_string(home.path().join("config.toml")).expect("read");
+        assert_eq!(kept, "model = \"o3\"\n");
+    }
+}
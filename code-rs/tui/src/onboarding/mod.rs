@@ -1,5 +1,6 @@
 mod auth;
 mod continue_to_chat;
+mod first_run;
 pub mod onboarding_screen;
 mod trust_directory;
 mod welcome;
@@ -13,6 +13,7 @@ use crate::app_event_sender::AppEventSender;
 use crate::onboarding::auth::AuthModeWidget;
 use crate::onboarding::auth::SignInState;
 use crate::onboarding::continue_to_chat::ContinueToChatWidget;
+use crate::onboarding::first_run::FirstRunSetupWidget;
 use crate::onboarding::trust_directory::TrustDirectorySelection;
 use crate::onboarding::trust_directory::TrustDirectoryWidget;
 use crate::onboarding::welcome::WelcomeWidget;
@@ -25,6 +26,7 @@ use std::sync::Mutex;
 enum Step {
     Welcome(WelcomeWidget),
     Auth(AuthModeWidget),
+    FirstRun(FirstRunSetupWidget),
     TrustDirectory(TrustDirectoryWidget),
     ContinueToChat(ContinueToChatWidget),
 }
@@ -55,6 +57,7 @@ pub(crate) struct OnboardingScreenArgs {
     pub cwd: PathBuf,
     pub show_trust_screen: bool,
     pub show_login_screen: bool,
+    pub show_first_run_setup: bool,
     pub login_status: LoginStatus,
 }
 
@@ -67,6 +70,7 @@ impl OnboardingScreen {
             cwd,
             show_trust_screen,
             show_login_screen,
+            show_first_run_setup,
             login_status,
         } = args;
         let shared_chat_args = Arc::new(Mutex::new(chat_widget_args));
@@ -86,6 +90,13 @@ impl OnboardingScreen {
                 chat_widget_args: shared_chat_args.clone(),
             }))
         }
+        if show_first_run_setup {
+            steps.push(Step::FirstRun(FirstRunSetupWidget::new(
+                code_home.clone(),
+                &cwd,
+                shared_chat_args.clone(),
+            )));
+        }
         let is_git_repo = get_git_repo_root(&cwd).is_some();
         let highlighted = if is_git_repo {
             TrustDirectorySelection::Trust
@@ -260,6 +271,7 @@ impl KeyboardHandler for Step {
         match self {
             Step::Welcome(_) | Step::ContinueToChat(_) => (),
             Step::Auth(widget) => widget.handle_key_event(key_event),
+            Step::FirstRun(widget) => widget.handle_key_event(key_event),
             Step::TrustDirectory(widget) => widget.handle_key_event(key_event),
         }
     }
@@ -270,6 +282,7 @@ impl StepStateProvider for Step {
         match self {
             Step::Welcome(w) => w.get_step_state(),
             Step::Auth(w) => w.get_step_state(),
+            Step::FirstRun(w) => w.get_step_state(),
             Step::TrustDirectory(w) => w.get_step_state(),
             Step::ContinueToChat(w) => w.get_step_state(),
         }
@@ -285,6 +298,9 @@ impl WidgetRef for Step {
             Step::Auth(widget) => {
                 widget.render_ref(area, buf);
             }
+            Step::FirstRun(widget) => {
+                widget.render_ref(area, buf);
+            }
             Step::TrustDirectory(widget) => {
                 widget.render_ref(area, buf);
             }
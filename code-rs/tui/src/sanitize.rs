@@ -1,10 +1,15 @@
 use std::sync::OnceLock;
 use unicode_width::UnicodeWidthChar;
 
+/// Longest SGR parameter list passed through to the ANSI parser; anything
+/// longer is not a real style and only costs parse time.
+const MAX_SGR_PARAMS_LEN: usize = 64;
+
 /// Sanitization mode:
 /// - Plain: remove all control/escape sequences; output is pure printable text.
-/// - AnsiPreserving: keep CSI (ESC '[' ... final) so color/style can be parsed
-///   later by the ANSI parser; strip OSC/DCS/APC/PM/SOS and other controls.
+/// - AnsiPreserving: keep SGR (ESC '[' params 'm') so color/style can be parsed
+///   later by the ANSI parser; strip cursor movement, screen/mode switches and
+///   other CSI, OSC/DCS/APC/PM/SOS and other controls.
 #[derive(Clone, Copy)]
 pub enum Mode {
     Plain,
@@ -113,17 +118,28 @@ fn strip_specials(input: String, mode: Mode, debug_markers: bool) -> String {
                 match it.peek().copied() {
                     // CSI: ESC [ ... final (0x40..0x7E)
                     Some('[') => {
-                        // Keep if preserving ANSI; drop otherwise
+                        // Keep SGR if preserving ANSI; drop everything else
                         if let Mode::AnsiPreserving = mode {
-                            out.push('\u{001B}');
-                            out.push('[');
                             it.next();
+                            let mut params = String::new();
+                            let mut final_byte = None;
                             while let Some(&c) = it.peek() {
-                                let u = c as u32;
-                                let is_final = (0x40..=0x7E).contains(&u);
-                                out.push(c);
                                 it.next();
-                                if is_final { break; }
+                                if (0x40..=0x7E).contains(&(c as u32)) {
+                                    final_byte = Some(c);
+                                    break;
+                                }
+                                params.push(c);
+                            }
+                            let is_sgr = final_byte == Some('m')
+                                && params.len() <= MAX_SGR_PARAMS_LEN
+                                && params
+                                    .chars()
+                                    .all(|c| c.is_ascii_digit() || c == ';' || c == ':');
+                            if is_sgr {
+                                out.push_str("\u{001B}[");
+                                out.push_str(&params);
+                                out.push('m');
                             }
                         } else {
                             // Consume but do not emit
@@ -0,0 +1,310 @@
+//! Screen-reader mode.
+//!
+//! With `tui.screen_reader` (or `--screen-reader`) the TUI stays in the
+//! terminal's normal buffer, stops animating spinners and shimmer text, and
+//! appends each finished history item to the scrollback as plain lines with a
+//! spoken label ("Command:", "Output:", "Patch:") in place of gutter icons,
+//! borders and colors.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use code_core::plan_tool::StepStatus;
+use code_core::protocol::FileChange;
+
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::history::state::{
+    BulletMarker,
+    DiffLineKind,
+    ExecRecord,
+    ExecStatus,
+    ExploreSummary,
+    HistoryRecord,
+    InlineSpan,
+    MessageLine,
+    MessageLineKind,
+    PatchEventType,
+    PlainMessageKind,
+    ToolStatus,
+};
+use crate::i18n::tr_args;
+use crate::sanitize::{sanitize_for_tui, Mode as SanitizeMode, Options as SanitizeOptions};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether `record` can still change. While a turn runs, later items wait
+/// behind it so the transcript is read in order.
+pub(crate) fn is_pending(record: &HistoryRecord) -> bool {
+    match record {
+        HistoryRecord::Exec(exec) => exec.status == ExecStatus::Running,
+        HistoryRecord::MergedExec(merged) => {
+            merged.segments.iter().any(|exec| exec.status == ExecStatus::Running)
+        }
+        HistoryRecord::ToolCall(tool) => tool.status == ToolStatus::Running,
+        HistoryRecord::Patch(patch) => {
+            matches!(patch.patch_type, PatchEventType::ApplyBegin { .. })
+        }
+        HistoryRecord::AssistantStream(stream) => stream.in_progress,
+        HistoryRecord::Reasoning(reasoning) => reasoning.in_progress,
+        _ => false,
+    }
+}
+
+/// Labeled plain-text lines for a finished record; empty when it has nothing
+/// worth reading aloud.
+pub(crate) fn linear_lines(record: &HistoryRecord) -> Vec<String> {
+    let mut out = Vec::new();
+    match record {
+        HistoryRecord::PlainMessage(message) => {
+            let label = match message.kind {
+                PlainMessageKind::User => "You:",
+                PlainMessageKind::Assistant => "Assistant:",
+                PlainMessageKind::Tool => "Tool:",
+                PlainMessageKind::Error => "Error:",
+                PlainMessageKind::Plain
+                | PlainMessageKind::Background
+                | PlainMessageKind::Notice => "Note:",
+            };
+            out.push(label.to_string());
+            if let Some(header) = &message.header {
+                out.push(header.label.clone());
+            }
+            push_message_lines(&mut out, &message.lines);
+        }
+        HistoryRecord::AssistantMessage(message) => {
+            out.push("Assistant:".to_string());
+            push_text(&mut out, &message.markdown);
+        }
+        HistoryRecord::Exec(exec) => push_exec(&mut out, exec),
+        HistoryRecord::MergedExec(merged) => {
+            for exec in &merged.segments {
+                push_exec(&mut out, exec);
+            }
+        }
+        HistoryRecord::Explore(explore) => {
+            out.push("Explored:".to_string());
+            for entry in &explore.entries {
+                out.push(match &entry.summary {
+                    ExploreSummary::Search { query, path } => format!(
+                        "Searched {} in {}",
+                        query.as_deref().unwrap_or("files"),
+                        path.as_deref().unwrap_or("the workspace"),
+                    ),
+                    ExploreSummary::List { path } => {
+                        format!("Listed {}", path.as_deref().unwrap_or("the workspace"))
+                    }
+                    ExploreSummary::Read { display_path, range, .. } => match range {
+                        Some((start, end)) => format!("Read {display_path} lines {start} to {end}"),
+                        None => format!("Read {display_path}"),
+                    },
+                    ExploreSummary::Count { target, .. } => {
+                        format!("Counted {}", target.as_deref().unwrap_or("lines"))
+                    }
+                    ExploreSummary::Command { display, .. } => format!("Ran {display}"),
+                    ExploreSummary::Fallback { text } => text.clone(),
+                });
+            }
+        }
+        HistoryRecord::ToolCall(tool) => {
+            let outcome = match tool.status {
+                ToolStatus::Failed => " (failed)",
+                ToolStatus::Running | ToolStatus::Success => "",
+            };
+            out.push(format!("Tool: {}{outcome}", tool.title));
+            if let Some(preview) = &tool.result_preview {
+                out.push("Output:".to_string());
+                out.extend(preview.lines.iter().map(|line| plain(line)));
+            }
+            if let Some(error) = &tool.error_message {
+                out.push(tr_args("error.message", &[("message", error.as_str())]));
+            }
+        }
+        HistoryRecord::PlanUpdate(plan) => {
+            out.push(format!(
+                "Plan: {} ({} of {} done)",
+                plan.name, plan.progress.completed, plan.progress.total
+            ));
+            for step in &plan.steps {
+                let status = match step.status {
+                    StepStatus::Completed => "done",
+                    StepStatus::InProgress => "in progress",
+                    StepStatus::Pending => "pending",
+                };
+                out.push(format!("{}, {status}", step.description));
+            }
+        }
+        HistoryRecord::Patch(patch) => {
+            out.push(match patch.patch_type {
+                PatchEventType::ApprovalRequest => "Patch, awaiting approval:",
+                PatchEventType::ApplyBegin { .. } | PatchEventType::ApplySuccess => "Patch:",
+                PatchEventType::ApplyFailure => "Patch failed:",
+            }
+            .to_string());
+            let mut changes: Vec<_> = patch.changes.iter().collect();
+            changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (path, change) in changes {
+                let path = path.display();
+                out.push(match change {
+                    FileChange::Add { .. } => format!("Added {path}"),
+                    FileChange::Delete => format!("Deleted {path}"),
+                    FileChange::Update { move_path: Some(dest), .. } => {
+                        format!("Moved {path} to {}", dest.display())
+                    }
+                    FileChange::Update { .. } => format!("Updated {path}"),
+                });
+            }
+            if let Some(failure) = &patch.failure {
+                out.push(tr_args("error.message", &[("message", failure.message.as_str())]));
+            }
+        }
+        HistoryRecord::Diff(diff) => {
+            out.push(format!("Diff: {}", diff.title));
+            for hunk in &diff.hunks {
+                out.push(hunk.header.clone());
+                for line in &hunk.lines {
+                    let marker = match line.kind {
+                        DiffLineKind::Addition => "+",
+                        DiffLineKind::Removal => "-",
+                        DiffLineKind::Context => " ",
+                    };
+                    out.push(format!("{marker}{}", plain(&line.content)));
+                }
+            }
+        }
+        HistoryRecord::Image(image) => {
+            let description = image
+                .alt_text
+                .clone()
+                .or_else(|| image.source_path.as_ref().map(|path| path.display().to_string()))
+                .unwrap_or_else(|| "attached".to_string());
+            out.push(format!("Image: {description}"));
+        }
+        HistoryRecord::BackgroundEvent(event) => {
+            out.push("Note:".to_string());
+            if !event.title.is_empty() {
+                out.push(event.title.clone());
+            }
+            push_text(&mut out, &event.description);
+        }
+        HistoryRecord::Notice(notice) => {
+            out.push("Note:".to_string());
+            if let Some(title) = &notice.title {
+                out.push(title.clone());
+            }
+            push_message_lines(&mut out, &notice.body);
+        }
+        HistoryRecord::UpgradeNotice(notice) => {
+            out.push(format!("Note: {}", notice.message));
+        }
+        // A finished stream is replaced by its final assistant message.
+        HistoryRecord::AssistantStream(_)
+        | HistoryRecord::Reasoning(_)
+        | HistoryRecord::WaitStatus(_)
+        | HistoryRecord::Loading(_)
+        | HistoryRecord::RunningTool(_)
+        | HistoryRecord::RateLimits(_)
+        | HistoryRecord::Context(_) => {}
+    }
+    out
+}
+
+fn push_exec(out: &mut Vec<String>, exec: &ExecRecord) {
+    out.push(format!("Command: {}", strip_bash_lc_and_escape(&exec.command)));
+    let stdout: String = exec.stdout_chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+    let stderr: String = exec.stderr_chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+    let output = format!("{stdout}{stderr}");
+    if !output.trim().is_empty() {
+        out.push("Output:".to_string());
+        push_text(out, &output);
+    }
+    match exec.exit_code {
+        Some(0) | None => {}
+        Some(code) => out.push(format!("Exit code: {code}")),
+    }
+}
+
+fn push_message_lines(out: &mut Vec<String>, lines: &[MessageLine]) {
+    for line in lines {
+        let text = spans_text(&line.spans);
+        match &line.kind {
+            MessageLineKind::Separator => {}
+            MessageLineKind::Blank => out.push(String::new()),
+            MessageLineKind::Bullet { marker, .. } => out.push(match marker {
+                BulletMarker::Numbered(number) => format!("{number}. {text}"),
+                BulletMarker::Dash | BulletMarker::Custom(_) => format!("- {text}"),
+            }),
+            MessageLineKind::Paragraph
+            | MessageLineKind::Code { .. }
+            | MessageLineKind::Quote
+            | MessageLineKind::Metadata => out.push(text),
+        }
+    }
+}
+
+fn spans_text(spans: &[InlineSpan]) -> String {
+    plain(&spans.iter().map(|span| span.text.as_str()).collect::<String>())
+}
+
+fn push_text(out: &mut Vec<String>, text: &str) {
+    out.extend(plain(text.trim_end()).lines().map(str::to_string));
+}
+
+fn plain(text: &str) -> String {
+    sanitize_for_tui(text, SanitizeMode::Plain, SanitizeOptions::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::state::{ExecAction, ExecStreamChunk, HistoryId};
+    use pretty_assertions::assert_eq;
+    use std::time::SystemTime;
+
+    #[test]
+    fn exec_records_read_as_labeled_command_and_output() {
+        let mut exec = ExecRecord {
+            id: HistoryId(1),
+            call_id: None,
+            command: vec!["bash".into(), "-lc".into(), "cargo test".into()],
+            parsed: Vec::new(),
+            action: ExecAction::Run,
+            status: ExecStatus::Running,
+            stdout_chunks: vec![ExecStreamChunk {
+                offset: 0,
+                content: "\u{1b}[32mok\u{1b}[0m\n".to_string(),
+            }],
+            stderr_chunks: vec![ExecStreamChunk {
+                offset: 0,
+                content: "1 failed\n".to_string(),
+            }],
+            exit_code: None,
+            wait_total: None,
+            wait_active: false,
+            wait_notes: Vec::new(),
+            started_at: SystemTime::UNIX_EPOCH,
+            completed_at: None,
+            working_dir: None,
+            env: Vec::new(),
+            tags: Vec::new(),
+            redactions: 0,
+        };
+        assert!(is_pending(&HistoryRecord::Exec(exec.clone())));
+
+        exec.status = ExecStatus::Error;
+        exec.exit_code = Some(101);
+        let record = HistoryRecord::Exec(exec);
+        assert!(!is_pending(&record));
+        assert_eq!(
+            linear_lines(&record),
+            vec!["Command: cargo test", "Output:", "ok", "1 failed", "Exit code: 101"]
+        );
+    }
+}
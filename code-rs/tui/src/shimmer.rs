@@ -11,7 +11,7 @@ static PROCESS_START: OnceLock<Instant> = OnceLock::new();
 
 fn elapsed_since_start() -> Duration {
     let start = PROCESS_START.get_or_init(Instant::now);
-    start.elapsed()
+    crate::deterministic::elapsed(*start)
 }
 
 pub(crate) fn shimmer_spans(text: &str) -> Vec<Span<'static>> {
@@ -19,6 +19,9 @@ pub(crate) fn shimmer_spans(text: &str) -> Vec<Span<'static>> {
     if chars.is_empty() {
         return Vec::new();
     }
+    if crate::screen_reader::enabled() {
+        return vec![Span::raw(text.to_string())];
+    }
     // Use time-based sweep synchronized to process start.
     let padding = 10usize;
     let period = chars.len() + padding * 2;
@@ -6,6 +6,8 @@ use strum_macros::EnumIter;
 use strum_macros::EnumString;
 use strum_macros::IntoStaticStr;
 
+use crate::i18n::tr_args;
+
 const BUILD_PROFILE: Option<&str> = option_env!("CODEX_PROFILE");
 
 fn demo_command_enabled() -> bool {
@@ -62,8 +64,10 @@ pub enum SlashCommand {
     Copy,
     Diff,
     Mention,
+    Outline,
     Cmd,
     Status,
+    Stats,
     Limits,
     #[strum(serialize = "update", serialize = "upgrade")]
     Update,
@@ -71,6 +75,7 @@ pub enum SlashCommand {
     Theme,
     Settings,
     Model,
+    Profile,
     Fast,
     Reasoning,
     Verbosity,
@@ -85,9 +90,12 @@ pub enum SlashCommand {
     Push,
     Validation,
     Mcp,
+    Tools,
     Resume,
     Rename,
     Login,
+    Account,
+    Auth,
     // Prompt-expanding commands
     Plan,
     Solve,
@@ -123,8 +131,10 @@ impl SlashCommand {
             SlashCommand::Copy => "copy last response as markdown",
             SlashCommand::Diff => "show git diff (including untracked files)",
             SlashCommand::Mention => "mention a file",
+            SlashCommand::Outline => "show the symbols defined in a file",
             SlashCommand::Cmd => "run a project command",
             SlashCommand::Status => "show current session configuration and token usage",
+            SlashCommand::Stats => "show session stats (turns, tools, tokens and cost per turn)",
             SlashCommand::Limits => "adjust session limits",
             SlashCommand::Update => "check for updates and optionally upgrade",
             SlashCommand::Notifications => "manage notification settings",
@@ -133,6 +143,7 @@ impl SlashCommand {
             SlashCommand::Prompts => "manage custom prompts",
             SlashCommand::Skills => "manage skills",
             SlashCommand::Model => "choose your default model",
+            SlashCommand::Profile => "switch to a config profile (model, provider, access)",
             SlashCommand::Fast => "open model settings with the Fast mode toggle",
             SlashCommand::Agents => "configure agents",
             SlashCommand::Auto => "work autonomously on long tasks with Auto Drive",
@@ -143,9 +154,12 @@ impl SlashCommand {
             SlashCommand::Push => "commit, push, and monitor workflows",
             SlashCommand::Validation => "control validation harness (status/on/off)",
             SlashCommand::Mcp => "manage MCP servers",
+            SlashCommand::Tools => "list available tools and MCP tool name mappings",
             SlashCommand::Perf => "performance tracing (on/off/show/reset)",
             SlashCommand::Demo => "populate history with demo cells (dev/perf only)",
             SlashCommand::Login => "manage Code sign-ins (add/select/disconnect)",
+            SlashCommand::Account => "switch between named sign-in profiles",
+            SlashCommand::Auth => "store API keys for model providers",
             SlashCommand::Logout => "log out of Code",
             #[cfg(debug_assertions)]
             SlashCommand::TestApproval => "test approval request",
@@ -264,22 +278,20 @@ pub fn process_slash_command_message(message: &str) -> ProcessedCommand {
     if let Ok(command) = canonical_command.parse::<SlashCommand>() {
         if !command.is_available() {
             let command_name = command.command();
-            let message = match command {
-                SlashCommand::Demo => {
-                    format!("Error: /{command_name} is only available in dev or perf builds.")
-                }
-                _ => format!("Error: /{command_name} is not available in this build."),
+            let key = match command {
+                SlashCommand::Demo => "error.command_dev_only",
+                _ => "error.command_unavailable",
             };
+            let message = tr_args(key, &[("command", command_name)]);
             return ProcessedCommand::Error(message);
         }
 
         // Check if it's a prompt-expanding command
         if command.is_prompt_expanding() {
             if args_raw.is_empty() && command.requires_arguments() {
-                return ProcessedCommand::Error(format!(
-                    "Error: /{} requires a task description. Usage: /{} <task>",
-                    command.command(),
-                    command.command()
+                return ProcessedCommand::Error(tr_args(
+                    "error.command_needs_task",
+                    &[("command", command.command())],
                 ));
             }
 
@@ -159,8 +159,13 @@ pub fn spinner_group_for(name: &str) -> &'static str {
 }
 
 pub fn frame_at_time(def: &Spinner, now_ms: u128) -> String {
-    if def.frames.is_empty() { return String::new(); }
-    let idx = ((now_ms as u64 / def.interval_ms) as usize) % def.frames.len();
+    // A changing glyph would be re-read on every frame.
+    if def.frames.is_empty() || crate::screen_reader::enabled() { return String::new(); }
+    let idx = if crate::deterministic::enabled() {
+        crate::deterministic::spinner_frame(def.frames.len())
+    } else {
+        ((now_ms as u64 / def.interval_ms) as usize) % def.frames.len()
+    };
     def.frames[idx].clone()
 }
 
@@ -0,0 +1,127 @@
+//! Session state in the terminal title and taskbar.
+//!
+//! While a turn runs the title is prefixed with what the agent is doing
+//! (`● running cargo test · Code`), and terminals that understand the
+//! ConEmu/Windows Terminal `OSC 9;4` sequence show a progress indicator on the
+//! tab or taskbar. Both stay visible when the window is in the background.
+
+use std::io::Write;
+
+/// Longest activity label kept in the title before it is cut with `…`.
+const MAX_LABEL_CHARS: usize = 40;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum TerminalActivity {
+    #[default]
+    Idle,
+    /// A turn is running. `percent` is the active plan's progress, if any.
+    Working { label: String, percent: Option<u8> },
+    AwaitingApproval,
+}
+
+impl TerminalActivity {
+    /// The window title for this state on top of `base`. Control characters
+    /// are dropped so a label cannot end the title sequence early.
+    pub(crate) fn title(&self, base: &str) -> String {
+        let base = sanitize(base);
+        match self {
+            TerminalActivity::Idle => base,
+            TerminalActivity::Working { label, .. } => {
+                format!("● {} · {base}", truncate(&sanitize(label)))
+            }
+            TerminalActivity::AwaitingApproval => format!("● awaiting approval · {base}"),
+        }
+    }
+
+    /// The `OSC 9;4` sequence for this state: hidden when idle, paused while
+    /// waiting for approval, and indeterminate unless a plan reports progress.
+    fn progress_sequence(&self) -> String {
+        let (state, percent) = match self {
+            TerminalActivity::Idle => (0, 0),
+            TerminalActivity::Working { percent: Some(percent), .. } => (1, *percent),
+            TerminalActivity::Working { percent: None, .. } => (3, 0),
+            TerminalActivity::AwaitingApproval => (4, 100),
+        };
+        format!("\u{1b}]9;4;{state};{percent}\u{7}")
+    }
+}
+
+/// Turns control characters (C0, DEL and C1) into spaces and collapses runs
+/// of whitespace.
+fn sanitize(text: &str) -> String {
+    let spaced: String =
+        text.chars().map(|ch| if ch.is_control() { ' ' } else { ch }).collect();
+    spaced.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate(label: &str) -> String {
+    if label.chars().count() <= MAX_LABEL_CHARS {
+        return label.to_string();
+    }
+    let mut cut: String = label.chars().take(MAX_LABEL_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Whether to emit `OSC 9;4`. `setting` is `tui.terminal_progress`; when
+/// unset, only terminals known to render the sequence get it, because others
+/// (iTerm2 before 3.6, for one) treat any `OSC 9` as a notification.
+pub(crate) fn progress_enabled(setting: Option<bool>) -> bool {
+    if let Some(enabled) = setting {
+        return enabled;
+    }
+    // Multiplexers swallow or mangle unknown OSC sequences.
+    if std::env::var_os("TMUX").is_some() || std::env::var_os("ZELLIJ").is_some() {
+        return false;
+    }
+    if std::env::var_os("WT_SESSION").is_some() {
+        return true;
+    }
+    if std::env::var("ConEmuANSI").is_ok_and(|value| value == "ON") {
+        return true;
+    }
+    std::env::var("TERM_PROGRAM").is_ok_and(|program| program.eq_ignore_ascii_case("ghostty"))
+}
+
+pub(crate) fn emit_progress(activity: &TerminalActivity) {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(activity.progress_sequence().as_bytes());
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn titles_and_progress_follow_the_session_state() {
+        let working = TerminalActivity::Working {
+            label: "running cargo test --workspace --all-features --no-fail-fast".to_string(),
+            percent: None,
+        };
+        assert_eq!(
+            working.title("Code"),
+            "● running cargo test --workspace --all-fe… · Code"
+        );
+        assert_eq!(working.progress_sequence(), "\u{1b}]9;4;3;0\u{7}");
+
+        let planned = TerminalActivity::Working {
+            label: "thinking".to_string(),
+            percent: Some(40),
+        };
+        assert_eq!(planned.progress_sequence(), "\u{1b}]9;4;1;40\u{7}");
+        assert_eq!(
+            TerminalActivity::AwaitingApproval.title("Fix login"),
+            "● awaiting approval · Fix login"
+        );
+        assert_eq!(TerminalActivity::Idle.title("Code"), "Code");
+
+        let hostile = TerminalActivity::Working {
+            label: "echo \u{7}\u{1b}]0;pwned\u{9c}\n done".to_string(),
+            percent: None,
+        };
+        assert_eq!(hostile.title("Code\u{1b}"), "● echo ]0;pwned done · Code");
+        assert_eq!(TerminalActivity::Idle.progress_sequence(), "\u{1b}]9;4;0;0\u{7}");
+    }
+}
@@ -0,0 +1,366 @@
+//! Scripted UI runs (`--script FILE`).
+//!
+//! The chat widget is driven by a script of inputs instead of a terminal and
+//! each requested frame is written out as plain text, so end-to-end UI tests
+//! and bug reports can reproduce a screen exactly. Rendering runs in
+//! [`crate::deterministic`] mode and no model is contacted: agent output
+//! comes from `event` lines carrying protocol events as JSON.
+//!
+//! One command per line; blank lines and `#` comment lines are skipped, and
+//! commands other than `type`, `paste` and `event` may end in ` # comment`.
+//!
+//! ```text
+//! # Terminal size for later frames (default 80x24).
+//! size 100x30
+//! # Type or paste text as written; `\n`, `\t` and `\\` are escapes.
+//! type fix the build
+//! paste line one\nline two
+//! # One or more chords written as in `[keys]`.
+//! key ctrl+c
+//! key down down enter
+//! # A protocol event, as the agent would send it.
+//! event {"id":"1","event_seq":0,"msg":{"type":"agent_message","message":"Done."}}
+//! # Advance streaming output by N commit ticks (default 1).
+//! tick 3
+//! # Dump the screen, with an optional label.
+//! frame after reply
+//! ```
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+
+use code_core::config::Config;
+use code_core::keymap::KeyChord;
+use code_core::keymap::KeyName;
+use code_core::protocol::Event;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::chatwidget::ChatWidget;
+use crate::tui::TerminalInfo;
+
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+#[derive(Debug)]
+enum Step {
+    Size { width: u16, height: u16 },
+    Type(String),
+    Paste(String),
+    Keys(Vec<KeyEvent>),
+    Event(Box<Event>),
+    Tick(usize),
+    Frame(Option<String>),
+}
+
+/// Runs the script at `script_path` and writes its frames to `output`, or to
+/// stdout when no output file is given.
+pub(crate) fn run(
+    script_path: &Path,
+    output: Option<&Path>,
+    config: Config,
+) -> color_eyre::Result<()> {
+    let text = std::fs::read_to_string(script_path)
+        .map_err(|err| eyre!("failed to read {}: {err}", script_path.display()))?;
+    let steps = parse_script(&text).map_err(|err| eyre!("{}: {err}", script_path.display()))?;
+
+    let (tx, events) = std::sync::mpsc::channel::<AppEvent>();
+    let terminal_info = TerminalInfo {
+        picker: None,
+        font_size: (8, 16),
+    };
+    let chat = ChatWidget::new(
+        config,
+        AppEventSender::new(tx),
+        None,
+        Vec::new(),
+        false,
+        terminal_info,
+        false,
+        None,
+    );
+    let mut runner = Runner {
+        chat,
+        events,
+        size: DEFAULT_SIZE,
+        frames: 0,
+        out: String::new(),
+    };
+    for step in steps {
+        runner.apply(step)?;
+    }
+
+    match output {
+        Some(path) => std::fs::write(path, runner.out)
+            .map_err(|err| eyre!("failed to write {}: {err}", path.display()))?,
+        None => std::io::stdout().write_all(runner.out.as_bytes())?,
+    }
+    Ok(())
+}
+
+struct Runner {
+    chat: ChatWidget<'static>,
+    events: Receiver<AppEvent>,
+    size: (u16, u16),
+    frames: usize,
+    out: String,
+}
+
+impl Runner {
+    fn apply(&mut self, step: Step) -> color_eyre::Result<()> {
+        match step {
+            Step::Size { width, height } => self.size = (width, height),
+            Step::Type(text) => {
+                for ch in text.chars() {
+                    let key = match ch {
+                        '\n' => KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT),
+                        '\t' => KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE),
+                        ch => KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE),
+                    };
+                    self.chat.handle_key_event(key);
+                }
+            }
+            Step::Paste(text) => self.chat.handle_paste(text),
+            Step::Keys(keys) => {
+                for key in keys {
+                    self.chat.handle_key_event(key);
+                    self.flush();
+                }
+            }
+            Step::Event(event) => self.chat.handle_code_event(*event),
+            Step::Tick(count) => {
+                for _ in 0..count {
+                    self.chat.on_commit_tick();
+                    self.flush();
+                }
+            }
+            Step::Frame(label) => self.frame(label)?,
+        }
+        self.flush();
+        Ok(())
+    }
+
+    /// Applies the app events a scripted run can honor; the rest (redraw
+    /// scheduling, ops for the absent agent, app-level commands) are dropped.
+    fn flush(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                AppEvent::InsertHistory(lines) => self.chat.insert_history_lines(lines),
+                AppEvent::InsertHistoryWithKind { id, kind, lines } => {
+                    self.chat.insert_history_lines_with_kind(kind, id, lines);
+                }
+                AppEvent::InsertFinalAnswer { id, lines, source } => {
+                    self.chat.insert_final_answer_with_id(id, lines, source);
+                }
+                AppEvent::InsertBackgroundEvent { message, placement, order } => {
+                    self.chat
+                        .insert_background_event_with_placement(message, placement, order);
+                }
+                AppEvent::CommitTick => self.chat.on_commit_tick(),
+                AppEvent::FlushInterruptsIfIdle => self.chat.flush_interrupts_if_stream_idle(),
+                AppEvent::RecheckSpinnerIfIdle => self.chat.recheck_spinner_if_idle(),
+                _ => {}
+            }
+        }
+    }
+
+    fn frame(&mut self, label: Option<String>) -> color_eyre::Result<()> {
+        let (width, height) = self.size;
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|frame| frame.render_widget_ref(&self.chat, frame.area()))?;
+        // Drawing can queue follow-up events (e.g. height changes).
+        self.flush();
+
+        self.frames += 1;
+        let label = label.map(|label| format!(": {label}")).unwrap_or_default();
+        self.out
+            .push_str(&format!("--- frame {}{label} ({width}x{height}) ---\n", self.frames));
+        self.out.push_str(&buffer_text(terminal.backend().buffer()));
+        Ok(())
+    }
+}
+
+/// The buffer's rows as text, without trailing spaces. Cells covered by a
+/// wide character are skipped so it is not followed by padding.
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut row = String::new();
+        let mut covered = 0usize;
+        for x in area.left()..area.right() {
+            let symbol = buffer[(x, y)].symbol();
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            covered = symbol.width().saturating_sub(1);
+            row.push_str(symbol);
+        }
+        text.push_str(row.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn parse_script(text: &str) -> Result<Vec<Step>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(index, line)| {
+            parse_step(line.trim()).map_err(|err| format!("line {}: {err}", index + 1))
+        })
+        .collect()
+}
+
+fn parse_step(line: &str) -> Result<Step, String> {
+    let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let arg = strip_comment(rest).trim();
+    match command {
+        "size" => {
+            let (width, height) = arg
+                .split_once('x')
+                .and_then(|(w, h)| Some((w.parse::<u16>().ok()?, h.parse::<u16>().ok()?)))
+                .filter(|&(w, h)| w > 0 && h > 0)
+                .ok_or_else(|| format!("expected `size WIDTHxHEIGHT`, got `{arg}`"))?;
+            Ok(Step::Size { width, height })
+        }
+        // Text arguments keep `#` and inner spacing as typed.
+        "type" => Ok(Step::Type(unescape(rest))),
+        "paste" => Ok(Step::Paste(unescape(rest))),
+        "key" => {
+            let keys = arg
+                .split_whitespace()
+                .map(key_event)
+                .collect::<Result<Vec<_>, _>>()?;
+            if keys.is_empty() {
+                return Err("expected at least one key after `key`".to_string());
+            }
+            Ok(Step::Keys(keys))
+        }
+        "event" => serde_json::from_str::<Event>(rest)
+            .map(|event| Step::Event(Box::new(event)))
+            .map_err(|err| format!("invalid event JSON: {err}")),
+        "tick" if arg.is_empty() => Ok(Step::Tick(1)),
+        "tick" => arg
+            .parse()
+            .map(Step::Tick)
+            .map_err(|_| format!("expected a tick count, got `{arg}`")),
+        "frame" => Ok(Step::Frame((!arg.is_empty()).then(|| arg.to_string()))),
+        other => Err(format!("unknown command `{other}`")),
+    }
+}
+
+fn strip_comment(text: &str) -> &str {
+    text.split_once(" #").map_or(text, |(before, _)| before)
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn key_event(spec: &str) -> Result<KeyEvent, String> {
+    let chord: KeyChord = spec.parse()?;
+    let code = match chord.key {
+        KeyName::Char(ch) => KeyCode::Char(ch),
+        KeyName::Enter => KeyCode::Enter,
+        KeyName::Tab if chord.shift => KeyCode::BackTab,
+        KeyName::Tab => KeyCode::Tab,
+        KeyName::Backspace => KeyCode::Backspace,
+        KeyName::Delete => KeyCode::Delete,
+        KeyName::Esc => KeyCode::Esc,
+        KeyName::Up => KeyCode::Up,
+        KeyName::Down => KeyCode::Down,
+        KeyName::Left => KeyCode::Left,
+        KeyName::Right => KeyCode::Right,
+        KeyName::Home => KeyCode::Home,
+        KeyName::End => KeyCode::End,
+        KeyName::PageUp => KeyCode::PageUp,
+        KeyName::PageDown => KeyCode::PageDown,
+        KeyName::Insert => KeyCode::Insert,
+        KeyName::F(n) => KeyCode::F(n),
+    };
+    let mut modifiers = KeyModifiers::NONE;
+    modifiers.set(KeyModifiers::CONTROL, chord.ctrl);
+    modifiers.set(KeyModifiers::ALT, chord.alt);
+    modifiers.set(KeyModifiers::SHIFT, chord.shift);
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_script_commands() {
+        let script = r#"
+# reproduce the wrap bug
+size 100x30
+type fix   the build # not a comment
+key ctrl+c shift+tab  # cancel, then cycle
+event {"id":"1","event_seq":0,"msg":{"type":"agent_message","message":"Done."}}
+tick
+frame after reply
+"#;
+        let steps = parse_script(script).expect("script");
+        assert_eq!(steps.len(), 6);
+        assert!(matches!(steps[0], Step::Size { width: 100, height: 30 }));
+        assert!(matches!(&steps[1], Step::Type(text) if text == "fix   the build # not a comment"));
+        let Step::Keys(keys) = &steps[2] else {
+            panic!("expected keys, got {:?}", steps[2]);
+        };
+        assert_eq!(
+            keys,
+            &vec![
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT),
+            ]
+        );
+        assert!(matches!(&steps[3], Step::Event(event) if event.id == "1"));
+        assert!(matches!(steps[4], Step::Tick(1)));
+        assert!(matches!(&steps[5], Step::Frame(Some(label)) if label == "after reply"));
+
+        assert_eq!(unescape(r"one\ntwo\\"), "one\ntwo\\");
+        assert_eq!(
+            parse_script("size 80\nframe").expect_err("bad size"),
+            "line 1: expected `size WIDTHxHEIGHT`, got `80`"
+        );
+        assert_eq!(
+            parse_script("\n\nwait 1").expect_err("unknown"),
+            "line 3: unknown command `wait`"
+        );
+    }
+
+    #[test]
+    fn buffer_text_skips_wide_character_padding() {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "界a", ratatui::style::Style::default());
+        assert_eq!(buffer_text(&buffer), "界a\n\n");
+    }
+}
@@ -10,6 +10,7 @@ use std::path::PathBuf;
 use code_core::util::extract_shell_script;
 use code_core::protocol::Op;
 use code_core::protocol::ReviewDecision;
+use code_core::keymap::KeyAction;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -27,6 +28,8 @@ use shlex::split as shlex_split;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::i18n::tr;
+use crate::i18n::tr_args;
 use crate::chatwidget::BackgroundOrderTicket;
 use crate::exec_command::strip_bash_lc_and_escape;
 use code_core::protocol::ApprovedCommandMatchKind;
@@ -53,7 +56,7 @@ pub(crate) enum ApprovalRequest {
 struct SelectOption {
     label: String,
     description: String,
-    hotkey: KeyCode,
+    hotkey: KeyAction,
     action: SelectAction,
 }
 
@@ -103,7 +106,7 @@ impl UserApprovalWidget<'_> {
                     Line::from(""), // extra spacing above the prompt
                     Line::from(vec![
                         "? ".fg(crate::colors::info()),
-                        "Code wants to run ".bold(),
+                        tr("approval.exec_prompt").bold(),
                         cmd_span,
                     ]),
                     Line::from(""),
@@ -125,10 +128,9 @@ impl UserApprovalWidget<'_> {
                 }
 
                 if let Some(root) = grant_root {
-                    contents.push(Line::from(format!(
-                        "This will grant write access to {} for the remainder of this session.",
-                        root.display()
-                    )));
+                    let path = root.display().to_string();
+                    let notice = tr_args("approval.grant_root", &[("path", path.as_str())]);
+                    contents.push(Line::from(notice));
                     contents.push(Line::from(""));
                 }
 
@@ -141,9 +143,9 @@ impl UserApprovalWidget<'_> {
                     Line::from(""),
                     Line::from(vec![
                         "? ".fg(crate::colors::info()),
-                        "Run shell command ".bold(),
+                        tr("approval.terminal_prompt").bold(),
                         cmd_span,
-                        " now?".into(),
+                        tr("approval.terminal_prompt_suffix").into(),
                     ]),
                     Line::from(""),
                 ];
@@ -192,16 +194,6 @@ impl UserApprovalWidget<'_> {
         }
     }
 
-    /// Normalize a key for comparison.
-    /// - For `KeyCode::Char`, converts to lowercase for case-insensitive matching.
-    /// - Other key codes are returned unchanged.
-    fn normalize_keycode(code: KeyCode) -> KeyCode {
-        match code {
-            KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
-            other => other,
-        }
-    }
-
     /// Handle Ctrl-C pressed by the user while the modal is visible.
     /// Behaves like pressing Escape: abort the request and close the modal.
     pub(crate) fn on_ctrl_c(&mut self) {
@@ -232,13 +224,13 @@ impl UserApprovalWidget<'_> {
             KeyCode::Esc => {
                 self.perform_action(SelectAction::Abort);
             }
-            other => {
-                let normalized = Self::normalize_keycode(other);
+            _ => {
+                // Hotkeys come from `[keys]`; letters match case-insensitively.
                 if let Some((idx, option)) = self
                     .select_options
                     .iter()
                     .enumerate()
-                    .find(|(_, opt)| Self::normalize_keycode(opt.hotkey) == normalized)
+                    .find(|(_, opt)| crate::keymap::matches(opt.hotkey, &key_event))
                 {
                     self.selected_option = idx;
                     self.perform_action(option.action.clone());
@@ -415,17 +407,17 @@ fn build_exec_select_options(command: &[String]) -> Vec<SelectOption> {
     let mut options = Vec::new();
 
     options.push(SelectOption {
-        label: "Yes".to_string(),
-        description: "Approve and run the command".to_string(),
-        hotkey: KeyCode::Char('y'),
+        label: tr("approval.yes").to_string(),
+        description: tr("approval.run_once").to_string(),
+        hotkey: KeyAction::Approve,
         action: SelectAction::ApproveOnce,
     });
 
     let full_display = strip_bash_lc_and_escape(command);
     options.push(SelectOption {
-        label: format!("Always allow '{full_display}' for this project"),
-        description: "Approve this exact command automatically next time".to_string(),
-        hotkey: KeyCode::Char('a'),
+        label: tr_args("approval.always_allow", &[("command", full_display.as_str())]),
+        description: tr("approval.always_exact").to_string(),
+        hotkey: KeyAction::ApproveAlways,
         action: SelectAction::ApproveForSession {
             command: command.to_vec(),
             match_kind: ApprovedCommandMatchKind::Exact,
@@ -440,9 +432,9 @@ fn build_exec_select_options(command: &[String]) -> Vec<SelectOption> {
             let prefix_display = strip_bash_lc_and_escape(&prefix);
             let prefix_with_wildcard = format!("{prefix_display} *");
         options.push(SelectOption {
-            label: format!("Always allow '{prefix_with_wildcard}' for this project"),
-            description: "Approve any command starting with this prefix".to_string(),
-            hotkey: KeyCode::Char('p'),
+            label: tr_args("approval.always_allow", &[("command", prefix_with_wildcard.as_str())]),
+            description: tr("approval.always_prefix").to_string(),
+            hotkey: KeyAction::ApprovePrefix,
             action: SelectAction::ApproveForSession {
                 command: prefix.clone(),
                 match_kind: ApprovedCommandMatchKind::Prefix,
@@ -454,9 +446,9 @@ fn build_exec_select_options(command: &[String]) -> Vec<SelectOption> {
     }
 
     options.push(SelectOption {
-        label: "No, provide feedback".to_string(),
-        description: "Do not run the command; provide feedback".to_string(),
-        hotkey: KeyCode::Char('n'),
+        label: tr("approval.no_feedback").to_string(),
+        description: tr("approval.deny_exec").to_string(),
+        hotkey: KeyAction::Deny,
         action: SelectAction::Abort,
     });
 
@@ -466,15 +458,15 @@ fn build_exec_select_options(command: &[String]) -> Vec<SelectOption> {
 fn build_patch_select_options() -> Vec<SelectOption> {
     vec![
         SelectOption {
-            label: "Yes".to_string(),
-            description: "Approve and apply the changes".to_string(),
-            hotkey: KeyCode::Char('y'),
+            label: tr("approval.yes").to_string(),
+            description: tr("approval.apply_patch").to_string(),
+            hotkey: KeyAction::Approve,
             action: SelectAction::ApproveOnce,
         },
         SelectOption {
-            label: "No, provide feedback".to_string(),
-            description: "Do not apply the changes; provide feedback".to_string(),
-            hotkey: KeyCode::Char('n'),
+            label: tr("approval.no_feedback").to_string(),
+            description: tr("approval.deny_patch").to_string(),
+            hotkey: KeyAction::Deny,
             action: SelectAction::Abort,
         },
     ]
@@ -483,15 +475,15 @@ fn build_patch_select_options() -> Vec<SelectOption> {
 fn build_terminal_select_options() -> Vec<SelectOption> {
     vec![
         SelectOption {
-            label: "Yes".to_string(),
-            description: "Approve and run the command".to_string(),
-            hotkey: KeyCode::Char('y'),
+            label: tr("approval.yes").to_string(),
+            description: tr("approval.run_once").to_string(),
+            hotkey: KeyAction::Approve,
             action: SelectAction::ApproveOnce,
         },
         SelectOption {
-            label: "No".to_string(),
-            description: "Dismiss without running the command".to_string(),
-            hotkey: KeyCode::Char('n'),
+            label: tr("approval.no").to_string(),
+            description: tr("approval.dismiss_terminal").to_string(),
+            hotkey: KeyAction::Deny,
             action: SelectAction::Abort,
         },
     ]
@@ -545,9 +537,8 @@ fn prefix_candidate(tokens: &[String]) -> Option<Vec<String>> {
     }
 }
 
-fn hotkey_suffix(key: KeyCode) -> String {
-    match key {
-        KeyCode::Char(c) => format!(" ({})", c.to_ascii_lowercase()),
-        _ => String::new(),
-    }
+fn hotkey_suffix(action: KeyAction) -> String {
+    crate::keymap::label(action)
+        .map(|label| format!(" ({label})"))
+        .unwrap_or_default()
 }
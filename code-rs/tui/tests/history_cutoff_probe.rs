@@ -175,6 +175,7 @@ fn collapsed_reasoning_before_exec_keeps_last_line() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(120),
+            redactions: 0,
         }),
         order: Some(order(&mut seq)),
     });
@@ -407,6 +408,7 @@ fn exec_history_small_viewport_keeps_last_line() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(120),
+            redactions: 0,
         }),
         order: Some(order(&mut seq)),
     });
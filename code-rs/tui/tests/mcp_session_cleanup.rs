@@ -107,6 +107,8 @@ async fn mcp_stdio_server_exits_before_next_session() {
         tool_timeout_sec: Some(Duration::from_secs(5)),
         enabled_tools: None,
         disabled_tools: None,
+        approval_required_tools: None,
+        tool_timeouts_sec: None,
     };
 
     let mut mcp_servers = HashMap::new();
@@ -90,6 +90,10 @@ fn cli_web_search_flag_defaults() {
         resume_session_id: None,
         compact_prompt_override: None,
         compact_prompt_file: None,
+        screen_reader: false,
+        script: None,
+        script_output: None,
+        spinner_seed: 0,
     };
 
     cli.finalize_defaults();
@@ -123,6 +127,10 @@ fn cli_web_search_flag_explicit_enable() {
         resume_session_id: None,
         compact_prompt_override: None,
         compact_prompt_file: None,
+        screen_reader: false,
+        script: None,
+        script_output: None,
+        spinner_seed: 0,
     };
 
     cli.finalize_defaults();
@@ -156,6 +164,10 @@ fn cli_web_search_flag_disable() {
         resume_session_id: None,
         compact_prompt_override: None,
         compact_prompt_file: None,
+        screen_reader: false,
+        script: None,
+        script_output: None,
+        spinner_seed: 0,
     };
 
     cli.finalize_defaults();
@@ -492,6 +504,7 @@ fn smoke_exec_begin_end_consistency() {
         stderr: String::new(),
         exit_code: 0,
         duration: Duration::from_secs(2),
+        redactions: 0,
     };
 
     assert_eq!(end.call_id, begin.call_id);
@@ -678,6 +691,7 @@ fn smoke_exec_command_stream() {
             stderr: String::new(),
             exit_code: 0,
             duration: Duration::from_millis(20),
+            redactions: 0,
         }),
         order: Some(OrderMeta {
             request_ordinal: 1,